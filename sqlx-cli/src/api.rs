@@ -0,0 +1,679 @@
+//! The database, migration and `prepare` operations of the CLI as functions returning typed
+//! errors, for tools which drive them from Rust (such as an `xtask` or a deployment tool)
+//! instead of running `sqlx` in a subprocess.
+//!
+//! Unlike the commands, these functions never prompt for confirmation and do not read the
+//! database URL from the environment: it is taken from the [`ConnectOpts`] as given. What they
+//! did is returned to the caller instead of being printed.
+//!
+//! ```rust,no_run
+//! # async fn example() -> Result<(), sqlx_cli::api::Error> {
+//! use sqlx_cli::api::{self, RunOptions};
+//! use sqlx_cli::opt::{ConnectOpts, MigrationSourceOpt};
+//! use sqlx_cli::Config;
+//!
+//! let config = Config::default();
+//! let connect_opts = ConnectOpts::new("postgres://localhost/app");
+//!
+//! api::create_database(&connect_opts).await?;
+//!
+//! let outcomes = api::run_migrations(
+//!     &config,
+//!     &MigrationSourceOpt::default(),
+//!     &connect_opts,
+//!     &RunOptions::default(),
+//! )
+//! .await?;
+//!
+//! for migration in outcomes {
+//!     println!(
+//!         "{:?} {} {}",
+//!         migration.status, migration.version, migration.description
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use sqlx::any::Any;
+use sqlx::migrate::{AuditInfo, Migrate, MigrateDatabase, MigrateError, Migration, MigrationType};
+use sqlx::Connection;
+
+use crate::migrate::{apply_migration, validate_applied_migrations};
+use crate::opt::{self, ConnectOpts, MigrationSourceOpt};
+use crate::{prepare, Config};
+
+/// An error returned by the functions of this module.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The [`ConnectOpts`] have no database URL.
+    #[error("database URL not set")]
+    DatabaseUrlNotSet,
+
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+
+    #[error(transparent)]
+    Migrate(#[from] MigrateError),
+
+    /// A migration file or its directory could not be created.
+    #[error("{context}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+
+    /// The query data in `.sqlx` is out of date, as found by [`prepare()`] with
+    /// [`PrepareOptions::check`] set.
+    #[error("prepare check failed: {0}; you should re-run sqlx prepare")]
+    PrepareCheck(String),
+
+    /// The query data could not be generated, such as when `cargo check` fails.
+    #[error(transparent)]
+    Prepare(anyhow::Error),
+}
+
+/// Options for [`run_migrations()`].
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Only report the migrations which would be applied, without applying them.
+    pub dry_run: bool,
+
+    /// Ignore applied migrations which are missing from the migration source.
+    pub ignore_missing: bool,
+
+    /// Apply migrations with a lower version than the latest applied one.
+    ///
+    /// Also enabled by `migrate.allow-out-of-order` in `sqlx.toml`.
+    pub allow_out_of_order: bool,
+
+    /// Apply migrations up to and including this version, instead of all of them.
+    pub target_version: Option<i64>,
+}
+
+/// Options for [`revert_migrations()`].
+#[derive(Debug, Clone, Default)]
+pub struct RevertOptions {
+    /// Only report the migrations which would be reverted, without reverting them.
+    pub dry_run: bool,
+
+    /// Ignore applied migrations which are missing from the migration source.
+    pub ignore_missing: bool,
+
+    /// Revert migrations down to, but not including, this version; `0` reverts all of them.
+    ///
+    /// If not set, only the latest migration is reverted.
+    pub target_version: Option<i64>,
+}
+
+/// Options for [`prepare()`].
+#[derive(Debug, Clone, Default)]
+pub struct PrepareOptions {
+    /// Check that the query data in `.sqlx` is up to date instead of writing it.
+    pub check: bool,
+
+    /// Also prepare the queries of dependencies outside of the workspace.
+    pub all: bool,
+
+    /// Prepare the queries of the whole workspace, in `.sqlx` at its root.
+    pub workspace: bool,
+
+    /// Arguments passed to `cargo check`.
+    pub cargo_args: Vec<String>,
+}
+
+/// A migration reached by [`run_migrations()`] or [`revert_migrations()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct MigrationOutcome {
+    /// The version of the migration; meaningless for repeatable migrations.
+    pub version: i64,
+    pub description: String,
+    pub migration_type: MigrationType,
+    pub status: MigrationStatus,
+    /// Whether the migration has a lower version than the latest one applied before it.
+    pub out_of_order: bool,
+    /// How long the migration took to apply or revert, or zero if it was not.
+    pub elapsed: Duration,
+}
+
+/// What was done with a [`MigrationOutcome`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MigrationStatus {
+    /// The migration was applied or reverted.
+    Applied,
+    /// The migration would have been applied or reverted, but `dry_run` is set.
+    Pending,
+    /// The migration was skipped because of the target version.
+    Skipped,
+}
+
+impl MigrationOutcome {
+    fn new(migration: &Migration, status: MigrationStatus, elapsed: Duration) -> Self {
+        MigrationOutcome {
+            version: migration.version,
+            description: migration.description.to_string(),
+            migration_type: migration.migration_type,
+            status,
+            out_of_order: false,
+            elapsed,
+        }
+    }
+}
+
+/// Create the database, if it does not already exist.
+///
+/// Returns `true` if it was created.
+pub async fn create_database(connect_opts: &ConnectOpts) -> Result<bool, Error> {
+    // NOTE: only retry the idempotent action.
+    // We're assuming that if this succeeds, then any following operations should also succeed.
+    let exists = crate::retry_connect_errors(connect_opts, Any::database_exists).await?;
+
+    if !exists {
+        Any::create_database(connect_opts.expect_db_url()?).await?;
+    }
+
+    Ok(!exists)
+}
+
+/// Drop the database, if it exists, without asking for confirmation.
+///
+/// With `force`, other connections to the database are terminated first.
+///
+/// Returns `true` if it was dropped.
+pub async fn drop_database(connect_opts: &ConnectOpts, force: bool) -> Result<bool, Error> {
+    // NOTE: only retry the idempotent action.
+    // We're assuming that if this succeeds, then any following operations should also succeed.
+    let exists = crate::retry_connect_errors(connect_opts, Any::database_exists).await?;
+
+    if exists {
+        if force {
+            Any::force_drop_database(connect_opts.expect_db_url()?).await?;
+        } else {
+            Any::drop_database(connect_opts.expect_db_url()?).await?;
+        }
+    }
+
+    Ok(exists)
+}
+
+/// Create the files of a new migration in the migration source, returning their paths.
+///
+/// The migration is reversible if `reversible` is `Some(true)`, or if it is `None` and
+/// `sqlx.toml` or the existing migrations call for it, as with `sqlx migrate add`. Its
+/// versioning follows `sqlx.toml`, or the existing migrations.
+pub async fn add_migration(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    description: &str,
+    reversible: Option<bool>,
+) -> Result<Vec<PathBuf>, Error> {
+    let source = migration_source.resolve_path(config);
+
+    create_migrations_dir(source)?;
+
+    let migrator = migration_source.resolve(config).await?;
+
+    let version_prefix = opt::default_version_prefix(config, &migrator);
+    let reversible = reversible.unwrap_or_else(|| opt::default_reversible(config, &migrator));
+
+    create_migration_files(source, &version_prefix, description, reversible, |_| ())
+}
+
+pub(crate) fn create_migrations_dir(source: &str) -> Result<(), Error> {
+    fs::create_dir_all(source).map_err(|source| Error::Io {
+        context: "Unable to create migrations directory".into(),
+        source,
+    })
+}
+
+/// Create the files of a new migration, calling `creating` with the path of each file before
+/// it is created.
+pub(crate) fn create_migration_files(
+    migration_source: &str,
+    version_prefix: &str,
+    description: &str,
+    reversible: bool,
+    mut creating: impl FnMut(&Path),
+) -> Result<Vec<PathBuf>, Error> {
+    let migration_types: &[_] = if reversible {
+        &[MigrationType::ReversibleUp, MigrationType::ReversibleDown]
+    } else {
+        &[MigrationType::Simple]
+    };
+
+    migration_types
+        .iter()
+        .map(|&migration_type| {
+            create_migration_file(
+                migration_source,
+                version_prefix,
+                description,
+                migration_type,
+                &mut creating,
+            )
+        })
+        .collect()
+}
+
+fn create_migration_file(
+    migration_source: &str,
+    file_prefix: &str,
+    description: &str,
+    migration_type: MigrationType,
+    creating: &mut impl FnMut(&Path),
+) -> Result<PathBuf, Error> {
+    let mut file_name = file_prefix.to_string();
+    file_name.push('_');
+    file_name.push_str(&description.replace(' ', "_"));
+    file_name.push_str(migration_type.suffix());
+
+    let mut path = PathBuf::new();
+    path.push(migration_source);
+    path.push(&file_name);
+
+    creating(&path);
+
+    File::create(&path)
+        .and_then(|mut file| file.write_all(migration_type.file_content().as_bytes()))
+        .map_err(|source| Error::Io {
+            context: format!("Failed to create migration file {}", path.display()),
+            source,
+        })?;
+
+    Ok(path)
+}
+
+/// Apply the pending migrations of the migration source.
+///
+/// Returns each pending migration with its [`MigrationStatus`]: applied, pending with
+/// [`RunOptions::dry_run`], or skipped because it is past [`RunOptions::target_version`].
+pub async fn run_migrations(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    connect_opts: &ConnectOpts,
+    options: &RunOptions,
+) -> Result<Vec<MigrationOutcome>, Error> {
+    let mut outcomes = Vec::new();
+
+    run_migrations_with(config, migration_source, connect_opts, options, |outcome| {
+        outcomes.push(outcome.clone())
+    })
+    .await?;
+
+    Ok(outcomes)
+}
+
+/// Like [`run_migrations()`], but reports each migration as soon as it is applied.
+pub(crate) async fn run_migrations_with(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    connect_opts: &ConnectOpts,
+    options: &RunOptions,
+    mut report: impl FnMut(&MigrationOutcome),
+) -> Result<(), Error> {
+    let RunOptions {
+        dry_run,
+        ignore_missing,
+        allow_out_of_order,
+        target_version,
+    } = *options;
+
+    let allow_out_of_order = allow_out_of_order || config.migrate.allow_out_of_order;
+
+    let migrator = migration_source.resolve(config).await?;
+
+    if config.migrate.require_reversible {
+        migrator.validate_reversible()?;
+    }
+
+    if let Some(target_version) = target_version {
+        if !migrator.version_exists(target_version) {
+            return Err(MigrateError::VersionNotPresent(target_version).into());
+        }
+    }
+
+    let mut conn = crate::connect(config, connect_opts).await?;
+
+    for schema_name in &config.migrate.create_schemas {
+        conn.create_schema_if_not_exists(schema_name).await?;
+    }
+
+    conn.ensure_migrations_table(config.migrate.table_name())
+        .await?;
+
+    if config.migrate.record_audit && !dry_run {
+        conn.ensure_audit_columns(config.migrate.table_name())
+            .await?;
+    }
+
+    let version = conn.dirty_version(config.migrate.table_name()).await?;
+    if let Some(version) = version {
+        return Err(MigrateError::Dirty(version).into());
+    }
+
+    let applied_migrations = conn
+        .list_applied_migrations(config.migrate.table_name())
+        .await?;
+    validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
+
+    let latest_version = applied_migrations
+        .iter()
+        .max_by(|x, y| x.version.cmp(&y.version))
+        .map(|migration| migration.version)
+        .unwrap_or(0);
+    if let Some(target_version) = target_version {
+        if target_version < latest_version {
+            return Err(MigrateError::VersionTooOld(target_version, latest_version).into());
+        }
+    }
+
+    let applied_migrations: HashMap<_, _> = applied_migrations
+        .into_iter()
+        .map(|m| (m.version, m))
+        .collect();
+
+    let audit = config.migrate.record_audit.then(AuditInfo::from_env);
+
+    for migration in migrator.iter() {
+        if migration.migration_type.is_down_migration() || migration.migration_type.is_repeatable()
+        {
+            // Skipping down migrations; repeatable migrations are applied last
+            continue;
+        }
+
+        match applied_migrations.get(&migration.version) {
+            Some(applied_migration) => {
+                if migration.checksum != applied_migration.checksum {
+                    return Err(MigrateError::VersionMismatch(migration.version).into());
+                }
+            }
+            None => {
+                let skip =
+                    target_version.is_some_and(|target_version| migration.version > target_version);
+
+                let out_of_order = if skip {
+                    migration.version < latest_version
+                } else {
+                    check_order(migration, latest_version, allow_out_of_order)?
+                };
+
+                let (status, elapsed) = if skip {
+                    (MigrationStatus::Skipped, Duration::ZERO)
+                } else if dry_run {
+                    (MigrationStatus::Pending, Duration::ZERO)
+                } else {
+                    let elapsed =
+                        apply_migration(&mut conn, config, migration, audit.as_ref()).await?;
+                    (MigrationStatus::Applied, elapsed)
+                };
+
+                report(&MigrationOutcome {
+                    out_of_order,
+                    ..MigrationOutcome::new(migration, status, elapsed)
+                });
+            }
+        }
+    }
+
+    // Repeatable migrations are only applied once all versioned migrations are.
+    let reached_latest = target_version.is_none_or(|target_version| {
+        migrator
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration())
+            .all(|m| m.version <= target_version)
+    });
+
+    if reached_latest {
+        let applied_repeatable: HashMap<_, _> = conn
+            .list_applied_repeatable_migrations(config.migrate.table_name())
+            .await?
+            .into_iter()
+            .map(|m| (m.version, m.checksum))
+            .collect();
+
+        for migration in migrator.iter().filter(|m| m.migration_type.is_repeatable()) {
+            if applied_repeatable.get(&migration.version) == Some(&migration.checksum) {
+                continue;
+            }
+
+            let (status, elapsed) = if dry_run {
+                (MigrationStatus::Pending, Duration::ZERO)
+            } else {
+                let elapsed = apply_migration(&mut conn, config, migration, audit.as_ref()).await?;
+                (MigrationStatus::Applied, elapsed)
+            };
+
+            report(&MigrationOutcome::new(migration, status, elapsed));
+        }
+    }
+
+    // Close the connection before exiting:
+    // * For Postgres this should ensure timely cleanup on the server side,
+    //   including decrementing the open connection count.
+    let _ = conn.close().await;
+
+    Ok(())
+}
+
+/// Revert applied migrations of the migration source, latest first, returning those which
+/// were reverted, or would be with [`RevertOptions::dry_run`].
+pub async fn revert_migrations(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    connect_opts: &ConnectOpts,
+    options: &RevertOptions,
+) -> Result<Vec<MigrationOutcome>, Error> {
+    let mut outcomes = Vec::new();
+
+    revert_migrations_with(config, migration_source, connect_opts, options, |outcome| {
+        outcomes.push(outcome.clone())
+    })
+    .await?;
+
+    Ok(outcomes)
+}
+
+/// Like [`revert_migrations()`], but reports each migration as soon as it is reverted.
+pub(crate) async fn revert_migrations_with(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    connect_opts: &ConnectOpts,
+    options: &RevertOptions,
+    mut report: impl FnMut(&MigrationOutcome),
+) -> Result<(), Error> {
+    let RevertOptions {
+        dry_run,
+        ignore_missing,
+        target_version,
+    } = *options;
+
+    let migrator = migration_source.resolve(config).await?;
+
+    if config.migrate.require_reversible {
+        migrator.validate_reversible()?;
+    }
+
+    if let Some(target_version) = target_version {
+        if target_version != 0 && !migrator.version_exists(target_version) {
+            return Err(MigrateError::VersionNotPresent(target_version).into());
+        }
+    }
+
+    let mut conn = crate::connect(config, connect_opts).await?;
+
+    // FIXME: we should not be creating anything here if it doesn't exist
+    for schema_name in &config.migrate.create_schemas {
+        conn.create_schema_if_not_exists(schema_name).await?;
+    }
+
+    conn.ensure_migrations_table(config.migrate.table_name())
+        .await?;
+
+    let version = conn.dirty_version(config.migrate.table_name()).await?;
+    if let Some(version) = version {
+        return Err(MigrateError::Dirty(version).into());
+    }
+
+    let applied_migrations = conn
+        .list_applied_migrations(config.migrate.table_name())
+        .await?;
+    validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
+
+    let latest_version = applied_migrations
+        .iter()
+        .max_by(|x, y| x.version.cmp(&y.version))
+        .map(|migration| migration.version)
+        .unwrap_or(0);
+    if let Some(target_version) = target_version {
+        if target_version > latest_version {
+            return Err(MigrateError::VersionTooNew(target_version, latest_version).into());
+        }
+    }
+
+    let applied_migrations: HashMap<_, _> = applied_migrations
+        .into_iter()
+        .map(|m| (m.version, m))
+        .collect();
+
+    for migration in migrator.iter().rev() {
+        if !migration.migration_type.is_down_migration() {
+            // Skipping non down migration
+            // This will skip any simple or up migration file
+            continue;
+        }
+
+        if applied_migrations.contains_key(&migration.version) {
+            let skip =
+                target_version.is_some_and(|target_version| migration.version <= target_version);
+
+            let (status, elapsed) = if skip {
+                (MigrationStatus::Skipped, Duration::ZERO)
+            } else if dry_run {
+                (MigrationStatus::Pending, Duration::ZERO)
+            } else {
+                let elapsed = conn.revert(config.migrate.table_name(), migration).await?;
+                (MigrationStatus::Applied, elapsed)
+            };
+
+            report(&MigrationOutcome::new(migration, status, elapsed));
+
+            // Only a single migration will be reverted at a time if no target
+            // version is supplied, so we break.
+            if target_version.is_none() {
+                break;
+            }
+        }
+    }
+
+    let _ = conn.close().await;
+
+    Ok(())
+}
+
+/// Generate the query data of the project in the current directory for offline builds, or
+/// check that it is up to date with [`PrepareOptions::check`], as with `cargo sqlx prepare`.
+///
+/// This runs `cargo check`, so it must be called from a process started by Cargo, such as an
+/// `xtask`, for the `CARGO` environment variable to be set. Unlike the other functions here,
+/// the output of Cargo and any warnings are printed as with the command.
+pub async fn prepare(
+    config: &Config,
+    connect_opts: ConnectOpts,
+    options: &PrepareOptions,
+) -> Result<(), Error> {
+    prepare::run(
+        config,
+        options.check,
+        options.all,
+        options.workspace,
+        connect_opts,
+        options.cargo_args.clone(),
+    )
+    .await
+    .map_err(|e| e.downcast().unwrap_or_else(Error::Prepare))
+}
+
+/// Whether `migration` is older than the latest applied migration, which is an error unless
+/// `allow_out_of_order` is set.
+fn check_order(
+    migration: &Migration,
+    latest_version: i64,
+    allow_out_of_order: bool,
+) -> Result<bool, MigrateError> {
+    if migration.version >= latest_version {
+        return Ok(false);
+    }
+
+    if !allow_out_of_order {
+        return Err(MigrateError::VersionTooOld(
+            migration.version,
+            latest_version,
+        ));
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::{AssertSqlSafe, SqlSafeStr};
+
+    #[test]
+    fn out_of_order_migrations_are_rejected_unless_allowed() {
+        let migration = Migration::new(
+            2,
+            "second".into(),
+            MigrationType::Simple,
+            AssertSqlSafe("-- 2").into_sql_str(),
+            false,
+        );
+
+        assert!(!check_order(&migration, 1, false).unwrap());
+        assert!(!check_order(&migration, 1, true).unwrap());
+
+        assert!(matches!(
+            check_order(&migration, 3, false),
+            Err(MigrateError::VersionTooOld(2, 3))
+        ));
+        assert!(check_order(&migration, 3, true).unwrap());
+    }
+
+    #[test]
+    fn create_migration_files_works() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let source = dir.path().to_str().unwrap();
+
+        let mut created = Vec::new();
+        let paths = create_migration_files(source, "0001", "create users", true, |path| {
+            // reported before the file is created
+            assert!(!path.exists());
+            created.push(path.to_owned());
+        })?;
+
+        assert_eq!(
+            paths,
+            [
+                dir.path().join("0001_create_users.up.sql"),
+                dir.path().join("0001_create_users.down.sql"),
+            ]
+        );
+        assert!(paths.iter().all(|path| path.exists()));
+        assert_eq!(created, paths);
+
+        let paths = create_migration_files(source, "0002", "add email", false, |_| ())?;
+
+        assert_eq!(paths, [dir.path().join("0002_add_email.sql")]);
+
+        Ok(())
+    }
+}
@@ -1,20 +1,12 @@
 use crate::opt::{ConnectOpts, MigrationSourceOpt};
-use crate::{migrate, Config};
+use crate::{api, migrate, Config};
 use console::{style, Term};
 use dialoguer::Confirm;
-use sqlx::any::Any;
-use sqlx::migrate::MigrateDatabase;
 use std::{io, mem};
 use tokio::task;
 
 pub async fn create(connect_opts: &ConnectOpts) -> anyhow::Result<()> {
-    // NOTE: only retry the idempotent action.
-    // We're assuming that if this succeeds, then any following operations should also succeed.
-    let exists = crate::retry_connect_errors(connect_opts, Any::database_exists).await?;
-
-    if !exists {
-        Any::create_database(connect_opts.expect_db_url()?).await?;
-    }
+    api::create_database(connect_opts).await?;
 
     Ok(())
 }
@@ -24,17 +16,7 @@ pub async fn drop(connect_opts: &ConnectOpts, confirm: bool, force: bool) -> any
         return Ok(());
     }
 
-    // NOTE: only retry the idempotent action.
-    // We're assuming that if this succeeds, then any following operations should also succeed.
-    let exists = crate::retry_connect_errors(connect_opts, Any::database_exists).await?;
-
-    if exists {
-        if force {
-            Any::force_drop_database(connect_opts.expect_db_url()?).await?;
-        } else {
-            Any::drop_database(connect_opts.expect_db_url()?).await?;
-        }
-    }
+    api::drop_database(connect_opts, force).await?;
 
     Ok(())
 }
@@ -56,7 +38,16 @@ pub async fn setup(
     connect_opts: &ConnectOpts,
 ) -> anyhow::Result<()> {
     create(connect_opts).await?;
-    migrate::run(config, migration_source, connect_opts, false, false, None).await
+    migrate::run(
+        config,
+        migration_source,
+        connect_opts,
+        false,
+        false,
+        false,
+        None,
+    )
+    .await
 }
 
 async fn ask_to_continue_drop(db_url: String) -> bool {
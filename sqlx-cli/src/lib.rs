@@ -29,6 +29,7 @@ use tokio::{select, signal};
 
 use crate::opt::{Command, ConnectOpts, DatabaseCommand, MigrateCommand};
 
+pub mod api;
 pub mod database;
 pub mod metadata;
 // mod migration;
@@ -81,6 +82,7 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
                 config,
                 dry_run,
                 ignore_missing,
+                allow_out_of_order,
                 mut connect_opts,
                 target_version,
             } => {
@@ -94,6 +96,7 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
                     &connect_opts,
                     dry_run,
                     *ignore_missing,
+                    allow_out_of_order,
                     target_version,
                 )
                 .await?
@@ -120,6 +123,40 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
                 )
                 .await?
             }
+            MigrateCommand::Baseline {
+                source,
+                config,
+                dry_run,
+                ignore_missing,
+                mut connect_opts,
+                target_version,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::baseline(
+                    &config,
+                    &source,
+                    &connect_opts,
+                    dry_run,
+                    *ignore_missing,
+                    target_version,
+                )
+                .await?
+            }
+            MigrateCommand::Repair {
+                source,
+                config,
+                dry_run,
+                mut connect_opts,
+            } => {
+                let config = config.load_config().await?;
+
+                connect_opts.populate_db_url(&config)?;
+
+                migrate::repair(&config, &source, &connect_opts, dry_run).await?
+            }
             MigrateCommand::Info {
                 source,
                 config,
@@ -208,7 +245,7 @@ async fn do_run(opt: Opt) -> anyhow::Result<()> {
 }
 
 /// Attempt to connect to the database server, retrying up to `ops.connect_timeout`.
-async fn connect(config: &Config, opts: &ConnectOpts) -> anyhow::Result<AnyConnection> {
+async fn connect(config: &Config, opts: &ConnectOpts) -> Result<AnyConnection, api::Error> {
     retry_connect_errors(opts, move |url| {
         AnyConnection::connect_with_driver_config(url, &config.drivers)
     })
@@ -222,7 +259,7 @@ async fn connect(config: &Config, opts: &ConnectOpts) -> anyhow::Result<AnyConne
 async fn retry_connect_errors<'a, F, Fut, T>(
     opts: &'a ConnectOpts,
     mut connect: F,
-) -> anyhow::Result<T>
+) -> Result<T, api::Error>
 where
     F: FnMut(&'a str) -> Fut,
     Fut: Future<Output = sqlx::Result<T>> + 'a,
@@ -234,21 +271,22 @@ where
             .with_max_elapsed_time(Some(Duration::from_secs(opts.connect_timeout)))
             .build(),
         || {
-            connect(db_url).map_err(|e| -> backoff::Error<anyhow::Error> {
+            connect(db_url).map_err(|e| -> backoff::Error<sqlx::Error> {
                 if let sqlx::Error::Io(ref ioe) = e {
                     match ioe.kind() {
                         io::ErrorKind::ConnectionRefused
                         | io::ErrorKind::ConnectionReset
                         | io::ErrorKind::ConnectionAborted => {
-                            return backoff::Error::transient(e.into());
+                            return backoff::Error::transient(e);
                         }
                         _ => (),
                     }
                 }
 
-                backoff::Error::permanent(e.into())
+                backoff::Error::permanent(e)
             })
         },
     )
     .await
+    .map_err(api::Error::Database)
 }
@@ -1,13 +1,14 @@
+use crate::api::{self, MigrationOutcome, MigrationStatus, RevertOptions, RunOptions};
 use crate::config::Config;
 use crate::opt::{AddMigrationOpts, ConnectOpts, MigrationSourceOpt};
-use anyhow::{bail, Context};
+use anyhow::bail;
 use console::style;
-use sqlx::migrate::{AppliedMigration, Migrate, MigrateError, MigrationType, Migrator};
+use sqlx::migrate::{AppliedMigration, AuditInfo, Migrate, MigrateError, Migration, Migrator};
 use sqlx::Connection;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
-use std::fs::{self, File};
+use std::fs;
 use std::path::Path;
 use std::time::Duration;
 
@@ -16,33 +17,19 @@ pub async fn add(opts: AddMigrationOpts) -> anyhow::Result<()> {
 
     let source = opts.source.resolve_path(&config);
 
-    fs::create_dir_all(source).context("Unable to create migrations directory")?;
+    api::create_migrations_dir(source)?;
 
     let migrator = opts.source.resolve(&config).await?;
 
     let version_prefix = opts.version_prefix(&config, &migrator);
 
-    if opts.reversible(&config, &migrator) {
-        create_file(
-            source,
-            &version_prefix,
-            &opts.description,
-            MigrationType::ReversibleUp,
-        )?;
-        create_file(
-            source,
-            &version_prefix,
-            &opts.description,
-            MigrationType::ReversibleDown,
-        )?;
-    } else {
-        create_file(
-            source,
-            &version_prefix,
-            &opts.description,
-            MigrationType::Simple,
-        )?;
-    }
+    api::create_migration_files(
+        source,
+        &version_prefix,
+        &opts.description,
+        opts.reversible(&config, &migrator),
+        |path| println!("Creating {}", style(path.display()).cyan()),
+    )?;
 
     // if the migrations directory is empty
     let has_existing_migrations = fs::read_dir(source)
@@ -89,32 +76,6 @@ See: https://docs.rs/sqlx/{version}/sqlx/macro.migrate.html
     Ok(())
 }
 
-fn create_file(
-    migration_source: &str,
-    file_prefix: &str,
-    description: &str,
-    migration_type: MigrationType,
-) -> anyhow::Result<()> {
-    use std::path::PathBuf;
-
-    let mut file_name = file_prefix.to_string();
-    file_name.push('_');
-    file_name.push_str(&description.replace(' ', "_"));
-    file_name.push_str(migration_type.suffix());
-
-    let mut path = PathBuf::new();
-    path.push(migration_source);
-    path.push(&file_name);
-
-    println!("Creating {}", style(path.display()).cyan());
-
-    let mut file = File::create(&path).context("Failed to create migration file")?;
-
-    std::io::Write::write_all(&mut file, migration_type.file_content().as_bytes())?;
-
-    Ok(())
-}
-
 fn short_checksum(checksum: &[u8]) -> String {
     let mut s = String::with_capacity(checksum.len() * 2);
     for b in checksum {
@@ -147,12 +108,38 @@ pub async fn info(
         .map(|m| (m.version, m))
         .collect();
 
+    let applied_repeatable: HashMap<_, _> = conn
+        .list_applied_repeatable_migrations(config.migrate.table_name())
+        .await?
+        .into_iter()
+        .map(|m| (m.version, m))
+        .collect();
+
     for migration in migrator.iter() {
         if migration.migration_type.is_down_migration() {
             // Skipping down migrations
             continue;
         }
 
+        if migration.migration_type.is_repeatable() {
+            let status_msg = match applied_repeatable.get(&migration.version) {
+                Some(applied) if applied.checksum == migration.checksum => {
+                    style("installed").green()
+                }
+                Some(_) => style("changed (pending)").yellow(),
+                None => style("pending").yellow(),
+            };
+
+            println!(
+                "{}/{} {}",
+                style("R").cyan(),
+                status_msg,
+                migration.description
+            );
+
+            continue;
+        }
+
         let applied = applied_migrations.get(&migration.version);
 
         let (status_msg, mismatched_checksum) = if let Some(applied) = applied {
@@ -193,7 +180,7 @@ pub async fn info(
     Ok(())
 }
 
-fn validate_applied_migrations(
+pub(crate) fn validate_applied_migrations(
     applied_migrations: &[AppliedMigration],
     migrator: &Migrator,
     ignore_missing: bool,
@@ -219,14 +206,130 @@ pub async fn run(
     connect_opts: &ConnectOpts,
     dry_run: bool,
     ignore_missing: bool,
+    allow_out_of_order: bool,
     target_version: Option<i64>,
 ) -> anyhow::Result<()> {
-    let migrator = migration_source.resolve(config).await?;
+    let options = RunOptions {
+        dry_run,
+        ignore_missing,
+        allow_out_of_order,
+        target_version,
+    };
+
+    api::run_migrations_with(
+        config,
+        migration_source,
+        connect_opts,
+        &options,
+        print_outcome,
+    )
+    .await?;
+
+    Ok(())
+}
 
-    if let Some(target_version) = target_version {
-        if !migrator.version_exists(target_version) {
-            bail!(MigrateError::VersionNotPresent(target_version));
+pub(crate) async fn apply_migration(
+    conn: &mut (impl Migrate + Send),
+    config: &Config,
+    migration: &Migration,
+    audit: Option<&AuditInfo>,
+) -> Result<Duration, MigrateError> {
+    let table_name = config.migrate.table_name();
+
+    match audit {
+        Some(audit) => conn.apply_with_audit(table_name, migration, audit).await,
+        None => conn.apply(table_name, migration).await,
+    }
+}
+
+async fn mark_migration_applied(
+    conn: &mut (impl Migrate + Send),
+    config: &Config,
+    migration: &Migration,
+    audit: Option<&AuditInfo>,
+) -> Result<(), MigrateError> {
+    let table_name = config.migrate.table_name();
+
+    match audit {
+        Some(audit) => {
+            conn.mark_applied_with_audit(table_name, migration, audit)
+                .await
         }
+        None => conn.mark_applied(table_name, migration).await,
+    }
+}
+
+pub async fn revert(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    connect_opts: &ConnectOpts,
+    dry_run: bool,
+    ignore_missing: bool,
+    target_version: Option<i64>,
+) -> anyhow::Result<()> {
+    let options = RevertOptions {
+        dry_run,
+        ignore_missing,
+        target_version,
+    };
+
+    let mut is_applied = false;
+    api::revert_migrations_with(
+        config,
+        migration_source,
+        connect_opts,
+        &options,
+        |outcome| {
+            print_outcome(outcome);
+            is_applied = true;
+        },
+    )
+    .await?;
+
+    if !is_applied {
+        println!("No migrations available to revert");
+    }
+
+    Ok(())
+}
+
+fn print_outcome(outcome: &MigrationOutcome) {
+    let text = match (outcome.status, outcome.out_of_order) {
+        (MigrationStatus::Skipped, _) => "Skipped",
+        (MigrationStatus::Pending, false) => "Can apply",
+        (MigrationStatus::Pending, true) => "Can apply (out of order)",
+        (MigrationStatus::Applied, false) => "Applied",
+        (MigrationStatus::Applied, true) => "Applied (out of order)",
+    };
+
+    let version = if outcome.migration_type.is_repeatable() {
+        style("R".to_string()).cyan()
+    } else {
+        style(outcome.version.to_string()).cyan()
+    };
+
+    println!(
+        "{} {}/{} {} {}",
+        text,
+        version,
+        style(outcome.migration_type.label()).green(),
+        outcome.description,
+        style(format!("({:?})", outcome.elapsed)).dim()
+    );
+}
+
+pub async fn baseline(
+    config: &Config,
+    migration_source: &MigrationSourceOpt,
+    connect_opts: &ConnectOpts,
+    dry_run: bool,
+    ignore_missing: bool,
+    target_version: i64,
+) -> anyhow::Result<()> {
+    let migrator = migration_source.resolve(config).await?;
+
+    if !migrator.version_exists(target_version) {
+        bail!(MigrateError::VersionNotPresent(target_version));
     }
 
     let mut conn = crate::connect(config, connect_opts).await?;
@@ -238,6 +341,11 @@ pub async fn run(
     conn.ensure_migrations_table(config.migrate.table_name())
         .await?;
 
+    if config.migrate.record_audit && !dry_run {
+        conn.ensure_audit_columns(config.migrate.table_name())
+            .await?;
+    }
+
     let version = conn.dirty_version(config.migrate.table_name()).await?;
     if let Some(version) = version {
         bail!(MigrateError::Dirty(version));
@@ -248,28 +356,19 @@ pub async fn run(
         .await?;
     validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
 
-    let latest_version = applied_migrations
-        .iter()
-        .max_by(|x, y| x.version.cmp(&y.version))
-        .map(|migration| migration.version)
-        .unwrap_or(0);
-    if let Some(target_version) = target_version {
-        if target_version < latest_version {
-            bail!(MigrateError::VersionTooOld(target_version, latest_version));
-        }
-    }
-
     let applied_migrations: HashMap<_, _> = applied_migrations
         .into_iter()
         .map(|m| (m.version, m))
         .collect();
 
-    for migration in migrator.iter() {
-        if migration.migration_type.is_down_migration() {
-            // Skipping down migrations
-            continue;
-        }
+    let audit = config.migrate.record_audit.then(AuditInfo::from_env);
 
+    let mut is_marked = false;
+    for migration in migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+        .take_while(|m| m.version <= target_version)
+    {
         match applied_migrations.get(&migration.version) {
             Some(applied_migration) => {
                 if migration.checksum != applied_migration.checksum {
@@ -277,61 +376,43 @@ pub async fn run(
                 }
             }
             None => {
-                let skip =
-                    target_version.is_some_and(|target_version| migration.version > target_version);
-
-                let elapsed = if dry_run || skip {
-                    Duration::new(0, 0)
-                } else {
-                    conn.apply(config.migrate.table_name(), migration).await?
-                };
-                let text = if skip {
-                    "Skipped"
-                } else if dry_run {
-                    "Can apply"
-                } else {
-                    "Applied"
-                };
+                if !dry_run {
+                    mark_migration_applied(&mut conn, config, migration, audit.as_ref()).await?;
+                }
+
+                let text = if dry_run { "Can mark" } else { "Marked" };
 
                 println!(
-                    "{} {}/{} {} {}",
+                    "{} {}/{} {}",
                     text,
                     style(migration.version).cyan(),
-                    style(migration.migration_type.label()).green(),
+                    style("baseline").green(),
                     migration.description,
-                    style(format!("({elapsed:?})")).dim()
                 );
+
+                is_marked = true;
             }
         }
     }
+    if !is_marked {
+        println!("No migrations to mark as applied");
+    }
 
-    // Close the connection before exiting:
-    // * For Postgres this should ensure timely cleanup on the server side,
-    //   including decrementing the open connection count.
     let _ = conn.close().await;
 
     Ok(())
 }
 
-pub async fn revert(
+pub async fn repair(
     config: &Config,
     migration_source: &MigrationSourceOpt,
     connect_opts: &ConnectOpts,
     dry_run: bool,
-    ignore_missing: bool,
-    target_version: Option<i64>,
 ) -> anyhow::Result<()> {
     let migrator = migration_source.resolve(config).await?;
 
-    if let Some(target_version) = target_version {
-        if target_version != 0 && !migrator.version_exists(target_version) {
-            bail!(MigrateError::VersionNotPresent(target_version));
-        }
-    }
-
     let mut conn = crate::connect(config, connect_opts).await?;
 
-    // FIXME: we should not be creating anything here if it doesn't exist
     for schema_name in &config.migrate.create_schemas {
         conn.create_schema_if_not_exists(schema_name).await?;
     }
@@ -344,72 +425,45 @@ pub async fn revert(
         bail!(MigrateError::Dirty(version));
     }
 
-    let applied_migrations = conn
+    let applied_migrations: HashMap<_, _> = conn
         .list_applied_migrations(config.migrate.table_name())
-        .await?;
-    validate_applied_migrations(&applied_migrations, &migrator, ignore_missing)?;
-
-    let latest_version = applied_migrations
-        .iter()
-        .max_by(|x, y| x.version.cmp(&y.version))
-        .map(|migration| migration.version)
-        .unwrap_or(0);
-    if let Some(target_version) = target_version {
-        if target_version > latest_version {
-            bail!(MigrateError::VersionTooNew(target_version, latest_version));
-        }
-    }
-
-    let applied_migrations: HashMap<_, _> = applied_migrations
+        .await?
         .into_iter()
         .map(|m| (m.version, m))
         .collect();
 
-    let mut is_applied = false;
-    for migration in migrator.iter().rev() {
-        if !migration.migration_type.is_down_migration() {
-            // Skipping non down migration
-            // This will skip any simple or up migration file
+    let mut is_repaired = false;
+    for migration in migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration())
+    {
+        let Some(applied_migration) = applied_migrations.get(&migration.version) else {
             continue;
-        }
+        };
 
-        if applied_migrations.contains_key(&migration.version) {
-            let skip =
-                target_version.is_some_and(|target_version| migration.version <= target_version);
+        if migration.checksum == applied_migration.checksum {
+            continue;
+        }
 
-            let elapsed = if dry_run || skip {
-                Duration::new(0, 0)
-            } else {
-                conn.revert(config.migrate.table_name(), migration).await?
-            };
-            let text = if skip {
-                "Skipped"
-            } else if dry_run {
-                "Can apply"
-            } else {
-                "Applied"
-            };
+        if !dry_run {
+            conn.update_checksum(config.migrate.table_name(), migration)
+                .await?;
+        }
 
-            println!(
-                "{} {}/{} {} {}",
-                text,
-                style(migration.version).cyan(),
-                style(migration.migration_type.label()).green(),
-                migration.description,
-                style(format!("({elapsed:?})")).dim()
-            );
+        let text = if dry_run { "Can repair" } else { "Repaired" };
 
-            is_applied = true;
+        println!(
+            "{} {}/{} {}",
+            text,
+            style(migration.version).cyan(),
+            style("checksum").green(),
+            migration.description,
+        );
 
-            // Only a single migration will be reverted at a time if no target
-            // version is supplied, so we break.
-            if target_version.is_none() {
-                break;
-            }
-        }
+        is_repaired = true;
     }
-    if !is_applied {
-        println!("No migrations available to revert");
+    if !is_repaired {
+        println!("No migrations to repair");
     }
 
     let _ = conn.close().await;
@@ -1,3 +1,4 @@
+use crate::api;
 use crate::config::migrate::{DefaultMigrationType, DefaultVersioning};
 use crate::config::Config;
 use anyhow::Context;
@@ -8,7 +9,7 @@ use clap::{
 };
 #[cfg(feature = "completions")]
 use clap_complete::Shell;
-use sqlx::migrate::{MigrateError, Migrator, ResolveWith};
+use sqlx::migrate::{MigrateError, Migration, Migrator, ResolveWith};
 use std::env;
 use std::ops::{Deref, Not};
 use std::path::PathBuf;
@@ -201,7 +202,8 @@ pub enum MigrateCommand {
     /// Version numbers do not need to be strictly consecutive.
     ///
     /// The migration process will abort if SQLx encounters a migration with a version number
-    /// less than _any_ previously applied migration.
+    /// less than _any_ previously applied migration, unless `--allow-out-of-order` is passed
+    /// to `sqlx migrate run`.
     ///
     /// Migrations should only be created with increasing version number.
     ///
@@ -238,6 +240,13 @@ pub enum MigrateCommand {
         #[clap(flatten)]
         ignore_missing: IgnoreMissing,
 
+        /// Apply pending migrations that are older than the latest applied migration,
+        /// instead of returning an error.
+        ///
+        /// May also be enabled with `migrate.allow-out-of-order` in `sqlx.toml`.
+        #[clap(long)]
+        allow_out_of_order: bool,
+
         #[clap(flatten)]
         connect_opts: ConnectOpts,
 
@@ -272,6 +281,52 @@ pub enum MigrateCommand {
         target_version: Option<i64>,
     },
 
+    /// Mark all migrations up to the specified version as applied, without running them.
+    ///
+    /// Use this to adopt migrations for an existing database whose schema was created
+    /// by other means.
+    Baseline {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        /// List the migrations to be marked as applied without changing anything
+        #[clap(long)]
+        dry_run: bool,
+
+        #[clap(flatten)]
+        ignore_missing: IgnoreMissing,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+
+        /// Mark migrations up to and including this version as applied.
+        #[clap(long)]
+        target_version: i64,
+    },
+
+    /// Update the checksums recorded for applied migrations to match the migration files,
+    /// without running them.
+    ///
+    /// Use this after intentionally changing applied migrations in ways that don't affect
+    /// the schema, e.g. reformatting them or changing `checksum-normalization`.
+    Repair {
+        #[clap(flatten)]
+        source: MigrationSourceOpt,
+
+        #[clap(flatten)]
+        config: ConfigOpt,
+
+        /// List the migrations whose checksum would be updated without changing anything
+        #[clap(long)]
+        dry_run: bool,
+
+        #[clap(flatten)]
+        connect_opts: ConnectOpts,
+    },
+
     /// List all available migrations.
     Info {
         #[clap(flatten)]
@@ -332,7 +387,7 @@ pub struct AddMigrationOpts {
 }
 
 /// Argument for the migration scripts source.
-#[derive(Args, Debug)]
+#[derive(Args, Debug, Default)]
 pub struct MigrationSourceOpt {
     /// Path to folder containing migrations.
     ///
@@ -351,11 +406,18 @@ impl MigrationSourceOpt {
     }
 
     pub async fn resolve(&self, config: &Config) -> Result<Migrator, MigrateError> {
-        Migrator::new(ResolveWith(
+        let mut migrator = Migrator::new(ResolveWith(
             self.resolve_path(config),
             config.migrate.to_resolve_config(),
         ))
-        .await
+        .await?;
+
+        migrator
+            .set_require_reversible(config.migrate.require_reversible)
+            .set_allow_out_of_order(config.migrate.allow_out_of_order)
+            .set_record_audit(config.migrate.record_audit);
+
+        Ok(migrator)
     }
 }
 
@@ -400,12 +462,21 @@ pub struct ConfigOpt {
 }
 
 impl ConnectOpts {
+    /// Options to connect to the database at `database_url`, with the default timeout.
+    pub fn new(database_url: impl Into<String>) -> Self {
+        ConnectOpts {
+            no_dotenv: NoDotenvOpt { no_dotenv: false },
+            database_url: Some(database_url.into()),
+            connect_timeout: 10,
+        }
+    }
+
     /// Require a database URL to be provided, otherwise
     /// return an error.
-    pub fn expect_db_url(&self) -> anyhow::Result<&str> {
+    pub fn expect_db_url(&self) -> Result<&str, api::Error> {
         self.database_url
             .as_deref()
-            .context("BUG: database_url not populated")
+            .ok_or(api::Error::DatabaseUrlNotSet)
     }
 
     /// Populate `database_url` from the environment, if not set.
@@ -509,53 +580,77 @@ impl AddMigrationOpts {
             return false;
         }
 
-        match config.migrate.defaults.migration_type {
-            DefaultMigrationType::Inferred => migrator
-                .iter()
-                .last()
-                .is_some_and(|m| m.migration_type.is_reversible()),
-            DefaultMigrationType::Simple => false,
-            DefaultMigrationType::Reversible => true,
-        }
+        default_reversible(config, migrator)
     }
 
     pub fn version_prefix(&self, config: &Config, migrator: &Migrator) -> String {
-        let default_versioning = &config.migrate.defaults.migration_versioning;
-
-        match (self.timestamp, self.sequential, default_versioning) {
-            (true, false, _) | (false, false, DefaultVersioning::Timestamp) => next_timestamp(),
-            (false, true, _) | (false, false, DefaultVersioning::Sequential) => fmt_sequential(
-                migrator
-                    .migrations
-                    .last()
-                    .map_or(1, |migration| migration.version + 1),
-            ),
-            (false, false, DefaultVersioning::Inferred) => {
-                migrator
-                    .migrations
-                    .rchunks(2)
-                    .next()
-                    .and_then(|migrations| {
-                        match migrations {
-                            [previous, latest] => {
-                                // If the latest two versions differ by 1, infer sequential.
-                                (latest.version - previous.version == 1)
-                                    .then_some(latest.version + 1)
-                            }
-                            [latest] => {
-                                // If only one migration exists and its version is 0 or 1, infer sequential
-                                matches!(latest.version, 0 | 1).then_some(latest.version + 1)
-                            }
-                            _ => unreachable!(),
+        match (self.timestamp, self.sequential) {
+            (true, false) => next_timestamp(),
+            (false, true) => next_sequential(migrator),
+            (false, false) => default_version_prefix(config, migrator),
+            (true, true) => unreachable!("BUG: Clap should have rejected this case"),
+        }
+    }
+}
+
+/// Whether a new migration should be reversible if neither `--simple` nor `--reversible` is set.
+pub(crate) fn default_reversible(config: &Config, migrator: &Migrator) -> bool {
+    if config.migrate.require_reversible {
+        return true;
+    }
+
+    match config.migrate.defaults.migration_type {
+        DefaultMigrationType::Inferred => migrator
+            .iter()
+            .rfind(|m| !m.migration_type.is_repeatable())
+            .is_some_and(|m| m.migration_type.is_reversible()),
+        DefaultMigrationType::Simple => false,
+        DefaultMigrationType::Reversible => true,
+    }
+}
+
+/// The version of a new migration if neither `--timestamp` nor `--sequential` is set.
+pub(crate) fn default_version_prefix(config: &Config, migrator: &Migrator) -> String {
+    match &config.migrate.defaults.migration_versioning {
+        DefaultVersioning::Timestamp => next_timestamp(),
+        DefaultVersioning::Sequential => next_sequential(migrator),
+        DefaultVersioning::Inferred => {
+            versioned_migrations(migrator)
+                .rchunks(2)
+                .next()
+                .and_then(|migrations| {
+                    match migrations {
+                        [previous, latest] => {
+                            // If the latest two versions differ by 1, infer sequential.
+                            (latest.version - previous.version == 1).then_some(latest.version + 1)
                         }
-                    })
-                    .map_or_else(next_timestamp, fmt_sequential)
-            }
-            (true, true, _) => unreachable!("BUG: Clap should have rejected this case"),
+                        [latest] => {
+                            // If only one migration exists and its version is 0 or 1, infer sequential
+                            matches!(latest.version, 0 | 1).then_some(latest.version + 1)
+                        }
+                        _ => unreachable!(),
+                    }
+                })
+                .map_or_else(next_timestamp, fmt_sequential)
         }
     }
 }
 
+fn versioned_migrations(migrator: &Migrator) -> &[Migration] {
+    // Repeatable migrations are sorted last and don't have a meaningful version.
+    &migrator.migrations[..migrator
+        .migrations
+        .partition_point(|m| !m.migration_type.is_repeatable())]
+}
+
+fn next_sequential(migrator: &Migrator) -> String {
+    fmt_sequential(
+        versioned_migrations(migrator)
+            .last()
+            .map_or(1, |migration| migration.version + 1),
+    )
+}
+
 fn next_timestamp() -> String {
     Utc::now().format("%Y%m%d%H%M%S").to_string()
 }
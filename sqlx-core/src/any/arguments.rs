@@ -1,6 +1,8 @@
+use crate::any::custom;
 use crate::any::value::AnyValueKind;
 use crate::any::{Any, AnyTypeInfoKind};
 use crate::arguments::Arguments;
+use crate::database::Database;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::types::Type;
@@ -39,6 +41,7 @@ impl AnyArguments {
     #[doc(hidden)]
     pub fn convert_into<'a, A: Arguments>(self) -> Result<A, BoxDynError>
     where
+        A::Database: Database<Arguments = A>,
         Option<i32>: Type<A::Database> + Encode<'a, A::Database>,
         Option<bool>: Type<A::Database> + Encode<'a, A::Database>,
         Option<i16>: Type<A::Database> + Encode<'a, A::Database>,
@@ -71,6 +74,9 @@ impl AnyArguments {
                 AnyValueKind::Null(AnyTypeInfoKind::Double) => out.add(Option::<f32>::None),
                 AnyValueKind::Null(AnyTypeInfoKind::Text) => out.add(Option::<String>::None),
                 AnyValueKind::Null(AnyTypeInfoKind::Blob) => out.add(Option::<Vec<u8>>::None),
+                AnyValueKind::Null(AnyTypeInfoKind::Custom(name)) => {
+                    custom::encode_null_value::<A::Database>(name, &mut out)
+                }
                 AnyValueKind::Bool(b) => out.add(b),
                 AnyValueKind::SmallInt(i) => out.add(i),
                 AnyValueKind::Integer(i) => out.add(i),
@@ -80,6 +86,7 @@ impl AnyArguments {
                 AnyValueKind::Text(t) => out.add(t),
                 AnyValueKind::TextSlice(t) => out.add(t),
                 AnyValueKind::Blob(b) => out.add(b),
+                AnyValueKind::Custom(c) => custom::encode_value::<A::Database>(&c, &mut out),
             }?
         }
         Ok(out)
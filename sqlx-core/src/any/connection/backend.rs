@@ -5,6 +5,7 @@ use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use std::fmt::Debug;
+use std::time::Duration;
 
 pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// The backend name.
@@ -23,8 +24,8 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     #[doc(hidden)]
     fn close_hard(self: Box<Self>) -> BoxFuture<'static, crate::Result<()>>;
 
-    /// Checks if a connection to the database is still valid.
-    fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>>;
+    /// Checks if a connection to the database is still valid, returning the round-trip time.
+    fn ping(&mut self) -> BoxFuture<'_, crate::Result<Duration>>;
 
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
@@ -1,5 +1,6 @@
 use futures_core::future::BoxFuture;
 use std::future::Future;
+use std::time::Duration;
 
 use crate::any::{Any, AnyConnectOptions};
 use crate::connection::{ConnectOptions, Connection};
@@ -103,7 +104,7 @@ impl Connection for AnyConnection {
         self.backend.close()
     }
 
-    fn ping(&mut self) -> impl Future<Output = Result<(), Error>> + Send + '_ {
+    fn ping(&mut self) -> impl Future<Output = Result<Duration, Error>> + Send + '_ {
         self.backend.ping()
     }
 
@@ -0,0 +1,300 @@
+use crate::any::{Any, AnyArgumentBuffer, AnyTypeInfo, AnyTypeInfoKind, AnyValueKind};
+use crate::arguments::Arguments;
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use std::any::{type_name, Any as StdAny, TypeId};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, OnceLock};
+
+static TYPES: OnceLock<Vec<AnyCustomType>> = OnceLock::new();
+
+/// A type outside of the scalar types natively supported by the `Any` driver, together with how
+/// a specific driver encodes and decodes it.
+///
+/// Once registered with [`install_types`], values of the type can be bound and decoded through
+/// the `Any` driver by wrapping them in [`AnyCustom`].
+///
+/// ```rust,ignore
+/// use sqlx::any::{AnyCustom, AnyCustomType};
+/// use sqlx::Postgres;
+/// use uuid::Uuid;
+///
+/// sqlx::any::install_default_drivers();
+/// sqlx::any::install_types([AnyCustomType::new::<Postgres, Uuid>("UUID")])?;
+///
+/// let AnyCustom(id) = sqlx::query_scalar::<_, AnyCustom<Uuid>>("SELECT gen_random_uuid()")
+///     .fetch_one(&mut any_conn)
+///     .await?;
+/// ```
+pub struct AnyCustomType {
+    name: &'static str,
+    type_id: TypeId,
+    bridge: Box<dyn StdAny + Send + Sync>,
+}
+
+// The driver-specific half of an `AnyCustomType`; retrieved by downcasting to the `Bridge`
+// of the database a value is being converted to or from.
+struct Bridge<DB: Database> {
+    compatible: fn(&DB::TypeInfo) -> bool,
+    encode: fn(&AnyCustomValue, &mut DB::Arguments) -> Result<(), BoxDynError>,
+    encode_null: fn(&mut DB::Arguments) -> Result<(), BoxDynError>,
+    decode: for<'r> fn(DB::ValueRef<'r>) -> Result<AnyCustomValue, BoxDynError>,
+}
+
+impl AnyCustomType {
+    /// Register `T` for the database `DB`, under the type name `name`.
+    ///
+    /// Columns of any type `T` is compatible with in `DB` are decoded through `T`,
+    /// and reported as the type `name` by the `Any` driver.
+    ///
+    /// To support the same type on several databases, register it once for each of them,
+    /// under the same name.
+    pub fn new<DB, T>(name: &'static str) -> Self
+    where
+        DB: Database,
+        T: Type<DB> + for<'q> Encode<'q, DB> + for<'r> Decode<'r, DB>,
+        T: Clone + Send + Sync + 'static,
+        Option<T>: for<'q> Encode<'q, DB>,
+    {
+        AnyCustomType {
+            name,
+            type_id: TypeId::of::<T>(),
+            bridge: Box::new(Bridge::<DB> {
+                compatible: <T as Type<DB>>::compatible,
+                encode: encode::<DB, T>,
+                encode_null: encode_null::<DB, T>,
+                decode: decode::<DB, T>,
+            }),
+        }
+    }
+
+    /// The name of the type, as reported by [`AnyTypeInfo`].
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn bridge<DB: Database>(&self) -> Option<&Bridge<DB>> {
+        self.bridge.downcast_ref()
+    }
+}
+
+impl Debug for AnyCustomType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyCustomType")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+fn encode<DB, T>(value: &AnyCustomValue, args: &mut DB::Arguments) -> Result<(), BoxDynError>
+where
+    DB: Database,
+    T: Type<DB> + for<'q> Encode<'q, DB> + Clone + 'static,
+{
+    args.add(value.downcast::<T>()?.clone())
+}
+
+fn encode_null<DB, T>(args: &mut DB::Arguments) -> Result<(), BoxDynError>
+where
+    DB: Database,
+    T: Type<DB>,
+    Option<T>: for<'q> Encode<'q, DB>,
+{
+    args.add(Option::<T>::None)
+}
+
+fn decode<DB, T>(value: DB::ValueRef<'_>) -> Result<AnyCustomValue, BoxDynError>
+where
+    DB: Database,
+    T: for<'r> Decode<'r, DB> + Send + Sync + 'static,
+{
+    let name = registered_name::<T>().unwrap_or_else(type_name::<T>);
+
+    Ok(AnyCustomValue {
+        name,
+        value: Arc::new(T::decode(value)?),
+    })
+}
+
+/// Install the list of custom types for the `Any` driver to support.
+///
+/// Should be called before any queries using the types are executed; until then, binding an
+/// [`AnyCustom`] is an error, and columns of the types cannot be decoded.
+///
+/// ### Errors
+/// If called more than once.
+pub fn install_types(
+    types: impl IntoIterator<Item = AnyCustomType>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    TYPES
+        .set(types.into_iter().collect())
+        .map_err(|_| "custom types already installed".into())
+}
+
+fn installed() -> &'static [AnyCustomType] {
+    TYPES.get().map_or(&[], Vec::as_slice)
+}
+
+fn registered_name<T: 'static>() -> Option<&'static str> {
+    installed()
+        .iter()
+        .find(|ty| ty.type_id == TypeId::of::<T>())
+        .map(|ty| ty.name)
+}
+
+fn find<DB: Database>(name: &str) -> Result<&'static Bridge<DB>, BoxDynError> {
+    installed()
+        .iter()
+        .filter(|ty| ty.name == name)
+        .find_map(AnyCustomType::bridge::<DB>)
+        .ok_or_else(|| {
+            format!(
+                "type {name} has not been registered for the {} driver",
+                DB::NAME
+            )
+            .into()
+        })
+}
+
+/// Map a type of `DB` which the `Any` driver does not natively support to a registered custom type.
+#[doc(hidden)]
+pub fn custom_type_info<DB: Database>(ty: &DB::TypeInfo) -> Option<AnyTypeInfo> {
+    installed()
+        .iter()
+        .find(|custom| {
+            custom
+                .bridge::<DB>()
+                .is_some_and(|bridge| (bridge.compatible)(ty))
+        })
+        .map(|custom| AnyTypeInfo {
+            kind: AnyTypeInfoKind::Custom(custom.name),
+        })
+}
+
+pub(crate) fn encode_value<DB: Database>(
+    value: &AnyCustomValue,
+    args: &mut DB::Arguments,
+) -> Result<(), BoxDynError> {
+    (find::<DB>(value.name)?.encode)(value, args)
+}
+
+pub(crate) fn encode_null_value<DB: Database>(
+    name: &str,
+    args: &mut DB::Arguments,
+) -> Result<(), BoxDynError> {
+    (find::<DB>(name)?.encode_null)(args)
+}
+
+pub(crate) fn decode_value<DB: Database>(
+    name: &str,
+    value: DB::ValueRef<'_>,
+) -> Result<AnyCustomValue, BoxDynError> {
+    (find::<DB>(name)?.decode)(value)
+}
+
+/// A value of a custom type, decoded by the driver.
+#[derive(Clone)]
+#[doc(hidden)]
+pub struct AnyCustomValue {
+    name: &'static str,
+    value: Arc<dyn StdAny + Send + Sync>,
+}
+
+impl AnyCustomValue {
+    pub(crate) fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn downcast<T: 'static>(&self) -> Result<&T, BoxDynError> {
+        self.value.downcast_ref().ok_or_else(|| {
+            format!(
+                "expected a value of `{}`, got a value of the custom type {}",
+                type_name::<T>(),
+                self.name
+            )
+            .into()
+        })
+    }
+}
+
+impl Debug for AnyCustomValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AnyCustomValue").field(&self.name).finish()
+    }
+}
+
+/// Binds or decodes a value of a custom type registered with [`install_types`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnyCustom<T>(pub T);
+
+impl<T: 'static> Type<Any> for AnyCustom<T> {
+    fn type_info() -> AnyTypeInfo {
+        AnyTypeInfo {
+            kind: AnyTypeInfoKind::Custom(registered_name::<T>().unwrap_or_else(type_name::<T>)),
+        }
+    }
+
+    fn compatible(ty: &AnyTypeInfo) -> bool {
+        match ty.kind {
+            AnyTypeInfoKind::Custom(name) => installed()
+                .iter()
+                .any(|custom| custom.type_id == TypeId::of::<T>() && custom.name == name),
+            _ => false,
+        }
+    }
+}
+
+impl<T> Encode<'_, Any> for AnyCustom<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn encode_by_ref(&self, buf: &mut AnyArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let name = registered_name::<T>().ok_or_else(|| {
+            format!(
+                "`{}` has not been registered with `install_types`",
+                type_name::<T>()
+            )
+        })?;
+
+        buf.0.push(AnyValueKind::Custom(AnyCustomValue {
+            name,
+            value: Arc::new(self.0.clone()),
+        }));
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r, T> Decode<'r, Any> for AnyCustom<T>
+where
+    T: Clone + 'static,
+{
+    fn decode(value: <Any as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.kind {
+            AnyValueKind::Custom(custom) => Ok(AnyCustom(custom.downcast::<T>()?.clone())),
+            other => other.unexpected(),
+        }
+    }
+}
+
+#[test]
+fn unregistered_types_are_rejected() {
+    #[derive(Clone)]
+    struct Unregistered;
+
+    let ty = <AnyCustom<Unregistered> as Type<Any>>::type_info();
+    assert_eq!(
+        ty.kind,
+        AnyTypeInfoKind::Custom(type_name::<Unregistered>())
+    );
+    assert!(!<AnyCustom<Unregistered> as Type<Any>>::compatible(&ty));
+
+    let mut buf = AnyArgumentBuffer::default();
+    let Err(err) = AnyCustom(Unregistered).encode_by_ref(&mut buf) else {
+        panic!("encoding an unregistered type should fail");
+    };
+    assert!(err.to_string().contains("has not been registered"));
+    assert!(buf.0.is_empty());
+}
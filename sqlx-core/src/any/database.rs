@@ -33,6 +33,9 @@ impl Database for Any {
     const NAME: &'static str = "Any";
 
     const URL_SCHEMES: &'static [&'static str] = &[];
+
+    // The lowest limit of the drivers built into SQLx.
+    const MAX_ARGUMENTS: usize = 65535;
 }
 
 // This _may_ be true, depending on the selected database
@@ -1,7 +1,9 @@
 use crate::any::driver;
 use crate::any::{Any, AnyConnection};
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration};
+use crate::migrate::{
+    AppliedMigration, AuditInfo, Migrate, MigrateDatabase, MigrateError, Migration, SchemaSnapshot,
+};
 use futures_core::future::BoxFuture;
 use std::time::Duration;
 
@@ -47,6 +49,17 @@ impl Migrate for AnyConnection {
         })
     }
 
+    fn migrations_table_exists<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .migrations_table_exists(table_name)
+                .await
+        })
+    }
+
     fn ensure_migrations_table<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -58,6 +71,17 @@ impl Migrate for AnyConnection {
         })
     }
 
+    fn ensure_audit_columns<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .ensure_audit_columns(table_name)
+                .await
+        })
+    }
+
     fn dirty_version<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -76,12 +100,44 @@ impl Migrate for AnyConnection {
         })
     }
 
-    fn lock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
-        Box::pin(async { self.get_migrate()?.lock().await })
+    fn list_applied_repeatable_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .list_applied_repeatable_migrations(table_name)
+                .await
+        })
+    }
+
+    fn lock<'e>(&'e mut self, table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.lock(table_name).await })
+    }
+
+    fn unlock<'e>(&'e mut self, table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.unlock(table_name).await })
     }
 
-    fn unlock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
-        Box::pin(async { self.get_migrate()?.unlock().await })
+    fn try_lock<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async { self.get_migrate()?.try_lock(table_name).await })
+    }
+
+    fn try_lock_table<'e>(
+        &'e mut self,
+        lock_table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async { self.get_migrate()?.try_lock_table(lock_table_name).await })
+    }
+
+    fn unlock_table<'e>(
+        &'e mut self,
+        lock_table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.unlock_table(lock_table_name).await })
     }
 
     fn apply<'e>(
@@ -92,6 +148,69 @@ impl Migrate for AnyConnection {
         Box::pin(async { self.get_migrate()?.apply(table_name, migration).await })
     }
 
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .mark_applied(table_name, migration)
+                .await
+        })
+    }
+
+    fn record_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .record_audit(table_name, version, audit)
+                .await
+        })
+    }
+
+    fn apply_with_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .apply_with_audit(table_name, migration, audit)
+                .await
+        })
+    }
+
+    fn mark_applied_with_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            self.get_migrate()?
+                .mark_applied_with_audit(table_name, migration, audit)
+                .await
+        })
+    }
+
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async {
+            self.get_migrate()?
+                .update_checksum(table_name, migration)
+                .await
+        })
+    }
+
     fn revert<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -99,4 +218,11 @@ impl Migrate for AnyConnection {
     ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
         Box::pin(async { self.get_migrate()?.revert(table_name, migration).await })
     }
+
+    fn schema_snapshot<'e>(
+        &'e mut self,
+        exclude_tables: &'e [String],
+    ) -> BoxFuture<'e, Result<SchemaSnapshot, MigrateError>> {
+        Box::pin(async { self.get_migrate()?.schema_snapshot(exclude_tables).await })
+    }
 }
@@ -8,6 +8,7 @@ use crate::executor::Executor;
 mod arguments;
 pub(crate) mod column;
 mod connection;
+pub(crate) mod custom;
 mod database;
 mod error;
 mod kind;
@@ -28,6 +29,7 @@ mod migrate;
 pub use arguments::{AnyArgumentBuffer, AnyArguments};
 pub use column::AnyColumn;
 pub use connection::AnyConnection;
+pub use custom::{install_types, AnyCustom, AnyCustomType};
 // Used internally in `sqlx-macros`
 
 use crate::encode::Encode;
@@ -45,6 +47,8 @@ pub use value::{AnyValue, AnyValueRef};
 
 use crate::types::Type;
 #[doc(hidden)]
+pub use custom::{custom_type_info, AnyCustomValue};
+#[doc(hidden)]
 pub use value::AnyValueKind;
 
 pub type AnyPool = crate::pool::Pool<Any>;
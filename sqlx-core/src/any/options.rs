@@ -1,6 +1,7 @@
 use crate::any::AnyConnection;
-use crate::connection::{ConnectOptions, LogSettings};
+use crate::connection::{ConnectOptions, ConnectRetryPolicy, LogSettings};
 use crate::error::Error;
+use crate::statistics::StatementStatistics;
 use log::LevelFilter;
 use std::future::Future;
 use std::str::FromStr;
@@ -18,6 +19,7 @@ use url::Url;
 pub struct AnyConnectOptions {
     pub database_url: Url,
     pub log_settings: LogSettings,
+    pub connect_retry: Option<ConnectRetryPolicy>,
 }
 impl FromStr for AnyConnectOptions {
     type Err = Error;
@@ -28,6 +30,7 @@ impl FromStr for AnyConnectOptions {
                 .parse::<Url>()
                 .map_err(|e| Error::Configuration(e.into()))?,
             log_settings: LogSettings::default(),
+            connect_retry: None,
         })
     }
 }
@@ -39,6 +42,7 @@ impl ConnectOptions for AnyConnectOptions {
         Ok(AnyConnectOptions {
             database_url: url.clone(),
             log_settings: LogSettings::default(),
+            connect_retry: None,
         })
     }
 
@@ -61,6 +65,25 @@ impl ConnectOptions for AnyConnectOptions {
         self.log_settings.slow_statements_duration = duration;
         self
     }
+
+    fn slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.log_settings.slow_query_threshold = Some(threshold);
+        self
+    }
+
+    fn collect_statistics(mut self, max_statements: usize) -> Self {
+        self.log_settings.collect_statistics(max_statements);
+        self
+    }
+
+    fn statistics(&self) -> Option<&StatementStatistics> {
+        self.log_settings.statistics.as_ref()
+    }
+
+    fn retry(mut self, policy: ConnectRetryPolicy) -> Self {
+        self.connect_retry = Some(policy);
+        self
+    }
 }
 
 impl AnyConnectOptions {}
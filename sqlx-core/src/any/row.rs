@@ -1,3 +1,4 @@
+use crate::any::custom;
 use crate::any::error::mismatched_types;
 use crate::any::{Any, AnyColumn, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueKind};
 use crate::column::{Column, ColumnIndex};
@@ -127,6 +128,9 @@ impl AnyRow {
                 AnyTypeInfoKind::Double => AnyValueKind::Double(decode(value)?),
                 AnyTypeInfoKind::Blob => AnyValueKind::Blob(decode::<_, Vec<u8>>(value)?.into()),
                 AnyTypeInfoKind::Text => AnyValueKind::Text(decode::<_, String>(value)?.into()),
+                AnyTypeInfoKind::Custom(name) => AnyValueKind::Custom(
+                    custom::decode_value::<R::Database>(name, value).map_err(Error::decode)?,
+                ),
             };
 
             row_out.columns.push(any_col);
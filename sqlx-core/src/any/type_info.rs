@@ -27,6 +27,8 @@ pub enum AnyTypeInfoKind {
     Double,
     Text,
     Blob,
+    /// A custom type registered with [`install_types`][crate::any::install_types].
+    Custom(&'static str),
 }
 
 impl TypeInfo for AnyTypeInfo {
@@ -47,6 +49,7 @@ impl TypeInfo for AnyTypeInfo {
             Text => "TEXT",
             Blob => "BLOB",
             Null => "NULL",
+            Custom(name) => name,
         }
     }
 }
@@ -28,7 +28,7 @@ impl<'a> Encode<'a, Any> for &'a str {
         &self,
         buf: &mut <Any as Database>::ArgumentBuffer,
     ) -> Result<IsNull, BoxDynError> {
-        (*self).encode(buf)
+        <&str as Encode<'_, Any>>::encode(*self, buf)
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::any::{Any, AnyTypeInfo, AnyTypeInfoKind};
+use crate::any::{Any, AnyCustomValue, AnyTypeInfo, AnyTypeInfoKind};
 use crate::database::Database;
 use crate::error::BoxDynError;
 use crate::types::Type;
@@ -19,6 +19,7 @@ pub enum AnyValueKind {
     Text(Arc<String>),
     TextSlice(Arc<str>),
     Blob(Arc<Vec<u8>>),
+    Custom(AnyCustomValue),
 }
 
 impl AnyValueKind {
@@ -35,6 +36,7 @@ impl AnyValueKind {
                 AnyValueKind::Text(_) => AnyTypeInfoKind::Text,
                 AnyValueKind::TextSlice(_) => AnyTypeInfoKind::Text,
                 AnyValueKind::Blob(_) => AnyTypeInfoKind::Blob,
+                AnyValueKind::Custom(custom) => AnyTypeInfoKind::Custom(custom.name()),
             },
         }
     }
@@ -0,0 +1,370 @@
+//! Audit logging of the statements executed through an [`Executor`].
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::database::Database;
+use crate::describe::Describe;
+use crate::error::{BoxDynError, Error};
+use crate::executor::{Execute, Executor, ResultLimits};
+use crate::logger::{sanitize_statement, QueryLogOptions};
+use crate::sql_str::SqlStr;
+
+/// A statement about to be executed by an [`AuditedExecutor`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AuditStatement {
+    /// The user on whose behalf the statement is executed, as set with
+    /// [`AuditedExecutor::user_id()`].
+    pub user_id: Option<String>,
+    /// The statement, with its literals replaced as by [`sanitize_statement()`]. The values
+    /// bound to it are not included.
+    pub summary: String,
+    /// The name of the query, if it has [one][crate::query::Query::log_as].
+    pub query_name: Option<&'static str>,
+}
+
+/// How a statement executed by an [`AuditedExecutor`] ended.
+pub enum AuditOutcome<'a, DB: Database> {
+    /// The statement succeeded.
+    Success {
+        /// The combined result of the statement, such as the number of rows it affected.
+        result: &'a DB::QueryResult,
+        /// The number of rows the statement returned.
+        rows_returned: u64,
+    },
+    /// The statement failed with this error.
+    Failure(&'a Error),
+}
+
+impl<DB: Database> Debug for AuditOutcome<'_, DB>
+where
+    DB::QueryResult: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditOutcome::Success {
+                result,
+                rows_returned,
+            } => f
+                .debug_struct("Success")
+                .field("result", result)
+                .field("rows_returned", rows_returned)
+                .finish(),
+            AuditOutcome::Failure(error) => f.debug_tuple("Failure").field(error).finish(),
+        }
+    }
+}
+
+/// A hook which an [`AuditedExecutor`] calls around each statement it executes.
+///
+/// The executor waits for the hook before executing the statement, and before returning
+/// its outcome, so the hook may write to an audit log which must be complete.
+pub trait AuditHook<DB: Database>: Send + Sync {
+    /// Called before `statement` is executed.
+    fn before<'a>(&'a self, statement: &'a AuditStatement) -> BoxFuture<'a, ()> {
+        let _ = statement;
+        Box::pin(std::future::ready(()))
+    }
+
+    /// Called once `statement` succeeded or failed.
+    ///
+    /// Not called if the statement is cancelled, by dropping the stream of its results
+    /// before it ends.
+    fn after<'a>(
+        &'a self,
+        statement: &'a AuditStatement,
+        outcome: AuditOutcome<'a, DB>,
+    ) -> BoxFuture<'a, ()>;
+}
+
+impl<DB: Database, H: AuditHook<DB> + ?Sized> AuditHook<DB> for &'_ H {
+    fn before<'a>(&'a self, statement: &'a AuditStatement) -> BoxFuture<'a, ()> {
+        (**self).before(statement)
+    }
+
+    fn after<'a>(
+        &'a self,
+        statement: &'a AuditStatement,
+        outcome: AuditOutcome<'a, DB>,
+    ) -> BoxFuture<'a, ()> {
+        (**self).after(statement, outcome)
+    }
+}
+
+impl<DB: Database, H: AuditHook<DB> + ?Sized> AuditHook<DB> for Arc<H> {
+    fn before<'a>(&'a self, statement: &'a AuditStatement) -> BoxFuture<'a, ()> {
+        (**self).before(statement)
+    }
+
+    fn after<'a>(
+        &'a self,
+        statement: &'a AuditStatement,
+        outcome: AuditOutcome<'a, DB>,
+    ) -> BoxFuture<'a, ()> {
+        (**self).after(statement, outcome)
+    }
+}
+
+/// An [`Executor`] which calls an [`AuditHook`] before and after each statement executed
+/// through it, and otherwise behaves like the executor it wraps.
+///
+/// Statements which are only prepared or described are not audited.
+///
+/// ```rust,no_run
+/// use futures_util::future::BoxFuture;
+/// use sqlx::audit::{AuditHook, AuditOutcome, AuditStatement, AuditedExecutor};
+/// use sqlx::postgres::{PgPool, Postgres};
+///
+/// struct AuditLog;
+///
+/// impl AuditHook<Postgres> for AuditLog {
+///     fn after<'a>(
+///         &'a self,
+///         statement: &'a AuditStatement,
+///         outcome: AuditOutcome<'a, Postgres>,
+///     ) -> BoxFuture<'a, ()> {
+///         Box::pin(async move {
+///             match outcome {
+///                 AuditOutcome::Success { result, .. } => println!(
+///                     "{:?} ran {} ({} rows affected)",
+///                     statement.user_id,
+///                     statement.summary,
+///                     result.rows_affected(),
+///                 ),
+///                 AuditOutcome::Failure(error) => println!(
+///                     "{:?} failed to run {}: {error}",
+///                     statement.user_id, statement.summary,
+///                 ),
+///             }
+///         })
+///     }
+/// }
+///
+/// # async fn example(pool: PgPool) -> sqlx::Result<()> {
+/// sqlx::query("DELETE FROM sessions WHERE user_id = $1")
+///     .bind(42_i64)
+///     .execute(AuditedExecutor::new(&pool, &AuditLog).user_id("42"))
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AuditedExecutor<E, H> {
+    executor: E,
+    hook: H,
+    user_id: Option<String>,
+}
+
+impl<E, H> AuditedExecutor<E, H> {
+    /// Wrap `executor`, calling `hook` around each statement executed through it.
+    pub fn new(executor: E, hook: H) -> Self {
+        AuditedExecutor {
+            executor,
+            hook,
+            user_id: None,
+        }
+    }
+
+    /// Set the user on whose behalf statements are executed, passed to the hook.
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_id = Some(user_id.into());
+        self
+    }
+
+    /// Returns the wrapped executor.
+    pub fn into_inner(self) -> E {
+        self.executor
+    }
+}
+
+impl<E: Debug, H> Debug for AuditedExecutor<E, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditedExecutor")
+            .field("executor", &self.executor)
+            .field("user_id", &self.user_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'c, E, H> Executor<'c> for AuditedExecutor<E, H>
+where
+    E: Executor<'c> + 'c,
+    H: AuditHook<E::Database> + 'c,
+    <E::Database as Database>::QueryResult: Clone,
+{
+    type Database = E::Database;
+
+    fn fetch_many<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxStream<
+        'e,
+        Result<
+            Either<<Self::Database as Database>::QueryResult, <Self::Database as Database>::Row>,
+            Error,
+        >,
+    >
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, Self::Database>,
+    {
+        let AuditedExecutor {
+            executor,
+            hook,
+            user_id,
+        } = self;
+
+        let query = AuditedQuery::new(query);
+        let statement = AuditStatement {
+            user_id,
+            summary: sanitize_statement(query.sql.as_str()),
+            query_name: query.log_options.name,
+        };
+
+        Box::pin(try_stream! {
+            hook.before(&statement).await;
+
+            let mut s = executor.fetch_many(query);
+            let mut result = <Self::Database as Database>::QueryResult::default();
+            let mut rows_returned = 0;
+
+            loop {
+                match s.try_next().await {
+                    Ok(Some(Either::Left(done))) => {
+                        result.extend([done.clone()]);
+                        r#yield!(Either::Left(done));
+                    }
+                    Ok(Some(Either::Right(row))) => {
+                        rows_returned += 1;
+                        r#yield!(Either::Right(row));
+                    }
+                    Ok(None) => break,
+                    Err(error) => {
+                        hook.after(&statement, AuditOutcome::Failure(&error)).await;
+                        return Err(error);
+                    }
+                }
+            }
+
+            hook.after(
+                &statement,
+                AuditOutcome::Success {
+                    result: &result,
+                    rows_returned,
+                },
+            )
+            .await;
+
+            Ok(())
+        })
+    }
+
+    fn fetch_optional<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<<Self::Database as Database>::Row>, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, Self::Database>,
+    {
+        let mut s = self.fetch_many(query);
+
+        // The statement runs to completion, so its outcome is known to the hook.
+        Box::pin(async move {
+            let mut ret = None;
+            while let Some(result) = s.try_next().await? {
+                match result {
+                    Either::Right(r) if ret.is_none() => ret = Some(r),
+                    _ => {}
+                }
+            }
+            Ok(ret)
+        })
+    }
+
+    fn prepare_with<'e>(
+        self,
+        sql: SqlStr,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<<Self::Database as Database>::Statement, Error>>
+    where
+        'c: 'e,
+    {
+        self.executor.prepare_with(sql, parameters)
+    }
+
+    fn describe<'e>(self, sql: SqlStr) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
+    where
+        'c: 'e,
+    {
+        self.executor.describe(sql)
+    }
+}
+
+/// A query taken apart so that its SQL is known before it is executed.
+struct AuditedQuery<DB: Database> {
+    sql: SqlStr,
+    statement: Option<DB::Statement>,
+    arguments: Option<Result<Option<DB::Arguments>, BoxDynError>>,
+    persistent: bool,
+    timeout: Option<Duration>,
+    log_options: QueryLogOptions,
+    result_limits: ResultLimits,
+}
+
+impl<DB: Database> AuditedQuery<DB> {
+    fn new<'q, Q: Execute<'q, DB>>(mut query: Q) -> Self {
+        let statement = query.statement().cloned();
+        let arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let timeout = query.timeout();
+        let log_options = query.log_options();
+        let result_limits = query.result_limits();
+
+        AuditedQuery {
+            sql: query.sql(),
+            statement,
+            arguments: Some(arguments),
+            persistent,
+            timeout,
+            log_options,
+            result_limits,
+        }
+    }
+}
+
+impl<DB: Database> Execute<'_, DB> for AuditedQuery<DB> {
+    fn sql(self) -> SqlStr {
+        self.sql
+    }
+
+    fn statement(&self) -> Option<&DB::Statement> {
+        self.statement.as_ref()
+    }
+
+    fn take_arguments(&mut self) -> Result<Option<DB::Arguments>, BoxDynError> {
+        self.arguments.take().unwrap_or(Ok(None))
+    }
+
+    fn persistent(&self) -> bool {
+        self.persistent
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    fn log_options(&self) -> QueryLogOptions {
+        self.log_options
+    }
+
+    fn result_limits(&self) -> ResultLimits {
+        self.result_limits
+    }
+}
@@ -0,0 +1,435 @@
+//! Blocking wrappers of connections and pools, for code which is not async, such as
+//! command-line tools and build scripts.
+//!
+//! The wrappers run the async drivers on a runtime of their own, so the same drivers, queries
+//! and types can be used without setting up an async runtime:
+//!
+//! ```rust,no_run
+//! # fn example() -> sqlx::Result<()> {
+//! use sqlx::blocking::Pool;
+//! use sqlx::Postgres;
+//!
+//! let pool = Pool::<Postgres>::connect("postgres://localhost/app")?;
+//!
+//! pool.execute(sqlx::query("UPDATE jobs SET state = 'done' WHERE id = $1").bind(7_i64))?;
+//!
+//! // queries with typed results are run with `.run()`
+//! let names: Vec<String> = pool.run(|pool| {
+//!     Box::pin(sqlx::query_scalar("SELECT name FROM jobs").fetch_all(pool))
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With Tokio, the runtime is driven by a background thread shared by a pool and its
+//! connections. With the other runtimes, the calls block on the future directly.
+//!
+//! The wrappers must not be used from within an async runtime: blocking the thread of an
+//! async task would stall every other task scheduled on it, and Tokio panics instead.
+
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cfg_if::cfg_if;
+use futures_core::future::BoxFuture;
+
+use crate::connection::{ConnectOptions, Connection as _};
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::pool::{self, PoolConnection, PoolOptions};
+
+/// The runtime running the drivers for the blocking wrappers which share it.
+struct Runtime {
+    #[cfg(feature = "_rt-tokio")]
+    handle: tokio::runtime::Handle,
+    // dropped with the last wrapper, which stops the thread driving the runtime
+    #[cfg(feature = "_rt-tokio")]
+    _shutdown: tokio::sync::oneshot::Sender<()>,
+}
+
+impl Runtime {
+    fn start() -> Result<Arc<Self>, Error> {
+        #[cfg(feature = "_rt-tokio")]
+        {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()?;
+
+            let handle = runtime.handle().clone();
+            let (shutdown, stopped) = tokio::sync::oneshot::channel::<()>();
+
+            // `Handle::block_on()` does not drive I/O, timers or spawned tasks, such as those
+            // of a pool, on a current-thread runtime; this thread does, until it is shut down
+            std::thread::Builder::new()
+                .name("sqlx-blocking".into())
+                .spawn(move || {
+                    let _ = runtime.block_on(stopped);
+                })?;
+
+            Ok(Arc::new(Runtime {
+                handle,
+                _shutdown: shutdown,
+            }))
+        }
+
+        #[cfg(not(feature = "_rt-tokio"))]
+        Ok(Arc::new(Runtime {}))
+    }
+
+    #[track_caller]
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        cfg_if! {
+            if #[cfg(feature = "_rt-tokio")] {
+                self.handle.block_on(future)
+            } else if #[cfg(feature = "_rt-async-io")] {
+                async_io::block_on(future)
+            } else {
+                crate::rt::missing_rt(future)
+            }
+        }
+    }
+
+    /// Drop `value` in the context of the runtime, as its destructor may spawn tasks.
+    fn drop_in_context<T>(&self, value: T) {
+        #[cfg(feature = "_rt-tokio")]
+        let _guard = self.handle.enter();
+
+        drop(value);
+    }
+}
+
+enum Inner<DB: Database> {
+    Direct(DB::Connection),
+    Pooled(PoolConnection<DB>),
+}
+
+/// A blocking connection to the database, either opened directly or acquired from a
+/// blocking [`Pool`].
+///
+/// See the [module documentation][self] for details.
+///
+/// # Panics
+///
+/// With Tokio, its methods panic if called from within an async runtime.
+pub struct Connection<DB: Database> {
+    // only `None` while it is being closed or dropped
+    inner: Option<Inner<DB>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<DB: Database> Connection<DB> {
+    /// Establish a new connection to the database at `url`.
+    pub fn connect(url: &str) -> Result<Self, Error> {
+        let runtime = Runtime::start()?;
+        let conn = runtime.block_on(DB::Connection::connect(url))?;
+
+        Ok(Connection {
+            inner: Some(Inner::Direct(conn)),
+            runtime,
+        })
+    }
+
+    /// Establish a new connection to the database with the given options.
+    pub fn connect_with(
+        options: &<DB::Connection as crate::connection::Connection>::Options,
+    ) -> Result<Self, Error> {
+        let runtime = Runtime::start()?;
+        let conn = runtime.block_on(options.connect())?;
+
+        Ok(Connection {
+            inner: Some(Inner::Direct(conn)),
+            runtime,
+        })
+    }
+
+    fn raw(&mut self) -> &mut DB::Connection {
+        match self
+            .inner
+            .as_mut()
+            .expect("BUG: inner connection already taken")
+        {
+            Inner::Direct(conn) => conn,
+            Inner::Pooled(conn) => conn,
+        }
+    }
+
+    /// Execute `query` and return the total number of rows affected.
+    pub fn execute<'q, E>(&mut self, query: E) -> Result<DB::QueryResult, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let runtime = Arc::clone(&self.runtime);
+        runtime.block_on(self.raw().execute(query))
+    }
+
+    /// Execute `query` and return all the resulting rows.
+    pub fn fetch_all<'q, E>(&mut self, query: E) -> Result<Vec<DB::Row>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let runtime = Arc::clone(&self.runtime);
+        runtime.block_on(self.raw().fetch_all(query))
+    }
+
+    /// Execute `query`, returning the first row or [`Error::RowNotFound`] otherwise.
+    pub fn fetch_one<'q, E>(&mut self, query: E) -> Result<DB::Row, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let runtime = Arc::clone(&self.runtime);
+        runtime.block_on(self.raw().fetch_one(query))
+    }
+
+    /// Execute `query`, returning the first row or [`None`] otherwise.
+    pub fn fetch_optional<'q, E>(&mut self, query: E) -> Result<Option<DB::Row>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let runtime = Arc::clone(&self.runtime);
+        runtime.block_on(self.raw().fetch_optional(query))
+    }
+
+    /// Run the future returned by `callback` with the async connection, and return its
+    /// output.
+    ///
+    /// This is how to use the rest of the API, such as `query_as()` or transactions:
+    ///
+    /// ```rust,no_run
+    /// # fn example(mut conn: sqlx::blocking::Connection<sqlx::Postgres>) -> sqlx::Result<()> {
+    /// let (id, name): (i64, String) = conn.run(|conn| {
+    ///     Box::pin(sqlx::query_as("SELECT id, name FROM users LIMIT 1").fetch_one(conn))
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run<F, R>(&mut self, callback: F) -> R
+    where
+        for<'c> F: FnOnce(&'c mut DB::Connection) -> BoxFuture<'c, R>,
+    {
+        let runtime = Arc::clone(&self.runtime);
+        runtime.block_on(callback(self.raw()))
+    }
+
+    /// Checks if the connection to the database is still valid, returning the round-trip
+    /// time.
+    pub fn ping(&mut self) -> Result<Duration, Error> {
+        let runtime = Arc::clone(&self.runtime);
+        runtime.block_on(self.raw().ping())
+    }
+
+    /// Explicitly close the connection, or if it was acquired from a pool, close it
+    /// instead of returning it to the pool.
+    pub fn close(mut self) -> Result<(), Error> {
+        match self
+            .inner
+            .take()
+            .expect("BUG: inner connection already taken")
+        {
+            Inner::Direct(conn) => self.runtime.block_on(conn.close()),
+            Inner::Pooled(conn) => self.runtime.block_on(conn.close()),
+        }
+    }
+}
+
+impl<DB: Database> Drop for Connection<DB> {
+    fn drop(&mut self) {
+        // a pooled connection spawns a task to return itself to the pool
+        self.runtime.drop_in_context(self.inner.take());
+    }
+}
+
+/// A blocking pool of connections to the database.
+///
+/// Cloning it is cheap: clones share the same pool. See the [module documentation][self]
+/// for details.
+///
+/// # Panics
+///
+/// With Tokio, its methods panic if called from within an async runtime, like those of
+/// [`Connection`].
+pub struct Pool<DB: Database> {
+    // only `None` while it is being dropped
+    pool: Option<pool::Pool<DB>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<DB: Database> Pool<DB> {
+    /// Create a new pool with the default options, and immediately establish one
+    /// connection to the database at `url`.
+    pub fn connect(url: &str) -> Result<Self, Error> {
+        let runtime = Runtime::start()?;
+        let pool = runtime.block_on(pool::Pool::connect(url))?;
+
+        Ok(Pool {
+            pool: Some(pool),
+            runtime,
+        })
+    }
+
+    /// Create a new pool with the default options, and immediately establish one
+    /// connection with the given options.
+    pub fn connect_with(
+        options: <DB::Connection as crate::connection::Connection>::Options,
+    ) -> Result<Self, Error> {
+        Self::connect_with_pool_options(PoolOptions::new(), options)
+    }
+
+    /// Create a new pool with `pool_options`, and immediately establish the connections it
+    /// requires with `options`.
+    pub fn connect_with_pool_options(
+        pool_options: PoolOptions<DB>,
+        options: <DB::Connection as crate::connection::Connection>::Options,
+    ) -> Result<Self, Error> {
+        let runtime = Runtime::start()?;
+        let pool = runtime.block_on(pool_options.connect_with(options))?;
+
+        Ok(Pool {
+            pool: Some(pool),
+            runtime,
+        })
+    }
+
+    fn inner(&self) -> &pool::Pool<DB> {
+        self.pool.as_ref().expect("BUG: inner pool already taken")
+    }
+
+    /// Retrieve a connection from the pool, waiting for one to become available if needed.
+    ///
+    /// The connection is returned to the pool when it is dropped.
+    pub fn acquire(&self) -> Result<Connection<DB>, Error> {
+        let conn = self.runtime.block_on(self.inner().acquire())?;
+
+        Ok(Connection {
+            inner: Some(Inner::Pooled(conn)),
+            runtime: Arc::clone(&self.runtime),
+        })
+    }
+
+    /// Execute `query` on a connection of the pool and return the total number of rows
+    /// affected.
+    pub fn execute<'q, E>(&self, query: E) -> Result<DB::QueryResult, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.runtime.block_on(self.inner().execute(query))
+    }
+
+    /// Execute `query` on a connection of the pool and return all the resulting rows.
+    pub fn fetch_all<'q, E>(&self, query: E) -> Result<Vec<DB::Row>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.runtime.block_on(self.inner().fetch_all(query))
+    }
+
+    /// Execute `query` on a connection of the pool, returning the first row or
+    /// [`Error::RowNotFound`] otherwise.
+    pub fn fetch_one<'q, E>(&self, query: E) -> Result<DB::Row, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.runtime.block_on(self.inner().fetch_one(query))
+    }
+
+    /// Execute `query` on a connection of the pool, returning the first row or [`None`]
+    /// otherwise.
+    pub fn fetch_optional<'q, E>(&self, query: E) -> Result<Option<DB::Row>, Error>
+    where
+        E: 'q + Execute<'q, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.runtime.block_on(self.inner().fetch_optional(query))
+    }
+
+    /// Run the future returned by `callback` with the async pool, and return its output.
+    ///
+    /// See [`Connection::run()`].
+    pub fn run<F, R>(&self, callback: F) -> R
+    where
+        for<'p> F: FnOnce(&'p pool::Pool<DB>) -> BoxFuture<'p, R>,
+    {
+        self.runtime.block_on(callback(self.inner()))
+    }
+
+    /// Shut down the pool, waiting for all connections to be returned to it and closed.
+    ///
+    /// See [`pool::Pool::close()`].
+    pub fn close(&self) {
+        self.runtime.block_on(self.inner().close());
+    }
+
+    /// Returns the number of connections currently active, including idle connections.
+    pub fn size(&self) -> u32 {
+        self.inner().size()
+    }
+
+    /// Returns the number of connections active and idle (not in use).
+    pub fn num_idle(&self) -> usize {
+        self.inner().num_idle()
+    }
+}
+
+impl<DB: Database> Clone for Pool<DB> {
+    fn clone(&self) -> Self {
+        Pool {
+            pool: self.pool.clone(),
+            runtime: Arc::clone(&self.runtime),
+        }
+    }
+}
+
+impl<DB: Database> Drop for Pool<DB> {
+    fn drop(&mut self) {
+        self.runtime.drop_in_context(self.pool.take());
+    }
+}
+
+impl<DB: Database> Debug for Pool<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Pool").field(self.inner()).finish()
+    }
+}
+
+#[cfg(all(test, feature = "_rt-tokio"))]
+mod tests {
+    use super::Runtime;
+    use std::time::Duration;
+
+    #[test]
+    fn runtime_drives_tasks_and_timers() {
+        let runtime = Runtime::start().unwrap();
+
+        // the task and its timer only make progress on the driver thread
+        let output = runtime.block_on(async {
+            let task = tokio::spawn(async {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+                7
+            });
+
+            task.await.unwrap()
+        });
+
+        assert_eq!(output, 7);
+    }
+
+    #[test]
+    #[should_panic(expected = "from within a runtime")]
+    fn block_on_within_a_runtime_panics() {
+        let runtime = Runtime::start().unwrap();
+
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(async { runtime.block_on(async {}) });
+    }
+}
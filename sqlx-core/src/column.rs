@@ -34,6 +34,37 @@ pub trait Column: 'static + Send + Sync + Debug {
     fn origin(&self) -> ColumnOrigin {
         ColumnOrigin::Unknown
     }
+
+    /// Returns whether the column may contain `NULL`, if known.
+    ///
+    /// For a column which comes from a table, this is whether the table column allows `NULL`;
+    /// an outer join may still produce `NULL`s in a column which does not.
+    ///
+    /// Returns `None` if the database driver does not have that information,
+    /// or has not overridden this method.
+    fn nullable(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns the maximum length of the column, in characters, if it has a character type
+    /// declared with one, such as `VARCHAR(N)`.
+    fn max_length(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns the total number of significant digits of the column, if it has a numeric type
+    /// declared with a precision, such as `NUMERIC(P, S)`.
+    fn precision(&self) -> Option<u32> {
+        None
+    }
+
+    /// Returns the number of digits after the decimal point of the column, if it has a numeric
+    /// type declared with a precision, such as `NUMERIC(P, S)`.
+    ///
+    /// May be negative, if the database supports rounding to the left of the decimal point.
+    fn scale(&self) -> Option<i32> {
+        None
+    }
 }
 
 /// A [`Column`] that originates from a table.
@@ -232,10 +232,11 @@ pub struct PreferredCrates {
     /// Specify the crate to use for mapping date/time types to Rust.
     ///
     /// The default behavior is to use whatever crate is enabled,
-    /// [`chrono`] or [`time`] (the latter takes precedent).
+    /// [`time`], [`chrono`] or [`jiff`] (in that order of precedence).
     ///
     /// [`chrono`]: crate::types::chrono
     /// [`time`]: crate::types::time
+    /// [`jiff`]: crate::types::jiff
     ///
     /// Example: Always Use Chrono
     /// -------
@@ -290,7 +291,7 @@ pub struct PreferredCrates {
     serde(rename_all = "snake_case")
 )]
 pub enum DateTimeCrate {
-    /// Use whichever crate is enabled (`time` then `chrono`).
+    /// Use whichever crate is enabled (`time`, then `chrono`, then `jiff`).
     #[default]
     Inferred,
 
@@ -309,6 +310,14 @@ pub enum DateTimeCrate {
     /// date-time = "time"
     /// ```
     Time,
+
+    /// Always use types from [`jiff`][crate::types::jiff].
+    ///
+    /// ```toml
+    /// [macros.preferred-crates]
+    /// date-time = "jiff"
+    /// ```
+    Jiff,
 }
 
 /// The preferred crate to use for mapping `NUMERIC` types to Rust.
@@ -391,6 +400,7 @@ impl DateTimeCrate {
             Self::Inferred => None,
             Self::Chrono => Some("chrono"),
             Self::Time => Some("time"),
+            Self::Jiff => Some("jiff"),
         }
     }
 }
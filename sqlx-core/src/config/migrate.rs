@@ -37,7 +37,8 @@ pub struct Config {
     ///
     /// May be schema-qualified and/or contain quotes. Defaults to `_sqlx_migrations`.
     ///
-    /// Potentially useful for multi-tenant databases.
+    /// Potentially useful for multi-tenant databases, or to let multiple independent applications
+    /// share one database without clobbering each other's migration history.
     ///
     /// ### Warning: Potential Data Loss or Corruption!
     /// Changing this option for a production database will likely result in data loss or corruption
@@ -114,10 +115,171 @@ pub struct Config {
     // Likely lower overhead for small sets than `HashSet`.
     pub ignored_chars: BTreeSet<char>,
 
+    /// Normalize migrations before hashing them, so that reformatting them
+    /// (and optionally, changing their comments) does not change their checksum.
+    ///
+    /// Unlike `ignored-chars`, whitespace inside string literals and quoted identifiers
+    /// is still significant.
+    ///
+    /// ### Warning: May Change Hashes for Existing Migrations
+    /// Changing this option will likely change the output of the hash.
+    ///
+    /// Run `sqlx migrate repair` (or [`Migrator::repair()`][crate::migrate::Migrator::repair])
+    /// against deployed databases to update the checksums of applied migrations.
+    ///
+    /// ### Example: Ignore Formatting and Comments
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// checksum-normalization = "comments"
+    /// ```
+    pub checksum_normalization: ChecksumNormalization,
+
+    /// Require every up migration to have a corresponding down migration.
+    ///
+    /// If set, `sqlx::migrate!()` and `sqlx-cli` will refuse to run or revert migrations
+    /// while any migration is simple (non-reversible) or is missing its `.down.sql` file,
+    /// and `sqlx migrate add` will create reversible migrations regardless of `defaults`.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// require-reversible = true
+    /// ```
+    pub require_reversible: bool,
+
+    /// Allow applying pending migrations older than the latest applied migration.
+    ///
+    /// By default, running migrations fails if such a migration is found, which typically
+    /// happens when migrations were added on separate branches with interleaved versions.
+    ///
+    /// ### Warning
+    /// Out-of-order migrations are applied against a schema that already includes the changes
+    /// of newer migrations. Make sure they don't depend on the schema as it was at their version.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// allow-out-of-order = true
+    /// ```
+    pub allow_out_of_order: bool,
+
+    /// Record who applied each migration in the migrations table.
+    ///
+    /// The `applied_by` column is set to `<user>@<hostname>`, and the `application` column
+    /// to the value of the `SQLX_MIGRATE_APPLICATION` environment variable, if set.
+    /// Existing migrations tables are upgraded automatically to add these columns.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// record-audit = true
+    /// ```
+    pub record_audit: bool,
+
+    /// Compress the migrations embedded by `sqlx::migrate!()`.
+    ///
+    /// By default, the SQL of every migration is embedded verbatim. If set, the SQL is
+    /// compressed at compile time, with identical files only stored once, and decompressed
+    /// the first time the migrations are used.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate]
+    /// compress-embedded = true
+    /// ```
+    pub compress_embedded: bool,
+
+    /// Specify how the database is locked while migrations are running.
+    pub lock: LockConfig,
+
     /// Specify default options for new migrations created with `sqlx migrate add`.
     pub defaults: MigrationDefaults,
 }
 
+#[derive(Debug, Default)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+pub struct LockConfig {
+    /// Specify the strategy used to lock the database while migrations are running.
+    ///
+    /// ### Example: Use a Lock Table
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate.lock]
+    /// strategy = "table"
+    /// ```
+    pub strategy: LockStrategy,
+
+    /// Specify whether to wait for or fail on a lock held by another migrator.
+    ///
+    /// ### Example: Fail Immediately
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate.lock]
+    /// policy = "fail"
+    /// ```
+    pub policy: LockPolicy,
+
+    /// Specify the maximum time, in seconds, to wait for a lock held by another migrator.
+    ///
+    /// Only used with `policy = "wait"`. Defaults to waiting indefinitely.
+    ///
+    /// ### Example
+    /// `sqlx.toml`:
+    /// ```toml
+    /// [migrate.lock]
+    /// timeout-secs = 30
+    /// ```
+    pub timeout_secs: Option<u64>,
+}
+
+/// The strategy used to lock the database while migrations are running.
+///
+/// See [`MigrationLock`][crate::migrate::MigrationLock] for details.
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LockStrategy {
+    /// Use a database-level advisory lock ([`AdvisoryLock`][crate::migrate::AdvisoryLock]).
+    #[default]
+    Advisory,
+
+    /// Insert a row into a dedicated lock table ([`TableLock`][crate::migrate::TableLock]).
+    Table,
+
+    /// Do not lock the database ([`NoLock`][crate::migrate::NoLock]).
+    None,
+}
+
+/// Whether to wait for or fail on a lock held by another migrator.
+///
+/// See [`LockPolicy`][crate::migrate::LockPolicy] for details.
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum LockPolicy {
+    /// Wait for the lock to be released, up to `timeout-secs` if set.
+    #[default]
+    Wait,
+
+    /// Fail immediately.
+    Fail,
+}
+
 #[derive(Debug, Default)]
 #[cfg_attr(
     feature = "sqlx-toml",
@@ -146,6 +308,27 @@ pub struct MigrationDefaults {
     pub migration_versioning: DefaultVersioning,
 }
 
+/// How migrations are normalized before they are hashed.
+///
+/// See [`ChecksumNormalization`][crate::migrate::ChecksumNormalization] for details.
+#[derive(Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ChecksumNormalization {
+    /// Hash migrations as-is.
+    #[default]
+    None,
+
+    /// Ignore changes to whitespace between tokens.
+    Whitespace,
+
+    /// Ignore changes to whitespace between tokens and to comments.
+    Comments,
+}
+
 /// The default type of migration that `sqlx migrate add` should create by default.
 #[derive(Debug, Default, PartialEq, Eq)]
 #[cfg_attr(
@@ -207,6 +390,11 @@ impl Config {
     pub fn to_resolve_config(&self) -> crate::migrate::ResolveConfig {
         let mut config = crate::migrate::ResolveConfig::new();
         config.ignore_chars(self.ignored_chars.iter().copied());
+        config.set_checksum_normalization(match self.checksum_normalization {
+            ChecksumNormalization::None => crate::migrate::ChecksumNormalization::None,
+            ChecksumNormalization::Whitespace => crate::migrate::ChecksumNormalization::Whitespace,
+            ChecksumNormalization::Comments => crate::migrate::ChecksumNormalization::Comments,
+        });
         config
     }
 }
@@ -100,6 +100,20 @@ fn assert_migrate_config(config: &config::migrate::Config) {
 
     assert_eq!(config.ignored_chars, ignored_chars);
 
+    assert_eq!(
+        config.checksum_normalization,
+        ChecksumNormalization::Comments
+    );
+
+    assert!(config.require_reversible);
+    assert!(config.allow_out_of_order);
+    assert!(config.record_audit);
+    assert!(config.compress_embedded);
+
+    assert_eq!(config.lock.strategy, LockStrategy::Table);
+    assert_eq!(config.lock.policy, LockPolicy::Fail);
+    assert_eq!(config.lock.timeout_secs, Some(30));
+
     assert_eq!(
         config.defaults.migration_type,
         DefaultMigrationType::Reversible
@@ -3,13 +3,16 @@ use crate::error::Error;
 
 use crate::config;
 use crate::sql_str::SqlSafeStr;
+use crate::statistics::StatementStatistics;
 use crate::transaction::{Transaction, TransactionManager};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
+use std::{cmp, io};
 use url::Url;
 
 /// Represents a single database connection.
@@ -43,7 +46,10 @@ pub trait Connection: Send {
     fn close_hard(self) -> impl Future<Output = Result<(), Error>> + Send + 'static;
 
     /// Checks if a connection to the database is still valid.
-    fn ping(&mut self) -> impl Future<Output = Result<(), Error>> + Send + '_;
+    ///
+    /// Returns the round-trip time of the check, which does not include waiting for the
+    /// results of statements still in flight on the connection.
+    fn ping(&mut self) -> impl Future<Output = Result<Duration, Error>> + Send + '_;
 
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
@@ -196,6 +202,14 @@ pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    /// The names of the types whose bind values are logged along with statements; values of
+    /// other types are redacted. If `None`, the default, bind values are never logged.
+    pub bind_value_types: Option<Arc<[String]>>,
+    /// Queries which take longer than this emit an event on the `sqlx::slow_query` target,
+    /// whatever the levels above are. If `None`, the default, no such event is emitted.
+    pub slow_query_threshold: Option<Duration>,
+    /// Where the execution statistics of statements are collected, if anywhere.
+    pub statistics: Option<StatementStatistics>,
 }
 
 impl Default for LogSettings {
@@ -204,6 +218,9 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            bind_value_types: None,
+            slow_query_threshold: None,
+            statistics: None,
         }
     }
 }
@@ -216,6 +233,135 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+    pub fn log_bind_values<I>(&mut self, types: I)
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.bind_value_types = Some(types.into_iter().map(Into::into).collect());
+    }
+    pub fn slow_query_threshold(&mut self, threshold: Duration) {
+        self.slow_query_threshold = Some(threshold);
+    }
+    pub fn collect_statistics(&mut self, max_statements: usize) {
+        self.statistics = Some(StatementStatistics::new(max_statements));
+    }
+}
+
+/// How [`ConnectOptions::connect()`] retries a connection which failed to open, as set with
+/// [`ConnectOptions::retry()`].
+///
+/// By default, connecting is attempted at most 5 times, waiting from 100ms up to 5s in between,
+/// and is retried only if the server could not be reached or is still starting up. Other
+/// errors, such as failing to authenticate, are returned right away.
+#[derive(Clone)]
+pub struct ConnectRetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retry_if: Option<Arc<dyn Fn(&Error) -> bool + Send + Sync>>,
+}
+
+impl Default for ConnectRetryPolicy {
+    fn default() -> Self {
+        ConnectRetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            retry_if: None,
+        }
+    }
+}
+
+impl ConnectRetryPolicy {
+    /// The default policy; see the type documentation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempt to connect at most `max_attempts` times, including the first.
+    ///
+    /// The error of the last attempt is returned once they run out. `0` is treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Wait `initial` before the first retry, then twice as long before each of the next,
+    /// up to `max`.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Retry the errors for which `retry_if` returns `true`, instead of only those which show
+    /// the server could not be reached or is still starting up.
+    pub fn retry_if(mut self, retry_if: impl Fn(&Error) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_if = Some(Arc::new(retry_if));
+        self
+    }
+
+    /// Call `connect` until it succeeds, fails with an error which is not retried, or the
+    /// attempts run out.
+    #[doc(hidden)]
+    pub async fn retry_connect<F, Fut, C>(&self, mut connect: F) -> Result<C, Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<C, Error>>,
+    {
+        let mut attempt = 1;
+        let mut backoff = self.initial_backoff;
+
+        loop {
+            match connect().await {
+                Err(err) if attempt < self.max_attempts && self.is_retryable(&err) => {
+                    tracing::debug!(attempt, ?backoff, "retrying connection: {err}");
+
+                    crate::rt::sleep(backoff).await;
+
+                    attempt += 1;
+                    backoff = cmp::min(backoff * 2, self.max_backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn is_retryable(&self, error: &Error) -> bool {
+        match &self.retry_if {
+            Some(retry_if) => retry_if(error),
+            None => Self::is_transient(error),
+        }
+    }
+
+    // the server could not be reached (yet), or is not accepting connections (yet)
+    fn is_transient(error: &Error) -> bool {
+        match error {
+            Error::Database(e) => e.is_transient_in_connect_phase(),
+            // failing to resolve the host name has no specific kind, so only errors which
+            // will certainly happen again are not retried
+            Error::Io(e) => !matches!(
+                e.kind(),
+                io::ErrorKind::InvalidInput
+                    | io::ErrorKind::InvalidData
+                    | io::ErrorKind::PermissionDenied
+                    | io::ErrorKind::Unsupported
+            ),
+            _ => false,
+        }
+    }
+}
+
+impl Debug for ConnectRetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectRetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("retry_if", &self.retry_if.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -265,6 +411,62 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
+    /// Emit a `WARN` event on the `sqlx::slow_query` target for each query which takes longer
+    /// than `threshold`.
+    ///
+    /// Unlike [`log_slow_statements()`][Self::log_slow_statements], this is independent of
+    /// statement logging, which may be turned off or filtered out by the `sqlx::query` target.
+    /// The event has the fields:
+    ///
+    /// * `summary`: the first few words of the query, or its name if it has one.
+    /// * `db.statement`: the full query.
+    /// * `db.query_name`: the name of the query, if it has one.
+    /// * `rows_affected` and `rows_returned`.
+    /// * `elapsed_secs`: how long the query took, in seconds.
+    /// * `slow_threshold_secs`: the threshold, in seconds.
+    /// * `connection_id`: an identifier of the connection, if the database has one, such as
+    ///   the process ID of the backend in Postgres.
+    ///
+    /// Set on the options a pool is created with, this applies to all of its connections.
+    fn slow_query_threshold(self, threshold: Duration) -> Self;
+
+    /// Collect the execution statistics of at most `max_statements` statements, shared by
+    /// all connections opened with these options, such as those of a pool.
+    ///
+    /// See [`StatementStatistics`] for details.
+    fn collect_statistics(self, max_statements: usize) -> Self;
+
+    /// The statistics collected by connections opened with these options, if enabled with
+    /// [`collect_statistics()`][Self::collect_statistics].
+    fn statistics(&self) -> Option<&StatementStatistics>;
+
+    /// Retry [`connect()`][Self::connect] as set by `policy` if it fails because the server
+    /// could not be reached or is still starting up, such as when the application starts
+    /// before the database.
+    ///
+    /// Any other error, such as failing to authenticate, is returned right away unless
+    /// [`ConnectRetryPolicy::retry_if()`] says otherwise. By default, connecting is attempted
+    /// once.
+    ///
+    /// The default implementation ignores `policy`, for options which cannot retry.
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::connection::{ConnectOptions, ConnectRetryPolicy};
+    /// # use std::time::Duration;
+    /// # fn f<O: ConnectOptions>(options: O) -> O {
+    /// // try for about a minute
+    /// options.retry(
+    ///     ConnectRetryPolicy::new()
+    ///         .max_attempts(20)
+    ///         .backoff(Duration::from_millis(100), Duration::from_secs(5)),
+    /// )
+    /// # }
+    /// ```
+    fn retry(self, policy: ConnectRetryPolicy) -> Self {
+        let _ = policy;
+        self
+    }
+
     /// Entirely disables statement logging (both slow and regular).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
@@ -279,3 +481,20 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
         Ok(self)
     }
 }
+
+#[test]
+fn retryable_connect_errors() {
+    let policy = ConnectRetryPolicy::new();
+    let retryable = |kind: io::ErrorKind| policy.is_retryable(&Error::Io(kind.into()));
+
+    assert!(retryable(io::ErrorKind::ConnectionRefused));
+    assert!(retryable(io::ErrorKind::NotFound));
+    assert!(retryable(io::ErrorKind::TimedOut));
+    assert!(!retryable(io::ErrorKind::PermissionDenied));
+
+    assert!(!policy.is_retryable(&Error::Configuration("invalid".into())));
+
+    let policy = policy.retry_if(|e| matches!(e, Error::Configuration(_)));
+    assert!(policy.is_retryable(&Error::Configuration("invalid".into())));
+    assert!(!policy.is_retryable(&Error::Io(io::ErrorKind::ConnectionRefused.into())));
+}
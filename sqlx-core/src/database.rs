@@ -94,6 +94,12 @@ pub trait Database: 'static + Sized + Send + Debug {
 
     /// The schemes for database URLs that should match this driver.
     const URL_SCHEMES: &'static [&'static str];
+
+    /// The maximum number of bind arguments the database accepts in a single query.
+    ///
+    /// Defaults to `u16::MAX`, for protocols which count the arguments in 16 bits;
+    /// drivers with a different limit override it.
+    const MAX_ARGUMENTS: usize = u16::MAX as usize;
 }
 
 /// A [`Database`] that maintains a client-side cache of prepared statements.
@@ -62,6 +62,20 @@ use crate::value::ValueRef;
 ///     }
 /// }
 /// ```
+///
+/// ## Zero-copy decoding
+///
+/// Types which borrow with the lifetime `'r` (e.g. `&'r str` and `&'r [u8]`) are decoded without
+/// copying, by borrowing directly from the buffer of the row. Prefer these for values which are
+/// only inspected or forwarded, to avoid an allocation per row on hot read paths.
+///
+/// See the `types` module of each database for which types can be decoded without copying,
+/// and any restrictions (e.g. on the format the value is sent in).
+///
+/// `Cow<'_, T>` is always decoded as [`Cow::Owned`], because its lifetime is not tied to
+/// the row: otherwise, e.g. a `Cow<'static, str>` field of a [`FromRow`][crate::from_row::FromRow]
+/// struct could never be decoded. Decode `&'r T` and convert it instead when borrowing is
+/// possible.
 pub trait Decode<'r, DB: Database>: Sized {
     /// Decode a new value of this type using a raw value from the database.
     fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError>;
@@ -134,7 +148,7 @@ where
 {
     fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
         // See https://github.com/launchbadge/sqlx/pull/3674#discussion_r2008611502 for more info
-        // about why decoding to a `Cow::Owned` was chosen.
+        // about why decoding to a `Cow::Owned` was chosen, and the trait docs for alternatives.
         <<T as ToOwned>::Owned as Decode<DB>>::decode(value).map(Cow::Owned)
     }
 }
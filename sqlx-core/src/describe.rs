@@ -7,7 +7,25 @@ use std::convert::identity;
 /// Returned from [`Executor::describe`].
 ///
 /// The query macros (e.g., `query!`, `query_as!`, etc.) use the information here to validate
-/// output and parameter types; and, generate an anonymous record.
+/// output and parameter types; and, generate an anonymous record. It is also available to
+/// external tooling, such as code generators, which want to analyze queries the same way the
+/// macros do.
+///
+/// ```rust,ignore
+/// use sqlx::{Column, Executor, SqlSafeStr};
+///
+/// let describe = conn
+///     .describe("SELECT id, name FROM users WHERE id = $1".into_sql_str())
+///     .await?;
+///
+/// for (i, column) in describe.columns().iter().enumerate() {
+///     // `None` if the nullability could not be inferred; the macros assume `true`
+///     let nullable = describe.nullable(i).unwrap_or(true);
+///     println!("{}: {} (nullable: {nullable})", column.name(), column.type_info());
+/// }
+/// ```
+///
+/// [`Executor::describe`]: crate::executor::Executor::describe
 #[derive(Debug)]
 #[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -17,10 +35,13 @@ use std::convert::identity;
         deserialize = "DB::TypeInfo: serde::de::DeserializeOwned, DB::Column: serde::de::DeserializeOwned",
     ))
 )]
-#[doc(hidden)]
 pub struct Describe<DB: Database> {
+    // NOTE: these fields are semver-exempt, for use by drivers; use the accessors instead.
+    #[doc(hidden)]
     pub columns: Vec<DB::Column>,
+    #[doc(hidden)]
     pub parameters: Option<Either<Vec<DB::TypeInfo>, usize>>,
+    #[doc(hidden)]
     pub nullable: Vec<Option<bool>>,
 }
 
@@ -49,6 +70,14 @@ impl<DB: Database> Describe<DB> {
     }
 
     /// Gets whether a column may be `NULL`, if this information is available.
+    ///
+    /// This is the result of the same nullability inference the query macros use, which may
+    /// go further than [`Column::nullable()`]: **PostgreSQL**, for example, also inspects the
+    /// query plan to find columns made nullable by outer joins.
+    ///
+    /// Returns `None` if `column` is out of bounds.
+    ///
+    /// [`Column::nullable()`]: crate::column::Column::nullable
     pub fn nullable(&self, column: usize) -> Option<bool> {
         self.nullable.get(column).copied().and_then(identity)
     }
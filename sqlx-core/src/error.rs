@@ -26,6 +26,24 @@ pub type BoxDynError = Box<dyn StdError + 'static + Send + Sync>;
 #[error("unexpected null; try decoding as an `Option`")]
 pub struct UnexpectedNullError;
 
+/// The limit exceeded by the result of a query, in [`Error::ResultTooLarge`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResultLimit {
+    /// The most rows the query may return.
+    Rows(u64),
+    /// The most bytes of rows the query may return, as sent by the database.
+    Bytes(u64),
+}
+
+impl Display for ResultLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResultLimit::Rows(rows) => write!(f, "{rows} rows"),
+            ResultLimit::Bytes(bytes) => write!(f, "{bytes} bytes"),
+        }
+    }
+}
+
 /// Represents all the ways a method can fail within SQLx.
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -103,6 +121,25 @@ pub enum Error {
     #[error("pool timed out while waiting for an open connection")]
     PoolTimedOut,
 
+    /// A query did not complete within its [`timeout`](crate::query::Query::timeout).
+    ///
+    /// The query was cancelled on the server, and the connection can still be used.
+    #[error("query timed out and was cancelled")]
+    Timeout,
+
+    /// A query returned more rows, or more bytes of rows, than its [limits] allow.
+    ///
+    /// The rest of the result is discarded, and the connection can still be used.
+    ///
+    /// [limits]: crate::query::Query::max_rows
+    #[error("query result exceeded the limit of {0}")]
+    ResultTooLarge(ResultLimit),
+
+    /// A [`QueryInterceptor`](crate::interceptor::QueryInterceptor) rejected the statement,
+    /// so it was not executed.
+    #[error("statement rejected by an interceptor: {0}")]
+    Rejected(#[source] BoxDynError),
+
     /// [`Pool::close`] was called while we were waiting in [`Pool::acquire`].
     ///
     /// [`Pool::acquire`]: crate::pool::Pool::acquire
@@ -148,6 +185,62 @@ impl Error {
         }
     }
 
+    /// Returns `true` if the error is likely to go away if the operation is tried again,
+    /// possibly on a new connection.
+    ///
+    /// This covers database errors which are [transient][DatabaseError::is_transient], such as
+    /// serialization failures or the server restarting, lost connections, and timing out while
+    /// waiting for a connection from the pool. A [query timeout][Error::Timeout] is not
+    /// considered transient, as the same query is likely to time out again.
+    ///
+    /// Whether retrying is safe is up to the caller: if the connection was lost during a
+    /// `COMMIT` or outside of a transaction, the statement may or may not have taken effect.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Database(err) => err.is_transient(),
+            Error::Io(err) => matches!(
+                err.kind(),
+                io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::UnexpectedEof
+                    | io::ErrorKind::TimedOut
+            ),
+            Error::PoolTimedOut => true,
+            _ => false,
+        }
+    }
+
+    /// The kind of the error, as the `kind` label of the metrics of failed queries.
+    #[cfg(any(feature = "metrics", feature = "prometheus"))]
+    pub(crate) fn kind_label(&self) -> &'static str {
+        match self {
+            Error::Database(e) => match e.kind() {
+                ErrorKind::UniqueViolation => "unique_violation",
+                ErrorKind::ForeignKeyViolation => "foreign_key_violation",
+                ErrorKind::NotNullViolation => "not_null_violation",
+                ErrorKind::CheckViolation => "check_violation",
+                ErrorKind::ExclusionViolation => "exclusion_violation",
+                ErrorKind::SerializationFailure => "serialization_failure",
+                ErrorKind::DeadlockDetected => "deadlock_detected",
+                ErrorKind::InsufficientPrivilege => "insufficient_privilege",
+                ErrorKind::DiskFull => "disk_full",
+                ErrorKind::TooManyConnections => "too_many_connections",
+                ErrorKind::Other => "database",
+            },
+            Error::Io(_) => "io",
+            Error::Tls(_) => "tls",
+            Error::Protocol(_) => "protocol",
+            Error::Timeout => "timeout",
+            Error::ResultTooLarge(_) => "result_too_large",
+            Error::Rejected(_) => "rejected",
+            Error::Encode(_) => "encode",
+            Error::Decode(_) | Error::ColumnDecode { .. } => "decode",
+            _ => "other",
+        }
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn protocol(err: impl Display) -> Self {
@@ -191,7 +284,7 @@ pub fn mismatched_types<DB: Database, T: Type<DB>>(ty: &DB::TypeInfo) -> BoxDynE
 /// The error kind.
 ///
 /// This enum is to be used to identify frequent errors that can be handled by the program.
-/// Although it currently only supports constraint violations, the type may grow in the future.
+/// It currently covers constraint violations and transaction conflicts, and may grow in the future.
 #[derive(Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum ErrorKind {
@@ -205,6 +298,18 @@ pub enum ErrorKind {
     CheckViolation,
     /// Exclusion constraint violation.
     ExclusionViolation,
+    /// The transaction could not be serialized with concurrent transactions, and should be
+    /// retried.
+    SerializationFailure,
+    /// The transaction was aborted to break a deadlock with concurrent transactions, and should
+    /// be retried.
+    DeadlockDetected,
+    /// The current role lacks the privilege needed for the operation.
+    InsufficientPrivilege,
+    /// The database server ran out of disk space.
+    DiskFull,
+    /// The database server, or the role or database, has reached its limit on connections.
+    TooManyConnections,
     /// An unmapped error.
     Other,
 }
@@ -233,6 +338,15 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         false
     }
 
+    /// Returns `true` if the error is likely to go away if the operation is tried again.
+    ///
+    /// By default, this is the case for [transaction conflicts][Self::is_transaction_conflict]
+    /// and [too many connections][ErrorKind::TooManyConnections]; drivers may recognize more,
+    /// such as the server shutting down.
+    fn is_transient(&self) -> bool {
+        self.is_transaction_conflict() || matches!(self.kind(), ErrorKind::TooManyConnections)
+    }
+
     /// Returns the name of the constraint that triggered the error, if applicable.
     /// If the error was caused by a conflict of a unique index, this will be the index name.
     ///
@@ -242,6 +356,15 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         None
     }
 
+    /// Returns the name of the schema of the object that was affected by the error,
+    /// if applicable.
+    ///
+    /// ### Note
+    /// Currently only populated by the Postgres driver.
+    fn schema(&self) -> Option<&str> {
+        None
+    }
+
     /// Returns the name of the table that was affected by the error, if applicable.
     ///
     /// ### Note
@@ -250,6 +373,15 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         None
     }
 
+    /// Returns the name of the column that was affected by the error, if applicable, such as
+    /// the column of a not-null violation.
+    ///
+    /// ### Note
+    /// Currently only populated by the Postgres driver.
+    fn column(&self) -> Option<&str> {
+        None
+    }
+
     /// Returns the kind of the error, if supported.
     ///
     /// ### Note
@@ -266,10 +398,24 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
         matches!(self.kind(), ErrorKind::ForeignKeyViolation)
     }
 
+    /// Returns whether the error kind is a violation of a not-null constraint.
+    fn is_not_null_violation(&self) -> bool {
+        matches!(self.kind(), ErrorKind::NotNullViolation)
+    }
+
     /// Returns whether the error kind is a violation of a check.
     fn is_check_violation(&self) -> bool {
         matches!(self.kind(), ErrorKind::CheckViolation)
     }
+
+    /// Returns whether the error is a serialization failure or a deadlock, after which
+    /// the transaction should be retried.
+    fn is_transaction_conflict(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::SerializationFailure | ErrorKind::DeadlockDetected
+        )
+    }
 }
 
 impl dyn DatabaseError {
@@ -1,12 +1,15 @@
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::{BoxDynError, Error};
+use crate::logger::QueryLogOptions;
 use crate::sql_str::{SqlSafeStr, SqlStr};
 
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::stream::TryChunksError;
 use futures_util::{FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use std::time::Duration;
 use std::{fmt::Debug, future};
 
 /// A type that contains or can provide a database
@@ -84,6 +87,26 @@ pub trait Executor<'c>: Send + Debug + Sized {
             .boxed()
     }
 
+    /// Execute the query and return the generated results as a stream of chunks of
+    /// `chunk_size` rows each; the last chunk may be shorter.
+    ///
+    /// Rows are only read from the connection as the stream is polled, so no more than one chunk
+    /// is buffered at a time.
+    ///
+    /// ### Panics
+    /// If `chunk_size` is zero.
+    fn fetch_chunked<'e, 'q: 'e, E>(
+        self,
+        query: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<<Self::Database as Database>::Row>, Error>>
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Self::Database>,
+    {
+        chunked(self.fetch(query), chunk_size)
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     fn fetch_many<'e, 'q: 'e, E>(
@@ -173,16 +196,29 @@ pub trait Executor<'c>: Send + Debug + Sized {
         'c: 'e;
 
     /// Describe the SQL query and return type information about its parameters
-    /// and results.
+    /// and results, including whether each column may be `NULL`.
     ///
     /// This is used by compile-time verification in the query macros to
-    /// power their type inference.
-    #[doc(hidden)]
+    /// power their type inference, and gives external tooling access to the same analysis.
+    /// See [`Describe`] for details.
+    ///
+    /// Unlike [`prepare()`](Self::prepare), this may run additional queries against the database
+    /// catalog to infer nullability, so it is best suited to tooling rather than hot paths.
     fn describe<'e>(self, sql: SqlStr) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
     where
         'c: 'e;
 }
 
+pub(crate) fn chunked<'e, T: Send + 'e>(
+    stream: BoxStream<'e, Result<T, Error>>,
+    chunk_size: usize,
+) -> BoxStream<'e, Result<Vec<T>, Error>> {
+    stream
+        .try_chunks(chunk_size)
+        .map_err(|TryChunksError(_, e)| e)
+        .boxed()
+}
+
 /// A type that may be executed against a database connection.
 ///
 /// Implemented for the following:
@@ -208,6 +244,46 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns how long the query may run before it is cancelled, if limited.
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Returns how the query is logged.
+    fn log_options(&self) -> QueryLogOptions {
+        QueryLogOptions::default()
+    }
+
+    /// Returns the limits on the result of the query.
+    fn result_limits(&self) -> ResultLimits {
+        ResultLimits::default()
+    }
+}
+
+/// Limits on the result of a single query, as set with [`Query::max_rows()`] and
+/// [`Query::max_result_bytes()`].
+///
+/// A limit which is not set falls back to the limit of the connection, if any.
+///
+/// [`Query::max_rows()`]: crate::query::Query::max_rows
+/// [`Query::max_result_bytes()`]: crate::query::Query::max_result_bytes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ResultLimits {
+    pub(crate) max_rows: Option<u64>,
+    pub(crate) max_bytes: Option<u64>,
+}
+
+impl ResultLimits {
+    /// The most rows the query may return, if limited.
+    pub fn max_rows(&self) -> Option<u64> {
+        self.max_rows
+    }
+
+    /// The most bytes of rows the query may return, if limited.
+    pub fn max_result_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
 }
 
 impl<DB: Database, T> Execute<'_, DB> for T
@@ -259,3 +335,23 @@ where
         true
     }
 }
+
+#[test]
+fn chunked_splits_rows_and_keeps_errors() {
+    use futures_util::stream;
+
+    let collect = |items: Vec<Result<i32, Error>>| {
+        chunked(stream::iter(items).boxed(), 2)
+            .try_collect::<Vec<_>>()
+            .now_or_never()
+            .unwrap()
+    };
+
+    let chunks = collect((1..=5).map(Ok).collect()).unwrap();
+    assert_eq!(chunks, [vec![1, 2], vec![3, 4], vec![5]]);
+
+    assert!(matches!(
+        collect(vec![Ok(1), Err(Error::RowNotFound), Ok(2)]),
+        Err(Error::RowNotFound)
+    ));
+}
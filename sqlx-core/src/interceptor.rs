@@ -0,0 +1,179 @@
+//! Interception of the statements executed by a connection, to rewrite or reject them
+//! before they are sent to the database.
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::error::{BoxDynError, Error};
+
+/// A statement about to be executed, as seen by a [`QueryInterceptor`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct InterceptedQuery<'a> {
+    /// The SQL of the statement, as rewritten by any interceptor before this one.
+    pub sql: &'a str,
+    /// The names of the types of the values bound to the statement.
+    pub argument_types: &'a [&'a str],
+    /// Whether the statement is cached by the connection once prepared.
+    pub persistent: bool,
+    /// The name of the query, if it has [one][crate::query::Query::log_as].
+    pub query_name: Option<&'static str>,
+}
+
+impl<'a> InterceptedQuery<'a> {
+    /// Describe a statement to the interceptors; used by drivers.
+    pub fn new(
+        sql: &'a str,
+        argument_types: &'a [&'a str],
+        persistent: bool,
+        query_name: Option<&'static str>,
+    ) -> Self {
+        InterceptedQuery {
+            sql,
+            argument_types,
+            persistent,
+            query_name,
+        }
+    }
+}
+
+/// What a [`QueryInterceptor`] decided to do with a statement.
+#[derive(Debug)]
+pub enum Interception {
+    /// Execute the statement as it is.
+    Continue,
+    /// Execute this SQL instead. It is bound to the same values, so it must have the same
+    /// parameters.
+    Rewrite(String),
+    /// Do not execute the statement; it fails with [`Error::Rejected`] holding this error.
+    Reject(BoxDynError),
+}
+
+/// Inspects each statement before a connection executes it, and may rewrite or reject it.
+///
+/// Installed in the options of a connection, such as with
+/// `PgConnectOptions::interceptor()`, and so in every connection of a pool which uses them.
+/// It can, for instance, qualify tables with the schema of a tenant, or refuse to run
+/// `DROP` statements in production.
+///
+/// ```rust
+/// use sqlx::interceptor::{InterceptedQuery, Interception, QueryInterceptor};
+///
+/// struct ReadOnly;
+///
+/// impl QueryInterceptor for ReadOnly {
+///     fn before_execute(&self, query: &InterceptedQuery<'_>) -> Interception {
+///         let keyword = query.sql.split_whitespace().next().unwrap_or_default();
+///
+///         if keyword.eq_ignore_ascii_case("SELECT") {
+///             Interception::Continue
+///         } else {
+///             Interception::Reject("only SELECT statements may be run".into())
+///         }
+///     }
+/// }
+/// ```
+///
+/// The interceptor is called synchronously, on the task which executes the statement, so it
+/// should not block.
+///
+/// Rewritten statements are cached as usual: an interceptor which rewrites a statement the
+/// same way each time does not prevent it from being prepared only once.
+pub trait QueryInterceptor: Send + Sync + 'static {
+    /// Decide what to do with `query` before it is executed.
+    fn before_execute(&self, query: &InterceptedQuery<'_>) -> Interception;
+}
+
+impl<F> QueryInterceptor for F
+where
+    F: Fn(&InterceptedQuery<'_>) -> Interception + Send + Sync + 'static,
+{
+    fn before_execute(&self, query: &InterceptedQuery<'_>) -> Interception {
+        self(query)
+    }
+}
+
+/// The interceptors installed in the options of a connection, called in the order they
+/// were installed.
+#[derive(Clone, Default)]
+pub struct QueryInterceptors {
+    interceptors: Vec<Arc<dyn QueryInterceptor>>,
+}
+
+impl QueryInterceptors {
+    /// Add `interceptor` after those already installed.
+    pub fn push(&mut self, interceptor: impl QueryInterceptor) {
+        self.interceptors.push(Arc::new(interceptor));
+    }
+
+    /// Returns `true` if no interceptor is installed.
+    pub fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    /// Pass `query` through each interceptor in turn, and return the SQL to execute instead,
+    /// if any of them rewrote it.
+    ///
+    /// Returns [`Error::Rejected`] as soon as one of them rejects it.
+    pub fn intercept(&self, query: &InterceptedQuery<'_>) -> Result<Option<String>, Error> {
+        let mut rewritten: Option<String> = None;
+
+        for interceptor in &self.interceptors {
+            let query = InterceptedQuery {
+                sql: rewritten.as_deref().unwrap_or(query.sql),
+                ..*query
+            };
+
+            match interceptor.before_execute(&query) {
+                Interception::Continue => {}
+                Interception::Rewrite(sql) => rewritten = Some(sql),
+                Interception::Reject(error) => return Err(Error::Rejected(error)),
+            }
+        }
+
+        Ok(rewritten)
+    }
+}
+
+impl Debug for QueryInterceptors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryInterceptors")
+            .field("len", &self.interceptors.len())
+            .finish()
+    }
+}
+
+#[test]
+fn interceptors_are_chained() {
+    let mut interceptors = QueryInterceptors::default();
+
+    let query = InterceptedQuery::new("SELECT * FROM users", &[], true, None);
+
+    assert_eq!(interceptors.intercept(&query).unwrap(), None);
+
+    interceptors.push(|query: &InterceptedQuery<'_>| {
+        Interception::Rewrite(query.sql.replace(" users", " tenant_1.users"))
+    });
+    interceptors.push(|query: &InterceptedQuery<'_>| {
+        if query.sql.starts_with("DROP") {
+            Interception::Reject("DROP is not allowed".into())
+        } else {
+            Interception::Continue
+        }
+    });
+
+    assert_eq!(
+        interceptors.intercept(&query).unwrap().as_deref(),
+        Some("SELECT * FROM tenant_1.users")
+    );
+
+    let drop = InterceptedQuery {
+        sql: "DROP TABLE users",
+        ..query
+    };
+
+    assert!(matches!(
+        interceptors.intercept(&drop),
+        Err(Error::Rejected(e)) if e.to_string() == "DROP is not allowed"
+    ));
+}
@@ -15,7 +15,9 @@
 #![recursion_limit = "512"]
 #![warn(future_incompatible, rust_2018_idioms)]
 #![allow(clippy::needless_doctest_main, clippy::type_complexity)]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "io-uring"), forbid(unsafe_code))]
+// submitting to io_uring is unsafe; see `rt::rt_io_uring`
+#![cfg_attr(feature = "io-uring", deny(unsafe_code))]
 // Allows an API be documented as only available in some specific platforms.
 // <https://doc.rust-lang.org/unstable-book/language-features/doc-cfg.html>
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -58,23 +60,29 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+pub mod audit;
+pub mod blocking;
 pub mod common;
 pub mod database;
 pub mod describe;
 pub mod executor;
 pub mod from_row;
 pub mod fs;
+pub mod interceptor;
 pub mod io;
 pub mod logger;
 pub mod net;
 pub mod query_as;
 pub mod query_builder;
 pub mod query_scalar;
+pub mod query_store;
 pub mod sql_str;
 
 pub mod raw_sql;
 pub mod row;
 pub mod rt;
+pub mod sql_comment;
+pub mod statistics;
 pub mod sync;
 pub mod type_checking;
 pub mod type_info;
@@ -86,6 +94,18 @@ pub mod migrate;
 #[cfg(feature = "any")]
 pub mod any;
 
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+
+#[cfg(feature = "mock")]
+pub mod mock;
+
+#[cfg(feature = "otel")]
+pub mod otel;
+
 // Implements test support with automatic DB management.
 #[cfg(feature = "migrate")]
 pub mod testing;
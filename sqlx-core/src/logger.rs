@@ -1,5 +1,5 @@
 use crate::{connection::LogSettings, sql_str::SqlStr};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 // Yes these look silly. `tracing` doesn't currently support dynamic levels
 // https://github.com/tokio-rs/tracing/issues/372
@@ -60,12 +60,50 @@ pub(crate) fn private_level_filter_to_trace_level(
     private_level_filter_to_levels(filter).map(|(level, _)| level)
 }
 
+/// How a single query is logged, as set with [`Query::log_as()`] and
+/// [`Query::disable_logging()`].
+///
+/// [`Query::log_as()`]: crate::query::Query::log_as
+/// [`Query::disable_logging()`]: crate::query::Query::disable_logging
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryLogOptions {
+    pub(crate) name: Option<&'static str>,
+    pub(crate) enabled: bool,
+}
+
+impl Default for QueryLogOptions {
+    fn default() -> Self {
+        QueryLogOptions {
+            name: None,
+            enabled: true,
+        }
+    }
+}
+
+impl QueryLogOptions {
+    /// The name the query is logged as, if any.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Returns `false` if the query is not to be logged at all.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
 pub struct QueryLogger {
     sql: SqlStr,
     rows_returned: u64,
     rows_affected: u64,
     start: Instant,
     settings: LogSettings,
+    options: QueryLogOptions,
+    bind_values: Option<String>,
+    connection_id: Option<u64>,
+    failed: bool,
+    #[cfg(feature = "otel")]
+    span: Option<tracing::Span>,
 }
 
 impl QueryLogger {
@@ -76,9 +114,68 @@ impl QueryLogger {
             rows_affected: 0,
             start: Instant::now(),
             settings,
+            options: QueryLogOptions::default(),
+            bind_values: None,
+            connection_id: None,
+            failed: false,
+            #[cfg(feature = "otel")]
+            span: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: QueryLogOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Identify the connection the query runs on in the slow query event.
+    pub fn with_connection_id(mut self, connection_id: u64) -> Self {
+        self.connection_id = Some(connection_id);
+        self
+    }
+
+    /// Close `span` when the query is done, recording how many rows it returned.
+    ///
+    /// The query's log events are emitted within the span.
+    #[cfg(feature = "otel")]
+    pub fn with_span(mut self, span: tracing::Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Record that the query failed with `error`, on its span and in metrics if enabled.
+    pub fn record_error(&mut self, error: &crate::error::Error) {
+        self.failed = true;
+
+        #[cfg(feature = "otel")]
+        if let Some(span) = &self.span {
+            crate::otel::record_error(span, error);
+        }
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_query_error(self.sql.as_str(), self.options.name, error);
+
+        #[cfg(feature = "prometheus")]
+        crate::prometheus::record_query_error(self.sql.as_str(), self.options.name, error);
+
+        #[cfg(not(any(feature = "otel", feature = "metrics", feature = "prometheus")))]
+        let _ = error;
+    }
+
+    /// The names of the types whose bind values may be logged, if any may be.
+    pub fn bind_value_types(&self) -> Option<&[String]> {
+        if self.options.enabled {
+            self.settings.bind_value_types.as_deref()
+        } else {
+            None
         }
     }
 
+    /// Log the bind values of the query, as rendered by the driver.
+    pub fn set_bind_values(&mut self, bind_values: String) {
+        self.bind_values = Some(bind_values);
+    }
+
     pub fn increment_rows_returned(&mut self) {
         self.rows_returned += 1;
     }
@@ -92,8 +189,44 @@ impl QueryLogger {
     }
 
     pub fn finish(&self) {
+        #[cfg(feature = "otel")]
+        let _entered = self.span.as_ref().map(|span| {
+            crate::otel::record_returned_rows(span, self.rows_returned);
+            span.enter()
+        });
+
         let elapsed = self.start.elapsed();
 
+        if let Some(statistics) = &self.settings.statistics {
+            statistics.record(self.sql.as_str(), elapsed, self.rows_returned, self.failed);
+        }
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_query(
+            self.sql.as_str(),
+            self.options.name,
+            elapsed,
+            self.rows_returned,
+        );
+
+        #[cfg(feature = "prometheus")]
+        crate::prometheus::record_query(
+            self.sql.as_str(),
+            self.options.name,
+            elapsed,
+            self.rows_returned,
+        );
+
+        if !self.options.enabled {
+            return;
+        }
+
+        if let Some(threshold) = self.settings.slow_query_threshold {
+            if elapsed > threshold {
+                self.emit_slow_query(elapsed, threshold);
+            }
+        }
+
         let was_slow = elapsed >= self.settings.slow_statements_duration;
 
         let lvl = if was_slow {
@@ -108,13 +241,20 @@ impl QueryLogger {
             let log_is_enabled = log::log_enabled!(target: "sqlx::query", log_level)
                 || private_tracing_dynamic_enabled!(target: "sqlx::query", tracing_level);
             if log_is_enabled {
-                let mut summary = parse_query_summary(self.sql.as_str());
+                let (summary, sql) = match self.options.name {
+                    Some(name) => (name.to_owned(), format!("\n\n{}\n", self.sql.as_str())),
+                    None => {
+                        let mut summary = parse_query_summary(self.sql.as_str());
 
-                let sql = if summary != self.sql.as_str() {
-                    summary.push_str(" …");
-                    format!("\n\n{}\n", self.sql.as_str())
-                } else {
-                    String::new()
+                        let sql = if summary != self.sql.as_str() {
+                            summary.push_str(" …");
+                            format!("\n\n{}\n", self.sql.as_str())
+                        } else {
+                            String::new()
+                        };
+
+                        (summary, sql)
+                    }
                 };
 
                 if was_slow {
@@ -123,6 +263,8 @@ impl QueryLogger {
                         tracing_level,
                         summary,
                         db.statement = sql,
+                        db.query_name = self.options.name,
+                        db.bind_values = self.bind_values.as_deref(),
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         // Human-friendly - includes units (usually ms). Also kept for backward compatibility
@@ -141,6 +283,8 @@ impl QueryLogger {
                         tracing_level,
                         summary,
                         db.statement = sql,
+                        db.query_name = self.options.name,
+                        db.bind_values = self.bind_values.as_deref(),
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         // Human-friendly - includes units (usually ms). Also kept for backward compatibility
@@ -154,12 +298,51 @@ impl QueryLogger {
     }
 }
 
+impl QueryLogger {
+    fn emit_slow_query(&self, elapsed: Duration, threshold: Duration) {
+        // As above, check both the log and the tracing world.
+        let is_enabled = log::log_enabled!(target: "sqlx::slow_query", log::Level::Warn)
+            || tracing::enabled!(target: "sqlx::slow_query", tracing::Level::WARN);
+
+        if !is_enabled {
+            return;
+        }
+
+        let summary = match self.options.name {
+            Some(name) => name.to_owned(),
+            None => parse_query_summary(self.sql.as_str()),
+        };
+
+        tracing::warn!(
+            target: "sqlx::slow_query",
+            summary,
+            db.statement = self.sql.as_str(),
+            db.query_name = self.options.name,
+            rows_affected = self.rows_affected,
+            rows_returned = self.rows_returned,
+            elapsed_secs = elapsed.as_secs_f64(),
+            slow_threshold_secs = threshold.as_secs_f64(),
+            connection_id = self.connection_id,
+            "slow query: execution time exceeded threshold"
+        );
+    }
+}
+
 impl Drop for QueryLogger {
     fn drop(&mut self) {
         self.finish();
     }
 }
 
+/// The first keyword of the query, such as `SELECT`, in uppercase.
+pub fn parse_query_operation(sql: &str) -> Option<String> {
+    let keyword = sql
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .find(|word| !word.is_empty())?;
+
+    Some(keyword.to_ascii_uppercase())
+}
+
 pub fn parse_query_summary(sql: &str) -> String {
     // For now, just take the first 4 words
     sql.split_whitespace()
@@ -167,3 +350,189 @@ pub fn parse_query_summary(sql: &str) -> String {
         .collect::<Vec<&str>>()
         .join(" ")
 }
+
+/// Statements longer than this many bytes are truncated by [`sanitize_statement()`].
+pub const MAX_STATEMENT_LEN: usize = 2048;
+
+/// Replace the literals in `sql` with `?`, and truncate it to [`MAX_STATEMENT_LEN`] bytes.
+///
+/// String, dollar-quoted and numeric literals may hold data which has no place in traces,
+/// so they are removed; placeholders such as `$1`, quoted identifiers and comments are kept.
+///
+/// ```rust
+/// use sqlx_core::logger::sanitize_statement;
+///
+/// assert_eq!(
+///     sanitize_statement("SELECT * FROM users WHERE email = 'ferris@example.com' AND id > 10 AND org = $1"),
+///     "SELECT * FROM users WHERE email = ? AND id > ? AND org = $1",
+/// );
+/// ```
+pub fn sanitize_statement(sql: &str) -> String {
+    let mut sanitized = String::with_capacity(std::cmp::min(sql.len(), MAX_STATEMENT_LEN));
+    let mut rest = sql;
+    // Whether the previous character could be part of an identifier or keyword.
+    let mut in_word = false;
+
+    while let Some(c) = rest.chars().next() {
+        let (token, literal) = match c {
+            '\'' => {
+                let escapes = sanitized.ends_with(['E', 'e'])
+                    && !sanitized[..sanitized.len() - 1].ends_with(is_word_char);
+
+                if escapes {
+                    sanitized.pop();
+                }
+
+                (quoted_len(rest, '\'', escapes), true)
+            }
+            '"' => (quoted_len(rest, '"', false), false),
+            '-' if rest.starts_with("--") => (rest.find('\n').unwrap_or(rest.len()), false),
+            '/' if rest.starts_with("/*") => {
+                (rest.find("*/").map_or(rest.len(), |end| end + 2), false)
+            }
+            '$' if !in_word => match dollar_quoted_len(rest) {
+                Some(len) => (len, true),
+                // A placeholder such as `$1`.
+                None => (1 + digits_len(&rest[1..]), false),
+            },
+            '0'..='9' if !in_word => (number_len(rest), true),
+            '.' if !in_word && rest[1..].starts_with(|c: char| c.is_ascii_digit()) => {
+                (number_len(rest), true)
+            }
+            c => (c.len_utf8(), false),
+        };
+
+        if literal {
+            sanitized.push('?');
+        } else {
+            sanitized.push_str(&rest[..token]);
+        }
+
+        if sanitized.len() > MAX_STATEMENT_LEN {
+            truncate(&mut sanitized);
+            sanitized.push_str(" …");
+            break;
+        }
+
+        in_word = !literal && is_word_char(c) && token == c.len_utf8();
+        rest = &rest[token..];
+    }
+
+    sanitized
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+// The length of the literal or identifier quoted with `quote` at the start of `s`, where the
+// quote is escaped by doubling it and, if `backslash`, with a backslash.
+fn quoted_len(s: &str, quote: char, backslash: bool) -> usize {
+    let mut chars = s.char_indices().skip(1);
+
+    while let Some((i, c)) = chars.next() {
+        if backslash && c == '\\' {
+            chars.next();
+        } else if c == quote {
+            if s[i + 1..].starts_with(quote) {
+                chars.next();
+            } else {
+                return i + 1;
+            }
+        }
+    }
+
+    s.len()
+}
+
+// The length of the dollar-quoted literal at the start of `s`, such as `$tag$...$tag$`.
+fn dollar_quoted_len(s: &str) -> Option<usize> {
+    let tag_len = s[1..].find('$')? + 2;
+    let tag = &s[..tag_len];
+
+    if !tag[1..tag_len - 1]
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_')
+        || tag[1..].starts_with(|c: char| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let end = s[tag_len..]
+        .find(tag)
+        .map_or(s.len(), |end| tag_len + end + tag_len);
+
+    Some(end)
+}
+
+fn digits_len(s: &str) -> usize {
+    s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len())
+}
+
+// The length of the numeric literal at the start of `s`, such as `1.5e-3`.
+fn number_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    let mut len = 0;
+
+    while let Some(&b) = bytes.get(len) {
+        match b {
+            b'0'..=b'9' | b'.' | b'_' => len += 1,
+            b'e' | b'E' => {
+                len += 1;
+
+                if matches!(bytes.get(len), Some(b'+' | b'-')) {
+                    len += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+
+    len
+}
+
+fn truncate(s: &mut String) {
+    let mut len = MAX_STATEMENT_LEN;
+
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+
+    s.truncate(len);
+}
+
+#[test]
+fn statements_are_sanitized() {
+    let cases = [
+        ("SELECT 1", "SELECT ?"),
+        ("SELECT $1, $2::int8", "SELECT $1, $2::int8"),
+        (
+            "SELECT * FROM t2 WHERE x = 1.5e-3",
+            "SELECT * FROM t2 WHERE x = ?",
+        ),
+        ("SELECT 'it''s', E'a\\'b', e", "SELECT ?, ?, e"),
+        ("SELECT $$secret$$, $tag$ $$ $tag$", "SELECT ?, ?"),
+        (
+            r#"SELECT "col 1" FROM "t'1""#,
+            r#"SELECT "col 1" FROM "t'1""#,
+        ),
+        ("SELECT 1 -- 'comment'\n", "SELECT ? -- 'comment'\n"),
+        ("SELECT x FROM t LIMIT .5", "SELECT x FROM t LIMIT ?"),
+        ("SELECT 'unterminated", "SELECT ?"),
+    ];
+
+    for (sql, expected) in cases {
+        assert_eq!(sanitize_statement(sql), expected, "{sql}");
+    }
+
+    let long = format!("SELECT '{}', é{}", "x", "é".repeat(MAX_STATEMENT_LEN));
+    let sanitized = sanitize_statement(&long);
+    assert!(sanitized.len() <= MAX_STATEMENT_LEN + " …".len());
+    assert!(sanitized.ends_with(" …"));
+
+    assert_eq!(
+        parse_query_operation("  with x AS (SELECT 1) SELECT * FROM x").as_deref(),
+        Some("WITH")
+    );
+    assert_eq!(parse_query_operation("").as_deref(), None);
+}
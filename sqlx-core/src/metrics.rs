@@ -0,0 +1,111 @@
+//! Metrics of queries and pools, emitted through the [`metrics`](https://docs.rs/metrics)
+//! facade.
+//!
+//! Install a recorder, such as the one from the `metrics-exporter-prometheus` crate, and
+//! SQLx reports:
+//!
+//! * [`QUERY_DURATION`]: a histogram of how long queries take, in seconds.
+//! * [`QUERY_ROWS`]: a counter of the rows returned by queries.
+//! * [`QUERY_ERRORS`]: a counter of failed queries, labeled with `kind`, such as
+//!   `unique_violation`, `serialization_failure`, `timeout` or `io`.
+//! * [`POOL_ACQUIRE_DURATION`]: a histogram of how long it takes to acquire a connection
+//!   from a pool, in seconds.
+//! * [`POOL_ACQUIRE_TIMEOUTS`]: a counter of acquires which timed out.
+//!
+//! The query metrics are labeled with `operation`, the first keyword of the query such as
+//! `SELECT`, and with `query_name` if the query has a [name][crate::query::Query::log_as].
+//!
+//! Call [`describe()`] once the recorder is installed to register the units and descriptions
+//! of the metrics with it.
+
+use std::time::Duration;
+
+use crate::error::Error;
+
+/// The name of the histogram of query durations, in seconds.
+pub const QUERY_DURATION: &str = "sqlx_query_duration_seconds";
+
+/// The name of the counter of rows returned by queries.
+pub const QUERY_ROWS: &str = "sqlx_query_rows_total";
+
+/// The name of the counter of failed queries.
+pub const QUERY_ERRORS: &str = "sqlx_query_errors_total";
+
+/// The name of the histogram of pool acquire durations, in seconds.
+pub const POOL_ACQUIRE_DURATION: &str = "sqlx_pool_acquire_duration_seconds";
+
+/// The name of the counter of pool acquires which timed out.
+pub const POOL_ACQUIRE_TIMEOUTS: &str = "sqlx_pool_acquire_timeouts_total";
+
+/// Register the units and descriptions of SQLx's metrics with the installed recorder.
+pub fn describe() {
+    use ::metrics::Unit;
+
+    ::metrics::describe_histogram!(QUERY_DURATION, Unit::Seconds, "Duration of queries.");
+    ::metrics::describe_counter!(QUERY_ROWS, Unit::Count, "Rows returned by queries.");
+    ::metrics::describe_counter!(QUERY_ERRORS, Unit::Count, "Queries which failed.");
+    ::metrics::describe_histogram!(
+        POOL_ACQUIRE_DURATION,
+        Unit::Seconds,
+        "Time taken to acquire a connection from a pool."
+    );
+    ::metrics::describe_counter!(
+        POOL_ACQUIRE_TIMEOUTS,
+        Unit::Count,
+        "Acquires of a connection from a pool which timed out."
+    );
+}
+
+pub(crate) fn record_query(
+    sql: &str,
+    name: Option<&'static str>,
+    elapsed: Duration,
+    rows_returned: u64,
+) {
+    let labels = query_labels(sql, name);
+
+    ::metrics::histogram!(QUERY_DURATION, labels.clone()).record(elapsed);
+    ::metrics::counter!(QUERY_ROWS, labels).increment(rows_returned);
+}
+
+pub(crate) fn record_query_error(sql: &str, name: Option<&'static str>, error: &Error) {
+    let mut labels = query_labels(sql, name);
+    labels.push(::metrics::Label::from_static_parts(
+        "kind",
+        error.kind_label(),
+    ));
+
+    ::metrics::counter!(QUERY_ERRORS, labels).increment(1);
+}
+
+pub(crate) fn record_pool_acquire(elapsed: Duration) {
+    ::metrics::histogram!(POOL_ACQUIRE_DURATION).record(elapsed);
+}
+
+pub(crate) fn record_pool_acquire_timeout() {
+    ::metrics::counter!(POOL_ACQUIRE_TIMEOUTS).increment(1);
+}
+
+fn query_labels(sql: &str, name: Option<&'static str>) -> Vec<::metrics::Label> {
+    let operation = crate::logger::parse_query_operation(sql).unwrap_or_default();
+
+    let mut labels = vec![::metrics::Label::new("operation", operation)];
+
+    if let Some(name) = name {
+        labels.push(::metrics::Label::from_static_parts("query_name", name));
+    }
+
+    labels
+}
+
+#[test]
+fn errors_are_labeled_by_kind() {
+    use std::io;
+
+    assert_eq!(Error::Timeout.kind_label(), "timeout");
+    assert_eq!(
+        Error::from(io::Error::from(io::ErrorKind::BrokenPipe)).kind_label(),
+        "io"
+    );
+    assert_eq!(Error::RowNotFound.kind_label(), "other");
+}
@@ -0,0 +1,49 @@
+use std::env;
+use std::fs;
+
+/// Metadata recorded with each migration applied by a [`Migrator`][crate::migrate::Migrator]
+/// with auditing enabled, alongside the time it was applied and how long it took.
+///
+/// See [`Migrator::set_record_audit()`][crate::migrate::Migrator::set_record_audit].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditInfo {
+    /// Who applied the migration, e.g. `user@hostname`.
+    pub applied_by: Option<String>,
+
+    /// The application (and version) that applied the migration, e.g. `my-service 1.2.3`.
+    pub application: Option<String>,
+}
+
+impl AuditInfo {
+    /// Environment variable read by [`Self::from_env()`] for [`Self::application`].
+    pub const APPLICATION_VAR: &'static str = "SQLX_MIGRATE_APPLICATION";
+
+    /// Collect audit metadata from the environment of the current process.
+    ///
+    /// * `applied_by` is `<user>@<hostname>`, from the `USER` (or `USERNAME`) and `HOSTNAME`
+    ///   (or `COMPUTERNAME`) environment variables, falling back to `/etc/hostname` for the
+    ///   hostname. Either part is omitted if it cannot be determined.
+    /// * `application` is read from the `SQLX_MIGRATE_APPLICATION` environment variable,
+    ///   e.g. set by the deployment to the name and version of the application.
+    pub fn from_env() -> Self {
+        let user = var("USER").or_else(|| var("USERNAME"));
+        let hostname = var("HOSTNAME").or_else(|| var("COMPUTERNAME")).or_else(|| {
+            let hostname = fs::read_to_string("/etc/hostname").ok()?;
+            Some(hostname.trim().to_owned()).filter(|h| !h.is_empty())
+        });
+
+        let applied_by = match (user, hostname) {
+            (Some(user), Some(hostname)) => Some(format!("{user}@{hostname}")),
+            (user, hostname) => user.or(hostname),
+        };
+
+        AuditInfo {
+            applied_by,
+            application: var(Self::APPLICATION_VAR),
+        }
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
@@ -0,0 +1,68 @@
+use crate::database::Database;
+use crate::error::BoxDynError;
+use futures_core::future::BoxFuture;
+use std::any::Any;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// A migration implemented in Rust, for changes that need application logic
+/// (e.g. backfilling a column with hashed values or data fetched from another service).
+///
+/// Create a [`Migration`][crate::migrate::Migration] from it with
+/// [`Migration::code()`][crate::migrate::Migration::code] and register it with
+/// [`Migrator::add_migration()`][crate::migrate::Migrator::add_migration].
+/// It is then ordered, versioned and tracked like migrations loaded from SQL files.
+///
+/// Unless the migration is created with `no_tx = true`, `conn` is a transaction which is
+/// committed together with the bookkeeping for the migration.
+///
+/// ```rust,no_run
+/// # use sqlx_core::error::BoxDynError;
+/// # use sqlx_core::migrate::RustMigration;
+/// # use futures_core::future::BoxFuture;
+/// use sqlx_core::database::Database;
+///
+/// #[derive(Debug)]
+/// struct BackfillEmailHashes;
+///
+/// impl<DB: Database> RustMigration<DB> for BackfillEmailHashes {
+///     fn run<'c>(
+///         &'c self,
+///         conn: &'c mut DB::Connection,
+///     ) -> BoxFuture<'c, Result<(), BoxDynError>> {
+///         Box::pin(async move {
+///             // query, transform and update rows using `conn`
+///             Ok(())
+///         })
+///     }
+/// }
+/// ```
+pub trait RustMigration<DB: Database>: Debug + Send + Sync + 'static {
+    /// Apply the migration using `conn`.
+    fn run<'c>(&'c self, conn: &'c mut DB::Connection) -> BoxFuture<'c, Result<(), BoxDynError>>;
+}
+
+/// A type-erased [`RustMigration`], downcast by the driver applying it.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MigrationCode(Arc<dyn Any + Send + Sync>);
+
+impl MigrationCode {
+    pub(crate) fn new<DB: Database>(code: impl RustMigration<DB>) -> Self {
+        let code: Box<dyn RustMigration<DB>> = Box::new(code);
+        Self(Arc::new(code))
+    }
+
+    /// Get the migration, if it is implemented for `DB`.
+    pub fn get<DB: Database>(&self) -> Option<&dyn RustMigration<DB>> {
+        self.0
+            .downcast_ref::<Box<dyn RustMigration<DB>>>()
+            .map(|code| &**code)
+    }
+}
+
+impl Debug for MigrationCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MigrationCode").finish_non_exhaustive()
+    }
+}
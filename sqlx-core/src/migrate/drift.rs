@@ -0,0 +1,174 @@
+use std::collections::btree_map::{self, BTreeMap};
+use std::fmt::{self, Display, Formatter};
+
+/// An object in a database schema, e.g. a table, column or index.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SchemaObject {
+    /// The kind of object, e.g. `table`, `column` or `index`.
+    pub kind: String,
+    /// The qualified name of the object, e.g. `public.users.id` for a column.
+    pub name: String,
+}
+
+impl Display for SchemaObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.name)
+    }
+}
+
+/// The objects in a database schema along with their definitions,
+/// as returned by [`Migrate::schema_snapshot()`][crate::migrate::Migrate::schema_snapshot].
+///
+/// Definitions are only compared with each other, so their format is up to the driver.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaSnapshot {
+    objects: BTreeMap<SchemaObject, String>,
+}
+
+impl SchemaSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an object to the snapshot, replacing any previous definition.
+    pub fn insert(
+        &mut self,
+        kind: impl Into<String>,
+        name: impl Into<String>,
+        definition: impl Into<String>,
+    ) {
+        let object = SchemaObject {
+            kind: kind.into(),
+            name: name.into(),
+        };
+
+        self.objects.insert(object, definition.into());
+    }
+
+    /// Get an iterator over the objects in the snapshot and their definitions, ordered by kind
+    /// and name.
+    pub fn iter(&self) -> btree_map::Iter<'_, SchemaObject, String> {
+        self.objects.iter()
+    }
+
+    /// List the differences between this (expected) snapshot and `actual`.
+    pub fn diff(&self, actual: &SchemaSnapshot) -> Vec<SchemaChange> {
+        let mut changes = Vec::new();
+
+        for (object, expected) in &self.objects {
+            match actual.objects.get(object) {
+                None => changes.push(SchemaChange::Missing {
+                    object: object.clone(),
+                    expected: expected.clone(),
+                }),
+                Some(actual) if actual != expected => changes.push(SchemaChange::Changed {
+                    object: object.clone(),
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                }),
+                Some(_) => (),
+            }
+        }
+
+        for (object, actual) in &actual.objects {
+            if !self.objects.contains_key(object) {
+                changes.push(SchemaChange::Unexpected {
+                    object: object.clone(),
+                    actual: actual.clone(),
+                });
+            }
+        }
+
+        changes
+    }
+}
+
+/// A difference between the schema produced by the migrations and the schema of a database.
+///
+/// See [`Migrator::detect_drift()`][crate::migrate::Migrator::detect_drift].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SchemaChange {
+    /// An object created by the migrations does not exist in the database.
+    Missing {
+        object: SchemaObject,
+        expected: String,
+    },
+
+    /// An object in the database was not created by the migrations.
+    Unexpected {
+        object: SchemaObject,
+        actual: String,
+    },
+
+    /// An object in the database is defined differently than by the migrations.
+    Changed {
+        object: SchemaObject,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl SchemaChange {
+    /// The object that differs.
+    pub fn object(&self) -> &SchemaObject {
+        match self {
+            SchemaChange::Missing { object, .. }
+            | SchemaChange::Unexpected { object, .. }
+            | SchemaChange::Changed { object, .. } => object,
+        }
+    }
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::Missing { object, .. } => write!(f, "{object} is missing"),
+            SchemaChange::Unexpected { object, actual } => {
+                write!(f, "{object} is not created by migrations: {actual}")
+            }
+            SchemaChange::Changed {
+                object,
+                expected,
+                actual,
+            } => write!(f, "{object} was changed from {expected} to {actual}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_changed_objects() {
+        let mut expected = SchemaSnapshot::new();
+        expected.insert("table", "public.users", "");
+        expected.insert("column", "public.users.id", "bigint NOT NULL");
+        expected.insert("column", "public.users.name", "text NOT NULL");
+
+        let mut actual = expected.clone();
+        assert_eq!(expected.diff(&actual), []);
+
+        actual.insert("column", "public.users.name", "text");
+        actual.insert("index", "public.users.users_name_idx", "CREATE INDEX ...");
+        actual
+            .objects
+            .retain(|object, _| object.name != "public.users.id");
+
+        let changes: Vec<_> = expected
+            .diff(&actual)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        assert_eq!(
+            changes,
+            [
+                "column public.users.id is missing",
+                "column public.users.name was changed from text NOT NULL to text",
+                "index public.users.users_name_idx is not created by migrations: CREATE INDEX ...",
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use crate::migrate::Migration;
+use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
+use std::sync::Arc;
+
+/// Migrations embedded by `migrate!()` with compression enabled.
+///
+/// The SQL of identical migration files is only stored once, and all of it is compressed
+/// together, so it is decompressed at once on first use.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct EmbeddedMigrations {
+    /// The migrations, with an empty `sql`.
+    pub migrations: &'static [Migration],
+    /// The index of the SQL of each migration in `lengths`.
+    pub sql: &'static [usize],
+    /// The length in bytes of each distinct SQL text, in the order they appear in `contents`.
+    pub lengths: &'static [usize],
+    /// The distinct SQL texts, concatenated and compressed with DEFLATE.
+    pub contents: &'static [u8],
+}
+
+impl EmbeddedMigrations {
+    pub fn decompress(&self) -> Vec<Migration> {
+        let contents = miniz_oxide::inflate::decompress_to_vec(self.contents)
+            .ok()
+            .and_then(|contents| String::from_utf8(contents).ok())
+            .expect("BUG: embedded migrations are corrupted");
+
+        let mut offset = 0;
+        let texts: Vec<Arc<str>> = self
+            .lengths
+            .iter()
+            .map(|&len| {
+                let text = Arc::from(&contents[offset..offset + len]);
+                offset += len;
+                text
+            })
+            .collect();
+
+        self.migrations
+            .iter()
+            .zip(self.sql)
+            .map(|(migration, &i)| Migration {
+                sql: AssertSqlSafe(texts[i].clone()).into_sql_str(),
+                ..migration.clone()
+            })
+            .collect()
+    }
+}
+
+/// Compress the concatenated SQL of embedded migrations; see [`EmbeddedMigrations::contents`].
+#[doc(hidden)]
+pub fn compress_embedded(contents: &[u8]) -> Vec<u8> {
+    miniz_oxide::deflate::compress_to_vec(contents, 9)
+}
+
+#[test]
+fn decompress_shares_identical_sql() {
+    use crate::migrate::MigrationType;
+    use crate::sql_str::SqlStr;
+
+    const fn migration(version: i64) -> Migration {
+        Migration {
+            version,
+            description: std::borrow::Cow::Borrowed("test"),
+            migration_type: MigrationType::Simple,
+            sql: SqlStr::from_static(""),
+            checksum: std::borrow::Cow::Borrowed(&[]),
+            no_tx: false,
+            code: None,
+        }
+    }
+
+    let contents = compress_embedded("CREATE TABLE a ();SELECT 1;".as_bytes());
+    let embedded = EmbeddedMigrations {
+        migrations: const { &[migration(1), migration(2), migration(3)] },
+        sql: &[0, 1, 0],
+        lengths: &[18, 9],
+        contents: contents.leak(),
+    };
+
+    let sql: Vec<_> = embedded
+        .decompress()
+        .iter()
+        .map(|m| m.sql.as_str().to_owned())
+        .collect();
+
+    assert_eq!(
+        sql,
+        ["CREATE TABLE a ();", "SELECT 1;", "CREATE TABLE a ();"]
+    );
+}
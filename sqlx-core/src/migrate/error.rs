@@ -1,4 +1,5 @@
 use crate::error::{BoxDynError, Error};
+use std::time::Duration;
 
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
@@ -9,6 +10,12 @@ pub enum MigrateError {
     #[error("while executing migration {1}: {0}")]
     ExecuteMigration(#[source] Error, i64),
 
+    #[error("while executing migration {1}: {0}")]
+    ExecuteCode(#[source] BoxDynError, i64),
+
+    #[error("migration {0} is implemented in Rust for a different database driver")]
+    CodeNotSupported(i64),
+
     #[error("while resolving migrations: {0}")]
     Source(#[source] BoxDynError),
 
@@ -42,4 +49,25 @@ pub enum MigrateError {
 
     #[error("database driver does not support creation of schemas at migrate time: {0}")]
     CreateSchemasNotSupported(String),
+
+    #[error("migration {0} has no corresponding down migration")]
+    MissingDownMigration(i64),
+
+    #[error("migrations are locked by another migrator")]
+    Locked,
+
+    #[error("timed out after {0:?} waiting for the migration lock")]
+    LockTimeout(Duration),
+
+    #[error("migration {0} cannot be reverted: applied migration {1} depends on it")]
+    Dependency(i64, i64),
+
+    #[error("down migration {0} contains a destructive statement ({1}); use force to revert it")]
+    Destructive(i64, String),
+
+    #[error("database driver does not support snapshots of the schema to detect drift")]
+    SchemaSnapshotNotSupported,
+
+    #[error("database driver does not support {0}")]
+    NotSupported(&'static str),
 }
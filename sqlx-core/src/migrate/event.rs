@@ -0,0 +1,50 @@
+use crate::migrate::{MigrateError, Migration};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Progress of a single migration, reported while [`Migrator::run()`] applies migrations.
+///
+/// See [`Migrator::set_event_handler()`].
+///
+/// [`Migrator::run()`]: crate::migrate::Migrator::run
+/// [`Migrator::set_event_handler()`]: crate::migrate::Migrator::set_event_handler
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MigrationEvent<'a> {
+    /// The migration is about to be applied.
+    Started { migration: &'a Migration },
+
+    /// The migration was applied successfully.
+    Applied {
+        migration: &'a Migration,
+        elapsed: Duration,
+    },
+
+    /// The migration failed to apply; `run()` returns `error` after this event.
+    Failed {
+        migration: &'a Migration,
+        error: &'a MigrateError,
+    },
+}
+
+impl MigrationEvent<'_> {
+    /// The migration this event is about.
+    pub fn migration(&self) -> &Migration {
+        match self {
+            Self::Started { migration }
+            | Self::Applied { migration, .. }
+            | Self::Failed { migration, .. } => migration,
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct EventHandler(pub(crate) Arc<dyn Fn(MigrationEvent<'_>) + Send + Sync>);
+
+impl Debug for EventHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EventHandler").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,226 @@
+use crate::migrate::{migration, Migrate, MigrateError};
+use futures_core::future::BoxFuture;
+use std::cmp;
+use std::fmt::Debug;
+use std::time::Duration;
+
+/// Upper bound for the delay between attempts when polling for a lock.
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the [`Migrator`][crate::migrate::Migrator] should do if the migration lock
+/// is already held by another migrator.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum LockPolicy {
+    /// Wait until the lock is released, up to the configured lock timeout (if any).
+    ///
+    /// Fails with [`MigrateError::LockTimeout`] if the timeout elapses first.
+    #[default]
+    Wait,
+
+    /// Fail immediately with [`MigrateError::Locked`].
+    Fail,
+}
+
+/// A strategy to ensure only one migrator applies migrations to a database at a time.
+///
+/// The lock is acquired before migrations are checked or applied and released afterwards.
+///
+/// Built-in strategies:
+/// * [`AdvisoryLock`] (default): a database-level advisory lock held by the connection.
+/// * [`TableLock`]: a row in a dedicated lock table.
+/// * [`NoLock`]: no mutual exclusion at all.
+pub trait MigrationLock: Debug + Send + Sync {
+    /// Acquire the lock, waiting until it is released by any other migrator.
+    ///
+    /// The default implementation polls [`Self::try_lock()`] with an increasing delay.
+    fn lock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut interval = Duration::from_millis(50);
+
+            while !self.try_lock(&mut *conn, table_name).await? {
+                crate::rt::sleep(interval).await;
+                interval = cmp::min(interval * 2, MAX_POLL_INTERVAL);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Attempt to acquire the lock without waiting.
+    ///
+    /// Returns `false` if the lock is held by another migrator.
+    fn try_lock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>>;
+
+    /// Release the lock.
+    fn unlock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>>;
+}
+
+/// Lock the database using an advisory lock held by the migrating connection.
+///
+/// The lock is specific to the migrations table, so migrators using different tables
+/// (e.g. independent applications sharing a database) don't block each other. The default
+/// `_sqlx_migrations` table keeps the lock of the whole database taken by earlier versions,
+/// so they still exclude newer migrators during an upgrade.
+///
+/// The lock is released automatically if the connection is closed, but does not work through
+/// connection poolers which don't pin sessions to a server connection (e.g. PgBouncer in
+/// transaction mode), nor with databases that don't support advisory locks (e.g. CockroachDB).
+#[derive(Debug, Default, Copy, Clone)]
+pub struct AdvisoryLock;
+
+impl MigrationLock for AdvisoryLock {
+    fn lock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        conn.lock(table_name)
+    }
+
+    fn try_lock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        conn.try_lock(table_name)
+    }
+
+    fn unlock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        conn.unlock(table_name)
+    }
+}
+
+/// Lock the database by inserting a row into a dedicated lock table,
+/// named after the migrations table with a `_lock` suffix (e.g. `_sqlx_migrations_lock`).
+///
+/// This works through any connection pooler and with databases that don't support
+/// advisory locks.
+///
+/// ### Warning: Stale Locks
+/// If a migrator dies while holding the lock, the row is not deleted and other migrators
+/// will not be able to acquire the lock until it is deleted manually.
+/// Consider setting a lock timeout so this does not block deployments indefinitely.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TableLock;
+
+impl MigrationLock for TableLock {
+    fn try_lock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async move { conn.try_lock_table(&lock_table_name(table_name)).await })
+    }
+
+    fn unlock<'e>(
+        &'e self,
+        conn: &'e mut (dyn Migrate + Send + 'e),
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move { conn.unlock_table(&lock_table_name(table_name)).await })
+    }
+}
+
+/// Do not lock the database during migration.
+///
+/// ### Warning
+/// This can lead to errors or data loss if multiple clients attempt to apply migrations
+/// simultaneously without some sort of external mutual exclusion.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct NoLock;
+
+impl MigrationLock for NoLock {
+    fn lock<'e>(
+        &'e self,
+        _conn: &'e mut (dyn Migrate + Send + 'e),
+        _table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn try_lock<'e>(
+        &'e self,
+        _conn: &'e mut (dyn Migrate + Send + 'e),
+        _table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async { Ok(true) })
+    }
+
+    fn unlock<'e>(
+        &'e self,
+        _conn: &'e mut (dyn Migrate + Send + 'e),
+        _table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Acquire `strategy` according to `policy`.
+pub(crate) async fn acquire(
+    strategy: &dyn MigrationLock,
+    policy: LockPolicy,
+    timeout: Option<Duration>,
+    conn: &mut (dyn Migrate + Send + '_),
+    table_name: &str,
+) -> Result<(), MigrateError> {
+    match (policy, timeout) {
+        (LockPolicy::Wait, None) => strategy.lock(conn, table_name).await,
+        (LockPolicy::Wait, Some(timeout)) => {
+            let start = std::time::Instant::now();
+            let mut interval = Duration::from_millis(50);
+
+            while !strategy.try_lock(&mut *conn, table_name).await? {
+                let elapsed = start.elapsed();
+
+                if elapsed >= timeout {
+                    return Err(MigrateError::LockTimeout(timeout));
+                }
+
+                crate::rt::sleep(cmp::min(interval, timeout - elapsed)).await;
+                interval = cmp::min(interval * 2, MAX_POLL_INTERVAL);
+            }
+
+            Ok(())
+        }
+        (LockPolicy::Fail, _) => {
+            if strategy.try_lock(conn, table_name).await? {
+                Ok(())
+            } else {
+                Err(MigrateError::Locked)
+            }
+        }
+    }
+}
+
+fn lock_table_name(table_name: &str) -> String {
+    migration::suffix_table_name(table_name, "_lock")
+}
+
+#[test]
+fn lock_table_name_appends_suffix() {
+    assert_eq!(lock_table_name("_sqlx_migrations"), "_sqlx_migrations_lock");
+    assert_eq!(
+        lock_table_name("foo._sqlx_migrations"),
+        "foo._sqlx_migrations_lock"
+    );
+    assert_eq!(
+        lock_table_name(r#""Foo"."Migrations""#),
+        r#""Foo"."Migrations_lock""#
+    );
+}
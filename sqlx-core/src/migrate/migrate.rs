@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, MigrateError, Migration};
+use crate::migrate::{AppliedMigration, AuditInfo, MigrateError, Migration, SchemaSnapshot};
 use futures_core::future::BoxFuture;
 use std::future::Future;
 use std::time::Duration;
@@ -32,6 +32,21 @@ pub trait Migrate {
         schema_name: &'e str,
     ) -> BoxFuture<'e, Result<(), MigrateError>>;
 
+    // check if the migrations table exists, without creating it
+    // by default, `NotSupported` is returned, so migrations cannot be planned
+    fn migrations_table_exists<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        let _ = table_name;
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "checking whether the migrations table exists",
+            ))
+        })
+    }
+
     // ensure migrations table exists
     // will create or migrate it if needed
     fn ensure_migrations_table<'e>(
@@ -39,6 +54,23 @@ pub trait Migrate {
         table_name: &'e str,
     ) -> BoxFuture<'e, Result<(), MigrateError>>;
 
+    // ensure the migrations table can record who/what applied migrations,
+    // upgrading tables created by earlier versions if needed
+    // called after `ensure_migrations_table()`, only if audit info is to be recorded
+    // by default, `NotSupported` is returned before any migration is applied
+    fn ensure_audit_columns<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = table_name;
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "recording who applied migrations",
+            ))
+        })
+    }
+
     // Return the version on which the database is dirty or None otherwise.
     // "dirty" means there is a partially applied migration that failed.
     fn dirty_version<'e>(
@@ -52,17 +84,79 @@ pub trait Migrate {
         table_name: &'e str,
     ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>>;
 
+    // Return the list of applied repeatable migrations, keyed by
+    // [`repeatable_version()`][crate::migrate::repeatable_version]
+    // by default, none are listed, so repeatable migrations are applied on every run
+    fn list_applied_repeatable_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
+        let _ = table_name;
+
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
     // Should acquire a database lock so that only one migration process
     // can run at a time. [`Migrate`] will call this function before applying
     // any migrations.
-    fn lock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>>;
+    // Migrators using different migrations tables should not block each other.
+    fn lock<'e>(&'e mut self, table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>>;
 
     // Should release the lock. [`Migrate`] will call this function after all
     // migrations have been run.
-    fn unlock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>>;
+    fn unlock<'e>(&'e mut self, table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>>;
+
+    // Should attempt to acquire the same lock as [`Migrate::lock()`] without waiting,
+    // returning `false` if it is held elsewhere.
+    // by default, `NotSupported` is returned, so the lock can only be waited on without a timeout
+    fn try_lock<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        let _ = table_name;
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "acquiring the migration lock without waiting",
+            ))
+        })
+    }
+
+    // Should attempt to insert the lock row into the given lock table without waiting,
+    // creating the table if needed. Returns `false` if the row already exists.
+    // Used by [`TableLock`][crate::migrate::TableLock].
+    // by default, `NotSupported` is returned
+    fn try_lock_table<'e>(
+        &'e mut self,
+        lock_table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        let _ = lock_table_name;
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "locking migrations with a table",
+            ))
+        })
+    }
+
+    // Should delete the lock row from the given lock table.
+    // by default, `NotSupported` is returned
+    fn unlock_table<'e>(
+        &'e mut self,
+        lock_table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = lock_table_name;
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "locking migrations with a table",
+            ))
+        })
+    }
 
     // run SQL from migration in a DDL transaction
     // insert new row to [_migrations] table on completion (success or failure)
+    // for repeatable migrations, the row from any previous application is replaced
     // returns the time taking to run the migration SQL
     fn apply<'e>(
         &'e mut self,
@@ -70,6 +164,96 @@ pub trait Migrate {
         migration: &'e Migration,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>>;
 
+    // insert new row to [_migrations] table as if the migration was applied,
+    // without running its SQL
+    // by default, `NotSupported` is returned, so migrations cannot be baselined
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = (table_name, migration);
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "marking migrations as applied without running them",
+            ))
+        })
+    }
+
+    // record who/what applied the migration with the given version
+    // only used by the default `*_with_audit()` methods;
+    // by default, `NotSupported` is returned
+    fn record_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        version: i64,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = (table_name, version, audit);
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "recording who applied migrations",
+            ))
+        })
+    }
+
+    // like `apply()`, also recording who/what applied the migration
+    // should insert the audit info with the row of the migration, so that it cannot be lost;
+    // by default, it is recorded with `record_audit()` once the migration is applied
+    fn apply_with_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>>
+    where
+        Self: Send,
+    {
+        Box::pin(async move {
+            let elapsed = self.apply(table_name, migration).await?;
+            self.record_audit(table_name, migration.version, audit)
+                .await?;
+            Ok(elapsed)
+        })
+    }
+
+    // like `mark_applied()`, also recording who/what marked the migration as applied
+    // by default, it is recorded with `record_audit()` once the migration is marked
+    fn mark_applied_with_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<(), MigrateError>>
+    where
+        Self: Send,
+    {
+        Box::pin(async move {
+            self.mark_applied(table_name, migration).await?;
+            self.record_audit(table_name, migration.version, audit)
+                .await
+        })
+    }
+
+    // update the checksum (and description) recorded for an applied migration
+    // to those of the given migration, without running its SQL
+    // by default, `NotSupported` is returned, so checksums cannot be repaired
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        let _ = (table_name, migration);
+
+        Box::pin(async {
+            Err(MigrateError::NotSupported(
+                "updating the checksums of applied migrations",
+            ))
+        })
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
@@ -78,4 +262,16 @@ pub trait Migrate {
         table_name: &'e str,
         migration: &'e Migration,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>>;
+
+    // list the objects in the database (excluding system objects and those of extensions)
+    // with their definitions, ignoring the given tables and any objects belonging to them
+    // by default, drift cannot be detected and `SchemaSnapshotNotSupported` is returned
+    fn schema_snapshot<'e>(
+        &'e mut self,
+        exclude_tables: &'e [String],
+    ) -> BoxFuture<'e, Result<SchemaSnapshot, MigrateError>> {
+        let _ = exclude_tables;
+
+        Box::pin(async { Err(MigrateError::SchemaSnapshotNotSupported) })
+    }
 }
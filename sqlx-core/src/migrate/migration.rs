@@ -1,9 +1,11 @@
 use sha2::{Digest, Sha384};
 use std::borrow::Cow;
 
+use crate::database::Database;
 use crate::sql_str::SqlStr;
 
-use super::MigrationType;
+use super::normalize::{self, ChecksumNormalization};
+use super::{MigrationCode, MigrationType, RustMigration};
 
 #[derive(Debug, Clone)]
 pub struct Migration {
@@ -13,6 +15,8 @@ pub struct Migration {
     pub sql: SqlStr,
     pub checksum: Cow<'static, [u8]>,
     pub no_tx: bool,
+    /// Set for migrations implemented in Rust, which are run instead of `sql`.
+    pub code: Option<MigrationCode>,
 }
 
 impl Migration {
@@ -35,6 +39,68 @@ impl Migration {
         )
     }
 
+    /// Create a repeatable migration, identified by its description.
+    ///
+    /// See [`MigrationType::Repeatable`].
+    pub fn repeatable(description: Cow<'static, str>, sql: SqlStr, no_tx: bool) -> Self {
+        Self::new(
+            repeatable_version(&description),
+            description,
+            MigrationType::Repeatable,
+            sql,
+            no_tx,
+        )
+    }
+
+    /// Create a migration implemented in Rust.
+    ///
+    /// The checksum is computed from `source`, which should change whenever the behavior of the
+    /// migration does, e.g. `include_str!()` of the file implementing it.
+    ///
+    /// See [`RustMigration`].
+    pub fn code<DB: Database>(
+        version: i64,
+        description: Cow<'static, str>,
+        migration_type: MigrationType,
+        source: &str,
+        code: impl RustMigration<DB>,
+        no_tx: bool,
+    ) -> Self {
+        Migration {
+            code: Some(MigrationCode::new(code)),
+            ..Self::with_checksum(
+                version,
+                description,
+                migration_type,
+                SqlStr::from_static(""),
+                checksum(source).into(),
+                no_tx,
+            )
+        }
+    }
+
+    /// The versions of the migrations this migration depends on, as declared by
+    /// a `-- depends: <version>, ...` comment before its first statement.
+    ///
+    /// Used by [`Migrator::undo_last()`][crate::migrate::Migrator::undo_last] to refuse
+    /// to revert migrations that other applied migrations depend on.
+    pub fn dependencies(&self) -> Vec<i64> {
+        self.sql
+            .as_str()
+            .trim_start_matches('\u{FEFF}')
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with("--"))
+            .filter_map(|line| {
+                let line = line.trim_start_matches('-').trim_start();
+                line.strip_prefix("depends:")
+                    .or_else(|| line.strip_prefix("sqlx:depends:"))
+            })
+            .flat_map(|versions| versions.split([',', ' ']))
+            .filter_map(|version| version.trim().parse().ok())
+            .collect()
+    }
+
     pub(crate) fn with_checksum(
         version: i64,
         description: Cow<'static, str>,
@@ -50,16 +116,92 @@ impl Migration {
             sql,
             checksum,
             no_tx,
+            code: None,
         }
     }
 }
 
+/// Sort migrations in the order they are applied: versioned migrations by ascending version,
+/// followed by repeatable migrations by description.
+pub(crate) fn sort(migrations: &mut [Migration]) {
+    migrations.sort_by(|a, b| order(a).cmp(&order(b)));
+}
+
+pub(crate) fn order(migration: &Migration) -> (bool, i64, &str) {
+    if migration.migration_type.is_repeatable() {
+        (true, 0, &migration.description)
+    } else {
+        (false, migration.version, "")
+    }
+}
+
+/// Statements which lose data, checked for in down migrations.
+const DESTRUCTIVE_STATEMENTS: &[&str] = &[
+    "DROP TABLE",
+    "DROP COLUMN",
+    "DROP SCHEMA",
+    "DROP DATABASE",
+    "DROP MATERIALIZED VIEW",
+    "TRUNCATE",
+    "DELETE FROM",
+];
+
+/// Find a statement which loses data (e.g. `DROP TABLE`) in `sql`, ignoring comments and literals.
+pub(crate) fn destructive_statement(sql: &str) -> Option<&'static str> {
+    let normalized = normalize::normalize(sql, ChecksumNormalization::Comments);
+    let mut code = String::with_capacity(normalized.len());
+    let mut rest = normalized.as_str();
+
+    // Blank out string literals and quoted identifiers so they are never matched.
+    while let Some(start) = rest.find(['\'', '"', '$']) {
+        code.push_str(&rest[..start]);
+        let len = normalize::literal_len(&rest[start..]);
+        code.push(' ');
+        rest = &rest[start + len..];
+    }
+    code.push_str(rest);
+
+    let code = code.to_ascii_uppercase();
+
+    DESTRUCTIVE_STATEMENTS.iter().copied().find(|statement| {
+        code.match_indices(statement).any(|(i, _)| {
+            let before = code[..i].chars().next_back();
+            let after = code[i + statement.len()..].chars().next();
+            !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+        })
+    })
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Append `suffix` to the last segment of a (possibly schema-qualified and quoted) table name.
+pub(crate) fn suffix_table_name(table_name: &str, suffix: &str) -> String {
+    match table_name.strip_suffix('"') {
+        Some(unquoted) => format!("{unquoted}{suffix}\""),
+        None => format!("{table_name}{suffix}"),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AppliedMigration {
     pub version: i64,
     pub checksum: Cow<'static, [u8]>,
 }
 
+/// The key under which a repeatable migration is tracked in the migrations table.
+///
+/// Derived from the description so that it stays stable across edits to the migration,
+/// and always negative so it cannot collide with the version of a versioned migration.
+pub fn repeatable_version(description: &str) -> i64 {
+    let digest = Sha384::digest(description);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+
+    i64::from_be_bytes(bytes) | i64::MIN
+}
+
 pub fn checksum(sql: &str) -> Vec<u8> {
     Vec::from(Sha384::digest(sql).as_slice())
 }
@@ -74,6 +216,15 @@ pub fn checksum_fragments<'a>(fragments: impl Iterator<Item = &'a str>) -> Vec<u
     digest.finalize().to_vec()
 }
 
+#[test]
+fn repeatable_versions_are_negative_and_stable() {
+    let version = repeatable_version("create views");
+
+    assert!(version < 0);
+    assert_eq!(version, repeatable_version("create views"));
+    assert_ne!(version, repeatable_version("create functions"));
+}
+
 #[test]
 fn fragments_checksum_equals_full_checksum() {
     // Copied from `examples/postgres/axum-social-with-tests/migrations/3_comment.sql`
@@ -95,3 +246,41 @@ fn fragments_checksum_equals_full_checksum() {
 
     assert_eq!(fragments_checksum, full_checksum);
 }
+
+#[test]
+fn down_migrations_are_checked_for_destructive_statements() {
+    use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
+
+    let migration = Migration::new(
+        3,
+        "comments".into(),
+        MigrationType::ReversibleUp,
+        AssertSqlSafe("-- depends: 1, 2\n-- sqlx:depends: 5\nCREATE TABLE comments ();")
+            .into_sql_str(),
+        false,
+    );
+    assert_eq!(migration.dependencies(), [1, 2, 5]);
+
+    assert_eq!(
+        destructive_statement("drop  table comments;"),
+        Some("DROP TABLE")
+    );
+    assert_eq!(
+        destructive_statement("ALTER TABLE users\n    DROP COLUMN name;"),
+        Some("DROP COLUMN")
+    );
+    assert_eq!(
+        destructive_statement("DELETE FROM users WHERE id = $1"),
+        Some("DELETE FROM")
+    );
+
+    for sql in [
+        "DROP INDEX users_name_idx;",
+        "-- DROP TABLE users\nDROP VIEW active_users;",
+        "INSERT INTO log VALUES ('DROP TABLE users');",
+        "ALTER TABLE posts ADD FOREIGN KEY (user_id) REFERENCES users ON DELETE CASCADE;",
+        "DROP TABLESPACE_ALIAS;",
+    ] {
+        assert_eq!(destructive_statement(sql), None, "{sql}");
+    }
+}
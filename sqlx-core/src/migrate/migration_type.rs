@@ -1,5 +1,8 @@
 use super::Migrator;
 
+/// Filename prefix of repeatable migrations, which replaces `<VERSION>_`.
+pub const REPEATABLE_PREFIX: &str = "R__";
+
 /// Migration Type represents the type of migration
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum MigrationType {
@@ -13,6 +16,12 @@ pub enum MigrationType {
     /// ReversibleDown migrations represents the  delete or downgrade part of a reversible migrations
     /// It is expected the every migration of this type will have a corresponding up file
     ReversibleDown,
+
+    /// Repeatable migrations are identified by their description instead of a version
+    /// and are re-applied whenever their checksum changes (`R__<DESCRIPTION>.sql`).
+    ///
+    /// They are always applied after all versioned migrations.
+    Repeatable,
 }
 
 impl MigrationType {
@@ -31,6 +40,7 @@ impl MigrationType {
             MigrationType::Simple => false,
             MigrationType::ReversibleUp => true,
             MigrationType::ReversibleDown => true,
+            MigrationType::Repeatable => false,
         }
     }
 
@@ -39,6 +49,7 @@ impl MigrationType {
             MigrationType::Simple => true,
             MigrationType::ReversibleUp => true,
             MigrationType::ReversibleDown => false,
+            MigrationType::Repeatable => false,
         }
     }
 
@@ -47,14 +58,20 @@ impl MigrationType {
             MigrationType::Simple => false,
             MigrationType::ReversibleUp => false,
             MigrationType::ReversibleDown => true,
+            MigrationType::Repeatable => false,
         }
     }
 
+    pub fn is_repeatable(&self) -> bool {
+        matches!(self, MigrationType::Repeatable)
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             MigrationType::Simple => "migrate",
             MigrationType::ReversibleUp => "migrate",
             MigrationType::ReversibleDown => "revert",
+            MigrationType::Repeatable => "repeat",
         }
     }
 
@@ -63,6 +80,7 @@ impl MigrationType {
             MigrationType::Simple => ".sql",
             MigrationType::ReversibleUp => ".up.sql",
             MigrationType::ReversibleDown => ".down.sql",
+            MigrationType::Repeatable => ".sql",
         }
     }
 
@@ -71,12 +89,13 @@ impl MigrationType {
             MigrationType::Simple => "-- Add migration script here\n",
             MigrationType::ReversibleUp => "-- Add up migration script here\n",
             MigrationType::ReversibleDown => "-- Add down migration script here\n",
+            MigrationType::Repeatable => "-- Add repeatable migration script here\n",
         }
     }
 
     #[deprecated = "unused"]
     pub fn infer(migrator: &Migrator, reversible: bool) -> MigrationType {
-        match migrator.iter().last() {
+        match migrator.iter().rfind(|m| !m.migration_type.is_repeatable()) {
             Some(first_migration) => first_migration.migration_type,
             None => {
                 if reversible {
@@ -1,9 +1,15 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::migrate::{lock, migration};
+use crate::migrate::{
+    AdvisoryLock, AppliedMigration, AuditInfo, EventHandler, LockPolicy, Migrate, MigrateError,
+    Migration, MigrationEvent, MigrationLock, MigrationSource, MigrationType, SchemaChange,
+};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::slice;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// A resolved set of migrations, ready to be run.
 ///
@@ -17,6 +23,10 @@ pub struct Migrator {
     // const-promotable context. A `const fn` constructor isn't implicitly const-promotable.
     #[doc(hidden)]
     pub migrations: Cow<'static, [Migration]>,
+    /// Set by `migrate!()` for compressed migrations, which are decompressed on first use
+    /// instead of being stored in `migrations`.
+    #[doc(hidden)]
+    pub embedded: Option<fn() -> &'static [Migration]>,
     #[doc(hidden)]
     pub ignore_missing: bool,
     #[doc(hidden)]
@@ -25,19 +35,57 @@ pub struct Migrator {
     pub no_tx: bool,
     #[doc(hidden)]
     pub table_name: Cow<'static, str>,
+    #[doc(hidden)]
+    pub require_reversible: bool,
+    #[doc(hidden)]
+    pub allow_out_of_order: bool,
+    #[doc(hidden)]
+    pub lock_strategy: &'static dyn MigrationLock,
+    #[doc(hidden)]
+    pub lock_policy: LockPolicy,
+    #[doc(hidden)]
+    pub lock_timeout: Option<Duration>,
+    #[doc(hidden)]
+    pub event_handler: Option<EventHandler>,
+    #[doc(hidden)]
+    pub namespaces: Cow<'static, [MigrationNamespace]>,
+    #[doc(hidden)]
+    pub record_audit: bool,
+    #[doc(hidden)]
+    pub audit_info: Option<AuditInfo>,
 
     #[doc(hidden)]
     pub create_schemas: Cow<'static, [Cow<'static, str>]>,
 }
 
+/// Migrations from another source, tracked separately from the migrator's own migrations.
+///
+/// See [`Migrator::add_namespace()`].
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct MigrationNamespace {
+    pub name: Cow<'static, str>,
+    pub migrations: Cow<'static, [Migration]>,
+}
+
 impl Migrator {
     #[doc(hidden)]
     pub const DEFAULT: Migrator = Migrator {
         migrations: Cow::Borrowed(&[]),
+        embedded: None,
         ignore_missing: false,
         no_tx: false,
         locking: true,
         table_name: Cow::Borrowed("_sqlx_migrations"),
+        require_reversible: false,
+        allow_out_of_order: false,
+        lock_strategy: &AdvisoryLock,
+        lock_policy: LockPolicy::Wait,
+        lock_timeout: None,
+        event_handler: None,
+        namespaces: Cow::Borrowed(&[]),
+        record_audit: false,
+        audit_info: None,
         create_schemas: Cow::Borrowed(&[]),
     };
 
@@ -87,19 +135,85 @@ impl Migrator {
     ///  let m = Migrator::with_migrations(migrations);
     /// ```
     pub fn with_migrations(mut migrations: Vec<Migration>) -> Self {
-        // Ensure that we are sorted by version in ascending order.
-        migrations.sort_by_key(|m| m.version);
+        // Ensure that we are sorted by version in ascending order, with repeatable migrations last.
+        migration::sort(&mut migrations);
         Self {
             migrations: Cow::Owned(migrations),
             ..Self::DEFAULT
         }
     }
 
+    /// Register an additional migration, e.g. one implemented in Rust with [`Migration::code()`].
+    ///
+    /// Migrations are kept in the order they are applied, regardless of the order they are added.
+    pub fn add_migration(&mut self, migration: Migration) -> &mut Self {
+        if let Some(embedded) = self.embedded.take() {
+            self.migrations = Cow::Borrowed(embedded());
+        }
+
+        let migrations = self.migrations.to_mut();
+        migrations.push(migration);
+        migration::sort(migrations);
+        self
+    }
+
+    /// Add the migrations of another migrator (e.g. the `migrate!()` of a library crate)
+    /// under `namespace`.
+    ///
+    /// Namespaced migrations are versioned independently and tracked in their own table,
+    /// named after the migrations table with a `__{namespace}` suffix
+    /// (e.g. `_sqlx_migrations__auth`), so `namespace` should be a valid unquoted identifier.
+    ///
+    /// [`Self::run()`] applies the migrations of each namespace, in the order they were added,
+    /// before the migrator's own migrations, using the settings of this migrator.
+    /// [`Self::undo()`], [`Self::baseline()`] and the target version of [`Self::run_to()`]
+    /// only apply to the migrator's own migrations.
+    ///
+    /// Namespaces are only supported by this API: `sqlx migrate` and `migrate!()` handle a single
+    /// set of migrations, so a migrator with namespaces must be built and run from Rust code.
+    pub fn add_namespace(
+        &mut self,
+        namespace: impl Into<Cow<'static, str>>,
+        migrator: &Migrator,
+    ) -> &mut Self {
+        self.namespaces.to_mut().push(MigrationNamespace {
+            name: namespace.into(),
+            migrations: match migrator.embedded {
+                Some(embedded) => Cow::Borrowed(embedded()),
+                None => migrator.migrations.clone(),
+            },
+        });
+        self
+    }
+
+    /// Resolve the migrations of `source` and add them under `namespace`.
+    ///
+    /// See [`Self::add_namespace()`].
+    pub async fn add_source<'s, S>(
+        &mut self,
+        namespace: impl Into<Cow<'static, str>>,
+        source: S,
+    ) -> Result<&mut Self, MigrateError>
+    where
+        S: MigrationSource<'s>,
+    {
+        let mut migrations = source.resolve().await.map_err(MigrateError::Source)?;
+        migration::sort(&mut migrations);
+
+        self.namespaces.to_mut().push(MigrationNamespace {
+            name: namespace.into(),
+            migrations: Cow::Owned(migrations),
+        });
+        Ok(self)
+    }
+
     /// Override the name of the table used to track executed migrations.
     ///
     /// May be schema-qualified and/or contain quotes. Defaults to `_sqlx_migrations`.
     ///
-    /// Potentially useful for multi-tenant databases.
+    /// Potentially useful for multi-tenant databases, or to let multiple independent applications
+    /// share one database: each tracks its own migrations, and with the default
+    /// [`AdvisoryLock`], migrators using different tables don't block each other.
     ///
     /// ### Warning: Potential Data Loss or Corruption!
     /// Changing this option for a production database will likely result in data loss or corruption
@@ -137,14 +251,158 @@ impl Migrator {
     ///
     /// This should only be used if the database does not support locking, e.g. CockroachDB which talks the Postgres
     /// protocol but does not support advisory locks used by SQLx's migrations support for Postgres.
+    ///
+    /// Consider using [`TableLock`] with [`Self::set_lock_strategy()`] in that case instead.
     pub fn set_locking(&mut self, locking: bool) -> &mut Self {
         self.locking = locking;
         self
     }
 
+    /// Specify how to lock the database during migration. Defaults to [`AdvisoryLock`].
+    ///
+    /// Has no effect if locking is disabled with [`Self::set_locking()`].
+    pub fn set_lock_strategy(&mut self, lock_strategy: &'static dyn MigrationLock) -> &mut Self {
+        self.lock_strategy = lock_strategy;
+        self
+    }
+
+    /// Specify whether to wait for or fail on a migration lock held by another migrator.
+    /// Defaults to [`LockPolicy::Wait`].
+    pub fn set_lock_policy(&mut self, lock_policy: LockPolicy) -> &mut Self {
+        self.lock_policy = lock_policy;
+        self
+    }
+
+    /// Specify the maximum time to wait for the migration lock with [`LockPolicy::Wait`].
+    /// Defaults to `None` (wait indefinitely).
+    ///
+    /// If set, the lock is polled instead of waited on.
+    pub fn set_lock_timeout(&mut self, lock_timeout: Option<Duration>) -> &mut Self {
+        self.lock_timeout = lock_timeout;
+        self
+    }
+
+    /// Set a handler to be called with the progress of each migration applied by [`Self::run()`].
+    ///
+    /// The handler is called with [`MigrationEvent::Started`] before each migration is applied,
+    /// and with [`MigrationEvent::Applied`] or [`MigrationEvent::Failed`] after, so long-running
+    /// migrations can report their status (e.g. to a log or deployment dashboard).
+    ///
+    /// The handler is called synchronously from the migrating task and should not block.
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::migrate::{MigrationEvent, Migrator};
+    /// # fn example(migrator: &mut Migrator) {
+    /// migrator.set_event_handler(|event| match event {
+    ///     MigrationEvent::Started { migration } => {
+    ///         println!("applying {}/{}", migration.version, migration.description)
+    ///     }
+    ///     MigrationEvent::Applied { migration, elapsed } => {
+    ///         println!("applied {} in {elapsed:?}", migration.version)
+    ///     }
+    ///     MigrationEvent::Failed { migration, error } => {
+    ///         println!("failed to apply {}: {error}", migration.version)
+    ///     }
+    ///     _ => {}
+    /// });
+    /// # }
+    /// ```
+    pub fn set_event_handler<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: Fn(MigrationEvent<'_>) + Send + Sync + 'static,
+    {
+        self.event_handler = Some(EventHandler(Arc::new(handler)));
+        self
+    }
+
+    /// Specify whether to record who applied each migration. Defaults to `false`.
+    ///
+    /// If set, the `applied_by` and `application` columns of the migrations table are set for
+    /// each migration applied by [`Self::run()`] or [`Self::baseline()`], from
+    /// [`AuditInfo::from_env()`] unless overridden with [`Self::set_audit_info()`].
+    /// The time each migration was applied and how long it took are always recorded.
+    ///
+    /// Migrations tables created by older versions of SQLx are upgraded automatically
+    /// to add these columns, the first time audit info is recorded.
+    pub fn set_record_audit(&mut self, record_audit: bool) -> &mut Self {
+        self.record_audit = record_audit;
+        self
+    }
+
+    /// Record `audit_info` for each migration applied, instead of collecting it
+    /// from the environment.
+    ///
+    /// Implies [`Self::set_record_audit(true)`][Self::set_record_audit].
+    pub fn set_audit_info(&mut self, audit_info: AuditInfo) -> &mut Self {
+        self.record_audit = true;
+        self.audit_info = Some(audit_info);
+        self
+    }
+
+    /// Specify whether every up migration must have a corresponding down migration.
+    /// Defaults to `false`.
+    ///
+    /// If set, [`Self::run()`] and [`Self::undo()`] will fail with
+    /// [`MigrateError::MissingDownMigration`] before touching the database
+    /// if any migration cannot be reverted.
+    ///
+    /// See also [`Self::validate_reversible()`].
+    pub fn set_require_reversible(&mut self, require_reversible: bool) -> &mut Self {
+        self.require_reversible = require_reversible;
+        self
+    }
+
+    /// Check that every up migration has a corresponding down migration.
+    ///
+    /// Simple (non-reversible) migrations are considered to be missing their down migration.
+    pub fn validate_reversible(&self) -> Result<(), MigrateError> {
+        for (_, migrations) in self.migration_sets() {
+            let down_versions: HashSet<_> = migrations
+                .iter()
+                .filter(|m| m.migration_type.is_down_migration())
+                .map(|m| m.version)
+                .collect();
+
+            for migration in migrations {
+                if migration.migration_type.is_up_migration()
+                    && !down_versions.contains(&migration.version)
+                {
+                    return Err(MigrateError::MissingDownMigration(migration.version));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Specify whether pending migrations older than the latest applied migration
+    /// may be applied. Defaults to `false`.
+    ///
+    /// By default, [`Self::run()`] fails with [`MigrateError::VersionTooOld`] if it finds such a
+    /// migration, which typically happens when migrations were added on separate branches with
+    /// interleaved versions.
+    ///
+    /// If set, these migrations are applied in version order along with any other pending
+    /// migrations, and a warning is logged for each of them.
+    ///
+    /// ### Warning
+    /// Out-of-order migrations are applied against a schema that already includes the changes
+    /// of newer migrations. Make sure they don't depend on the schema as it was at their version.
+    pub fn set_allow_out_of_order(&mut self, allow_out_of_order: bool) -> &mut Self {
+        self.allow_out_of_order = allow_out_of_order;
+        self
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
-        self.migrations.iter()
+        self.migrations().iter()
+    }
+
+    fn migrations(&self) -> &[Migration] {
+        match self.embedded {
+            Some(embedded) => embedded(),
+            None => &self.migrations,
+        }
     }
 
     /// Check if a migration version exists.
@@ -154,19 +412,28 @@ impl Migrator {
 
     /// Run any pending migrations against the database; and, validate previously applied migrations
     /// against the current migration source to detect accidental changes in previously-applied migrations.
+    ///
+    /// Repeatable migrations are (re-)applied afterwards if they are new or their checksum changed.
     pub async fn run<'a, A>(&self, migrator: A) -> Result<(), MigrateError>
     where
         A: Acquire<'a>,
-        <A::Connection as Deref>::Target: Migrate,
+        <A::Connection as Deref>::Target: Migrate + Send,
     {
         let mut conn = migrator.acquire().await?;
         self.run_direct(None, &mut *conn).await
     }
 
+    /// Migrate the database to a specific version, e.g. to test the upgrade path from a release.
+    ///
+    /// Pending migrations up to and including `target` are applied, as in [`Self::run()`].
+    /// If migrations newer than `target` are already applied, they are reverted first,
+    /// as in [`Self::undo()`].
+    ///
+    /// Repeatable migrations are only (re-)applied if `target` is at or after the latest version.
     pub async fn run_to<'a, A>(&self, target: i64, migrator: A) -> Result<(), MigrateError>
     where
         A: Acquire<'a>,
-        <A::Connection as Deref>::Target: Migrate,
+        <A::Connection as Deref>::Target: Migrate + Send,
     {
         let mut conn = migrator.acquire().await?;
         self.run_direct(Some(target), &mut *conn).await
@@ -176,41 +443,197 @@ impl Migrator {
     #[doc(hidden)]
     pub async fn run_direct<C>(&self, target: Option<i64>, conn: &mut C) -> Result<(), MigrateError>
     where
-        C: Migrate,
+        C: Migrate + Send,
     {
+        if self.require_reversible {
+            self.validate_reversible()?;
+        }
+
         // lock the database for exclusive access by the migrator
-        if self.locking {
-            conn.lock().await?;
+        self.lock(conn).await?;
+
+        let result = async {
+            for schema_name in self.create_schemas.iter() {
+                conn.create_schema_if_not_exists(schema_name).await?;
+            }
+
+            if let Some(target) = target {
+                self.revert_to(target, conn).await?;
+            }
+
+            // Check every set of migrations before applying any of them.
+            let mut plan = Vec::new();
+
+            for (table_name, migrations) in self.migration_sets() {
+                // creates [_migrations] table only if needed
+                // eventually this will likely migrate previous versions of the table
+                conn.ensure_migrations_table(&table_name).await?;
+
+                if self.record_audit {
+                    conn.ensure_audit_columns(&table_name).await?;
+                }
+
+                let target = target.filter(|_| table_name == self.table_name);
+                let pending = self
+                    .read_pending(migrations, &table_name, target, conn)
+                    .await?;
+                plan.push((table_name, pending));
+            }
+
+            for (_, pending) in &plan {
+                pending.warn_out_of_order();
+            }
+
+            let audit = self.audit_info();
+
+            for (table_name, migration) in plan.iter().flat_map(|(table_name, pending)| {
+                pending.migrations.iter().map(move |m| (table_name, *m))
+            }) {
+                self.emit(MigrationEvent::Started { migration });
+
+                let applied = match &audit {
+                    Some(audit) => conn.apply_with_audit(table_name, migration, audit).await,
+                    None => conn.apply(table_name, migration).await,
+                };
+
+                match applied {
+                    Ok(elapsed) => self.emit(MigrationEvent::Applied { migration, elapsed }),
+                    Err(error) => {
+                        self.emit(MigrationEvent::Failed {
+                            migration,
+                            error: &error,
+                        });
+                        return Err(error);
+                    }
+                }
+            }
+
+            Ok::<_, MigrateError>(())
+        }
+        .await;
+
+        // unlock the migrator to allow other migrators to run, even if this failed
+        self.unlock_after(conn, result).await
+    }
+
+    /// Return the migrations that [`Self::run()`] would apply, in order, without applying them.
+    ///
+    /// Previously applied migrations are validated the same way, so this returns the same errors
+    /// `run()` would (e.g. [`MigrateError::VersionMismatch`]) before it applied anything.
+    ///
+    /// Nothing is written to the database, and the database is not locked; the plan may be
+    /// outdated by the time it is run if migrations are applied concurrently.
+    pub async fn plan<'a, A>(&self, migrator: A) -> Result<Vec<&Migration>, MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.plan_direct(None, &mut *conn).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn plan_direct<C>(
+        &self,
+        target: Option<i64>,
+        conn: &mut C,
+    ) -> Result<Vec<&Migration>, MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        if self.require_reversible {
+            self.validate_reversible()?;
         }
 
-        for schema_name in self.create_schemas.iter() {
-            conn.create_schema_if_not_exists(schema_name).await?;
+        let mut plan = Vec::new();
+
+        for (table_name, migrations) in self.migration_sets() {
+            let target = target.filter(|_| table_name == self.table_name);
+
+            if conn.migrations_table_exists(&table_name).await? {
+                plan.push(
+                    self.read_pending(migrations, &table_name, target, conn)
+                        .await?,
+                );
+            } else {
+                plan.push(self.pending(migrations, target, &[], &[])?);
+            }
         }
 
-        // creates [_migrations] table only if needed
-        // eventually this will likely migrate previous versions of the table
-        conn.ensure_migrations_table(&self.table_name).await?;
+        for pending in &plan {
+            pending.warn_out_of_order();
+        }
 
-        let version = conn.dirty_version(&self.table_name).await?;
+        Ok(plan
+            .into_iter()
+            .flat_map(|pending| pending.migrations)
+            .collect())
+    }
+
+    /// The migrations of each namespace followed by the migrator's own migrations,
+    /// along with the name of the table they are tracked in.
+    fn migration_sets(&self) -> impl Iterator<Item = (Cow<'_, str>, &[Migration])> {
+        self.namespaces
+            .iter()
+            .map(|namespace| {
+                let suffix = format!("__{}", namespace.name);
+                let table_name = migration::suffix_table_name(&self.table_name, &suffix);
+                (Cow::Owned(table_name), &*namespace.migrations)
+            })
+            .chain([(Cow::Borrowed(&*self.table_name), self.migrations())])
+    }
+
+    async fn read_pending<'m, C>(
+        &self,
+        migrations: &'m [Migration],
+        table_name: &str,
+        target: Option<i64>,
+        conn: &mut C,
+    ) -> Result<Pending<'m>, MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        let version = conn.dirty_version(table_name).await?;
         if let Some(version) = version {
             return Err(MigrateError::Dirty(version));
         }
 
-        let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
-        validate_applied_migrations(&applied_migrations, self)?;
+        let applied_migrations = conn.list_applied_migrations(table_name).await?;
+        validate_applied_migrations(&applied_migrations, migrations, self.ignore_missing)?;
 
-        let applied_migrations: HashMap<_, _> = applied_migrations
-            .into_iter()
-            .map(|m| (m.version, m))
-            .collect();
+        let applied_repeatable = conn.list_applied_repeatable_migrations(table_name).await?;
+
+        self.pending(migrations, target, &applied_migrations, &applied_repeatable)
+    }
+
+    /// Determine the migrations to apply, in order, given those that are already applied.
+    fn pending<'m>(
+        &self,
+        migrations: &'m [Migration],
+        target: Option<i64>,
+        applied_migrations: &[AppliedMigration],
+        applied_repeatable: &[AppliedMigration],
+    ) -> Result<Pending<'m>, MigrateError> {
+        let latest_version = applied_migrations.iter().map(|m| m.version).max();
 
-        for migration in self.iter() {
+        let applied_migrations: HashMap<_, _> =
+            applied_migrations.iter().map(|m| (m.version, m)).collect();
+
+        let mut pending = Pending {
+            migrations: Vec::new(),
+            out_of_order: Vec::new(),
+        };
+
+        for migration in migrations {
             if target.is_some_and(|target| target < migration.version) {
                 // Target version reached
                 break;
             }
 
-            if migration.migration_type.is_down_migration() {
+            if migration.migration_type.is_down_migration()
+                || migration.migration_type.is_repeatable()
+            {
                 continue;
             }
 
@@ -221,33 +644,420 @@ impl Migrator {
                     }
                 }
                 None => {
-                    conn.apply(&self.table_name, migration).await?;
+                    if let Some(latest_version) =
+                        latest_version.filter(|latest| *latest > migration.version)
+                    {
+                        if !self.allow_out_of_order {
+                            return Err(MigrateError::VersionTooOld(
+                                migration.version,
+                                latest_version,
+                            ));
+                        }
+
+                        pending
+                            .out_of_order
+                            .push((migration.version, latest_version));
+                    }
+
+                    pending.migrations.push(migration);
                 }
             }
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        if self.locking {
-            conn.unlock().await?;
+        // Repeatable migrations may depend on any versioned migration,
+        // so they are only applied once all of those are.
+        let reached_latest = target.is_none_or(|target| {
+            migrations
+                .iter()
+                .filter(|m| m.migration_type.is_up_migration())
+                .all(|m| m.version <= target)
+        });
+
+        if reached_latest {
+            let applied_repeatable: HashMap<_, _> = applied_repeatable
+                .iter()
+                .map(|m| (m.version, &m.checksum))
+                .collect();
+
+            pending.migrations.extend(migrations.iter().filter(|m| {
+                m.migration_type.is_repeatable()
+                    && applied_repeatable.get(&m.version) != Some(&&m.checksum)
+            }));
         }
 
-        Ok(())
+        Ok(pending)
+    }
+
+    /// Mark all migrations up to and including `version` as applied, without running them.
+    ///
+    /// This allows adopting SQLx migrations for an existing database whose schema was created
+    /// by other means: write migrations reproducing the current schema, then baseline the
+    /// database at the latest of those so only newer migrations are run against it.
+    ///
+    /// Migrations that are already applied are validated as in [`Self::run()`] but otherwise
+    /// left untouched. Repeatable migrations are not affected.
+    pub async fn baseline<'a, A>(&self, version: i64, migrator: A) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.baseline_direct(version, &mut *conn).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn baseline_direct<C>(&self, version: i64, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        if !self.version_exists(version) {
+            return Err(MigrateError::VersionNotPresent(version));
+        }
+
+        // lock the database for exclusive access by the migrator
+        self.lock(conn).await?;
+
+        let result = async {
+            for schema_name in self.create_schemas.iter() {
+                conn.create_schema_if_not_exists(schema_name).await?;
+            }
+
+            // creates [_migrations] table only if needed
+            // eventually this will likely migrate previous versions of the table
+            conn.ensure_migrations_table(&self.table_name).await?;
+
+            if self.record_audit {
+                conn.ensure_audit_columns(&self.table_name).await?;
+            }
+
+            let dirty_version = conn.dirty_version(&self.table_name).await?;
+            if let Some(dirty_version) = dirty_version {
+                return Err(MigrateError::Dirty(dirty_version));
+            }
+
+            let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
+            validate_applied_migrations(
+                &applied_migrations,
+                self.migrations(),
+                self.ignore_missing,
+            )?;
+
+            let applied_migrations: HashMap<_, _> = applied_migrations
+                .into_iter()
+                .map(|m| (m.version, m))
+                .collect();
+
+            let audit = self.audit_info();
+
+            for migration in self
+                .iter()
+                .filter(|m| m.migration_type.is_up_migration())
+                .take_while(|m| m.version <= version)
+            {
+                match applied_migrations.get(&migration.version) {
+                    Some(applied_migration) => {
+                        if migration.checksum != applied_migration.checksum {
+                            return Err(MigrateError::VersionMismatch(migration.version));
+                        }
+                    }
+                    None => match &audit {
+                        Some(audit) => {
+                            conn.mark_applied_with_audit(&self.table_name, migration, audit)
+                                .await?
+                        }
+                        None => conn.mark_applied(&self.table_name, migration).await?,
+                    },
+                }
+            }
+
+            Ok::<_, MigrateError>(())
+        }
+        .await;
+
+        // unlock the migrator to allow other migrators to run, even if this failed
+        self.unlock_after(conn, result).await
+    }
+
+    /// Update the checksums recorded for applied migrations to match the current migrations,
+    /// without running them; returns the versions that were updated.
+    ///
+    /// Use this after intentionally changing applied migrations in ways that don't affect the
+    /// schema (e.g. reformatting them, or changing how they are hashed with
+    /// [`ResolveConfig::set_checksum_normalization()`][crate::migrate::ResolveConfig::set_checksum_normalization]),
+    /// which would otherwise make [`Self::run()`] fail with [`MigrateError::VersionMismatch`].
+    ///
+    /// ### Warning
+    /// This accepts _any_ change to applied migrations, including ones that were never applied
+    /// to the database. Only use this after checking that every change is intentional.
+    pub async fn repair<'a, A>(&self, migrator: A) -> Result<Vec<i64>, MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.repair_direct(&mut *conn).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn repair_direct<C>(&self, conn: &mut C) -> Result<Vec<i64>, MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        // lock the database for exclusive access by the migrator
+        self.lock(conn).await?;
+
+        let result = async {
+            let mut repaired = Vec::new();
+
+            for (table_name, migrations) in self.migration_sets() {
+                // creates [_migrations] table only if needed
+                // eventually this will likely migrate previous versions of the table
+                conn.ensure_migrations_table(&table_name).await?;
+
+                let dirty_version = conn.dirty_version(&table_name).await?;
+                if let Some(dirty_version) = dirty_version {
+                    return Err(MigrateError::Dirty(dirty_version));
+                }
+
+                let applied_migrations: HashMap<_, _> = conn
+                    .list_applied_migrations(&table_name)
+                    .await?
+                    .into_iter()
+                    .map(|m| (m.version, m.checksum))
+                    .collect();
+
+                for migration in migrations
+                    .iter()
+                    .filter(|m| m.migration_type.is_up_migration())
+                {
+                    if applied_migrations
+                        .get(&migration.version)
+                        .is_some_and(|checksum| *checksum != migration.checksum)
+                    {
+                        conn.update_checksum(&table_name, migration).await?;
+                        repaired.push(migration.version);
+                    }
+                }
+            }
+
+            Ok::<_, MigrateError>(repaired)
+        }
+        .await;
+
+        // unlock the migrator to allow other migrators to run, even if this failed
+        self.unlock_after(conn, result).await
+    }
+
+    /// Compare the schema of a database with the schema produced by the migrations,
+    /// reporting objects that were created, changed or dropped outside of migrations
+    /// (e.g. by a manual hotfix).
+    ///
+    /// The expected schema is produced by running the migrations against `scratch`,
+    /// a connection to an empty database, e.g. one created for this purpose with
+    /// [`MigrateDatabase::create_database()`][crate::migrate::MigrateDatabase::create_database]
+    /// and dropped afterwards.
+    ///
+    /// Migrations tables are ignored, but pending migrations are reported as drift,
+    /// so this should be used against a fully migrated database.
+    ///
+    /// Returns an empty list if the schemas match.
+    pub async fn detect_drift<'a, 'b, A, B>(
+        &self,
+        migrator: A,
+        scratch: B,
+    ) -> Result<Vec<SchemaChange>, MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
+        B: Acquire<'b>,
+        <B::Connection as Deref>::Target: Migrate + Send,
+    {
+        let mut conn = migrator.acquire().await?;
+        let mut scratch = scratch.acquire().await?;
+        self.detect_drift_direct(&mut *conn, &mut *scratch).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn detect_drift_direct<C, S>(
+        &self,
+        conn: &mut C,
+        scratch: &mut S,
+    ) -> Result<Vec<SchemaChange>, MigrateError>
+    where
+        C: Migrate + Send,
+        S: Migrate + Send,
+    {
+        self.run_direct(None, scratch).await?;
+
+        let exclude_tables: Vec<String> = self
+            .migration_sets()
+            .flat_map(|(table_name, _)| {
+                let lock_table_name = migration::suffix_table_name(&table_name, "_lock");
+                [table_name.into_owned(), lock_table_name]
+            })
+            .collect();
+
+        let expected = scratch.schema_snapshot(&exclude_tables).await?;
+        let actual = conn.schema_snapshot(&exclude_tables).await?;
+
+        Ok(expected.diff(&actual))
     }
 
     /// Run down migrations against the database until a specific version.
+    ///
+    /// Every applied migration with a version greater than `target` is reverted, newest first.
+    /// Pass `0` to revert all applied migrations.
+    ///
+    /// Simple migrations, which have no down migration, are skipped and remain applied.
+    /// If [`Self::set_require_reversible()`] is set, this instead returns
+    /// [`MigrateError::MissingDownMigration`] without reverting anything.
     pub async fn undo<'a, A>(&self, migrator: A, target: i64) -> Result<(), MigrateError>
     where
         A: Acquire<'a>,
-        <A::Connection as Deref>::Target: Migrate,
+        <A::Connection as Deref>::Target: Migrate + Send,
+    {
+        let mut conn = migrator.acquire().await?;
+        self.undo_direct(target, &mut *conn).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn undo_direct<C>(&self, target: i64, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        if self.require_reversible {
+            self.validate_reversible()?;
+        }
+
+        // lock the database for exclusive access by the migrator
+        self.lock(conn).await?;
+
+        let result = self.revert_to(target, conn).await;
+
+        // unlock the migrator to allow other migrators to run, even if this failed
+        self.unlock_after(conn, result).await
+    }
+
+    /// Revert the `n` most recent applied migrations (by version), newest first.
+    ///
+    /// Nothing is reverted unless:
+    /// * every one of them has a down migration; otherwise this returns
+    ///   [`MigrateError::MissingDownMigration`].
+    /// * no other applied migration depends on them, as declared with a `-- depends:` comment
+    ///   (see [`Migration::dependencies()`]), which is possible if migrations were applied
+    ///   out of order; otherwise this returns [`MigrateError::Dependency`].
+    /// * unless `force` is set, none of their down migrations contains a destructive statement,
+    ///   such as `DROP TABLE`, `DROP COLUMN`, `TRUNCATE` or `DELETE FROM`; otherwise this returns
+    ///   [`MigrateError::Destructive`].
+    pub async fn undo_last<'a, A>(
+        &self,
+        migrator: A,
+        n: usize,
+        force: bool,
+    ) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
     {
         let mut conn = migrator.acquire().await?;
+        self.undo_last_direct(n, force, &mut *conn).await
+    }
+
+    // Getting around the annoying "implementation of `Acquire` is not general enough" error
+    #[doc(hidden)]
+    pub async fn undo_last_direct<C>(
+        &self,
+        n: usize,
+        force: bool,
+        conn: &mut C,
+    ) -> Result<(), MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        if self.require_reversible {
+            self.validate_reversible()?;
+        }
 
         // lock the database for exclusive access by the migrator
-        if self.locking {
-            conn.lock().await?;
+        self.lock(conn).await?;
+
+        let result = async {
+            let applied_migrations = self.read_applied(conn).await?;
+            let (kept, reverted) =
+                applied_migrations.split_at(applied_migrations.len().saturating_sub(n));
+
+            let to_revert = self.down_migrations(reverted.iter().rev(), false)?;
+
+            for kept in kept {
+                let dependencies = self
+                    .iter()
+                    .filter(|m| m.version == kept.version && m.migration_type.is_up_migration())
+                    .flat_map(|m| m.dependencies());
+
+                for dependency in dependencies {
+                    if reverted.iter().any(|m| m.version == dependency) {
+                        return Err(MigrateError::Dependency(dependency, kept.version));
+                    }
+                }
+            }
+
+            if !force {
+                for migration in &to_revert {
+                    if let Some(statement) =
+                        migration::destructive_statement(migration.sql.as_str())
+                    {
+                        return Err(MigrateError::Destructive(
+                            migration.version,
+                            statement.to_owned(),
+                        ));
+                    }
+                }
+            }
+
+            for migration in to_revert {
+                conn.revert(&self.table_name, migration).await?;
+            }
+
+            Ok::<_, MigrateError>(())
+        }
+        .await;
+
+        // unlock the migrator to allow other migrators to run, even if this failed
+        self.unlock_after(conn, result).await
+    }
+
+    /// Revert every applied migration with a version greater than `target`, newest first.
+    async fn revert_to<C>(&self, target: i64, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        let applied_migrations = self.read_applied(conn).await?;
+
+        let to_revert = self.down_migrations(
+            applied_migrations
+                .iter()
+                .rev()
+                .filter(|m| m.version > target),
+            !self.require_reversible,
+        )?;
+
+        for migration in to_revert {
+            conn.revert(&self.table_name, migration).await?;
         }
 
+        Ok(())
+    }
+
+    /// Read and validate the migrations applied from this migrator's own migrations,
+    /// ordered by version.
+    async fn read_applied<C>(&self, conn: &mut C) -> Result<Vec<AppliedMigration>, MigrateError>
+    where
+        C: Migrate + Send,
+    {
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
         conn.ensure_migrations_table(&self.table_name).await?;
@@ -258,42 +1068,126 @@ impl Migrator {
         }
 
         let applied_migrations = conn.list_applied_migrations(&self.table_name).await?;
-        validate_applied_migrations(&applied_migrations, self)?;
+        validate_applied_migrations(&applied_migrations, self.migrations(), self.ignore_missing)?;
 
-        let applied_migrations: HashMap<_, _> = applied_migrations
-            .into_iter()
-            .map(|m| (m.version, m))
-            .collect();
+        Ok(applied_migrations)
+    }
 
-        for migration in self
+    /// Resolve the down migrations reverting `applied`, in the same order, skipping simple
+    /// migrations if `skip_simple` is set.
+    ///
+    /// Every down migration is resolved up front so we never leave the database half-reverted
+    /// because of a migration that cannot be undone.
+    fn down_migrations<'m>(
+        &'m self,
+        applied: impl Iterator<Item = &'m AppliedMigration>,
+        skip_simple: bool,
+    ) -> Result<Vec<&'m Migration>, MigrateError> {
+        let down_migrations: HashMap<_, _> = self
             .iter()
-            .rev()
             .filter(|m| m.migration_type.is_down_migration())
-            .filter(|m| applied_migrations.contains_key(&m.version))
-            .filter(|m| m.version > target)
-        {
-            conn.revert(&self.table_name, migration).await?;
+            .map(|m| (m.version, m))
+            .collect();
+
+        let mut to_revert = Vec::new();
+
+        for applied_migration in applied {
+            match down_migrations.get(&applied_migration.version) {
+                Some(migration) => to_revert.push(*migration),
+                // Only reachable with `ignore_missing`; nothing to revert.
+                None if !self.version_exists(applied_migration.version) => {}
+                None if skip_simple
+                    && self.iter().any(|m| {
+                        m.version == applied_migration.version
+                            && matches!(m.migration_type, MigrationType::Simple)
+                    }) => {}
+                None => {
+                    return Err(MigrateError::MissingDownMigration(
+                        applied_migration.version,
+                    ))
+                }
+            }
+        }
+
+        Ok(to_revert)
+    }
+
+    async fn lock<C>(&self, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        if !self.locking {
+            return Ok(());
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        if self.locking {
-            conn.unlock().await?;
+        lock::acquire(
+            self.lock_strategy,
+            self.lock_policy,
+            self.lock_timeout,
+            conn,
+            &self.table_name,
+        )
+        .await
+    }
+
+    fn audit_info(&self) -> Option<Cow<'_, AuditInfo>> {
+        if !self.record_audit {
+            return None;
         }
 
-        Ok(())
+        Some(match &self.audit_info {
+            Some(audit_info) => Cow::Borrowed(audit_info),
+            None => Cow::Owned(AuditInfo::from_env()),
+        })
+    }
+
+    fn emit(&self, event: MigrationEvent<'_>) {
+        if let Some(handler) = &self.event_handler {
+            (handler.0)(event);
+        }
+    }
+
+    async fn unlock<C>(&self, conn: &mut C) -> Result<(), MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        if !self.locking {
+            return Ok(());
+        }
+
+        self.lock_strategy.unlock(conn, &self.table_name).await
+    }
+
+    /// Release the lock taken by [`Self::lock()`] after running an operation with it, whether or
+    /// not the operation succeeded, so a failure does not leave the database locked.
+    ///
+    /// Returns the result of the operation, or the error unlocking if it succeeded.
+    async fn unlock_after<C, T>(
+        &self,
+        conn: &mut C,
+        result: Result<T, MigrateError>,
+    ) -> Result<T, MigrateError>
+    where
+        C: Migrate + Send,
+    {
+        let unlocked = self.unlock(conn).await;
+        let value = result?;
+        unlocked?;
+
+        Ok(value)
     }
 }
 
 fn validate_applied_migrations(
     applied_migrations: &[AppliedMigration],
-    migrator: &Migrator,
+    migrations: &[Migration],
+    ignore_missing: bool,
 ) -> Result<(), MigrateError> {
-    if migrator.ignore_missing {
+    if ignore_missing {
         return Ok(());
     }
 
-    let migrations: HashSet<_> = migrator.iter().map(|m| m.version).collect();
+    let migrations: HashSet<_> = migrations.iter().map(|m| m.version).collect();
 
     for applied_migration in applied_migrations {
         if !migrations.contains(&applied_migration.version) {
@@ -303,3 +1197,479 @@ fn validate_applied_migrations(
 
     Ok(())
 }
+
+/// The migrations to apply to one migrations table, in order.
+struct Pending<'m> {
+    migrations: Vec<&'m Migration>,
+    /// The version of each migration applied out of order, with the latest applied version.
+    out_of_order: Vec<(i64, i64)>,
+}
+
+impl Pending<'_> {
+    /// Warn of the migrations applied out of order, once the whole plan is validated.
+    fn warn_out_of_order(&self) {
+        for &(version, latest_version) in &self.out_of_order {
+            tracing::warn!(
+                target: "sqlx::migrate",
+                version,
+                latest_version,
+                "migration is applied out of order"
+            );
+        }
+    }
+}
+
+#[test]
+fn pending_migrations_are_planned_in_order() {
+    use crate::migrate::MigrationType;
+    use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
+
+    let up = |version: i64| {
+        Migration::new(
+            version,
+            format!("migration {version}").into(),
+            MigrationType::ReversibleUp,
+            AssertSqlSafe(format!("-- {version}")).into_sql_str(),
+            false,
+        )
+    };
+    let applied = |migration: &Migration| AppliedMigration {
+        version: migration.version,
+        checksum: migration.checksum.clone(),
+    };
+
+    let repeatable = Migration::repeatable(
+        "view".into(),
+        AssertSqlSafe("-- view").into_sql_str(),
+        false,
+    );
+    let mut migrator = Migrator::with_migrations(vec![up(1), up(2), up(3), repeatable.clone()]);
+
+    let versions = |plan: Pending<'_>| {
+        plan.migrations
+            .iter()
+            .map(|m| m.version)
+            .collect::<Vec<_>>()
+    };
+
+    let plan = migrator
+        .pending(&migrator.migrations, None, &[], &[])
+        .unwrap();
+    assert_eq!(versions(plan), [1, 2, 3, repeatable.version]);
+
+    // The repeatable migration waits until all versioned migrations are applied.
+    let plan = migrator
+        .pending(&migrator.migrations, Some(2), &[], &[])
+        .unwrap();
+    assert_eq!(versions(plan), [1, 2]);
+
+    let plan = migrator
+        .pending(
+            &migrator.migrations,
+            None,
+            &[applied(&up(1)), applied(&up(2))],
+            &[applied(&repeatable)],
+        )
+        .unwrap();
+    assert_eq!(versions(plan), [3]);
+
+    let mut changed = applied(&up(2));
+    changed.checksum = vec![0; 48].into();
+    assert!(matches!(
+        migrator.pending(&migrator.migrations, None, &[applied(&up(1)), changed], &[]),
+        Err(MigrateError::VersionMismatch(2))
+    ));
+
+    let skipped = [applied(&up(1)), applied(&up(3))];
+    assert!(matches!(
+        migrator.pending(&migrator.migrations, None, &skipped, &[]),
+        Err(MigrateError::VersionTooOld(2, 3))
+    ));
+
+    migrator.set_allow_out_of_order(true);
+    let plan = migrator
+        .pending(&migrator.migrations, None, &skipped, &[])
+        .unwrap();
+    assert_eq!(plan.out_of_order, [(2, 3)]);
+    assert_eq!(versions(plan), [2, repeatable.version]);
+}
+
+#[test]
+fn namespaces_are_tracked_in_separate_tables() {
+    let mut migrator = Migrator::DEFAULT;
+    migrator.add_namespace("auth", &Migrator::DEFAULT);
+    migrator.dangerous_set_table_name(r#""app"."migrations""#);
+
+    let tables: Vec<_> = migrator
+        .migration_sets()
+        .map(|(table_name, _)| table_name.into_owned())
+        .collect();
+
+    assert_eq!(
+        tables,
+        [r#""app"."migrations__auth""#, r#""app"."migrations""#]
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrate::{MigrationType, TableLock};
+    use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
+    use futures_core::future::BoxFuture;
+    use std::future::Future;
+
+    /// A connection keeping the migrations tables and the lock row of [`TableLock`] in memory,
+    /// which fails to apply migrations whose SQL is `FAIL`.
+    #[derive(Default)]
+    struct TestConnection {
+        applied: HashMap<String, Vec<AppliedMigration>>,
+        audited: Vec<(i64, AuditInfo)>,
+        // the tables prepared to record audit info
+        audit_tables: Vec<String>,
+        locked: bool,
+    }
+
+    impl TestConnection {
+        /// The versions applied according to `table_name`.
+        fn versions(&self, table_name: &str) -> Vec<i64> {
+            self.applied.get(table_name).map_or(Vec::new(), |applied| {
+                applied.iter().map(|m| m.version).collect()
+            })
+        }
+    }
+
+    impl Migrate for TestConnection {
+        fn create_schema_if_not_exists<'e>(
+            &'e mut self,
+            _schema_name: &'e str,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn migrations_table_exists<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+        ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+            Box::pin(async { Ok(true) })
+        }
+
+        fn ensure_migrations_table<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn ensure_audit_columns<'e>(
+            &'e mut self,
+            table_name: &'e str,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            self.audit_tables.push(table_name.to_owned());
+            Box::pin(async { Ok(()) })
+        }
+
+        fn dirty_version<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+        ) -> BoxFuture<'e, Result<Option<i64>, MigrateError>> {
+            Box::pin(async { Ok(None) })
+        }
+
+        fn list_applied_migrations<'e>(
+            &'e mut self,
+            table_name: &'e str,
+        ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
+            Box::pin(async { Ok(self.applied.get(table_name).cloned().unwrap_or_default()) })
+        }
+
+        fn list_applied_repeatable_migrations<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+        ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+
+        fn lock<'e>(&'e mut self, _table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>> {
+            unreachable!("only `TableLock` is used")
+        }
+
+        fn unlock<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            unreachable!("only `TableLock` is used")
+        }
+
+        fn try_lock<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+        ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+            unreachable!("only `TableLock` is used")
+        }
+
+        fn try_lock_table<'e>(
+            &'e mut self,
+            _lock_table_name: &'e str,
+        ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+            Box::pin(async { Ok(!std::mem::replace(&mut self.locked, true)) })
+        }
+
+        fn unlock_table<'e>(
+            &'e mut self,
+            _lock_table_name: &'e str,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            Box::pin(async {
+                self.locked = false;
+                Ok(())
+            })
+        }
+
+        fn apply<'e>(
+            &'e mut self,
+            table_name: &'e str,
+            migration: &'e Migration,
+        ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+            Box::pin(async move {
+                if migration.sql.as_str() == "FAIL" {
+                    return Err(MigrateError::ExecuteMigration(
+                        crate::Error::Protocol("failed".into()),
+                        migration.version,
+                    ));
+                }
+
+                self.mark_applied(table_name, migration).await?;
+                Ok(Duration::ZERO)
+            })
+        }
+
+        fn mark_applied<'e>(
+            &'e mut self,
+            table_name: &'e str,
+            migration: &'e Migration,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            Box::pin(async move {
+                let applied = self.applied.entry(table_name.to_owned()).or_default();
+                applied.push(AppliedMigration {
+                    version: migration.version,
+                    checksum: migration.checksum.clone(),
+                });
+                Ok(())
+            })
+        }
+
+        fn record_audit<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+            version: i64,
+            audit: &'e AuditInfo,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            Box::pin(async move {
+                self.audited.push((version, audit.clone()));
+                Ok(())
+            })
+        }
+
+        fn update_checksum<'e>(
+            &'e mut self,
+            _table_name: &'e str,
+            _migration: &'e Migration,
+        ) -> BoxFuture<'e, Result<(), MigrateError>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn revert<'e>(
+            &'e mut self,
+            table_name: &'e str,
+            migration: &'e Migration,
+        ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+            Box::pin(async move {
+                if let Some(applied) = self.applied.get_mut(table_name) {
+                    applied.retain(|m| m.version != migration.version);
+                }
+                Ok(Duration::ZERO)
+            })
+        }
+    }
+
+    fn migration(version: i64, migration_type: MigrationType, sql: &str) -> Migration {
+        Migration::new(
+            version,
+            format!("migration {version}").into(),
+            migration_type,
+            AssertSqlSafe(sql.to_owned()).into_sql_str(),
+            false,
+        )
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn failed_migration_releases_table_lock() {
+        let mut migrator = Migrator::with_migrations(vec![
+            migration(1, MigrationType::Simple, "-- 1"),
+            migration(2, MigrationType::Simple, "FAIL"),
+        ]);
+        migrator.set_lock_strategy(&TableLock);
+
+        let mut conn = TestConnection::default();
+
+        assert!(matches!(
+            block_on(migrator.run_direct(None, &mut conn)),
+            Err(MigrateError::ExecuteMigration(_, 2))
+        ));
+        assert_eq!(conn.versions("_sqlx_migrations"), [1]);
+        assert!(!conn.locked);
+    }
+
+    #[test]
+    fn undo_skips_simple_migrations_unless_reversible_is_required() {
+        let mut migrator = Migrator::with_migrations(vec![
+            migration(1, MigrationType::ReversibleUp, "-- 1"),
+            migration(1, MigrationType::ReversibleDown, "-- 1"),
+            migration(2, MigrationType::Simple, "-- 2"),
+            migration(3, MigrationType::ReversibleUp, "-- 3"),
+            migration(3, MigrationType::ReversibleDown, "-- 3"),
+        ]);
+        migrator.set_lock_strategy(&TableLock);
+
+        let mut conn = TestConnection::default();
+        block_on(migrator.run_direct(None, &mut conn)).unwrap();
+
+        migrator.set_require_reversible(true);
+        assert!(matches!(
+            block_on(migrator.undo_direct(0, &mut conn)),
+            Err(MigrateError::MissingDownMigration(2))
+        ));
+        assert_eq!(conn.versions("_sqlx_migrations"), [1, 2, 3]);
+
+        migrator.set_require_reversible(false);
+        block_on(migrator.undo_direct(0, &mut conn)).unwrap();
+        assert_eq!(conn.versions("_sqlx_migrations"), [2]);
+    }
+
+    #[test]
+    fn refused_undo_releases_table_lock() {
+        let mut migrator = Migrator::with_migrations(vec![
+            migration(1, MigrationType::ReversibleUp, "CREATE TABLE foo ()"),
+            migration(1, MigrationType::ReversibleDown, "DROP TABLE foo"),
+        ]);
+        migrator.set_lock_strategy(&TableLock);
+
+        let mut conn = TestConnection::default();
+        block_on(migrator.run_direct(None, &mut conn)).unwrap();
+
+        assert!(matches!(
+            block_on(migrator.undo_last_direct(1, false, &mut conn)),
+            Err(MigrateError::Destructive(1, _))
+        ));
+        assert_eq!(conn.versions("_sqlx_migrations"), [1]);
+        assert!(!conn.locked);
+    }
+
+    #[test]
+    fn audit_is_recorded_for_each_applied_migration() {
+        let mut migrator = Migrator::with_migrations(vec![
+            migration(1, MigrationType::Simple, "-- 1"),
+            migration(2, MigrationType::Simple, "-- 2"),
+            migration(3, MigrationType::Simple, "-- 3"),
+        ]);
+        migrator.set_audit_info(AuditInfo {
+            applied_by: Some("alice@host".into()),
+            application: None,
+        });
+        migrator.set_lock_strategy(&TableLock);
+
+        let mut conn = TestConnection::default();
+        block_on(migrator.baseline_direct(1, &mut conn)).unwrap();
+        block_on(migrator.run_direct(None, &mut conn)).unwrap();
+
+        assert_eq!(conn.audit_tables, ["_sqlx_migrations", "_sqlx_migrations"]);
+
+        let versions: Vec<_> = conn.audited.iter().map(|(version, _)| *version).collect();
+        assert_eq!(versions, [1, 2, 3]);
+        assert!(conn
+            .audited
+            .iter()
+            .all(|(_, audit)| audit.applied_by.as_deref() == Some("alice@host")));
+    }
+
+    #[test]
+    fn migrations_table_is_only_upgraded_when_recording_audit() {
+        let mut migrator =
+            Migrator::with_migrations(vec![migration(1, MigrationType::Simple, "-- 1")]);
+        migrator.set_lock_strategy(&TableLock);
+
+        let mut conn = TestConnection::default();
+        block_on(migrator.run_direct(None, &mut conn)).unwrap();
+
+        assert!(conn.audit_tables.is_empty());
+        assert!(conn.audited.is_empty());
+    }
+
+    #[test]
+    fn baseline_marks_migrations_applied_without_running_them() {
+        let mut migrator = Migrator::with_migrations(vec![
+            migration(1, MigrationType::Simple, "FAIL"),
+            migration(2, MigrationType::Simple, "FAIL"),
+            migration(3, MigrationType::Simple, "-- 3"),
+        ]);
+        migrator.set_lock_strategy(&TableLock);
+
+        let mut conn = TestConnection::default();
+        assert!(matches!(
+            block_on(migrator.baseline_direct(4, &mut conn)),
+            Err(MigrateError::VersionNotPresent(4))
+        ));
+
+        block_on(migrator.baseline_direct(2, &mut conn)).unwrap();
+        assert_eq!(conn.versions("_sqlx_migrations"), [1, 2]);
+
+        // only the migrations after the baseline are run
+        block_on(migrator.run_direct(None, &mut conn)).unwrap();
+        assert_eq!(conn.versions("_sqlx_migrations"), [1, 2, 3]);
+
+        // applied migrations are left untouched
+        block_on(migrator.baseline_direct(3, &mut conn)).unwrap();
+        assert_eq!(conn.versions("_sqlx_migrations"), [1, 2, 3]);
+    }
+
+    #[test]
+    fn namespaces_are_applied_first_and_not_reverted() {
+        let library = Migrator::with_migrations(vec![
+            migration(1, MigrationType::ReversibleUp, "-- library 1"),
+            migration(1, MigrationType::ReversibleDown, "-- library 1"),
+        ]);
+
+        let mut migrator = Migrator::with_migrations(vec![
+            migration(1, MigrationType::ReversibleUp, "-- app 1"),
+            migration(1, MigrationType::ReversibleDown, "-- app 1"),
+            migration(2, MigrationType::ReversibleUp, "-- app 2"),
+            migration(2, MigrationType::ReversibleDown, "-- app 2"),
+        ]);
+        migrator
+            .set_lock_strategy(&TableLock)
+            .add_namespace("library", &library);
+
+        let mut conn = TestConnection::default();
+        block_on(migrator.run_direct(None, &mut conn)).unwrap();
+
+        assert_eq!(conn.versions("_sqlx_migrations__library"), [1]);
+        assert_eq!(conn.versions("_sqlx_migrations"), [1, 2]);
+
+        // each namespace is versioned independently
+        let plan = block_on(migrator.plan_direct(None, &mut conn)).unwrap();
+        assert!(plan.is_empty());
+
+        block_on(migrator.undo_direct(0, &mut conn)).unwrap();
+
+        assert_eq!(conn.versions("_sqlx_migrations__library"), [1]);
+        assert!(conn.versions("_sqlx_migrations").is_empty());
+        assert!(!conn.locked);
+    }
+}
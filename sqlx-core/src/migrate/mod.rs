@@ -1,17 +1,42 @@
+mod audit;
+mod code;
+mod drift;
+mod embed;
 mod error;
+mod event;
+mod lock;
 #[allow(clippy::module_inception)]
 mod migrate;
 mod migration;
 mod migration_type;
 mod migrator;
+mod normalize;
+mod remote;
+mod seeder;
 mod source;
 
+pub use audit::AuditInfo;
+pub use code::RustMigration;
+pub use drift::{SchemaChange, SchemaObject, SchemaSnapshot};
 pub use error::MigrateError;
+pub use event::MigrationEvent;
+pub use lock::{AdvisoryLock, LockPolicy, MigrationLock, NoLock, TableLock};
 pub use migrate::{Migrate, MigrateDatabase};
-pub use migration::{AppliedMigration, Migration};
-pub use migration_type::MigrationType;
+pub use migration::{repeatable_version, AppliedMigration, Migration};
+pub use migration_type::{MigrationType, REPEATABLE_PREFIX};
 pub use migrator::Migrator;
-pub use source::{MigrationSource, ResolveConfig, ResolveWith};
+pub use normalize::ChecksumNormalization;
+pub use remote::{FetchSource, TarSource};
+pub use seeder::Seeder;
+pub use source::{resolve_files, MigrationSource, ResolveConfig, ResolveError, ResolveWith};
 
+#[doc(hidden)]
+pub use code::MigrationCode;
+#[doc(hidden)]
+pub use embed::{compress_embedded, EmbeddedMigrations};
+#[doc(hidden)]
+pub use event::EventHandler;
+#[doc(hidden)]
+pub use migrator::MigrationNamespace;
 #[doc(hidden)]
 pub use source::{resolve_blocking, resolve_blocking_with_config};
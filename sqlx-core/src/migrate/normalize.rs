@@ -0,0 +1,203 @@
+/// How migration SQL is normalized before it is hashed, so that changes which don't affect
+/// its meaning don't change the checksum.
+///
+/// String literals, quoted identifiers and dollar-quoted strings are never changed.
+///
+/// See [`ResolveConfig::set_checksum_normalization()`][crate::migrate::ResolveConfig::set_checksum_normalization].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChecksumNormalization {
+    /// Hash the SQL as-is.
+    #[default]
+    None,
+
+    /// Ignore changes to whitespace between tokens,
+    /// e.g. re-indenting or wrapping statements differently.
+    Whitespace,
+
+    /// Like [`Self::Whitespace`], but also ignore changes to comments.
+    Comments,
+}
+
+/// Normalize `sql` according to `mode`.
+///
+/// This is only used for hashing and does not need to produce valid SQL.
+pub(crate) fn normalize(sql: &str, mode: ChecksumNormalization) -> String {
+    let strip_comments = match mode {
+        ChecksumNormalization::None => return sql.to_owned(),
+        ChecksumNormalization::Whitespace => false,
+        ChecksumNormalization::Comments => true,
+    };
+
+    let mut out = String::with_capacity(sql.len());
+    // Whitespace (or a stripped comment) was skipped since the last character written.
+    let mut pending_space = false;
+    let mut rest = sql;
+
+    while let Some(c) = rest.chars().next() {
+        let token_len = match c {
+            c if c.is_whitespace() => {
+                pending_space = true;
+                rest = &rest[c.len_utf8()..];
+                continue;
+            }
+            '-' if rest.starts_with("--") => {
+                let len = rest.find('\n').unwrap_or(rest.len());
+
+                if strip_comments {
+                    pending_space = true;
+                    rest = &rest[len..];
+                    continue;
+                }
+
+                len
+            }
+            '/' if rest.starts_with("/*") => {
+                let len = block_comment_len(rest);
+
+                if strip_comments {
+                    pending_space = true;
+                    rest = &rest[len..];
+                    continue;
+                }
+
+                len
+            }
+            _ => literal_len(rest),
+        };
+
+        let (token, tail) = rest.split_at(token_len);
+
+        // Only keep whitespace where it separates two words (e.g. `CREATE TABLE`),
+        // so `f(a,b)` and `f( a, b )` normalize the same.
+        if pending_space && ends_with_word(&out) && starts_with_word(token) {
+            out.push(' ');
+        }
+
+        out.push_str(token);
+        pending_space = false;
+        rest = tail;
+    }
+
+    out
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '\'' | '"')
+}
+
+fn ends_with_word(s: &str) -> bool {
+    s.chars().next_back().is_some_and(is_word_char)
+}
+
+fn starts_with_word(s: &str) -> bool {
+    s.chars().next().is_some_and(is_word_char)
+}
+
+/// Length of the string literal, quoted identifier or dollar-quoted string at the start of `s`,
+/// or of its first character if it is none of those.
+pub(crate) fn literal_len(s: &str) -> usize {
+    match s.chars().next() {
+        Some(c @ ('\'' | '"')) => quoted_len(s, c),
+        Some('$') => dollar_quoted_len(s).unwrap_or(1),
+        Some(c) => c.len_utf8(),
+        None => 0,
+    }
+}
+
+/// Length of a (possibly nested) block comment at the start of `s`, including delimiters.
+fn block_comment_len(s: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+
+    while i < s.len() {
+        if s[i..].starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if s[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += s[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    s.len()
+}
+
+/// Length of a string literal or quoted identifier at the start of `s`, including quotes.
+///
+/// A doubled quote inside is treated as an escaped quote.
+fn quoted_len(s: &str, quote: char) -> usize {
+    let mut chars = s.char_indices().skip(1).peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            if chars.peek().is_some_and(|&(_, next)| next == quote) {
+                chars.next();
+                continue;
+            }
+
+            return i + 1;
+        }
+    }
+
+    s.len()
+}
+
+/// Length of a dollar-quoted string (e.g. `$body$ ... $body$`) at the start of `s`,
+/// or `None` if `s` does not start with a dollar quote.
+fn dollar_quoted_len(s: &str) -> Option<usize> {
+    let tag_len = s[1..].find('$')? + 2;
+    let tag = &s[..tag_len];
+
+    if !tag[1..tag_len - 1]
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    let end = s[tag_len..]
+        .find(tag)
+        .map_or(s.len(), |i| tag_len + i + tag_len);
+
+    Some(end)
+}
+
+#[test]
+fn normalize_ignores_formatting() {
+    let sql = "CREATE TABLE foo (\n    id BIGINT PRIMARY KEY, -- the id\n    name TEXT\n);\n";
+    let reformatted = "CREATE TABLE foo(id BIGINT PRIMARY KEY,  -- the id\n name TEXT);";
+    assert_eq!(
+        normalize(sql, ChecksumNormalization::Whitespace),
+        normalize(reformatted, ChecksumNormalization::Whitespace)
+    );
+
+    let recommented = "/* foo */ CREATE TABLE foo (id BIGINT PRIMARY KEY, name TEXT);";
+    assert_ne!(
+        normalize(sql, ChecksumNormalization::Whitespace),
+        normalize(recommented, ChecksumNormalization::Whitespace)
+    );
+    assert_eq!(
+        normalize(sql, ChecksumNormalization::Comments),
+        normalize(recommented, ChecksumNormalization::Comments)
+    );
+
+    // Whitespace in literals, quoted identifiers and dollar-quoted strings is significant.
+    for (a, b) in [
+        ("SELECT 'a  b'", "SELECT 'a b'"),
+        (r#"SELECT "a  b""#, r#"SELECT "a b""#),
+        ("SELECT $$a  b$$", "SELECT $$a b$$"),
+        ("SELECT $x$ -- a $x$", "SELECT $x$ -- b $x$"),
+    ] {
+        assert_ne!(
+            normalize(a, ChecksumNormalization::Comments),
+            normalize(b, ChecksumNormalization::Comments)
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use crate::error::BoxDynError;
+use crate::migrate::{resolve_files, Migration, MigrationSource, ResolveConfig};
+use futures_core::future::BoxFuture;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::io;
+
+/// A [`MigrationSource`] which fetches migration files with an async function,
+/// e.g. from an artifact store at startup.
+///
+/// The function returns `(file name, contents)` pairs, which are resolved as if they were
+/// the files of a migrations directory; see [`resolve_files()`].
+///
+/// ```rust,no_run
+/// # use sqlx_core::error::BoxDynError;
+/// # use sqlx_core::migrate::{FetchSource, MigrateError, Migrator};
+/// # async fn download_migrations() -> Result<Vec<(String, String)>, BoxDynError> { unimplemented!() }
+/// # fn main() -> Result<(), MigrateError> {
+/// # sqlx::__rt::test_block_on(async move {
+/// let migrator = Migrator::new(FetchSource::new(download_migrations)).await?;
+/// # Ok(())
+/// # })
+/// # }
+/// ```
+pub struct FetchSource<F> {
+    fetch: F,
+    config: ResolveConfig,
+}
+
+impl<F> FetchSource<F> {
+    pub fn new(fetch: F) -> Self {
+        Self::with_config(fetch, ResolveConfig::new())
+    }
+
+    pub fn with_config(fetch: F, config: ResolveConfig) -> Self {
+        FetchSource { fetch, config }
+    }
+}
+
+impl<F> Debug for FetchSource<F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FetchSource")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'s, F, Fut, N, S> MigrationSource<'s> for FetchSource<F>
+where
+    F: FnOnce() -> Fut + Send + 's,
+    Fut: Future<Output = Result<Vec<(N, S)>, BoxDynError>> + Send + 's,
+    N: AsRef<str>,
+    S: Into<String>,
+{
+    fn resolve(self) -> BoxFuture<'s, Result<Vec<Migration>, BoxDynError>> {
+        Box::pin(async move {
+            let files = (self.fetch)().await?;
+
+            Ok(resolve_files(files, &self.config)?)
+        })
+    }
+}
+
+/// A [`MigrationSource`] which reads migrations from an in-memory tar archive
+/// (e.g. a release artifact downloaded at startup).
+///
+/// Every regular file in the archive is resolved by its file name, regardless of the directory
+/// it is in, as if it were in a migrations directory; see [`resolve_files()`]. Long names of
+/// GNU and PAX (`path` records) archives are supported.
+///
+/// The archive must not be compressed; decompress it first if needed.
+#[derive(Debug)]
+pub struct TarSource {
+    archive: Vec<u8>,
+    config: ResolveConfig,
+}
+
+impl TarSource {
+    pub fn new(archive: impl Into<Vec<u8>>) -> Self {
+        Self::with_config(archive, ResolveConfig::new())
+    }
+
+    pub fn with_config(archive: impl Into<Vec<u8>>, config: ResolveConfig) -> Self {
+        TarSource {
+            archive: archive.into(),
+            config,
+        }
+    }
+}
+
+impl MigrationSource<'static> for TarSource {
+    fn resolve(self) -> BoxFuture<'static, Result<Vec<Migration>, BoxDynError>> {
+        Box::pin(async move {
+            let files = read_tar(&self.archive)?;
+
+            Ok(resolve_files(files, &self.config)?)
+        })
+    }
+}
+
+const BLOCK_SIZE: usize = 512;
+
+/// Read the name (without directories) and contents of every regular file in a tar archive.
+fn read_tar(archive: &[u8]) -> io::Result<Vec<(String, String)>> {
+    let mut files = Vec::new();
+    // Set by a GNU long name or PAX extended header for the entry following it.
+    let mut long_name = None;
+    let mut offset = 0;
+
+    while let Some(header) = archive.get(offset..offset + BLOCK_SIZE) {
+        if header.iter().all(|&b| b == 0) {
+            // end of archive
+            break;
+        }
+
+        let size = parse_octal(&header[124..136])?;
+        let data_start = offset + BLOCK_SIZE;
+        let data = archive
+            .get(data_start..data_start.saturating_add(size))
+            .ok_or_else(|| invalid_data("tar archive is truncated"))?;
+
+        match header[156] {
+            // regular file
+            b'0' | 0 => {
+                let path = match long_name.take() {
+                    Some(name) => name,
+                    None => header_path(header),
+                };
+
+                let name = path.rsplit('/').next().unwrap_or_default().to_owned();
+                let contents = String::from_utf8(data.to_vec()).map_err(|_| {
+                    invalid_data(format!("file {path:?} in tar archive is not UTF-8"))
+                })?;
+
+                files.push((name, contents));
+            }
+            b'L' => long_name = Some(c_str(data)),
+            b'x' => {
+                if let Some(path) = pax_path(data)? {
+                    long_name = Some(path);
+                }
+            }
+            // directories, links and global extended headers are ignored
+            _ => long_name = None,
+        }
+
+        offset = data_start + size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    }
+
+    Ok(files)
+}
+
+fn header_path(header: &[u8]) -> String {
+    let name = c_str(&header[..100]);
+
+    // ustar headers may split long paths into a prefix and a name
+    if &header[257..262] == b"ustar" {
+        let prefix = c_str(&header[345..500]);
+
+        if !prefix.is_empty() {
+            return format!("{prefix}/{name}");
+        }
+    }
+
+    name
+}
+
+/// Read the `path` of a PAX extended header, made of `"<length> <key>=<value>\n"` records.
+fn pax_path(mut records: &[u8]) -> io::Result<Option<String>> {
+    let invalid = || invalid_data("invalid PAX extended header in tar archive");
+    let mut path = None;
+
+    while !records.is_empty() {
+        let space = records
+            .iter()
+            .position(|&b| b == b' ')
+            .ok_or_else(invalid)?;
+        let len = std::str::from_utf8(&records[..space])
+            .ok()
+            .and_then(|len| len.parse::<usize>().ok())
+            .filter(|&len| len > space && len <= records.len())
+            .ok_or_else(invalid)?;
+
+        let record = records[space + 1..len]
+            .strip_suffix(b"\n")
+            .ok_or_else(invalid)?;
+
+        if let Some(value) = record.strip_prefix(b"path=") {
+            let value = String::from_utf8(value.to_vec())
+                .map_err(|_| invalid_data("path in tar archive is not UTF-8"))?;
+            path = Some(value);
+        }
+
+        records = &records[len..];
+    }
+
+    Ok(path)
+}
+
+fn c_str(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+fn parse_octal(field: &[u8]) -> io::Result<usize> {
+    let digits = c_str(field);
+
+    usize::from_str_radix(digits.trim(), 8)
+        .map_err(|_| invalid_data(format!("invalid size in tar header: {digits:?}")))
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+#[test]
+fn read_tar_files() {
+    fn entry(archive: &mut Vec<u8>, path: &str, typeflag: u8, data: &[u8]) {
+        let mut header = [0u8; BLOCK_SIZE];
+        header[..path.len()].copy_from_slice(path.as_bytes());
+        header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+        header[156] = typeflag;
+        header[257..263].copy_from_slice(b"ustar\0");
+
+        archive.extend_from_slice(&header);
+        archive.extend_from_slice(data);
+        archive.resize(archive.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+    }
+
+    let mut archive = Vec::new();
+    entry(&mut archive, "migrations/", b'5', b"");
+    entry(
+        &mut archive,
+        "migrations/1_users.sql",
+        b'0',
+        b"CREATE TABLE users ();",
+    );
+    entry(
+        &mut archive,
+        "migrations/2_posts.sql",
+        b'0',
+        b"CREATE TABLE posts ();",
+    );
+    // the name in the header is truncated, as written by `tar --format=pax`
+    entry(
+        &mut archive,
+        "migrations/PaxHeaders/3_comments",
+        b'x',
+        b"13 mtime=1.5\n47 path=migrations/3_create_comments_table.sql\n",
+    );
+    entry(
+        &mut archive,
+        "migrations/3_create_comments",
+        b'0',
+        b"CREATE TABLE comments ();",
+    );
+    archive.extend_from_slice(&[0; BLOCK_SIZE * 2]);
+
+    assert_eq!(
+        read_tar(&archive).unwrap(),
+        [
+            (
+                "1_users.sql".to_owned(),
+                "CREATE TABLE users ();".to_owned()
+            ),
+            (
+                "2_posts.sql".to_owned(),
+                "CREATE TABLE posts ();".to_owned()
+            ),
+            (
+                "3_create_comments_table.sql".to_owned(),
+                "CREATE TABLE comments ();".to_owned()
+            ),
+        ]
+    );
+
+    assert!(pax_path(b"46 path=migrations/3_create_comments_table.sql").is_err());
+    assert!(pax_path(b"99 path=x\n").is_err());
+
+    archive.truncate(BLOCK_SIZE * 2 + 10);
+    assert!(read_tar(&archive).is_err());
+}
@@ -0,0 +1,226 @@
+use crate::acquire::Acquire;
+use crate::migrate::{Migrate, MigrateError, Migration, Migrator};
+use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
+use std::borrow::Cow;
+use std::fs;
+use std::io;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// A set of seed scripts, applied separately from migrations.
+///
+/// Seed scripts insert or update reference data (e.g. lookup tables, or test accounts in
+/// a development environment) and must be idempotent, e.g. using `INSERT ... ON CONFLICT`.
+///
+/// Each seed is applied if it is new or has changed since it was last applied,
+/// like a [repeatable migration][crate::migrate::MigrationType::Repeatable].
+/// Seeds are tracked in their own table (`_sqlx_seeds` by default), so they never
+/// conflict with the migration history.
+///
+/// Seeds are applied in order of their name, which is the path of the seed script relative
+/// to the seed directory, without the `.sql` extension (e.g. `users` or `dev/users`).
+///
+/// # Example
+/// Given a directory like:
+///
+/// ```text
+/// seeds/
+/// ├── countries.sql
+/// ├── dev/
+/// │   └── users.sql
+/// └── prod/
+///     └── users.sql
+/// ```
+///
+/// ```rust,no_run
+/// # use sqlx_core::migrate::{MigrateError, Seeder};
+/// # fn main() -> Result<(), MigrateError> {
+/// # sqlx::__rt::test_block_on(async move {
+/// // Resolves `countries` and `dev/users`
+/// let seeder = Seeder::for_environment("seeds", "dev").await?;
+/// # Ok(())
+/// # })
+/// # }
+/// ```
+///
+/// Then apply them with [`Seeder::run()`] after running migrations.
+#[derive(Debug)]
+pub struct Seeder {
+    migrator: Migrator,
+}
+
+impl Seeder {
+    /// Resolve the seed scripts (`*.sql`) directly in `path`.
+    ///
+    /// Subdirectories are ignored.
+    pub async fn new(path: impl Into<PathBuf>) -> Result<Self, MigrateError> {
+        Self::resolve(path.into(), None).await
+    }
+
+    /// Resolve the seed scripts (`*.sql`) directly in `path`, as well as those in
+    /// its `environment` subdirectory.
+    ///
+    /// If there is no `environment` subdirectory, only the seeds shared by all environments
+    /// are resolved. Other subdirectories are ignored.
+    pub async fn for_environment(
+        path: impl Into<PathBuf>,
+        environment: impl Into<String>,
+    ) -> Result<Self, MigrateError> {
+        Self::resolve(path.into(), Some(environment.into())).await
+    }
+
+    /// Create a seeder from `(name, sql)` pairs.
+    pub fn with_seeds<N, S>(seeds: impl IntoIterator<Item = (N, S)>) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        S: SqlSafeStr,
+    {
+        let seeds = seeds
+            .into_iter()
+            .map(|(name, sql)| Migration::repeatable(name.into(), sql.into_sql_str(), false))
+            .collect();
+
+        let mut migrator = Migrator::with_migrations(seeds);
+        migrator.dangerous_set_table_name("_sqlx_seeds");
+
+        Self { migrator }
+    }
+
+    async fn resolve(path: PathBuf, environment: Option<String>) -> Result<Self, MigrateError> {
+        let seeds = crate::rt::spawn_blocking(move || resolve_seeds(&path, environment.as_deref()))
+            .await
+            .map_err(|e| MigrateError::Source(e.into()))?;
+
+        Ok(Self::with_seeds(
+            seeds
+                .into_iter()
+                .map(|(name, sql)| (name, AssertSqlSafe(sql))),
+        ))
+    }
+
+    /// Override the name of the table used to track applied seeds.
+    ///
+    /// May be schema-qualified and/or contain quotes. Defaults to `_sqlx_seeds`.
+    ///
+    /// Changing this for an existing database causes all seeds to be applied again.
+    pub fn set_table_name(&mut self, table_name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.migrator.dangerous_set_table_name(table_name);
+        self
+    }
+
+    /// Specify whether or not to lock the database while seeding. Defaults to `true`.
+    ///
+    /// See [`Migrator::set_locking()`].
+    pub fn set_locking(&mut self, locking: bool) -> &mut Self {
+        self.migrator.set_locking(locking);
+        self
+    }
+
+    /// Get an iterator over all seeds, in the order they are applied.
+    ///
+    /// Seeds are represented as repeatable migrations whose description is the name of the seed.
+    pub fn iter(&self) -> impl Iterator<Item = &Migration> {
+        self.migrator.iter()
+    }
+
+    /// Apply every seed that is new or has changed since it was last applied.
+    pub async fn run<'a, A>(&self, migrator: A) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
+    {
+        self.migrator.run(migrator).await
+    }
+
+    /// Return the seeds that [`Self::run()`] would apply, in order, without applying them.
+    pub async fn plan<'a, A>(&self, migrator: A) -> Result<Vec<&Migration>, MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate + Send,
+    {
+        self.migrator.plan(migrator).await
+    }
+}
+
+fn resolve_seeds(path: &Path, environment: Option<&str>) -> io::Result<Vec<(String, String)>> {
+    let mut seeds = resolve_dir(path, None)?;
+
+    if let Some(environment) = environment {
+        match resolve_dir(&path.join(environment), Some(environment)) {
+            Ok(environment_seeds) => seeds.extend(environment_seeds),
+            // an environment without seeds of its own
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(seeds)
+}
+
+/// Read the `*.sql` files directly in `dir`.
+fn resolve_dir(dir: &Path, prefix: Option<&str>) -> io::Result<Vec<(String, String)>> {
+    let mut seeds = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+
+        let Some(stem) = file_name.strip_suffix(".sql") else {
+            continue;
+        };
+
+        let name = match prefix {
+            Some(prefix) => format!("{prefix}/{stem}"),
+            None => stem.to_owned(),
+        };
+
+        seeds.push((name, fs::read_to_string(entry.path())?));
+    }
+
+    Ok(seeds)
+}
+
+#[test]
+fn seeds_are_ordered_by_name() {
+    let seeder = Seeder::with_seeds([
+        ("dev/users", AssertSqlSafe("-- users")),
+        ("countries", AssertSqlSafe("-- countries")),
+    ]);
+
+    let names: Vec<_> = seeder.iter().map(|seed| &*seed.description).collect();
+
+    assert_eq!(names, ["countries", "dev/users"]);
+    assert_eq!(seeder.migrator.table_name, "_sqlx_seeds");
+}
+
+#[test]
+fn missing_environment_falls_back_to_shared_seeds() -> io::Result<()> {
+    let dir = std::env::temp_dir().join(format!("sqlx-seeds-{}", std::process::id()));
+    fs::create_dir_all(dir.join("dev"))?;
+    fs::write(dir.join("countries.sql"), "-- countries")?;
+    fs::write(dir.join("dev/users.sql"), "-- users")?;
+
+    let names = |environment| -> io::Result<Vec<String>> {
+        let mut seeds = resolve_seeds(&dir, environment)?;
+        seeds.sort();
+        Ok(seeds.into_iter().map(|(name, _)| name).collect())
+    };
+
+    let dev = names(Some("dev"));
+    let staging = names(Some("staging"));
+    let missing = resolve_seeds(&dir.join("missing"), None);
+    fs::remove_dir_all(&dir)?;
+
+    assert_eq!(dev?, ["countries", "dev/users"]);
+    assert_eq!(staging?, ["countries"]);
+    // the seed directory itself is still required
+    assert_eq!(missing.unwrap_err().kind(), io::ErrorKind::NotFound);
+
+    Ok(())
+}
@@ -1,5 +1,7 @@
 use crate::error::BoxDynError;
-use crate::migrate::{migration, Migration, MigrationType};
+use crate::migrate::{
+    migration, normalize, ChecksumNormalization, Migration, MigrationType, REPEATABLE_PREFIX,
+};
 use crate::sql_str::{AssertSqlSafe, SqlSafeStr};
 use futures_core::future::BoxFuture;
 
@@ -16,7 +18,15 @@ use std::path::{Path, PathBuf};
 /// `<VERSION>` is a string that can be parsed into `i64` and its value is
 /// greater than zero, and `<DESCRIPTION>` is a string.
 ///
-/// Files that don't match this format are silently ignored.
+/// Repeatable migrations use the format `R__<DESCRIPTION>.sql` instead. They are re-applied
+/// whenever their contents change, after all versioned migrations have been applied.
+///
+/// Files that don't match either format are silently ignored.
+///
+/// Migrations run in a transaction, except if their filename has a `.no-transaction` suffix
+/// (e.g. `<VERSION>_<DESCRIPTION>.no-transaction.up.sql`) or they start with a
+/// `-- sqlx:no-transaction` (or `-- no-transaction`) comment, for statements that can't be run
+/// in a transaction like `CREATE INDEX CONCURRENTLY`.
 ///
 /// You can create a new empty migration script using sqlx-cli:
 /// `sqlx migrate add <DESCRIPTION>`.
@@ -87,6 +97,7 @@ pub struct ResolveError {
 #[derive(Debug, Default)]
 pub struct ResolveConfig {
     ignored_chars: BTreeSet<char>,
+    checksum_normalization: ChecksumNormalization,
 }
 
 impl ResolveConfig {
@@ -94,9 +105,34 @@ impl ResolveConfig {
     pub fn new() -> Self {
         ResolveConfig {
             ignored_chars: BTreeSet::new(),
+            checksum_normalization: ChecksumNormalization::None,
         }
     }
 
+    /// Normalize migrations before hashing them, so that changing their formatting
+    /// (and optionally, their comments) does not change their checksum.
+    ///
+    /// The migration SQL string itself is not changed.
+    ///
+    /// Unlike ignoring whitespace characters with [`Self::ignore_chars()`], this does not
+    /// ignore changes to whitespace inside string literals or quoted identifiers.
+    ///
+    /// ### Note: Changes Migration Checksum
+    /// This will change the checksum of resolved migrations,
+    /// which may cause problems with existing deployments.
+    ///
+    /// Use [`Migrator::repair()`][crate::migrate::Migrator::repair] to update the checksums
+    /// of applied migrations after enabling this.
+    pub fn set_checksum_normalization(&mut self, mode: ChecksumNormalization) -> &mut Self {
+        self.checksum_normalization = mode;
+        self
+    }
+
+    /// Get the normalization applied to migrations before hashing them.
+    pub fn checksum_normalization(&self) -> ChecksumNormalization {
+        self.checksum_normalization
+    }
+
     /// Ignore a character when hashing migrations.
     ///
     /// The migration SQL string itself will still contain the character,
@@ -201,26 +237,10 @@ pub fn resolve_blocking_with_config(
         // would be a breaking change.
         let file_name = file_name.to_string_lossy();
 
-        let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
-
-        if parts.len() != 2 || !parts[1].ends_with(".sql") {
-            // not of the format: <VERSION>_<DESCRIPTION>.<REVERSIBLE_DIRECTION>.sql; ignore
+        let Some((version, migration_type, description, no_tx)) = parse_file_name(&file_name)?
+        else {
             continue;
-        }
-
-        let version: i64 = parts[0].parse()
-            .map_err(|_e| ResolveError {
-                message: format!("error parsing migration filename {file_name:?}; expected integer version prefix (e.g. `01_foo.sql`)"),
-                source: None,
-            })?;
-
-        let migration_type = MigrationType::from_filename(parts[1]);
-
-        // remove the `.sql` and replace `_` with ` `
-        let description = parts[1]
-            .trim_end_matches(migration_type.suffix())
-            .replace('_', " ")
-            .to_owned();
+        };
 
         let sql = fs::read_to_string(&entry_path).map_err(|e| ResolveError {
             message: format!(
@@ -230,31 +250,160 @@ pub fn resolve_blocking_with_config(
             source: Some(e),
         })?;
 
-        // opt-out of migration transaction
-        let no_tx = sql.starts_with("-- no-transaction");
-
-        let checksum = checksum_with(&sql, &config.ignored_chars);
-
         migrations.push((
-            Migration::with_checksum(
-                version,
-                Cow::Owned(description),
-                migration_type,
-                AssertSqlSafe(sql).into_sql_str(),
-                checksum.into(),
-                no_tx,
-            ),
+            build_migration(version, migration_type, description, no_tx, sql, config),
             entry_path,
         ));
     }
 
-    // Ensure that we are sorted by version in ascending order.
-    migrations.sort_by_key(|(m, _)| m.version);
+    // Ensure that we are sorted by version in ascending order, with repeatable migrations last.
+    migrations.sort_by(|(a, _), (b, _)| migration::order(a).cmp(&migration::order(b)));
+
+    Ok(migrations)
+}
+
+/// Resolve migrations from `(file name, contents)` pairs, e.g. fetched from a remote location,
+/// the same way as the files of a migrations directory.
+///
+/// Files whose name is not that of a migration are ignored.
+/// The result is sorted in the order the migrations are applied.
+///
+/// Use this to implement [`MigrationSource`] for sources other than the filesystem.
+pub fn resolve_files<N, S>(
+    files: impl IntoIterator<Item = (N, S)>,
+    config: &ResolveConfig,
+) -> Result<Vec<Migration>, ResolveError>
+where
+    N: AsRef<str>,
+    S: Into<String>,
+{
+    let mut migrations = Vec::new();
+
+    for (file_name, sql) in files {
+        let Some((version, migration_type, description, no_tx)) =
+            parse_file_name(file_name.as_ref())?
+        else {
+            continue;
+        };
+
+        migrations.push(build_migration(
+            version,
+            migration_type,
+            description,
+            no_tx,
+            sql.into(),
+            config,
+        ));
+    }
+
+    migration::sort(&mut migrations);
 
     Ok(migrations)
 }
 
-fn checksum_with(sql: &str, ignored_chars: &BTreeSet<char>) -> Vec<u8> {
+fn build_migration(
+    version: i64,
+    migration_type: MigrationType,
+    description: String,
+    no_tx: bool,
+    sql: String,
+    config: &ResolveConfig,
+) -> Migration {
+    // opt-out of migration transaction
+    let no_tx = no_tx || has_no_tx_comment(&sql);
+
+    let checksum = checksum_with(&sql, config);
+
+    Migration::with_checksum(
+        version,
+        Cow::Owned(description),
+        migration_type,
+        AssertSqlSafe(sql).into_sql_str(),
+        checksum.into(),
+        no_tx,
+    )
+}
+
+/// Filename suffix (before the `.sql` extension) marking a migration to be run outside a transaction.
+const NO_TX_SUFFIX: &str = ".no-transaction";
+
+/// Parse the version, type, description and whether to opt out of the migration transaction
+/// of a migration from its filename.
+///
+/// Returns `None` if the file is not a migration.
+fn parse_file_name(
+    file_name: &str,
+) -> Result<Option<(i64, MigrationType, String, bool)>, ResolveError> {
+    if let Some(name) = file_name.strip_prefix(REPEATABLE_PREFIX) {
+        let Some(name) = name.strip_suffix(".sql") else {
+            // not of the format: R__<DESCRIPTION>.sql; ignore
+            return Ok(None);
+        };
+
+        let (name, no_tx) = strip_no_tx_suffix(name);
+        let description = name.replace('_', " ");
+        let version = migration::repeatable_version(&description);
+
+        return Ok(Some((
+            version,
+            MigrationType::Repeatable,
+            description,
+            no_tx,
+        )));
+    }
+
+    let parts = file_name.splitn(2, '_').collect::<Vec<_>>();
+
+    if parts.len() != 2 || !parts[1].ends_with(".sql") {
+        // not of the format: <VERSION>_<DESCRIPTION>.<REVERSIBLE_DIRECTION>.sql; ignore
+        return Ok(None);
+    }
+
+    let version: i64 = parts[0].parse()
+        .map_err(|_e| ResolveError {
+            message: format!("error parsing migration filename {file_name:?}; expected integer version prefix (e.g. `01_foo.sql`)"),
+            source: None,
+        })?;
+
+    let migration_type = MigrationType::from_filename(parts[1]);
+
+    // remove the `.sql` and replace `_` with ` `
+    let (name, no_tx) = strip_no_tx_suffix(parts[1].trim_end_matches(migration_type.suffix()));
+    let description = name.replace('_', " ");
+
+    Ok(Some((version, migration_type, description, no_tx)))
+}
+
+fn strip_no_tx_suffix(name: &str) -> (&str, bool) {
+    match name.strip_suffix(NO_TX_SUFFIX) {
+        Some(name) => (name, true),
+        None => (name, false),
+    }
+}
+
+/// Check for a `-- sqlx:no-transaction` or `-- no-transaction` comment before the first statement.
+fn has_no_tx_comment(sql: &str) -> bool {
+    sql.trim_start_matches('\u{FEFF}')
+        .lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with("--"))
+        .any(|line| {
+            line.starts_with("-- no-transaction") || line.starts_with("-- sqlx:no-transaction")
+        })
+}
+
+fn checksum_with(sql: &str, config: &ResolveConfig) -> Vec<u8> {
+    let normalized;
+    let sql = match config.checksum_normalization {
+        ChecksumNormalization::None => sql,
+        mode => {
+            normalized = normalize::normalize(sql, mode);
+            &normalized
+        }
+    };
+
+    let ignored_chars = &config.ignored_chars;
+
     if ignored_chars.is_empty() {
         // This is going to be much faster because it doesn't have to UTF-8 decode `sql`.
         return migration::checksum(sql);
@@ -290,10 +439,55 @@ fn checksum_with_ignored_chars() {
 
     let stripped_sql = sql.replace(&ignored_chars[..], "");
 
-    let ignored_chars = BTreeSet::from(ignored_chars);
+    let mut config = ResolveConfig::new();
+    config.ignore_chars(ignored_chars);
 
-    let digest_ignored = checksum_with(sql, &ignored_chars);
+    let digest_ignored = checksum_with(sql, &config);
     let digest_stripped = migration::checksum(&stripped_sql);
 
     assert_eq!(digest_ignored, digest_stripped);
 }
+
+#[test]
+fn parse_repeatable_file_name() {
+    let (version, migration_type, description, _) =
+        parse_file_name("R__create_views.sql").unwrap().unwrap();
+
+    assert_eq!(version, migration::repeatable_version("create views"));
+    assert_eq!(migration_type, MigrationType::Repeatable);
+    assert_eq!(description, "create views");
+
+    let (version, migration_type, _, _) = parse_file_name("1_R__create_views.up.sql")
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(version, 1);
+    assert_eq!(migration_type, MigrationType::ReversibleUp);
+
+    assert!(parse_file_name("R__notes.txt").unwrap().is_none());
+}
+
+#[test]
+fn parse_no_tx_markers() {
+    let (_, migration_type, description, no_tx) =
+        parse_file_name("2_add_index.no-transaction.up.sql")
+            .unwrap()
+            .unwrap();
+
+    assert_eq!(migration_type, MigrationType::ReversibleUp);
+    assert_eq!(description, "add index");
+    assert!(no_tx);
+
+    let (_, _, _, no_tx) = parse_file_name("2_add_index.sql").unwrap().unwrap();
+    assert!(!no_tx);
+
+    assert!(has_no_tx_comment(
+        "-- no-transaction\nCREATE INDEX CONCURRENTLY ..."
+    ));
+    assert!(has_no_tx_comment(
+        "-- Add an index without blocking writes\n\n-- sqlx:no-transaction\nCREATE INDEX ..."
+    ));
+    assert!(!has_no_tx_comment(
+        "CREATE INDEX ...;\n-- sqlx:no-transaction"
+    ));
+}
@@ -0,0 +1,290 @@
+use std::future::{self, Future};
+use std::str::FromStr;
+use std::time::Duration;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::{stream, FutureExt, StreamExt, TryStreamExt};
+use log::LevelFilter;
+
+use crate::connection::{ConnectOptions, Connection};
+use crate::database::Database;
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::mock::{Mock, MockQueryResult, MockRow, MockScript, MockStatement, MockTypeInfo};
+use crate::sql_str::SqlStr;
+use crate::statistics::StatementStatistics;
+use crate::transaction::{
+    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
+    Transaction, TransactionManager,
+};
+use crate::Url;
+
+/// A connection to the mock database, running a [`MockScript`].
+#[derive(Debug)]
+pub struct MockConnection {
+    script: MockScript,
+    transaction_depth: usize,
+}
+
+/// Options to connect to the mock database, from [`MockScript::connect_options()`].
+///
+/// Parsing a `mock:` URL gives options with a new, empty script. Statements are neither logged
+/// nor timed on the mock database, so the logging and statistics settings have no effect.
+#[derive(Debug, Clone)]
+pub struct MockConnectOptions {
+    pub(crate) script: MockScript,
+}
+
+/// Implementation of [`TransactionManager`] for the mock database.
+pub struct MockTransactionManager;
+
+impl MockConnection {
+    pub(crate) fn new(script: MockScript) -> Self {
+        Self {
+            script,
+            transaction_depth: 0,
+        }
+    }
+
+    /// The script this connection runs.
+    pub fn script(&self) -> &MockScript {
+        &self.script
+    }
+}
+
+impl Connection for MockConnection {
+    type Database = Mock;
+
+    type Options = MockConnectOptions;
+
+    fn close(self) -> impl Future<Output = Result<(), Error>> + Send + 'static {
+        future::ready(Ok(()))
+    }
+
+    fn close_hard(self) -> impl Future<Output = Result<(), Error>> + Send + 'static {
+        future::ready(Ok(()))
+    }
+
+    fn ping(&mut self) -> impl Future<Output = Result<Duration, Error>> + Send + '_ {
+        future::ready(Ok(Duration::ZERO))
+    }
+
+    fn begin(
+        &mut self,
+    ) -> impl Future<Output = Result<Transaction<'_, Self::Database>, Error>> + Send + '_ {
+        Transaction::begin(self, None)
+    }
+
+    fn shrink_buffers(&mut self) {}
+
+    fn flush(&mut self) -> impl Future<Output = Result<(), Error>> + Send + '_ {
+        future::ready(Ok(()))
+    }
+
+    fn should_flush(&self) -> bool {
+        false
+    }
+}
+
+impl<'c> Executor<'c> for &'c mut MockConnection {
+    type Database = Mock;
+
+    fn fetch_many<'e, 'q: 'e, E>(
+        self,
+        mut query: E,
+    ) -> BoxStream<'e, Result<Either<MockQueryResult, MockRow>, Error>>
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Mock>,
+    {
+        let arguments = match query.take_arguments().map_err(Error::Encode) {
+            Ok(arguments) => arguments.map(|arguments| arguments.values),
+            Err(error) => return stream::once(future::ready(Err(error))).boxed(),
+        };
+
+        let script = self.script.clone();
+        let sql = query.sql();
+
+        // respond once polled, as a connection to a database would execute the statement
+        stream::once(async move { script.execute(sql.as_str(), arguments.unwrap_or_default()) })
+            .map_ok(|(rows, result)| {
+                let steps = rows
+                    .into_iter()
+                    .map(Either::Right)
+                    .chain([Either::Left(result)]);
+
+                stream::iter(steps.map(Ok))
+            })
+            .try_flatten()
+            .boxed()
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<MockRow>, Error>>
+    where
+        'c: 'e,
+        E: 'q + Execute<'q, Mock>,
+    {
+        let mut steps = self.fetch_many(query);
+
+        Box::pin(async move {
+            while let Some(step) = steps.try_next().await? {
+                if let Either::Right(row) = step {
+                    return Ok(Some(row));
+                }
+            }
+
+            Ok(None)
+        })
+    }
+
+    fn prepare_with<'e>(
+        self,
+        sql: SqlStr,
+        _parameters: &'e [MockTypeInfo],
+    ) -> BoxFuture<'e, Result<MockStatement, Error>>
+    where
+        'c: 'e,
+    {
+        let result_set = self.script.describe(sql.as_str());
+
+        future::ready(result_set.map(|result_set| MockStatement {
+            sql,
+            columns: result_set.columns,
+            column_names: result_set.column_names,
+        }))
+        .boxed()
+    }
+
+    fn describe<'e>(self, sql: SqlStr) -> BoxFuture<'e, Result<Describe<Mock>, Error>>
+    where
+        'c: 'e,
+    {
+        let result_set = self.script.describe(sql.as_str());
+
+        future::ready(result_set.map(|result_set| Describe {
+            nullable: vec![None; result_set.columns.len()],
+            columns: result_set.columns.to_vec(),
+            parameters: None,
+        }))
+        .boxed()
+    }
+}
+
+impl TransactionManager for MockTransactionManager {
+    type Database = Mock;
+
+    async fn begin(conn: &mut MockConnection, statement: Option<SqlStr>) -> Result<(), Error> {
+        let depth = conn.transaction_depth;
+
+        let statement = match statement {
+            // custom `BEGIN` statements are not allowed if we're already in
+            // a transaction (we need to issue a `SAVEPOINT` instead)
+            Some(_) if depth > 0 => return Err(Error::InvalidSavePointStatement),
+            Some(statement) => statement,
+            None => begin_ansi_transaction_sql(depth),
+        };
+
+        conn.script.transaction(statement.as_str())?;
+        conn.transaction_depth += 1;
+
+        Ok(())
+    }
+
+    async fn commit(conn: &mut MockConnection) -> Result<(), Error> {
+        if conn.transaction_depth > 0 {
+            let statement = commit_ansi_transaction_sql(conn.transaction_depth);
+
+            conn.script.transaction(statement.as_str())?;
+            conn.transaction_depth -= 1;
+        }
+
+        Ok(())
+    }
+
+    async fn rollback(conn: &mut MockConnection) -> Result<(), Error> {
+        if conn.transaction_depth > 0 {
+            let statement = rollback_ansi_transaction_sql(conn.transaction_depth);
+
+            conn.script.transaction(statement.as_str())?;
+            conn.transaction_depth -= 1;
+        }
+
+        Ok(())
+    }
+
+    fn start_rollback(conn: &mut MockConnection) {
+        if conn.transaction_depth > 0 {
+            let statement = rollback_ansi_transaction_sql(conn.transaction_depth);
+
+            // the rollback is recorded, but as with a real connection its result is not awaited
+            let _ = conn.script.transaction(statement.as_str());
+            conn.transaction_depth -= 1;
+        }
+    }
+
+    fn get_transaction_depth(conn: &<Self::Database as Database>::Connection) -> usize {
+        conn.transaction_depth
+    }
+}
+
+impl ConnectOptions for MockConnectOptions {
+    type Connection = MockConnection;
+
+    fn from_url(url: &Url) -> Result<Self, Error> {
+        if url.scheme() != "mock" {
+            return Err(Error::Configuration(
+                format!(
+                    "unsupported URL scheme for the mock database: {}",
+                    url.scheme()
+                )
+                .into(),
+            ));
+        }
+
+        Ok(MockScript::new().connect_options())
+    }
+
+    fn to_url_lossy(&self) -> Url {
+        Url::parse("mock:").expect("BUG: generated un-parseable URL")
+    }
+
+    fn connect(&self) -> impl Future<Output = Result<MockConnection, Error>> + Send + '_ {
+        future::ready(Ok(self.script.connection()))
+    }
+
+    fn log_statements(self, _level: LevelFilter) -> Self {
+        self
+    }
+
+    fn log_slow_statements(self, _level: LevelFilter, _duration: Duration) -> Self {
+        self
+    }
+
+    fn slow_query_threshold(self, _threshold: Duration) -> Self {
+        self
+    }
+
+    fn collect_statistics(self, _max_statements: usize) -> Self {
+        self
+    }
+
+    fn statistics(&self) -> Option<&StatementStatistics> {
+        None
+    }
+}
+
+impl FromStr for MockConnectOptions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let url: Url = s.parse().map_err(Error::config)?;
+
+        Self::from_url(&url)
+    }
+}
@@ -0,0 +1,108 @@
+//! A mock database driver, to unit test code that runs queries without a database.
+//!
+//! A test scripts the statements it expects the code under test to execute with a
+//! [`MockScript`], matching their SQL by a regular expression and optionally their arguments,
+//! and the rows or errors to respond with. The code under test takes a [`MockPool`] or a
+//! [`MockConnection`] where it would otherwise take a pool or connection of a real database,
+//! typically by being generic over the [`Database`] or [`Executor`].
+//!
+//! The mock database supports booleans, integers, floats, strings and byte arrays, which are
+//! enough to decode most rows with; see [`MockValue`].
+//!
+//! ```rust,no_run
+//! # async fn example() -> sqlx_core::Result<()> {
+//! use sqlx_core::mock::{MockPool, MockRows, MockScript};
+//!
+//! let script = MockScript::new();
+//!
+//! script
+//!     .expect(r"^SELECT name FROM users WHERE id = \$1$")
+//!     .with_arguments([42_i64])
+//!     .returns(MockRows::new(["name"]).row(("alice",)));
+//!
+//! script
+//!     .expect("^UPDATE users SET")
+//!     .fails(|| sqlx_core::Error::RowNotFound);
+//!
+//! let pool = MockPool::connect_lazy_with(script.connect_options());
+//!
+//! let name: String = sqlx_core::query_scalar::query_scalar("SELECT name FROM users WHERE id = $1")
+//!     .bind(42_i64)
+//!     .fetch_one(&pool)
+//!     .await?;
+//!
+//! assert_eq!(name, "alice");
+//!
+//! let updated = sqlx_core::query::query("UPDATE users SET name = $1 WHERE id = $2")
+//!     .bind("bob")
+//!     .bind(42_i64)
+//!     .execute(&pool)
+//!     .await;
+//!
+//! assert!(updated.is_err());
+//!
+//! // panics unless every expectation was met
+//! script.verify();
+//! # Ok(())
+//! # }
+//! ```
+use crate::database::Database;
+use crate::executor::Executor;
+
+mod connection;
+mod row;
+mod script;
+mod value;
+
+pub use connection::{MockConnectOptions, MockConnection, MockTransactionManager};
+pub use row::{MockColumn, MockQueryResult, MockRow, MockStatement};
+pub use script::{IntoMockRow, MockExecution, MockExpectation, MockRows, MockScript};
+pub use value::{MockArguments, MockTypeInfo, MockValue};
+
+/// The mock database driver.
+#[derive(Debug)]
+pub struct Mock;
+
+impl Database for Mock {
+    type Connection = MockConnection;
+
+    type TransactionManager = MockTransactionManager;
+
+    type Row = MockRow;
+
+    type QueryResult = MockQueryResult;
+
+    type Column = MockColumn;
+
+    type TypeInfo = MockTypeInfo;
+
+    type Value = MockValue;
+    type ValueRef<'r> = &'r MockValue;
+
+    type Arguments = MockArguments;
+    type ArgumentBuffer = Vec<MockValue>;
+
+    type Statement = MockStatement;
+
+    const NAME: &'static str = "Mock";
+
+    const URL_SCHEMES: &'static [&'static str] = &["mock"];
+
+    // The limit of PostgreSQL, so code which binds too many arguments fails under test as well.
+    const MAX_ARGUMENTS: usize = u16::MAX as usize;
+}
+
+pub type MockPool = crate::pool::Pool<Mock>;
+
+pub type MockPoolOptions = crate::pool::PoolOptions<Mock>;
+
+/// An alias for [`Executor<'_, Database = Mock>`][Executor].
+pub trait MockExecutor<'c>: Executor<'c, Database = Mock> {}
+impl<'c, T: Executor<'c, Database = Mock>> MockExecutor<'c> for T {}
+
+// NOTE: required due to the lack of lazy normalization
+impl_into_arguments_for_arguments!(MockArguments);
+impl_acquire!(Mock, MockConnection);
+impl_column_index_for_row!(MockRow);
+impl_column_index_for_statement!(MockStatement);
+impl_encode_for_option!(Mock);
@@ -0,0 +1,149 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use either::Either;
+
+use crate::column::{Column, ColumnIndex};
+use crate::database::Database;
+use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::mock::{Mock, MockArguments, MockTypeInfo, MockValue};
+use crate::row::Row;
+use crate::sql_str::SqlStr;
+use crate::statement::Statement;
+use crate::HashMap;
+
+/// A row returned by a [`MockScript`][crate::mock::MockScript].
+#[derive(Clone)]
+pub struct MockRow {
+    pub(crate) columns: Arc<[MockColumn]>,
+    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) values: Vec<MockValue>,
+}
+
+/// A column of the rows returned by a [`MockScript`][crate::mock::MockScript].
+///
+/// Its type is the one of the first non-`NULL` value in the column, or `NULL` if there is none.
+#[derive(Debug, Clone)]
+pub struct MockColumn {
+    pub(crate) ordinal: usize,
+    pub(crate) name: UStr,
+    pub(crate) type_info: MockTypeInfo,
+}
+
+/// A statement prepared on a [`MockConnection`][crate::mock::MockConnection].
+///
+/// Its columns are those of the rows the matching expectation returns.
+#[derive(Debug, Clone)]
+pub struct MockStatement {
+    pub(crate) sql: SqlStr,
+    pub(crate) columns: Arc<[MockColumn]>,
+    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+}
+
+/// The result of a statement executed on the mock database.
+#[derive(Debug, Default, Clone)]
+pub struct MockQueryResult {
+    pub(crate) rows_affected: u64,
+}
+
+impl Row for MockRow {
+    type Database = Mock;
+
+    fn columns(&self) -> &[MockColumn] {
+        &self.columns
+    }
+
+    fn try_get_raw<I>(&self, index: I) -> Result<&MockValue, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+
+        Ok(&self.values[index])
+    }
+}
+
+impl Debug for MockRow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.columns
+                    .iter()
+                    .map(|column| &*column.name)
+                    .zip(&self.values),
+            )
+            .finish()
+    }
+}
+
+impl ColumnIndex<MockRow> for &'_ str {
+    fn index(&self, row: &MockRow) -> Result<usize, Error> {
+        row.column_names
+            .get(*self)
+            .copied()
+            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
+    }
+}
+
+impl Column for MockColumn {
+    type Database = Mock;
+
+    fn ordinal(&self) -> usize {
+        self.ordinal
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn type_info(&self) -> &MockTypeInfo {
+        &self.type_info
+    }
+}
+
+impl Statement for MockStatement {
+    type Database = Mock;
+
+    fn into_sql(self) -> SqlStr {
+        self.sql
+    }
+
+    fn sql(&self) -> &SqlStr {
+        &self.sql
+    }
+
+    fn parameters(&self) -> Option<Either<&[MockTypeInfo], usize>> {
+        None
+    }
+
+    fn columns(&self) -> &[<Self::Database as Database>::Column] {
+        &self.columns
+    }
+
+    impl_statement_query!(MockArguments);
+}
+
+impl ColumnIndex<MockStatement> for &'_ str {
+    fn index(&self, statement: &MockStatement) -> Result<usize, Error> {
+        statement
+            .column_names
+            .get(*self)
+            .copied()
+            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
+    }
+}
+
+impl MockQueryResult {
+    pub fn rows_affected(&self) -> u64 {
+        self.rows_affected
+    }
+}
+
+impl Extend<MockQueryResult> for MockQueryResult {
+    fn extend<T: IntoIterator<Item = MockQueryResult>>(&mut self, iter: T) {
+        for elem in iter {
+            self.rows_affected += elem.rows_affected;
+        }
+    }
+}
@@ -0,0 +1,532 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use regex::Regex;
+
+use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::mock::{MockColumn, MockConnectOptions, MockConnection, MockQueryResult, MockRow};
+use crate::mock::{MockTypeInfo, MockValue};
+use crate::HashMap;
+
+/// The statements a test expects to run on the mock database, and how to respond to them.
+///
+/// A script is shared by the connections created from it, so expectations can be added and
+/// the statements executed inspected while the code under test holds a pool.
+///
+/// Each statement executed is matched against the expectations in the order they were added;
+/// the first one which matches and is not used up responds to it. A statement that no
+/// expectation matches fails with [`Error::Protocol`].
+///
+/// Transaction statements (`BEGIN`, `SAVEPOINT`, `COMMIT`, ...) are recorded but succeed
+/// without an expectation; one can still be added to make them fail.
+#[derive(Clone, Default)]
+pub struct MockScript {
+    state: Arc<Mutex<ScriptState>>,
+}
+
+/// An expectation added to a [`MockScript`] with [`MockScript::expect()`].
+///
+/// By default the expectation matches once and responds with zero rows affected.
+pub struct MockExpectation {
+    script: MockScript,
+    index: usize,
+}
+
+/// The rows an expectation returns, as in [`MockExpectation::returns()`].
+///
+/// ```rust
+/// # use sqlx_core::mock::MockRows;
+/// let rows = MockRows::new(["id", "name", "email"])
+///     .row((1_i64, "alice", Some("alice@example.com")))
+///     .row((2_i64, "bob", None::<&str>));
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockRows {
+    columns: Vec<String>,
+    rows: Vec<Vec<MockValue>>,
+}
+
+/// A row of values for [`MockRows::row()`]; implemented for tuples of up to 16 values, arrays
+/// and `Vec`s.
+pub trait IntoMockRow {
+    fn into_mock_row(self) -> Vec<MockValue>;
+}
+
+/// A statement executed on the mock database, as returned by [`MockScript::executed()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockExecution {
+    sql: String,
+    arguments: Vec<MockValue>,
+}
+
+#[derive(Default)]
+struct ScriptState {
+    expectations: Vec<Expectation>,
+    executed: Vec<MockExecution>,
+}
+
+struct Expectation {
+    pattern: Regex,
+    arguments: Option<Vec<MockValue>>,
+    response: Response,
+    // the number of times the expectation still matches, `None` for any number
+    remaining: Option<usize>,
+}
+
+#[derive(Clone)]
+enum Response {
+    Rows(ResultSet),
+    Affected(u64),
+    Error(Arc<dyn Fn() -> Error + Send + Sync>),
+}
+
+#[derive(Clone)]
+pub(crate) struct ResultSet {
+    pub(crate) columns: Arc<[MockColumn]>,
+    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    rows: Vec<Vec<MockValue>>,
+}
+
+impl MockScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expect a statement matching `pattern`, a regular expression.
+    ///
+    /// The pattern is searched for in the SQL with runs of whitespace collapsed into a single
+    /// space, so it may match any part of it; anchor it with `^` and `$` to match the whole
+    /// statement. Special characters such as `$`, `(` and `*` must be escaped with `\`.
+    ///
+    /// ### Panics
+    /// If `pattern` is not a valid regular expression.
+    pub fn expect(&self, pattern: &str) -> MockExpectation {
+        let pattern = Regex::new(pattern)
+            .unwrap_or_else(|e| panic!("invalid pattern for mock expectation: {e}"));
+
+        let mut state = self.lock();
+
+        state.expectations.push(Expectation {
+            pattern,
+            arguments: None,
+            response: Response::Affected(0),
+            remaining: Some(1),
+        });
+
+        MockExpectation {
+            script: self.clone(),
+            index: state.expectations.len() - 1,
+        }
+    }
+
+    /// The statements executed so far, in order, including transaction statements and those
+    /// which matched no expectation.
+    pub fn executed(&self) -> Vec<MockExecution> {
+        self.lock().executed.clone()
+    }
+
+    /// Panic if an expectation was not matched as many times as it expects to be.
+    ///
+    /// Call it at the end of a test to check that every statement expected has run.
+    #[track_caller]
+    pub fn verify(&self) {
+        let unmet: Vec<_> = self
+            .lock()
+            .expectations
+            .iter()
+            .filter(|expectation| expectation.remaining.is_some_and(|n| n > 0))
+            .map(|expectation| expectation.pattern.to_string())
+            .collect();
+
+        if !unmet.is_empty() {
+            panic!("mock expectations were not met: {unmet:?}");
+        }
+    }
+
+    /// Options to connect to the mock database with this script, such as with
+    /// [`Pool::connect_lazy_with()`][crate::pool::Pool::connect_lazy_with].
+    pub fn connect_options(&self) -> MockConnectOptions {
+        MockConnectOptions {
+            script: self.clone(),
+        }
+    }
+
+    /// A connection to the mock database running this script.
+    pub fn connection(&self) -> MockConnection {
+        MockConnection::new(self.clone())
+    }
+
+    /// Respond to a statement executed on a connection.
+    pub(crate) fn execute(
+        &self,
+        sql: &str,
+        arguments: Vec<MockValue>,
+    ) -> Result<(Vec<MockRow>, MockQueryResult), Error> {
+        match self.respond(sql, arguments.clone()) {
+            Some(response) => response.into_result(),
+            None => Err(Error::Protocol(format!(
+                "mock: no expectation matches {sql:?} with arguments {arguments:?}"
+            ))),
+        }
+    }
+
+    /// Respond to a transaction statement, which succeeds unless an expectation says otherwise.
+    pub(crate) fn transaction(&self, sql: &str) -> Result<(), Error> {
+        match self.respond(sql, Vec::new()) {
+            Some(response) => response.into_result().map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// The columns returned by a statement, for preparing or describing it.
+    pub(crate) fn describe(&self, sql: &str) -> Result<ResultSet, Error> {
+        let normalized = normalize(sql);
+        let state = self.lock();
+
+        let expectation = state
+            .expectations
+            .iter()
+            .find(|expectation| expectation.matches(&normalized, None))
+            .ok_or_else(|| Error::Protocol(format!("mock: no expectation matches {sql:?}")))?;
+
+        Ok(match &expectation.response {
+            Response::Rows(result_set) => result_set.clone(),
+            _ => MockRows::new(Vec::<String>::new()).into_result_set(),
+        })
+    }
+
+    fn respond(&self, sql: &str, arguments: Vec<MockValue>) -> Option<Response> {
+        let normalized = normalize(sql);
+        let mut state = self.lock();
+
+        let response = state
+            .expectations
+            .iter_mut()
+            .find(|expectation| expectation.matches(&normalized, Some(&arguments[..])))
+            .map(|expectation| {
+                if let Some(remaining) = &mut expectation.remaining {
+                    *remaining -= 1;
+                }
+
+                expectation.response.clone()
+            });
+
+        state.executed.push(MockExecution {
+            sql: sql.to_owned(),
+            arguments,
+        });
+
+        response
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ScriptState> {
+        self.state
+            .lock()
+            .expect("BUG: panicked while holding a lock")
+    }
+
+    fn update(&self, index: usize, f: impl FnOnce(&mut Expectation)) {
+        f(&mut self.lock().expectations[index]);
+    }
+}
+
+impl Debug for MockScript {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let state = self.lock();
+
+        f.debug_struct("MockScript")
+            .field(
+                "expectations",
+                &state
+                    .expectations
+                    .iter()
+                    .map(|expectation| expectation.pattern.as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .field("executed", &state.executed)
+            .finish()
+    }
+}
+
+impl MockExpectation {
+    /// Only match the statement if it is executed with exactly these arguments.
+    pub fn with_arguments<I>(self, arguments: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<MockValue>,
+    {
+        let arguments = arguments.into_iter().map(Into::into).collect();
+
+        self.script.update(self.index, |expectation| {
+            expectation.arguments = Some(arguments)
+        });
+        self
+    }
+
+    /// Respond with `rows`.
+    pub fn returns(self, rows: MockRows) -> Self {
+        let result_set = rows.into_result_set();
+
+        self.script.update(self.index, |expectation| {
+            expectation.response = Response::Rows(result_set)
+        });
+        self
+    }
+
+    /// Respond with `rows_affected` rows affected and no rows.
+    pub fn affects(self, rows_affected: u64) -> Self {
+        self.script.update(self.index, |expectation| {
+            expectation.response = Response::Affected(rows_affected)
+        });
+        self
+    }
+
+    /// Respond with the error returned by `error`, called each time the expectation matches.
+    pub fn fails<F>(self, error: F) -> Self
+    where
+        F: Fn() -> Error + Send + Sync + 'static,
+    {
+        self.script.update(self.index, |expectation| {
+            expectation.response = Response::Error(Arc::new(error))
+        });
+        self
+    }
+
+    /// Match the statement `times` times; [`MockScript::verify()`] checks that it ran as many.
+    pub fn times(self, times: usize) -> Self {
+        self.script.update(self.index, |expectation| {
+            expectation.remaining = Some(times)
+        });
+        self
+    }
+
+    /// Match the statement any number of times, including none.
+    pub fn repeatedly(self) -> Self {
+        self.script
+            .update(self.index, |expectation| expectation.remaining = None);
+        self
+    }
+}
+
+impl MockRows {
+    /// Rows with the named columns, and none yet.
+    pub fn new<I>(columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row.
+    ///
+    /// ### Panics
+    /// If the row does not have as many values as there are columns.
+    #[track_caller]
+    pub fn row(mut self, row: impl IntoMockRow) -> Self {
+        let values = row.into_mock_row();
+
+        assert_eq!(
+            values.len(),
+            self.columns.len(),
+            "mock row has {} values for {} columns",
+            values.len(),
+            self.columns.len()
+        );
+
+        self.rows.push(values);
+        self
+    }
+
+    /// Add each of `rows`.
+    #[track_caller]
+    pub fn rows<I>(self, rows: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoMockRow,
+    {
+        rows.into_iter().fold(self, Self::row)
+    }
+
+    fn into_result_set(self) -> ResultSet {
+        let columns: Arc<[MockColumn]> = self
+            .columns
+            .into_iter()
+            .enumerate()
+            .map(|(ordinal, name)| MockColumn {
+                ordinal,
+                name: UStr::new(&name),
+                type_info: self
+                    .rows
+                    .iter()
+                    .map(|row| row[ordinal].kind())
+                    .find(|kind| *kind != MockTypeInfo::Null)
+                    .unwrap_or(MockTypeInfo::Null),
+            })
+            .collect();
+
+        let column_names = columns
+            .iter()
+            .map(|column| (column.name.clone(), column.ordinal))
+            .collect();
+
+        ResultSet {
+            columns,
+            column_names: Arc::new(column_names),
+            rows: self.rows,
+        }
+    }
+}
+
+impl MockExecution {
+    /// The SQL of the statement, as executed.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The arguments bound to the statement.
+    pub fn arguments(&self) -> &[MockValue] {
+        &self.arguments
+    }
+}
+
+impl Expectation {
+    fn matches(&self, sql: &str, arguments: Option<&[MockValue]>) -> bool {
+        self.remaining != Some(0)
+            && self.pattern.is_match(sql)
+            && match (&self.arguments, arguments) {
+                (Some(expected), Some(arguments)) => expected == arguments,
+                _ => true,
+            }
+    }
+}
+
+impl Response {
+    fn into_result(self) -> Result<(Vec<MockRow>, MockQueryResult), Error> {
+        match self {
+            Response::Rows(result_set) => {
+                let rows_affected = result_set.rows.len() as u64;
+
+                let rows = result_set
+                    .rows
+                    .into_iter()
+                    .map(|values| MockRow {
+                        columns: Arc::clone(&result_set.columns),
+                        column_names: Arc::clone(&result_set.column_names),
+                        values,
+                    })
+                    .collect();
+
+                Ok((rows, MockQueryResult { rows_affected }))
+            }
+
+            Response::Affected(rows_affected) => {
+                Ok((Vec::new(), MockQueryResult { rows_affected }))
+            }
+
+            Response::Error(error) => Err(error()),
+        }
+    }
+}
+
+// collapse runs of whitespace, so patterns need not follow how the SQL is laid out
+fn normalize(sql: &str) -> String {
+    sql.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl<V: Into<MockValue>> IntoMockRow for Vec<V> {
+    fn into_mock_row(self) -> Vec<MockValue> {
+        self.into_iter().map(Into::into).collect()
+    }
+}
+
+impl<V: Into<MockValue>, const N: usize> IntoMockRow for [V; N] {
+    fn into_mock_row(self) -> Vec<MockValue> {
+        self.into_iter().map(Into::into).collect()
+    }
+}
+
+macro_rules! impl_into_mock_row_for_tuple {
+    ($($T:ident),+) => {
+        impl<$($T: Into<MockValue>),+> IntoMockRow for ($($T,)+) {
+            #[allow(non_snake_case)]
+            fn into_mock_row(self) -> Vec<MockValue> {
+                let ($($T,)+) = self;
+
+                vec![$($T.into()),+]
+            }
+        }
+    };
+}
+
+impl_into_mock_row_for_tuple!(T1);
+impl_into_mock_row_for_tuple!(T1, T2);
+impl_into_mock_row_for_tuple!(T1, T2, T3);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14);
+impl_into_mock_row_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15);
+impl_into_mock_row_for_tuple!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
+);
+
+#[test]
+fn it_matches_expectations_in_order() {
+    let script = MockScript::new();
+
+    script
+        .expect(r"^SELECT name FROM users WHERE id = \$1$")
+        .with_arguments([1_i64])
+        .returns(MockRows::new(["name"]).row(("alice",)));
+    script.expect("^DELETE FROM users").affects(3).times(2);
+
+    let (rows, _) = script
+        .execute(
+            "SELECT name\n  FROM users WHERE id = $1",
+            vec![1_i64.into()],
+        )
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].values, [MockValue::Text("alice".into())]);
+
+    // used up
+    assert!(script
+        .execute("SELECT name FROM users WHERE id = $1", vec![1_i64.into()])
+        .is_err());
+
+    let (_, result) = script.execute("DELETE FROM users", Vec::new()).unwrap();
+    assert_eq!(result.rows_affected(), 3);
+
+    assert!(std::panic::catch_unwind(|| script.verify()).is_err());
+    script.execute("DELETE FROM users", Vec::new()).unwrap();
+    script.verify();
+
+    assert_eq!(script.executed().len(), 4);
+    assert_eq!(script.executed()[0].arguments(), [MockValue::Int(1)]);
+}
+
+#[test]
+fn it_infers_column_types() {
+    let result_set = MockRows::new(["a", "b", "c"])
+        .row((None::<i64>, "x", None::<bool>))
+        .row((Some(1_i64), "y", None::<bool>))
+        .into_result_set();
+
+    let types: Vec<_> = result_set.columns.iter().map(|c| c.type_info).collect();
+    assert_eq!(
+        types,
+        [MockTypeInfo::Int, MockTypeInfo::Text, MockTypeInfo::Null]
+    );
+}
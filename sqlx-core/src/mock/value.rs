@@ -0,0 +1,266 @@
+use std::borrow::Cow;
+use std::fmt::{self, Display, Formatter, Write};
+
+use crate::arguments::Arguments;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::mock::Mock;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+use crate::value::{Value, ValueRef};
+
+/// A value bound to or returned by a [`MockScript`][crate::mock::MockScript].
+///
+/// Converted from the Rust types the mock database supports with [`From`]; `None` becomes
+/// [`Null`][Self::Null].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// The type of a [`MockValue`], or of a column of the mock database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockTypeInfo {
+    Null,
+    Bool,
+    Int,
+    Float,
+    Text,
+    Blob,
+}
+
+/// The arguments bound to a query on the mock database.
+#[derive(Debug, Default, Clone)]
+pub struct MockArguments {
+    pub(crate) values: Vec<MockValue>,
+}
+
+impl MockValue {
+    pub(crate) fn kind(&self) -> MockTypeInfo {
+        match self {
+            MockValue::Null => MockTypeInfo::Null,
+            MockValue::Bool(_) => MockTypeInfo::Bool,
+            MockValue::Int(_) => MockTypeInfo::Int,
+            MockValue::Float(_) => MockTypeInfo::Float,
+            MockValue::Text(_) => MockTypeInfo::Text,
+            MockValue::Blob(_) => MockTypeInfo::Blob,
+        }
+    }
+
+    fn unexpected<T>(&self, expected: MockTypeInfo) -> Result<T, BoxDynError> {
+        Err(format!("expected {expected}, got {}", self.kind()).into())
+    }
+}
+
+impl Value for MockValue {
+    type Database = Mock;
+
+    fn as_ref(&self) -> &MockValue {
+        self
+    }
+
+    fn type_info(&self) -> Cow<'_, MockTypeInfo> {
+        Cow::Owned(self.kind())
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self, MockValue::Null)
+    }
+}
+
+impl<'r> ValueRef<'r> for &'r MockValue {
+    type Database = Mock;
+
+    fn to_owned(&self) -> MockValue {
+        (*self).clone()
+    }
+
+    fn type_info(&self) -> Cow<'_, MockTypeInfo> {
+        Cow::Owned(self.kind())
+    }
+
+    fn is_null(&self) -> bool {
+        matches!(self, MockValue::Null)
+    }
+}
+
+impl TypeInfo for MockTypeInfo {
+    fn is_null(&self) -> bool {
+        matches!(self, MockTypeInfo::Null)
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            MockTypeInfo::Null => "NULL",
+            MockTypeInfo::Bool => "BOOL",
+            MockTypeInfo::Int => "INT",
+            MockTypeInfo::Float => "FLOAT",
+            MockTypeInfo::Text => "TEXT",
+            MockTypeInfo::Blob => "BLOB",
+        }
+    }
+}
+
+impl Display for MockTypeInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Arguments for MockArguments {
+    type Database = Mock;
+
+    fn reserve(&mut self, additional: usize, _size: usize) {
+        self.values.reserve(additional);
+    }
+
+    fn add<'t, T>(&mut self, value: T) -> Result<(), BoxDynError>
+    where
+        T: Encode<'t, Mock> + Type<Mock>,
+    {
+        if let IsNull::Yes = value.encode(&mut self.values)? {
+            self.values.push(MockValue::Null);
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "${}", self.values.len())
+    }
+}
+
+impl<T: Into<MockValue>> From<Option<T>> for MockValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(MockValue::Null, Into::into)
+    }
+}
+
+impl From<&str> for MockValue {
+    fn from(value: &str) -> Self {
+        MockValue::Text(value.to_owned())
+    }
+}
+
+impl From<&[u8]> for MockValue {
+    fn from(value: &[u8]) -> Self {
+        MockValue::Blob(value.to_owned())
+    }
+}
+
+// `Type`, `Encode`, `Decode` and `From` for the types stored as `$variant`
+macro_rules! impl_mock_type {
+    ($($ty:ty => $variant:ident,)*) => {$(
+        impl Type<Mock> for $ty {
+            fn type_info() -> MockTypeInfo {
+                MockTypeInfo::$variant
+            }
+        }
+
+        impl Encode<'_, Mock> for $ty {
+            fn encode_by_ref(&self, buf: &mut Vec<MockValue>) -> Result<IsNull, BoxDynError> {
+                buf.push(MockValue::from(<$ty as Clone>::clone(self)));
+                Ok(IsNull::No)
+            }
+        }
+
+        impl From<$ty> for MockValue {
+            fn from(value: $ty) -> Self {
+                MockValue::$variant(value.into())
+            }
+        }
+    )*};
+}
+
+impl_mock_type! {
+    bool => Bool,
+    i8 => Int,
+    i16 => Int,
+    i32 => Int,
+    i64 => Int,
+    f32 => Float,
+    f64 => Float,
+    String => Text,
+    Vec<u8> => Blob,
+}
+
+macro_rules! impl_mock_decode {
+    ($($ty:ty => $variant:ident($value:ident) => $convert:expr,)*) => {$(
+        impl<'r> Decode<'r, Mock> for $ty {
+            fn decode(value: &'r MockValue) -> Result<Self, BoxDynError> {
+                match value {
+                    MockValue::$variant($value) => Ok($convert),
+                    other => other.unexpected(MockTypeInfo::$variant),
+                }
+            }
+        }
+    )*};
+}
+
+impl_mock_decode! {
+    bool => Bool(value) => *value,
+    i8 => Int(value) => (*value).try_into()?,
+    i16 => Int(value) => (*value).try_into()?,
+    i32 => Int(value) => (*value).try_into()?,
+    i64 => Int(value) => *value,
+    String => Text(value) => value.clone(),
+    &'r str => Text(value) => value.as_str(),
+    Vec<u8> => Blob(value) => value.clone(),
+    &'r [u8] => Blob(value) => value.as_slice(),
+}
+
+impl Type<Mock> for str {
+    fn type_info() -> MockTypeInfo {
+        MockTypeInfo::Text
+    }
+}
+
+impl Encode<'_, Mock> for &'_ str {
+    fn encode_by_ref(&self, buf: &mut Vec<MockValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MockValue::from(*self));
+        Ok(IsNull::No)
+    }
+}
+
+impl Type<Mock> for [u8] {
+    fn type_info() -> MockTypeInfo {
+        MockTypeInfo::Blob
+    }
+}
+
+impl Encode<'_, Mock> for &'_ [u8] {
+    fn encode_by_ref(&self, buf: &mut Vec<MockValue>) -> Result<IsNull, BoxDynError> {
+        buf.push(MockValue::from(*self));
+        Ok(IsNull::No)
+    }
+}
+
+// integers are accepted where floats are expected, as literals in scripts are often written
+// without a decimal point
+impl<'r> Decode<'r, Mock> for f64 {
+    fn decode(value: &'r MockValue) -> Result<Self, BoxDynError> {
+        match value {
+            MockValue::Float(float) => Ok(*float),
+            MockValue::Int(int) => Ok(*int as f64),
+            other => other.unexpected(MockTypeInfo::Float),
+        }
+    }
+}
+
+impl<'r> Decode<'r, Mock> for f32 {
+    fn decode(value: &'r MockValue) -> Result<Self, BoxDynError> {
+        // floats are stored as `f64`, so a value set as an `f32` round-trips exactly
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(<f64 as Decode<Mock>>::decode(value)? as f32)
+    }
+}
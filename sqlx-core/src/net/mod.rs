@@ -1,6 +1,14 @@
+pub mod proxy;
 mod socket;
 pub mod tls;
 
+#[cfg(all(feature = "ssh-tunnel", unix))]
+pub mod ssh;
+
 pub use socket::{
-    connect_tcp, connect_uds, BufferedSocket, Socket, SocketIntoBox, WithSocket, WriteBuffer,
+    connect_tcp, connect_tcp_with_options, connect_uds, BufferedSocket, Resolver, SharedResolver,
+    Socket, SocketIntoBox, TcpOptions, WithSocket, WriteBuffer, DEFAULT_BUF_SIZE,
 };
+
+#[cfg(feature = "_rt-async-io")]
+pub(crate) use socket::connect_tcp_async_io;
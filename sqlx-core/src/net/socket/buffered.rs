@@ -1,13 +1,14 @@
 use crate::error::Error;
 use crate::net::Socket;
-use bytes::BytesMut;
+use bytes::{Buf, BytesMut};
+use std::io::IoSlice;
 use std::ops::ControlFlow;
 use std::{cmp, io};
 
 use crate::io::{AsyncRead, AsyncReadExt, ProtocolDecode, ProtocolEncode};
 
 // Tokio, async-std, and std all use this as the default capacity for their buffered I/O.
-const DEFAULT_BUF_SIZE: usize = 8192;
+pub const DEFAULT_BUF_SIZE: usize = 8192;
 
 pub struct BufferedSocket<S> {
     socket: S,
@@ -19,11 +20,15 @@ pub struct WriteBuffer {
     buf: Vec<u8>,
     bytes_written: usize,
     bytes_flushed: usize,
+    // the capacity `shrink()` goes back to
+    capacity: usize,
 }
 
 pub struct ReadBuffer {
     read: BytesMut,
     available: BytesMut,
+    // the capacity `shrink()` goes back to
+    capacity: usize,
 }
 
 impl<S: Socket> BufferedSocket<S> {
@@ -31,16 +36,31 @@ impl<S: Socket> BufferedSocket<S> {
     where
         S: Sized,
     {
+        Self::with_capacity(socket, DEFAULT_BUF_SIZE, DEFAULT_BUF_SIZE)
+    }
+
+    /// Buffer `socket`, with buffers of the given initial capacities.
+    ///
+    /// The buffers grow to fit larger messages, and go back to these capacities when shrunk.
+    pub fn with_capacity(socket: S, read_capacity: usize, write_capacity: usize) -> Self
+    where
+        S: Sized,
+    {
+        // the write buffer is never empty
+        let write_capacity = cmp::max(write_capacity, 1);
+
         BufferedSocket {
             socket,
             write_buf: WriteBuffer {
-                buf: Vec::with_capacity(DEFAULT_BUF_SIZE),
+                buf: Vec::with_capacity(write_capacity),
                 bytes_written: 0,
                 bytes_flushed: 0,
+                capacity: write_capacity,
             },
             read_buf: ReadBuffer {
                 read: BytesMut::new(),
-                available: BytesMut::with_capacity(DEFAULT_BUF_SIZE),
+                available: BytesMut::with_capacity(read_capacity),
+                capacity: read_capacity,
             },
         }
     }
@@ -80,6 +100,25 @@ impl<S: Socket> BufferedSocket<S> {
         }
     }
 
+    /// Read and drop the next `len` bytes, without buffering more than the capacity of the
+    /// read buffer at once.
+    ///
+    /// Not cancel-safe: the bytes dropped so far are lost.
+    pub async fn discard(&mut self, len: usize) -> io::Result<()> {
+        let mut remaining = len;
+
+        while remaining > 0 {
+            let chunk = cmp::min(remaining, cmp::max(self.read_buf.capacity, 1));
+            self.read_buf.read(chunk, &mut self.socket).await?;
+
+            let consumed = cmp::min(remaining, self.read_buf.read.len());
+            self.read_buf.read.advance(consumed);
+            remaining -= consumed;
+        }
+
+        Ok(())
+    }
+
     pub fn write_buffer(&self) -> &WriteBuffer {
         &self.write_buf
     }
@@ -134,6 +173,47 @@ impl<S: Socket> BufferedSocket<S> {
         Ok(())
     }
 
+    /// Flush the write buffer followed by `slice`, which is written from where it is instead of
+    /// being copied into the buffer, then buffer `tail` behind it.
+    ///
+    /// For payloads too large to be worth copying; the buffer is written alongside `slice`
+    /// with vectored writes where the socket supports them.
+    ///
+    /// Cancel-safe: if the future is dropped or fails part-way, whatever is left of `slice`
+    /// is copied into the buffer along with `tail`, so the next flush finishes the write.
+    pub async fn flush_with(&mut self, slice: &[u8], tail: &[u8]) -> io::Result<()> {
+        let mut unwritten = Unwritten {
+            write_buf: &mut self.write_buf,
+            slice,
+            tail,
+        };
+
+        while !unwritten.write_buf.is_empty() || !unwritten.slice.is_empty() {
+            let bufs = [
+                IoSlice::new(unwritten.write_buf.get()),
+                IoSlice::new(unwritten.slice),
+            ];
+            let written = self.socket.write_vectored(&bufs).await?;
+
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+
+            let buffered = cmp::min(written, unwritten.write_buf.get().len());
+            unwritten.write_buf.consume(buffered);
+            unwritten.write_buf.sanity_check();
+
+            unwritten.slice = &unwritten.slice[written - buffered..];
+        }
+
+        // buffers `tail`
+        drop(unwritten);
+
+        self.socket.flush().await?;
+
+        Ok(())
+    }
+
     pub async fn shutdown(&mut self) -> io::Result<()> {
         self.flush().await?;
         self.socket.shutdown().await
@@ -158,6 +238,21 @@ impl<S: Socket> BufferedSocket<S> {
     }
 }
 
+/// The part of a [`BufferedSocket::flush_with()`] write that has not reached the socket yet;
+/// moved into the write buffer when dropped.
+struct Unwritten<'a> {
+    write_buf: &'a mut WriteBuffer,
+    slice: &'a [u8],
+    tail: &'a [u8],
+}
+
+impl Drop for Unwritten<'_> {
+    fn drop(&mut self) {
+        self.write_buf.put_slice(self.slice);
+        self.write_buf.put_slice(self.tail);
+    }
+}
+
 impl WriteBuffer {
     fn sanity_check(&self) {
         assert_ne!(self.buf.capacity(), 0);
@@ -222,6 +317,11 @@ impl WriteBuffer {
         Ok(read)
     }
 
+    /// The capacity the buffer was created with, and goes back to when shrunk.
+    pub fn initial_capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn is_empty(&self) -> bool {
         self.bytes_flushed >= self.bytes_written
     }
@@ -250,7 +350,7 @@ impl WriteBuffer {
 
         // Drop excess capacity.
         self.buf
-            .truncate(cmp::max(self.bytes_written, DEFAULT_BUF_SIZE));
+            .truncate(cmp::max(self.bytes_written, self.capacity));
         self.buf.shrink_to_fit();
     }
 
@@ -311,7 +411,7 @@ impl ReadBuffer {
     }
 
     fn shrink(&mut self) {
-        if self.available.capacity() > DEFAULT_BUF_SIZE {
+        if self.available.capacity() > self.capacity {
             // `BytesMut` doesn't have a way to shrink its capacity,
             // but we only use `available` for spare capacity anyway so we can just replace it.
             //
@@ -323,7 +423,72 @@ impl ReadBuffer {
             // but that's also kind of unavoidable.
             //
             // We should be warning the user not to call this often.
-            self.available = BytesMut::with_capacity(DEFAULT_BUF_SIZE);
+            self.available = BytesMut::with_capacity(self.capacity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::ReadBuf;
+    use futures_util::task::noop_waker_ref;
+    use std::future::Future;
+    use std::task::{Context, Poll};
+
+    /// Accepts `capacity` bytes, then blocks forever.
+    struct Stalling {
+        written: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl Socket for Stalling {
+        fn try_read(&mut self, _buf: &mut dyn ReadBuf) -> io::Result<usize> {
+            Err(io::ErrorKind::WouldBlock.into())
         }
+
+        fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let len = cmp::min(buf.len(), self.capacity - self.written.len());
+
+            if len == 0 {
+                return Err(io::ErrorKind::WouldBlock.into());
+            }
+
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn poll_read_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Pending
+        }
+
+        fn poll_write_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Pending
+        }
+
+        fn poll_shutdown(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    #[test]
+    fn cancelled_flush_with_keeps_the_rest_buffered() {
+        let mut socket = BufferedSocket::with_capacity(
+            Stalling {
+                written: Vec::new(),
+                capacity: 6,
+            },
+            16,
+            16,
+        );
+        socket.write_buffer_mut().put_slice(b"head");
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut flush = Box::pin(socket.flush_with(b"slice", b"tail"));
+        assert!(flush.as_mut().poll(&mut cx).is_pending());
+        drop(flush);
+
+        assert_eq!(socket.write_buffer().get(), b"icetail");
+        assert_eq!(socket.into_inner().written, b"headsl");
     }
 }
@@ -1,22 +1,37 @@
 use std::future::Future;
-use std::io;
+use std::io::{self, IoSlice};
 use std::path::Path;
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-pub use buffered::{BufferedSocket, WriteBuffer};
+pub use buffered::{BufferedSocket, WriteBuffer, DEFAULT_BUF_SIZE};
 use bytes::BufMut;
 use cfg_if::cfg_if;
+pub use resolve::{Resolver, SharedResolver};
+pub use tcp::TcpOptions;
 
 use crate::io::ReadBuf;
 
 mod buffered;
+mod resolve;
+mod tcp;
 
 pub trait Socket: Send + Sync + Unpin + 'static {
     fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize>;
 
     fn try_write(&mut self, buf: &[u8]) -> io::Result<usize>;
 
+    /// Write from several buffers at once.
+    ///
+    /// The default implementation writes from the first non-empty buffer only.
+    fn try_write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        let buf = bufs
+            .iter()
+            .find(|buf| !buf.is_empty())
+            .map_or(&[][..], |buf| &**buf);
+        self.try_write(buf)
+    }
+
     fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
 
     fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
@@ -42,6 +57,13 @@ pub trait Socket: Send + Sync + Unpin + 'static {
         Write { socket: self, buf }
     }
 
+    fn write_vectored<'a>(&'a mut self, bufs: &'a [IoSlice<'a>]) -> WriteVectored<'a, Self>
+    where
+        Self: Sized,
+    {
+        WriteVectored { socket: self, bufs }
+    }
+
     fn flush(&mut self) -> Flush<'_, Self>
     where
         Self: Sized,
@@ -112,6 +134,33 @@ where
     }
 }
 
+pub struct WriteVectored<'a, S: ?Sized> {
+    socket: &'a mut S,
+    bufs: &'a [IoSlice<'a>],
+}
+
+impl<S: ?Sized> Future for WriteVectored<'_, S>
+where
+    S: Socket,
+{
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        while this.bufs.iter().any(|buf| !buf.is_empty()) {
+            match this.socket.try_write_vectored(this.bufs) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    ready!(this.socket.poll_write_ready(cx))?;
+                }
+                ready => return Poll::Ready(ready),
+            }
+        }
+
+        Poll::Ready(Ok(0))
+    }
+}
+
 pub struct Flush<'a, S: ?Sized> {
     socket: &'a mut S,
 }
@@ -164,6 +213,10 @@ impl<S: Socket + ?Sized> Socket for Box<S> {
         (**self).try_write(buf)
     }
 
+    fn try_write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (**self).try_write_vectored(bufs)
+    }
+
     fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         (**self).poll_read_ready(cx)
     }
@@ -186,6 +239,32 @@ pub async fn connect_tcp<Ws: WithSocket>(
     port: u16,
     with_socket: Ws,
 ) -> crate::Result<Ws::Output> {
+    connect_tcp_with_options(host, port, &TcpOptions::default(), with_socket).await
+}
+
+/// Open a TCP socket to `host` and `port`, with the socket options of `options`.
+pub async fn connect_tcp_with_options<Ws: WithSocket>(
+    host: &str,
+    port: u16,
+    options: &TcpOptions,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    if options.resolver.is_some() || options.happy_eyeballs_delay.is_some() {
+        return connect_tcp_resolved(host, port, options, with_socket).await;
+    }
+
+    if !options.is_default() {
+        return connect_tcp_tuned(host, port, options, with_socket).await;
+    }
+
+    if let Some(runtime) = crate::rt::runtime::custom() {
+        let socket = runtime.connect_tcp(host, port).await?;
+
+        return Ok(with_socket
+            .with_socket(crate::rt::runtime::CustomSocket(socket))
+            .await);
+    }
+
     #[cfg(feature = "_rt-tokio")]
     if crate::rt::rt_tokio::available() {
         return Ok(with_socket
@@ -202,13 +281,119 @@ pub async fn connect_tcp<Ws: WithSocket>(
     }
 }
 
+/// Connect with a blocking call, to set socket options before connecting.
+async fn connect_tcp_tuned<Ws: WithSocket>(
+    host: &str,
+    port: u16,
+    options: &TcpOptions,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    // IPv6 addresses in URLs will be wrapped in brackets and the `url` crate doesn't trim those.
+    let host = host.trim_matches(&['[', ']'][..]).to_owned();
+    let options = options.clone();
+
+    let stream = crate::rt::spawn_blocking(move || options.connect(&host, port)).await?;
+
+    with_std_stream(stream, with_socket).await
+}
+
+/// Resolve `host` and connect to its addresses, with the resolver and happy eyeballs
+/// of `options`.
+async fn connect_tcp_resolved<Ws: WithSocket>(
+    host: &str,
+    port: u16,
+    options: &TcpOptions,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    let mut addrs = resolve::resolve(host, port, options.resolver.as_ref()).await?;
+    let delay = options.happy_eyeballs_delay;
+
+    // an address of the other family cannot be bound
+    if let Some(bind) = options.bind_address {
+        addrs.retain(|addr| addr.is_ipv4() == bind.is_ipv4());
+    }
+
+    if options.sets_socket_options() {
+        let stream = resolve::connect_any(addrs, delay, |addr| {
+            let options = options.clone();
+            crate::rt::spawn_blocking(move || options.connect_addr(addr))
+        })
+        .await?;
+
+        return with_std_stream(stream, with_socket).await;
+    }
+
+    if let Some(runtime) = crate::rt::runtime::custom() {
+        let socket = resolve::connect_any(addrs, delay, |addr| {
+            runtime.connect_tcp(&addr.ip().to_string(), addr.port())
+        })
+        .await?;
+
+        return Ok(with_socket
+            .with_socket(crate::rt::runtime::CustomSocket(socket))
+            .await);
+    }
+
+    #[cfg(feature = "_rt-tokio")]
+    if crate::rt::rt_tokio::available() {
+        let stream = resolve::connect_any(addrs, delay, tokio::net::TcpStream::connect).await?;
+
+        return Ok(with_socket.with_socket(stream).await);
+    }
+
+    cfg_if! {
+        if #[cfg(feature = "_rt-async-io")] {
+            use async_io::Async;
+            use std::net::TcpStream;
+
+            let stream =
+                resolve::connect_any(addrs, delay, Async::<TcpStream>::connect).await?;
+
+            Ok(with_socket.with_socket(stream).await)
+        } else {
+            crate::rt::missing_rt((addrs, delay, with_socket))
+        }
+    }
+}
+
+/// Wrap a socket connected with a blocking call for the runtime.
+async fn with_std_stream<Ws: WithSocket>(
+    stream: std::net::TcpStream,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    if let Some(runtime) = crate::rt::runtime::custom() {
+        let socket = runtime.wrap_std_tcp(stream)?;
+
+        return Ok(with_socket
+            .with_socket(crate::rt::runtime::CustomSocket(socket))
+            .await);
+    }
+
+    stream.set_nonblocking(true)?;
+
+    #[cfg(feature = "_rt-tokio")]
+    if crate::rt::rt_tokio::available() {
+        return Ok(with_socket
+            .with_socket(tokio::net::TcpStream::from_std(stream)?)
+            .await);
+    }
+
+    cfg_if! {
+        if #[cfg(feature = "_rt-async-io")] {
+            Ok(with_socket.with_socket(async_io::Async::new(stream)?).await)
+        } else {
+            crate::rt::missing_rt((stream, with_socket))
+        }
+    }
+}
+
 /// Open a TCP socket to `host` and `port`.
 ///
 /// If `host` is a hostname, attempt to connect to each address it resolves to.
 ///
 /// This implements the same behavior as [`tokio::net::TcpStream::connect()`].
 #[cfg(feature = "_rt-async-io")]
-async fn connect_tcp_async_io(host: &str, port: u16) -> crate::Result<impl Socket> {
+pub(crate) async fn connect_tcp_async_io(host: &str, port: u16) -> io::Result<impl Socket> {
     use async_io::Async;
     use std::net::{IpAddr, TcpStream, ToSocketAddrs};
 
@@ -216,7 +401,7 @@ async fn connect_tcp_async_io(host: &str, port: u16) -> crate::Result<impl Socke
     let host = host.trim_matches(&['[', ']'][..]);
 
     if let Ok(addr) = host.parse::<IpAddr>() {
-        return Ok(Async::<TcpStream>::connect((addr, port)).await?);
+        return Async::<TcpStream>::connect((addr, port)).await;
     }
 
     let host = host.to_string();
@@ -239,14 +424,12 @@ async fn connect_tcp_async_io(host: &str, port: u16) -> crate::Result<impl Socke
 
     // If we reach this point, it means we failed to connect to any of the addresses.
     // Return the last error we encountered, or a custom error if the hostname didn't resolve to any address.
-    Err(last_err
-        .unwrap_or_else(|| {
-            io::Error::new(
-                io::ErrorKind::AddrNotAvailable,
-                "Hostname did not resolve to any addresses",
-            )
-        })
-        .into())
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            "Hostname did not resolve to any addresses",
+        )
+    }))
 }
 
 /// Connect a Unix Domain Socket at the given path.
@@ -256,6 +439,14 @@ pub async fn connect_uds<P: AsRef<Path>, Ws: WithSocket>(
     path: P,
     with_socket: Ws,
 ) -> crate::Result<Ws::Output> {
+    if let Some(runtime) = crate::rt::runtime::custom() {
+        let socket = runtime.connect_uds(path.as_ref()).await?;
+
+        return Ok(with_socket
+            .with_socket(crate::rt::runtime::CustomSocket(socket))
+            .await);
+    }
+
     #[cfg(unix)]
     {
         #[cfg(feature = "_rt-tokio")]
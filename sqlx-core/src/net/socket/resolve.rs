@@ -0,0 +1,216 @@
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use futures_util::stream::{FuturesUnordered, StreamExt};
+
+/// Resolves host names to the addresses to connect to, in place of the system resolver.
+///
+/// Implemented for closures taking the host name and port:
+///
+/// ```rust
+/// # use sqlx_core::net::SharedResolver;
+/// # use std::net::SocketAddr;
+/// let resolver = SharedResolver::new(|host: &str, port: u16| {
+///     let host = host.to_owned();
+///
+///     async move {
+///         // look `host` up in service discovery, a custom DNS server, ...
+///         # drop(host);
+///         std::io::Result::Ok(vec![SocketAddr::from(([127, 0, 0, 1], port))])
+///     }
+/// });
+/// ```
+pub trait Resolver: Send + Sync + 'static {
+    /// Resolve `host` to addresses with `port`, in the order they should be tried in.
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> BoxFuture<'a, io::Result<Vec<SocketAddr>>>;
+}
+
+impl<F, Fut> Resolver for F
+where
+    F: Fn(&str, u16) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = io::Result<Vec<SocketAddr>>> + Send + 'static,
+{
+    fn resolve<'a>(
+        &'a self,
+        host: &'a str,
+        port: u16,
+    ) -> BoxFuture<'a, io::Result<Vec<SocketAddr>>> {
+        Box::pin(self(host, port))
+    }
+}
+
+/// A [`Resolver`] shared between connections.
+///
+/// Compares equal only to clones of itself.
+#[derive(Clone)]
+pub struct SharedResolver(Arc<dyn Resolver>);
+
+impl SharedResolver {
+    pub fn new(resolver: impl Resolver) -> Self {
+        SharedResolver(Arc::new(resolver))
+    }
+}
+
+impl Debug for SharedResolver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SharedResolver")
+            .field(&"<resolver>")
+            .finish()
+    }
+}
+
+impl PartialEq for SharedResolver {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedResolver {}
+
+/// Resolve `host` with `resolver`, or with the system resolver if `None`.
+///
+/// IP addresses are not passed to the resolver.
+pub(crate) async fn resolve(
+    host: &str,
+    port: u16,
+    resolver: Option<&SharedResolver>,
+) -> io::Result<Vec<SocketAddr>> {
+    // IPv6 addresses in URLs will be wrapped in brackets and the `url` crate doesn't trim those.
+    let host = host.trim_matches(&['[', ']'][..]);
+
+    if let Ok(addr) = host.parse::<IpAddr>() {
+        return Ok(vec![SocketAddr::new(addr, port)]);
+    }
+
+    if let Some(resolver) = resolver {
+        return resolver.0.resolve(host, port).await;
+    }
+
+    let host = host.to_owned();
+
+    let addrs = crate::rt::spawn_blocking(move || (host.as_str(), port).to_socket_addrs()).await?;
+
+    Ok(addrs.collect())
+}
+
+/// Connect to one of `addrs` with `connect`, returning the first connection to succeed.
+///
+/// With a `delay`, this is "happy eyeballs" ([RFC 8305]): the addresses are tried alternating
+/// between IPv6 and IPv4, and an attempt which has not completed within `delay` does not hold
+/// up the next one, which is started alongside it. Without, the addresses are tried one after
+/// the other in the order given.
+///
+/// [RFC 8305]: https://datatracker.ietf.org/doc/html/rfc8305
+pub(crate) async fn connect_any<T, F, Fut>(
+    addrs: Vec<SocketAddr>,
+    delay: Option<Duration>,
+    mut connect: F,
+) -> io::Result<T>
+where
+    F: FnMut(SocketAddr) -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    let addrs = match delay {
+        Some(_) => interleave_families(addrs),
+        None => addrs,
+    };
+
+    let mut pending = addrs.into_iter();
+    let mut attempts = FuturesUnordered::new();
+    let mut last_err = None;
+
+    loop {
+        if attempts.is_empty() {
+            match pending.next() {
+                Some(addr) => attempts.push(connect(addr)),
+                None => break,
+            }
+        }
+
+        let next = match delay {
+            Some(delay) if !pending.as_slice().is_empty() => {
+                crate::rt::timeout(delay, attempts.next()).await
+            }
+            _ => Ok(attempts.next().await),
+        };
+
+        match next {
+            Ok(Some(Ok(stream))) => return Ok(stream),
+            Ok(Some(Err(e))) => {
+                last_err = Some(e);
+
+                // don't wait out the delay once an attempt fails
+                if let Some(addr) = pending.next() {
+                    attempts.push(connect(addr));
+                }
+            }
+            Ok(None) => {}
+            // the delay is up
+            Err(_) => {
+                if let Some(addr) = pending.next() {
+                    attempts.push(connect(addr));
+                }
+            }
+        }
+    }
+
+    // the host name may have resolved to no addresses at all
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::AddrNotAvailable,
+            "Hostname did not resolve to any addresses",
+        )
+    }))
+}
+
+/// Reorder `addrs` to alternate between address families, starting with the family of the
+/// first address and otherwise keeping their order.
+fn interleave_families(addrs: Vec<SocketAddr>) -> Vec<SocketAddr> {
+    let first_is_ipv6 = addrs.first().is_some_and(SocketAddr::is_ipv6);
+
+    let (first, second): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_is_ipv6);
+
+    let mut interleaved = Vec::with_capacity(first.len() + second.len());
+    let mut second = second.into_iter();
+
+    for addr in first {
+        interleaved.push(addr);
+        interleaved.extend(second.next());
+    }
+
+    interleaved.extend(second);
+    interleaved
+}
+
+#[test]
+fn it_interleaves_address_families() {
+    let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+
+    let addrs = vec![
+        addr("[::1]:5432"),
+        addr("[::2]:5432"),
+        addr("[::3]:5432"),
+        addr("10.0.0.1:5432"),
+    ];
+
+    assert_eq!(
+        interleave_families(addrs),
+        [
+            addr("[::1]:5432"),
+            addr("10.0.0.1:5432"),
+            addr("[::2]:5432"),
+            addr("[::3]:5432"),
+        ]
+    );
+}
@@ -0,0 +1,272 @@
+use std::io;
+use std::net::{IpAddr, SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+// sockets are those of the runtime on WebAssembly, whose options cannot be set
+#[cfg(not(target_family = "wasm"))]
+use socket2::{Domain, Protocol, SockAddr, Socket, TcpKeepalive, Type};
+
+use super::resolve::SharedResolver;
+
+/// Options for TCP sockets, applied before connecting.
+///
+/// Options left unset keep the defaults of the operating system. Those usually detect a dead
+/// connection only after TCP gives up retransmitting, which on Linux takes about 15 minutes;
+/// keepalives and [`user_timeout`][Self::user_timeout] shorten that to seconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TcpOptions {
+    /// Disable Nagle's algorithm (`TCP_NODELAY`), sending small writes at once.
+    pub nodelay: Option<bool>,
+    /// Enable or disable keepalives (`SO_KEEPALIVE`).
+    ///
+    /// Setting any of the other keepalive options enables them unless this is `Some(false)`.
+    pub keepalive: Option<bool>,
+    /// The idle time before the first keepalive is sent.
+    pub keepalive_idle: Option<Duration>,
+    /// The time between keepalives without response.
+    pub keepalive_interval: Option<Duration>,
+    /// The number of keepalives without response before the connection is dropped.
+    pub keepalive_retries: Option<u32>,
+    /// The longest time sent data may remain unacknowledged before the connection is dropped
+    /// (`TCP_USER_TIMEOUT`). Linux only.
+    pub user_timeout: Option<Duration>,
+    /// The local address to connect from.
+    pub bind_address: Option<IpAddr>,
+    /// The network interface to connect through (`SO_BINDTODEVICE`). Linux only.
+    pub bind_interface: Option<String>,
+    /// Resolves host names in place of the system resolver.
+    pub resolver: Option<SharedResolver>,
+    /// Connect "happy eyeballs" style: try addresses alternating between IPv6 and IPv4, and
+    /// start the next attempt alongside one which has not completed within this delay.
+    pub happy_eyeballs_delay: Option<Duration>,
+}
+
+impl TcpOptions {
+    /// Whether every option is left to the defaults of the operating system.
+    pub fn is_default(&self) -> bool {
+        *self == TcpOptions::default()
+    }
+
+    /// Whether any option applies to the socket itself, rather than to how it is connected.
+    pub(crate) fn sets_socket_options(&self) -> bool {
+        *self
+            != TcpOptions {
+                resolver: self.resolver.clone(),
+                happy_eyeballs_delay: self.happy_eyeballs_delay,
+                ..TcpOptions::default()
+            }
+    }
+
+    /// Connect to `host` and `port`, trying each address `host` resolves to in turn.
+    ///
+    /// This blocks; run it with [`spawn_blocking()`][crate::rt::spawn_blocking].
+    pub(crate) fn connect(&self, host: &str, port: u16) -> io::Result<TcpStream> {
+        let mut last_err = None;
+
+        for addr in (host, port).to_socket_addrs()? {
+            // an address of the other family cannot be bound
+            if let Some(bind) = self.bind_address {
+                if bind.is_ipv4() != addr.is_ipv4() {
+                    last_err.get_or_insert_with(|| {
+                        io::Error::new(
+                            io::ErrorKind::AddrNotAvailable,
+                            format!("{host} has no address of the family of {bind}"),
+                        )
+                    });
+                    continue;
+                }
+            }
+
+            match self.connect_addr(addr) {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::AddrNotAvailable,
+                "Hostname did not resolve to any addresses",
+            )
+        }))
+    }
+
+    /// Connect to `addr`. This blocks, as [`connect()`][Self::connect] does.
+    #[cfg(not(target_family = "wasm"))]
+    pub(crate) fn connect_addr(&self, addr: SocketAddr) -> io::Result<TcpStream> {
+        let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+
+        if let Some(address) = self.bind_address {
+            socket.bind(&SockAddr::from(SocketAddr::new(address, 0)))?;
+        }
+
+        if let Some(interface) = &self.bind_interface {
+            bind_interface(&socket, interface)?;
+        }
+
+        if let Some(nodelay) = self.nodelay {
+            socket.set_nodelay(nodelay)?;
+        }
+
+        self.set_keepalive(&socket)?;
+
+        if let Some(timeout) = self.user_timeout {
+            set_user_timeout(&socket, timeout)?;
+        }
+
+        socket.connect(&SockAddr::from(addr))?;
+
+        Ok(socket.into())
+    }
+
+    #[cfg(target_family = "wasm")]
+    pub(crate) fn connect_addr(&self, _addr: SocketAddr) -> io::Result<TcpStream> {
+        Err(unsupported("setting TCP socket options"))
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn set_keepalive(&self, socket: &Socket) -> io::Result<()> {
+        let tuned = self.keepalive_idle.is_some()
+            || self.keepalive_interval.is_some()
+            || self.keepalive_retries.is_some();
+
+        match self.keepalive {
+            Some(false) => return socket.set_keepalive(false),
+            Some(true) if !tuned => return socket.set_keepalive(true),
+            None if !tuned => return Ok(()),
+            _ => {}
+        }
+
+        let mut keepalive = TcpKeepalive::new();
+
+        if let Some(idle) = self.keepalive_idle {
+            keepalive = keepalive.with_time(idle);
+        }
+
+        if let Some(interval) = self.keepalive_interval {
+            keepalive = with_interval(keepalive, interval)?;
+        }
+
+        if let Some(retries) = self.keepalive_retries {
+            keepalive = with_retries(keepalive, retries)?;
+        }
+
+        // also enables keepalives
+        socket.set_tcp_keepalive(&keepalive)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn bind_interface(socket: &Socket, interface: &str) -> io::Result<()> {
+    socket.bind_device(Some(interface.as_bytes()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_family = "wasm")))]
+fn bind_interface(_socket: &Socket, _interface: &str) -> io::Result<()> {
+    Err(unsupported("binding to a network interface"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_user_timeout(socket: &Socket, timeout: Duration) -> io::Result<()> {
+    socket.set_tcp_user_timeout(Some(timeout))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_family = "wasm")))]
+fn set_user_timeout(_socket: &Socket, _timeout: Duration) -> io::Result<()> {
+    Err(unsupported("`TCP_USER_TIMEOUT`"))
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "windows",
+))]
+fn with_interval(keepalive: TcpKeepalive, interval: Duration) -> io::Result<TcpKeepalive> {
+    Ok(keepalive.with_interval(interval))
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "windows",
+    target_family = "wasm",
+)))]
+fn with_interval(_keepalive: TcpKeepalive, _interval: Duration) -> io::Result<TcpKeepalive> {
+    Err(unsupported("setting the keepalive interval"))
+}
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+))]
+fn with_retries(keepalive: TcpKeepalive, retries: u32) -> io::Result<TcpKeepalive> {
+    Ok(keepalive.with_retries(retries))
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_family = "wasm",
+)))]
+fn with_retries(_keepalive: TcpKeepalive, _retries: u32) -> io::Result<TcpKeepalive> {
+    Err(unsupported("setting the keepalive retries"))
+}
+
+#[allow(dead_code)] // unused on Linux
+fn unsupported(what: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{what} is not supported on this platform"),
+    )
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+fn it_applies_tcp_options() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let options = TcpOptions {
+        nodelay: Some(true),
+        keepalive_idle: Some(Duration::from_secs(30)),
+        keepalive_interval: Some(Duration::from_secs(5)),
+        keepalive_retries: Some(3),
+        user_timeout: Some(Duration::from_secs(20)),
+        bind_address: Some("127.0.0.1".parse().unwrap()),
+        ..TcpOptions::default()
+    };
+
+    let stream = options.connect("localhost", port).unwrap();
+    let socket = socket2::SockRef::from(&stream);
+
+    assert!(socket.nodelay().unwrap());
+    assert!(socket.keepalive().unwrap());
+    assert_eq!(socket.keepalive_time().unwrap(), Duration::from_secs(30));
+
+    #[cfg(target_os = "linux")]
+    {
+        assert_eq!(socket.keepalive_interval().unwrap(), Duration::from_secs(5));
+        assert_eq!(socket.keepalive_retries().unwrap(), 3);
+        assert_eq!(
+            socket.tcp_user_timeout().unwrap(),
+            Some(Duration::from_secs(20))
+        );
+    }
+}
@@ -0,0 +1,425 @@
+use std::ffi::OsString;
+use std::io::{self, Read};
+use std::os::fd::OwnedFd;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::task::{Context, Poll};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use cfg_if::cfg_if;
+
+use crate::io::ReadBuf;
+use crate::net::{Socket, WithSocket};
+
+/// A jump host to reach the database through, with an SSH tunnel opened for each connection.
+///
+/// Each connection runs the `ssh` client of the system as `ssh -W host:port`, which asks the
+/// jump host to open a TCP connection to the database and relays it over the standard input
+/// and output of the client. Authentication therefore follows the usual rules of `ssh`: keys
+/// of the agent, [`identity_file()`][Self::identity_file], and `~/.ssh/config`; it never
+/// prompts, so a key with a passphrase must be in the agent.
+///
+/// Host keys are checked against `~/.ssh/known_hosts`, or
+/// [`known_hosts_file()`][Self::known_hosts_file].
+///
+/// Besides the `ssh` process, each connection has a thread reading what it prints to its
+/// standard error, to report why the tunnel closed.
+///
+/// ```rust,no_run
+/// # use std::time::Duration;
+/// use sqlx::postgres::SshCommandTunnel;
+/// let tunnel = SshCommandTunnel::new("bastion.example.com")
+///     .user("deploy")
+///     .identity_file("/etc/app/id_ed25519")
+///     .keepalive(Duration::from_secs(30));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SshCommandTunnel {
+    host: String,
+    port: Option<u16>,
+    user: Option<String>,
+    identity_file: Option<PathBuf>,
+    known_hosts_file: Option<PathBuf>,
+    keepalive: Option<Duration>,
+    options: Vec<(String, String)>,
+    program: PathBuf,
+}
+
+impl SshCommandTunnel {
+    /// Tunnel through the SSH server at `host`, a host name, an address, or an alias of
+    /// `~/.ssh/config`.
+    pub fn new(host: impl Into<String>) -> Self {
+        SshCommandTunnel {
+            host: host.into(),
+            port: None,
+            user: None,
+            identity_file: None,
+            known_hosts_file: None,
+            keepalive: None,
+            options: Vec::new(),
+            program: "ssh".into(),
+        }
+    }
+
+    /// Set the port of the SSH server, instead of 22 or the port of `~/.ssh/config`.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the user to log in to the SSH server as, instead of the current user.
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Authenticate with the private key in `path`, and only with it.
+    pub fn identity_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.identity_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Check the host key of the SSH server against the `known_hosts` file at `path`.
+    pub fn known_hosts_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.known_hosts_file = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Send a keepalive to the SSH server after `interval` without traffic, and close the
+    /// tunnel after 3 keepalives without response.
+    ///
+    /// Keeps idle connections from being dropped by firewalls, and detects a dead server.
+    pub fn keepalive(mut self, interval: Duration) -> Self {
+        self.keepalive = Some(interval);
+        self
+    }
+
+    /// Pass an option to `ssh`, as `-o key=value`; see `ssh_config(5)`.
+    pub fn option(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.push((key.into(), value.into()));
+        self
+    }
+
+    /// Run the SSH client at `path`, instead of the `ssh` found in `PATH`.
+    pub fn program(mut self, path: impl AsRef<Path>) -> Self {
+        self.program = path.as_ref().to_path_buf();
+        self
+    }
+
+    fn command(&self, host: &str, port: u16) -> Command {
+        let mut command = Command::new(&self.program);
+
+        // IPv6 addresses are bracketed, as in URLs
+        let target = if host.contains(':') && !host.starts_with('[') {
+            format!("[{host}]:{port}")
+        } else {
+            format!("{host}:{port}")
+        };
+
+        command.arg("-W").arg(target);
+
+        // never prompt, and fail if the jump host cannot open the connection
+        for (key, value) in [
+            ("BatchMode", "yes"),
+            ("ExitOnForwardFailure", "yes"),
+            ("LogLevel", "ERROR"),
+        ] {
+            command.arg("-o").arg(format!("{key}={value}"));
+        }
+
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+
+        if let Some(user) = &self.user {
+            command.arg("-l").arg(user);
+        }
+
+        if let Some(identity_file) = &self.identity_file {
+            command.arg("-i").arg(identity_file);
+            command.arg("-o").arg("IdentitiesOnly=yes");
+        }
+
+        if let Some(known_hosts_file) = &self.known_hosts_file {
+            let mut option = OsString::from("UserKnownHostsFile=");
+            option.push(known_hosts_file);
+            command.arg("-o").arg(option);
+        }
+
+        if let Some(keepalive) = self.keepalive {
+            let secs = std::cmp::max(keepalive.as_secs(), 1);
+            command.arg("-o").arg(format!("ServerAliveInterval={secs}"));
+            command.arg("-o").arg("ServerAliveCountMax=3");
+        }
+
+        for (key, value) in &self.options {
+            command.arg("-o").arg(format!("{key}={value}"));
+        }
+
+        command.arg("--").arg(&self.host);
+        command
+    }
+
+    /// Start `ssh`, returning the end of the tunnel, its standard error, and the process.
+    fn spawn(&self, host: &str, port: u16) -> io::Result<(UnixStream, Stderr, Child)> {
+        let (stream, ssh_stdio) = UnixStream::pair()?;
+        let (stderr, ssh_stderr) = UnixStream::pair()?;
+
+        let child = self
+            .command(host, port)
+            .stdin(Stdio::from(OwnedFd::from(ssh_stdio.try_clone()?)))
+            .stdout(Stdio::from(OwnedFd::from(ssh_stdio)))
+            .stderr(Stdio::from(OwnedFd::from(ssh_stderr)))
+            .spawn()
+            .map_err(|e| {
+                io::Error::new(e.kind(), format!("failed to run {:?}: {e}", self.program))
+            })?;
+
+        stream.set_nonblocking(true)?;
+
+        Ok((stream, Stderr::drain(stderr)?, child))
+    }
+}
+
+/// Connect to `host` and `port` as seen from the jump host of `tunnel`.
+pub async fn connect_ssh<Ws: WithSocket>(
+    tunnel: &SshCommandTunnel,
+    host: &str,
+    port: u16,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    if crate::rt::runtime::custom().is_some() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "SSH tunnels are not supported with a runtime set by `set_runtime()`",
+        )
+        .into());
+    }
+
+    let (stream, stderr, child) = tunnel.spawn(host, port)?;
+
+    #[cfg(feature = "_rt-tokio")]
+    if crate::rt::rt_tokio::available() {
+        let socket = tokio::net::UnixStream::from_std(stream)?;
+
+        return Ok(with_socket
+            .with_socket(SshSocket {
+                socket,
+                stderr,
+                child,
+            })
+            .await);
+    }
+
+    cfg_if! {
+        if #[cfg(feature = "_rt-async-io")] {
+            let socket = async_io::Async::new(stream)?;
+
+            Ok(with_socket
+                .with_socket(SshSocket {
+                    socket,
+                    stderr,
+                    child,
+                })
+                .await)
+        } else {
+            crate::rt::missing_rt((stream, stderr, child, with_socket))
+        }
+    }
+}
+
+/// The end of a tunnel, which stops `ssh` when dropped.
+// unused without a runtime feature
+#[cfg_attr(
+    not(any(feature = "_rt-tokio", feature = "_rt-async-io")),
+    allow(dead_code)
+)]
+struct SshSocket<S> {
+    socket: S,
+    stderr: Stderr,
+    child: Child,
+}
+
+/// The standard error of `ssh`, read in the background so that `ssh` never blocks on a full pipe.
+struct Stderr {
+    drain: Option<JoinHandle<Vec<u8>>>,
+}
+
+impl Stderr {
+    /// The most bytes kept, from the end of the output.
+    const MAX_LEN: usize = 4096;
+
+    fn drain(mut stderr: UnixStream) -> io::Result<Self> {
+        let drain = thread::Builder::new()
+            .name("sqlx-ssh-stderr".into())
+            .spawn(move || {
+                let mut output = Vec::new();
+                let mut buf = [0; 1024];
+
+                // until `ssh` exits
+                while let Ok(n @ 1..) = stderr.read(&mut buf) {
+                    output.extend_from_slice(&buf[..n]);
+                    output.drain(..output.len().saturating_sub(Self::MAX_LEN));
+                }
+
+                output
+            })?;
+
+        Ok(Stderr { drain: Some(drain) })
+    }
+
+    /// Wait for `ssh` to exit, and take the end of what it printed.
+    fn take(&mut self) -> String {
+        let Some(drain) = self.drain.take() else {
+            return String::new();
+        };
+
+        let output = drain.join().unwrap_or_default();
+
+        String::from_utf8_lossy(&output).trim().to_owned()
+    }
+}
+
+#[cfg_attr(
+    not(any(feature = "_rt-tokio", feature = "_rt-async-io")),
+    allow(dead_code)
+)]
+impl<S> SshSocket<S> {
+    /// If `ssh` closed the tunnel, turn the end of the stream into the error it printed.
+    fn check<T>(
+        &mut self,
+        result: io::Result<T>,
+        closed: impl FnOnce(&T) -> bool,
+    ) -> io::Result<T> {
+        let failed = match &result {
+            Ok(value) => closed(value),
+            Err(e) => e.kind() != io::ErrorKind::WouldBlock,
+        };
+
+        if !failed {
+            return result;
+        }
+
+        // the tunnel is closed, so make sure `ssh` exits before waiting on its output
+        let _ = self.child.kill();
+        let message = self.stderr.take();
+
+        if message.is_empty() {
+            return result;
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::ConnectionAborted,
+            format!("SSH tunnel closed: {message}"),
+        ))
+    }
+}
+
+impl<S: Socket> Socket for SshSocket<S> {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        let wanted = buf.chunk_mut().len() > 0;
+        let result = self.socket.try_read(buf);
+
+        self.check(result, |n| wanted && *n == 0)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let result = self.socket.try_write(buf);
+
+        self.check(result, |_| false)
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.socket.poll_read_ready(cx)
+    }
+
+    fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.socket.poll_write_ready(cx)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.socket.poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.socket.poll_shutdown(cx)
+    }
+}
+
+impl<S> Drop for SshSocket<S> {
+    fn drop(&mut self) {
+        // reaping a killed process does not block for long
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn it_builds_the_ssh_command() {
+    let tunnel = SshCommandTunnel::new("bastion")
+        .port(2222)
+        .user("deploy")
+        .identity_file("/keys/id_ed25519")
+        .keepalive(Duration::from_millis(500))
+        .option("Compression", "yes");
+
+    let command = tunnel.command("::1", 5432);
+    let args: Vec<_> = command
+        .get_args()
+        .map(|arg| arg.to_str().unwrap())
+        .collect();
+
+    assert_eq!(
+        args,
+        [
+            "-W",
+            "[::1]:5432",
+            "-o",
+            "BatchMode=yes",
+            "-o",
+            "ExitOnForwardFailure=yes",
+            "-o",
+            "LogLevel=ERROR",
+            "-p",
+            "2222",
+            "-l",
+            "deploy",
+            "-i",
+            "/keys/id_ed25519",
+            "-o",
+            "IdentitiesOnly=yes",
+            "-o",
+            "ServerAliveInterval=1",
+            "-o",
+            "ServerAliveCountMax=3",
+            "-o",
+            "Compression=yes",
+            "--",
+            "bastion",
+        ]
+    );
+}
+
+#[test]
+fn it_drains_the_standard_error_of_ssh() {
+    use std::io::Write;
+
+    let (mut ssh_stderr, stderr) = UnixStream::pair().unwrap();
+    let mut stderr = Stderr::drain(stderr).unwrap();
+
+    // more than fits in the buffer of the socket, which would block without the drain
+    ssh_stderr.write_all(&vec![b'.'; 1 << 20]).unwrap();
+    ssh_stderr
+        .write_all(b"\nchannel 0: open failed: connect failed: Connection refused\n")
+        .unwrap();
+    drop(ssh_stderr);
+
+    let message = stderr.take();
+    // without the trailing newline
+    assert_eq!(message.len(), Stderr::MAX_LEN - 1);
+    assert!(message.ends_with(".\nchannel 0: open failed: connect failed: Connection refused"));
+
+    assert_eq!(stderr.take(), "");
+}
@@ -2,7 +2,7 @@ use std::io::{self, Read, Write};
 
 use crate::io::ReadBuf;
 use crate::net::tls::util::StdSocket;
-use crate::net::tls::TlsConfig;
+use crate::net::tls::{error_if_encrypted, TlsConfig};
 use crate::net::Socket;
 use crate::Error;
 
@@ -54,9 +54,17 @@ pub async fn handshake<S: Socket>(
     }
 
     // authentication using user's key-file and its associated certificate
-    if let (Some(cert_path), Some(key_path)) = (config.client_cert_path, config.client_key_path) {
-        let cert_path = cert_path.data().await?;
-        let key_path = key_path.data().await?;
+    if let Some(pkcs12) = config.client_pkcs12 {
+        let der = pkcs12.data().await?;
+        let password = config.client_key_password.unwrap_or_default();
+        let identity = Identity::from_pkcs12(&der, password).map_err(Error::tls)?;
+        builder.identity(identity);
+    } else if let (Some(cert_path), Some(key_path)) =
+        (config.client_cert_path, config.client_key_path)
+    {
+        let cert_path = cert_path.pem("CERTIFICATE").await?;
+        let key_path = key_path.pem("PRIVATE KEY").await?;
+        error_if_encrypted(&key_path)?;
         let identity = Identity::from_pkcs8(&cert_path, &key_path).map_err(Error::tls)?;
         builder.identity(identity);
     }
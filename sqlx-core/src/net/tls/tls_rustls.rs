@@ -1,12 +1,13 @@
+use std::collections::HashMap;
 use std::future;
 use std::io::{self, Read, Write};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::{ready, Context, Poll};
 
 use rustls::{
     client::{
         danger::{ServerCertVerified, ServerCertVerifier},
-        WebPkiServerVerifier,
+        ClientSessionMemoryCache, Resumption, WebPkiServerVerifier,
     },
     crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider},
     pki_types::{
@@ -19,7 +20,7 @@ use rustls::{
 use crate::error::Error;
 use crate::io::ReadBuf;
 use crate::net::tls::util::StdSocket;
-use crate::net::tls::TlsConfig;
+use crate::net::tls::{error_if_encrypted, CertificateInput, TlsConfig};
 use crate::net::Socket;
 
 pub struct RustlsSocket<S: Socket> {
@@ -108,11 +109,26 @@ where
         .with_safe_default_protocol_versions()
         .unwrap();
 
+    if tls_config.client_pkcs12.is_some() {
+        return Err(Error::Configuration(
+            "PKCS#12 bundles are not supported by rustls; \
+             pass the certificate and key in PEM or DER instead"
+                .into(),
+        ));
+    }
+
     // authentication using user's key and its associated certificate
     let user_auth = match (tls_config.client_cert_path, tls_config.client_key_path) {
         (Some(cert_path), Some(key_path)) => {
-            let cert_chain = certs_from_pem(cert_path.data().await?)?;
-            let key_der = private_key_from_pem(key_path.data().await?)?;
+            let cert_chain = match cert_path {
+                CertificateInput::Der(der) => vec![CertificateDer::from(der.clone())],
+                _ => certs_from_pem(cert_path.data().await?)?,
+            };
+            let key_der = match key_path {
+                CertificateInput::Der(der) => PrivateKeyDer::try_from(der.clone())
+                    .map_err(|e| Error::Configuration(e.into()))?,
+                _ => private_key_from_pem(key_path.data().await?)?,
+            };
             Some((cert_chain, key_der))
         }
         (None, None) => None,
@@ -123,7 +139,7 @@ where
         }
     };
 
-    let config = if tls_config.accept_invalid_certs {
+    let mut config = if tls_config.accept_invalid_certs {
         if let Some(user_auth) = user_auth {
             config
                 .dangerous()
@@ -180,11 +196,47 @@ where
         }
     };
 
-    let host = ServerName::try_from(tls_config.hostname.to_owned()).map_err(Error::tls)?;
+    if let Some(cache) = tls_config.session_cache {
+        config.resumption =
+            Resumption::store(cache.stores.get(tls_config.hostname, tls_config.port));
+    }
+
+    handshake_with_config(socket, Arc::new(config), tls_config.hostname).await
+}
+
+/// The session stores of a [`TlsSessionCache`][crate::net::tls::TlsSessionCache], one for each
+/// host name and port.
+#[derive(Clone, Default)]
+pub(super) struct SessionStores(Arc<Mutex<HashMap<(String, u16), Arc<ClientSessionMemoryCache>>>>);
+
+impl SessionStores {
+    // a store keeps sessions with this many servers, but is only used with one
+    const SERVERS_PER_STORE: usize = 1;
+
+    fn get(&self, host: &str, port: u16) -> Arc<ClientSessionMemoryCache> {
+        let mut stores = self.0.lock().expect("BUG: panicked while holding a lock");
+
+        stores
+            .entry((host.to_owned(), port))
+            .or_insert_with(|| Arc::new(ClientSessionMemoryCache::new(Self::SERVERS_PER_STORE)))
+            .clone()
+    }
+}
+
+/// Perform the handshake with a configuration given as is.
+pub async fn handshake_with_config<S>(
+    socket: S,
+    config: Arc<ClientConfig>,
+    hostname: &str,
+) -> Result<RustlsSocket<S>, Error>
+where
+    S: Socket,
+{
+    let host = ServerName::try_from(hostname.to_owned()).map_err(Error::tls)?;
 
     let mut socket = RustlsSocket {
         inner: StdSocket::new(socket),
-        state: ClientConnection::new(Arc::new(config), host).map_err(Error::tls)?,
+        state: ClientConnection::new(config, host).map_err(Error::tls)?,
         close_notify_sent: false,
     };
 
@@ -201,6 +253,8 @@ fn certs_from_pem(pem: Vec<u8>) -> Result<Vec<CertificateDer<'static>>, Error> {
 }
 
 fn private_key_from_pem(pem: Vec<u8>) -> Result<PrivateKeyDer<'static>, Error> {
+    error_if_encrypted(&pem)?;
+
     match PrivateKeyDer::from_pem_slice(&pem) {
         Ok(key) => Ok(key),
         Err(pem::Error::NoItemsFound) => Err(Error::Configuration("no keys found pem file".into())),
@@ -0,0 +1,135 @@
+//! Tracing spans for queries and transactions which follow the OpenTelemetry semantic
+//! conventions for database clients.
+//!
+//! With a subscriber which exports spans to OpenTelemetry, such as the one from the
+//! `tracing-opentelemetry` crate, each query shows up in traces as a `client` span with the
+//! attributes an APM expects: `db.system`, `db.name`, `db.operation`, `db.statement`,
+//! `server.address` and `server.port`. Queries run in a transaction are children of a span
+//! for the transaction.
+//!
+//! Query spans are on the `sqlx::query` target and transaction spans on the
+//! `sqlx::transaction` target, both at the `INFO` level.
+//!
+//! The statement is [sanitized][sanitize_statement] before it is recorded, and the values
+//! bound to it are never recorded.
+
+use tracing::field::Empty;
+use tracing::Span;
+
+use crate::error::Error;
+use crate::logger::parse_query_operation;
+
+pub use crate::logger::{sanitize_statement, MAX_STATEMENT_LEN};
+
+/// The attributes of the database a connection is connected to, recorded on each span.
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionAttributes<'a> {
+    /// The `db.system` attribute, such as `"postgresql"`.
+    pub system: &'static str,
+    /// The name of the database, if known.
+    pub database: Option<&'a str>,
+    /// The hostname, IP address or Unix socket path of the server.
+    pub server_address: Option<&'a str>,
+    /// The port of the server, if connected over TCP.
+    pub server_port: Option<u16>,
+}
+
+/// Create the span of a query, as a child of `parent` or otherwise of the current span.
+///
+/// The span is named after `name`, if the query has one, or else after its operation.
+pub fn query_span(
+    attributes: &ConnectionAttributes<'_>,
+    sql: &str,
+    name: Option<&str>,
+    parent: Option<&Span>,
+) -> Span {
+    let parent = parent.cloned().unwrap_or_else(Span::current);
+    let operation = parse_query_operation(sql);
+
+    let span = tracing::info_span!(
+        target: "sqlx::query",
+        parent: &parent,
+        "sqlx.query",
+        otel.name = Empty,
+        otel.kind = "client",
+        otel.status_code = Empty,
+        otel.status_message = Empty,
+        db.system = attributes.system,
+        db.name = attributes.database,
+        db.operation = operation.as_deref(),
+        db.statement = Empty,
+        db.response.returned_rows = Empty,
+        db.response.status_code = Empty,
+        error.type = Empty,
+        server.address = attributes.server_address,
+        server.port = attributes.server_port,
+    );
+
+    if !span.is_disabled() {
+        span.record(
+            "otel.name",
+            name.or(operation.as_deref()).unwrap_or(attributes.system),
+        );
+        span.record("db.statement", sanitize_statement(sql));
+    }
+
+    span
+}
+
+/// Create the span of a transaction, as a child of the current span.
+///
+/// The span stays open until the transaction is committed or rolled back, as recorded
+/// by [`record_transaction_outcome()`].
+pub fn transaction_span(attributes: &ConnectionAttributes<'_>) -> Span {
+    tracing::info_span!(
+        target: "sqlx::transaction",
+        "sqlx.transaction",
+        otel.name = "transaction",
+        otel.kind = "client",
+        db.system = attributes.system,
+        db.name = attributes.database,
+        db.transaction.outcome = Empty,
+        server.address = attributes.server_address,
+        server.port = attributes.server_port,
+    )
+}
+
+/// Record how a transaction ended, such as `"commit"` or `"rollback"`, on its span.
+pub fn record_transaction_outcome(span: &Span, outcome: &'static str) {
+    span.record("db.transaction.outcome", outcome);
+}
+
+/// Record the number of rows a query returned on its span.
+pub fn record_returned_rows(span: &Span, rows: u64) {
+    span.record("db.response.returned_rows", rows);
+}
+
+/// Record that a query failed with `error` on its span.
+///
+/// For errors returned by the database, the code of the error is recorded as
+/// `db.response.status_code` and `error.type`.
+pub fn record_error(span: &Span, error: &Error) {
+    if span.is_disabled() {
+        return;
+    }
+
+    span.record("otel.status_code", "ERROR");
+    span.record("otel.status_message", tracing::field::display(error));
+
+    match error {
+        Error::Database(e) => {
+            let code = e.code();
+            span.record("db.response.status_code", code.as_deref());
+            span.record("error.type", code.as_deref().unwrap_or("database"));
+        }
+        Error::Timeout => {
+            span.record("error.type", "timeout");
+        }
+        Error::Io(_) => {
+            span.record("error.type", "io");
+        }
+        _ => {
+            span.record("error.type", "_OTHER");
+        }
+    }
+}
@@ -369,7 +369,7 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
         }
     }
 
-    pub async fn ping(&mut self) -> Result<(), Error> {
+    pub async fn ping(&mut self) -> Result<Duration, Error> {
         self.live.raw.ping().await
     }
 
@@ -217,6 +217,50 @@ impl<DB: Database> PoolInner<DB> {
         self.num_idle.fetch_add(1, Ordering::AcqRel);
     }
 
+    /// Like [`Self::release()`], but for a connection which was never handed out, so it keeps
+    /// the time it has been idle for.
+    fn release_idle(&self, floating: Floating<DB, Idle<DB>>) {
+        let Floating { inner: idle, guard } = floating;
+
+        if self.idle_conns.push(idle).is_err() {
+            panic!("BUG: connection queue overflow in release_idle()");
+        }
+
+        guard.release_permit();
+
+        self.num_idle.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Ping each idle connection, returning the round-trip times in the order the connections
+    /// were pinged. A connection whose ping fails is closed.
+    pub(super) async fn ping_all(self: &Arc<Self>) -> Vec<Result<Duration, Error>> {
+        let mut latencies = Vec::new();
+
+        // As in the reaper, connections may be popped from the queue in the meantime,
+        // and released connections go to the back, so each is pinged at most once.
+        for _ in 0..self.num_idle() {
+            let Some(mut conn) = self.try_acquire() else {
+                break;
+            };
+
+            match conn.ping().await {
+                Ok(latency) => {
+                    latencies.push(Ok(latency));
+                    self.release_idle(conn);
+                }
+                Err(error) => {
+                    tracing::info!(%error, "ping on idle connection returned error");
+                    latencies.push(Err(error));
+                    // connection is broken so don't try to close nicely
+                    let _ = conn.close_hard().await;
+                    self.min_connections_maintenance(None).await;
+                }
+            }
+        }
+
+        latencies
+    }
+
     /// Try to atomically increment the pool size for a new connection.
     ///
     /// Returns `Err` if the pool is at max capacity already or is closed.
@@ -294,10 +338,24 @@ impl<DB: Database> PoolInner<DB> {
             }
         )
             .await
-            .map_err(|_| Error::PoolTimedOut)??;
+            .map_err(|_| {
+                #[cfg(feature = "metrics")]
+                crate::metrics::record_pool_acquire_timeout();
+
+                #[cfg(feature = "prometheus")]
+                crate::prometheus::record_pool_acquire_timeout();
+
+                Error::PoolTimedOut
+            })??;
 
         let acquired_after = acquire_started_at.elapsed();
 
+        #[cfg(feature = "metrics")]
+        crate::metrics::record_pool_acquire(acquired_after);
+
+        #[cfg(feature = "prometheus")]
+        crate::prometheus::record_pool_acquire(acquired_after);
+
         let acquire_slow_level = self
             .acquire_slow_level
             .filter(|_| acquired_after > self.options.acquire_slow_threshold);
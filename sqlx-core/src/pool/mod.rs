@@ -43,22 +43,28 @@
 //! [`Pool::acquire`] or
 //! [`Pool::begin`].
 
+use std::cmp;
 use std::fmt;
 use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
 use std::pin::{pin, Pin};
 use std::sync::Arc;
 use std::task::{ready, Context, Poll};
 use std::time::{Duration, Instant};
 
 use event_listener::EventListener;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
 use futures_core::FusedFuture;
-use futures_util::FutureExt;
+use futures_util::{FutureExt, TryStreamExt};
 
-use crate::connection::Connection;
+use crate::connection::{ConnectOptions, Connection};
 use crate::database::Database;
 use crate::error::Error;
+use crate::executor::{Execute, Executor};
 use crate::sql_str::SqlSafeStr;
-use crate::transaction::Transaction;
+use crate::statistics::{StatementStatistics, StatementStats};
+use crate::transaction::{RetryPolicy, Transaction};
 
 pub use self::connection::PoolConnection;
 use self::inner::PoolInner;
@@ -335,6 +341,44 @@ impl<DB: Database> Pool<DB> {
         self.0.try_acquire().map(|conn| conn.into_live().reattach())
     }
 
+    /// Execute `query` on a connection of the pool and return the generated results as a
+    /// stream which owns the connection, so it does not borrow the pool.
+    ///
+    /// The stream can be returned from a function or handed to a streaming response body,
+    /// as in `axum` or `hyper`. The connection is acquired when the stream is first polled, and
+    /// is returned to the pool when the stream is dropped.
+    ///
+    /// As the query is moved into the stream, the values bound to it must be owned.
+    ///
+    /// ```rust,no_run
+    /// use futures_util::stream::{BoxStream, TryStreamExt};
+    /// use sqlx::{PgPool, Row};
+    ///
+    /// fn user_names(pool: &PgPool, team_id: i64) -> BoxStream<'static, sqlx::Result<String>> {
+    ///     let query = sqlx::query("SELECT name FROM users WHERE team_id = $1").bind(team_id);
+    ///
+    ///     Box::pin(pool.fetch_owned(query).and_then(|row| async move { row.try_get("name") }))
+    /// }
+    /// ```
+    pub fn fetch_owned<E>(&self, query: E) -> BoxStream<'static, Result<DB::Row, Error>>
+    where
+        E: 'static + Execute<'static, DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let pool = self.clone();
+
+        Box::pin(try_stream! {
+            let mut conn = pool.acquire().await?;
+            let mut rows = conn.fetch(query);
+
+            while let Some(row) = rows.try_next().await? {
+                r#yield!(row);
+            }
+
+            Ok(())
+        })
+    }
+
     /// Retrieves a connection and immediately begins a new transaction.
     pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
         Transaction::begin(
@@ -385,6 +429,153 @@ impl<DB: Database> Pool<DB> {
         }
     }
 
+    /// Run `callback` in a new transaction, which is committed if it returns `Ok` and rolled
+    /// back if it returns `Err`.
+    ///
+    /// This takes care of ending the transaction on every path out of `callback`, including
+    /// early returns with `?`. `callback` only borrows the transaction, so it cannot be kept
+    /// around past the end of the callback by mistake.
+    ///
+    /// If `callback` panics, the transaction is rolled back before the panic resumes.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// let user_id: i64 = pool
+    ///     .transact(|tx| {
+    ///         Box::pin(async move {
+    ///             let user_id =
+    ///                 sqlx::query_scalar("INSERT INTO users (name) VALUES ($1) RETURNING id")
+    ///                     .bind("ferris")
+    ///                     .fetch_one(&mut **tx)
+    ///                     .await?;
+    ///
+    ///             sqlx::query("INSERT INTO audit_log (user_id, action) VALUES ($1, 'signup')")
+    ///                 .bind(user_id)
+    ///                 .execute(&mut **tx)
+    ///                 .await?;
+    ///
+    ///             Ok::<_, sqlx::Error>(user_id)
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// `E` can be any error type which a [`sqlx::Error`][Error] converts into, for the errors
+    /// of `BEGIN`, `COMMIT` and `ROLLBACK` themselves.
+    pub async fn transact<F, R, E>(&self, callback: F) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut Transaction<'static, DB>) -> BoxFuture<'c, Result<R, E>>,
+        E: From<Error>,
+    {
+        let mut transaction = self.begin().await?;
+        let result = AssertUnwindSafe(callback(&mut transaction))
+            .catch_unwind()
+            .await;
+
+        match result {
+            Ok(Ok(ret)) => {
+                transaction.commit().await?;
+
+                Ok(ret)
+            }
+            Ok(Err(err)) => {
+                transaction.rollback().await?;
+
+                Err(err)
+            }
+            Err(panic) => {
+                if let Err(err) = transaction.rollback().await {
+                    tracing::warn!("failed to roll back transaction after a panic: {err}");
+                }
+
+                panic::resume_unwind(panic)
+            }
+        }
+    }
+
+    /// Run `callback` in a new transaction, and retry it if the transaction fails because of
+    /// a conflict with concurrent transactions.
+    ///
+    /// The transaction is committed if `callback` returns `Ok`, and rolled back if it returns
+    /// `Err`. If the error is a serialization failure or a deadlock, whether it came from
+    /// `callback` or from the commit, the whole transaction is retried on a new connection
+    /// after a backoff, as configured by `policy`. Any other error is returned as is.
+    ///
+    /// `callback` may be called several times, so it should have no side effects outside of
+    /// the transaction.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// use sqlx::{IsolationLevel, RetryPolicy, TransactionOptions};
+    ///
+    /// let policy = RetryPolicy::new()
+    ///     .begin_with(TransactionOptions::new().isolation(IsolationLevel::Serializable));
+    ///
+    /// let balance: i64 = pool
+    ///     .transaction_with_retry(&policy, |tx| {
+    ///         Box::pin(async move {
+    ///             sqlx::query("UPDATE accounts SET balance = balance - 10 WHERE id = 1")
+    ///                 .execute(&mut **tx)
+    ///                 .await?;
+    ///
+    ///             sqlx::query_scalar("SELECT balance FROM accounts WHERE id = 1")
+    ///                 .fetch_one(&mut **tx)
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn transaction_with_retry<F, R>(
+        &self,
+        policy: &RetryPolicy,
+        mut callback: F,
+    ) -> Result<R, Error>
+    where
+        for<'c> F: FnMut(&'c mut Transaction<'static, DB>) -> BoxFuture<'c, Result<R, Error>>,
+    {
+        let mut attempt = 1;
+        let mut backoff = policy.initial_backoff;
+
+        loop {
+            let result = async {
+                let mut transaction = match &policy.begin {
+                    Some(statement) => self.begin_with(statement.clone()).await?,
+                    None => self.begin().await?,
+                };
+
+                match callback(&mut transaction).await {
+                    Ok(ret) => {
+                        transaction.commit().await?;
+
+                        Ok(ret)
+                    }
+                    Err(err) => {
+                        transaction.rollback().await?;
+
+                        Err(err)
+                    }
+                }
+            }
+            .await;
+
+            match result {
+                Err(err) if attempt < policy.max_attempts && RetryPolicy::is_retryable(&err) => {
+                    tracing::debug!(attempt, ?backoff, "retrying transaction: {err}");
+
+                    crate::rt::sleep(backoff).await;
+
+                    attempt += 1;
+                    backoff = cmp::min(backoff * 2, policy.max_backoff);
+                }
+                result => return result,
+            }
+        }
+    }
+
     /// Shut down the connection pool, immediately waking all tasks waiting for a connection.
     ///
     /// Upon calling this method, any currently waiting or subsequent calls to [`Pool::acquire`] and
@@ -535,6 +726,37 @@ impl<DB: Database> Pool<DB> {
     pub fn options(&self) -> &PoolOptions<DB> {
         &self.0.options
     }
+
+    /// Get a snapshot of the statistics of the statements executed by the connections of
+    /// this pool, if enabled with [`ConnectOptions::collect_statistics()`].
+    pub fn statistics(&self) -> Option<Vec<StatementStats>> {
+        self.connect_options()
+            .statistics()
+            .map(StatementStatistics::snapshot)
+    }
+
+    /// Ping each idle connection of the pool, and return the round-trip time of each ping,
+    /// or the error it failed with.
+    ///
+    /// This measures the latency of the database as seen by the application, for health
+    /// checks which report more than whether the database is up. Connections in use are not
+    /// pinged; connections whose ping fails are closed, and replaced if that takes the pool
+    /// below [`PoolOptions::min_connections()`].
+    ///
+    /// Pinged connections are returned to the pool as they were, so pinging does not keep
+    /// them from reaching [`PoolOptions::idle_timeout()`].
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) {
+    /// let latencies = pool.ping_all().await;
+    ///
+    /// let healthy = latencies.iter().filter_map(|ping| ping.as_ref().ok());
+    /// let slowest = healthy.max();
+    /// # }
+    /// ```
+    pub async fn ping_all(&self) -> Vec<Result<Duration, Error>> {
+        self.0.ping_all().await
+    }
 }
 
 /// Returns a new [Pool] tied to the same shared connection pool.
@@ -0,0 +1,312 @@
+//! Metrics of queries and pools for the [`prometheus`](https://docs.rs/prometheus) crate,
+//! for applications which do not use the `metrics` facade.
+//!
+//! Register a [`SqlxCollector`] with a registry, and it reports:
+//!
+//! * `sqlx_query_duration_seconds`: a histogram of how long queries take.
+//! * `sqlx_query_rows_total`: a counter of the rows returned by queries.
+//! * `sqlx_query_errors_total`: a counter of failed queries, labeled with `kind`, such as
+//!   `unique_violation`, `serialization_failure`, `timeout` or `io`.
+//! * `sqlx_pool_acquire_duration_seconds`: a histogram of how long it takes to acquire a
+//!   connection from a pool.
+//! * `sqlx_pool_acquire_timeouts_total`: a counter of acquires which timed out.
+//! * `sqlx_pool_connections`, `sqlx_pool_idle_connections` and
+//!   `sqlx_pool_max_connections`: gauges of the pools added with [`SqlxCollector::pool()`],
+//!   labeled with `pool`.
+//!
+//! The query metrics are labeled with `operation`, the first keyword of the query such as
+//! `SELECT`, and with `query_name`, the [name][crate::query::Query::log_as] of the query or
+//! empty. These are the same metrics as reported through the `metrics` facade with the
+//! `metrics` feature.
+//!
+//! Queries are only recorded once a collector was created.
+//!
+//! ```rust,no_run
+//! # use sqlx_core::{database::Database, pool::Pool};
+//! # fn example<DB: Database>(pool: Pool<DB>) -> prometheus::Result<()> {
+//! use sqlx_core::prometheus::SqlxCollector;
+//!
+//! let collector = SqlxCollector::new().pool("main", &pool);
+//!
+//! prometheus::default_registry().register(Box::new(collector))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use ::prometheus::core::{Collector, Desc};
+use ::prometheus::proto::MetricFamily;
+use ::prometheus::{
+    Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGaugeVec, Opts,
+};
+
+use crate::database::Database;
+use crate::error::Error;
+use crate::pool::Pool;
+
+/// A [`Collector`] of the metrics of SQLx's queries and of the pools added to it.
+///
+/// The query and acquire metrics are shared by all collectors, so register one collector
+/// per registry.
+pub struct SqlxCollector {
+    metrics: &'static Metrics,
+    pool_connections: IntGaugeVec,
+    pool_idle_connections: IntGaugeVec,
+    pool_max_connections: IntGaugeVec,
+    pools: Vec<(String, Box<PoolStatsFn>)>,
+}
+
+type PoolStatsFn = dyn Fn() -> PoolStats + Send + Sync;
+
+struct PoolStats {
+    connections: u32,
+    idle_connections: usize,
+    max_connections: u32,
+}
+
+struct Metrics {
+    query_duration: HistogramVec,
+    query_rows: IntCounterVec,
+    query_errors: IntCounterVec,
+    pool_acquire_duration: Histogram,
+    pool_acquire_timeouts: IntCounter,
+}
+
+// created along with the first collector; nothing is recorded until then
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+impl SqlxCollector {
+    pub fn new() -> Self {
+        let pool_gauge = |name: &str, help: &str| {
+            IntGaugeVec::new(Opts::new(name, help), &["pool"]).expect("BUG: invalid metric options")
+        };
+
+        SqlxCollector {
+            metrics: METRICS.get_or_init(Metrics::new),
+            pool_connections: pool_gauge(
+                "sqlx_pool_connections",
+                "Connections of the pool, idle or in use.",
+            ),
+            pool_idle_connections: pool_gauge(
+                "sqlx_pool_idle_connections",
+                "Idle connections of the pool.",
+            ),
+            pool_max_connections: pool_gauge(
+                "sqlx_pool_max_connections",
+                "Maximum number of connections of the pool.",
+            ),
+            pools: Vec::new(),
+        }
+    }
+
+    /// Report the connections of `pool`, labeled with `pool="<name>"`.
+    ///
+    /// The collector holds a handle to the pool, which does not keep it from being closed.
+    pub fn pool<DB: Database>(mut self, name: impl Into<String>, pool: &Pool<DB>) -> Self {
+        let pool = pool.clone();
+
+        self.pools.push((
+            name.into(),
+            Box::new(move || PoolStats {
+                connections: pool.size(),
+                idle_connections: pool.num_idle(),
+                max_connections: pool.options().get_max_connections(),
+            }),
+        ));
+        self
+    }
+}
+
+impl Default for SqlxCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for SqlxCollector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqlxCollector")
+            .field(
+                "pools",
+                &self.pools.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl Collector for SqlxCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        let metrics = self.metrics;
+
+        [
+            metrics.query_duration.desc(),
+            metrics.query_rows.desc(),
+            metrics.query_errors.desc(),
+            metrics.pool_acquire_duration.desc(),
+            metrics.pool_acquire_timeouts.desc(),
+            self.pool_connections.desc(),
+            self.pool_idle_connections.desc(),
+            self.pool_max_connections.desc(),
+        ]
+        .concat()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        for (name, stats) in &self.pools {
+            let stats = stats();
+
+            self.pool_connections
+                .with_label_values(&[name.as_str()])
+                .set(stats.connections.into());
+            self.pool_idle_connections
+                .with_label_values(&[name.as_str()])
+                .set(stats.idle_connections.try_into().unwrap_or(i64::MAX));
+            self.pool_max_connections
+                .with_label_values(&[name.as_str()])
+                .set(stats.max_connections.into());
+        }
+
+        let metrics = self.metrics;
+
+        [
+            metrics.query_duration.collect(),
+            metrics.query_rows.collect(),
+            metrics.query_errors.collect(),
+            metrics.pool_acquire_duration.collect(),
+            metrics.pool_acquire_timeouts.collect(),
+            self.pool_connections.collect(),
+            self.pool_idle_connections.collect(),
+            self.pool_max_connections.collect(),
+        ]
+        .concat()
+        .into_iter()
+        // a family with no metrics yet (no pools, or no errors recorded) fails to encode
+        .filter(|family| !family.get_metric().is_empty())
+        .collect()
+    }
+}
+
+impl Metrics {
+    fn new() -> Self {
+        const QUERY_LABELS: &[&str] = &["operation", "query_name"];
+
+        Metrics {
+            query_duration: HistogramVec::new(
+                HistogramOpts::new("sqlx_query_duration_seconds", "Duration of queries."),
+                QUERY_LABELS,
+            )
+            .expect("BUG: invalid metric options"),
+            query_rows: IntCounterVec::new(
+                Opts::new("sqlx_query_rows_total", "Rows returned by queries."),
+                QUERY_LABELS,
+            )
+            .expect("BUG: invalid metric options"),
+            query_errors: IntCounterVec::new(
+                Opts::new("sqlx_query_errors_total", "Queries which failed."),
+                &["operation", "query_name", "kind"],
+            )
+            .expect("BUG: invalid metric options"),
+            pool_acquire_duration: Histogram::with_opts(HistogramOpts::new(
+                "sqlx_pool_acquire_duration_seconds",
+                "Time taken to acquire a connection from a pool.",
+            ))
+            .expect("BUG: invalid metric options"),
+            pool_acquire_timeouts: IntCounter::new(
+                "sqlx_pool_acquire_timeouts_total",
+                "Acquires of a connection from a pool which timed out.",
+            )
+            .expect("BUG: invalid metric options"),
+        }
+    }
+}
+
+pub(crate) fn record_query(
+    sql: &str,
+    name: Option<&'static str>,
+    elapsed: Duration,
+    rows_returned: u64,
+) {
+    let Some(metrics) = METRICS.get() else {
+        return;
+    };
+
+    let operation = crate::logger::parse_query_operation(sql).unwrap_or_default();
+    let labels = [operation.as_str(), name.unwrap_or_default()];
+
+    metrics
+        .query_duration
+        .with_label_values(&labels)
+        .observe(elapsed.as_secs_f64());
+    metrics
+        .query_rows
+        .with_label_values(&labels)
+        .inc_by(rows_returned);
+}
+
+pub(crate) fn record_query_error(sql: &str, name: Option<&'static str>, error: &Error) {
+    let Some(metrics) = METRICS.get() else {
+        return;
+    };
+
+    let operation = crate::logger::parse_query_operation(sql).unwrap_or_default();
+
+    metrics
+        .query_errors
+        .with_label_values(&[
+            operation.as_str(),
+            name.unwrap_or_default(),
+            error.kind_label(),
+        ])
+        .inc();
+}
+
+pub(crate) fn record_pool_acquire(elapsed: Duration) {
+    if let Some(metrics) = METRICS.get() {
+        metrics.pool_acquire_duration.observe(elapsed.as_secs_f64());
+    }
+}
+
+pub(crate) fn record_pool_acquire_timeout() {
+    if let Some(metrics) = METRICS.get() {
+        metrics.pool_acquire_timeouts.inc();
+    }
+}
+
+#[test]
+fn it_collects_recorded_queries() {
+    use ::prometheus::{Encoder, TextEncoder};
+
+    let collector = SqlxCollector::new();
+
+    record_query("select 1", Some("one"), Duration::from_millis(5), 1);
+    record_query_error("UPDATE foo SET bar = 1", None, &Error::Timeout);
+
+    let mut text = Vec::new();
+    TextEncoder::new()
+        .encode(&collector.collect(), &mut text)
+        .unwrap();
+    let text = String::from_utf8(text).unwrap();
+
+    assert!(text.contains(r#"sqlx_query_rows_total{operation="SELECT",query_name="one"} 1"#));
+    assert!(text
+        .contains(r#"sqlx_query_errors_total{kind="timeout",operation="UPDATE",query_name=""} 1"#));
+}
+
+#[test]
+fn it_encodes_without_pools() {
+    use ::prometheus::{Encoder, TextEncoder};
+
+    let collector = SqlxCollector::new();
+
+    let mut text = Vec::new();
+    TextEncoder::new()
+        .encode(&collector.collect(), &mut text)
+        .unwrap();
+
+    let text = String::from_utf8(text).unwrap();
+
+    assert!(!text.contains("sqlx_pool_connections"));
+}
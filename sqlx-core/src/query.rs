@@ -1,3 +1,4 @@
+use std::time::Duration;
 use std::{future, marker::PhantomData};
 
 use either::Either;
@@ -8,7 +9,8 @@ use crate::arguments::{Arguments, IntoArguments};
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
-use crate::executor::{Execute, Executor};
+use crate::executor::{chunked, Execute, Executor, ResultLimits};
+use crate::logger::QueryLogOptions;
 use crate::sql_str::{SqlSafeStr, SqlStr};
 use crate::statement::Statement;
 use crate::types::Type;
@@ -20,6 +22,9 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<Result<A, BoxDynError>>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) log: QueryLogOptions,
+    pub(crate) limits: ResultLimits,
 }
 
 /// A single SQL query that will map its results to an owned Rust type.
@@ -71,6 +76,21 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    #[inline]
+    fn log_options(&self) -> QueryLogOptions {
+        self.log
+    }
+
+    #[inline]
+    fn result_limits(&self) -> ResultLimits {
+        self.limits
+    }
 }
 
 impl<DB: Database> Query<'_, DB, <DB as Database>::Arguments> {
@@ -141,6 +161,73 @@ where
     }
 }
 
+impl<DB: Database, A> Query<'_, DB, A> {
+    /// Cancel the query if it has not completed within `timeout`, failing with [`Error::Timeout`].
+    ///
+    /// The deadline covers sending the query and reading all of its results. When it passes, the
+    /// driver asks the server to cancel the query and waits for it to stop, so the connection can
+    /// still be used afterwards. If the query completes just as the deadline passes, the server
+    /// may not receive the cancellation until it is running the next query on the connection.
+    ///
+    /// Only the Postgres driver supports cancelling queries; the timeout is ignored through `Any`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Log the query under `name`, in place of the first few words of its SQL.
+    ///
+    /// The name is also recorded in the `db.query_name` field of the event, so the logs of
+    /// a query can be found and filtered by name.
+    ///
+    /// Only supported by the Postgres driver; the name is ignored through `Any`.
+    pub fn log_as(mut self, name: &'static str) -> Self {
+        self.log.name = Some(name);
+        self
+    }
+
+    /// Do not log this query at all, even if it is slow.
+    ///
+    /// This is useful for queries which are too frequent to be worth logging, or whose SQL
+    /// itself is sensitive.
+    ///
+    /// Only supported by the Postgres driver; through `Any`, the query is logged as usual.
+    pub fn disable_logging(mut self) -> Self {
+        self.log.enabled = false;
+        self
+    }
+
+    /// Fail with [`Error::ResultTooLarge`] if the query returns more than `max` rows.
+    ///
+    /// This guards against a single unbounded query, e.g. collected with
+    /// [`fetch_all()`](Self::fetch_all), exhausting the memory of the application. The rows
+    /// of all the result sets of the query are counted, whether they are buffered or streamed.
+    ///
+    /// Overrides the default limit of the connection, if any, e.g. to let a known-large export
+    /// through. Only supported by the Postgres driver; the limit is ignored through `Any`.
+    pub fn max_rows(mut self, max: u64) -> Self {
+        self.limits.max_rows = Some(max);
+        self
+    }
+
+    /// Fail with [`Error::ResultTooLarge`] if the rows returned by the query add up to more
+    /// than `max` bytes, as sent by the database.
+    ///
+    /// See [`max_rows()`](Self::max_rows) for details.
+    pub fn max_result_bytes(mut self, max: u64) -> Self {
+        self.limits.max_bytes = Some(max);
+        self
+    }
+
+    /// Lift the result limits of the connection for this query.
+    ///
+    /// Useful for a query which is known to return a large result, e.g. an export; SQLx uses
+    /// this for its own queries, so that they are not failed by the limits of the application.
+    pub fn unlimited(self) -> Self {
+        self.max_rows(u64::MAX).max_result_bytes(u64::MAX)
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -217,6 +304,24 @@ where
         executor.fetch(self)
     }
 
+    /// Execute the query and return the generated results as a stream of chunks of
+    /// `chunk_size` rows each; the last chunk may be shorter.
+    ///
+    /// See [`Executor::fetch_chunked()`].
+    #[inline]
+    pub fn fetch_chunked<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<DB::Row>, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        executor.fetch_chunked(self, chunk_size)
+    }
+
     /// Execute multiple queries and return the generated results as a stream.
     ///
     /// For each query in the stream, any generated rows are returned first,
@@ -322,6 +427,21 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        self.inner.timeout
+    }
+
+    #[inline]
+    fn log_options(&self) -> QueryLogOptions {
+        self.inner.log
+    }
+
+    #[inline]
+    fn result_limits(&self) -> ResultLimits {
+        self.inner.limits
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -331,6 +451,55 @@ where
     O: Send + Unpin,
     A: 'q + Send + IntoArguments<DB>,
 {
+    /// Cancel the query if it has not completed within `timeout`, failing with [`Error::Timeout`].
+    ///
+    /// See [`Query::timeout`](Query::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
+    /// Log the query under `name`.
+    ///
+    /// See [`Query::log_as`](Query::log_as).
+    pub fn log_as(mut self, name: &'static str) -> Self {
+        self.inner = self.inner.log_as(name);
+        self
+    }
+
+    /// Do not log this query at all.
+    ///
+    /// See [`Query::disable_logging`](Query::disable_logging).
+    pub fn disable_logging(mut self) -> Self {
+        self.inner = self.inner.disable_logging();
+        self
+    }
+
+    /// Fail with [`Error::ResultTooLarge`] if the query returns more than `max` rows.
+    ///
+    /// See [`Query::max_rows`](Query::max_rows).
+    pub fn max_rows(mut self, max: u64) -> Self {
+        self.inner = self.inner.max_rows(max);
+        self
+    }
+
+    /// Fail with [`Error::ResultTooLarge`] if the rows returned by the query add up to more
+    /// than `max` bytes.
+    ///
+    /// See [`Query::max_result_bytes`](Query::max_result_bytes).
+    pub fn max_result_bytes(mut self, max: u64) -> Self {
+        self.inner = self.inner.max_result_bytes(max);
+        self
+    }
+
+    /// Lift the result limits of the connection for this query.
+    ///
+    /// See [`Query::unlimited`](Query::unlimited).
+    pub fn unlimited(mut self) -> Self {
+        self.inner = self.inner.unlimited();
+        self
+    }
+
     /// Map each row in the result to another type.
     ///
     /// See [`try_map`](Map::try_map) for a fallible version of this method.
@@ -391,6 +560,25 @@ where
             .boxed()
     }
 
+    /// Execute the query and return the generated results as a stream of chunks of
+    /// `chunk_size` rows each; the last chunk may be shorter.
+    ///
+    /// See [`Executor::fetch_chunked()`].
+    pub fn fetch_chunked<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<O>, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e,
+        O: 'e,
+    {
+        chunked(self.fetch(executor), chunk_size)
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[deprecated]
@@ -509,6 +697,9 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
+        log: QueryLogOptions::default(),
+        limits: ResultLimits::default(),
     }
 }
 
@@ -523,6 +714,9 @@ where
         arguments: Some(Ok(arguments)),
         statement: Either::Right(statement),
         persistent: true,
+        timeout: None,
+        log: QueryLogOptions::default(),
+        limits: ResultLimits::default(),
     }
 }
 
@@ -641,6 +835,9 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Left(sql.into_sql_str()),
         persistent: true,
+        timeout: None,
+        log: QueryLogOptions::default(),
+        limits: ResultLimits::default(),
     }
 }
 
@@ -669,5 +866,8 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql.into_sql_str()),
         persistent: true,
+        timeout: None,
+        log: QueryLogOptions::default(),
+        limits: ResultLimits::default(),
     }
 }
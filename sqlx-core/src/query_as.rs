@@ -1,4 +1,5 @@
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
@@ -8,8 +9,9 @@ use crate::arguments::IntoArguments;
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
-use crate::executor::{Execute, Executor};
+use crate::executor::{chunked, Execute, Executor, ResultLimits};
 use crate::from_row::FromRow;
+use crate::logger::QueryLogOptions;
 use crate::query::{query, query_statement, query_statement_with, query_with_result, Query};
 use crate::sql_str::{SqlSafeStr, SqlStr};
 use crate::types::Type;
@@ -46,6 +48,21 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn timeout(&self) -> Option<Duration> {
+        Execute::timeout(&self.inner)
+    }
+
+    #[inline]
+    fn log_options(&self) -> QueryLogOptions {
+        Execute::log_options(&self.inner)
+    }
+
+    #[inline]
+    fn result_limits(&self) -> ResultLimits {
+        Execute::result_limits(&self.inner)
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as Database>::Arguments> {
@@ -78,6 +95,57 @@ where
     }
 }
 
+impl<DB: Database, O, A> QueryAs<'_, DB, O, A> {
+    /// Cancel the query if it has not completed within `timeout`, failing with [`Error::Timeout`].
+    ///
+    /// See [`Query::timeout`](crate::query::Query::timeout).
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.timeout(timeout);
+        self
+    }
+
+    /// Log the query under `name`.
+    ///
+    /// See [`Query::log_as`](crate::query::Query::log_as).
+    pub fn log_as(mut self, name: &'static str) -> Self {
+        self.inner = self.inner.log_as(name);
+        self
+    }
+
+    /// Do not log this query at all.
+    ///
+    /// See [`Query::disable_logging`](crate::query::Query::disable_logging).
+    pub fn disable_logging(mut self) -> Self {
+        self.inner = self.inner.disable_logging();
+        self
+    }
+
+    /// Fail with [`Error::ResultTooLarge`] if the query returns more than `max` rows.
+    ///
+    /// See [`Query::max_rows`](crate::query::Query::max_rows).
+    pub fn max_rows(mut self, max: u64) -> Self {
+        self.inner = self.inner.max_rows(max);
+        self
+    }
+
+    /// Fail with [`Error::ResultTooLarge`] if the rows returned by the query add up to more
+    /// than `max` bytes.
+    ///
+    /// See [`Query::max_result_bytes`](crate::query::Query::max_result_bytes).
+    pub fn max_result_bytes(mut self, max: u64) -> Self {
+        self.inner = self.inner.max_result_bytes(max);
+        self
+    }
+
+    /// Lift the result limits of the connection for this query.
+    ///
+    /// See [`Query::unlimited`](crate::query::Query::unlimited).
+    pub fn unlimited(mut self) -> Self {
+        self.inner = self.inner.unlimited();
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -101,6 +169,25 @@ where
             .boxed()
     }
 
+    /// Execute the query and return the generated results as a stream of chunks of
+    /// `chunk_size` rows each; the last chunk may be shorter.
+    ///
+    /// See [`Executor::fetch_chunked()`].
+    pub fn fetch_chunked<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<O>, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+    {
+        chunked(self.fetch(executor), chunk_size)
+    }
+
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
     #[deprecated]
@@ -8,7 +8,10 @@ use std::sync::Arc;
 use crate::arguments::{Arguments, IntoArguments};
 use crate::database::Database;
 use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::ResultLimits;
 use crate::from_row::FromRow;
+use crate::logger::QueryLogOptions;
 use crate::query::Query;
 use crate::query_as::QueryAs;
 use crate::query_scalar::QueryScalar;
@@ -18,6 +21,16 @@ use crate::sql_str::SqlStr;
 use crate::types::Type;
 use crate::Either;
 
+mod bulk_insert;
+mod keyset;
+mod where_builder;
+
+pub use bulk_insert::BulkInsert;
+#[cfg(feature = "json")]
+pub use keyset::Page;
+pub use keyset::{KeysetKey, KeysetPage};
+pub use where_builder::WhereBuilder;
+
 /// A builder type for constructing queries at runtime.
 ///
 /// See [`.push_values()`][Self::push_values] for an example of building a bulk `INSERT` statement.
@@ -144,6 +157,8 @@ where
     ///       as discussed in [this Github issue][postgres-limit-issue]. Postgres itself
     ///       asserts that the number of parameters is in the range `[0, 65535)`.
     ///
+    /// The limit of each driver is also available as [`Database::MAX_ARGUMENTS`].
+    ///
     /// Exceeding these limits may panic (as a sanity check) or trigger a database error at runtime
     /// depending on the implementation.
     ///
@@ -168,6 +183,43 @@ where
         self
     }
 
+    /// Push an identifier, such as the name of a table or column, quoted so that it is taken as
+    /// it is, case-sensitively.
+    ///
+    /// Unlike [`.push()`][Self::push], this is safe to use with names which are not known
+    /// ahead of time: double quotes in the identifier are doubled, as SQL requires, so it cannot
+    /// end its quotes early to inject SQL.
+    ///
+    /// A name qualified with its schema or table, such as `"public.users"`, is quoted one part
+    /// at a time, as are the names given to [`.push_upsert()`][Self::push_upsert],
+    /// [`.where_clause()`][Self::where_clause] and [`KeysetPage`]. A name which itself
+    /// contains a dot has to be quoted by hand.
+    ///
+    /// ```rust
+    /// use sqlx::{Postgres, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM ");
+    /// query_builder.push_identifier("audit.Audit Log").push(" WHERE id = ").push_bind(1);
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql().as_str(),
+    ///     r#"SELECT * FROM "audit"."Audit Log" WHERE id = $1"#,
+    /// );
+    ///
+    /// query_builder.reset().push_identifier(r#"users"; DROP TABLE users; --"#);
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql().as_str(),
+    ///     r#"SELECT * FROM "users""; DROP TABLE users; --""#,
+    /// );
+    /// ```
+    ///
+    /// The identifier is not otherwise checked: an empty name, for one, is pushed as `""`, which
+    /// the database rejects.
+    pub fn push_identifier(&mut self, identifier: &str) -> &mut Self {
+        self.push(QualifiedIdent(identifier))
+    }
+
     /// Start a list separated by `separator`.
     ///
     /// The returned type exposes identical [`.push()`][Separated::push] and
@@ -187,6 +239,16 @@ where
         }
     }
 
+    /// Start a `WHERE` clause made of conditions which are added one at a time, some of which
+    /// may be optional.
+    ///
+    /// See [`WhereBuilder`] for details.
+    pub fn where_clause(&mut self) -> WhereBuilder<'_, DB> {
+        self.sanity_check();
+
+        WhereBuilder::new(self)
+    }
+
     // Most of the `QueryBuilder` API is purposefully very low-level but this was a commonly
     // requested use-case so it made sense to support.
     /// Push a `VALUES` clause where each item in `tuples` represents a tuple/row in the clause.
@@ -287,6 +349,106 @@ where
         separated.query_builder
     }
 
+    /// Push an `INSERT` statement which updates the existing row instead when a row conflicts
+    /// with it, using the `INSERT ... ON CONFLICT ... DO UPDATE` syntax of PostgreSQL.
+    ///
+    /// The values of `columns` are inserted for each item of `tuples`, pushed by `push_tuple` as
+    /// in [`.push_values()`][Self::push_values]. When a row conflicts with an existing row on the
+    /// unique index or constraint made of the `conflict_target` columns, the `update_columns` of
+    /// the existing row are set to the values of the row which was being inserted. If
+    /// `update_columns` is empty, the conflicting row is skipped instead (`DO NOTHING`).
+    ///
+    /// The table and column names are quoted, as with
+    /// [`.push_identifier()`][Self::push_identifier], so they are taken as they are,
+    /// case-sensitively. `table` may be qualified with its schema, as in `"schema.table"`.
+    ///
+    /// Further clauses, such as `RETURNING`, can be pushed afterwards.
+    ///
+    /// ```rust
+    /// use sqlx::{Postgres, QueryBuilder};
+    ///
+    /// struct Setting {
+    ///     user_id: i64,
+    ///     key: String,
+    ///     value: String,
+    /// }
+    ///
+    /// let settings = vec![Setting { user_id: 1, key: "theme".into(), value: "dark".into() }];
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("");
+    ///
+    /// query_builder.push_upsert(
+    ///     "settings",
+    ///     &["user_id", "key", "value"],
+    ///     &["user_id", "key"],
+    ///     &["value"],
+    ///     settings,
+    ///     |mut b, setting| {
+    ///         b.push_bind(setting.user_id)
+    ///             .push_bind(setting.key)
+    ///             .push_bind(setting.value);
+    ///     },
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql().as_str(),
+    ///     r#"INSERT INTO "settings" ("user_id", "key", "value") VALUES ($1, $2, $3) ON CONFLICT ("user_id", "key") DO UPDATE SET "value" = EXCLUDED."value""#,
+    /// );
+    /// # Ok::<(), sqlx::Error>(())
+    /// ```
+    ///
+    /// ### Notes
+    ///
+    /// As with [`.push_values()`][Self::push_values], `tuples` should not be empty, and is subject
+    /// to the limit on the number of bind arguments.
+    ///
+    /// PostgreSQL rejects a statement which would update the same row twice, so `tuples` should
+    /// not contain two rows with the same values for the `conflict_target` columns.
+    ///
+    /// ### Errors
+    /// [`Error::InvalidArgument`] if `conflict_target` is empty but `update_columns` is not:
+    /// PostgreSQL requires the conflict target to be given for `DO UPDATE`. An empty
+    /// `conflict_target` may only be used with `DO NOTHING`, to skip a row which conflicts on any
+    /// unique index or constraint. Nothing is pushed in that case.
+    pub fn push_upsert<I, F>(
+        &mut self,
+        table: &str,
+        columns: &[&str],
+        conflict_target: &[&str],
+        update_columns: &[&str],
+        tuples: I,
+        push_tuple: F,
+    ) -> Result<&mut Self, Error>
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, DB, &'static str>, I::Item),
+    {
+        let on_conflict = OnConflict::new(conflict_target, update_columns)?;
+
+        self.push_insert_into(table, columns);
+        self.push_values(tuples, push_tuple);
+
+        Ok(self.push(on_conflict))
+    }
+
+    // Push `INSERT INTO "table" ("column", ...) `, quoting each identifier.
+    fn push_insert_into(&mut self, table: &str, columns: &[&str]) -> &mut Self {
+        self.sanity_check();
+
+        self.push("INSERT INTO ");
+
+        self.push(QualifiedIdent(table));
+        self.push(" (");
+
+        let mut column_names = self.separated(", ");
+
+        for column in columns {
+            column_names.push(QuotedIdent(column));
+        }
+
+        self.push(") ")
+    }
+
     /// Produce an executable query from this builder.
     ///
     /// ### Note: Query is not Checked
@@ -307,6 +469,9 @@ where
             arguments: self.arguments.take().map(Ok),
             database: PhantomData,
             persistent: true,
+            timeout: None,
+            log: QueryLogOptions::default(),
+            limits: ResultLimits::default(),
         }
     }
 
@@ -455,3 +620,182 @@ where
         self
     }
 }
+
+// An identifier quoted for use in SQL: wrapped in double quotes, with double quotes doubled.
+struct QuotedIdent<'a>(&'a str);
+
+impl Display for QuotedIdent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('"')?;
+
+        for part in self.0.split_inclusive('"') {
+            f.write_str(part)?;
+
+            if part.ends_with('"') {
+                f.write_char('"')?;
+            }
+        }
+
+        f.write_char('"')
+    }
+}
+
+// The `ON CONFLICT` clause of an upsert, with `DO NOTHING` if there are no columns to update.
+struct OnConflict<'a> {
+    target: &'a [&'a str],
+    update: &'a [&'a str],
+}
+
+impl<'a> OnConflict<'a> {
+    fn new(target: &'a [&'a str], update: &'a [&'a str]) -> Result<Self, Error> {
+        if target.is_empty() && !update.is_empty() {
+            return Err(Error::InvalidArgument(format!(
+                "cannot update {update:?} on conflict without a conflict target"
+            )));
+        }
+
+        Ok(OnConflict { target, update })
+    }
+}
+
+impl Display for OnConflict<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(" ON CONFLICT ")?;
+
+        if !self.target.is_empty() {
+            f.write_char('(')?;
+
+            for (i, column) in self.target.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+
+                QuotedIdent(column).fmt(f)?;
+            }
+
+            f.write_str(") ")?;
+        }
+
+        if self.update.is_empty() {
+            return f.write_str("DO NOTHING");
+        }
+
+        f.write_str("DO UPDATE SET ")?;
+
+        for (i, column) in self.update.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(
+                f,
+                "{} = EXCLUDED.{}",
+                QuotedIdent(column),
+                QuotedIdent(column)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// An identifier which may be qualified, such as `schema.table`, quoted one part at a time.
+struct QualifiedIdent<'a>(&'a str);
+
+impl Display for QualifiedIdent<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, part) in self.0.split('.').enumerate() {
+            if i > 0 {
+                f.write_char('.')?;
+            }
+
+            QuotedIdent(part).fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn identifiers_are_quoted() {
+    assert_eq!(QuotedIdent("users").to_string(), r#""users""#);
+    assert_eq!(QuotedIdent("Mixed Case").to_string(), r#""Mixed Case""#);
+    assert_eq!(
+        QuotedIdent(r#"a"; DROP TABLE users; --"#).to_string(),
+        r#""a""; DROP TABLE users; --""#
+    );
+}
+
+#[test]
+fn qualified_identifiers_are_quoted_one_part_at_a_time() {
+    assert_eq!(QualifiedIdent("users").to_string(), r#""users""#);
+    assert_eq!(
+        QualifiedIdent("public.Users").to_string(),
+        r#""public"."Users""#
+    );
+    assert_eq!(QualifiedIdent(r#"a".b"#).to_string(), r#""a"""."b""#);
+    assert_eq!(QualifiedIdent("").to_string(), r#""""#);
+}
+
+#[test]
+fn conflicts_are_updated_or_skipped() {
+    assert_eq!(
+        OnConflict::new(&["user_id", "key"], &["value", "Updated At"])
+            .unwrap()
+            .to_string(),
+        r#" ON CONFLICT ("user_id", "key") DO UPDATE SET "value" = EXCLUDED."value", "Updated At" = EXCLUDED."Updated At""#
+    );
+    assert_eq!(
+        OnConflict::new(&["id"], &[]).unwrap().to_string(),
+        r#" ON CONFLICT ("id") DO NOTHING"#
+    );
+    assert_eq!(
+        OnConflict::new(&[], &[]).unwrap().to_string(),
+        " ON CONFLICT DO NOTHING"
+    );
+}
+
+#[test]
+fn conflicts_cannot_be_updated_without_a_target() {
+    assert!(matches!(
+        OnConflict::new(&[], &["name"]),
+        Err(Error::InvalidArgument(message)) if message.contains("without a conflict target")
+    ));
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn upsert_without_conflict_target_does_nothing() {
+    let mut query_builder = QueryBuilder::<crate::mock::Mock>::new("");
+
+    query_builder
+        .push_upsert("users", &["id"], &[], &[], [1], |mut b, id| {
+            b.push_bind(id);
+        })
+        .unwrap();
+
+    assert_eq!(
+        query_builder.sql().as_str(),
+        r#"INSERT INTO "users" ("id") VALUES ($1) ON CONFLICT DO NOTHING"#
+    );
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn upsert_without_conflict_target_cannot_update() {
+    let mut query_builder = QueryBuilder::<crate::mock::Mock>::new("");
+
+    let result = query_builder.push_upsert(
+        "users",
+        &["id", "name"],
+        &[],
+        &["name"],
+        [(1, "alice")],
+        |mut b, (id, name)| {
+            b.push_bind(id).push_bind(name);
+        },
+    );
+
+    assert!(matches!(result, Err(Error::InvalidArgument(_))));
+    assert_eq!(query_builder.sql().as_str(), "");
+}
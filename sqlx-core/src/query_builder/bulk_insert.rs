@@ -0,0 +1,162 @@
+//! Inserting any number of rows, split across as many statements as needed.
+
+use std::cmp;
+
+use crate::acquire::Acquire;
+use crate::arguments::IntoArguments;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::Executor;
+
+use super::{QueryBuilder, Separated};
+
+/// An `INSERT` of any number of rows, split into statements which each stay below the limit
+/// on bind arguments.
+///
+/// [`QueryBuilder::push_values()`] builds a single statement, so the caller has to keep
+/// the number of rows in check. `BulkInsert` instead takes as many rows as fit in
+/// [`Database::MAX_ARGUMENTS`] bind arguments, inserts them, and repeats until the rows
+/// run out.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::query_builder::BulkInsert;
+///
+/// struct User {
+///     id: i64,
+///     username: String,
+/// }
+///
+/// let users = (0..100_000).map(|id| User {
+///     id,
+///     username: format!("user_{id}"),
+/// });
+///
+/// let result = BulkInsert::new("users", &["id", "username"])
+///     .in_transaction(true)
+///     .execute(&pool, users, |mut b, user| {
+///         b.push_bind(user.id).push_bind(user.username);
+///     })
+///     .await?;
+///
+/// assert_eq!(result.rows_affected(), 100_000);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The table and column names are quoted, as with [`QueryBuilder::push_identifier()`];
+/// a schema-qualified table name is quoted one part at a time.
+#[derive(Debug, Clone)]
+pub struct BulkInsert<'a> {
+    table: &'a str,
+    columns: &'a [&'a str],
+    max_rows: Option<usize>,
+    in_transaction: bool,
+}
+
+impl<'a> BulkInsert<'a> {
+    /// Insert into `table`, pushing a value for each of `columns` for each row.
+    pub fn new(table: &'a str, columns: &'a [&'a str]) -> Self {
+        BulkInsert {
+            table,
+            columns,
+            max_rows: None,
+            in_transaction: false,
+        }
+    }
+
+    /// Insert at most `max_rows` rows per statement, if that is fewer than fit in the limit
+    /// on bind arguments.
+    ///
+    /// Smaller statements hold locks for less time, and bound the size of each message.
+    pub fn max_rows_per_statement(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// If `true`, insert all of the rows in a single transaction, so that either all of them
+    /// are inserted or none are.
+    ///
+    /// Otherwise, each statement is committed on its own (unless the connection is already
+    /// in a transaction), and an error leaves the rows of the statements before it inserted.
+    pub fn in_transaction(mut self, in_transaction: bool) -> Self {
+        self.in_transaction = in_transaction;
+        self
+    }
+
+    /// Insert `rows`, calling `push_row` to push the value of each column for a row, in order.
+    ///
+    /// Returns the combined result of every statement, such as the total number of rows
+    /// affected. No statement is executed if `rows` is empty.
+    ///
+    /// ### Panics
+    /// If there are no columns, or more than [`Database::MAX_ARGUMENTS`].
+    pub async fn execute<'c, A, DB, I, F>(
+        &self,
+        conn: A,
+        rows: I,
+        push_row: F,
+    ) -> Result<DB::QueryResult, Error>
+    where
+        A: Acquire<'c, Database = DB>,
+        DB: Database,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+        DB::Arguments: IntoArguments<DB>,
+        I: IntoIterator,
+        F: FnMut(Separated<'_, DB, &'static str>, I::Item),
+    {
+        if self.in_transaction {
+            let mut tx = conn.begin().await?;
+            let result = self.insert_chunks(&mut tx, rows, push_row).await?;
+            tx.commit().await?;
+
+            Ok(result)
+        } else {
+            let mut conn = conn.acquire().await?;
+            self.insert_chunks(&mut conn, rows, push_row).await
+        }
+    }
+
+    async fn insert_chunks<DB, I, F>(
+        &self,
+        conn: &mut DB::Connection,
+        rows: I,
+        mut push_row: F,
+    ) -> Result<DB::QueryResult, Error>
+    where
+        DB: Database,
+        for<'e> &'e mut DB::Connection: Executor<'e, Database = DB>,
+        DB::Arguments: IntoArguments<DB>,
+        I: IntoIterator,
+        F: FnMut(Separated<'_, DB, &'static str>, I::Item),
+    {
+        assert!(
+            !self.columns.is_empty() && self.columns.len() <= DB::MAX_ARGUMENTS,
+            "cannot insert {} columns: expected between 1 and {}",
+            self.columns.len(),
+            DB::MAX_ARGUMENTS
+        );
+
+        let mut chunk_rows = DB::MAX_ARGUMENTS / self.columns.len();
+
+        if let Some(max_rows) = self.max_rows {
+            chunk_rows = cmp::max(cmp::min(chunk_rows, max_rows), 1);
+        }
+
+        let mut rows = rows.into_iter().peekable();
+        let mut result = DB::QueryResult::default();
+        let mut query_builder = QueryBuilder::new("");
+
+        while rows.peek().is_some() {
+            query_builder.reset();
+            query_builder
+                .push_insert_into(self.table, self.columns)
+                .push_values(rows.by_ref().take(chunk_rows), &mut push_row);
+
+            let chunk_result = query_builder.build().execute(&mut *conn).await?;
+            result.extend(Some(chunk_result));
+        }
+
+        Ok(result)
+    }
+}
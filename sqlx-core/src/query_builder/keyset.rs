@@ -0,0 +1,276 @@
+//! Keyset (a.k.a. "seek") pagination on top of [`QueryBuilder`].
+
+use crate::database::Database;
+use crate::encode::Encode;
+#[cfg(feature = "json")]
+use crate::error::Error;
+use crate::types::Type;
+
+use super::{QualifiedIdent, QueryBuilder, Separated};
+
+/// Keyset pagination for a query ordered by one or more key columns.
+///
+/// Instead of skipping over `OFFSET` rows, which the database still has to read, each page
+/// starts where the previous one ended: `WHERE ("k1", "k2") > ($1, $2) ORDER BY "k1", "k2"`.
+/// With an index on the key columns, fetching a page costs the same however deep it is.
+///
+/// The key columns must uniquely identify a row, or rows with the same key may be skipped
+/// at the boundary between two pages; end the list with the primary key if need be.
+///
+/// `K` is the type of the key: a tuple with one element per key column, such as `(i64,)`
+/// or `(OffsetDateTime, Uuid)`. See [`KeysetKey`].
+///
+/// ```rust
+/// use sqlx::{Postgres, QueryBuilder};
+/// use sqlx::query_builder::KeysetPage;
+///
+/// let page = KeysetPage::new(&["created_at", "id"], 50).after((1_700_000_000_i64, 42_i64));
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM posts");
+/// page.push_where(&mut query_builder);
+/// page.push_order_by(&mut query_builder);
+///
+/// assert_eq!(
+///     query_builder.sql().as_str(),
+///     r#"SELECT * FROM posts WHERE ("created_at", "id") > ($1, $2) ORDER BY "created_at", "id" LIMIT 51"#,
+/// );
+/// ```
+///
+/// One more row than the page size is fetched so that [`.finish()`][Self::finish] can tell
+/// whether there is a next page, and produce the cursor to fetch it with.
+#[derive(Debug, Clone)]
+pub struct KeysetPage<K> {
+    columns: Vec<String>,
+    descending: bool,
+    limit: usize,
+    after: Option<K>,
+}
+
+/// One page of results from a [`KeysetPage`].
+#[cfg(feature = "json")]
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// The rows of this page, at most as many as the page size.
+    pub items: Vec<T>,
+    /// The cursor to fetch the next page with, or `None` if this is the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl<K> KeysetPage<K> {
+    /// Paginate on the given key columns, `limit` rows per page, starting from the first page.
+    ///
+    /// The column names are quoted when pushed, as with
+    /// [`QueryBuilder::push_identifier()`][super::QueryBuilder::push_identifier].
+    pub fn new(columns: &[&str], limit: usize) -> Self {
+        KeysetPage {
+            columns: columns.iter().map(|&column| column.to_owned()).collect(),
+            descending: false,
+            limit,
+            after: None,
+        }
+    }
+
+    /// Order by the key columns in descending order instead.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    /// Start the page after the row with the given key.
+    pub fn after(mut self, key: K) -> Self {
+        self.after = Some(key);
+        self
+    }
+
+    /// Start the page after the given cursor, as returned in [`Page::next_cursor`].
+    ///
+    /// `None` starts from the first page, so the cursor a client sent back can be passed as is.
+    ///
+    /// ### Errors
+    /// [`Error::InvalidArgument`] if the cursor is malformed or does not hold a `K`.
+    #[cfg(feature = "json")]
+    pub fn after_cursor(mut self, cursor: Option<&str>) -> Result<Self, Error>
+    where
+        K: serde::de::DeserializeOwned,
+    {
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        use base64::Engine;
+
+        let Some(cursor) = cursor else {
+            self.after = None;
+            return Ok(self);
+        };
+
+        let invalid = |e: &dyn std::fmt::Display| {
+            Error::InvalidArgument(format!("invalid pagination cursor {cursor:?}: {e}"))
+        };
+
+        let json = URL_SAFE_NO_PAD.decode(cursor).map_err(|e| invalid(&e))?;
+        let key = serde_json::from_slice(&json).map_err(|e| invalid(&e))?;
+
+        self.after = Some(key);
+        Ok(self)
+    }
+
+    /// Push ` WHERE (<key columns>) > (<key>)`, or nothing on the first page.
+    ///
+    /// To combine the page with filters of your own, use
+    /// [`.push_condition()`][Self::push_condition] instead.
+    pub fn push_where<'qb, DB>(
+        &self,
+        query_builder: &'qb mut QueryBuilder<DB>,
+    ) -> &'qb mut QueryBuilder<DB>
+    where
+        DB: Database,
+        K: KeysetKey<DB>,
+    {
+        if self.after.is_some() {
+            query_builder.push(" WHERE ");
+            self.push_condition(query_builder);
+        }
+
+        query_builder
+    }
+
+    /// Push `(<key columns>) > (<key>)`, or `TRUE` on the first page, as one condition of
+    /// a `WHERE` clause.
+    ///
+    /// The comparison is `<` instead if the page is [descending][Self::descending].
+    pub fn push_condition<'qb, DB>(
+        &self,
+        query_builder: &'qb mut QueryBuilder<DB>,
+    ) -> &'qb mut QueryBuilder<DB>
+    where
+        DB: Database,
+        K: KeysetKey<DB>,
+    {
+        let Some(after) = &self.after else {
+            return query_builder.push("TRUE");
+        };
+
+        query_builder.push("(");
+
+        let mut columns = query_builder.separated(", ");
+
+        for column in &self.columns {
+            columns.push(QualifiedIdent(column));
+        }
+
+        query_builder.push(if self.descending { ") < (" } else { ") > (" });
+        after.push_binds(&mut query_builder.separated(", "));
+        query_builder.push(")")
+    }
+
+    /// Push ` ORDER BY <key columns> LIMIT <page size + 1>`.
+    pub fn push_order_by<'qb, DB>(
+        &self,
+        query_builder: &'qb mut QueryBuilder<DB>,
+    ) -> &'qb mut QueryBuilder<DB>
+    where
+        DB: Database,
+    {
+        query_builder.push(" ORDER BY ");
+
+        let mut columns = query_builder.separated(", ");
+
+        for column in &self.columns {
+            if self.descending {
+                columns.push(format_args!("{} DESC", QualifiedIdent(column)));
+            } else {
+                columns.push(QualifiedIdent(column));
+            }
+        }
+
+        query_builder.push(format_args!(" LIMIT {}", self.limit.saturating_add(1)))
+    }
+
+    /// Turn the rows fetched by the query into a [`Page`], using `key` to get the key of a row.
+    ///
+    /// If there are more rows than the page size, the extra row is dropped and
+    /// [`Page::next_cursor`] is set to an opaque cursor for the last row of the page, which
+    /// can be handed out to clients and passed back to [`.after_cursor()`][Self::after_cursor].
+    ///
+    /// ### Errors
+    /// [`Error::Encode`] if the key cannot be serialized.
+    #[cfg(feature = "json")]
+    pub fn finish<T, F>(&self, mut rows: Vec<T>, key: F) -> Result<Page<T>, Error>
+    where
+        F: FnOnce(&T) -> K,
+        K: serde::Serialize,
+    {
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        use base64::Engine;
+
+        if rows.len() <= self.limit {
+            return Ok(Page {
+                items: rows,
+                next_cursor: None,
+            });
+        }
+
+        rows.truncate(self.limit);
+
+        let next_cursor = match rows.last() {
+            Some(last) => {
+                let json = serde_json::to_vec(&key(last)).map_err(|e| Error::Encode(e.into()))?;
+                Some(URL_SAFE_NO_PAD.encode(json))
+            }
+            // A page size of zero never gets anywhere.
+            None => None,
+        };
+
+        Ok(Page {
+            items: rows,
+            next_cursor,
+        })
+    }
+}
+
+/// The key of a row in a [`KeysetPage`]: a tuple of values, one for each key column.
+///
+/// Implemented for tuples of up to 6 elements. A single key column still takes a tuple,
+/// such as `(i64,)`.
+pub trait KeysetKey<DB: Database> {
+    /// Push a bind argument for each element, in order.
+    fn push_binds(&self, separated: &mut Separated<'_, DB, &'static str>);
+}
+
+macro_rules! impl_keyset_key {
+    ($($T:ident: $idx:tt),+) => {
+        impl<DB: Database, $($T),+> KeysetKey<DB> for ($($T,)+)
+        where
+            $($T: for<'t> Encode<'t, DB> + Type<DB>,)+
+        {
+            fn push_binds(&self, separated: &mut Separated<'_, DB, &'static str>) {
+                $(separated.push_bind(&self.$idx);)+
+            }
+        }
+    };
+}
+
+impl_keyset_key!(T1: 0);
+impl_keyset_key!(T1: 0, T2: 1);
+impl_keyset_key!(T1: 0, T2: 1, T3: 2);
+impl_keyset_key!(T1: 0, T2: 1, T3: 2, T4: 3);
+impl_keyset_key!(T1: 0, T2: 1, T3: 2, T4: 3, T5: 4);
+impl_keyset_key!(T1: 0, T2: 1, T3: 2, T4: 3, T5: 4, T6: 5);
+
+#[cfg(feature = "json")]
+#[test]
+fn cursors_round_trip() {
+    let page = KeysetPage::<(i64, String)>::new(&["id", "name"], 2);
+
+    let first = page
+        .finish(vec![1, 2, 3], |&id| (id, format!("row {id}")))
+        .unwrap();
+    assert_eq!(first.items, [1, 2]);
+
+    let cursor = first.next_cursor.expect("there is a next page");
+    let next = page.clone().after_cursor(Some(&cursor)).unwrap();
+    assert_eq!(next.after, Some((2, "row 2".to_owned())));
+
+    let last = next.finish(vec![3], |&id| (id, String::new())).unwrap();
+    assert_eq!(last.next_cursor, None);
+
+    assert!(page.after_cursor(Some("not a cursor")).is_err());
+}
@@ -0,0 +1,333 @@
+//! Dynamic `WHERE` clauses on top of [`QueryBuilder`].
+
+use std::fmt::Display;
+
+use crate::database::Database;
+use crate::encode::Encode;
+use crate::types::Type;
+
+use super::{QualifiedIdent, QueryBuilder};
+
+/// A `WHERE` clause which is pushed to a [`QueryBuilder`] one condition at a time.
+///
+/// Returned by [`QueryBuilder::where_clause()`]. Conditions are joined with `AND`, and
+/// [`.any()`][Self::any] and [`.all()`][Self::all] nest `OR` and `AND` groups in parentheses.
+/// `WHERE` itself, like the parentheses of a group, is only pushed along with the first
+/// condition, so filters which are only sometimes present can be added with a plain `if`:
+///
+/// ```rust
+/// use sqlx::{Postgres, QueryBuilder};
+///
+/// let name: Option<&str> = Some("%ferris%");
+/// let min_age: Option<i32> = None;
+/// let statuses = ["active", "invited"];
+///
+/// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+///
+/// let mut filter = query_builder.where_clause();
+///
+/// if let Some(name) = name {
+///     filter.any(|any| {
+///         any.ilike("name", name).ilike("email", name);
+///     });
+/// }
+///
+/// if let Some(min_age) = min_age {
+///     filter.gte("age", min_age);
+/// }
+///
+/// filter.is_in("status", statuses).is_null("deleted_at");
+///
+/// query_builder.push(" ORDER BY id");
+///
+/// assert_eq!(
+///     query_builder.sql().as_str(),
+///     r#"SELECT * FROM users WHERE ("name" ILIKE $1 OR "email" ILIKE $2) AND "status" IN ($3, $4) AND "deleted_at" IS NULL ORDER BY id"#,
+/// );
+/// ```
+///
+/// Column names are quoted, as with [`QueryBuilder::push_identifier()`], so they are matched
+/// case-sensitively; a qualified name such as `"users.name"` is quoted one part at a time.
+/// Conditions which are not about a single column can be pushed with [`.push()`][Self::push]
+/// or [`.push_with()`][Self::push_with].
+///
+/// Values are always pushed as bind arguments. Keep in mind that comparing with `NULL`, such
+/// as `.eq("column", None::<i32>)`, is never true; use [`.is_null()`][Self::is_null] instead.
+pub struct WhereBuilder<'qb, DB>
+where
+    DB: Database,
+{
+    query_builder: &'qb mut QueryBuilder<DB>,
+    // SQL pushed before the first condition, such as ` WHERE ` or ` AND (`.
+    lead: String,
+    separator: &'static str,
+    empty: bool,
+}
+
+impl<'qb, DB> WhereBuilder<'qb, DB>
+where
+    DB: Database,
+{
+    pub(super) fn new(query_builder: &'qb mut QueryBuilder<DB>) -> Self {
+        WhereBuilder {
+            query_builder,
+            lead: " WHERE ".into(),
+            separator: " AND ",
+            empty: true,
+        }
+    }
+
+    /// Returns `true` if no condition has been pushed, in which case neither has `WHERE`.
+    pub fn is_empty(&self) -> bool {
+        self.empty
+    }
+
+    /// Push `column = value`.
+    pub fn eq<'t, T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " = ", value)
+    }
+
+    /// Push `column <> value`.
+    pub fn ne<'t, T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " <> ", value)
+    }
+
+    /// Push `column < value`.
+    pub fn lt<'t, T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " < ", value)
+    }
+
+    /// Push `column <= value`.
+    pub fn lte<'t, T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " <= ", value)
+    }
+
+    /// Push `column > value`.
+    pub fn gt<'t, T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " > ", value)
+    }
+
+    /// Push `column >= value`.
+    pub fn gte<'t, T>(&mut self, column: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " >= ", value)
+    }
+
+    /// Push `column LIKE pattern`.
+    ///
+    /// `%` and `_` in `pattern` are wildcards, and must be escaped with `\` to be matched as is.
+    pub fn like<'t, T>(&mut self, column: &str, pattern: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " LIKE ", pattern)
+    }
+
+    /// Push `column ILIKE pattern`, the case-insensitive version of [`.like()`][Self::like].
+    pub fn ilike<'t, T>(&mut self, column: &str, pattern: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        self.compare(column, " ILIKE ", pattern)
+    }
+
+    /// Push `column IS NULL`.
+    pub fn is_null(&mut self, column: &str) -> &mut Self {
+        let query_builder = self.condition();
+        push_column(query_builder, column);
+        query_builder.push(" IS NULL");
+        self
+    }
+
+    /// Push `column IS NOT NULL`.
+    pub fn is_not_null(&mut self, column: &str) -> &mut Self {
+        let query_builder = self.condition();
+        push_column(query_builder, column);
+        query_builder.push(" IS NOT NULL");
+        self
+    }
+
+    /// Push `column IN (value, ...)`, with a bind argument for each value.
+    ///
+    /// An empty list matches nothing, so `FALSE` is pushed instead.
+    ///
+    /// Each value takes up a bind argument; see [`QueryBuilder::push_bind()`] for the limit
+    /// on their number. With Postgres, binding an array to `= ANY($1)` with
+    /// [`.push_with()`][Self::push_with] takes a single one.
+    pub fn is_in<'t, I>(&mut self, column: &str, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: Encode<'t, DB> + Type<DB>,
+    {
+        self.in_list(column, " IN (", "FALSE", values)
+    }
+
+    /// Push `column NOT IN (value, ...)`, with a bind argument for each value.
+    ///
+    /// An empty list matches everything, so `TRUE` is pushed instead.
+    pub fn not_in<'t, I>(&mut self, column: &str, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: Encode<'t, DB> + Type<DB>,
+    {
+        self.in_list(column, " NOT IN (", "TRUE", values)
+    }
+
+    /// Push a group of conditions joined with `OR`, in parentheses.
+    ///
+    /// If `f` pushes no condition, neither is the group:
+    ///
+    /// ```rust
+    /// use sqlx::{Postgres, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM users");
+    ///
+    /// query_builder
+    ///     .where_clause()
+    ///     .any(|_| {})
+    ///     .any(|any| {
+    ///         any.eq("id", 1).all(|all| {
+    ///             all.gt("age", 18).is_not_null("users.verified_at");
+    ///         });
+    ///     });
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql().as_str(),
+    ///     r#"SELECT * FROM users WHERE ("id" = $1 OR ("age" > $2 AND "users"."verified_at" IS NOT NULL))"#,
+    /// );
+    /// ```
+    pub fn any<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut WhereBuilder<'_, DB>),
+    {
+        self.group(" OR ", f)
+    }
+
+    /// Push a group of conditions joined with `AND`, in parentheses.
+    ///
+    /// This is only useful within [`.any()`][Self::any]. If `f` pushes no condition,
+    /// neither is the group.
+    pub fn all<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut WhereBuilder<'_, DB>),
+    {
+        self.group(" AND ", f)
+    }
+
+    /// Push a condition written in SQL.
+    ///
+    /// ### Note: Sql Injection
+    /// As with [`QueryBuilder::push()`], `sql` is pushed as is, and must not contain
+    /// untrusted input.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.condition().push(sql);
+        self
+    }
+
+    /// Push a condition with direct access to the [`QueryBuilder`], to bind arguments
+    /// or use other helpers such as [`KeysetPage::push_condition()`][super::KeysetPage::push_condition].
+    ///
+    /// `f` must push exactly one condition.
+    pub fn push_with<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut QueryBuilder<DB>),
+    {
+        f(self.condition());
+        self
+    }
+
+    fn compare<'t, T>(&mut self, column: &str, operator: &str, value: T) -> &mut Self
+    where
+        T: Encode<'t, DB> + Type<DB>,
+    {
+        let query_builder = self.condition();
+        push_column(query_builder, column);
+        query_builder.push(operator).push_bind(value);
+        self
+    }
+
+    fn in_list<'t, I>(&mut self, column: &str, operator: &str, empty: &str, values: I) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: Encode<'t, DB> + Type<DB>,
+    {
+        let mut values = values.into_iter().peekable();
+
+        if values.peek().is_none() {
+            return self.push(empty);
+        }
+
+        let query_builder = self.condition();
+        push_column(query_builder, column);
+        query_builder.push(operator);
+
+        let mut binds = query_builder.separated(", ");
+
+        for value in values {
+            binds.push_bind(value);
+        }
+
+        query_builder.push(")");
+        self
+    }
+
+    fn group<F>(&mut self, separator: &'static str, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut WhereBuilder<'_, DB>),
+    {
+        let lead = if self.empty {
+            format!("{}(", self.lead)
+        } else {
+            format!("{}(", self.separator)
+        };
+
+        let mut group = WhereBuilder {
+            query_builder: &mut *self.query_builder,
+            lead,
+            separator,
+            empty: true,
+        };
+
+        f(&mut group);
+
+        if !group.empty {
+            self.query_builder.push(")");
+            self.empty = false;
+        }
+
+        self
+    }
+
+    // Push what goes before a new condition, and return the query builder to push it to.
+    fn condition(&mut self) -> &mut QueryBuilder<DB> {
+        if self.empty {
+            self.query_builder.push(&self.lead);
+            self.empty = false;
+        } else {
+            self.query_builder.push(self.separator);
+        }
+
+        self.query_builder
+    }
+}
+
+fn push_column<DB: Database>(query_builder: &mut QueryBuilder<DB>, column: &str) {
+    query_builder.push(QualifiedIdent(column));
+}
@@ -0,0 +1,465 @@
+//! Named queries kept in `.sql` files rather than in Rust source.
+//!
+//! A [`QueryStore`] loads a directory of `.sql` files, each holding one query named after
+//! its path without the extension: `users/find_by_id.sql` is the query `users/find_by_id`.
+//!
+//! The parameters of a query are declared in its front-matter, the `--` comments at the top
+//! of the file, one `-- param: <name> [<type>]` line per parameter in the order they are
+//! bound. Other comments are left alone.
+//!
+//! ```sql
+//! -- Find a user by their email address.
+//! -- param: email TEXT
+//! -- param: active BOOL
+//! SELECT id, name FROM users WHERE email = $1 AND active = $2
+//! ```
+//!
+//! [`QueryStore::validate()`] prepares every query on the database, checking its SQL and
+//! that it takes the declared parameters, so that a broken query is found at startup rather
+//! than when it is first executed:
+//!
+//! ```rust,ignore
+//! # async fn example(pool: sqlx::PgPool) -> Result<(), sqlx::error::BoxDynError> {
+//! use sqlx::query_store::QueryStore;
+//!
+//! let store = QueryStore::load("queries").await?;
+//! store.validate(&pool).await?;
+//!
+//! let result = store
+//!     .query("users/deactivate")
+//!     .bind("jane@example.com")
+//!     .execute(&pool)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use either::Either;
+
+use crate::acquire::Acquire;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::{query, Query};
+use crate::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
+use crate::statement::Statement;
+use crate::type_info::TypeInfo;
+
+/// A set of named queries, loaded from `.sql` files.
+///
+/// See [the module documentation][self] for the format of the files.
+#[derive(Debug, Clone, Default)]
+pub struct QueryStore {
+    queries: BTreeMap<String, StoredQuery>,
+}
+
+/// A query of a [`QueryStore`].
+#[derive(Debug, Clone)]
+pub struct StoredQuery {
+    name: String,
+    path: Option<PathBuf>,
+    params: Vec<QueryParam>,
+    sql: SqlStr,
+}
+
+/// A parameter declared in the front-matter of a [`StoredQuery`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryParam {
+    name: String,
+    type_name: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum QueryStoreError {
+    #[error("error reading {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("invalid front-matter in query {name}: {message}")]
+    FrontMatter { name: String, message: String },
+
+    #[error("query {0} is defined more than once")]
+    Duplicate(String),
+
+    #[error(transparent)]
+    Database(#[from] Error),
+
+    #[error("query {name} failed to prepare: {source}")]
+    Prepare {
+        name: String,
+        #[source]
+        source: Error,
+    },
+
+    #[error("query {name} declares {declared} parameters but takes {actual}")]
+    ParameterCount {
+        name: String,
+        declared: usize,
+        actual: usize,
+    },
+
+    #[error("parameter {param} of query {name} is declared as {declared} but is {actual}")]
+    ParameterType {
+        name: String,
+        param: String,
+        declared: String,
+        actual: String,
+    },
+}
+
+impl QueryStore {
+    /// Load the `.sql` files of `dir` and its subdirectories.
+    ///
+    /// Files with other extensions are ignored.
+    pub async fn load(dir: impl AsRef<Path>) -> Result<Self, QueryStoreError> {
+        let root = dir.as_ref().to_path_buf();
+        let mut store = QueryStore::default();
+        let mut dirs = vec![root.clone()];
+
+        while let Some(dir) = dirs.pop() {
+            let mut entries =
+                crate::fs::read_dir(dir.clone())
+                    .await
+                    .map_err(|source| QueryStoreError::Io {
+                        path: dir.clone(),
+                        source,
+                    })?;
+
+            while let Some(entry) = entries.next().await.map_err(|source| QueryStoreError::Io {
+                path: dir.clone(),
+                source,
+            })? {
+                if entry.metadata.is_dir() {
+                    dirs.push(entry.path);
+                    continue;
+                }
+
+                if !entry.metadata.is_file()
+                    || entry.path.extension().is_none_or(|ext| ext != "sql")
+                {
+                    continue;
+                }
+
+                let name = query_name(&root, &entry.path);
+
+                let sql = crate::fs::read_to_string(&entry.path)
+                    .await
+                    .map_err(|source| QueryStoreError::Io {
+                        path: entry.path.clone(),
+                        source,
+                    })?;
+
+                store.insert(name, Some(entry.path), sql)?;
+            }
+        }
+
+        Ok(store)
+    }
+
+    /// Build a store from `(name, SQL)` pairs, e.g. of files embedded with `include_str!()`.
+    ///
+    /// The SQL is parsed as the contents of a `.sql` file, with its front-matter.
+    pub fn from_files<N, S>(
+        files: impl IntoIterator<Item = (N, S)>,
+    ) -> Result<Self, QueryStoreError>
+    where
+        N: Into<String>,
+        S: Into<String>,
+    {
+        let mut store = QueryStore::default();
+
+        for (name, sql) in files {
+            store.insert(name.into(), None, sql.into())?;
+        }
+
+        Ok(store)
+    }
+
+    fn insert(
+        &mut self,
+        name: String,
+        path: Option<PathBuf>,
+        sql: String,
+    ) -> Result<(), QueryStoreError> {
+        if self.queries.contains_key(&name) {
+            return Err(QueryStoreError::Duplicate(name));
+        }
+
+        let params = parse_front_matter(&name, &sql)?;
+
+        self.queries.insert(
+            name.clone(),
+            StoredQuery {
+                name,
+                path,
+                params,
+                // shared by the queries built from it rather than copied
+                sql: AssertSqlSafe(Arc::<str>::from(sql)).into_sql_str(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Prepare every query on a connection from `conn`, checking that its SQL is valid and
+    /// that it takes the parameters declared in its front-matter.
+    ///
+    /// The number of parameters is always checked. Their types are checked when both the
+    /// front-matter declares them and the driver reports them, comparing the declared type
+    /// case-insensitively with the [name][TypeInfo::name] of the type, such as `INT8` or
+    /// `TEXT` for Postgres.
+    ///
+    /// The first query which fails is returned as an error.
+    pub async fn validate<'a, A, DB>(&self, conn: A) -> Result<(), QueryStoreError>
+    where
+        A: Acquire<'a, Database = DB>,
+        DB: Database,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        for stored in self.queries.values() {
+            let statement = (&mut *conn)
+                .prepare(stored.sql.clone())
+                .await
+                .map_err(|source| QueryStoreError::Prepare {
+                    name: stored.name.clone(),
+                    source,
+                })?;
+
+            stored.check_parameters(statement.parameters())?;
+        }
+
+        Ok(())
+    }
+
+    /// A query of the named stored query, to bind its parameters to and execute.
+    ///
+    /// ### Panics
+    /// If there is no query named `name`; use [`Self::get()`] if the name may be missing.
+    pub fn query<'q, DB: Database>(&self, name: &str) -> Query<'q, DB, DB::Arguments> {
+        match self.get(name) {
+            Some(stored) => stored.query(),
+            None => panic!("no query named {name:?} in the query store"),
+        }
+    }
+
+    /// The query named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&StoredQuery> {
+        self.queries.get(name)
+    }
+
+    /// The queries of the store, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = &StoredQuery> + '_ {
+        self.queries.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+}
+
+impl StoredQuery {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The file the query was loaded from, if it was loaded from a directory.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The parameters declared in the front-matter, in the order they are bound.
+    pub fn params(&self) -> &[QueryParam] {
+        &self.params
+    }
+
+    /// The contents of the file, front-matter included.
+    pub fn sql(&self) -> &str {
+        self.sql.as_str()
+    }
+
+    /// A query of this SQL, to bind its parameters to and execute.
+    pub fn query<'q, DB: Database>(&self) -> Query<'q, DB, DB::Arguments> {
+        query(self.sql.clone())
+    }
+
+    fn check_parameters<T: TypeInfo>(
+        &self,
+        parameters: Option<Either<&[T], usize>>,
+    ) -> Result<(), QueryStoreError> {
+        let actual = match parameters {
+            Some(Either::Left(types)) => types.len(),
+            Some(Either::Right(count)) => count,
+            None => return Ok(()),
+        };
+
+        if actual != self.params.len() {
+            return Err(QueryStoreError::ParameterCount {
+                name: self.name.clone(),
+                declared: self.params.len(),
+                actual,
+            });
+        }
+
+        let Some(Either::Left(types)) = parameters else {
+            return Ok(());
+        };
+
+        for (param, type_info) in self.params.iter().zip(types) {
+            let Some(declared) = &param.type_name else {
+                continue;
+            };
+
+            if !declared.eq_ignore_ascii_case(type_info.name()) {
+                return Err(QueryStoreError::ParameterType {
+                    name: self.name.clone(),
+                    param: param.name.clone(),
+                    declared: declared.clone(),
+                    actual: type_info.name().to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl QueryParam {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The declared type of the parameter, if any.
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
+}
+
+impl<'s> IntoIterator for &'s QueryStore {
+    type Item = &'s StoredQuery;
+    type IntoIter = std::collections::btree_map::Values<'s, String, StoredQuery>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.queries.values()
+    }
+}
+
+/// The path of `file` relative to `root`, without its extension and with `/` separators.
+fn query_name(root: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(root).unwrap_or(file).with_extension("");
+
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn parse_front_matter(name: &str, sql: &str) -> Result<Vec<QueryParam>, QueryStoreError> {
+    let invalid = |message: String| QueryStoreError::FrontMatter {
+        name: name.to_string(),
+        message,
+    };
+
+    let mut params: Vec<QueryParam> = Vec::new();
+
+    for line in sql.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+
+        // the front-matter ends with the first line of SQL
+        let Some(comment) = line.strip_prefix("--") else {
+            break;
+        };
+
+        let Some(("param", param)) = comment
+            .split_once(':')
+            .map(|(key, value)| (key.trim(), value.trim()))
+        else {
+            continue;
+        };
+
+        let (param_name, type_name) = match param.split_once(char::is_whitespace) {
+            Some((param_name, type_name)) => (param_name, Some(type_name.trim().to_string())),
+            None => (param, None),
+        };
+
+        if param_name.is_empty() {
+            return Err(invalid("`param` without a name".into()));
+        }
+
+        if params.iter().any(|param| param.name == param_name) {
+            return Err(invalid(format!(
+                "parameter {param_name} is declared more than once"
+            )));
+        }
+
+        params.push(QueryParam {
+            name: param_name.to_string(),
+            type_name,
+        });
+    }
+
+    Ok(params)
+}
+
+#[test]
+fn it_parses_front_matter() {
+    let store = QueryStore::from_files([(
+        "users/find",
+        "-- Find a user.\n\
+         -- param: email TEXT\n\
+         -- param: score double precision\n\
+         -- param: active\n\
+         \n\
+         SELECT * FROM users -- param: ignored\n\
+         WHERE email = $1 AND score > $2 AND active = $3\n",
+    )])
+    .unwrap();
+
+    let stored = store.get("users/find").unwrap();
+
+    assert_eq!(
+        stored
+            .params()
+            .iter()
+            .map(|param| (param.name(), param.type_name()))
+            .collect::<Vec<_>>(),
+        [
+            ("email", Some("TEXT")),
+            ("score", Some("double precision")),
+            ("active", None)
+        ]
+    );
+
+    assert!(matches!(
+        QueryStore::from_files([("dup", "-- param: a\n-- param: a\nSELECT $1")]),
+        Err(QueryStoreError::FrontMatter { .. })
+    ));
+}
+
+#[test]
+fn it_names_queries_by_path() {
+    assert_eq!(
+        query_name(
+            Path::new("queries"),
+            Path::new("queries/users/find_by_id.sql")
+        ),
+        "users/find_by_id"
+    );
+}
@@ -1,6 +1,9 @@
+use std::fmt::{self, Debug, Formatter};
+
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::StreamExt;
 
 use crate::database::Database;
 use crate::error::BoxDynError;
@@ -158,6 +161,23 @@ impl RawSql {
         executor.fetch(self)
     }
 
+    /// Execute the SQL string and return the generated results as a stream of chunks of
+    /// `chunk_size` rows each; the last chunk may be shorter.
+    ///
+    /// See [`Executor::fetch_chunked()`].
+    #[inline]
+    pub fn fetch_chunked<'e, E, DB>(
+        self,
+        executor: E,
+        chunk_size: usize,
+    ) -> BoxStream<'e, Result<Vec<DB::Row>, Error>>
+    where
+        DB: Database,
+        E: Executor<'e, Database = DB>,
+    {
+        executor.fetch_chunked(self, chunk_size)
+    }
+
     /// Execute the SQL string and return the generated results as a stream.
     ///
     /// For each query in the stream, any generated rows are returned first,
@@ -174,6 +194,86 @@ impl RawSql {
         executor.fetch_many(self)
     }
 
+    /// Execute the SQL string and return the generated results as a stream, each tagged with
+    /// the index of the statement it comes from.
+    ///
+    /// As with [`fetch_many()`][Self::fetch_many], the rows of each statement come first,
+    /// then its `QueryResult`, which marks the end of the statement. An error is tagged with
+    /// the index of the statement which failed; no statement after it is executed.
+    ///
+    /// Statements are counted from zero as the database completes them, so empty statements
+    /// (e.g. `;;`) are not counted.
+    pub fn fetch_statements<'e, E, DB>(
+        self,
+        executor: E,
+    ) -> BoxStream<'e, Result<RawStatementOutput<DB>, RawStatementError>>
+    where
+        DB: Database,
+        E: Executor<'e, Database = DB>,
+    {
+        let mut index = 0;
+
+        executor
+            .fetch_many(self)
+            .map(move |output| match output {
+                Ok(Either::Left(result)) => {
+                    let output = RawStatementOutput {
+                        index,
+                        output: Either::Left(result),
+                    };
+                    index += 1;
+                    Ok(output)
+                }
+                Ok(Either::Right(row)) => Ok(RawStatementOutput {
+                    index,
+                    output: Either::Right(row),
+                }),
+                Err(error) => Err(RawStatementError { index, error }),
+            })
+            .boxed()
+    }
+
+    /// Execute the SQL string. Returns a stream which gives the index of each statement
+    /// in the string and the number of rows it affected, as it completes.
+    ///
+    /// This allows reporting the progress of a long script, such as a dump or a migration,
+    /// and pinpointing the statement which failed. See
+    /// [`fetch_statements()`][Self::fetch_statements] for how statements are counted.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut conn: sqlx::PgConnection, script: String) -> sqlx::Result<()> {
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mut statements = sqlx::raw_sql(sqlx::AssertSqlSafe(script)).execute_statements(&mut conn);
+    ///
+    /// while let Some((index, result)) = statements.try_next().await? {
+    ///     println!("statement {index}: {} rows affected", result.rows_affected());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_statements<'e, E, DB>(
+        self,
+        executor: E,
+    ) -> BoxStream<'e, Result<(usize, DB::QueryResult), RawStatementError>>
+    where
+        DB: Database,
+        E: Executor<'e, Database = DB>,
+    {
+        self.fetch_statements(executor)
+            .filter_map(|output| async move {
+                match output {
+                    Ok(RawStatementOutput {
+                        index,
+                        output: Either::Left(result),
+                    }) => Some(Ok((index, result))),
+                    Ok(_) => None,
+                    Err(error) => Some(Err(error)),
+                }
+            })
+            .boxed()
+    }
+
     /// Execute the SQL string and return all the resulting rows collected into a [`Vec`].
     ///
     /// ### Note: beware result set size.
@@ -232,3 +332,43 @@ impl RawSql {
         executor.fetch_optional(self).await
     }
 }
+
+/// A row or the result of a statement of a [`RawSql`], as returned by
+/// [`RawSql::fetch_statements()`].
+pub struct RawStatementOutput<DB: Database> {
+    /// The index of the statement in the SQL string, counting from zero.
+    pub index: usize,
+    /// A row returned by the statement, or once it has completed, its result.
+    pub output: Either<DB::QueryResult, DB::Row>,
+}
+
+impl<DB: Database> Debug for RawStatementOutput<DB>
+where
+    DB::QueryResult: Debug,
+    DB::Row: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawStatementOutput")
+            .field("index", &self.index)
+            .field("output", &self.output)
+            .finish()
+    }
+}
+
+/// An error returned while executing a statement of a [`RawSql`].
+#[derive(Debug, thiserror::Error)]
+#[error("error in statement {index} of the SQL string: {error}")]
+pub struct RawStatementError {
+    /// The index of the statement which failed, counting from zero.
+    pub index: usize,
+    /// The error it failed with.
+    #[source]
+    pub error: Error,
+}
+
+/// Discards the index of the statement, so `?` works in functions returning [`Error`].
+impl From<RawStatementError> for Error {
+    fn from(error: RawStatementError) -> Self {
+        error.error
+    }
+}
@@ -12,6 +12,22 @@ pub mod rt_async_io;
 #[cfg(feature = "_rt-tokio")]
 pub mod rt_tokio;
 
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub mod rt_io_uring;
+
+pub(crate) mod runtime;
+
+pub use runtime::{set_runtime, Runtime, RuntimeAlreadySet};
+
+#[cfg(feature = "_rt-async-io")]
+pub use rt_async_io::AsyncIo;
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub use rt_io_uring::IoUring;
+
+#[cfg(feature = "_rt-tokio")]
+pub use rt_tokio::Tokio;
+
 #[derive(Debug, thiserror::Error)]
 #[error("operation timed out")]
 pub struct TimeoutError;
@@ -27,6 +43,9 @@ pub enum JoinHandle<T> {
     #[cfg(feature = "_rt-async-task")]
     AsyncTask(Option<async_task::Task<T>>),
 
+    // A task of a runtime set with `set_runtime()`, which sends its output
+    Custom(futures_channel::oneshot::Receiver<T>),
+
     // `PhantomData<T>` requires `T: Unpin`
     _Phantom(PhantomData<fn() -> T>),
 }
@@ -35,6 +54,10 @@ pub async fn timeout<F: Future>(duration: Duration, f: F) -> Result<F::Output, T
     #[cfg(debug_assertions)]
     let f = Box::pin(f);
 
+    if let Some(runtime) = runtime::custom() {
+        return runtime::timeout(runtime, duration, f).await;
+    }
+
     #[cfg(feature = "_rt-tokio")]
     if rt_tokio::available() {
         return tokio::time::timeout(duration, f)
@@ -52,6 +75,10 @@ pub async fn timeout<F: Future>(duration: Duration, f: F) -> Result<F::Output, T
 }
 
 pub async fn sleep(duration: Duration) {
+    if let Some(runtime) = runtime::custom() {
+        return runtime::sleep(runtime, duration).await;
+    }
+
     #[cfg(feature = "_rt-tokio")]
     if rt_tokio::available() {
         return tokio::time::sleep(duration).await;
@@ -72,18 +99,18 @@ where
     F: Future + Send + 'static,
     F::Output: Send + 'static,
 {
+    if let Some(runtime) = runtime::custom() {
+        return runtime::spawn(runtime, fut);
+    }
+
     #[cfg(feature = "_rt-tokio")]
     if let Ok(handle) = tokio::runtime::Handle::try_current() {
         return JoinHandle::Tokio(handle.spawn(fut));
     }
 
     cfg_if! {
-        if #[cfg(feature = "_rt-async-global-executor")] {
-            JoinHandle::AsyncTask(Some(async_global_executor::spawn(fut)))
-        } else if #[cfg(feature = "_rt-smol")] {
-            JoinHandle::AsyncTask(Some(smol::spawn(fut)))
-        } else if #[cfg(feature = "_rt-async-std")] {
-            JoinHandle::AsyncStd(async_std::task::spawn(fut))
+        if #[cfg(feature = "_rt-async-io")] {
+            rt_async_io::spawn(fut)
         } else {
             missing_rt(fut)
         }
@@ -96,18 +123,18 @@ where
     F: FnOnce() -> R + Send + 'static,
     R: Send + 'static,
 {
+    if let Some(runtime) = runtime::custom() {
+        return runtime::spawn_blocking(runtime, f);
+    }
+
     #[cfg(feature = "_rt-tokio")]
     if let Ok(handle) = tokio::runtime::Handle::try_current() {
         return JoinHandle::Tokio(handle.spawn_blocking(f));
     }
 
     cfg_if! {
-        if #[cfg(feature = "_rt-async-global-executor")] {
-            JoinHandle::AsyncTask(Some(async_global_executor::spawn_blocking(f)))
-        } else if #[cfg(feature = "_rt-smol")] {
-            JoinHandle::AsyncTask(Some(smol::unblock(f)))
-        } else if #[cfg(feature = "_rt-async-std")] {
-            JoinHandle::AsyncStd(async_std::task::spawn_blocking(f))
+        if #[cfg(feature = "_rt-async-io")] {
+            rt_async_io::spawn_blocking(f)
         } else {
             missing_rt(f)
         }
@@ -116,7 +143,7 @@ where
 
 pub async fn yield_now() {
     #[cfg(feature = "_rt-tokio")]
-    if rt_tokio::available() {
+    if runtime::custom().is_none() && rt_tokio::available() {
         return tokio::task::yield_now().await;
     }
 
@@ -166,7 +193,7 @@ pub const fn missing_rt<T>(_unused: T) -> ! {
         panic!("this functionality requires a Tokio context")
     }
 
-    panic!("one of the `runtime` features of SQLx must be enabled")
+    panic!("one of the `runtime` features of SQLx must be enabled, or a runtime set with `sqlx::rt::set_runtime()`")
 }
 
 impl<T: Send + 'static> Future for JoinHandle<T> {
@@ -189,6 +216,10 @@ impl<T: Send + 'static> Future for JoinHandle<T> {
                 .poll(cx)
                 .map(|res| res.expect("spawned task panicked")),
 
+            Self::Custom(receiver) => Pin::new(receiver)
+                .poll(cx)
+                .map(|res| res.expect("spawned task panicked or was dropped by the runtime")),
+
             Self::_Phantom(_) => {
                 let _ = cx;
                 unreachable!("runtime should have been checked on spawn")
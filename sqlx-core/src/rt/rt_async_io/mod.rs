@@ -1,4 +1,98 @@
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use cfg_if::cfg_if;
+use futures_core::future::BoxFuture;
+
+use crate::net::Socket;
+use crate::rt::{JoinHandle, Runtime};
+
 mod socket;
 
 mod timeout;
 pub use timeout::*;
+
+/// The runtime selected by the `runtime-async-std`, `runtime-smol` or
+/// `runtime-async-global-executor` feature, built on `async-io`.
+///
+/// SQLx uses it by default outside of a Tokio runtime; set it with
+/// [`set_runtime()`][crate::rt::set_runtime] to use it even within one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsyncIo;
+
+impl Runtime for AsyncIo {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        // dropping the `JoinHandle` detaches the task
+        drop(spawn(future));
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send + 'static>) {
+        drop(spawn_blocking(f));
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        Box::pin(sleep(duration))
+    }
+
+    fn connect_tcp(
+        &self,
+        host: &str,
+        port: u16,
+    ) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        let host = host.to_owned();
+
+        Box::pin(async move {
+            let socket = crate::net::connect_tcp_async_io(&host, port).await?;
+            Ok(Box::new(socket) as Box<dyn Socket>)
+        })
+    }
+
+    #[cfg(unix)]
+    fn connect_uds(
+        &self,
+        path: &std::path::Path,
+    ) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        let path = path.to_owned();
+
+        Box::pin(async move {
+            let stream = async_io::Async::<std::os::unix::net::UnixStream>::connect(path).await?;
+            Ok(Box::new(stream) as Box<dyn Socket>)
+        })
+    }
+    fn wrap_std_tcp(&self, stream: std::net::TcpStream) -> io::Result<Box<dyn Socket>> {
+        Ok(Box::new(async_io::Async::new(stream)?))
+    }
+}
+
+pub(crate) fn spawn<F>(fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    cfg_if! {
+        if #[cfg(feature = "_rt-async-global-executor")] {
+            JoinHandle::AsyncTask(Some(async_global_executor::spawn(fut)))
+        } else if #[cfg(feature = "_rt-smol")] {
+            JoinHandle::AsyncTask(Some(smol::spawn(fut)))
+        } else {
+            JoinHandle::AsyncStd(async_std::task::spawn(fut))
+        }
+    }
+}
+
+pub(crate) fn spawn_blocking<F, R>(f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    cfg_if! {
+        if #[cfg(feature = "_rt-async-global-executor")] {
+            JoinHandle::AsyncTask(Some(async_global_executor::spawn_blocking(f)))
+        } else if #[cfg(feature = "_rt-smol")] {
+            JoinHandle::AsyncTask(Some(smol::unblock(f)))
+        } else {
+            JoinHandle::AsyncStd(async_std::task::spawn_blocking(f))
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::net::Socket;
 
 use std::io;
-use std::io::{Read, Write};
+use std::io::{IoSlice, Read, Write};
 use std::net::{Shutdown, TcpStream};
 use std::task::{Context, Poll};
 
@@ -18,6 +18,10 @@ impl Socket for Async<TcpStream> {
         self.get_ref().write(buf)
     }
 
+    fn try_write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.get_ref().write_vectored(bufs)
+    }
+
     fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         self.poll_readable(cx)
     }
@@ -41,6 +45,10 @@ impl Socket for Async<std::os::unix::net::UnixStream> {
         self.get_ref().write(buf)
     }
 
+    fn try_write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.get_ref().write_vectored(bufs)
+    }
+
     fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         self.poll_readable(cx)
     }
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::{iter, mem};
+
+use io_uring::{opcode, squeue, types};
+
+/// The `user_data` of the poll on the socket which wakes the driver thread.
+const WAKE: u64 = u64::MAX;
+
+/// A ring and the thread which submits its operations and completes them.
+///
+/// The thread runs until the last handle is dropped and the operations in flight complete.
+pub(super) struct Driver {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    // written to wake the thread when it is parked in `io_uring_enter()`
+    wake_tx: UnixStream,
+    wake_rx: UnixStream,
+}
+
+struct State {
+    queue: Vec<(squeue::Entry, Arc<Operation>)>,
+    parked: bool,
+    closed: bool,
+    // the `errno` which stopped the thread, if any
+    error: Option<i32>,
+}
+
+/// The socket an operation reads from or writes to.
+pub(super) type Fd = Arc<dyn AsRawFd + Send + Sync>;
+
+/// A read or write submitted to the ring, which owns its buffer and its socket until it
+/// completes.
+pub(super) struct Operation {
+    state: Mutex<OperationState>,
+}
+
+struct OperationState {
+    buf: Vec<u8>,
+    // keeps the file descriptor open, so its number cannot be reused by another socket while
+    // the operation is queued or in flight
+    fd: Option<Fd>,
+    result: Option<i32>,
+    waker: Option<Waker>,
+}
+
+impl Driver {
+    pub(super) fn start(entries: u32) -> io::Result<Arc<Self>> {
+        let ring = io_uring::IoUring::new(entries)?;
+
+        let (wake_tx, wake_rx) = UnixStream::pair()?;
+        wake_tx.set_nonblocking(true)?;
+        wake_rx.set_nonblocking(true)?;
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State {
+                queue: Vec::new(),
+                parked: false,
+                closed: false,
+                error: None,
+            }),
+            wake_tx,
+            wake_rx,
+        });
+
+        std::thread::Builder::new()
+            .name("sqlx-io-uring".into())
+            .spawn({
+                let shared = shared.clone();
+                move || shared.run(ring)
+            })?;
+
+        Ok(Arc::new(Driver { shared }))
+    }
+
+    /// Receive from `fd` into `buf`, up to its length.
+    pub(super) fn recv(&self, fd: Fd, buf: Vec<u8>) -> Arc<Operation> {
+        self.submit(fd, buf, |fd, buf| {
+            opcode::Recv::new(types::Fd(fd), buf.as_mut_ptr(), len_u32(buf.len())).build()
+        })
+    }
+
+    /// Send `buf[offset..]` to `fd`.
+    pub(super) fn send(&self, fd: Fd, buf: Vec<u8>, offset: usize) -> Arc<Operation> {
+        self.submit(fd, buf, |fd, buf| {
+            let buf = &buf[offset..];
+
+            opcode::Send::new(types::Fd(fd), buf.as_ptr(), len_u32(buf.len()))
+                .flags(libc::MSG_NOSIGNAL)
+                .build()
+        })
+    }
+
+    fn submit(
+        &self,
+        fd: Fd,
+        buf: Vec<u8>,
+        entry: impl FnOnce(RawFd, &mut [u8]) -> squeue::Entry,
+    ) -> Arc<Operation> {
+        let raw_fd = fd.as_raw_fd();
+
+        let op = Arc::new(Operation {
+            state: Mutex::new(OperationState {
+                buf,
+                fd: Some(fd),
+                result: None,
+                waker: None,
+            }),
+        });
+
+        // the buffer is kept in place and left alone until the operation completes
+        let entry = entry(raw_fd, &mut op.lock().buf);
+
+        let mut state = self.shared.lock();
+
+        if state.closed {
+            let errno = state.error.unwrap_or(libc::ECANCELED);
+            drop(state);
+
+            op.complete(-errno);
+            return op;
+        }
+
+        state.queue.push((entry, op.clone()));
+
+        if mem::take(&mut state.parked) {
+            drop(state);
+            self.shared.wake();
+        }
+
+        op
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        self.shared.lock().closed = true;
+        self.shared.wake();
+    }
+}
+
+impl Debug for Driver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Driver").finish_non_exhaustive()
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .expect("BUG: panicked while holding a lock")
+    }
+
+    fn wake(&self) {
+        // fails only if the socket is full, in which case the thread is woken anyway
+        let _ = (&self.wake_tx).write(&[1]);
+    }
+
+    /// Put back entries which were taken from the queue but not pushed to the ring, for
+    /// `run()` to fail them.
+    fn requeue(&self, entries: impl IntoIterator<Item = (squeue::Entry, Arc<Operation>)>) {
+        let mut state = self.lock();
+        let queued = mem::take(&mut state.queue);
+        state.queue = entries.into_iter().chain(queued).collect();
+    }
+
+    fn run(&self, mut ring: io_uring::IoUring) {
+        let mut in_flight = HashMap::new();
+
+        if let Err(error) = self.drive(&mut ring, &mut in_flight) {
+            tracing::error!(%error, "io_uring driver failed");
+
+            let errno = error.raw_os_error().unwrap_or(libc::EIO);
+
+            let queue = {
+                let mut state = self.lock();
+                state.closed = true;
+                state.error = Some(errno);
+                mem::take(&mut state.queue)
+            };
+
+            for (_, op) in queue {
+                op.complete(-errno);
+            }
+
+            // the kernel may still use the buffers and sockets of submitted operations
+            for op in in_flight.into_values() {
+                let mut state = op.lock();
+                mem::forget(mem::take(&mut state.buf));
+                mem::forget(state.fd.take());
+                drop(state);
+
+                op.complete(-errno);
+            }
+        }
+    }
+
+    fn drive(
+        &self,
+        ring: &mut io_uring::IoUring,
+        in_flight: &mut HashMap<u64, Arc<Operation>>,
+    ) -> io::Result<()> {
+        let mut next_key = 0u64;
+        let mut wake_armed = false;
+
+        loop {
+            let (queue, closed) = {
+                let mut state = self.lock();
+                let queue = mem::take(&mut state.queue);
+
+                // nothing to submit, so wait for a completion or to be woken
+                state.parked = queue.is_empty();
+
+                (queue, state.closed)
+            };
+
+            if closed && queue.is_empty() && in_flight.is_empty() {
+                return Ok(());
+            }
+
+            if !wake_armed {
+                let poll = opcode::PollAdd::new(
+                    types::Fd(self.wake_rx.as_raw_fd()),
+                    u32::from(libc::POLLIN.unsigned_abs()),
+                )
+                .build()
+                .user_data(WAKE);
+
+                if let Err(e) = push(ring, &poll) {
+                    self.requeue(queue);
+                    return Err(e);
+                }
+                wake_armed = true;
+            }
+
+            let parked = queue.is_empty();
+
+            let mut queue = queue.into_iter();
+            while let Some((entry, op)) = queue.next() {
+                let key = next_key;
+                next_key = next_key.wrapping_add(1) % WAKE;
+
+                let entry = entry.user_data(key);
+
+                if let Err(e) = push(ring, &entry) {
+                    self.requeue(iter::once((entry, op)).chain(queue));
+                    return Err(e);
+                }
+                in_flight.insert(key, op);
+            }
+
+            let submitted = if parked {
+                ring.submit_and_wait(1)
+            } else {
+                ring.submit()
+            };
+
+            match submitted {
+                Ok(_) => {}
+                // interrupted by a signal, or the completion queue is full
+                Err(e) if matches!(e.raw_os_error(), Some(libc::EINTR | libc::EBUSY)) => {}
+                Err(e) => return Err(e),
+            }
+
+            for cqe in ring.completion() {
+                if cqe.user_data() == WAKE {
+                    let mut buf = [0u8; 64];
+                    while matches!((&self.wake_rx).read(&mut buf), Ok(1..)) {}
+
+                    wake_armed = false;
+                } else if let Some(op) = in_flight.remove(&cqe.user_data()) {
+                    op.complete(cqe.result());
+                }
+            }
+        }
+    }
+}
+
+impl Operation {
+    fn lock(&self) -> MutexGuard<'_, OperationState> {
+        self.state
+            .lock()
+            .expect("BUG: panicked while holding a lock")
+    }
+
+    fn complete(&self, result: i32) {
+        let (waker, fd) = {
+            let mut state = self.lock();
+            state.result = Some(result);
+            (state.waker.take(), state.fd.take())
+        };
+
+        // the kernel is done with the socket
+        drop(fd);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Poll for the number of bytes transferred.
+    pub(super) fn poll_complete(&self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let mut state = self.lock();
+
+        let Some(result) = state.result else {
+            state.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        };
+
+        Poll::Ready(match usize::try_from(result) {
+            Ok(len) => Ok(len),
+            Err(_) => Err(io::Error::from_raw_os_error(-result)),
+        })
+    }
+
+    /// Take back the buffer of a completed operation.
+    pub(super) fn take_buf(&self) -> Vec<u8> {
+        let mut state = self.lock();
+        debug_assert!(state.result.is_some());
+        mem::take(&mut state.buf)
+    }
+}
+
+#[allow(unsafe_code)]
+fn push(ring: &mut io_uring::IoUring, entry: &squeue::Entry) -> io::Result<()> {
+    // SAFETY: the buffers of the entries are owned by their operations, which are kept in
+    // flight until they complete
+    while unsafe { ring.submission().push(entry) }.is_err() {
+        // full: hand the entries to the kernel to make room
+        ring.submit()?;
+    }
+
+    Ok(())
+}
+
+fn len_u32(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+#[test]
+fn operations_keep_their_socket_open() {
+    use std::future::poll_fn;
+
+    // io_uring may be unavailable, e.g. denied in a container
+    let Ok(driver) = Driver::start(8) else {
+        return;
+    };
+
+    let (stream, mut peer) = UnixStream::pair().unwrap();
+    let stream = Arc::new(stream);
+    let weak = Arc::downgrade(&stream);
+
+    let op = driver.recv(stream, vec![0; 16]);
+
+    // the socket is only referenced by the operation in flight
+    assert!(weak.upgrade().is_some());
+
+    peer.write_all(b"hello").unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let received = runtime
+        .block_on(poll_fn(|cx| op.poll_complete(cx)))
+        .unwrap();
+
+    assert_eq!(&op.take_buf()[..received], b"hello");
+    assert!(weak.upgrade().is_none());
+}
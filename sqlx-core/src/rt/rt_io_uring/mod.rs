@@ -0,0 +1,127 @@
+use std::io;
+use std::net::TcpStream;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_channel::oneshot;
+use futures_core::future::BoxFuture;
+
+use crate::net::Socket;
+use crate::rt::Runtime;
+
+use self::driver::Driver;
+use self::socket::{Stream, UringSocket};
+
+mod driver;
+mod socket;
+
+/// A [`Runtime`] which does the network IO of SQLx through [io_uring], and everything else
+/// on another runtime.
+///
+/// Reads and writes of sockets are submitted to a ring shared by every connection and
+/// completed by a thread of its own, which saves the readiness polling and many of the
+/// system calls of the other runtimes when connections are busy.
+///
+/// Sockets are connected with blocking calls on
+/// [`spawn_blocking()`][Runtime::spawn_blocking]. Tasks and timers are those of the inner
+/// runtime.
+///
+/// Requires Linux 5.6 or newer; install it with [`set_runtime()`][crate::rt::set_runtime]:
+///
+/// ```rust,ignore
+/// let runtime = sqlx::rt::IoUring::new(sqlx::rt::Tokio::current())?;
+/// sqlx::rt::set_runtime(runtime).expect("runtime was already set");
+/// ```
+///
+/// [io_uring]: https://man7.org/linux/man-pages/man7/io_uring.7.html
+#[derive(Debug)]
+pub struct IoUring<R> {
+    runtime: R,
+    driver: Arc<Driver>,
+}
+
+impl<R: Runtime> IoUring<R> {
+    /// Submit the network IO to a ring of 256 entries, running everything else on `runtime`.
+    ///
+    /// Returns an error if io_uring is not supported, e.g. by an older kernel or in a
+    /// container which denies it.
+    pub fn new(runtime: R) -> io::Result<Self> {
+        Self::with_entries(runtime, 256)
+    }
+
+    /// Submit the network IO to a ring of `entries` entries, a power of two.
+    ///
+    /// A ring holds the operations submitted at once, not those in flight; more entries
+    /// allow larger batches when many connections are busy.
+    pub fn with_entries(runtime: R, entries: u32) -> io::Result<Self> {
+        Ok(IoUring {
+            runtime,
+            driver: Driver::start(entries)?,
+        })
+    }
+
+    /// The runtime tasks and timers run on.
+    pub fn runtime(&self) -> &R {
+        &self.runtime
+    }
+
+    fn connect<F>(&self, connect: F) -> BoxFuture<'static, io::Result<Box<dyn Socket>>>
+    where
+        F: FnOnce() -> io::Result<Stream> + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+
+        self.runtime.spawn_blocking(Box::new(move || {
+            let _ = tx.send(connect());
+        }));
+
+        let driver = self.driver.clone();
+
+        Box::pin(async move {
+            let stream = rx.await.map_err(|_| {
+                io::Error::new(io::ErrorKind::Interrupted, "connect task was cancelled")
+            })??;
+
+            Ok(Box::new(UringSocket::new(stream, driver)) as Box<dyn Socket>)
+        })
+    }
+}
+
+impl<R: Runtime> Runtime for IoUring<R> {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.runtime.spawn(future);
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send + 'static>) {
+        self.runtime.spawn_blocking(f);
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        self.runtime.sleep(duration)
+    }
+
+    fn connect_tcp(
+        &self,
+        host: &str,
+        port: u16,
+    ) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        // IPv6 addresses in URLs will be wrapped in brackets and the `url` crate doesn't trim those.
+        let host = host.trim_matches(&['[', ']'][..]).to_owned();
+
+        self.connect(move || Ok(Stream::Tcp(TcpStream::connect((host, port))?)))
+    }
+
+    fn connect_uds(&self, path: &Path) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        let path = path.to_owned();
+
+        self.connect(move || Ok(Stream::Unix(UnixStream::connect(path)?)))
+    }
+    fn wrap_std_tcp(&self, stream: TcpStream) -> io::Result<Box<dyn Socket>> {
+        Ok(Box::new(UringSocket::new(
+            Stream::Tcp(stream),
+            self.driver.clone(),
+        )))
+    }
+}
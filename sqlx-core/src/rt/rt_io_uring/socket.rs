@@ -0,0 +1,286 @@
+use std::cmp;
+use std::io;
+use std::mem;
+use std::net::{Shutdown, TcpStream};
+use std::os::fd::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll, Waker};
+
+use super::driver::{Driver, Operation};
+use crate::io::ReadBuf;
+use crate::net::Socket;
+
+/// The most bytes read or written by one operation.
+const BUF_SIZE: usize = 16 * 1024;
+
+/// A socket whose reads and writes are submitted to a ring.
+///
+/// `Socket` is readiness-based, so a read is only submitted once `try_read()` finds nothing
+/// buffered, and `try_write()` copies the bytes into a buffer of its own to hand them to the
+/// kernel, returning before they are sent; a failed send is returned by the next write or
+/// flush.
+pub(super) struct UringSocket {
+    // shared with the operations in flight, which close it if they outlive the socket
+    stream: Arc<Stream>,
+    driver: Arc<Driver>,
+    read: Read,
+    write: Option<Write>,
+    // the buffers of the last completed operations, to be reused
+    read_buf: Vec<u8>,
+    write_buf: Vec<u8>,
+}
+
+/// A connected socket, only used for its file descriptor and to shut it down.
+pub(super) enum Stream {
+    Tcp(TcpStream),
+    Unix(UnixStream),
+}
+
+enum Read {
+    Idle,
+    Pending(Arc<Operation>),
+    Ready {
+        buf: Vec<u8>,
+        pos: usize,
+        end: usize,
+    },
+}
+
+struct Write {
+    op: Arc<Operation>,
+    // bytes of the buffer sent before this operation
+    offset: usize,
+}
+
+impl UringSocket {
+    pub(super) fn new(stream: Stream, driver: Arc<Driver>) -> Self {
+        UringSocket {
+            stream: Arc::new(stream),
+            driver,
+            read: Read::Idle,
+            write: None,
+            read_buf: Vec::new(),
+            write_buf: Vec::new(),
+        }
+    }
+
+    fn start_read(&mut self) {
+        let mut buf = mem::take(&mut self.read_buf);
+        buf.resize(BUF_SIZE, 0);
+
+        self.read = Read::Pending(self.driver.recv(self.stream.clone(), buf));
+    }
+
+    /// Poll for the write in flight, if any, to be sent in full.
+    fn poll_write_done(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while let Some(write) = &mut self.write {
+            let result = ready!(write.op.poll_complete(cx));
+            let buf = write.op.take_buf();
+
+            let offset = match result {
+                Ok(0) => Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => Ok(write.offset + n),
+                Err(e) => Err(e),
+            };
+
+            match offset {
+                Ok(offset) if offset < buf.len() => {
+                    write.op = self.driver.send(self.stream.clone(), buf, offset);
+                    write.offset = offset;
+                }
+                Ok(_) => {
+                    self.write = None;
+                    self.write_buf = buf;
+                }
+                Err(e) => {
+                    self.write = None;
+                    self.write_buf = buf;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Socket for UringSocket {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        if let Read::Pending(op) = &self.read {
+            let result = op.poll_complete(&mut Context::from_waker(Waker::noop()));
+
+            let Poll::Ready(result) = result else {
+                return Err(io::ErrorKind::WouldBlock.into());
+            };
+
+            let data = op.take_buf();
+
+            match result {
+                Ok(end) => {
+                    self.read = Read::Ready {
+                        buf: data,
+                        pos: 0,
+                        end,
+                    }
+                }
+                Err(e) => {
+                    self.read = Read::Idle;
+                    self.read_buf = data;
+                    return Err(e);
+                }
+            }
+        }
+
+        let Read::Ready {
+            buf: data,
+            pos,
+            end,
+        } = &mut self.read
+        else {
+            self.start_read();
+            return Err(io::ErrorKind::WouldBlock.into());
+        };
+
+        let n = cmp::min(*end - *pos, buf.chunk_mut().len());
+        buf.put_slice(&data[*pos..*pos + n]);
+        *pos += n;
+
+        // consumed, or at the end of the stream
+        if *pos == *end {
+            if let Read::Ready { buf: data, .. } = mem::replace(&mut self.read, Read::Idle) {
+                self.read_buf = data;
+            }
+        }
+
+        Ok(n)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.poll_write_done(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result?,
+            Poll::Pending => return Err(io::ErrorKind::WouldBlock.into()),
+        }
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let len = cmp::min(buf.len(), BUF_SIZE);
+
+        let mut data = mem::take(&mut self.write_buf);
+        data.clear();
+        data.extend_from_slice(&buf[..len]);
+
+        self.write = Some(Write {
+            op: self.driver.send(self.stream.clone(), data, 0),
+            offset: 0,
+        });
+
+        Ok(len)
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            match &self.read {
+                Read::Idle => self.start_read(),
+                // an error is returned by `try_read()`
+                Read::Pending(op) => {
+                    let _ = ready!(op.poll_complete(cx));
+                    return Poll::Ready(Ok(()));
+                }
+                Read::Ready { .. } => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+
+    fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_write_done(cx)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_write_done(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_done(cx))?;
+
+        Poll::Ready(self.stream.shutdown(Shutdown::Write))
+    }
+}
+
+impl Drop for UringSocket {
+    fn drop(&mut self) {
+        // completes a read in flight, which would otherwise wait for the peer; the operations
+        // keep their buffers and the socket open until then
+        let _ = self.stream.shutdown(Shutdown::Both);
+    }
+}
+
+impl Stream {
+    fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        match self {
+            Stream::Tcp(stream) => stream.shutdown(how),
+            Stream::Unix(stream) => stream.shutdown(how),
+        }
+    }
+}
+
+impl AsRawFd for Stream {
+    fn as_raw_fd(&self) -> RawFd {
+        match self {
+            Stream::Tcp(stream) => stream.as_raw_fd(),
+            Stream::Unix(stream) => stream.as_raw_fd(),
+        }
+    }
+}
+
+#[test]
+fn round_trip_through_the_ring() {
+    use std::io::{Read as _, Write as _};
+
+    // io_uring may be unavailable, e.g. denied in a container
+    let Ok(driver) = Driver::start(8) else {
+        return;
+    };
+
+    let (stream, mut peer) = UnixStream::pair().unwrap();
+    let mut socket = UringSocket::new(Stream::Unix(stream), driver);
+
+    let echo = std::thread::spawn(move || {
+        let mut received = vec![0u8; 100_000];
+        peer.read_exact(&mut received).unwrap();
+        peer.write_all(b"done").unwrap();
+        received
+    });
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let reply = runtime.block_on(async {
+        // more than one operation sends at once
+        let data: Vec<u8> = (0..100_000u32).map(|i| i.to_le_bytes()[0]).collect();
+        let mut written = 0;
+
+        while written < data.len() {
+            written += socket.write(&data[written..]).await.unwrap();
+        }
+
+        socket.flush().await.unwrap();
+
+        let mut reply = bytes::BytesMut::with_capacity(16);
+        while reply.len() < 4 {
+            socket.read(&mut reply).await.unwrap();
+        }
+        reply
+    });
+
+    assert_eq!(&reply[..], b"done");
+
+    let received = echo.join().unwrap();
+    assert!(received
+        .iter()
+        .enumerate()
+        .all(|(i, b)| *b == i.to_le_bytes()[0]));
+}
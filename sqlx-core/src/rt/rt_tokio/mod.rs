@@ -1,5 +1,128 @@
+use std::io;
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use tokio::runtime::Handle;
+
+use crate::net::Socket;
+use crate::rt::Runtime;
+
 mod socket;
 
 pub fn available() -> bool {
     tokio::runtime::Handle::try_current().is_ok()
 }
+
+/// A Tokio runtime, given by its [`Handle`].
+///
+/// SQLx uses the Tokio runtime of the current task by default; set this with
+/// [`set_runtime()`][crate::rt::set_runtime] to always use a given runtime, even from
+/// threads outside of it, such as those of another executor.
+#[derive(Debug, Clone)]
+pub struct Tokio {
+    handle: Handle,
+}
+
+impl Tokio {
+    /// Use the runtime of `handle`.
+    pub fn new(handle: Handle) -> Self {
+        Tokio { handle }
+    }
+
+    /// Use the runtime of the current task.
+    ///
+    /// # Panics
+    /// If called outside of a Tokio runtime.
+    pub fn current() -> Self {
+        Self::new(Handle::current())
+    }
+}
+
+impl Runtime for Tokio {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.handle.spawn(future);
+    }
+
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send + 'static>) {
+        self.handle.spawn_blocking(f);
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+        // the timer is registered with the runtime it is created in
+        let _guard = self.handle.enter();
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn connect_tcp(
+        &self,
+        host: &str,
+        port: u16,
+    ) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        let host = host.to_owned();
+
+        // connect on the runtime, so the socket is registered with its reactor
+        let connect = self
+            .handle
+            .spawn(async move { tokio::net::TcpStream::connect((host, port)).await });
+
+        Box::pin(async move {
+            let stream = connect.await.map_err(io::Error::other)??;
+            Ok(Box::new(stream) as Box<dyn Socket>)
+        })
+    }
+
+    #[cfg(unix)]
+    fn connect_uds(
+        &self,
+        path: &std::path::Path,
+    ) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        let path = path.to_owned();
+
+        let connect = self
+            .handle
+            .spawn(async move { tokio::net::UnixStream::connect(path).await });
+
+        Box::pin(async move {
+            let stream = connect.await.map_err(io::Error::other)??;
+            Ok(Box::new(stream) as Box<dyn Socket>)
+        })
+    }
+    fn wrap_std_tcp(&self, stream: std::net::TcpStream) -> io::Result<Box<dyn Socket>> {
+        stream.set_nonblocking(true)?;
+
+        // register the socket with the reactor of the runtime
+        let _guard = self.handle.enter();
+        Ok(Box::new(tokio::net::TcpStream::from_std(stream)?))
+    }
+}
+
+#[test]
+fn takes_over_a_std_stream() {
+    use std::io::Read as _;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let stream = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut peer, _) = listener.accept().unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_io()
+        .build()
+        .unwrap();
+
+    // outside of the runtime, as from the thread of another executor
+    let mut socket = Tokio::new(runtime.handle().clone())
+        .wrap_std_tcp(stream)
+        .unwrap();
+
+    runtime.block_on(async {
+        let mut written = 0;
+
+        while written < 4 {
+            written += socket.write(&b"ping"[written..]).await.unwrap();
+        }
+    });
+
+    let mut received = [0u8; 4];
+    peer.read_exact(&mut received).unwrap();
+    assert_eq!(&received, b"ping");
+}
@@ -1,4 +1,4 @@
-use std::io;
+use std::io::{self, IoSlice};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
@@ -18,6 +18,10 @@ impl Socket for TcpStream {
         (*self).try_write(buf)
     }
 
+    fn try_write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (*self).try_write_vectored(bufs)
+    }
+
     fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         (*self).poll_read_ready(cx)
     }
@@ -41,6 +45,10 @@ impl Socket for tokio::net::UnixStream {
         (*self).try_write(buf)
     }
 
+    fn try_write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (*self).try_write_vectored(bufs)
+    }
+
     fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         (*self).poll_read_ready(cx)
     }
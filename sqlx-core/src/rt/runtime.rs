@@ -0,0 +1,269 @@
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
+use futures_util::future::{select, Either};
+
+use super::{JoinHandle, TimeoutError};
+use crate::io::ReadBuf;
+use crate::net::Socket;
+
+static RUNTIME: OnceLock<Box<dyn Runtime>> = OnceLock::new();
+
+/// An async runtime for SQLx to spawn tasks, sleep and open sockets on.
+///
+/// SQLx supports Tokio, `async-std`, `smol` and `async-global-executor` through its `runtime-*`
+/// features. To embed SQLx in an application running another executor, implement this trait
+/// for it and install it with [`set_runtime()`]; no `runtime-*` feature is then required.
+///
+/// This is also how SQLx runs on WebAssembly, such as `wasm32-wasip2` in edge runtimes:
+/// [`connect_tcp()`][Self::connect_tcp] opens the connection with WASI sockets or whatever
+/// transport the host provides, and without threads [`spawn_blocking()`][Self::spawn_blocking]
+/// may just call `f`. Socket options of [`TcpOptions`][crate::net::TcpOptions] need
+/// [`wrap_std_tcp()`][Self::wrap_std_tcp], so they cannot be set on these targets.
+///
+/// Timeouts are implemented on top of [`sleep()`][Self::sleep].
+pub trait Runtime: Send + Sync + 'static {
+    /// Spawn `future` as a task detached from the caller.
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+
+    /// Run `f` where it may block, such as on a thread pool for blocking work.
+    fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send + 'static>);
+
+    /// Return a future which completes after `duration`.
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+
+    /// Open a TCP connection to `host` and `port`, trying each address `host` resolves to in
+    /// turn.
+    fn connect_tcp(&self, host: &str, port: u16)
+        -> BoxFuture<'static, io::Result<Box<dyn Socket>>>;
+
+    /// Connect to the Unix domain socket at `path`.
+    ///
+    /// Returns [`io::ErrorKind::Unsupported`] unless overridden.
+    fn connect_uds(&self, path: &Path) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+        let _ = path;
+
+        Box::pin(async {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Unix domain sockets are not supported by this runtime",
+            ))
+        })
+    }
+
+    /// Take over `stream`, which SQLx connected with a blocking call in
+    /// [`spawn_blocking()`][Self::spawn_blocking] to set the socket options of
+    /// [`TcpOptions`][crate::net::TcpOptions] before connecting.
+    ///
+    /// `stream` is in blocking mode. Returns [`io::ErrorKind::Unsupported`] unless overridden,
+    /// failing connections with socket options.
+    fn wrap_std_tcp(&self, stream: std::net::TcpStream) -> io::Result<Box<dyn Socket>> {
+        let _ = stream;
+
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "TCP socket options are not supported by this runtime",
+        ))
+    }
+}
+
+/// Returned by [`set_runtime()`] if a runtime was already set.
+#[derive(Debug, thiserror::Error)]
+#[error("a runtime was already set for SQLx")]
+pub struct RuntimeAlreadySet;
+
+/// Set the runtime SQLx runs on for the rest of the process, in place of the runtime selected
+/// by the `runtime-*` features.
+///
+/// Call it before anything else in SQLx: tasks already spawned and sockets already open stay
+/// on the runtime they were created with. The runtime can only be set once.
+///
+/// ```rust,ignore
+/// // run on this Tokio runtime from any thread, such as one of another executor
+/// sqlx::rt::set_runtime(sqlx::rt::Tokio::new(handle)).expect("runtime was already set");
+/// ```
+pub fn set_runtime(runtime: impl Runtime) -> Result<(), RuntimeAlreadySet> {
+    RUNTIME
+        .set(Box::new(runtime))
+        .map_err(|_| RuntimeAlreadySet)
+}
+
+/// The runtime set with [`set_runtime()`], if any.
+pub(crate) fn custom() -> Option<&'static dyn Runtime> {
+    RUNTIME.get().map(|runtime| &**runtime)
+}
+
+// The functions of `sqlx::rt` on a runtime set with `set_runtime()`, taking it as an argument
+// so they can be tested on a runtime of their own.
+
+pub(crate) fn spawn<F>(runtime: &dyn Runtime, fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let (sender, receiver) = futures_channel::oneshot::channel();
+
+    runtime.spawn(Box::pin(async move {
+        // the `JoinHandle` may have been dropped, detaching the task
+        let _ = sender.send(fut.await);
+    }));
+
+    JoinHandle::Custom(receiver)
+}
+
+pub(crate) fn spawn_blocking<F, R>(runtime: &dyn Runtime, f: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let (sender, receiver) = futures_channel::oneshot::channel();
+
+    runtime.spawn_blocking(Box::new(move || {
+        let _ = sender.send(f());
+    }));
+
+    JoinHandle::Custom(receiver)
+}
+
+pub(crate) async fn sleep(runtime: &dyn Runtime, duration: Duration) {
+    runtime.sleep(duration).await
+}
+
+pub(crate) async fn timeout<F: Future>(
+    runtime: &dyn Runtime,
+    duration: Duration,
+    f: F,
+) -> Result<F::Output, TimeoutError> {
+    match select(pin!(f), runtime.sleep(duration)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(TimeoutError),
+    }
+}
+
+/// A socket opened by a runtime set with [`set_runtime()`].
+///
+/// Passing the `Box<dyn Socket>` to `WithSocket::with_socket()` as is makes rustc fail to
+/// prove the connect futures of the drivers `Send` (a higher-ranked lifetime error), but a
+/// named type is fine.
+pub(crate) struct CustomSocket(pub(crate) Box<dyn Socket>);
+
+impl Socket for CustomSocket {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        self.0.try_read(buf)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.try_write(buf)
+    }
+
+    fn poll_read_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_read_ready(cx)
+    }
+
+    fn poll_write_ready(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_write_ready(cx)
+    }
+
+    fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_flush(cx)
+    }
+
+    fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::{pending, ready};
+    use std::io;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use futures_core::future::BoxFuture;
+    use futures_util::FutureExt;
+
+    use super::Runtime;
+    use crate::net::Socket;
+
+    // Runs tasks as soon as they are spawned, and records the calls made to it.
+    #[derive(Default)]
+    struct Recording {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl Recording {
+        fn record(&self, call: String) {
+            self.calls.lock().unwrap().push(call);
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl Runtime for Recording {
+        fn spawn(&self, future: BoxFuture<'static, ()>) {
+            self.record("spawn".into());
+            future.now_or_never().expect("the task should be ready");
+        }
+
+        fn spawn_blocking(&self, f: Box<dyn FnOnce() + Send + 'static>) {
+            self.record("spawn_blocking".into());
+            f();
+        }
+
+        fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+            self.record(format!("sleep {duration:?}"));
+            // time passes instantly
+            Box::pin(ready(()))
+        }
+
+        fn connect_tcp(
+            &self,
+            _host: &str,
+            _port: u16,
+        ) -> BoxFuture<'static, io::Result<Box<dyn Socket>>> {
+            Box::pin(ready(Err(io::ErrorKind::Unsupported.into())))
+        }
+    }
+
+    #[test]
+    fn tasks_are_spawned_on_the_runtime() {
+        let runtime = Recording::default();
+
+        let handle = super::spawn(&runtime, async { 1 });
+        assert_eq!(handle.now_or_never(), Some(1));
+
+        // a task still runs if its `JoinHandle` is dropped
+        drop(super::spawn(&runtime, async { 2 }));
+
+        let handle = super::spawn_blocking(&runtime, || 3);
+        assert_eq!(handle.now_or_never(), Some(3));
+
+        assert_eq!(runtime.calls(), ["spawn", "spawn", "spawn_blocking"]);
+    }
+
+    #[test]
+    fn timeouts_sleep_on_the_runtime() {
+        let runtime = Recording::default();
+
+        super::sleep(&runtime, Duration::from_secs(1))
+            .now_or_never()
+            .unwrap();
+
+        let finished = super::timeout(&runtime, Duration::from_secs(2), ready(4));
+        assert!(matches!(finished.now_or_never(), Some(Ok(4))));
+
+        let unfinished = super::timeout(&runtime, Duration::from_secs(3), pending::<()>());
+        assert!(matches!(unfinished.now_or_never(), Some(Err(_))));
+
+        assert_eq!(runtime.calls(), ["sleep 1s", "sleep 2s", "sleep 3s"]);
+    }
+}
@@ -0,0 +1,184 @@
+//! [sqlcommenter](https://google.github.io/sqlcommenter/)-style comments, which tag each
+//! statement sent to the database with where it came from.
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+type TraceparentFn = dyn Fn() -> Option<String> + Send + Sync;
+
+// Everything but the unreserved characters of RFC 3986; notably, `'`, `*` and `/` are
+// encoded, so a value can neither end its quotes nor the comment.
+const ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Appends a comment such as `/*application='billing',traceparent='00-...-01'*/` to each
+/// statement, so the statements in the database's logs, `pg_stat_activity` or tools such as
+/// pganalyze can be correlated with the application and the traces it comes from.
+///
+/// The comment holds the [tags][Self::tag] and, if a [provider][Self::traceparent] is set,
+/// the [W3C trace context](https://www.w3.org/TR/trace-context/) of the current trace.
+/// Keys and values are percent-encoded as specified by sqlcommenter, so they cannot end
+/// the comment early or inject SQL.
+///
+/// Statements which already contain a comment are sent as is.
+///
+/// ```rust
+/// use sqlx::SqlCommenter;
+///
+/// let commenter = SqlCommenter::new()
+///     .tag("application", "billing")
+///     .tag("route", "/invoices/{id}")
+///     .traceparent(|| Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".into()));
+///
+/// assert_eq!(
+///     commenter.apply("SELECT * FROM invoices WHERE id = $1").unwrap(),
+///     "SELECT * FROM invoices WHERE id = $1 \
+///      /*application='billing',route='%2Finvoices%2F%7Bid%7D',\
+///      traceparent='00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01'*/",
+/// );
+/// ```
+///
+/// The trace context of a statement differs from one trace to the next, so statements
+/// which carry one are prepared anew each time instead of being cached, as with
+/// [`Query::persistent(false)`][crate::query::Query::persistent].
+#[derive(Clone, Default)]
+pub struct SqlCommenter {
+    tags: Vec<(String, String)>,
+    traceparent: Option<Arc<TraceparentFn>>,
+}
+
+impl SqlCommenter {
+    /// A commenter without tags or trace context, which leaves statements as they are.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a tag to every comment, such as the name of the application or of the framework.
+    ///
+    /// Tags are sorted by key in the comment. Adding a tag with the same key as an existing
+    /// one replaces it.
+    pub fn tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        let value = value.into();
+
+        match self.tags.binary_search_by(|(k, _)| k.as_str().cmp(&key)) {
+            Ok(i) => self.tags[i].1 = value,
+            Err(i) => self.tags.insert(i, (key, value)),
+        }
+
+        self
+    }
+
+    /// Set the function which returns the `traceparent` of the current trace, if any.
+    ///
+    /// It is called each time a statement is executed, in the task which executes it. With
+    /// the `tracing-opentelemetry` crate, for example, it can format the span context of
+    /// `Span::current()`.
+    pub fn traceparent<F>(mut self, traceparent: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static,
+    {
+        self.traceparent = Some(Arc::new(traceparent));
+        self
+    }
+
+    /// Returns `sql` with the comment appended, or `None` if it is to be sent as is: if
+    /// there is nothing to put in the comment, or `sql` already contains a comment.
+    pub fn apply(&self, sql: &str) -> Option<String> {
+        self.apply_with_trace_context(sql).map(|(sql, _)| sql)
+    }
+
+    /// Like [`.apply()`][Self::apply], but also returns whether the comment carries trace
+    /// context, which makes the statement unique to this execution.
+    pub fn apply_with_trace_context(&self, sql: &str) -> Option<(String, bool)> {
+        if sql.trim().is_empty() || sql.contains("/*") || sql.contains("--") {
+            return None;
+        }
+
+        let traceparent = self
+            .traceparent
+            .as_ref()
+            .and_then(|traceparent| traceparent());
+
+        let mut tags = self
+            .tags
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+
+        if let Some(traceparent) = &traceparent {
+            match tags.binary_search_by(|(k, _)| k.cmp(&"traceparent")) {
+                Ok(i) => tags[i].1 = traceparent,
+                Err(i) => tags.insert(i, ("traceparent", traceparent)),
+            }
+        }
+
+        if tags.is_empty() {
+            return None;
+        }
+
+        let comment = tags
+            .iter()
+            .map(|(key, value)| {
+                format!(
+                    "{}='{}'",
+                    utf8_percent_encode(key, ENCODE_SET),
+                    utf8_percent_encode(value, ENCODE_SET)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        // The comment goes before the final semicolon, if any.
+        let trimmed = sql.trim_end();
+        let (statement, semicolon) = match trimmed.strip_suffix(';') {
+            Some(statement) => (statement.trim_end(), ";"),
+            None => (trimmed, ""),
+        };
+
+        Some((
+            format!("{statement} /*{comment}*/{semicolon}"),
+            traceparent.is_some(),
+        ))
+    }
+}
+
+impl Debug for SqlCommenter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqlCommenter")
+            .field("tags", &self.tags)
+            .field("traceparent", &self.traceparent.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+#[test]
+fn comments_are_appended_and_encoded() {
+    let commenter = SqlCommenter::new()
+        .tag("framework", "sqlx")
+        .tag("app name", "it's */ DROP TABLE users; --")
+        .tag("framework", "sqlx-postgres");
+
+    assert_eq!(
+        commenter.apply("SELECT 1;").unwrap(),
+        "SELECT 1 /*app%20name='it%27s%20%2A%2F%20DROP%20TABLE%20users%3B%20--',framework='sqlx-postgres'*/;"
+    );
+
+    assert_eq!(commenter.apply("SELECT 1 /* mine */"), None);
+    assert_eq!(commenter.apply("  "), None);
+    assert_eq!(SqlCommenter::new().apply("SELECT 1"), None);
+
+    let traced = SqlCommenter::new().traceparent(|| Some("00-ab-cd-01".into()));
+    assert_eq!(
+        traced.apply_with_trace_context("SELECT 1"),
+        Some(("SELECT 1 /*traceparent='00-ab-cd-01'*/".into(), true))
+    );
+
+    let untraced = SqlCommenter::new().traceparent(|| None);
+    assert_eq!(untraced.apply("SELECT 1"), None);
+}
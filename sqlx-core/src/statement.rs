@@ -17,6 +17,19 @@ use either::Either;
 ///
 /// Statements can be re-used with any connection and on first-use it will be re-prepared and
 /// cached within the connection.
+///
+/// This makes a statement prepared through a [`Pool`](crate::pool::Pool) a handle which can be
+/// executed on any of its connections: each connection looks the statement up in its cache by
+/// its SQL text, and a connection which has not prepared it yet only needs to parse it again,
+/// as the types of the parameters and columns are already known from the statement.
+///
+/// ```rust,ignore
+/// // once, at startup
+/// let find_user = pool.prepare("SELECT * FROM users WHERE id = $1").await?;
+///
+/// // then, on whichever connection executes it
+/// let user: User = find_user.query_as().bind(user_id).fetch_one(&pool).await?;
+/// ```
 pub trait Statement: Send + Sync + Clone {
     type Database: Database;
 
@@ -0,0 +1,216 @@
+//! Execution statistics of statements, collected by the connections which run them.
+
+use std::cmp::{self, Reverse};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::logger::sanitize_statement;
+use crate::HashMap;
+
+/// The statistics of statements executed by the connections which share this collector,
+/// such as all connections of a pool.
+///
+/// Enabled with [`ConnectOptions::collect_statistics()`], and read with
+/// [`Pool::statistics()`] or from the connection.
+///
+/// Statements are grouped by their normalized text: literals are replaced with `?`, as by
+/// [`sanitize_statement()`], and runs of whitespace with a single space. Each call site of
+/// `query!()`, for instance, is a single statement.
+///
+/// At most `max_statements` statements are tracked. Once that many are, a new statement
+/// replaces the one executed the fewest times, so that memory use stays bounded even if
+/// the application builds statements dynamically.
+///
+/// [`ConnectOptions::collect_statistics()`]: crate::connection::ConnectOptions::collect_statistics
+/// [`Pool::statistics()`]: crate::pool::Pool::statistics
+#[derive(Debug, Clone)]
+pub struct StatementStatistics {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    max_statements: usize,
+    statements: HashMap<String, Counters>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counters {
+    calls: u64,
+    errors: u64,
+    rows_returned: u64,
+    total_time: Duration,
+    max_time: Duration,
+}
+
+/// A snapshot of the statistics of one statement, returned by
+/// [`StatementStatistics::snapshot()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StatementStats {
+    /// The normalized text of the statement.
+    pub statement: String,
+    /// The number of times the statement was executed.
+    pub calls: u64,
+    /// The number of executions which failed.
+    pub errors: u64,
+    /// The total number of rows the statement returned.
+    pub rows_returned: u64,
+    /// The total time spent executing the statement.
+    pub total_time: Duration,
+    /// The longest time an execution of the statement took.
+    pub max_time: Duration,
+}
+
+impl StatementStats {
+    /// The average time an execution of the statement took.
+    pub fn mean_time(&self) -> Duration {
+        if self.calls == 0 {
+            return Duration::ZERO;
+        }
+
+        let nanos = self.total_time.as_nanos() / u128::from(self.calls);
+        Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+    }
+}
+
+impl StatementStatistics {
+    /// Collect the statistics of at most `max_statements` statements.
+    pub fn new(max_statements: usize) -> Self {
+        StatementStatistics {
+            inner: Arc::new(Mutex::new(Inner {
+                max_statements,
+                statements: HashMap::new(),
+            })),
+        }
+    }
+
+    /// The statistics of each statement, from the one with the most total execution time
+    /// to the one with the least.
+    pub fn snapshot(&self) -> Vec<StatementStats> {
+        let inner = self
+            .inner
+            .lock()
+            .expect("BUG: panicked while holding a lock");
+
+        let mut stats = inner
+            .statements
+            .iter()
+            .map(|(statement, counters)| StatementStats {
+                statement: statement.clone(),
+                calls: counters.calls,
+                errors: counters.errors,
+                rows_returned: counters.rows_returned,
+                total_time: counters.total_time,
+                max_time: counters.max_time,
+            })
+            .collect::<Vec<_>>();
+
+        stats.sort_by_key(|stats| Reverse(stats.total_time));
+        stats
+    }
+
+    /// Forget the statistics collected so far.
+    pub fn reset(&self) {
+        self.inner
+            .lock()
+            .expect("BUG: panicked while holding a lock")
+            .statements
+            .clear();
+    }
+
+    pub(crate) fn record(&self, sql: &str, elapsed: Duration, rows_returned: u64, failed: bool) {
+        let statement = normalize(sql);
+
+        let mut inner = self
+            .inner
+            .lock()
+            .expect("BUG: panicked while holding a lock");
+
+        if inner.max_statements == 0 {
+            return;
+        }
+
+        if !inner.statements.contains_key(&statement)
+            && inner.statements.len() >= inner.max_statements
+        {
+            let least_called = inner
+                .statements
+                .iter()
+                .min_by_key(|(_, counters)| counters.calls)
+                .map(|(statement, _)| statement.clone());
+
+            if let Some(least_called) = least_called {
+                inner.statements.remove(&least_called);
+            }
+        }
+
+        let counters = inner.statements.entry(statement).or_default();
+
+        counters.calls += 1;
+        counters.errors += u64::from(failed);
+        counters.rows_returned += rows_returned;
+        counters.total_time += elapsed;
+        counters.max_time = cmp::max(counters.max_time, elapsed);
+    }
+}
+
+fn normalize(sql: &str) -> String {
+    sanitize_statement(sql)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn statistics_are_grouped_and_bounded() {
+    let statistics = StatementStatistics::new(2);
+
+    statistics.record(
+        "SELECT * FROM users WHERE id = 1",
+        Duration::from_millis(10),
+        1,
+        false,
+    );
+    statistics.record(
+        "SELECT *\n  FROM users\n  WHERE id = 2",
+        Duration::from_millis(30),
+        0,
+        true,
+    );
+    statistics.record("DELETE FROM sessions", Duration::from_millis(5), 0, false);
+
+    let stats = statistics.snapshot();
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].statement, "SELECT * FROM users WHERE id = ?");
+    assert_eq!(stats[0].calls, 2);
+    assert_eq!(stats[0].errors, 1);
+    assert_eq!(stats[0].rows_returned, 1);
+    assert_eq!(stats[0].total_time, Duration::from_millis(40));
+    assert_eq!(stats[0].max_time, Duration::from_millis(30));
+    assert_eq!(stats[0].mean_time(), Duration::from_millis(20));
+
+    // the least executed statement makes room for the new one
+    statistics.record(
+        "UPDATE users SET name = $1",
+        Duration::from_millis(1),
+        0,
+        false,
+    );
+
+    let statements = statistics
+        .snapshot()
+        .into_iter()
+        .map(|stats| stats.statement)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        statements,
+        [
+            "SELECT * FROM users WHERE id = ?",
+            "UPDATE users SET name = $1"
+        ]
+    );
+
+    statistics.reset();
+    assert!(statistics.snapshot().is_empty());
+}
@@ -22,7 +22,8 @@ pub trait TestSupport: Database {
     ///   on the database server.
     /// * each invocation results in a different temporary database.
     ///
-    /// The implementation may require `DATABASE_URL` to be set in order to manage databases.
+    /// The implementation may require `DATABASE_URL` to be set in order to manage databases,
+    /// unless it supports starting a container for them when [`TestArgs::container`] is set.
     /// The user credentials it contains must have the privilege to create and drop databases.
     fn test_context(
         args: &TestArgs,
@@ -66,6 +67,10 @@ pub struct TestArgs {
     pub test_path: &'static str,
     pub migrator: Option<&'static Migrator>,
     pub fixtures: &'static [TestFixture],
+    /// Start a container to run the test database in if `DATABASE_URL` is not set.
+    pub container: bool,
+    /// The image of the container, if not the default one of the database.
+    pub container_image: Option<&'static str>,
 }
 
 pub trait TestFn {
@@ -158,6 +163,8 @@ impl TestArgs {
             test_path,
             migrator: None,
             fixtures: &[],
+            container: false,
+            container_image: None,
         }
     }
 
@@ -168,6 +175,11 @@ impl TestArgs {
     pub fn fixtures(&mut self, fixtures: &'static [TestFixture]) {
         self.fixtures = fixtures;
     }
+
+    pub fn container(&mut self, image: Option<&'static str>) {
+        self.container = true;
+        self.container_image = image;
+    }
 }
 
 impl TestTermination for () {
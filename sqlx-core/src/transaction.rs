@@ -1,6 +1,7 @@
 use std::fmt::{self, Debug, Formatter};
 use std::future::{self, Future};
 use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
 use futures_core::future::BoxFuture;
 
@@ -278,6 +279,208 @@ where
     }
 }
 
+/// The isolation level of a transaction, which decides what it can see of concurrent
+/// transactions.
+///
+/// See [`TransactionOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum IsolationLevel {
+    /// Statements may see changes which other transactions have not committed yet.
+    ///
+    /// PostgreSQL treats this as [`ReadCommitted`][Self::ReadCommitted].
+    ReadUncommitted,
+    /// Each statement sees what was committed before it began. This is the default of
+    /// PostgreSQL.
+    ReadCommitted,
+    /// Every statement sees what was committed before the first statement of the
+    /// transaction began.
+    RepeatableRead,
+    /// The transaction behaves as if transactions ran one after the other. A transaction
+    /// which cannot be ordered this way fails with a serialization failure, and should be
+    /// retried.
+    Serializable,
+}
+
+impl IsolationLevel {
+    fn as_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// The characteristics of a new transaction: its isolation level and access mode.
+///
+/// Pass this to [`Connection::begin_with()`] or [`Pool::begin_with()`] to begin the
+/// transaction with a `BEGIN` statement which sets them, instead of issuing
+/// `SET TRANSACTION` after [`begin()`][Connection::begin]. Anything left unset takes the
+/// default of the session.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::{IsolationLevel, TransactionOptions};
+///
+/// let mut tx = pool
+///     .begin_with(
+///         TransactionOptions::new()
+///             .isolation(IsolationLevel::Serializable)
+///             .read_only(true)
+///             .deferrable(true),
+///     )
+///     .await?;
+///
+/// // BEGIN ISOLATION LEVEL SERIALIZABLE, READ ONLY, DEFERRABLE
+/// # tx.commit().await
+/// # }
+/// ```
+///
+/// As with any statement passed to `begin_with()`, an error is returned if the connection is
+/// already in a transaction: the characteristics of a savepoint cannot be changed.
+///
+/// [`Connection::begin_with()`]: crate::connection::Connection::begin_with
+/// [`Connection::begin`]: crate::connection::Connection::begin
+/// [`Pool::begin_with()`]: crate::pool::Pool::begin_with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TransactionOptions {
+    isolation: Option<IsolationLevel>,
+    read_only: Option<bool>,
+    deferrable: Option<bool>,
+}
+
+impl TransactionOptions {
+    /// Options which leave everything at the default of the session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the isolation level of the transaction.
+    pub fn isolation(mut self, level: IsolationLevel) -> Self {
+        self.isolation = Some(level);
+        self
+    }
+
+    /// Begin a `READ ONLY` transaction if `true`, or a `READ WRITE` one if `false`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = Some(read_only);
+        self
+    }
+
+    /// Begin a `DEFERRABLE` transaction if `true`, or a `NOT DEFERRABLE` one if `false`.
+    ///
+    /// A serializable, read-only and deferrable transaction may wait when it begins, but then
+    /// runs without the overhead of serializable isolation, and cannot fail with
+    /// a serialization failure. This has no effect on other transactions.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = Some(deferrable);
+        self
+    }
+}
+
+impl SqlSafeStr for TransactionOptions {
+    fn into_sql_str(self) -> SqlStr {
+        let mut modes = Vec::new();
+
+        if let Some(level) = self.isolation {
+            modes.push(format!("ISOLATION LEVEL {}", level.as_sql()));
+        }
+
+        if let Some(read_only) = self.read_only {
+            modes.push(if read_only { "READ ONLY" } else { "READ WRITE" }.into());
+        }
+
+        if let Some(deferrable) = self.deferrable {
+            modes.push(
+                if deferrable {
+                    "DEFERRABLE"
+                } else {
+                    "NOT DEFERRABLE"
+                }
+                .into(),
+            );
+        }
+
+        if modes.is_empty() {
+            return "BEGIN".into_sql_str();
+        }
+
+        // Only keywords are pushed above, so this is safe.
+        AssertSqlSafe(format!("BEGIN {}", modes.join(", "))).into_sql_str()
+    }
+}
+
+/// How [`Pool::transaction_with_retry()`] retries a transaction which failed because of
+/// a conflict with concurrent transactions.
+///
+/// A transaction is retried if it fails with a [serialization failure] or a
+/// [deadlock], after waiting for a backoff which doubles with each
+/// attempt. By default, a transaction is attempted at most 5 times, waiting from 10ms up to
+/// 1s in between, and begins with a plain `BEGIN`.
+///
+/// [`Pool::transaction_with_retry()`]: crate::pool::Pool::transaction_with_retry
+/// [serialization failure]: crate::error::ErrorKind::SerializationFailure
+/// [deadlock]: crate::error::ErrorKind::DeadlockDetected
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub(crate) max_attempts: u32,
+    pub(crate) initial_backoff: Duration,
+    pub(crate) max_backoff: Duration,
+    pub(crate) begin: Option<SqlStr>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            begin: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The default policy; see the type documentation.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attempt the transaction at most `max_attempts` times, including the first.
+    ///
+    /// The error of the last attempt is returned once they run out. `0` is treated as `1`.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Wait `initial` before the first retry, then twice as long before each of the next,
+    /// up to `max`.
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Begin each attempt with `statement` instead of `BEGIN`, as with
+    /// [`Pool::begin_with()`][crate::pool::Pool::begin_with].
+    ///
+    /// This is usually [`TransactionOptions`], to set the isolation level.
+    pub fn begin_with(mut self, statement: impl SqlSafeStr) -> Self {
+        self.begin = Some(statement.into_sql_str());
+        self
+    }
+
+    pub(crate) fn is_retryable(error: &Error) -> bool {
+        match error {
+            Error::Database(e) => e.is_transaction_conflict(),
+            _ => false,
+        }
+    }
+}
+
 pub fn begin_ansi_transaction_sql(depth: usize) -> SqlStr {
     if depth == 0 {
         "BEGIN".into_sql_str()
@@ -305,3 +508,23 @@ pub fn rollback_ansi_transaction_sql(depth: usize) -> SqlStr {
         .into_sql_str()
     }
 }
+
+#[test]
+fn transaction_options_to_sql() {
+    let sql = |options: TransactionOptions| options.into_sql_str().as_str().to_owned();
+
+    assert_eq!(sql(TransactionOptions::new()), "BEGIN");
+    assert_eq!(
+        sql(TransactionOptions::new()
+            .isolation(IsolationLevel::Serializable)
+            .read_only(true)
+            .deferrable(true)),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE, READ ONLY, DEFERRABLE"
+    );
+    assert_eq!(
+        sql(TransactionOptions::new()
+            .isolation(IsolationLevel::RepeatableRead)
+            .read_only(false)),
+        "BEGIN ISOLATION LEVEL REPEATABLE READ, READ WRITE"
+    );
+}
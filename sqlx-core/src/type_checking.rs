@@ -170,6 +170,9 @@ macro_rules! impl_type_checking {
             time: {
                 $($time_ty:ty $(| $time_input:ty)?),*$(,)?
             },
+            jiff: {
+                $($jiff_ty:ty $(| $jiff_input:ty)?),*$(,)?
+            },
         },
         numeric-types: {
             bigdecimal: {
@@ -215,7 +218,7 @@ macro_rules! impl_type_checking {
                 if matches!(preferred_crates.date_time, DateTimeCrate::Time | DateTimeCrate::Inferred) {
                     $(
                         if <$time_ty as sqlx_core::types::Type<$database>>::type_info() == *info {
-                            if cfg!(feature = "chrono") {
+                            if cfg!(any(feature = "chrono", feature = "jiff")) {
                                 return Err($crate::type_checking::Error::AmbiguousDateTimeType {
                                     fallback: $crate::select_input_type!($time_ty $(, $time_input)?),
                                 });
@@ -227,7 +230,7 @@ macro_rules! impl_type_checking {
 
                     $(
                         if <$time_ty as sqlx_core::types::Type<$database>>::compatible(info) {
-                            if cfg!(feature = "chrono") {
+                            if cfg!(any(feature = "chrono", feature = "jiff")) {
                                 return Err($crate::type_checking::Error::AmbiguousDateTimeType {
                                     fallback: $crate::select_input_type!($time_ty $(, $time_input)?),
                                 });
@@ -247,12 +250,24 @@ macro_rules! impl_type_checking {
                 if matches!(preferred_crates.date_time, DateTimeCrate::Chrono | DateTimeCrate::Inferred) {
                     $(
                         if <$chrono_ty as sqlx_core::types::Type<$database>>::type_info() == *info {
+                            if cfg!(feature = "jiff") {
+                                return Err($crate::type_checking::Error::AmbiguousDateTimeType {
+                                    fallback: $crate::select_input_type!($chrono_ty $(, $chrono_input)?),
+                                });
+                            }
+
                             return Ok($crate::select_input_type!($chrono_ty $(, $chrono_input)?));
                         }
                     )*
 
                     $(
                         if <$chrono_ty as sqlx_core::types::Type<$database>>::compatible(info) {
+                            if cfg!(feature = "jiff") {
+                                return Err($crate::type_checking::Error::AmbiguousDateTimeType {
+                                    fallback: $crate::select_input_type!($chrono_ty $(, $chrono_input)?),
+                                });
+                            }
+
                             return Ok($crate::select_input_type!($chrono_ty $(, $chrono_input)?));
                         }
                     )*
@@ -263,6 +278,26 @@ macro_rules! impl_type_checking {
                     return Err(Error::DateTimeCrateFeatureNotEnabled);
                 }
 
+                #[cfg(feature = "jiff")]
+                if matches!(preferred_crates.date_time, DateTimeCrate::Jiff | DateTimeCrate::Inferred) {
+                    $(
+                        if <$jiff_ty as sqlx_core::types::Type<$database>>::type_info() == *info {
+                            return Ok($crate::select_input_type!($jiff_ty $(, $jiff_input)?));
+                        }
+                    )*
+
+                    $(
+                        if <$jiff_ty as sqlx_core::types::Type<$database>>::compatible(info) {
+                            return Ok($crate::select_input_type!($jiff_ty $(, $jiff_input)?));
+                        }
+                    )*
+                }
+
+                #[cfg(not(feature = "jiff"))]
+                if preferred_crates.date_time == DateTimeCrate::Jiff {
+                    return Err(Error::DateTimeCrateFeatureNotEnabled);
+                }
+
                 // Check `macros.preferred-crates.numeric`
                 //
                 // Due to legacy reasons, `bigdecimal` takes precedent over `rust_decimal` if
@@ -355,7 +390,7 @@ macro_rules! impl_type_checking {
                 if matches!(preferred_crates.date_time, DateTimeCrate::Time | DateTimeCrate::Inferred) {
                     $(
                         if <$time_ty as sqlx_core::types::Type<$database>>::type_info() == *info {
-                            if cfg!(feature = "chrono") {
+                            if cfg!(any(feature = "chrono", feature = "jiff")) {
                                 return Err($crate::type_checking::Error::AmbiguousDateTimeType {
                                     fallback: stringify!($time_ty),
                                 });
@@ -367,7 +402,7 @@ macro_rules! impl_type_checking {
 
                     $(
                         if <$time_ty as sqlx_core::types::Type<$database>>::compatible(info) {
-                            if cfg!(feature = "chrono") {
+                            if cfg!(any(feature = "chrono", feature = "jiff")) {
                                 return Err($crate::type_checking::Error::AmbiguousDateTimeType {
                                     fallback: stringify!($time_ty),
                                 });
@@ -387,12 +422,24 @@ macro_rules! impl_type_checking {
                 if matches!(preferred_crates.date_time, DateTimeCrate::Chrono | DateTimeCrate::Inferred) {
                     $(
                         if <$chrono_ty as sqlx_core::types::Type<$database>>::type_info() == *info {
+                            if cfg!(feature = "jiff") {
+                                return Err($crate::type_checking::Error::AmbiguousDateTimeType {
+                                    fallback: stringify!($chrono_ty),
+                                });
+                            }
+
                             return Ok(stringify!($chrono_ty));
                         }
                     )*
 
                     $(
                         if <$chrono_ty as sqlx_core::types::Type<$database>>::compatible(info) {
+                            if cfg!(feature = "jiff") {
+                                return Err($crate::type_checking::Error::AmbiguousDateTimeType {
+                                    fallback: stringify!($chrono_ty),
+                                });
+                            }
+
                             return Ok(stringify!($chrono_ty));
                         }
                     )*
@@ -403,6 +450,26 @@ macro_rules! impl_type_checking {
                     return Err(Error::DateTimeCrateFeatureNotEnabled);
                 }
 
+                #[cfg(feature = "jiff")]
+                if matches!(preferred_crates.date_time, DateTimeCrate::Jiff | DateTimeCrate::Inferred) {
+                    $(
+                        if <$jiff_ty as sqlx_core::types::Type<$database>>::type_info() == *info {
+                            return Ok(stringify!($jiff_ty));
+                        }
+                    )*
+
+                    $(
+                        if <$jiff_ty as sqlx_core::types::Type<$database>>::compatible(info) {
+                            return Ok(stringify!($jiff_ty));
+                        }
+                    )*
+                }
+
+                #[cfg(not(feature = "jiff"))]
+                if preferred_crates.date_time == DateTimeCrate::Jiff {
+                    return Err(Error::DateTimeCrateFeatureNotEnabled);
+                }
+
                 // Check `macros.preferred-crates.numeric`
                 //
                 // Due to legacy reasons, `bigdecimal` takes precedent over `rust_decimal` if
@@ -490,6 +557,15 @@ macro_rules! impl_type_checking {
                     )*
                 }
 
+                #[cfg(feature = "jiff")]
+                {
+                    $(
+                        if <$jiff_ty as sqlx_core::types::Type<$database>>::compatible(&info) {
+                            return $crate::type_checking::FmtValue::debug::<$jiff_ty>(value);
+                        }
+                    )*
+                }
+
                 #[cfg(feature = "bigdecimal")]
                 {
                     $(
@@ -0,0 +1,102 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+
+/// An integer within the inclusive range `MIN..=MAX`.
+///
+/// Like the [`NonZero*`][core::num::NonZero] types, the bounds are checked when the value
+/// is constructed and again when it is decoded, so a column which is (or should be)
+/// constrained in the database can't yield a value outside of the range.
+///
+/// `T` may be any integer type which converts losslessly into `i64`, e.g. `i16`, `i32` or `i64`.
+///
+/// ```rust
+/// use sqlx::types::Bounded;
+///
+/// type Percentage = Bounded<i16, 0, 100>;
+///
+/// assert_eq!(Percentage::new(42).map(Percentage::get), Some(42));
+/// assert_eq!(Percentage::new(101), None);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bounded<T, const MIN: i64, const MAX: i64>(T);
+
+impl<T, const MIN: i64, const MAX: i64> Bounded<T, MIN, MAX>
+where
+    T: Copy + Into<i64>,
+{
+    /// Returns `None` if `value` is not within `MIN..=MAX`.
+    pub fn new(value: T) -> Option<Self> {
+        (MIN..=MAX).contains(&value.into()).then_some(Self(value))
+    }
+
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const MIN: i64, const MAX: i64> Deref for Bounded<T, MIN, MAX> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Display, const MIN: i64, const MAX: i64> Display for Bounded<T, MIN, MAX> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<DB, T, const MIN: i64, const MAX: i64> Type<DB> for Bounded<T, MIN, MAX>
+where
+    DB: Database,
+    T: Type<DB>,
+{
+    fn type_info() -> <DB as Database>::TypeInfo {
+        <T as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &<DB as Database>::TypeInfo) -> bool {
+        <T as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB, T, const MIN: i64, const MAX: i64> Encode<'q, DB> for Bounded<T, MIN, MAX>
+where
+    DB: Database,
+    T: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer,
+    ) -> Result<IsNull, BoxDynError> {
+        <T as Encode<'q, DB>>::encode_by_ref(&self.0, buf)
+    }
+
+    fn produces(&self) -> Option<<DB as Database>::TypeInfo> {
+        <T as Encode<'q, DB>>::produces(&self.0)
+    }
+
+    fn size_hint(&self) -> usize {
+        <T as Encode<'q, DB>>::size_hint(&self.0)
+    }
+}
+
+impl<'r, DB, T, const MIN: i64, const MAX: i64> Decode<'r, DB> for Bounded<T, MIN, MAX>
+where
+    DB: Database,
+    T: Decode<'r, DB> + Copy + Into<i64> + Display,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let int = <T as Decode<'r, DB>>::decode(value)?;
+
+        Self::new(int).ok_or_else(|| format!("{int} is out of range {MIN}..={MAX}").into())
+    }
+}
@@ -0,0 +1,133 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::error::BoxDynError;
+
+/// Transparent compression for large binary values (`BYTEA` in Postgres).
+///
+/// The value is compressed when it is encoded and decompressed when it is decoded.
+///
+/// Values are compressed with [zstd](https://crates.io/crates/zstd) if the `zstd` feature
+/// is enabled, and [LZ4](https://crates.io/crates/lz4_flex) otherwise (`lz4` feature).
+/// Decoding detects the format from the value itself, so both can be read if both features
+/// are enabled, e.g. while migrating from one to the other.
+///
+/// Decoding a value which is not compressed in one of these formats is an error.
+///
+/// ```rust,ignore
+/// # async fn example() -> sqlx::Result<()> {
+/// # let mut conn: sqlx::PgConnection = unimplemented!();
+/// use sqlx::types::Compressed;
+///
+/// let report: Vec<u8> = std::fs::read("report.html")?;
+///
+/// sqlx::query("INSERT INTO reports (id, body) VALUES ($1, $2)")
+///     .bind(1_i64)
+///     .bind(Compressed(report))
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let Compressed(report): Compressed<Vec<u8>> =
+///     sqlx::query_scalar("SELECT body FROM reports WHERE id = $1")
+///         .bind(1_i64)
+///         .fetch_one(&mut conn)
+///         .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Compressed<T>(pub T);
+
+impl<T> From<T> for Compressed<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> Deref for Compressed<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Compressed<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> AsRef<T> for Compressed<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsMut<T> for Compressed<T> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "zstd")]
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+#[cfg(feature = "lz4")]
+const LZ4_MAGIC: [u8; 4] = [0x04, 0x22, 0x4D, 0x18];
+
+// UNSTABLE: for driver use only!
+#[doc(hidden)]
+impl<T: AsRef<[u8]>> Compressed<T> {
+    pub fn compress(&self) -> Result<Vec<u8>, BoxDynError> {
+        let data = self.0.as_ref();
+
+        #[cfg(feature = "zstd")]
+        {
+            // level 0 selects zstd's default level
+            Ok(zstd::stream::encode_all(data, 0)?)
+        }
+
+        #[cfg(not(feature = "zstd"))]
+        {
+            use std::io::Write;
+
+            let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+            encoder.write_all(data)?;
+            Ok(encoder.finish()?)
+        }
+    }
+}
+
+// UNSTABLE: for driver use only!
+#[doc(hidden)]
+impl<T: From<Vec<u8>>> Compressed<T> {
+    pub fn decompress(data: &[u8]) -> Result<Self, BoxDynError> {
+        #[cfg(feature = "zstd")]
+        if data.starts_with(&ZSTD_MAGIC) {
+            return Ok(Self(zstd::stream::decode_all(data)?.into()));
+        }
+
+        #[cfg(feature = "lz4")]
+        if data.starts_with(&LZ4_MAGIC) {
+            use std::io::Read;
+
+            let mut decompressed = Vec::new();
+            lz4_flex::frame::FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+            return Ok(Self(decompressed.into()));
+        }
+
+        Err("value is not compressed in a supported format".into())
+    }
+}
+
+#[test]
+fn compress_round_trip() {
+    let data = b"sqlx ".repeat(1000);
+    let compressed = Compressed(&data[..]).compress().unwrap();
+    assert!(compressed.len() < data.len() / 10);
+
+    let Compressed(decompressed) = Compressed::<Vec<u8>>::decompress(&compressed).unwrap();
+    assert_eq!(decompressed, data);
+
+    assert!(Compressed::<Vec<u8>>::decompress(&data).is_err());
+}
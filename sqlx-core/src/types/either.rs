@@ -0,0 +1,73 @@
+use either::Either;
+
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::value::ValueRef;
+
+// `Either<L, R>` is compatible with the SQL types of both `L` and `R`; decoding picks
+// whichever side accepts the type of the value, preferring `L`.
+
+impl<DB, L, R> Type<DB> for Either<L, R>
+where
+    DB: Database,
+    L: Type<DB>,
+    R: Type<DB>,
+{
+    fn type_info() -> <DB as Database>::TypeInfo {
+        <L as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &<DB as Database>::TypeInfo) -> bool {
+        <L as Type<DB>>::compatible(ty) || <R as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB, L, R> Encode<'q, DB> for Either<L, R>
+where
+    DB: Database,
+    L: Encode<'q, DB> + Type<DB>,
+    R: Encode<'q, DB> + Type<DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer,
+    ) -> Result<IsNull, BoxDynError> {
+        match self {
+            Either::Left(left) => left.encode_by_ref(buf),
+            Either::Right(right) => right.encode_by_ref(buf),
+        }
+    }
+
+    fn produces(&self) -> Option<<DB as Database>::TypeInfo> {
+        // `type_info()` is always `L`'s, so the type of a `Right` must be given explicitly
+        match self {
+            Either::Left(left) => left.produces(),
+            Either::Right(right) => right.produces().or_else(|| Some(R::type_info())),
+        }
+    }
+
+    fn size_hint(&self) -> usize {
+        match self {
+            Either::Left(left) => left.size_hint(),
+            Either::Right(right) => right.size_hint(),
+        }
+    }
+}
+
+impl<'r, DB, L, R> Decode<'r, DB> for Either<L, R>
+where
+    DB: Database,
+    L: Decode<'r, DB> + Type<DB>,
+    R: Decode<'r, DB> + Type<DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        if L::compatible(&value.type_info()) {
+            L::decode(value).map(Either::Left)
+        } else {
+            R::decode(value).map(Either::Right)
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::error::BoxDynError;
+
+/// Encrypts and decrypts the values of [`Encrypted`] columns.
+///
+/// The functions take no `self` because values are decoded without any context, so
+/// the key must be reachable from the implementing type itself (e.g. loaded into a `static`
+/// at startup).
+///
+/// Implementations should use authenticated encryption (e.g. AES-GCM or ChaCha20-Poly1305)
+/// with a fresh nonce for every value, stored alongside the ciphertext.
+pub trait Cipher {
+    /// Encrypt the encoded value.
+    fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>, BoxDynError>;
+
+    /// Decrypt a value previously returned by [`Self::encrypt()`].
+    fn decrypt(ciphertext: &[u8]) -> Result<Vec<u8>, BoxDynError>;
+}
+
+/// Application-level encryption for a column.
+///
+/// The inner value is encoded as usual, encrypted with `C` and stored as bytes
+/// (`BYTEA` in Postgres). Decoding decrypts the bytes and then decodes the inner value.
+///
+/// Because the database only sees ciphertext, encrypted columns cannot be compared,
+/// indexed or searched in SQL.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+/// use aes_gcm::{Aes256Gcm, Key, Nonce};
+/// use sqlx::error::BoxDynError;
+/// use sqlx::types::{Cipher, Encrypted};
+/// use std::sync::OnceLock;
+///
+/// // Loaded from a secret store at startup.
+/// static KEY: OnceLock<Key<Aes256Gcm>> = OnceLock::new();
+///
+/// struct AppCipher;
+///
+/// impl Cipher for AppCipher {
+///     fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>, BoxDynError> {
+///         let cipher = Aes256Gcm::new(KEY.get().ok_or("encryption key not loaded")?);
+///         let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+///
+///         let mut ciphertext = nonce.to_vec();
+///         ciphertext.extend(cipher.encrypt(&nonce, plaintext).map_err(|_| "encryption failed")?);
+///         Ok(ciphertext)
+///     }
+///
+///     fn decrypt(ciphertext: &[u8]) -> Result<Vec<u8>, BoxDynError> {
+///         let cipher = Aes256Gcm::new(KEY.get().ok_or("encryption key not loaded")?);
+///
+///         if ciphertext.len() < 12 {
+///             return Err("ciphertext is too short".into());
+///         }
+///
+///         let (nonce, ciphertext) = ciphertext.split_at(12);
+///         Ok(cipher
+///             .decrypt(Nonce::from_slice(nonce), ciphertext)
+///             .map_err(|_| "decryption failed")?)
+///     }
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct Patient {
+///     id: i64,
+///     ssn: Encrypted<String, AppCipher>,
+/// }
+///
+/// # async fn example() -> sqlx::Result<()> {
+/// # let mut conn: sqlx::PgConnection = unimplemented!();
+/// sqlx::query("INSERT INTO patients (id, ssn) VALUES ($1, $2)")
+///     .bind(1_i64)
+///     .bind(Encrypted::<_, AppCipher>::new("078-05-1120".to_owned()))
+///     .execute(&mut conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Encrypted<T, C> {
+    value: T,
+    cipher: PhantomData<fn() -> C>,
+}
+
+impl<T, C> Encrypted<T, C> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            cipher: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, C> From<T> for Encrypted<T, C> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T, C> Deref for Encrypted<T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T, C> DerefMut for Encrypted<T, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T, C> AsRef<T> for Encrypted<T, C> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, C> AsMut<T> for Encrypted<T, C> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+// Implemented by hand so `C` doesn't need to implement these traits.
+
+impl<T: Clone, C> Clone for Encrypted<T, C> {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, C> Copy for Encrypted<T, C> {}
+
+impl<T: Debug, C> Debug for Encrypted<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Encrypted").field(&self.value).finish()
+    }
+}
+
+impl<T: Default, C> Default for Encrypted<T, C> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for Encrypted<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, C> Eq for Encrypted<T, C> {}
@@ -80,6 +80,28 @@ use crate::types::Type;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Zero-copy decoding
+///
+/// To forward JSON without deserializing it, decode into [`&JsonRawValue`][JsonRawValue]
+/// or `Json<&JsonRawValue>`, which borrow the JSON text from the row:
+///
+/// ```rust,ignore
+/// # async fn example4() -> sqlx::Result<()> {
+/// # let mut conn: sqlx::PgConnection = unimplemented!();
+/// use sqlx::types::JsonRawValue;
+/// use sqlx::Row;
+///
+/// let row = sqlx::query("SELECT document FROM documents WHERE id = $1")
+///     .bind(1_i64)
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// let document: &JsonRawValue = row.try_get("document")?;
+/// println!("{}", document.get());
+/// # Ok(())
+/// # }
+/// ```
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
 )]
@@ -19,6 +19,13 @@ use std::{borrow::Cow, rc::Rc, sync::Arc};
 use crate::database::Database;
 use crate::type_info::TypeInfo;
 
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "zstd", feature = "lz4"))))]
+mod compressed;
+
+mod bounded;
+mod either;
+mod encrypted;
 mod non_zero;
 
 #[cfg(feature = "bstr")]
@@ -43,6 +50,11 @@ pub mod chrono {
     pub use chrono::{
         DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
     };
+
+    #[cfg(feature = "chrono-tz")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono-tz")))]
+    #[doc(no_inline)]
+    pub use chrono_tz::Tz;
 }
 
 #[cfg(feature = "bit-vec")]
@@ -50,6 +62,17 @@ pub mod chrono {
 #[doc(no_inline)]
 pub use bit_vec::BitVec;
 
+#[cfg(feature = "bitvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bitvec")))]
+pub mod bitvec {
+    #[doc(no_inline)]
+    pub use bitvec::order::{Lsb0, Msb0};
+    #[doc(no_inline)]
+    pub use bitvec::slice::BitSlice;
+    #[doc(no_inline)]
+    pub use bitvec::vec::BitVec;
+}
+
 #[cfg(feature = "time")]
 #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
 pub mod time {
@@ -57,6 +80,15 @@ pub mod time {
     pub use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 }
 
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+pub mod jiff {
+    #[doc(no_inline)]
+    pub use jiff::civil::{Date, DateTime, Time};
+    #[doc(no_inline)]
+    pub use jiff::{Timestamp, Zoned};
+}
+
 #[cfg(feature = "bigdecimal")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bigdecimal")))]
 #[doc(no_inline)]
@@ -87,9 +119,20 @@ pub mod mac_address {
     pub use mac_address::MacAddress;
 }
 
+#[cfg(feature = "secrecy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "secrecy")))]
+pub mod secrecy {
+    #[doc(no_inline)]
+    pub use secrecy::{ExposeSecret, SecretBox, SecretSlice, SecretString};
+}
+
+pub use bounded::Bounded;
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+pub use compressed::Compressed;
+pub use encrypted::{Cipher, Encrypted};
 #[cfg(feature = "json")]
 pub use json::{Json, JsonRawValue, JsonValue};
-pub use text::Text;
+pub use text::{Text, TryDisplay, TryText};
 
 #[cfg(feature = "bstr")]
 pub use bstr::{BStr, BString};
@@ -139,14 +182,13 @@ pub use bstr::{BStr, BString};
 ///    .await?;
 /// ```
 ///
-/// However, if you are wrapping a type that does not implement `PgHasArrayType`
-/// (e.g. `Vec` itself, because we don't currently support multidimensional arrays),
+/// However, if you are wrapping a type that does not implement `PgHasArrayType`,
 /// you may receive an error:
 ///
 /// ```rust,ignore
-/// #[derive(sqlx::Type)] // ERROR: `Vec<i64>` does not implement `PgHasArrayType`
+/// #[derive(sqlx::Type)] // ERROR: `ExternalType` does not implement `PgHasArrayType`
 /// #[sqlx(transparent)]
-/// struct UserIds(Vec<i64>);
+/// struct Wrapper(ExternalType);
 /// ```
 ///
 /// To remedy this, add `#[sqlx(no_pg_array)]`, which disables the generation
@@ -155,7 +197,7 @@ pub use bstr::{BStr, BString};
 /// ```rust,ignore
 /// #[derive(sqlx::Type)]
 /// #[sqlx(transparent, no_pg_array)]
-/// struct UserIds(Vec<i64>);
+/// struct Wrapper(ExternalType);
 /// ```
 ///
 /// ##### Attributes
@@ -190,6 +232,19 @@ pub use bstr::{BStr, BString};
 /// enum Color { Red, Green, Blue }
 /// ```
 ///
+/// With `#[sqlx(untagged)]` as well as `#[repr(_)]`, values are encoded as integers but can be
+/// decoded from either an integer or a textual SQL type, e.g. for a column which is `SMALLINT` in
+/// some tables and `TEXT` in others while a schema is partially migrated. Text is matched against
+/// the names of the variants (which may be renamed with `#[sqlx(rename_all = "..")]` and
+/// `#[sqlx(rename = "..")]`) and otherwise parsed as the integer representation.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::Type)]
+/// #[repr(i16)]
+/// #[sqlx(untagged, rename_all = "lowercase")]
+/// enum Color { Red = 1, Green = 2, Blue = 3 }
+/// ```
+///
 /// ### Records
 ///
 /// User-defined composite types are supported through deriving a `struct`.
@@ -205,6 +260,18 @@ pub use bstr::{BStr, BString};
 ///     price: f64
 /// }
 /// ```
+///
+/// ## Either
+///
+/// [`Either<L, R>`](crate::Either) is compatible with the SQL types of both `L` and `R`, for a
+/// column whose type is only known at runtime, e.g. in tools which query arbitrary tables.
+/// A value is decoded as `L` if `L` is compatible with its type, and as `R` otherwise.
+///
+/// ```rust,ignore
+/// use sqlx::Either;
+///
+/// let id: Either<i64, String> = row.try_get("id")?;
+/// ```
 pub trait Type<DB: Database> {
     /// Returns the canonical SQL type for this Rust type.
     ///
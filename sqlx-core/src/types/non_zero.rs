@@ -56,9 +56,10 @@ macro_rules! impl_non_zero {
         {
             fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, crate::error::BoxDynError> {
                 let int = <$int as Decode<'r, DB>>::decode(value)?;
-                let non_zero = Self::try_from(int)?;
 
-                Ok(non_zero)
+                Self::new(int).ok_or_else(|| {
+                    concat!("unexpected zero value for ", stringify!($non_zero)).into()
+                })
             }
         })*
     };
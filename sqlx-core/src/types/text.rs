@@ -1,3 +1,4 @@
+use crate::error::BoxDynError;
 use std::ops::{Deref, DerefMut};
 
 /// Map a SQL text value to/from a Rust type using [`Display`] and [`FromStr`].
@@ -22,6 +23,8 @@ use std::ops::{Deref, DerefMut};
 /// Fortunately, most `Display` implementations are infallible by convention anyway
 /// (the standard `ToString` trait also assumes this), but you may still want to audit
 /// the source code for any types you intend to use with this adapter, just to be safe.
+///
+/// For types whose conversion to text can fail, use [`TryText`] instead.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Text<T>(pub T);
 
@@ -46,6 +49,55 @@ impl<T> DerefMut for Text<T> {
     }
 }
 
+/// Map a SQL text value to/from a Rust type using [`TryDisplay`] and [`FromStr`].
+///
+/// Like [`Text`], but for types whose conversion to text can fail: the error is returned
+/// when the value is encoded (e.g. from `execute()` after binding it) instead of panicking.
+///
+/// [`FromStr`]: std::str::FromStr
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TryText<T>(pub T);
+
+/// Fallible conversion of a value to text, for use with [`TryText`].
+///
+/// ```rust
+/// # use sqlx_core::error::BoxDynError;
+/// # use sqlx_core::types::TryDisplay;
+/// struct Path(std::path::PathBuf);
+///
+/// impl TryDisplay for Path {
+///     fn try_fmt(&self, buf: &mut String) -> Result<(), BoxDynError> {
+///         buf.push_str(self.0.to_str().ok_or("path is not valid UTF-8")?);
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait TryDisplay {
+    /// Append the text representation of `self` to `buf`, or return an error if there is none.
+    fn try_fmt(&self, buf: &mut String) -> Result<(), BoxDynError>;
+}
+
+impl<T> TryText<T> {
+    /// Extract the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for TryText<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for TryText<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 /* We shouldn't use blanket impls so individual drivers can provide specialized ones.
 impl<T, DB> Type<DB> for Text<T>
 where
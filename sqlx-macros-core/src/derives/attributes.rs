@@ -59,6 +59,7 @@ pub struct SqlxContainerAttributes {
     pub repr: Option<Ident>,
     pub no_pg_array: bool,
     pub default: bool,
+    pub untagged: bool,
 }
 
 pub enum JsonAttribute {
@@ -82,6 +83,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
     let mut rename_all = None;
     let mut no_pg_array = None;
     let mut default = None;
+    let mut untagged = None;
 
     for attr in input {
         if attr.path().is_ident("sqlx") {
@@ -92,6 +94,8 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                     try_set!(no_pg_array, true, attr);
                 } else if meta.path.is_ident("default") {
                     try_set!(default, true, attr);
+                } else if meta.path.is_ident("untagged") {
+                    try_set!(untagged, true, attr);
                 } else if meta.path.is_ident("rename_all") {
                     meta.input.parse::<Token![=]>()?;
                     let lit: LitStr = meta.input.parse()?;
@@ -140,6 +144,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         rename_all,
         no_pg_array: no_pg_array.unwrap_or(false),
         default: default.unwrap_or(false),
+        untagged: untagged.unwrap_or(false),
     })
 }
 
@@ -212,6 +217,8 @@ pub fn check_transparent_attributes(
         field
     );
 
+    assert_attribute!(!attributes.untagged, "unexpected #[sqlx(untagged)]", field);
+
     let ch_attributes = parse_child_attributes(&field.attrs)?;
 
     assert_attribute!(
@@ -243,6 +250,11 @@ pub fn check_weak_enum_attributes(
 
     assert_attribute!(attributes.repr.is_some(), "expected #[repr(..)]", input);
 
+    // untagged enums also decode from the (renamed) names of their variants
+    if attributes.untagged {
+        return Ok(attributes);
+    }
+
     assert_attribute!(
         attributes.rename_all.is_none(),
         "unexpected #[sqlx(c = ..)]",
@@ -270,6 +282,12 @@ pub fn check_strong_enum_attributes(
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
+    assert_attribute!(
+        !attributes.untagged,
+        "#[sqlx(untagged)] requires #[repr(..)]",
+        input
+    );
+
     Ok(attributes)
 }
 
@@ -293,6 +311,8 @@ pub fn check_struct_attributes(
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
+    assert_attribute!(!attributes.untagged, "unexpected #[sqlx(untagged)]", input);
+
     for field in fields {
         let attributes = parse_child_attributes(&field.attrs)?;
 
@@ -1,6 +1,7 @@
 use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
     check_weak_enum_attributes, parse_child_attributes, parse_container_attributes,
+    SqlxContainerAttributes,
 };
 use super::rename_all;
 use proc_macro2::TokenStream;
@@ -102,7 +103,7 @@ fn expand_derive_decode_weak_enum(
     variants: &Punctuated<Variant, Comma>,
 ) -> syn::Result<TokenStream> {
     let attr = check_weak_enum_attributes(input, variants)?;
-    let repr = attr.repr.unwrap();
+    let repr = attr.repr.as_ref().unwrap();
 
     let ident = &input.ident;
     let ident_s = ident.to_string();
@@ -117,6 +118,10 @@ fn expand_derive_decode_weak_enum(
         })
         .collect::<Vec<Arm>>();
 
+    if attr.untagged {
+        return expand_derive_decode_untagged_enum(input, variants, &attr, &arms);
+    }
+
     Ok(quote!(
         #[automatically_derived]
         impl<'r, DB: ::sqlx::Database> ::sqlx::decode::Decode<'r, DB> for #ident
@@ -144,6 +149,92 @@ fn expand_derive_decode_weak_enum(
     ))
 }
 
+// Decodes from either the integer representation or text, which is matched against the
+// (renamed) variant names, falling back to parsing it as the integer representation.
+fn expand_derive_decode_untagged_enum(
+    input: &DeriveInput,
+    variants: &Punctuated<Variant, Comma>,
+    attr: &SqlxContainerAttributes,
+    repr_arms: &[Arm],
+) -> syn::Result<TokenStream> {
+    let repr = attr.repr.as_ref().unwrap();
+    let ident = &input.ident;
+    let ident_s = ident.to_string();
+
+    let name_arms = variants
+        .iter()
+        .map(|v| -> syn::Result<Arm> {
+            let id = &v.ident;
+            let attributes = parse_child_attributes(&v.attrs)?;
+
+            let name = if let Some(rename) = attributes.rename {
+                rename
+            } else if let Some(pattern) = attr.rename_all {
+                rename_all(&id.to_string(), pattern)
+            } else {
+                id.to_string()
+            };
+
+            Ok(parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),))
+        })
+        .collect::<syn::Result<Vec<Arm>>>()?;
+
+    Ok(quote!(
+        #[automatically_derived]
+        impl<'r, DB: ::sqlx::Database> ::sqlx::decode::Decode<'r, DB> for #ident
+        where
+            #repr: ::sqlx::decode::Decode<'r, DB> + ::sqlx::Type<DB>,
+            &'r ::std::primitive::str: ::sqlx::decode::Decode<'r, DB>,
+        {
+            fn decode(
+                value: <DB as ::sqlx::database::Database>::ValueRef<'r>,
+            ) -> ::std::result::Result<
+                Self,
+                ::std::boxed::Box<
+                    dyn ::std::error::Error + 'static + ::std::marker::Send + ::std::marker::Sync,
+                >,
+            > {
+                let from_repr = |value: #repr| -> ::std::result::Result<
+                    Self,
+                    ::sqlx::error::BoxDynError,
+                > {
+                    match value {
+                        #(#repr_arms)*
+                        _ => ::std::result::Result::Err(::std::format!(
+                            "invalid value {:?} for enum {}",
+                            value,
+                            #ident_s
+                        )
+                        .into()),
+                    }
+                };
+
+                let is_repr = <#repr as ::sqlx::Type<DB>>::compatible(
+                    &::sqlx::ValueRef::type_info(&value),
+                );
+
+                if is_repr {
+                    return from_repr(<#repr as ::sqlx::decode::Decode<'r, DB>>::decode(value)?);
+                }
+
+                let value =
+                    <&'r ::std::primitive::str as ::sqlx::decode::Decode<'r, DB>>::decode(value)?;
+
+                match value {
+                    #(#name_arms)*
+                    _ => match value.parse::<#repr>() {
+                        ::std::result::Result::Ok(value) => from_repr(value),
+                        ::std::result::Result::Err(_) => ::std::result::Result::Err(
+                            ::std::format!("invalid value {:?} for enum {}", value, #ident_s)
+                                .into(),
+                        ),
+                    },
+                }
+            }
+        }
+    ))
+}
+
 fn expand_derive_decode_strong_enum(
     input: &DeriveInput,
     variants: &Punctuated<Variant, Comma>,
@@ -0,0 +1,68 @@
+use super::attributes::parse_container_attributes;
+use super::expand_derive_type_encode_decode;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_quote, Data, DataStruct, DeriveInput, Fields, FieldsUnnamed};
+
+pub fn expand_derive_id(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let field = match &input.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
+            ..
+        }) if unnamed.len() == 1 => unnamed.first().unwrap(),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[derive(Id)] is only valid for tuple structs with exactly one field",
+            ))
+        }
+    };
+
+    // an ID always has the same SQL type as the value it wraps
+    let mut transparent = input.clone();
+
+    if !parse_container_attributes(&input.attrs)?.transparent {
+        transparent.attrs.push(parse_quote!(#[sqlx(transparent)]));
+    }
+
+    let mut tts = expand_derive_type_encode_decode(&transparent)?;
+
+    let ident = &input.ident;
+    let ty = &field.ty;
+
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::std::fmt::Display));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    tts.extend(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::std::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+    ));
+
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::std::str::FromStr));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    tts.extend(quote!(
+        #[automatically_derived]
+        impl #impl_generics ::std::str::FromStr for #ident #ty_generics #where_clause {
+            type Err = <#ty as ::std::str::FromStr>::Err;
+
+            fn from_str(s: &::std::primitive::str) -> ::std::result::Result<Self, Self::Err> {
+                <#ty as ::std::str::FromStr>::from_str(s).map(Self)
+            }
+        }
+    ));
+
+    Ok(tts)
+}
@@ -1,11 +1,13 @@
 mod attributes;
 mod decode;
 mod encode;
+mod id;
 mod row;
 mod r#type;
 
 pub use decode::expand_derive_decode;
 pub use encode::expand_derive_encode;
+pub use id::expand_derive_id;
 pub use r#type::expand_derive_type;
 pub use row::expand_derive_from_row;
 
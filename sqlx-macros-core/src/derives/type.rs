@@ -147,21 +147,42 @@ fn expand_derive_has_sql_type_weak_enum(
     let attrs = check_weak_enum_attributes(input, variants)?;
     let repr = attrs.repr.unwrap();
     let ident = &input.ident;
-    let mut ts = quote!(
-        #[automatically_derived]
-        impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #ident
-        where
-            #repr: ::sqlx::Type<DB>,
-        {
-            fn type_info() -> DB::TypeInfo {
-                <#repr as ::sqlx::Type<DB>>::type_info()
+
+    let mut ts = if attrs.untagged {
+        quote!(
+            #[automatically_derived]
+            impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #ident
+            where
+                #repr: ::sqlx::Type<DB>,
+                ::std::primitive::str: ::sqlx::Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    <#repr as ::sqlx::Type<DB>>::type_info()
+                }
+
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    <#repr as ::sqlx::Type<DB>>::compatible(ty)
+                        || <::std::primitive::str as ::sqlx::Type<DB>>::compatible(ty)
+                }
             }
+        )
+    } else {
+        quote!(
+            #[automatically_derived]
+            impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #ident
+            where
+                #repr: ::sqlx::Type<DB>,
+            {
+                fn type_info() -> DB::TypeInfo {
+                    <#repr as ::sqlx::Type<DB>>::type_info()
+                }
 
-            fn compatible(ty: &DB::TypeInfo) -> bool {
-                <#repr as ::sqlx::Type<DB>>::compatible(ty)
+                fn compatible(ty: &DB::TypeInfo) -> bool {
+                    <#repr as ::sqlx::Type<DB>>::compatible(ty)
+                }
             }
-        }
-    );
+        )
+    };
 
     if cfg!(feature = "postgres") && !attrs.no_pg_array {
         ts.extend(quote!(
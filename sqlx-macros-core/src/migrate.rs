@@ -5,9 +5,10 @@ use std::path::{Path, PathBuf};
 
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens, TokenStreamExt};
+use sqlx_core::config::migrate::{LockPolicy, LockStrategy};
 use sqlx_core::config::Config;
 use sqlx_core::migrate::{Migration, MigrationType};
-use syn::LitStr;
+use syn::{LitByteStr, LitStr};
 
 pub const DEFAULT_PATH: &str = "./migrations";
 
@@ -21,6 +22,7 @@ impl ToTokens for QuoteMigrationType {
             MigrationType::ReversibleDown => {
                 quote! { ::sqlx::migrate::MigrationType::ReversibleDown }
             }
+            MigrationType::Repeatable => quote! { ::sqlx::migrate::MigrationType::Repeatable },
         };
         tokens.append_all(ts);
     }
@@ -28,7 +30,8 @@ impl ToTokens for QuoteMigrationType {
 
 struct QuoteMigration {
     migration: Migration,
-    path: PathBuf,
+    /// The expression for the `sql` of the migration.
+    sql: TokenStream,
 }
 
 impl ToTokens for QuoteMigration {
@@ -43,28 +46,7 @@ impl ToTokens for QuoteMigration {
         } = &self.migration;
 
         let migration_type = QuoteMigrationType(*migration_type);
-
-        let sql = self
-            .path
-            .canonicalize()
-            .map_err(|e| {
-                format!(
-                    "error canonicalizing migration path {}: {e}",
-                    self.path.display()
-                )
-            })
-            .and_then(|path| {
-                let path_str = path.to_str().ok_or_else(|| {
-                    format!(
-                        "migration path cannot be represented as a string: {}",
-                        self.path.display()
-                    )
-                })?;
-
-                // this tells the compiler to watch this path for changes
-                Ok(quote! { include_str!(#path_str) })
-            })
-            .unwrap_or_else(|e| quote! { compile_error!(#e) });
+        let sql = &self.sql;
 
         let ts = quote! {
             ::sqlx::migrate::Migration {
@@ -73,6 +55,7 @@ impl ToTokens for QuoteMigration {
                 migration_type:  #migration_type,
                 sql: ::sqlx::SqlStr::from_static(#sql),
                 no_tx: #no_tx,
+                code: ::std::option::Option::None,
                 checksum: ::std::borrow::Cow::Borrowed(&[
                     #(#checksum),*
                 ]),
@@ -83,6 +66,83 @@ impl ToTokens for QuoteMigration {
     }
 }
 
+/// `include_str!()` the migration at `path`, which also tells the compiler
+/// to watch it for changes.
+fn include_migration(path: &Path) -> TokenStream {
+    path.canonicalize()
+        .map_err(|e| {
+            format!(
+                "error canonicalizing migration path {}: {e}",
+                path.display()
+            )
+        })
+        .and_then(|path| {
+            let path_str = path.to_str().ok_or_else(|| {
+                format!(
+                    "migration path cannot be represented as a string: {}",
+                    path.display()
+                )
+            })?;
+
+            Ok(quote! { include_str!(#path_str) })
+        })
+        .unwrap_or_else(|e| quote! { compile_error!(#e) })
+}
+
+/// Embed `migrations` compressed, storing the SQL of identical files only once.
+///
+/// Expands to a function returning the migrations, decompressing them on first use.
+fn quote_embedded(migrations: &[(Migration, PathBuf)]) -> TokenStream {
+    let mut texts: Vec<&str> = Vec::new();
+
+    let sql: Vec<usize> = migrations
+        .iter()
+        .map(|(migration, _)| {
+            let text = migration.sql.as_str();
+
+            texts.iter().position(|t| *t == text).unwrap_or_else(|| {
+                texts.push(text);
+                texts.len() - 1
+            })
+        })
+        .collect();
+
+    let lengths = texts.iter().map(|text| text.len());
+    let contents = sqlx_core::migrate::compress_embedded(texts.concat().as_bytes());
+    let contents = LitByteStr::new(&contents, Span::call_site());
+
+    // The files are still included so changes to them are tracked,
+    // but they are unused and so are not embedded in the binary.
+    let includes = migrations.iter().map(|(_, path)| include_migration(path));
+
+    let migrations = migrations.iter().map(|(migration, _)| QuoteMigration {
+        migration: migration.clone(),
+        sql: quote! { "" },
+    });
+
+    quote! {{
+        fn migrations() -> &'static [::sqlx::migrate::Migration] {
+            #(const _: &str = #includes;)*
+
+            static EMBEDDED: ::sqlx::migrate::EmbeddedMigrations =
+                ::sqlx::migrate::EmbeddedMigrations {
+                    migrations: &[#(#migrations),*],
+                    sql: &[#(#sql),*],
+                    lengths: &[#(#lengths),*],
+                    contents: #contents,
+                };
+
+            static MIGRATIONS: ::std::sync::OnceLock<
+                ::std::vec::Vec<::sqlx::migrate::Migration>,
+            > = ::std::sync::OnceLock::new();
+
+            MIGRATIONS.get_or_init(|| EMBEDDED.decompress())
+        }
+
+        migrations as fn() -> &'static [::sqlx::migrate::Migration]
+    }}
+}
+
 pub fn default_path(config: &Config) -> &str {
     config
         .migrate
@@ -113,12 +173,53 @@ pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStre
     let resolve_config = config.migrate.to_resolve_config();
 
     // Use the same code path to resolve migrations at compile time and runtime.
-    let migrations = sqlx_core::migrate::resolve_blocking_with_config(&path, &resolve_config)?
-        .into_iter()
-        .map(|(migration, path)| QuoteMigration { migration, path });
+    let migrations = sqlx_core::migrate::resolve_blocking_with_config(&path, &resolve_config)?;
+
+    let (migrations, embedded) = if config.migrate.compress_embedded {
+        let embedded = quote_embedded(&migrations);
+
+        (
+            quote! { &[] },
+            quote! { ::std::option::Option::Some(#embedded) },
+        )
+    } else {
+        let migrations = migrations
+            .into_iter()
+            .map(|(migration, path)| QuoteMigration {
+                sql: include_migration(&path),
+                migration,
+            });
+
+        (
+            quote! { const { &[#(#migrations),*] } },
+            quote! { ::std::option::Option::None },
+        )
+    };
 
     let table_name = config.migrate.table_name();
 
+    let require_reversible = config.migrate.require_reversible;
+    let allow_out_of_order = config.migrate.allow_out_of_order;
+    let record_audit = config.migrate.record_audit;
+
+    let lock_strategy = match config.migrate.lock.strategy {
+        LockStrategy::Advisory => quote! { &::sqlx::migrate::AdvisoryLock },
+        LockStrategy::Table => quote! { &::sqlx::migrate::TableLock },
+        LockStrategy::None => quote! { &::sqlx::migrate::NoLock },
+    };
+
+    let lock_policy = match config.migrate.lock.policy {
+        LockPolicy::Wait => quote! { ::sqlx::migrate::LockPolicy::Wait },
+        LockPolicy::Fail => quote! { ::sqlx::migrate::LockPolicy::Fail },
+    };
+
+    let lock_timeout = match config.migrate.lock.timeout_secs {
+        Some(secs) => {
+            quote! { ::std::option::Option::Some(::std::time::Duration::from_secs(#secs)) }
+        }
+        None => quote! { ::std::option::Option::None },
+    };
+
     let create_schemas = config.migrate.create_schemas.iter().map(|schema_name| {
         quote! { ::std::borrow::Cow::Borrowed(#schema_name) }
     });
@@ -137,11 +238,16 @@ pub fn expand_with_path(config: &Config, path: &Path) -> crate::Result<TokenStre
 
     Ok(quote! {
         ::sqlx::migrate::Migrator {
-            migrations: ::std::borrow::Cow::Borrowed(const {&[
-                    #(#migrations),*
-            ]}),
+            migrations: ::std::borrow::Cow::Borrowed(#migrations),
+            embedded: #embedded,
             create_schemas: ::std::borrow::Cow::Borrowed(&[#(#create_schemas),*]),
             table_name: ::std::borrow::Cow::Borrowed(#table_name),
+            require_reversible: #require_reversible,
+            allow_out_of_order: #allow_out_of_order,
+            record_audit: #record_audit,
+            lock_strategy: #lock_strategy,
+            lock_policy: #lock_policy,
+            lock_timeout: #lock_timeout,
             ..::sqlx::migrate::Migrator::DEFAULT
         }
     })
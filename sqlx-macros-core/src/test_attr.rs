@@ -6,6 +6,8 @@ use syn::parse::Parser;
 struct Args {
     fixtures: Vec<(FixturesType, Vec<syn::LitStr>)>,
     migrations: MigrationsOpt,
+    // `Some(None)` for the default image
+    container: Option<Option<syn::LitStr>>,
 }
 
 #[cfg(feature = "migrate")]
@@ -166,6 +168,12 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
         _ => quote! {},
     };
 
+    let container = match args.container {
+        Some(Some(image)) => quote! { args.container(Some(#image)); },
+        Some(None) => quote! { args.container(None); },
+        None => quote! {},
+    };
+
     Ok(quote! {
         #(#attrs)*
         #[::core::prelude::v1::test]
@@ -178,6 +186,8 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
 
             #migrations
 
+            #container
+
             args.fixtures(&[#(#fixtures),*]);
 
             // We need to give a coercion site or else we get "unimplemented trait" errors.
@@ -197,6 +207,7 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
 
     let mut fixtures = Vec::new();
     let mut migrations = MigrationsOpt::InferredPath;
+    let mut container = None;
 
     for arg in attr_args {
         let path = arg.path().clone();
@@ -292,10 +303,39 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
 
                 migrations = MigrationsOpt::ExplicitMigrator(lit.parse()?);
             }
+            // container
+            Meta::Path(_) if path.is_ident("container") => {
+                if container.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "cannot have more than one `container` arg",
+                    ));
+                }
+
+                container = Some(None);
+            }
+            // container = "<image>"
+            Meta::NameValue(MetaNameValue { value, .. }) if path.is_ident("container") => {
+                if container.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "cannot have more than one `container` arg",
+                    ));
+                }
+
+                let Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) = value
+                else {
+                    return Err(syn::Error::new_spanned(path, "expected string"));
+                };
+
+                container = Some(Some(lit));
+            }
             arg => {
                 return Err(syn::Error::new_spanned(
                     arg,
-                    r#"expected `fixtures("<filename>", ...)` or `migrations = "<path>" | false` or `migrator = "<rust path>"`"#,
+                    r#"expected `fixtures("<filename>", ...)` or `migrations = "<path>" | false` or `migrator = "<rust path>"` or `container [= "<image>"]`"#,
                 ))
             }
         }
@@ -304,6 +344,7 @@ fn parse_args(attr_args: AttributeArgs) -> syn::Result<Args> {
     Ok(Args {
         fixtures,
         migrations,
+        container,
     })
 }
 
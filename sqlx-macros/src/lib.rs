@@ -52,6 +52,16 @@ pub fn derive_type(tokenstream: TokenStream) -> TokenStream {
     }
 }
 
+#[cfg(feature = "derive")]
+#[proc_macro_derive(Id, attributes(sqlx))]
+pub fn derive_id(tokenstream: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(tokenstream as syn::DeriveInput);
+    match derives::expand_derive_id(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 #[cfg(feature = "derive")]
 #[proc_macro_derive(FromRow, attributes(sqlx))]
 pub fn derive_from_row(input: TokenStream) -> TokenStream {
@@ -207,6 +207,7 @@ impl PgAdvisoryLock {
             PgAdvisoryLockKey::BigInt(key) => {
                 crate::query::query("SELECT pg_advisory_lock($1)")
                     .bind(key)
+                    .unlimited()
                     .execute(conn.as_mut())
                     .await?;
             }
@@ -214,6 +215,7 @@ impl PgAdvisoryLock {
                 crate::query::query("SELECT pg_advisory_lock($1, $2)")
                     .bind(key1)
                     .bind(key2)
+                    .unlimited()
                     .execute(conn.as_mut())
                     .await?;
             }
@@ -250,6 +252,7 @@ impl PgAdvisoryLock {
             PgAdvisoryLockKey::BigInt(key) => {
                 crate::query_scalar::query_scalar("SELECT pg_try_advisory_lock($1)")
                     .bind(key)
+                    .unlimited()
                     .fetch_one(conn.as_mut())
                     .await?
             }
@@ -257,6 +260,7 @@ impl PgAdvisoryLock {
                 crate::query_scalar::query_scalar("SELECT pg_try_advisory_lock($1, $2)")
                     .bind(key1)
                     .bind(key2)
+                    .unlimited()
                     .fetch_one(conn.as_mut())
                     .await?
             }
@@ -285,6 +289,7 @@ impl PgAdvisoryLock {
             PgAdvisoryLockKey::BigInt(key) => {
                 crate::query_scalar::query_scalar("SELECT pg_advisory_unlock($1)")
                     .bind(key)
+                    .unlimited()
                     .fetch_one(conn.as_mut())
                     .await?
             }
@@ -292,6 +297,7 @@ impl PgAdvisoryLock {
                 crate::query_scalar::query_scalar("SELECT pg_advisory_unlock($1, $2)")
                     .bind(key1)
                     .bind(key2)
+                    .unlimited()
                     .fetch_one(conn.as_mut())
                     .await?
             }
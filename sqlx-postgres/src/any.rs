@@ -6,11 +6,12 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{stream, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use sqlx_core::sql_str::SqlStr;
+use std::time::Duration;
 use std::{future, pin::pin};
 
 use sqlx_core::any::{
-    Any, AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend, AnyQueryResult, AnyRow,
-    AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
+    custom_type_info, Any, AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend,
+    AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
 };
 
 use crate::type_info::PgType;
@@ -36,7 +37,7 @@ impl AnyConnectionBackend for PgConnection {
         Connection::close_hard(*self).boxed()
     }
 
-    fn ping(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+    fn ping(&mut self) -> BoxFuture<'_, sqlx_core::Result<Duration>> {
         Connection::ping(self).boxed()
     }
 
@@ -94,14 +95,21 @@ impl AnyConnectionBackend for PgConnection {
         };
 
         Box::pin(
-            self.run(query, arguments, persistent, None)
-                .try_flatten_stream()
-                .map(
-                    move |res: sqlx_core::Result<Either<PgQueryResult, PgRow>>| match res? {
-                        Either::Left(result) => Ok(Either::Left(map_result(result))),
-                        Either::Right(row) => Ok(Either::Right(AnyRow::try_from(&row)?)),
-                    },
-                ),
+            self.run(
+                query,
+                arguments,
+                persistent,
+                None,
+                Default::default(),
+                Default::default(),
+            )
+            .try_flatten_stream()
+            .map(
+                move |res: sqlx_core::Result<Either<PgQueryResult, PgRow>>| match res? {
+                    Either::Left(result) => Ok(Either::Left(map_result(result))),
+                    Either::Right(row) => Ok(Either::Right(AnyRow::try_from(&row)?)),
+                },
+            ),
         )
     }
 
@@ -119,7 +127,17 @@ impl AnyConnectionBackend for PgConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let mut stream = pin!(self.run(query, arguments, persistent, None).await?);
+            let mut stream = pin!(
+                self.run(
+                    query,
+                    arguments,
+                    persistent,
+                    None,
+                    Default::default(),
+                    Default::default(),
+                )
+                .await?
+            );
 
             if let Some(Either::Right(row)) = stream.try_next().await? {
                 return Ok(Some(AnyRow::try_from(&row)?));
@@ -198,9 +216,13 @@ impl<'a> TryFrom<&'a PgTypeInfo> for AnyTypeInfo {
                 PgType::Text | PgType::Varchar => AnyTypeInfoKind::Text,
                 PgType::DeclareWithName(UStr::Static("citext")) => AnyTypeInfoKind::Text,
                 _ => {
+                    if let Some(custom) = custom_type_info::<Postgres>(pg_type) {
+                        return Ok(custom);
+                    }
+
                     return Err(sqlx_core::Error::AnyDriverError(
                         format!("Any driver does not support the Postgres type {pg_type:?}").into(),
-                    ))
+                    ));
                 }
             },
         })
@@ -239,6 +261,7 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for PgConnectOptions {
     fn try_from(value: &'a AnyConnectOptions) -> Result<Self, Self::Error> {
         let mut opts = PgConnectOptions::parse_from_url(&value.database_url)?;
         opts.log_settings = value.log_settings.clone();
+        opts.connect_retry = value.connect_retry.clone();
         Ok(opts)
     }
 }
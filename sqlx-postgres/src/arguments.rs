@@ -22,7 +22,7 @@ use sqlx_core::error::BoxDynError;
 //            that has a patch, we then apply the patch which should write to &mut Vec<u8>,
 //            backtrack and update the prefixed-len, then write until the next patch offset
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Clone)]
 pub struct PgArgumentBuffer {
     buffer: Vec<u8>,
 
@@ -44,6 +44,27 @@ pub struct PgArgumentBuffer {
     // function and can just ask postgres.
     //
     type_holes: Vec<(usize, HoleKind)>, // Vec<{ offset, type_name }>
+
+    // Set when a secret (e.g. `secrecy::SecretString`) has been encoded,
+    // so the buffer is left out of `Debug` output.
+    redacted: bool,
+}
+
+impl fmt::Debug for PgArgumentBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("PgArgumentBuffer");
+
+        if self.redacted {
+            s.field("buffer", &"<redacted>");
+        } else {
+            s.field("buffer", &self.buffer);
+        }
+
+        s.field("count", &self.count)
+            .field("patches", &self.patches)
+            .field("type_holes", &self.type_holes)
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -138,6 +159,96 @@ impl PgArguments {
     }
 }
 
+impl PgArguments {
+    /// Render the arguments for logging, such as `$1 = 42, $2 = <TEXT>`.
+    ///
+    /// Only the values of `safe_types` are rendered; other values are replaced with the name
+    /// of their type, as are all of them if the buffer is redacted.
+    pub(crate) fn to_log_string(&self, safe_types: &[String]) -> String {
+        let mut rendered = String::new();
+        let mut buf = &self.buffer.buffer[..];
+
+        for (i, ty) in self.types.iter().enumerate() {
+            let Some((len, rest)) = buf.split_first_chunk::<4>() else {
+                break;
+            };
+
+            let value = match usize::try_from(i32::from_be_bytes(*len)) {
+                Ok(len) if len <= rest.len() => {
+                    let (value, rest) = rest.split_at(len);
+                    buf = rest;
+                    Some(value)
+                }
+                Ok(_) => break,
+                Err(_) => {
+                    buf = rest;
+                    None
+                }
+            };
+
+            if i > 0 {
+                rendered.push_str(", ");
+            }
+
+            let safe = !self.buffer.redacted
+                && safe_types
+                    .iter()
+                    .any(|safe| safe.eq_ignore_ascii_case(ty.display_name()));
+
+            let _ = write!(rendered, "${} = ", i + 1);
+
+            match value {
+                Some(value) if safe => match render_value(ty, value) {
+                    Some(value) => rendered.push_str(&value),
+                    None => {
+                        let _ = write!(rendered, "<{}>", ty.display_name());
+                    }
+                },
+                None if safe => rendered.push_str("NULL"),
+                _ => {
+                    let _ = write!(rendered, "<{}>", ty.display_name());
+                }
+            }
+        }
+
+        rendered
+    }
+}
+
+// Render a value of a simple built-in type, encoded in the binary format.
+fn render_value(ty: &PgTypeInfo, value: &[u8]) -> Option<String> {
+    use crate::type_info::PgType;
+
+    let rendered = match ty.0 {
+        PgType::Bool => (value.first()? != &0).to_string(),
+        PgType::Int2 => i16::from_be_bytes(value.try_into().ok()?).to_string(),
+        PgType::Int4 => i32::from_be_bytes(value.try_into().ok()?).to_string(),
+        PgType::Int8 => i64::from_be_bytes(value.try_into().ok()?).to_string(),
+        PgType::Float4 => f32::from_be_bytes(value.try_into().ok()?).to_string(),
+        PgType::Float8 => f64::from_be_bytes(value.try_into().ok()?).to_string(),
+        PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name => {
+            format!("'{}'", std::str::from_utf8(value).ok()?.replace('\'', "''"))
+        }
+        PgType::Uuid => {
+            let bytes: [u8; 16] = value.try_into().ok()?;
+            let mut uuid = String::with_capacity(36);
+
+            for (i, byte) in bytes.iter().enumerate() {
+                if matches!(i, 4 | 6 | 8 | 10) {
+                    uuid.push('-');
+                }
+
+                let _ = write!(uuid, "{byte:02x}");
+            }
+
+            uuid
+        }
+        _ => return None,
+    };
+
+    Some(rendered)
+}
+
 impl Arguments for PgArguments {
     type Database = Postgres;
 
@@ -231,12 +342,25 @@ impl PgArgumentBuffer {
         self.type_holes.push((offset, HoleKind::Array(array)));
     }
 
+    /// Omit the encoded arguments from `Debug` output, because they contain a secret.
+    #[allow(dead_code)]
+    pub(crate) fn redact(&mut self) {
+        self.redacted = true;
+    }
+
+    /// Whether the buffer contains patches or type holes, which can only be filled in
+    /// when it is bound to a query.
+    pub(crate) fn needs_patching(&self) -> bool {
+        !self.patches.is_empty() || !self.type_holes.is_empty()
+    }
+
     fn snapshot(&self) -> PgArgumentBufferSnapshot {
         let Self {
             buffer,
             count,
             patches,
             type_holes,
+            redacted,
         } = self;
 
         PgArgumentBufferSnapshot {
@@ -244,6 +368,7 @@ impl PgArgumentBuffer {
             count: *count,
             patches_length: patches.len(),
             type_holes_length: type_holes.len(),
+            redacted: *redacted,
         }
     }
 
@@ -254,12 +379,14 @@ impl PgArgumentBuffer {
             count,
             patches_length,
             type_holes_length,
+            redacted,
         }: PgArgumentBufferSnapshot,
     ) {
         self.buffer.truncate(buffer_length);
         self.count = count;
         self.patches.truncate(patches_length);
         self.type_holes.truncate(type_holes_length);
+        self.redacted = redacted;
     }
 }
 
@@ -268,6 +395,7 @@ struct PgArgumentBufferSnapshot {
     count: usize,
     patches_length: usize,
     type_holes_length: usize,
+    redacted: bool,
 }
 
 impl Deref for PgArgumentBuffer {
@@ -294,3 +422,27 @@ pub(crate) fn value_size_int4_checked(size: usize) -> Result<i32, String> {
         )
     })
 }
+
+#[test]
+fn bind_values_are_rendered_for_safe_types_only() {
+    let mut arguments = PgArguments::default();
+    arguments.add(42_i32).unwrap();
+    arguments.add("it's secret").unwrap();
+    arguments.add(None::<i64>).unwrap();
+    arguments.add(true).unwrap();
+
+    assert_eq!(
+        arguments.to_log_string(&["int4".into(), "INT8".into(), "BOOL".into()]),
+        "$1 = 42, $2 = <TEXT>, $3 = NULL, $4 = true"
+    );
+    assert_eq!(
+        arguments.to_log_string(&["TEXT".into()]),
+        "$1 = <INT4>, $2 = 'it''s secret', $3 = <INT8>, $4 = <BOOL>"
+    );
+
+    arguments.buffer.redact();
+    assert_eq!(
+        arguments.to_log_string(&["INT4".into()]),
+        "$1 = <INT4>, $2 = <TEXT>, $3 = <INT8>, $4 = <BOOL>"
+    );
+}
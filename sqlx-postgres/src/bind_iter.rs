@@ -1,5 +1,6 @@
 use crate::{type_info::PgType, PgArgumentBuffer, PgHasArrayType, PgTypeInfo, Postgres};
 use core::cell::Cell;
+use sqlx_core::bytes::Buf;
 use sqlx_core::{
     database::Database,
     encode::{Encode, IsNull},
@@ -92,6 +93,12 @@ where
             .and_then(Encode::produces)
             .unwrap_or_else(<I as Iterator>::Item::type_info);
 
+        if let Some(element) = type_info.try_array_element() {
+            // the items are arrays themselves, which make up a multi-dimensional array
+            let element = element.into_owned();
+            return Self::encode_nested(first, iter, element, buf);
+        }
+
         buf.extend(&1_i32.to_be_bytes()); // number of dimensions
         buf.extend(&0_i32.to_be_bytes()); // flags
 
@@ -132,6 +139,95 @@ where
 
         Ok(IsNull::No)
     }
+
+    fn encode_nested(
+        first: Option<<I as Iterator>::Item>,
+        iter: I,
+        element: PgTypeInfo,
+        buf: &mut PgArgumentBuffer,
+    ) -> Result<IsNull, BoxDynError> {
+        let element_oid = element
+            .0
+            .try_oid()
+            .ok_or("multi-dimensional arrays are only supported for types with a known OID")?;
+
+        // the dimensions of the first sub-array, which all sub-arrays must match
+        let mut sub_dims: Option<Vec<i32>> = None;
+        let mut elements = Vec::new();
+        let mut count = 0_i32;
+
+        for item in first.into_iter().chain(iter) {
+            let mut sub_array = PgArgumentBuffer::default();
+
+            if let IsNull::Yes = item.encode(&mut sub_array)? {
+                return Err("multi-dimensional arrays cannot contain NULL sub-arrays".into());
+            }
+
+            if sub_array.needs_patching() {
+                return Err(
+                    "multi-dimensional arrays are only supported for types with a known OID".into(),
+                );
+            }
+
+            let mut header = &sub_array[..];
+            let ndim = usize::try_from(header.get_i32())?;
+            let _flags = header.get_i32();
+            let _element_oid = header.get_u32();
+
+            // (length, lower bound) of each dimension
+            let dims: Vec<i32> = (0..ndim)
+                .map(|_| {
+                    let len = header.get_i32();
+                    let _lower = header.get_i32();
+                    len
+                })
+                .collect();
+
+            match &sub_dims {
+                None => sub_dims = Some(dims),
+                Some(expected) if *expected != dims => {
+                    return Err(format!(
+                        "multi-dimensional arrays must have sub-arrays with matching dimensions: \
+                        expected {expected:?}, got {dims:?} for sub-array {}",
+                        count + 1
+                    )
+                    .into());
+                }
+                Some(_) => {}
+            }
+
+            elements.extend_from_slice(header);
+            count = count
+                .checked_add(1)
+                .ok_or("encoded array is too large for Postgres")?;
+        }
+
+        let sub_dims = sub_dims.unwrap_or_default();
+
+        if sub_dims.is_empty() {
+            // Postgres represents an array of empty arrays as an empty array
+            buf.extend(&0_i32.to_be_bytes()); // number of dimensions
+            buf.extend(&0_i32.to_be_bytes()); // flags
+            buf.extend(&element_oid.0.to_be_bytes());
+
+            return Ok(IsNull::No);
+        }
+
+        let ndim = i32::try_from(sub_dims.len() + 1)?;
+
+        buf.extend(&ndim.to_be_bytes()); // number of dimensions
+        buf.extend(&0_i32.to_be_bytes()); // flags
+        buf.extend(&element_oid.0.to_be_bytes());
+
+        for len in std::iter::once(count).chain(sub_dims) {
+            buf.extend(&len.to_be_bytes()); // len
+            buf.extend(&1_i32.to_be_bytes()); // lower bound
+        }
+
+        buf.extend(&elements);
+
+        Ok(IsNull::No)
+    }
 }
 
 impl<'q, I> Encode<'q, Postgres> for PgBindIter<I>
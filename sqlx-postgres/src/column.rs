@@ -1,4 +1,5 @@
 use crate::ext::ustr::UStr;
+use crate::type_info::PgType;
 use crate::{PgTypeInfo, Postgres};
 
 use sqlx_core::column::ColumnOrigin;
@@ -18,6 +19,11 @@ pub struct PgColumn {
     pub(crate) relation_id: Option<crate::types::Oid>,
     #[cfg_attr(feature = "offline", serde(skip))]
     pub(crate) relation_attribute_no: Option<i16>,
+
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) nullable: Option<bool>,
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) type_modifier: Option<i32>,
 }
 
 impl PgColumn {
@@ -40,8 +46,31 @@ impl PgColumn {
     pub fn relation_attribute_no(&self) -> Option<i16> {
         self.relation_attribute_no
     }
+
+    /// Returns the type modifier of this column, if its type has one.
+    ///
+    /// The meaning of the modifier depends on the type; for the common types it is decoded by
+    /// [`Column::max_length()`], [`Column::precision()`] and [`Column::scale()`].
+    ///
+    /// Corresponds to column `atttypmod` of the `pg_catalog.pg_attribute` table:
+    /// <https://www.postgresql.org/docs/current/catalog-pg-attribute.html>
+    pub fn type_modifier(&self) -> Option<i32> {
+        self.type_modifier
+    }
+
+    // `NUMERIC(P, S)` is stored as `((P << 16) | S) + VARHDRSZ`,
+    // with `S` as an 11-bit two's complement number (see `make_numeric_typmod()` in Postgres)
+    fn numeric_modifier(&self) -> Option<i32> {
+        match self.type_info.0 {
+            PgType::Numeric | PgType::NumericArray => Some(self.type_modifier? - VARHDRSZ),
+            _ => None,
+        }
+    }
 }
 
+// the length of the header of variable-length values, which Postgres adds to length modifiers
+const VARHDRSZ: i32 = 4;
+
 impl Column for PgColumn {
     type Database = Postgres;
 
@@ -60,4 +89,57 @@ impl Column for PgColumn {
     fn origin(&self) -> ColumnOrigin {
         self.origin.clone()
     }
+
+    fn nullable(&self) -> Option<bool> {
+        self.nullable
+    }
+
+    fn max_length(&self) -> Option<u32> {
+        match self.type_info.0 {
+            PgType::Varchar | PgType::VarcharArray | PgType::Bpchar | PgType::BpcharArray => {
+                u32::try_from(self.type_modifier? - VARHDRSZ).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn precision(&self) -> Option<u32> {
+        u32::try_from((self.numeric_modifier()? >> 16) & 0xffff).ok()
+    }
+
+    fn scale(&self) -> Option<i32> {
+        Some(((self.numeric_modifier()? & 0x7ff) ^ 0x400) - 0x400)
+    }
+}
+
+#[test]
+fn type_modifiers_are_decoded() {
+    fn column(type_info: PgTypeInfo, type_modifier: i32) -> PgColumn {
+        PgColumn {
+            ordinal: 0,
+            name: "column".into(),
+            type_info,
+            origin: ColumnOrigin::Expression,
+            relation_id: None,
+            relation_attribute_no: None,
+            nullable: None,
+            type_modifier: Some(type_modifier),
+        }
+    }
+
+    // VARCHAR(20)
+    let varchar = column(PgTypeInfo::VARCHAR, 24);
+    assert_eq!(varchar.max_length(), Some(20));
+    assert_eq!(varchar.precision(), None);
+
+    // NUMERIC(10, 2)
+    let numeric = column(PgTypeInfo::NUMERIC, (10 << 16 | 2) + 4);
+    assert_eq!(numeric.max_length(), None);
+    assert_eq!(numeric.precision(), Some(10));
+    assert_eq!(numeric.scale(), Some(2));
+
+    // NUMERIC(5, -2)
+    let numeric = column(PgTypeInfo::NUMERIC, (5 << 16 | 0x7fe) + 4);
+    assert_eq!(numeric.precision(), Some(5));
+    assert_eq!(numeric.scale(), Some(-2));
 }
@@ -0,0 +1,454 @@
+use crate::connection::executor::ResultCounter;
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::io::{PortalId, StatementId};
+use crate::logger::{QueryLogOptions, QueryLogger};
+use crate::message::{self, BackendMessageFormat, Bind, Close, CommandComplete, Parse};
+use crate::statement::PgStatementMetadata;
+use crate::types::Oid;
+use crate::{PgArguments, PgConnection, PgQueryResult, PgRow, PgValueFormat, Postgres};
+use futures_core::stream::BoxStream;
+use sqlx_core::arguments::Arguments;
+use sqlx_core::Either;
+use std::sync::Arc;
+
+struct BatchStatement {
+    logger: QueryLogger,
+    arguments: PgArguments,
+    num_params: u16,
+    // set if the statement was already prepared and cached; otherwise it is parsed as the
+    // unnamed statement in the batch, and its metadata is filled in by `RowDescription`
+    statement: Option<StatementId>,
+    metadata: Option<Arc<PgStatementMetadata>>,
+    param_types: Vec<Oid>,
+    counter: ResultCounter,
+}
+
+impl PgConnection {
+    /// Execute several statements in a single round trip, yielding the results of each.
+    ///
+    /// The statements are sent together, followed by one `Sync`, so the server runs them one
+    /// after the other without waiting on the client. Unlike a multi-statement [`raw_sql()`],
+    /// each statement may have its own bind arguments and the results can be told apart:
+    /// every item is tagged with the index of the statement which produced it, and each
+    /// statement ends with exactly one [`PgQueryResult`], after all of its rows.
+    ///
+    /// Like a multi-statement `raw_sql()`, the batch runs in a single implicit transaction,
+    /// unless an explicit transaction is already open. If a statement fails, the error ends the
+    /// stream, the remaining statements are skipped and the earlier ones are rolled back.
+    ///
+    /// The [result limits] of each statement, or else those of the connection, apply to the rows
+    /// of that statement alone. A statement exceeding them also ends the stream with an error,
+    /// but the server still runs the rest of the batch, whose results are discarded.
+    ///
+    /// The [`timeout()`] of each statement is ignored: the statements are not cancelled, however
+    /// long they run. Dropping the stream early does not cancel them either; the rest of their
+    /// results are discarded before the next query on the connection.
+    ///
+    /// Statements found in the statement cache reuse the prepared statement; the others are
+    /// parsed as part of the batch and are not added to the cache, so a statement may depend on
+    /// a table created earlier in the same batch. The types of their columns are resolved without
+    /// querying the database, so user-defined types the connection has not seen before are only
+    /// known by their OID.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection, days: i32) -> sqlx::Result<()> {
+    /// use futures_util::TryStreamExt;
+    /// use sqlx::Either;
+    ///
+    /// let mut results = conn.batch([
+    ///     sqlx::query("UPDATE users SET active = false WHERE last_login < now() - make_interval(days => $1)")
+    ///         .bind(days),
+    ///     sqlx::query("DELETE FROM sessions WHERE expires_at < now()"),
+    ///     sqlx::query("SELECT id, name FROM users WHERE active"),
+    /// ]);
+    ///
+    /// while let Some((statement, result)) = results.try_next().await? {
+    ///     match result {
+    ///         Either::Left(done) => println!("{statement}: {} rows affected", done.rows_affected()),
+    ///         Either::Right(row) => println!("{statement}: {row:?}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`raw_sql()`]: sqlx_core::raw_sql::raw_sql
+    /// [result limits]: sqlx_core::query::Query::max_rows
+    /// [`timeout()`]: sqlx_core::query::Query::timeout
+    #[allow(clippy::type_complexity)]
+    pub fn batch<'c, 'q, E>(
+        &'c mut self,
+        statements: impl IntoIterator<Item = E>,
+    ) -> BoxStream<'c, Result<(usize, Either<PgQueryResult, PgRow>), Error>>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let statements = statements
+            .into_iter()
+            .map(|mut query| {
+                let arguments = query.take_arguments().map_err(Error::Encode)?;
+                let limits = query.result_limits();
+                Ok((query.sql(), arguments.unwrap_or_default(), limits))
+            })
+            .collect::<Result<Vec<_>, Error>>();
+
+        Box::pin(try_stream! {
+            let statements = statements?;
+
+            if statements.is_empty() {
+                return Ok(());
+            }
+
+            // before we continue, wait until we are "ready" to accept more queries
+            self.wait_until_ready().await?;
+
+            // anything which may need to query the database has to happen before
+            // the batch is written
+            let mut batch = Vec::with_capacity(statements.len());
+
+            for (sql, mut arguments, limits) in statements {
+                let sql = self.intercept(sql, Some(&arguments), true, QueryLogOptions::default())?;
+                let logger = self.query_logger(sql, QueryLogOptions::default());
+
+                let num_params = u16::try_from(arguments.len()).map_err(|_| {
+                    err_protocol!(
+                        "PgConnection::batch(): too many arguments for query: {}",
+                        arguments.len()
+                    )
+                })?;
+
+                let cached = self
+                    .inner
+                    .cache_statement
+                    .get_mut(logger.sql().as_str())
+                    .cloned();
+
+                // an uncached statement is parsed with the types of the arguments,
+                // which the server then reports as the types of the parameters
+                let parameters = match &cached {
+                    Some((_, metadata)) => metadata.parameters.clone(),
+                    None => arguments.types.clone(),
+                };
+
+                // patch holes created during encoding
+                arguments.apply_patches(self, &parameters).await?;
+
+                let mut param_types = Vec::new();
+
+                if cached.is_none() {
+                    param_types.reserve(arguments.types.len());
+
+                    for ty in &arguments.types {
+                        param_types.push(self.resolve_type_id(&ty.0).await?);
+                    }
+                }
+
+                let (statement, metadata) = cached.unzip();
+
+                batch.push(BatchStatement {
+                    logger,
+                    arguments,
+                    num_params,
+                    statement,
+                    metadata,
+                    param_types,
+                    counter: ResultCounter::new(limits, &self.inner.options),
+                });
+            }
+
+            self.wait_until_ready().await?;
+
+            for statement in &batch {
+                let id = match statement.statement {
+                    Some(id) => id,
+                    None => {
+                        // replaces the unnamed statement of the previous statement in the batch,
+                        // whose portal has already been bound
+                        self.inner.stream.write_msg(Parse {
+                            param_types: &statement.param_types,
+                            query: statement.logger.sql().as_str(),
+                            statement: StatementId::UNNAMED,
+                        })?;
+
+                        StatementId::UNNAMED
+                    }
+                };
+
+                self.inner
+                    .stream
+                    .write_bind(Bind {
+                        portal: PortalId::UNNAMED,
+                        statement: id,
+                        formats: &[PgValueFormat::Binary],
+                        num_params: statement.num_params,
+                        params: &statement.arguments.buffer,
+                        result_formats: &[PgValueFormat::Binary],
+                    })
+                    .await?;
+
+                if statement.metadata.is_none() {
+                    // get the columns of the rows, in the binary format they were bound with
+                    self.inner
+                        .stream
+                        .write_msg(message::Describe::Portal(PortalId::UNNAMED))?;
+                }
+
+                self.inner.stream.write_msg(message::Execute {
+                    portal: PortalId::UNNAMED,
+                    limit: 0,
+                })?;
+            }
+
+            self.inner
+                .stream
+                .write_msg(Close::Portal(PortalId::UNNAMED))?;
+
+            self.write_sync();
+            self.inner.stream.flush().await?;
+
+            let mut index = 0;
+
+            loop {
+                let message = match self.inner.stream.recv().await {
+                    Ok(message) => message,
+                    Err(e) => {
+                        if let Some(statement) = batch.get_mut(index) {
+                            statement.logger.record_error(&e);
+                        }
+
+                        return Err(e);
+                    }
+                };
+
+                match message.format {
+                    BackendMessageFormat::ParseComplete
+                    | BackendMessageFormat::BindComplete
+                    // the statement does not return rows
+                    | BackendMessageFormat::NoData
+                    // unnamed portal has been closed
+                    | BackendMessageFormat::CloseComplete
+                    => {
+                        // harmless messages to ignore
+                    }
+
+                    BackendMessageFormat::RowDescription => {
+                        let (columns, column_names) = self
+                            .handle_row_description(Some(message.decode()?), false, false)
+                            .await?;
+
+                        current(&mut batch, index)?.metadata = Some(Arc::new(PgStatementMetadata {
+                            column_names: Arc::new(column_names),
+                            columns,
+                            parameters: Vec::default(),
+                        }));
+                    }
+
+                    BackendMessageFormat::DataRow => {
+                        let statement = current(&mut batch, index)?;
+
+                        if let Err(e) = statement.counter.count(&message.contents) {
+                            statement.logger.record_error(&e);
+                            return Err(e);
+                        }
+
+                        statement.logger.increment_rows_returned();
+
+                        let metadata = statement.metadata.clone().ok_or_else(|| {
+                            err_protocol!("batch: received DataRow before RowDescription")
+                        })?;
+
+                        let data = self.inner.stream.row_arena.decode(message.contents)?;
+                        let row = PgRow {
+                            data,
+                            format: PgValueFormat::Binary,
+                            metadata,
+                            time_zone: self.inner.stream.time_zone.clone(),
+                        };
+
+                        r#yield!((index, Either::Right(row)));
+                    }
+
+                    // each statement ends with exactly one of these,
+                    // or with an error which ends the batch
+                    BackendMessageFormat::CommandComplete
+                    | BackendMessageFormat::EmptyQueryResponse => {
+                        let rows_affected = if message.format == BackendMessageFormat::CommandComplete {
+                            message.decode::<CommandComplete>()?.rows_affected()
+                        } else {
+                            0
+                        };
+
+                        current(&mut batch, index)?
+                            .logger
+                            .increase_rows_affected(rows_affected);
+
+                        r#yield!((index, Either::Left(PgQueryResult { rows_affected })));
+
+                        index += 1;
+                    }
+
+                    BackendMessageFormat::ReadyForQuery => {
+                        // all statements have been executed
+                        self.handle_ready_for_query(message)?;
+                        break;
+                    }
+
+                    _ => {
+                        return Err(err_protocol!(
+                            "batch: unexpected message: {:?}",
+                            message.format
+                        ));
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+fn current(batch: &mut [BatchStatement], index: usize) -> Result<&mut BatchStatement, Error> {
+    batch
+        .get_mut(index)
+        .ok_or_else(|| err_protocol!("batch: received results for more statements than were sent"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connection::scripted::{self, block_on};
+    use crate::error::Error;
+    use crate::io::StatementId;
+    use crate::statement::PgStatementMetadata;
+    use crate::types::Oid;
+    use crate::{PgConnectOptions, PgQueryResult, PgRow};
+    use futures_util::StreamExt;
+    use sqlx_core::executor::Executor;
+    use sqlx_core::query::query;
+    use sqlx_core::row::Row;
+    use sqlx_core::Either;
+    use std::sync::Arc;
+
+    const INT4: Oid = Oid(23);
+
+    type Item<L, R> = Result<(usize, Either<L, R>), Error>;
+
+    // the rows affected, or the value of the only column of the row
+    fn summarize(results: Vec<Item<PgQueryResult, PgRow>>) -> Vec<Item<u64, i32>> {
+        results
+            .into_iter()
+            .map(|result| {
+                result.map(|(index, result)| {
+                    let result = result
+                        .map_left(|done| done.rows_affected())
+                        .map_right(|row| row.get::<i32, _>(0));
+
+                    (index, result)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn it_tags_the_results_with_the_index_of_their_statement() {
+        let script = [
+            // the cached UPDATE
+            scripted::bind_complete(),
+            scripted::command_complete("UPDATE 3"),
+            // the uncached SELECT
+            scripted::parse_complete(),
+            scripted::bind_complete(),
+            scripted::row_description(&[("n", INT4)]),
+            scripted::data_row(&[&7_i32.to_be_bytes()]),
+            scripted::command_complete("SELECT 1"),
+            scripted::close_complete(),
+            scripted::ready_for_query(),
+        ]
+        .concat();
+
+        let (mut conn, written) = scripted::connect(&PgConnectOptions::new(), script);
+
+        conn.inner.cache_statement.insert(
+            "UPDATE t SET n = 1",
+            (
+                StatementId::NAMED_START,
+                Arc::new(PgStatementMetadata::default()),
+            ),
+        );
+
+        let results = block_on(
+            conn.batch([query("UPDATE t SET n = 1"), query("SELECT 7")])
+                .collect::<Vec<_>>(),
+        );
+
+        let results = summarize(results)
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            results,
+            [
+                (0, Either::Left(3)),
+                (1, Either::Right(7)),
+                (1, Either::Left(1))
+            ]
+        );
+
+        // the cached statement is only bound and executed, the other one is also parsed and
+        // described, then the portal is closed and the batch ends with one `Sync`
+        assert_eq!(written.messages(), "BEPBDECS");
+        assert_eq!(conn.inner.pending_ready_for_query_count, 0);
+    }
+
+    #[test]
+    fn an_error_ends_the_batch_and_the_connection_stays_usable() {
+        let script = [
+            scripted::parse_complete(),
+            scripted::bind_complete(),
+            scripted::no_data(),
+            scripted::command_complete("DELETE 2"),
+            scripted::parse_complete(),
+            scripted::bind_complete(),
+            scripted::no_data(),
+            scripted::error_response("23505", "duplicate key value"),
+            // the server skips the rest of the batch until the `Sync`
+            scripted::ready_for_query(),
+            scripted::row_description(&[("n", INT4)]),
+            scripted::data_row(&[b"3"]),
+            scripted::command_complete("SELECT 1"),
+            scripted::ready_for_query(),
+        ]
+        .concat();
+
+        let (mut conn, written) = scripted::connect(&PgConnectOptions::new(), script);
+
+        let results = block_on(
+            conn.batch([
+                query("DELETE FROM t"),
+                query("INSERT INTO t VALUES (1)"),
+                query("SELECT 1"),
+            ])
+            .collect::<Vec<_>>(),
+        );
+
+        let mut results = summarize(results).into_iter();
+
+        assert_eq!(results.next().unwrap().unwrap(), (0, Either::Left(2)));
+        assert!(matches!(
+            results.next(),
+            Some(Err(Error::Database(e))) if e.code().as_deref() == Some("23505")
+        ));
+        assert!(results.next().is_none());
+
+        assert_eq!(written.messages(), "PBDEPBDEPBDECS");
+
+        // the `ReadyForQuery` of the batch is left for the next query to wait for
+        assert_eq!(conn.inner.pending_ready_for_query_count, 1);
+
+        let rows = block_on(conn.fetch_all("SELECT 3")).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i32, _>(0), 3);
+        assert_eq!(conn.inner.pending_ready_for_query_count, 0);
+    }
+}
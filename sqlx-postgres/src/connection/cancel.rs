@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use crate::connection::stream::PgStream;
+use crate::error::Error;
+use crate::message::CancelRequest;
+use crate::{PgConnectOptions, PgConnection};
+
+// https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS
+
+/// Everything needed to cancel the query running on a connection, without borrowing it.
+pub(crate) struct CancelToken {
+    options: Arc<PgConnectOptions>,
+    process_id: u32,
+    secret_key: u32,
+}
+
+impl PgConnection {
+    pub(crate) fn cancel_token(&self) -> CancelToken {
+        CancelToken {
+            options: Arc::clone(&self.inner.options),
+            process_id: self.inner.process_id,
+            secret_key: self.inner.secret_key,
+        }
+    }
+}
+
+impl CancelToken {
+    /// Ask the server to cancel the query currently running on the connection.
+    ///
+    /// The request is sent over a new connection, which the server closes without replying.
+    /// Whether the query is cancelled can only be told from the original connection: if the
+    /// server was not running a query by the time the request arrives, nothing happens.
+    pub(crate) async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+
+        stream.write(CancelRequest {
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        })?;
+
+        stream.flush().await?;
+        stream.shutdown().await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,108 @@
+use futures_core::future::BoxFuture;
+use sqlx_core::connection::Connection;
+
+use crate::error::Error;
+use crate::{PgConnection, PgTransaction};
+
+const SET_CONTEXT: &str =
+    "SELECT set_config(name, value, true) FROM unnest($1::text[], $2::text[]) AS s(name, value)";
+
+const CURRENT_CONTEXT: &str =
+    "SELECT current_setting(name, true) FROM unnest($1::text[]) WITH ORDINALITY AS s(name, i) \
+     ORDER BY i";
+
+impl PgConnection {
+    /// Run `callback` in a transaction in which each setting in `settings` has the given
+    /// value, as with `SET LOCAL`.
+    ///
+    /// This is how a request passes its context, such as the id of its user, to
+    /// [row-level security](https://www.postgresql.org/docs/current/ddl-rowsecurity.html)
+    /// policies which read it with `current_setting()`. The settings are set with
+    /// `set_config(name, value, true)`, so the values are bound and never interpolated into
+    /// the SQL.
+    ///
+    /// The transaction is committed if `callback` returns `Ok`, and rolled back otherwise.
+    /// Either way, the settings go back to their previous values, so a pooled connection is
+    /// returned to the pool without the context of the last request, even if `callback`
+    /// panics or is cancelled. If the connection is already in a transaction, a savepoint is
+    /// used instead, and the settings are restored when it is released.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool, user_id: i64) -> sqlx::Result<()> {
+    /// let mut conn = pool.acquire().await?;
+    ///
+    /// let documents: Vec<String> = conn
+    ///     .with_context(&[("app.user_id", &user_id.to_string())], |tx| {
+    ///         Box::pin(async move {
+    ///             // filtered by a policy such as
+    ///             // `USING (owner_id = current_setting('app.user_id')::bigint)`
+    ///             sqlx::query_scalar("SELECT title FROM documents")
+    ///                 .fetch_all(&mut **tx)
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_context<F, R, E>(
+        &mut self,
+        settings: &[(&str, &str)],
+        callback: F,
+    ) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut PgTransaction<'_>) -> BoxFuture<'c, Result<R, E>> + Send,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        let names = settings.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+        let values = settings.iter().map(|(_, value)| *value).collect::<Vec<_>>();
+
+        // `SET LOCAL` lasts until the end of the transaction, which releasing a savepoint
+        // does not end, so within a savepoint the previous values are restored by hand
+        let nested = self.inner.transaction_depth > 0;
+
+        let mut transaction = self.begin().await?;
+
+        let previous: Option<Vec<Option<String>>> = if nested {
+            Some(
+                crate::query_scalar::query_scalar(CURRENT_CONTEXT)
+                    .bind(&names)
+                    .unlimited()
+                    .fetch_all(&mut *transaction)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        crate::query::query(SET_CONTEXT)
+            .bind(&names)
+            .bind(&values)
+            .unlimited()
+            .execute(&mut *transaction)
+            .await?;
+
+        match callback(&mut transaction).await {
+            Ok(ret) => {
+                if let Some(previous) = previous {
+                    crate::query::query(SET_CONTEXT)
+                        .bind(&names)
+                        .bind(previous)
+                        .unlimited()
+                        .execute(&mut *transaction)
+                        .await?;
+                }
+
+                transaction.commit().await?;
+
+                Ok(ret)
+            }
+            Err(err) => {
+                transaction.rollback().await?;
+
+                Err(err)
+            }
+        }
+    }
+}
@@ -126,13 +126,13 @@ impl PgConnection {
                 .maybe_fetch_type_info_by_oid(field.data_type_id, fetch_type_info)
                 .await?;
 
-            let origin = if let (Some(relation_oid), Some(attribute_no)) =
+            let (origin, nullable) = if let (Some(relation_oid), Some(attribute_no)) =
                 (field.relation_id, field.relation_attribute_no)
             {
                 self.maybe_fetch_column_origin(relation_oid, attribute_no, fetch_column_description)
                     .await?
             } else {
-                ColumnOrigin::Expression
+                (ColumnOrigin::Expression, None)
             };
 
             let column = PgColumn {
@@ -142,6 +142,9 @@ impl PgConnection {
                 relation_id: field.relation_id,
                 relation_attribute_no: field.relation_attribute_no,
                 origin,
+                nullable,
+                // -1 if the type has no modifier
+                type_modifier: (field.type_modifier >= 0).then_some(field.type_modifier),
             };
 
             columns.push(column);
@@ -164,7 +167,7 @@ impl PgConnection {
         Ok(params)
     }
 
-    async fn maybe_fetch_type_info_by_oid(
+    pub(crate) async fn maybe_fetch_type_info_by_oid(
         &mut self,
         oid: Oid,
         should_fetch: bool,
@@ -209,42 +212,46 @@ impl PgConnection {
         relation_id: Oid,
         attribute_no: i16,
         should_fetch: bool,
-    ) -> Result<ColumnOrigin, Error> {
+    ) -> Result<(ColumnOrigin, Option<bool>), Error> {
         if let Some(origin) = self
             .inner
             .cache_table_to_column_names
             .get(&relation_id)
             .and_then(|table_columns| {
-                let column_name = table_columns.columns.get(&attribute_no).cloned()?;
-
-                Some(ColumnOrigin::Table(TableColumn {
-                    table: table_columns.table_name.clone(),
-                    name: column_name,
-                }))
+                let (column_name, nullable) = table_columns.columns.get(&attribute_no).cloned()?;
+
+                Some((
+                    ColumnOrigin::Table(TableColumn {
+                        table: table_columns.table_name.clone(),
+                        name: column_name,
+                    }),
+                    Some(nullable),
+                ))
             })
         {
             return Ok(origin);
         }
 
         if !should_fetch {
-            return Ok(ColumnOrigin::Unknown);
+            return Ok((ColumnOrigin::Unknown, None));
         }
 
         // Looking up the table name _may_ end up being redundant,
         // but the round-trip to the server is by far the most expensive part anyway.
-        let Some((table_name, column_name)): Option<(String, String)> = query_as(
+        let Some((table_name, column_name, nullable)): Option<(String, String, bool)> = query_as(
             // language=PostgreSQL
-            "SELECT $1::oid::regclass::text, attname \
+            "SELECT $1::oid::regclass::text, attname, NOT attnotnull \
                  FROM pg_catalog.pg_attribute \
                  WHERE attrelid = $1 AND attnum = $2",
         )
         .bind(relation_id)
         .bind(attribute_no)
+        .unlimited()
         .fetch_optional(&mut *self)
         .await?
         else {
             // The column/table doesn't exist anymore for whatever reason.
-            return Ok(ColumnOrigin::Unknown);
+            return Ok((ColumnOrigin::Unknown, None));
         };
 
         let table_columns = self
@@ -256,15 +263,18 @@ impl PgConnection {
                 columns: Default::default(),
             });
 
-        let column_name = table_columns
+        let (column_name, nullable) = table_columns
             .columns
             .entry(attribute_no)
-            .or_insert(column_name.into());
-
-        Ok(ColumnOrigin::Table(TableColumn {
-            table: table_columns.table_name.clone(),
-            name: Arc::clone(column_name),
-        }))
+            .or_insert((column_name.into(), nullable));
+
+        Ok((
+            ColumnOrigin::Table(TableColumn {
+                table: table_columns.table_name.clone(),
+                name: Arc::clone(column_name),
+            }),
+            Some(*nullable),
+        ))
     }
 
     async fn fetch_type_by_oid(&mut self, oid: Oid) -> Result<PgTypeInfo, Error> {
@@ -288,6 +298,7 @@ impl PgConnection {
                      WHERE oid = $1",
         )
         .bind(oid)
+        .unlimited()
         .fetch_one(&mut *self)
         .await?;
 
@@ -343,6 +354,7 @@ ORDER BY enumsortorder
             "#,
         )
         .bind(oid)
+        .unlimited()
         .fetch_all(self)
         .await?;
 
@@ -370,6 +382,7 @@ ORDER BY attnum
                 "#,
         )
         .bind(relation_id)
+        .unlimited()
         .fetch_all(&mut *self)
         .await?;
 
@@ -412,6 +425,7 @@ WHERE rngtypid = $1
                 "#,
         )
         .bind(oid)
+        .unlimited()
         .fetch_one(&mut *self)
         .await?;
 
@@ -445,6 +459,7 @@ WHERE rngtypid = $1
         // language=SQL
         let (oid,): (Oid,) = query_as("SELECT $1::regtype::oid")
             .bind(name)
+            .unlimited()
             .fetch_optional(&mut *self)
             .await?
             .ok_or_else(|| Error::TypeNotFound {
@@ -471,6 +486,7 @@ WHERE rngtypid = $1
         let (elem_oid, array_oid): (Oid, Oid) =
             query_as("SELECT oid, typarray FROM pg_catalog.pg_type WHERE oid = $1::regtype::oid")
                 .bind(&*array.elem_name)
+                .unlimited()
                 .fetch_optional(&mut *self)
                 .await?
                 .ok_or_else(|| Error::TypeNotFound {
@@ -563,6 +579,7 @@ WHERE rngtypid = $1
 
         let mut nullables: Vec<Option<bool>> = nullable_query
             .build_query_scalar()
+            .unlimited()
             .fetch_all(&mut *self)
             .await
             .map_err(|e| {
@@ -619,8 +636,10 @@ WHERE rngtypid = $1
             explain += ")";
         }
 
-        let (Json(explains),): (Json<SmallVec<[Explain; 1]>>,) =
-            query_as(AssertSqlSafe(explain)).fetch_one(self).await?;
+        let (Json(explains),): (Json<SmallVec<[Explain; 1]>>,) = query_as(AssertSqlSafe(explain))
+            .unlimited()
+            .fetch_one(self)
+            .await?;
 
         let mut nullables = Vec::new();
 
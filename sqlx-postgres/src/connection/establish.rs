@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::HashMap;
 
 use crate::common::StatementCache;
@@ -30,7 +32,7 @@ impl PgConnection {
             // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
             ("client_encoding", "UTF8"),
             // Sets the time zone for displaying and interpreting time stamps.
-            ("TimeZone", "UTC"),
+            ("TimeZone", &options.time_zone),
         ];
 
         if let Some(ref extra_float_digits) = options.extra_float_digits {
@@ -140,6 +142,7 @@ impl PgConnection {
                 stream,
                 process_id,
                 secret_key,
+                options: Arc::new(options.clone()),
                 transaction_status,
                 transaction_depth: 0,
                 pending_ready_for_query_count: 0,
@@ -150,6 +153,8 @@ impl PgConnection {
                 cache_elem_type_to_array: HashMap::new(),
                 cache_table_to_column_names: HashMap::new(),
                 log_settings: options.log_settings.clone(),
+                #[cfg(feature = "otel")]
+                transaction_span: None,
             }),
         })
     }
@@ -1,26 +1,84 @@
 use crate::describe::Describe;
-use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::error::{Error, ResultLimit};
+use crate::executor::{Execute, Executor, ResultLimits};
 use crate::io::{PortalId, StatementId};
-use crate::logger::QueryLogger;
+use crate::logger::QueryLogOptions;
 use crate::message::{
-    self, BackendMessageFormat, Bind, Close, CommandComplete, DataRow, ParameterDescription, Parse,
+    self, BackendMessageFormat, Bind, Close, CommandComplete, ParameterDescription, Parse,
     ParseComplete, Query, RowDescription,
 };
 use crate::statement::PgStatementMetadata;
 use crate::{
-    statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
-    PgValueFormat, Postgres,
+    statement::PgStatement, PgArguments, PgConnectOptions, PgConnection, PgQueryResult, PgRow,
+    PgTypeInfo, PgValueFormat, Postgres,
 };
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
-use futures_util::TryStreamExt;
+use futures_util::{TryFutureExt, TryStreamExt};
 use sqlx_core::arguments::Arguments;
+use sqlx_core::rt;
 use sqlx_core::sql_str::SqlStr;
 use sqlx_core::Either;
+use std::time::{Duration, Instant};
 use std::{pin::pin, sync::Arc};
 
+// how often to repeat a cancel request, until the cancelled query ends
+const CANCEL_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+// the settings of a query on how it is run, rather than what it runs
+#[derive(Debug, Clone, Copy)]
+struct RunOptions {
+    timeout: Option<Duration>,
+    log_options: QueryLogOptions,
+    limits: ResultLimits,
+}
+
+impl RunOptions {
+    fn of<'q>(query: &impl Execute<'q, Postgres>) -> Self {
+        RunOptions {
+            timeout: query.timeout(),
+            log_options: query.log_options(),
+            limits: query.result_limits(),
+        }
+    }
+}
+
+// counts the rows returned by a query, failing it once they exceed its limits
+pub(super) struct ResultCounter {
+    max_rows: Option<u64>,
+    max_bytes: Option<u64>,
+    rows: u64,
+    bytes: u64,
+}
+
+impl ResultCounter {
+    pub(super) fn new(limits: ResultLimits, options: &PgConnectOptions) -> Self {
+        // the limits of the query take precedence over those of the connection
+        ResultCounter {
+            max_rows: limits.max_rows().or(options.max_rows),
+            max_bytes: limits.max_result_bytes().or(options.max_result_bytes),
+            rows: 0,
+            bytes: 0,
+        }
+    }
+
+    // count a `DataRow` with the given contents; on error, the rest of the result is left
+    // to be discarded with the next query, as when the stream is dropped
+    pub(super) fn count(&mut self, row: &[u8]) -> Result<(), Error> {
+        self.rows += 1;
+        self.bytes += row.len() as u64;
+
+        match (self.max_rows, self.max_bytes) {
+            (Some(max), _) if self.rows > max => Err(Error::ResultTooLarge(ResultLimit::Rows(max))),
+            (_, Some(max)) if self.bytes > max => {
+                Err(Error::ResultTooLarge(ResultLimit::Bytes(max)))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 async fn prepare(
     conn: &mut PgConnection,
     sql: &str,
@@ -208,20 +266,95 @@ impl PgConnection {
         Ok(statement)
     }
 
-    pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
+    // like `run`, but cancels the query if it has not completed within `timeout`
+    fn run_with_timeout<'e, 'c: 'e>(
         &'c mut self,
         query: SqlStr,
         arguments: Option<PgArguments>,
         persistent: bool,
-        metadata_opt: Option<Arc<PgStatementMetadata>>,
-    ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
-        let mut logger = QueryLogger::new(query, self.inner.log_settings.clone());
-        let sql = logger.sql().as_str();
+        metadata: Option<Arc<PgStatementMetadata>>,
+        options: RunOptions,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, Error>> {
+        let RunOptions {
+            timeout,
+            log_options,
+            limits,
+        } = options;
+
+        let Some(timeout) = timeout else {
+            return Box::pin(
+                self.run(query, arguments, persistent, metadata, log_options, limits)
+                    .try_flatten_stream(),
+            );
+        };
 
+        let deadline = Instant::now() + timeout;
+        let token = self.cancel_token();
+
+        Box::pin(try_stream! {
+            let mut s = pin!(self
+                .run(query, arguments, persistent, metadata, log_options, limits)
+                .try_flatten_stream());
+
+            let mut timed_out = false;
+
+            loop {
+                // once the query has been cancelled, keep reading until the server is done with
+                // it, so the connection is left ready for the next query; the cancel request is
+                // repeated in case it arrived before the query had started
+                let wait = if timed_out {
+                    CANCEL_RETRY_INTERVAL
+                } else {
+                    deadline.saturating_duration_since(Instant::now())
+                };
+
+                let Ok(next) = rt::timeout(wait, s.try_next()).await else {
+                    timed_out = true;
+                    token.cancel().await?;
+                    continue;
+                };
+
+                match next {
+                    Ok(Some(v)) => {
+                        if !timed_out {
+                            r#yield!(v);
+                        }
+                    }
+
+                    Ok(None) => break,
+
+                    // query_canceled
+                    Err(Error::Database(e)) if timed_out && e.code().as_deref() == Some("57014") => {
+                        return Err(Error::Timeout);
+                    }
+
+                    Err(e) => return Err(e),
+                }
+            }
+
+            if timed_out {
+                // the query completed before the cancel request reached it;
+                // its results were already discarded
+                return Err(Error::Timeout);
+            }
+
+            Ok(())
+        })
+    }
+
+    // write the messages to execute the query, returning the metadata of its rows if known
+    // and the format they are returned in
+    async fn write_query(
+        &mut self,
+        sql: &str,
+        arguments: Option<PgArguments>,
+        persistent: bool,
+        metadata_opt: Option<Arc<PgStatementMetadata>>,
+    ) -> Result<(Arc<PgStatementMetadata>, PgValueFormat), Error> {
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
-        let mut metadata: Arc<PgStatementMetadata>;
+        let metadata: Arc<PgStatementMetadata>;
 
         let format = if let Some(mut arguments) = arguments {
             // Check this before we write anything to the stream.
@@ -252,14 +385,17 @@ impl PgConnection {
             self.wait_until_ready().await?;
 
             // bind to attach the arguments to the statement and create a portal
-            self.inner.stream.write_msg(Bind {
-                portal: PortalId::UNNAMED,
-                statement,
-                formats: &[PgValueFormat::Binary],
-                num_params,
-                params: &arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
-            })?;
+            self.inner
+                .stream
+                .write_bind(Bind {
+                    portal: PortalId::UNNAMED,
+                    statement,
+                    formats: &[PgValueFormat::Binary],
+                    num_params,
+                    params: &arguments.buffer,
+                    result_formats: &[PgValueFormat::Binary],
+                })
+                .await?;
 
             // executes the portal up to the passed limit
             // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
@@ -285,8 +421,7 @@ impl PgConnection {
             // finally, [Sync] asks postgres to process the messages that we sent and respond with
             // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
             // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
-            // is still serial but it would reduce round-trips. Some kind of builder pattern that is
-            // termed batching might suit this.
+            // is still serial but it reduces round-trips; `PgConnection::batch()` does this.
             self.write_sync();
 
             // prepared statements are binary
@@ -305,9 +440,67 @@ impl PgConnection {
 
         self.inner.stream.flush().await?;
 
+        Ok((metadata, format))
+    }
+
+    pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
+        &'c mut self,
+        query: SqlStr,
+        arguments: Option<PgArguments>,
+        persistent: bool,
+        metadata_opt: Option<Arc<PgStatementMetadata>>,
+        log_options: QueryLogOptions,
+        limits: ResultLimits,
+    ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
+        let query = self.intercept(query, arguments.as_ref(), persistent, log_options)?;
+        let mut logger = self.query_logger(query, log_options);
+
+        if let (Some(arguments), Some(safe_types)) = (&arguments, logger.bind_value_types()) {
+            if !arguments.types.is_empty() {
+                let bind_values = arguments.to_log_string(safe_types);
+                logger.set_bind_values(bind_values);
+            }
+        }
+
+        let mut sql = logger.sql().as_str();
+        let mut persistent = persistent;
+
+        // a statement with trace context in its comment is unique to this execution,
+        // so it is not worth caching
+        let commented = self
+            .inner
+            .options
+            .sql_commenter
+            .as_ref()
+            .and_then(|commenter| commenter.apply_with_trace_context(sql));
+
+        if let Some((commented, has_trace_context)) = &commented {
+            sql = commented;
+            persistent &= !has_trace_context;
+        }
+
+        let mut counter = ResultCounter::new(limits, &self.inner.options);
+
+        let (mut metadata, format) = match self
+            .write_query(sql, arguments, persistent, metadata_opt)
+            .await
+        {
+            Ok(written) => written,
+            Err(e) => {
+                logger.record_error(&e);
+                return Err(e);
+            }
+        };
+
         Ok(try_stream! {
             loop {
-                let message = self.inner.stream.recv().await?;
+                let message = match self.inner.stream.recv().await {
+                    Ok(message) => message,
+                    Err(e) => {
+                        logger.record_error(&e);
+                        return Err(e);
+                    }
+                };
 
                 match message.format {
                     BackendMessageFormat::BindComplete
@@ -358,14 +551,20 @@ impl PgConnection {
                     }
 
                     BackendMessageFormat::DataRow => {
+                        if let Err(e) = counter.count(&message.contents) {
+                            logger.record_error(&e);
+                            return Err(e);
+                        }
+
                         logger.increment_rows_returned();
 
                         // one of the set of rows returned by a SELECT, FETCH, etc query
-                        let data: DataRow = message.decode()?;
+                        let data = self.inner.stream.row_arena.decode(message.contents)?;
                         let row = PgRow {
                             data,
                             format,
                             metadata: Arc::clone(&metadata),
+                            time_zone: self.inner.stream.time_zone.clone(),
                         };
 
                         r#yield!(Either::Right(row));
@@ -409,11 +608,18 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let options = RunOptions::of(&query);
         let sql = query.sql();
 
         Box::pin(try_stream! {
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, persistent, metadata).await?);
+            let mut s = self.run_with_timeout(
+                sql,
+                arguments,
+                persistent,
+                metadata,
+                options,
+            );
 
             while let Some(v) = s.try_next().await? {
                 r#yield!(v);
@@ -435,11 +641,12 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments().map_err(Error::Encode);
         let persistent = query.persistent();
+        let options = RunOptions::of(&query);
 
         Box::pin(async move {
             let sql = query.sql();
             let arguments = arguments?;
-            let mut s = pin!(self.run(sql, arguments, persistent, metadata).await?);
+            let mut s = self.run_with_timeout(sql, arguments, persistent, metadata, options);
 
             // With deferred constraints we need to check all responses as we
             // could get a OK response (with uncommitted data), only to get an
@@ -496,3 +703,93 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ResultCounter;
+    use crate::connection::scripted::{self, block_on};
+    use crate::error::{Error, ResultLimit};
+    use crate::executor::{Execute, Executor};
+    use crate::types::Oid;
+    use crate::{PgConnectOptions, Postgres};
+    use sqlx_core::query::query;
+    use sqlx_core::row::Row;
+
+    const INT4: Oid = Oid(23);
+
+    fn count_rows(mut counter: ResultCounter, rows: &[&[u8]]) -> Result<(), Error> {
+        rows.iter().try_for_each(|row| counter.count(row))
+    }
+
+    #[test]
+    fn fails_once_the_rows_exceed_the_limit() {
+        let options = PgConnectOptions::new().max_rows(2);
+        let limits = Execute::<Postgres>::result_limits(&query("SELECT 1"));
+
+        assert!(count_rows(ResultCounter::new(limits, &options), &[b"1", b"2"]).is_ok());
+        assert!(matches!(
+            count_rows(ResultCounter::new(limits, &options), &[b"1", b"2", b"3"]),
+            Err(Error::ResultTooLarge(ResultLimit::Rows(2)))
+        ));
+    }
+
+    #[test]
+    fn fails_once_the_bytes_exceed_the_limit() {
+        let options = PgConnectOptions::new().max_result_bytes(4);
+        let limits = Execute::<Postgres>::result_limits(&query("SELECT 1"));
+
+        assert!(count_rows(ResultCounter::new(limits, &options), &[b"12", b"34"]).is_ok());
+        assert!(matches!(
+            count_rows(ResultCounter::new(limits, &options), &[b"12", b"345"]),
+            Err(Error::ResultTooLarge(ResultLimit::Bytes(4)))
+        ));
+    }
+
+    #[test]
+    fn the_limits_of_the_query_override_the_connection() {
+        let options = PgConnectOptions::new().max_rows(1).max_result_bytes(1);
+
+        let raised = Execute::<Postgres>::result_limits(&query("SELECT 1").max_rows(3));
+        assert!(matches!(
+            count_rows(ResultCounter::new(raised, &options), &[b"1", b"2"]),
+            Err(Error::ResultTooLarge(ResultLimit::Bytes(1)))
+        ));
+
+        let lowered = Execute::<Postgres>::result_limits(&query("SELECT 1").max_rows(0));
+        assert!(matches!(
+            count_rows(ResultCounter::new(lowered, &options), &[b"1"]),
+            Err(Error::ResultTooLarge(ResultLimit::Rows(0)))
+        ));
+
+        let unlimited = Execute::<Postgres>::result_limits(&query("SELECT 1").unlimited());
+        assert!(count_rows(ResultCounter::new(unlimited, &options), &[b"12", b"34"]).is_ok());
+    }
+
+    #[test]
+    fn the_connection_is_usable_after_a_result_is_too_large() {
+        let script = [
+            scripted::row_description(&[("n", INT4)]),
+            scripted::data_row(&[b"1"]),
+            scripted::data_row(&[b"2"]),
+            scripted::command_complete("SELECT 2"),
+            scripted::ready_for_query(),
+            scripted::row_description(&[("n", INT4)]),
+            scripted::data_row(&[b"3"]),
+            scripted::command_complete("SELECT 1"),
+            scripted::ready_for_query(),
+        ]
+        .concat();
+
+        let (mut conn, _) = scripted::connect(&PgConnectOptions::new().max_rows(1), script);
+
+        assert!(matches!(
+            block_on(conn.fetch_all("SELECT n FROM generate_series(1, 2) n")),
+            Err(Error::ResultTooLarge(ResultLimit::Rows(1)))
+        ));
+
+        // the rest of the first result is discarded before the next query
+        let rows = block_on(conn.fetch_all("SELECT 3")).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i32, _>(0), 3);
+    }
+}
@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use crate::HashMap;
 
@@ -16,17 +17,25 @@ use crate::message::{
 use crate::statement::PgStatementMetadata;
 use crate::transaction::Transaction;
 use crate::types::Oid;
-use crate::{PgConnectOptions, PgTypeInfo, Postgres};
+use crate::{PgArguments, PgConnectOptions, PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::connection::*;
-use sqlx_core::sql_str::SqlSafeStr;
+use sqlx_core::interceptor::InterceptedQuery;
+use sqlx_core::logger::{QueryLogOptions, QueryLogger};
+use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
+use sqlx_core::statistics::{StatementStatistics, StatementStats};
 
 pub use self::stream::PgStream;
 
+mod batch;
+mod cancel;
+mod context;
 pub(crate) mod describe;
 mod establish;
 mod executor;
 mod sasl;
+#[cfg(test)]
+mod scripted;
 mod stream;
 mod tls;
 
@@ -45,14 +54,16 @@ pub struct PgConnectionInner {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // options the connection was established with
+    // used to open the connection cancel requests are sent over
+    options: Arc<PgConnectOptions>,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: StatementId,
@@ -74,12 +85,16 @@ pub struct PgConnectionInner {
     pub(crate) transaction_depth: usize,
 
     log_settings: LogSettings,
+
+    // span of the current transaction, which the spans of its queries are children of
+    #[cfg(feature = "otel")]
+    pub(crate) transaction_span: Option<tracing::Span>,
 }
 
 pub(crate) struct TableColumns {
     table_name: Arc<str>,
-    /// Attribute number -> name.
-    columns: BTreeMap<i16, Arc<str>>,
+    /// Attribute number -> name, and whether the column is nullable.
+    columns: BTreeMap<i16, (Arc<str>, bool)>,
 }
 
 impl PgConnection {
@@ -88,6 +103,17 @@ impl PgConnection {
         self.inner.stream.server_version_num
     }
 
+    /// A snapshot of the statistics of the statements executed by this connection, and by
+    /// any other connection sharing its options, if enabled with
+    /// [`collect_statistics()`][sqlx_core::connection::ConnectOptions::collect_statistics].
+    pub fn statistics(&self) -> Option<Vec<StatementStats>> {
+        self.inner
+            .log_settings
+            .statistics
+            .as_ref()
+            .map(StatementStatistics::snapshot)
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.inner.stream.write_buffer_mut().is_empty() {
@@ -138,6 +164,86 @@ impl PgConnection {
         Ok(())
     }
 
+    // start logging a query, in a span of its own if enabled
+    pub(crate) fn query_logger(&self, sql: SqlStr, log_options: QueryLogOptions) -> QueryLogger {
+        let logger = QueryLogger::new(sql, self.inner.log_settings.clone())
+            .with_options(log_options)
+            .with_connection_id(self.inner.process_id.into());
+
+        #[cfg(feature = "otel")]
+        let logger = {
+            let span = sqlx_core::otel::query_span(
+                &self.otel_attributes(),
+                logger.sql().as_str(),
+                log_options.name(),
+                self.inner.transaction_span.as_ref(),
+            );
+
+            logger.with_span(span)
+        };
+
+        logger
+    }
+
+    // pass a statement through the interceptors of the connection, which may rewrite it;
+    // a rejected statement is logged as failed
+    pub(crate) fn intercept(
+        &self,
+        sql: SqlStr,
+        arguments: Option<&PgArguments>,
+        persistent: bool,
+        log_options: QueryLogOptions,
+    ) -> Result<SqlStr, Error> {
+        let interceptors = &self.inner.options.interceptors;
+
+        if interceptors.is_empty() {
+            return Ok(sql);
+        }
+
+        let argument_types = arguments
+            .map(|arguments| {
+                arguments
+                    .types
+                    .iter()
+                    .map(|ty| ty.display_name())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        let query = InterceptedQuery::new(
+            sql.as_str(),
+            &argument_types,
+            persistent,
+            log_options.name(),
+        );
+
+        match interceptors.intercept(&query) {
+            Ok(Some(rewritten)) => Ok(AssertSqlSafe(rewritten).into_sql_str()),
+            Ok(None) => Ok(sql),
+            Err(e) => {
+                self.query_logger(sql, log_options).record_error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    #[cfg(feature = "otel")]
+    pub(crate) fn otel_attributes(&self) -> sqlx_core::otel::ConnectionAttributes<'_> {
+        let options = &self.inner.options;
+
+        let (server_address, server_port) = match &options.socket {
+            Some(socket) => (socket.to_str(), None),
+            None => (Some(options.host.as_str()), Some(options.port)),
+        };
+
+        sqlx_core::otel::ConnectionAttributes {
+            system: "postgresql",
+            database: options.database.as_deref(),
+            server_address,
+            server_port,
+        }
+    }
+
     pub(crate) fn in_transaction(&self) -> bool {
         match self.inner.transaction_status {
             TransactionStatus::Transaction => true,
@@ -175,14 +281,20 @@ impl Connection for PgConnection {
         Ok(())
     }
 
-    async fn ping(&mut self) -> Result<(), Error> {
+    async fn ping(&mut self) -> Result<Duration, Error> {
         // Users were complaining about this showing up in query statistics on the server.
         // By sending a comment we avoid an error if the connection was in the middle of a rowset
         // self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
 
+        // Drain anything still in flight first, so it is not counted in the round trip.
+        self.wait_until_ready().await?;
+
         // The simplest call-and-response that's possible.
+        let start = Instant::now();
         self.write_sync();
-        self.wait_until_ready().await
+        self.wait_until_ready().await?;
+
+        Ok(start.elapsed())
     }
 
     fn begin(
@@ -230,6 +342,7 @@ impl Connection for PgConnection {
 
     fn shrink_buffers(&mut self) {
         self.inner.stream.shrink_buffers();
+        self.inner.stream.row_arena.shrink();
     }
 
     #[doc(hidden)]
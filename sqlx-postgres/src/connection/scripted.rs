@@ -0,0 +1,180 @@
+// a connection to a server which replies with a script, for testing how the connection
+// handles the messages of the protocol without a database
+
+use std::future::Future;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use futures_util::FutureExt;
+use sqlx_core::io::ReadBuf;
+use sqlx_core::net::Socket;
+
+use super::{PgConnection, PgConnectionInner, PgStream};
+use crate::common::StatementCache;
+use crate::io::StatementId;
+use crate::message::TransactionStatus;
+use crate::types::Oid;
+use crate::{HashMap, PgConnectOptions};
+
+/// Replies with `input` and records what is written.
+struct Scripted {
+    input: Vec<u8>,
+    output: Written,
+}
+
+/// What the connection wrote to the scripted server.
+#[derive(Clone, Default)]
+pub(crate) struct Written(Arc<Mutex<Vec<u8>>>);
+
+impl Written {
+    /// The types of the messages written, e.g. `"PDS"` for `Parse`, `Describe` and `Sync`.
+    pub(crate) fn messages(&self) -> String {
+        let output = self.0.lock().unwrap();
+        let mut rest = &output[..];
+        let mut messages = String::new();
+
+        while let [format, len @ ..] = rest {
+            let len = u32::from_be_bytes(len[..4].try_into().unwrap()) as usize;
+            messages.push(char::from(*format));
+            rest = &rest[1 + len..];
+        }
+
+        messages
+    }
+}
+
+impl Socket for Scripted {
+    fn try_read(&mut self, buf: &mut dyn ReadBuf) -> io::Result<usize> {
+        let buf = buf.init_mut();
+        let n = std::cmp::min(self.input.len(), buf.len());
+        buf[..n].copy_from_slice(&self.input[..n]);
+        self.input.drain(..n);
+        Ok(n)
+    }
+
+    fn try_write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.output.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn poll_read_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_write_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A connection, as established with `options`, whose server replies with `script`.
+pub(crate) fn connect(options: &PgConnectOptions, script: Vec<u8>) -> (PgConnection, Written) {
+    let written = Written::default();
+    let socket = Scripted {
+        input: script,
+        output: written.clone(),
+    };
+    let stream = PgStream::new(Box::new(socket), options);
+
+    let conn = PgConnection {
+        inner: Box::new(PgConnectionInner {
+            stream,
+            process_id: 0,
+            secret_key: 0,
+            options: Arc::new(options.clone()),
+            transaction_status: TransactionStatus::Idle,
+            transaction_depth: 0,
+            pending_ready_for_query_count: 0,
+            next_statement_id: StatementId::NAMED_START,
+            cache_statement: StatementCache::new(options.statement_cache_capacity),
+            cache_type_oid: HashMap::new(),
+            cache_type_info: HashMap::new(),
+            cache_elem_type_to_array: HashMap::new(),
+            cache_table_to_column_names: HashMap::new(),
+            log_settings: options.log_settings.clone(),
+            #[cfg(feature = "otel")]
+            transaction_span: None,
+        }),
+    };
+
+    (conn, written)
+}
+
+/// Run `future` to completion; the script is always ready, so it never has to wait.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    future
+        .now_or_never()
+        .expect("the connection waited on the scripted server")
+}
+
+fn message(format: u8, body: &[u8]) -> Vec<u8> {
+    let mut message = vec![format];
+    message.extend_from_slice(&i32::try_from(body.len() + 4).unwrap().to_be_bytes());
+    message.extend_from_slice(body);
+    message
+}
+
+pub(crate) fn row_description(columns: &[(&str, Oid)]) -> Vec<u8> {
+    let mut body = i16::try_from(columns.len()).unwrap().to_be_bytes().to_vec();
+
+    for (name, oid) in columns {
+        body.extend_from_slice(name.as_bytes());
+        body.push(0);
+        // not a column of a table
+        body.extend_from_slice(&0_u32.to_be_bytes());
+        body.extend_from_slice(&0_i16.to_be_bytes());
+        body.extend_from_slice(&oid.0.to_be_bytes());
+        // size and modifier of the type
+        body.extend_from_slice(&(-1_i16).to_be_bytes());
+        body.extend_from_slice(&(-1_i32).to_be_bytes());
+        // text format
+        body.extend_from_slice(&0_i16.to_be_bytes());
+    }
+
+    message(b'T', &body)
+}
+
+pub(crate) fn data_row(values: &[&[u8]]) -> Vec<u8> {
+    let mut body = i16::try_from(values.len()).unwrap().to_be_bytes().to_vec();
+
+    for value in values {
+        body.extend_from_slice(&i32::try_from(value.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(value);
+    }
+
+    message(b'D', &body)
+}
+
+pub(crate) fn command_complete(tag: &str) -> Vec<u8> {
+    message(b'C', format!("{tag}\0").as_bytes())
+}
+
+pub(crate) fn error_response(code: &str, text: &str) -> Vec<u8> {
+    let body = format!("SERROR\0VERROR\0C{code}\0M{text}\0\0");
+
+    message(b'E', body.as_bytes())
+}
+
+pub(crate) fn parse_complete() -> Vec<u8> {
+    message(b'1', b"")
+}
+
+pub(crate) fn bind_complete() -> Vec<u8> {
+    message(b'2', b"")
+}
+
+pub(crate) fn close_complete() -> Vec<u8> {
+    message(b'3', b"")
+}
+
+pub(crate) fn no_data() -> Vec<u8> {
+    message(b'n', b"")
+}
+
+pub(crate) fn ready_for_query() -> Vec<u8> {
+    message(b'Z', b"I")
+}
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use std::ops::{ControlFlow, Deref, DerefMut};
 use std::str::FromStr;
+use std::sync::Arc;
 
 use futures_channel::mpsc::UnboundedSender;
 use futures_util::SinkExt;
@@ -10,8 +11,8 @@ use sqlx_core::bytes::Buf;
 use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
 use crate::message::{
-    BackendMessage, BackendMessageFormat, EncodeMessage, FrontendMessage, Notice, Notification,
-    ParameterStatus, ReceivedMessage,
+    BackendMessage, BackendMessageFormat, Bind, EncodeMessage, FrontendMessage, Notice,
+    Notification, ParameterStatus, ReceivedMessage, RowArena,
 };
 use crate::net::{self, BufferedSocket, Socket};
 use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
@@ -38,23 +39,95 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    // the current `TimeZone` of the session, shared with the rows returned by queries
+    pub(crate) time_zone: Option<Arc<str>>,
+
+    // the largest message to buffer; larger ones are dropped and fail with an error
+    max_message_size: Option<usize>,
+
+    // where the column ranges of received rows are stored
+    pub(crate) row_arena: RowArena,
 }
 
 impl PgStream {
     pub(super) async fn connect(options: &PgConnectOptions) -> Result<Self, Error> {
+        #[cfg(all(feature = "ssh-tunnel", unix))]
+        if let Some(tunnel) = &options.ssh_tunnel {
+            if options.fetch_socket().is_some() {
+                return Err(Error::Configuration(
+                    "Unix domain sockets cannot be tunneled over SSH; set a host name".into(),
+                ));
+            }
+
+            if options.proxy.is_some() {
+                return Err(Error::Configuration(
+                    "a proxy cannot be combined with an SSH tunnel".into(),
+                ));
+            }
+
+            let socket = net::ssh::connect_ssh(
+                tunnel,
+                &options.host,
+                options.port,
+                MaybeUpgradeTls(options),
+            )
+            .await??;
+
+            return Ok(Self::new(socket, options));
+        }
+
+        if let Some(proxy) = &options.proxy {
+            if options.fetch_socket().is_some() {
+                return Err(Error::Configuration(
+                    "Unix domain sockets cannot be proxied; set a host name".into(),
+                ));
+            }
+
+            let socket = net::proxy::connect_proxy(
+                proxy,
+                &options.tcp,
+                &options.host,
+                options.port,
+                MaybeUpgradeTls(options),
+            )
+            .await??;
+
+            return Ok(Self::new(socket, options));
+        }
+
         let socket_result = match options.fetch_socket() {
             Some(ref path) => net::connect_uds(path, MaybeUpgradeTls(options)).await?,
-            None => net::connect_tcp(&options.host, options.port, MaybeUpgradeTls(options)).await?,
+            None => {
+                net::connect_tcp_with_options(
+                    &options.host,
+                    options.port,
+                    &options.tcp,
+                    MaybeUpgradeTls(options),
+                )
+                .await?
+            }
         };
 
         let socket = socket_result?;
 
-        Ok(Self {
-            inner: BufferedSocket::new(socket),
+        Ok(Self::new(socket, options))
+    }
+
+    pub(super) fn new(socket: Box<dyn Socket>, options: &PgConnectOptions) -> Self {
+        Self {
+            inner: BufferedSocket::with_capacity(
+                socket,
+                options.read_buffer_size,
+                options.write_buffer_size,
+            ),
+            max_message_size: options.max_message_size,
+            row_arena: RowArena::default(),
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
-        })
+            time_zone: None,
+        }
     }
 
     #[inline(always)]
@@ -62,6 +135,24 @@ impl PgStream {
         self.write(EncodeMessage(message))
     }
 
+    /// Write a `Bind` message, flushing it along with the messages before it if its parameters
+    /// do not fit the write buffer.
+    ///
+    /// Large parameters are then written from the arguments they were encoded into,
+    /// instead of being copied into the write buffer first.
+    pub(crate) async fn write_bind(&mut self, bind: Bind<'_>) -> Result<(), Error> {
+        if bind.params.len() <= self.inner.write_buffer().initial_capacity() {
+            return self.write_msg(bind);
+        }
+
+        let (head, tail) = bind.encode_split()?;
+
+        self.inner.write(&head[..])?;
+        self.inner.flush_with(bind.params, &tail[..]).await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn send<T>(&mut self, message: T) -> Result<(), Error>
     where
         T: FrontendMessage,
@@ -79,7 +170,10 @@ impl PgStream {
     pub(crate) async fn recv_unchecked(&mut self) -> Result<ReceivedMessage, Error> {
         // NOTE: to not break everything, this should be cancel-safe;
         // DO NOT modify `buf` unless a full message has been read
-        self.inner
+        let max_message_size = self.max_message_size;
+
+        let received = self
+            .inner
             .try_read(|buf| {
                 // all packets in postgres start with a 5-byte header
                 // this header contains the message type and the total length of the message
@@ -98,6 +192,10 @@ impl PgStream {
                         err_protocol!("message_len + 1 overflows usize: {message_len}")
                     })?;
 
+                if max_message_size.is_some_and(|max| message_len > max) {
+                    return Ok(ControlFlow::Break(Err((format, message_len))));
+                }
+
                 if buf.len() < expected_len {
                     return Ok(ControlFlow::Continue(expected_len));
                 }
@@ -113,9 +211,24 @@ impl PgStream {
                 // cut off the length prefix
                 contents.advance(4);
 
-                Ok(ControlFlow::Break(ReceivedMessage { format, contents }))
+                Ok(ControlFlow::Break(Ok(ReceivedMessage { format, contents })))
             })
-            .await
+            .await?;
+
+        match received {
+            Ok(message) => Ok(message),
+            Err((format, len)) => {
+                // drop the message in pieces, to stay in sync with the server
+                // (the format code is not counted in the length)
+                self.inner.discard(len + 1).await?;
+
+                Err(err_protocol!(
+                    "server sent a {format:?} message of {len} bytes, more than the \
+                     `max_message_size` of {} bytes",
+                    max_message_size.unwrap_or_default()
+                ))
+            }
+        }
     }
 
     // Get the next message from the server
@@ -150,6 +263,11 @@ impl PgStream {
                         "server_version" => {
                             self.server_version_num = parse_server_version(&value);
                         }
+                        // also reported when changed with `SET [LOCAL] TIME ZONE`
+                        "TimeZone" => {
+                            self.time_zone = Some(value.as_str().into());
+                            self.parameter_statuses.insert(name, value);
+                        }
                         _ => {
                             self.parameter_statuses.insert(name, value);
                         }
@@ -55,9 +55,14 @@ async fn maybe_upgrade<S: Socket>(
         accept_invalid_certs,
         accept_invalid_hostnames,
         hostname: &options.host,
+        port: options.port,
         root_cert_path: options.ssl_root_cert.as_ref(),
         client_cert_path: options.ssl_client_cert.as_ref(),
         client_key_path: options.ssl_client_key.as_ref(),
+        client_pkcs12: options.ssl_client_pkcs12.as_ref(),
+        client_key_password: options.ssl_client_key_password.as_deref(),
+        rustls_client_config: options.ssl_client_config.as_ref(),
+        session_cache: options.ssl_session_cache.as_ref(),
     };
 
     tls::handshake(socket, config, SocketIntoBox).await
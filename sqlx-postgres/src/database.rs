@@ -35,6 +35,8 @@ impl Database for Postgres {
     const NAME: &'static str = "PostgreSQL";
 
     const URL_SCHEMES: &'static [&'static str] = &["postgres", "postgresql"];
+
+    const MAX_ARGUMENTS: usize = 65535;
 }
 
 impl HasStatementCache for Postgres {}
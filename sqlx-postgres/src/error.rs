@@ -189,10 +189,9 @@ impl DatabaseError for PgDatabaseError {
     fn is_transient_in_connect_phase(&self) -> bool {
         // https://www.postgresql.org/docs/current/errcodes-appendix.html
         [
-            // too_many_connections
             // This may be returned if we just un-gracefully closed a connection,
             // give the database a chance to notice it and clean it up.
-            "53300",
+            error_codes::TOO_MANY_CONNECTIONS,
             // cannot_connect_now
             // Returned if the database is still starting up.
             "57P03",
@@ -200,14 +199,42 @@ impl DatabaseError for PgDatabaseError {
         .contains(&self.code())
     }
 
+    fn is_transient(&self) -> bool {
+        // https://www.postgresql.org/docs/current/errcodes-appendix.html
+        let code = self.code();
+
+        // connection_exception, such as a connection failure
+        code.starts_with("08")
+            || [
+                error_codes::SERIALIZATION_FAILURE,
+                error_codes::DEADLOCK_DETECTED,
+                error_codes::TOO_MANY_CONNECTIONS,
+                // admin_shutdown, such as the server shutting down for a failover
+                "57P01",
+                // crash_shutdown
+                "57P02",
+                // cannot_connect_now, such as the server still starting up
+                "57P03",
+            ]
+            .contains(&code)
+    }
+
     fn constraint(&self) -> Option<&str> {
         self.constraint()
     }
 
+    fn schema(&self) -> Option<&str> {
+        self.schema()
+    }
+
     fn table(&self) -> Option<&str> {
         self.table()
     }
 
+    fn column(&self) -> Option<&str> {
+        self.column()
+    }
+
     fn kind(&self) -> ErrorKind {
         match self.code() {
             error_codes::UNIQUE_VIOLATION => ErrorKind::UniqueViolation,
@@ -215,6 +242,11 @@ impl DatabaseError for PgDatabaseError {
             error_codes::NOT_NULL_VIOLATION => ErrorKind::NotNullViolation,
             error_codes::CHECK_VIOLATION => ErrorKind::CheckViolation,
             error_codes::EXCLUSION_VIOLATION => ErrorKind::ExclusionViolation,
+            error_codes::SERIALIZATION_FAILURE => ErrorKind::SerializationFailure,
+            error_codes::DEADLOCK_DETECTED => ErrorKind::DeadlockDetected,
+            error_codes::INSUFFICIENT_PRIVILEGE => ErrorKind::InsufficientPrivilege,
+            error_codes::DISK_FULL => ErrorKind::DiskFull,
+            error_codes::TOO_MANY_CONNECTIONS => ErrorKind::TooManyConnections,
             _ => ErrorKind::Other,
         }
     }
@@ -242,4 +274,46 @@ pub(crate) mod error_codes {
     pub const CHECK_VIOLATION: &str = "23514";
     /// Caused when a exclude constraint is violated.
     pub const EXCLUSION_VIOLATION: &str = "23P01";
+    /// Caused when a transaction cannot be serialized with concurrent transactions.
+    pub const SERIALIZATION_FAILURE: &str = "40001";
+    /// Caused when a transaction is aborted to break a deadlock.
+    pub const DEADLOCK_DETECTED: &str = "40P01";
+    /// Caused when the current role lacks a privilege needed for the operation.
+    pub const INSUFFICIENT_PRIVILEGE: &str = "42501";
+    /// Caused when the server runs out of disk space.
+    pub const DISK_FULL: &str = "53100";
+    /// Caused when a limit on the number of connections is reached.
+    pub const TOO_MANY_CONNECTIONS: &str = "53300";
+}
+
+#[test]
+fn error_kinds_are_mapped_from_sqlstate() {
+    let error = |code: &str| {
+        let data = format!("SERROR\0VERROR\0C{code}\0Mmessage\0tusers\0cemail\0\0");
+        PgDatabaseError::decode_body(Bytes::from(data)).unwrap()
+    };
+
+    let not_null = error(error_codes::NOT_NULL_VIOLATION);
+    assert_eq!(DatabaseError::kind(&not_null), ErrorKind::NotNullViolation);
+    assert!(DatabaseError::is_not_null_violation(&not_null));
+    assert_eq!(DatabaseError::table(&not_null), Some("users"));
+    assert_eq!(DatabaseError::column(&not_null), Some("email"));
+
+    assert!(DatabaseError::is_transaction_conflict(&error("40001")));
+    assert!(DatabaseError::is_transaction_conflict(&error("40P01")));
+    assert_eq!(
+        DatabaseError::kind(&error("42501")),
+        ErrorKind::InsufficientPrivilege
+    );
+    assert_eq!(DatabaseError::kind(&error("53100")), ErrorKind::DiskFull);
+    assert_eq!(
+        DatabaseError::kind(&error("53300")),
+        ErrorKind::TooManyConnections
+    );
+    assert_eq!(DatabaseError::kind(&error("22012")), ErrorKind::Other);
+
+    assert!(DatabaseError::is_transient(&error("57P01")));
+    assert!(DatabaseError::is_transient(&error("08006")));
+    assert!(DatabaseError::is_transient(&error("53300")));
+    assert!(!DatabaseError::is_transient(&not_null));
 }
@@ -19,7 +19,10 @@ mod message;
 mod options;
 mod query_result;
 mod row;
+mod schema;
+mod session_token;
 mod statement;
+mod tenant;
 mod transaction;
 mod type_checking;
 mod type_info;
@@ -58,8 +61,24 @@ pub use listener::{PgListener, PgNotification};
 pub use message::PgSeverity;
 pub use options::{PgConnectOptions, PgSslMode};
 pub use query_result::PgQueryResult;
-pub use row::PgRow;
+#[cfg(feature = "arrow")]
+pub use row::PgFetchArrowExt;
+#[cfg(feature = "json")]
+pub use row::{PgExportExt, PgFetchJsonExt, PgRowDeserializeError};
+pub use row::{PgFetchMapsExt, PgRow, PgRowMap};
+#[cfg(feature = "spill")]
+pub use row::{PgFetchSpilledExt, PgSpilledRows, PgSpilledRowsIter};
+pub use schema::{
+    PgForeignKey, PgForeignKeyAction, PgIndex, PgPrimaryKey, PgSchema, PgTable, PgTableColumn,
+    PgTableKind,
+};
+pub use session_token::PgSessionToken;
+pub use sqlx_core::net::proxy::Proxy;
+#[cfg(all(feature = "ssh-tunnel", unix))]
+pub use sqlx_core::net::ssh::SshCommandTunnel;
+pub use sqlx_core::net::Resolver;
 pub use statement::PgStatement;
+pub use tenant::{PgTenantConnection, PgTenantRouter};
 pub use transaction::PgTransactionManager;
 pub use type_info::{PgTypeInfo, PgTypeKind};
 pub use types::PgHasArrayType;
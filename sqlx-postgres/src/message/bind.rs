@@ -67,6 +67,38 @@ impl FrontendMessage for Bind<'_> {
     }
 
     fn encode_body(&self, buf: &mut Vec<u8>) -> Result<(), crate::Error> {
+        self.put_head(buf)?;
+
+        buf.extend(self.params);
+
+        self.put_tail(buf)
+    }
+}
+
+impl Bind<'_> {
+    /// Encode this message without its parameters, returning the parts before and after them.
+    ///
+    /// Used to send large parameters from where they are instead of copying them into the write
+    /// buffer; the length prefix in the first part counts the parameters.
+    pub(crate) fn encode_split(&self) -> Result<(Vec<u8>, Vec<u8>), crate::Error> {
+        let mut tail = Vec::new();
+        self.put_tail(&mut tail)?;
+
+        let mut head = vec![Self::FORMAT as u8, 0, 0, 0, 0];
+        self.put_head(&mut head)?;
+
+        // the format code is not counted
+        let size = head.len() - 1 + self.params.len() + tail.len();
+        let size = i32::try_from(size)
+            .map_err(|_| err_protocol!("message size out of range for protocol: {size}"))?;
+
+        head[1..5].copy_from_slice(&size.to_be_bytes());
+
+        Ok((head, tail))
+    }
+
+    // everything before the parameters
+    fn put_head(&self, buf: &mut Vec<u8>) -> Result<(), crate::Error> {
         buf.put_portal_name(self.portal);
 
         buf.put_statement_name(self.statement);
@@ -87,8 +119,11 @@ impl FrontendMessage for Bind<'_> {
 
         buf.extend(self.num_params.to_be_bytes());
 
-        buf.extend(self.params);
+        Ok(())
+    }
 
+    // everything after the parameters
+    fn put_tail(&self, buf: &mut Vec<u8>) -> Result<(), crate::Error> {
         let result_formats_len = u16::try_from(self.formats.len())
             .map_err(|_| err_protocol!("too many result format codes ({})", self.formats.len()))?;
 
@@ -102,5 +137,25 @@ impl FrontendMessage for Bind<'_> {
     }
 }
 
-// TODO: Unit Test Bind
+#[test]
+fn test_encode_split_bind() {
+    let params = [0_u8, 0, 0, 4, 0, 0, 0, 42];
+
+    let bind = Bind {
+        portal: PortalId::UNNAMED,
+        statement: StatementId::UNNAMED,
+        formats: &[PgValueFormat::Binary],
+        num_params: 1,
+        params: &params,
+        result_formats: &[PgValueFormat::Binary],
+    };
+
+    let (head, tail) = bind.encode_split().unwrap();
+
+    let mut buf = Vec::new();
+    bind.encode_msg(&mut buf).unwrap();
+
+    assert_eq!(buf, [&head[..], &params, &tail].concat());
+}
+
 // TODO: Benchmark Bind
@@ -0,0 +1,41 @@
+use crate::io::ProtocolEncode;
+
+/// Asks the server to cancel the query currently running on another connection, identified by
+/// the [`BackendKeyData`](super::BackendKeyData) it received during start-up.
+///
+/// Sent as the first message of a new connection, instead of a [`Startup`](super::Startup).
+pub struct CancelRequest {
+    pub process_id: u32,
+    pub secret_key: u32,
+}
+
+impl CancelRequest {
+    // https://www.postgresql.org/docs/current/protocol-message-formats.html#PROTOCOL-MESSAGE-FORMATS-CANCELREQUEST
+    const CODE: u32 = (1234 << 16) | 5678;
+}
+
+// Cannot impl FrontendMessage because it does not have a format code
+impl ProtocolEncode<'_> for CancelRequest {
+    fn encode_with(&self, buf: &mut Vec<u8>, _context: ()) -> Result<(), crate::Error> {
+        buf.extend_from_slice(&16_u32.to_be_bytes());
+        buf.extend_from_slice(&Self::CODE.to_be_bytes());
+        buf.extend_from_slice(&self.process_id.to_be_bytes());
+        buf.extend_from_slice(&self.secret_key.to_be_bytes());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_encode_cancel_request() {
+    const EXPECTED: &[u8] = b"\x00\x00\x00\x10\x04\xd2\x16\x2e\x00\x00\x30\x39\xde\xad\xbe\xef";
+
+    let mut buf = Vec::new();
+    CancelRequest {
+        process_id: 12345,
+        secret_key: 0xdeadbeef,
+    }
+    .encode(&mut buf)
+    .unwrap();
+
+    assert_eq!(buf, EXPECTED);
+}
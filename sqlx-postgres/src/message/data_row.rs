@@ -1,34 +1,60 @@
-use byteorder::{BigEndian, ByteOrder};
-use sqlx_core::bytes::Bytes;
-use std::ops::Range;
+use byteorder::{BigEndian, ByteOrder, NativeEndian};
+use sqlx_core::bytes::{BufMut, Bytes, BytesMut};
+use std::cmp;
 
 use crate::error::Error;
 use crate::message::{BackendMessage, BackendMessageFormat};
 
+/// The bytes of the column ranges of a row: its start and end, as `u32`s.
+const RANGE_SIZE: usize = 8;
+
+/// The `start` of the range of a NULL value.
+const NULL: u32 = u32::MAX;
+
+/// The capacity a [`RowArena`] allocates at once.
+const ARENA_CHUNK_SIZE: usize = 8 * 1024;
+
 /// A row of data from the database.
 #[derive(Debug)]
 pub struct DataRow {
     pub(crate) storage: Bytes,
 
-    /// Ranges into the stored row data.
+    /// Ranges into the stored row data, as pairs of `u32` (see [`RowArena`]).
     /// This uses `u32` instead of usize to reduce the size of this type. Values cannot be larger
     /// than `i32` in postgres.
-    pub(crate) values: Vec<Option<Range<u32>>>,
+    pub(crate) values: Bytes,
+}
+
+/// Space for the column ranges of the rows received by a connection.
+///
+/// The ranges of each row are split off a shared buffer instead of being allocated on their
+/// own. A row keeps its part of the buffer alive until dropped; once all the rows of a chunk
+/// are dropped, the chunk is reused.
+#[derive(Debug, Default)]
+pub(crate) struct RowArena {
+    buf: BytesMut,
 }
 
 impl DataRow {
     #[inline]
     pub(crate) fn get(&self, index: usize) -> Option<&'_ [u8]> {
-        self.values[index]
-            .as_ref()
-            .map(|col| &self.storage[(col.start as usize)..(col.end as usize)])
+        let range = &self.values[index * RANGE_SIZE..][..RANGE_SIZE];
+        let start = NativeEndian::read_u32(range);
+        let end = NativeEndian::read_u32(&range[4..]);
+
+        (start != NULL).then(|| &self.storage[(start as usize)..(end as usize)])
     }
-}
 
-impl BackendMessage for DataRow {
-    const FORMAT: BackendMessageFormat = BackendMessageFormat::DataRow;
+    /// Copy the row out of the buffers it shares with other rows.
+    pub(crate) fn detach(&mut self) {
+        self.storage = Bytes::copy_from_slice(&self.storage);
+        self.values = Bytes::copy_from_slice(&self.values);
+    }
+}
 
-    fn decode_body(buf: Bytes) -> Result<Self, Error> {
+impl RowArena {
+    /// Decode the body of a `DataRow` message, with its column ranges in this arena.
+    pub(crate) fn decode(&mut self, buf: Bytes) -> Result<DataRow, Error> {
         if buf.len() < 2 {
             return Err(err_protocol!(
                 "expected at least 2 bytes, got {}",
@@ -37,8 +63,16 @@ impl BackendMessage for DataRow {
         }
 
         let cnt = BigEndian::read_u16(&buf) as usize;
+        let len = cnt * RANGE_SIZE;
+
+        // drop the ranges of a row which failed to decode
+        self.buf.clear();
+
+        if self.buf.capacity() < len {
+            // reclaims the chunk if no row still uses it
+            self.buf.reserve(cmp::max(len, ARENA_CHUNK_SIZE));
+        }
 
-        let mut values = Vec::with_capacity(cnt);
         let mut offset: u32 = 2;
 
         for _ in 0..cnt {
@@ -67,21 +101,37 @@ impl BackendMessage for DataRow {
                     err_protocol!("value_start + length out of range ({offset} + {length})")
                 })?;
 
-                values.push(Some(value_start..value_end));
+                // `value_start` is never `NULL`, as it is within `buf`
+                self.buf.put_u32_ne(value_start);
+                self.buf.put_u32_ne(value_end);
                 offset = value_end;
             } else {
                 // Negative values signify NULL
-                values.push(None);
+                self.buf.put_u32_ne(NULL);
+                self.buf.put_u32_ne(NULL);
                 // `value_start` is actually the next value now.
                 offset = value_start;
             }
         }
 
-        Ok(Self {
+        Ok(DataRow {
             storage: buf,
-            values,
+            values: self.buf.split_to(len).freeze(),
         })
     }
+
+    /// Let go of the current chunk.
+    pub(crate) fn shrink(&mut self) {
+        self.buf = BytesMut::new();
+    }
+}
+
+impl BackendMessage for DataRow {
+    const FORMAT: BackendMessageFormat = BackendMessageFormat::DataRow;
+
+    fn decode_body(buf: Bytes) -> Result<Self, Error> {
+        RowArena::default().decode(buf)
+    }
 }
 
 #[test]
@@ -103,7 +153,7 @@ fn test_decode_data_row() {
 
     let row = DataRow::decode_body(DATA.into()).unwrap();
 
-    assert_eq!(row.values.len(), 8);
+    assert_eq!(row.values.len(), 8 * RANGE_SIZE);
 
     assert!(row.get(0).is_none());
     assert_eq!(row.get(1).unwrap(), &[0_u8, 0, 0, 10][..]);
@@ -136,3 +186,33 @@ fn bench_decode_data_row(b: &mut test::Bencher) {
         let _ = DataRow::decode_body(test::black_box(Bytes::from_static(DATA)));
     });
 }
+
+#[test]
+fn test_row_arena_reuses_its_chunk() {
+    const DATA: &[u8] = b"\x00\x02\xff\xff\xff\xff\x00\x00\x00\x01x";
+
+    let mut arena = RowArena::default();
+
+    let row = arena.decode(Bytes::from_static(DATA)).unwrap();
+    let chunk = row.values.as_ptr();
+    assert_eq!(row.get(1).unwrap(), b"x");
+
+    // the next row shares the chunk
+    let next = arena.decode(Bytes::from_static(DATA)).unwrap();
+    assert_eq!(next.values.as_ptr(), chunk.wrapping_add(2 * RANGE_SIZE));
+
+    // once the chunk is used up, it is reused from the start if no row uses it
+    drop((row, next));
+
+    for _ in 2..ARENA_CHUNK_SIZE / (2 * RANGE_SIZE) {
+        arena.decode(Bytes::from_static(DATA)).unwrap();
+    }
+
+    let mut row = arena.decode(Bytes::from_static(DATA)).unwrap();
+    assert_eq!(row.values.as_ptr(), chunk);
+
+    row.detach();
+    assert_ne!(row.values.as_ptr(), chunk);
+    assert!(row.get(0).is_none());
+    assert_eq!(row.get(1).unwrap(), b"x");
+}
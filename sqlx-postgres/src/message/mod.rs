@@ -7,6 +7,7 @@ use crate::io::PgBufMutExt;
 mod authentication;
 mod backend_key_data;
 mod bind;
+mod cancel_request;
 mod close;
 mod command_complete;
 mod copy;
@@ -33,10 +34,12 @@ mod terminate;
 pub use authentication::{Authentication, AuthenticationSasl};
 pub use backend_key_data::BackendKeyData;
 pub use bind::Bind;
+pub use cancel_request::CancelRequest;
 pub use close::Close;
 pub use command_complete::CommandComplete;
 pub use copy::{CopyData, CopyDone, CopyFail, CopyInResponse, CopyOutResponse, CopyResponseData};
 pub use data_row::DataRow;
+pub(crate) use data_row::RowArena;
 pub use describe::Describe;
 pub use execute::Execute;
 #[allow(unused_imports)]
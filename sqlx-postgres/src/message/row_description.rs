@@ -33,7 +33,6 @@ pub struct Field {
 
     /// The type modifier (see pg_attribute.atttypmod). The meaning of the
     /// modifier is type-specific.
-    #[allow(dead_code)]
     pub type_modifier: i32,
 
     /// The format code being used for the field.
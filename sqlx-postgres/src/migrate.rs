@@ -5,7 +5,7 @@ use std::time::Instant;
 use futures_core::future::BoxFuture;
 
 pub(crate) use sqlx_core::migrate::MigrateError;
-pub(crate) use sqlx_core::migrate::{AppliedMigration, Migration};
+pub(crate) use sqlx_core::migrate::{AppliedMigration, AuditInfo, Migration, SchemaSnapshot};
 pub(crate) use sqlx_core::migrate::{Migrate, MigrateDatabase};
 use sqlx_core::sql_str::AssertSqlSafe;
 
@@ -61,6 +61,7 @@ impl MigrateDatabase for Postgres {
         let exists: bool =
             query_scalar("select exists(SELECT 1 from pg_database WHERE datname = $1)")
                 .bind(database)
+                .unlimited()
                 .fetch_one(&mut conn)
                 .await?;
 
@@ -86,6 +87,7 @@ impl MigrateDatabase for Postgres {
         let mut conn = options.connect().await?;
 
         let row: (String,) = query_as("SELECT current_setting('server_version_num')")
+            .unlimited()
             .fetch_one(&mut conn)
             .await?;
 
@@ -119,6 +121,23 @@ impl Migrate for PgConnection {
         })
     }
 
+    fn migrations_table_exists<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async move {
+            // `to_regclass()` parses the name the same way it is parsed in a query
+            // language=SQL
+            let exists: bool = query_scalar("SELECT to_regclass($1) IS NOT NULL")
+                .bind(table_name)
+                .unlimited()
+                .fetch_one(self)
+                .await?;
+
+            Ok(exists)
+        })
+    }
+
     fn ensure_migrations_table<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -133,7 +152,9 @@ CREATE TABLE IF NOT EXISTS {table_name} (
     installed_on TIMESTAMPTZ NOT NULL DEFAULT now(),
     success BOOLEAN NOT NULL,
     checksum BYTEA NOT NULL,
-    execution_time BIGINT NOT NULL
+    execution_time BIGINT NOT NULL,
+    applied_by TEXT,
+    application TEXT
 );
                 "#
             )))
@@ -143,6 +164,69 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
+    fn ensure_audit_columns<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let comment: Option<String> =
+                query_scalar("SELECT obj_description(to_regclass($1), 'pg_class')")
+                    .bind(table_name)
+                    .unlimited()
+                    .fetch_one(&mut *self)
+                    .await?;
+
+            if comment.as_deref() == Some(MIGRATIONS_TABLE_COMMENT) {
+                return Ok(());
+            }
+
+            // Upgrade tables created before the audit columns were added, once.
+            // Check first, as `ALTER TABLE` takes an exclusive lock even if there is nothing to do.
+            // language=SQL
+            let has_audit_columns: bool = query_scalar(
+                r#"
+SELECT EXISTS (
+    SELECT 1 FROM pg_attribute
+    WHERE attrelid = to_regclass($1) AND attname = 'applied_by' AND NOT attisdropped
+)
+                "#,
+            )
+            .bind(table_name)
+            .unlimited()
+            .fetch_one(&mut *self)
+            .await?;
+
+            if !has_audit_columns {
+                // `ADD COLUMN IF NOT EXISTS` (in case another migrator just added them)
+                // is only supported from PostgreSQL 9.6
+                let if_not_exists = if self.server_version_num().is_some_and(|v| v >= 90600) {
+                    "IF NOT EXISTS "
+                } else {
+                    ""
+                };
+
+                // language=SQL
+                self.execute(AssertSqlSafe(format!(
+                    r#"
+ALTER TABLE {table_name}
+    ADD COLUMN {if_not_exists}applied_by TEXT,
+    ADD COLUMN {if_not_exists}application TEXT;
+                    "#
+                )))
+                .await?;
+            }
+
+            // language=SQL
+            self.execute(AssertSqlSafe(format!(
+                "COMMENT ON TABLE {table_name} IS '{MIGRATIONS_TABLE_COMMENT}'"
+            )))
+            .await?;
+
+            Ok(())
+        })
+    }
+
     fn dirty_version<'e>(
         &'e mut self,
         table_name: &'e str,
@@ -152,6 +236,7 @@ CREATE TABLE IF NOT EXISTS {table_name} (
             let row: Option<(i64,)> = query_as(AssertSqlSafe(format!(
                 "SELECT version FROM {table_name} WHERE success = false ORDER BY version LIMIT 1"
             )))
+            .unlimited()
             .fetch_optional(self)
             .await?;
 
@@ -166,8 +251,10 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         Box::pin(async move {
             // language=SQL
             let rows: Vec<(i64, Vec<u8>)> = query_as(AssertSqlSafe(format!(
-                "SELECT version, checksum FROM {table_name} ORDER BY version"
+                // repeatable migrations are tracked with negative versions
+                "SELECT version, checksum FROM {table_name} WHERE version >= 0 ORDER BY version"
             )))
+            .unlimited()
             .fetch_all(self)
             .await?;
 
@@ -183,10 +270,34 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
-    fn lock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn list_applied_repeatable_migrations<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
-            let database_name = current_database(self).await?;
-            let lock_id = generate_lock_id(&database_name);
+            // language=SQL
+            let rows: Vec<(i64, Vec<u8>)> = query_as(AssertSqlSafe(format!(
+                "SELECT version, checksum FROM {table_name} WHERE version < 0"
+            )))
+            .unlimited()
+            .fetch_all(self)
+            .await?;
+
+            let migrations = rows
+                .into_iter()
+                .map(|(version, checksum)| AppliedMigration {
+                    version,
+                    checksum: checksum.into(),
+                })
+                .collect();
+
+            Ok(migrations)
+        })
+    }
+
+    fn lock<'e>(&'e mut self, table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let lock_id = lock_id(self, table_name).await?;
 
             // create an application lock over the database
             // this function will not return until the lock is acquired
@@ -197,6 +308,7 @@ CREATE TABLE IF NOT EXISTS {table_name} (
             // language=SQL
             let _ = query("SELECT pg_advisory_lock($1)")
                 .bind(lock_id)
+                .unlimited()
                 .execute(self)
                 .await?;
 
@@ -204,14 +316,14 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
-    fn unlock(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+    fn unlock<'e>(&'e mut self, table_name: &'e str) -> BoxFuture<'e, Result<(), MigrateError>> {
         Box::pin(async move {
-            let database_name = current_database(self).await?;
-            let lock_id = generate_lock_id(&database_name);
+            let lock_id = lock_id(self, table_name).await?;
 
             // language=SQL
             let _ = query("SELECT pg_advisory_unlock($1)")
                 .bind(lock_id)
+                .unlimited()
                 .execute(self)
                 .await?;
 
@@ -219,48 +331,120 @@ CREATE TABLE IF NOT EXISTS {table_name} (
         })
     }
 
+    fn try_lock<'e>(
+        &'e mut self,
+        table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async move {
+            let lock_id = lock_id(self, table_name).await?;
+
+            // language=SQL
+            let locked: bool = query_scalar("SELECT pg_try_advisory_lock($1)")
+                .bind(lock_id)
+                .unlimited()
+                .fetch_one(self)
+                .await?;
+
+            Ok(locked)
+        })
+    }
+
+    fn try_lock_table<'e>(
+        &'e mut self,
+        lock_table_name: &'e str,
+    ) -> BoxFuture<'e, Result<bool, MigrateError>> {
+        Box::pin(async move {
+            // The `CHECK` constraint ensures there can only be one row.
+            // language=SQL
+            self.execute(AssertSqlSafe(format!(
+                r#"
+CREATE TABLE IF NOT EXISTS {lock_table_name} (
+    locked BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (locked),
+    locked_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+                "#
+            )))
+            .await?;
+
+            // language=SQL
+            let result = self
+                .execute(AssertSqlSafe(format!(
+                    "INSERT INTO {lock_table_name} DEFAULT VALUES ON CONFLICT DO NOTHING"
+                )))
+                .await?;
+
+            Ok(result.rows_affected() == 1)
+        })
+    }
+
+    fn unlock_table<'e>(
+        &'e mut self,
+        lock_table_name: &'e str,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            self.execute(AssertSqlSafe(format!("DELETE FROM {lock_table_name}")))
+                .await?;
+
+            Ok(())
+        })
+    }
+
     fn apply<'e>(
         &'e mut self,
         table_name: &'e str,
         migration: &'e Migration,
     ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
-        Box::pin(async move {
-            let start = Instant::now();
+        Box::pin(apply(self, table_name, migration, None))
+    }
 
-            // execute migration queries
-            if migration.no_tx {
-                execute_migration(self, table_name, migration).await?;
-            } else {
-                // Use a single transaction for the actual migration script and the essential bookeeping so we never
-                // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
-                // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
-                // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
-                // and update it once the actual transaction completed.
-                let mut tx = self.begin().await?;
-                execute_migration(&mut tx, table_name, migration).await?;
-                tx.commit().await?;
-            }
+    fn mark_applied<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(mark_applied(self, table_name, migration, None))
+    }
 
-            // Update `elapsed_time`.
-            // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
-            //       this small risk since this value is not super important.
-            let elapsed = start.elapsed();
+    fn apply_with_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<Duration, MigrateError>> {
+        Box::pin(apply(self, table_name, migration, Some(audit)))
+    }
+
+    fn mark_applied_with_audit<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+        audit: &'e AuditInfo,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(mark_applied(self, table_name, migration, Some(audit)))
+    }
 
+    fn update_checksum<'e>(
+        &'e mut self,
+        table_name: &'e str,
+        migration: &'e Migration,
+    ) -> BoxFuture<'e, Result<(), MigrateError>> {
+        Box::pin(async move {
             // language=SQL
-            #[allow(clippy::cast_possible_truncation)]
             let _ = query(AssertSqlSafe(format!(
                 r#"
     UPDATE {table_name}
-    SET execution_time = $1
-    WHERE version = $2
+    SET checksum = $1, description = $2
+    WHERE version = $3
                 "#
             )))
-            .bind(elapsed.as_nanos() as i64)
+            .bind(&*migration.checksum)
+            .bind(&*migration.description)
             .bind(migration.version)
             .execute(self)
             .await?;
 
-            Ok(elapsed)
+            Ok(())
         })
     }
 
@@ -288,28 +472,247 @@ CREATE TABLE IF NOT EXISTS {table_name} (
             Ok(elapsed)
         })
     }
+
+    fn schema_snapshot<'e>(
+        &'e mut self,
+        exclude_tables: &'e [String],
+    ) -> BoxFuture<'e, Result<SchemaSnapshot, MigrateError>> {
+        Box::pin(async move {
+            let objects: Vec<(String, String, String)> = query_as(SCHEMA_SNAPSHOT_QUERY)
+                .bind(exclude_tables)
+                .unlimited()
+                .fetch_all(self)
+                .await?;
+
+            let mut snapshot = SchemaSnapshot::new();
+
+            for (kind, name, definition) in objects {
+                snapshot.insert(kind, name, definition);
+            }
+
+            Ok(snapshot)
+        })
+    }
+}
+
+/// The comment of the migrations table, recording the version of its schema.
+///
+/// Tables without it were created before the audit columns were added, or have not been
+/// checked for them yet.
+const MIGRATIONS_TABLE_COMMENT: &str = "sqlx migrations, schema version 2";
+
+/// Lists the objects in user schemas as `(kind, name, definition)`, skipping objects belonging
+/// to extensions or to the tables in `$1` (which don't need to exist).
+// language=SQL
+const SCHEMA_SNAPSHOT_QUERY: &str = r#"
+WITH
+excluded AS (
+    SELECT to_regclass(name) AS oid FROM unnest($1::text[]) AS name
+),
+namespaces AS (
+    SELECT oid, nspname FROM pg_namespace
+    WHERE nspname NOT IN ('pg_catalog', 'information_schema') AND nspname NOT LIKE 'pg\_%'
+),
+relations AS (
+    SELECT c.oid, c.relkind, format('%I.%I', n.nspname, c.relname) AS name
+    FROM pg_class c
+    JOIN namespaces n ON n.oid = c.relnamespace
+    WHERE c.relkind IN ('r', 'p', 'v', 'm', 'S', 'f')
+        AND c.oid NOT IN (SELECT oid FROM excluded WHERE oid IS NOT NULL)
+        AND NOT EXISTS (
+            SELECT 1 FROM pg_depend d
+            WHERE d.classid = 'pg_class'::regclass AND d.objid = c.oid AND d.deptype = 'e'
+        )
+)
+SELECT
+    CASE r.relkind
+        WHEN 'v' THEN 'view'
+        WHEN 'm' THEN 'materialized view'
+        WHEN 'S' THEN 'sequence'
+        WHEN 'f' THEN 'foreign table'
+        ELSE 'table'
+    END AS kind,
+    r.name AS name,
+    CASE
+        WHEN r.relkind IN ('v', 'm') THEN pg_get_viewdef(r.oid)
+        WHEN r.relkind = 'S' THEN (
+            SELECT format(
+                '%s START %s INCREMENT %s',
+                format_type(s.seqtypid, NULL), s.seqstart, s.seqincrement
+            )
+            FROM pg_sequence s WHERE s.seqrelid = r.oid
+        )
+        ELSE ''
+    END AS definition
+FROM relations r
+UNION ALL
+SELECT
+    'column',
+    r.name || '.' || quote_ident(a.attname),
+    format_type(a.atttypid, a.atttypmod)
+        || CASE WHEN a.attnotnull THEN ' NOT NULL' ELSE '' END
+        || COALESCE(' DEFAULT ' || pg_get_expr(ad.adbin, ad.adrelid), '')
+FROM relations r
+JOIN pg_attribute a ON a.attrelid = r.oid AND a.attnum > 0 AND NOT a.attisdropped
+LEFT JOIN pg_attrdef ad ON ad.adrelid = a.attrelid AND ad.adnum = a.attnum
+WHERE r.relkind <> 'S'
+UNION ALL
+SELECT 'constraint', r.name || '.' || quote_ident(con.conname), pg_get_constraintdef(con.oid)
+FROM relations r
+JOIN pg_constraint con ON con.conrelid = r.oid
+UNION ALL
+SELECT 'index', r.name || '.' || quote_ident(ic.relname), pg_get_indexdef(ix.indexrelid)
+FROM relations r
+JOIN pg_index ix ON ix.indrelid = r.oid
+JOIN pg_class ic ON ic.oid = ix.indexrelid
+UNION ALL
+SELECT 'trigger', r.name || '.' || quote_ident(t.tgname), pg_get_triggerdef(t.oid)
+FROM relations r
+JOIN pg_trigger t ON t.tgrelid = r.oid AND NOT t.tgisinternal
+UNION ALL
+SELECT
+    CASE p.prokind WHEN 'p' THEN 'procedure' ELSE 'function' END,
+    format('%I.%I(%s)', n.nspname, p.proname, pg_get_function_identity_arguments(p.oid)),
+    pg_get_functiondef(p.oid)
+FROM pg_proc p
+JOIN namespaces n ON n.oid = p.pronamespace
+WHERE p.prokind IN ('f', 'p')
+    AND NOT EXISTS (
+        SELECT 1 FROM pg_depend d
+        WHERE d.classid = 'pg_proc'::regclass AND d.objid = p.oid AND d.deptype = 'e'
+    )
+UNION ALL
+SELECT
+    'type',
+    format('%I.%I', n.nspname, t.typname),
+    'ENUM (' || (
+        SELECT string_agg(quote_literal(e.enumlabel), ', ' ORDER BY e.enumsortorder)
+        FROM pg_enum e WHERE e.enumtypid = t.oid
+    ) || ')'
+FROM pg_type t
+JOIN namespaces n ON n.oid = t.typnamespace
+WHERE t.typtype = 'e'
+    AND NOT EXISTS (
+        SELECT 1 FROM pg_depend d
+        WHERE d.classid = 'pg_type'::regclass AND d.objid = t.oid AND d.deptype = 'e'
+    )
+"#;
+
+async fn apply(
+    conn: &mut PgConnection,
+    table_name: &str,
+    migration: &Migration,
+    audit: Option<&AuditInfo>,
+) -> Result<Duration, MigrateError> {
+    let start = Instant::now();
+
+    // execute migration queries
+    if migration.no_tx {
+        execute_migration(conn, table_name, migration, audit).await?;
+    } else {
+        // Use a single transaction for the actual migration script and the essential bookeeping so we never
+        // execute migrations twice. See https://github.com/launchbadge/sqlx/issues/1966.
+        // The `execution_time` however can only be measured for the whole transaction. This value _only_ exists for
+        // data lineage and debugging reasons, so it is not super important if it is lost. So we initialize it to -1
+        // and update it once the actual transaction completed.
+        let mut tx = conn.begin().await?;
+        execute_migration(&mut tx, table_name, migration, audit).await?;
+        tx.commit().await?;
+    }
+
+    // Update `elapsed_time`.
+    // NOTE: The process may disconnect/die at this point, so the elapsed time value might be lost. We accept
+    //       this small risk since this value is not super important.
+    let elapsed = start.elapsed();
+
+    // language=SQL
+    #[allow(clippy::cast_possible_truncation)]
+    let _ = query(AssertSqlSafe(format!(
+        r#"
+    UPDATE {table_name}
+    SET execution_time = $1
+    WHERE version = $2
+                "#
+    )))
+    .bind(elapsed.as_nanos() as i64)
+    .bind(migration.version)
+    .execute(conn)
+    .await?;
+
+    Ok(elapsed)
+}
+
+async fn mark_applied(
+    conn: &mut PgConnection,
+    table_name: &str,
+    migration: &Migration,
+    audit: Option<&AuditInfo>,
+) -> Result<(), MigrateError> {
+    insert_migration(conn, table_name, migration, 0, audit).await
 }
 
 async fn execute_migration(
     conn: &mut PgConnection,
     table_name: &str,
     migration: &Migration,
+    audit: Option<&AuditInfo>,
 ) -> Result<(), MigrateError> {
-    let _ = conn
-        .execute(migration.sql.clone())
-        .await
-        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+    run_migration(&mut *conn, migration).await?;
+
+    if migration.migration_type.is_repeatable() {
+        // language=SQL
+        let _ = query(AssertSqlSafe(format!(
+            r#"DELETE FROM {table_name} WHERE version = $1"#
+        )))
+        .bind(migration.version)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    insert_migration(conn, table_name, migration, -1, audit).await
+}
+
+// record `migration` as applied, along with who/what applied it
+async fn insert_migration(
+    conn: &mut PgConnection,
+    table_name: &str,
+    migration: &Migration,
+    execution_time: i64,
+    audit: Option<&AuditInfo>,
+) -> Result<(), MigrateError> {
+    let Some(audit) = audit else {
+        // tables of earlier versions may not have the audit columns,
+        // which are only added once audit info is to be recorded
+        // language=SQL
+        let _ = query(AssertSqlSafe(format!(
+            r#"
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
+    VALUES ( $1, $2, TRUE, $3, $4 )
+                "#
+        )))
+        .bind(migration.version)
+        .bind(&*migration.description)
+        .bind(&*migration.checksum)
+        .bind(execution_time)
+        .execute(conn)
+        .await?;
+
+        return Ok(());
+    };
 
     // language=SQL
     let _ = query(AssertSqlSafe(format!(
         r#"
-    INSERT INTO {table_name} ( version, description, success, checksum, execution_time )
-    VALUES ( $1, $2, TRUE, $3, -1 )
+    INSERT INTO {table_name} ( version, description, success, checksum, execution_time, applied_by, application )
+    VALUES ( $1, $2, TRUE, $3, $4, $5, $6 )
                 "#
     )))
     .bind(migration.version)
     .bind(&*migration.description)
     .bind(&*migration.checksum)
+    .bind(execution_time)
+    .bind(audit.applied_by.as_deref())
+    .bind(audit.application.as_deref())
     .execute(conn)
     .await?;
 
@@ -321,10 +724,7 @@ async fn revert_migration(
     table_name: &str,
     migration: &Migration,
 ) -> Result<(), MigrateError> {
-    let _ = conn
-        .execute(migration.sql.clone())
-        .await
-        .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+    run_migration(&mut *conn, migration).await?;
 
     // language=SQL
     let _ = query(AssertSqlSafe(format!(
@@ -337,16 +737,69 @@ async fn revert_migration(
     Ok(())
 }
 
+async fn run_migration(conn: &mut PgConnection, migration: &Migration) -> Result<(), MigrateError> {
+    let Some(code) = &migration.code else {
+        let _ = conn
+            .execute(migration.sql.clone())
+            .await
+            .map_err(|e| MigrateError::ExecuteMigration(e, migration.version))?;
+
+        return Ok(());
+    };
+
+    let code = code
+        .get::<Postgres>()
+        .ok_or(MigrateError::CodeNotSupported(migration.version))?;
+
+    code.run(conn)
+        .await
+        .map_err(|e| MigrateError::ExecuteCode(e, migration.version))
+}
+
 async fn current_database(conn: &mut PgConnection) -> Result<String, MigrateError> {
     // language=SQL
     Ok(query_scalar("SELECT current_database()")
+        .unlimited()
         .fetch_one(conn)
         .await?)
 }
 
+async fn lock_id(conn: &mut PgConnection, table_name: &str) -> Result<i64, MigrateError> {
+    let database_name = current_database(conn).await?;
+
+    Ok(table_lock_id(&database_name, table_name))
+}
+
+fn table_lock_id(database_name: &str, table_name: &str) -> i64 {
+    // Keep the lock ID for the default table unchanged so older migrators still exclude newer ones.
+    if table_name == "_sqlx_migrations" {
+        return generate_lock_id(database_name);
+    }
+
+    generate_lock_id(&format!("{database_name}.{table_name}"))
+}
+
 // inspired from rails: https://github.com/rails/rails/blob/6e49cc77ab3d16c06e12f93158eaf3e507d4120e/activerecord/lib/active_record/migration.rb#L1308
 fn generate_lock_id(database_name: &str) -> i64 {
     const CRC_IEEE: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
     // 0x3d32ad9e chosen by fair dice roll
     0x3d32ad9e * (CRC_IEEE.checksum(database_name.as_bytes()) as i64)
 }
+
+#[test]
+fn default_table_keeps_the_database_lock_id() {
+    // the ID taken by migrators which locked the whole database
+    assert_eq!(
+        table_lock_id("app", "_sqlx_migrations"),
+        3_469_795_979_745_788_610
+    );
+
+    assert_ne!(
+        table_lock_id("app", "_billing_migrations"),
+        table_lock_id("app", "_sqlx_migrations")
+    );
+    assert_ne!(
+        table_lock_id("app", "_billing_migrations"),
+        table_lock_id("other", "_billing_migrations")
+    );
+}
@@ -1,9 +1,9 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, ConnectRetryPolicy};
 use crate::error::Error;
 use crate::{PgConnectOptions, PgConnection};
 use log::LevelFilter;
+use sqlx_core::statistics::StatementStatistics;
 use sqlx_core::Url;
-use std::future::Future;
 use std::time::Duration;
 
 impl ConnectOptions for PgConnectOptions {
@@ -17,11 +17,14 @@ impl ConnectOptions for PgConnectOptions {
         self.build_url()
     }
 
-    fn connect(&self) -> impl Future<Output = Result<Self::Connection, Error>> + Send + '_
+    async fn connect(&self) -> Result<Self::Connection, Error>
     where
         Self::Connection: Sized,
     {
-        PgConnection::establish(self)
+        match &self.connect_retry {
+            Some(policy) => policy.retry_connect(|| PgConnection::establish(self)).await,
+            None => PgConnection::establish(self).await,
+        }
     }
 
     fn log_statements(mut self, level: LevelFilter) -> Self {
@@ -33,4 +36,23 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.log_settings.slow_query_threshold(threshold);
+        self
+    }
+
+    fn collect_statistics(mut self, max_statements: usize) -> Self {
+        self.log_settings.collect_statistics(max_statements);
+        self
+    }
+
+    fn statistics(&self) -> Option<&StatementStatistics> {
+        self.log_settings.statistics.as_ref()
+    }
+
+    fn retry(mut self, policy: ConnectRetryPolicy) -> Self {
+        self.connect_retry = Some(policy);
+        self
+    }
 }
@@ -1,11 +1,18 @@
 use std::borrow::Cow;
 use std::env::var;
 use std::fmt::{self, Display, Write};
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub use ssl_mode::PgSslMode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::connection::{ConnectRetryPolicy, LogSettings};
+use crate::net::proxy::Proxy;
+use crate::net::tls::{CertificateInput, RustlsClientConfig, TlsSessionCache};
+use crate::net::{Resolver, SharedResolver, TcpOptions, DEFAULT_BUF_SIZE};
+use sqlx_core::interceptor::{QueryInterceptor, QueryInterceptors};
+use sqlx_core::sql_comment::SqlCommenter;
 
 mod connect;
 mod parse;
@@ -25,11 +32,28 @@ pub struct PgConnectOptions {
     pub(crate) ssl_root_cert: Option<CertificateInput>,
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
+    pub(crate) ssl_client_pkcs12: Option<CertificateInput>,
+    pub(crate) ssl_client_key_password: Option<String>,
+    pub(crate) ssl_client_config: Option<RustlsClientConfig>,
+    pub(crate) ssl_session_cache: Option<TlsSessionCache>,
+    pub(crate) tcp: TcpOptions,
+    pub(crate) proxy: Option<Proxy>,
+    #[cfg(all(feature = "ssh-tunnel", unix))]
+    pub(crate) ssh_tunnel: Option<crate::net::ssh::SshCommandTunnel>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) read_buffer_size: usize,
+    pub(crate) write_buffer_size: usize,
+    pub(crate) max_message_size: Option<usize>,
+    pub(crate) max_rows: Option<u64>,
+    pub(crate) max_result_bytes: Option<u64>,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) connect_retry: Option<ConnectRetryPolicy>,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
+    pub(crate) time_zone: Cow<'static, str>,
     pub(crate) options: Option<String>,
+    pub(crate) sql_commenter: Option<SqlCommenter>,
+    pub(crate) interceptors: QueryInterceptors,
 }
 
 impl Default for PgConnectOptions {
@@ -81,15 +105,32 @@ impl PgConnectOptions {
             // `-----BEGIN CERTIFICATE-----` and so will not attempt to parse
             // a PEM-encoded private key.
             ssl_client_key: var("PGSSLKEY").ok().map(CertificateInput::from),
+            ssl_client_pkcs12: None,
+            ssl_client_key_password: None,
+            ssl_client_config: None,
+            ssl_session_cache: Some(TlsSessionCache::default()),
+            tcp: TcpOptions::default(),
+            proxy: None,
+            #[cfg(all(feature = "ssh-tunnel", unix))]
+            ssh_tunnel: None,
             ssl_mode: var("PGSSLMODE")
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            read_buffer_size: DEFAULT_BUF_SIZE,
+            write_buffer_size: DEFAULT_BUF_SIZE,
+            max_message_size: None,
+            max_rows: None,
+            max_result_bytes: None,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
+            time_zone: "UTC".into(),
             log_settings: Default::default(),
+            connect_retry: None,
             options: var("PGOPTIONS").ok(),
+            sql_commenter: None,
+            interceptors: QueryInterceptors::default(),
         }
     }
 
@@ -152,6 +193,173 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether TCP keepalives are sent on idle connections.
+    ///
+    /// By default set to `None`, leaving the default of the operating system, which is usually
+    /// off; setting any of the other `keepalives_*` options turns them on unless this is
+    /// `false`. Without keepalives, a connection to a server which went away is only found
+    /// dead when the next query times out retransmitting, which takes about 15 minutes on
+    /// Linux.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .keepalives_idle(Duration::from_secs(30))
+    ///     .keepalives_interval(Duration::from_secs(10))
+    ///     .keepalives_count(3)
+    ///     .tcp_user_timeout(Duration::from_secs(30));
+    /// ```
+    pub fn keepalives(mut self, enabled: bool) -> Self {
+        self.tcp.keepalive = Some(enabled);
+        self
+    }
+
+    /// Sets the idle time after which a TCP keepalive is sent.
+    pub fn keepalives_idle(mut self, idle: Duration) -> Self {
+        self.tcp.keepalive_idle = Some(idle);
+        self
+    }
+
+    /// Sets the time between TCP keepalives which are not acknowledged.
+    pub fn keepalives_interval(mut self, interval: Duration) -> Self {
+        self.tcp.keepalive_interval = Some(interval);
+        self
+    }
+
+    /// Sets the number of TCP keepalives which may go unacknowledged before the connection is
+    /// considered dead.
+    pub fn keepalives_count(mut self, count: u32) -> Self {
+        self.tcp.keepalive_retries = Some(count);
+        self
+    }
+
+    /// Sets the longest time sent data may remain unacknowledged before the connection is
+    /// considered dead (`TCP_USER_TIMEOUT`).
+    ///
+    /// Unlike keepalives, this also applies while a query is being sent. Only supported on
+    /// Linux.
+    pub fn tcp_user_timeout(mut self, timeout: Duration) -> Self {
+        self.tcp.user_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether Nagle's algorithm is disabled (`TCP_NODELAY`), so small messages are sent
+    /// at once instead of being held back to be combined.
+    ///
+    /// By default set to `None`, leaving the default of the operating system.
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.tcp.nodelay = Some(nodelay);
+        self
+    }
+
+    /// Sets the local address to connect from, e.g. on a host with several addresses which
+    /// the server or a firewall tells apart.
+    ///
+    /// Host names which resolve to addresses of both families only connect to those of the
+    /// family of `address`.
+    pub fn bind_address(mut self, address: IpAddr) -> Self {
+        self.tcp.bind_address = Some(address);
+        self
+    }
+
+    /// Sets the network interface to connect through, e.g. `eth1`. Only supported on Linux,
+    /// and on kernels older than 5.7 requires the `CAP_NET_RAW` capability.
+    pub fn bind_interface(mut self, interface: impl Into<String>) -> Self {
+        self.tcp.bind_interface = Some(interface.into());
+        self
+    }
+
+    /// Sets the resolver of the host name, in place of the system resolver; e.g. to look it up
+    /// in service discovery. Not used for Unix domain sockets or hosts which are IP addresses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io;
+    /// # use std::net::SocketAddr;
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .host("db.internal")
+    ///     .resolver(|_host: &str, port: u16| async move {
+    ///         io::Result::Ok(vec![SocketAddr::from(([10, 0, 0, 5], port))])
+    ///     });
+    /// ```
+    pub fn resolver(mut self, resolver: impl Resolver) -> Self {
+        self.tcp.resolver = Some(SharedResolver::new(resolver));
+        self
+    }
+
+    /// Connects "happy eyeballs" style ([RFC 8305]): the addresses of the host are tried
+    /// alternating between IPv6 and IPv4, and an attempt which has not completed within
+    /// `delay` is joined by an attempt to the next address, the first connection to succeed
+    /// being used.
+    ///
+    /// Without, addresses are tried one after the other, so a host with an unreachable address
+    /// of one family connects only once the attempt to it times out. [RFC 8305] recommends a
+    /// delay of 250 milliseconds.
+    ///
+    /// [RFC 8305]: https://datatracker.ietf.org/doc/html/rfc8305
+    pub fn happy_eyeballs(mut self, delay: Duration) -> Self {
+        self.tcp.happy_eyeballs_delay = Some(delay);
+        self
+    }
+
+    /// Connects through a SOCKS5 or HTTP proxy.
+    ///
+    /// The [`host()`][Self::host] and [`port()`][Self::port] are then those of the server as
+    /// seen from the proxy; Unix domain sockets cannot be proxied. Can also be set with the
+    /// `proxy` parameter of the connection URL, e.g. `?proxy=socks5://proxy.internal:1080`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx_postgres::{PgConnectOptions, Proxy};
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .host("db.internal")
+    ///     .proxy(Proxy::http("proxy.internal", 3128).credentials("app", "hunter2"));
+    /// ```
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Connects through an SSH jump host, for a server only reachable from it.
+    ///
+    /// The [`host()`][Self::host] and [`port()`][Self::port] are then those of the server as
+    /// seen from the jump host, e.g. `localhost` for a server on the jump host itself; Unix
+    /// domain sockets cannot be tunneled.
+    ///
+    /// The tunnel is opened by running the `ssh` client of the system for each connection, see
+    /// [`SshCommandTunnel`][crate::SshCommandTunnel]. This has limitations:
+    /// * Only Unix is supported, and `ssh` must be installed (or set with
+    ///   [`SshCommandTunnel::program()`][crate::SshCommandTunnel::program]).
+    /// * `ssh` never prompts, so a key with a passphrase can only be used through the SSH agent.
+    /// * A runtime set with `set_runtime()` is not supported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// use sqlx_postgres::{PgConnectOptions, SshCommandTunnel};
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .host("db.internal")
+    ///     .ssh_tunnel(
+    ///         SshCommandTunnel::new("bastion.example.com")
+    ///             .user("deploy")
+    ///             .keepalive(Duration::from_secs(30)),
+    ///     );
+    /// ```
+    #[cfg(all(feature = "ssh-tunnel", unix))]
+    pub fn ssh_tunnel(mut self, tunnel: crate::net::ssh::SshCommandTunnel) -> Self {
+        self.ssh_tunnel = Some(tunnel);
+        self
+    }
+
     /// Sets the username to connect as.
     ///
     /// Defaults to be the same as the operating system name of
@@ -320,6 +528,125 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the SSL client certificate as DER-encoded bytes, such as those of a `.der` file
+    /// or a secret store.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_postgres::{PgSslMode, PgConnectOptions};
+    /// # let (cert, key) = (Vec::<u8>::new(), Vec::<u8>::new());
+    /// let options = PgConnectOptions::new()
+    ///     .ssl_mode(PgSslMode::VerifyFull)
+    ///     .ssl_client_cert_from_der(cert)
+    ///     .ssl_client_key_from_der(key);
+    /// ```
+    pub fn ssl_client_cert_from_der(mut self, cert: impl AsRef<[u8]>) -> Self {
+        self.ssl_client_cert = Some(CertificateInput::Der(cert.as_ref().to_vec()));
+        self
+    }
+
+    /// Sets the SSL client key as DER-encoded bytes.
+    ///
+    /// With native-tls, this must be a PKCS#8 key; rustls also accepts PKCS#1 (RSA) and
+    /// SEC1 (EC) keys.
+    pub fn ssl_client_key_from_der(mut self, key: impl AsRef<[u8]>) -> Self {
+        self.ssl_client_key = Some(CertificateInput::Der(key.as_ref().to_vec()));
+        self
+    }
+
+    /// Sets the name of a PKCS#12 (`.p12` or `.pfx`) file containing the SSL client key and
+    /// certificate, in place of [`ssl_client_cert()`][Self::ssl_client_cert] and
+    /// [`ssl_client_key()`][Self::ssl_client_key].
+    ///
+    /// Set its password with [`ssl_client_key_password()`][Self::ssl_client_key_password].
+    /// Requires the native-tls backend; rustls does not support PKCS#12.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgSslMode, PgConnectOptions};
+    /// let options = PgConnectOptions::new()
+    ///     .ssl_mode(PgSslMode::VerifyFull)
+    ///     .ssl_client_pkcs12("./client.p12")
+    ///     .ssl_client_key_password("hunter2");
+    /// ```
+    pub fn ssl_client_pkcs12(mut self, path: impl AsRef<Path>) -> Self {
+        self.ssl_client_pkcs12 = Some(CertificateInput::File(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Sets the SSL client key and certificate as a DER-encoded PKCS#12 bundle.
+    ///
+    /// See [`ssl_client_pkcs12()`][Self::ssl_client_pkcs12].
+    pub fn ssl_client_pkcs12_from_der(mut self, pkcs12: impl AsRef<[u8]>) -> Self {
+        self.ssl_client_pkcs12 = Some(CertificateInput::Der(pkcs12.as_ref().to_vec()));
+        self
+    }
+
+    /// Sets the password which decrypts the PKCS#12 bundle of the SSL client key.
+    ///
+    /// Encrypted PEM keys are not supported by either TLS backend; to use one, decrypt it
+    /// or convert it with its certificate to PKCS#12, e.g. with
+    /// `openssl pkcs12 -export -in client.crt -inkey client.key -out client.p12`.
+    ///
+    /// Also set by the `sslpassword` URL parameter, as in libpq.
+    pub fn ssl_client_key_password(mut self, password: impl Into<String>) -> Self {
+        self.ssl_client_key_password = Some(password.into());
+        self
+    }
+
+    /// Use `config` for TLS as is, with full control over its roots, certificate verification,
+    /// client authentication, ALPN and key logging.
+    ///
+    /// The other SSL options then only decide whether TLS is used: the root certificate,
+    /// client certificate and key are ignored, and so is the verification implied by
+    /// [`ssl_mode()`][Self::ssl_mode], which is left to `config`. The server name sent is the
+    /// [`host()`][Self::host].
+    ///
+    /// Requires a `tls-rustls-*` feature; `config` is a `ClientConfig` of rustls 0.23, also
+    /// re-exported as `sqlx::rustls`. rustls is used even if `tls-native-tls` is also enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use std::sync::Arc;
+    /// use sqlx::postgres::{PgConnectOptions, PgSslMode};
+    ///
+    /// let mut config = rustls::ClientConfig::builder()
+    ///     .with_root_certificates(roots)
+    ///     .with_no_client_auth();
+    ///
+    /// // write the session keys to `$SSLKEYLOGFILE`, to decrypt a capture in Wireshark
+    /// config.key_log = Arc::new(rustls::KeyLogFile::new());
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .ssl_mode(PgSslMode::Require)
+    ///     .ssl_client_config(Arc::new(config));
+    /// ```
+    #[cfg(feature = "_tls-rustls")]
+    pub fn ssl_client_config(
+        mut self,
+        config: std::sync::Arc<crate::net::tls::rustls::ClientConfig>,
+    ) -> Self {
+        self.ssl_client_config = Some(config.into());
+        self
+    }
+
+    /// Sets whether TLS sessions are resumed, so that reconnecting, such as a pool does after
+    /// a failover, does not take a full handshake.
+    ///
+    /// Sessions are shared by the connections opened with clones of these options, such as
+    /// those of a pool, for each host and port. Whether a session is resumed is up to the
+    /// server: PostgreSQL itself does not resume sessions, but proxies which terminate TLS in
+    /// front of it may. Only supported with rustls.
+    ///
+    /// By default, sessions are resumed.
+    pub fn ssl_session_resumption(mut self, enabled: bool) -> Self {
+        self.ssl_session_cache = enabled.then(TlsSessionCache::default);
+        self
+    }
+
     /// Sets PEM encoded trusted SSL Certificate Authorities (CA).
     ///
     /// # Example
@@ -347,6 +674,77 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the initial capacity of the connection's read buffer, in bytes.
+    ///
+    /// The buffer grows to fit larger messages, and goes back to this capacity on
+    /// [`Connection::shrink_buffers()`][sqlx_core::connection::Connection::shrink_buffers].
+    /// A smaller buffer saves memory on services with many idle connections; a larger one
+    /// saves system calls when reading large results.
+    ///
+    /// The default is 8 KiB.
+    pub fn read_buffer_size(mut self, size: usize) -> Self {
+        self.read_buffer_size = size;
+        self
+    }
+
+    /// Sets the initial capacity of the connection's write buffer, in bytes.
+    ///
+    /// Like the read buffer, it grows to fit larger messages and goes back to this capacity on
+    /// [`Connection::shrink_buffers()`][sqlx_core::connection::Connection::shrink_buffers].
+    ///
+    /// The default is 8 KiB.
+    pub fn write_buffer_size(mut self, size: usize) -> Self {
+        self.write_buffer_size = size;
+        self
+    }
+
+    /// Sets the largest message the server may send, in bytes, such as a row or an error.
+    ///
+    /// A larger message fails the query it belongs to with [`Error::Protocol`] instead of
+    /// being buffered; it is read and dropped in pieces, so the connection remains usable.
+    /// Guards against running out of memory on unexpectedly large values, or on a server
+    /// which is malicious or broken.
+    ///
+    /// By default there is no limit.
+    ///
+    /// [`Error::Protocol`]: crate::Error::Protocol
+    pub fn max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = Some(size);
+        self
+    }
+
+    /// Sets the most rows a query may return, failing it with [`Error::ResultTooLarge`] when
+    /// it returns more.
+    ///
+    /// Guards against a single unbounded query, e.g. collected with `fetch_all()`, exhausting
+    /// the memory of the application. The rest of the result is discarded, so the connection
+    /// remains usable. A query can override this with
+    /// [`Query::max_rows()`][sqlx_core::query::Query::max_rows], or lift it with
+    /// [`Query::unlimited()`][sqlx_core::query::Query::unlimited]. The queries SQLx runs itself,
+    /// e.g. to apply migrations or resolve types, are not limited.
+    ///
+    /// By default there is no limit.
+    ///
+    /// [`Error::ResultTooLarge`]: crate::Error::ResultTooLarge
+    pub fn max_rows(mut self, max: u64) -> Self {
+        self.max_rows = Some(max);
+        self
+    }
+
+    /// Sets the most bytes the rows returned by a query may add up to, as sent by the server,
+    /// failing it with [`Error::ResultTooLarge`] when they add up to more.
+    ///
+    /// See [`max_rows()`][Self::max_rows] for details. A query can override this with
+    /// [`Query::max_result_bytes()`][sqlx_core::query::Query::max_result_bytes].
+    ///
+    /// By default there is no limit.
+    ///
+    /// [`Error::ResultTooLarge`]: crate::Error::ResultTooLarge
+    pub fn max_result_bytes(mut self, max: u64) -> Self {
+        self.max_result_bytes = Some(max);
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -414,6 +812,27 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the time zone of the session (the `TimeZone` setting). Defaults to `UTC`.
+    ///
+    /// This is the zone Postgres uses to display `TIMESTAMPTZ` values as text and to interpret
+    /// timestamps without an explicit offset. With the `chrono-tz` feature, it is also the zone
+    /// `DateTime<chrono_tz::Tz>` values are decoded in.
+    ///
+    /// The zone can be changed for the rest of a session with `SET TIME ZONE`, or for
+    /// a single transaction with `SET LOCAL TIME ZONE`. Postgres reports the change once
+    /// the `SET` statement completes, so it applies to the queries executed after it.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .time_zone("Europe/Berlin");
+    /// ```
+    pub fn time_zone(mut self, time_zone: &str) -> Self {
+        self.time_zone = time_zone.to_owned().into();
+        self
+    }
+
     /// Set additional startup options for the connection as a list of key-value pairs.
     ///
     /// Escapes the options’ backslash and space characters as per
@@ -445,6 +864,76 @@ impl PgConnectOptions {
         self
     }
 
+    /// Log the bind values of statements along with them, for the given types.
+    ///
+    /// Bind values are never logged by default, as they are likely to contain personal data
+    /// or secrets. With this option, the values of the types named in `types`, such as
+    /// `"INT8"` or `"BOOL"`, are logged in the `db.bind_values` field of the statement's
+    /// event; the values of any other type are replaced with the name of their type. Types
+    /// are named as by [`TypeInfo::name()`][sqlx_core::type_info::TypeInfo::name], ignoring
+    /// case.
+    ///
+    /// Values are logged for the built-in boolean, integer, floating-point, text and `UUID`
+    /// types; the values of any other type are always replaced with the name of their type.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .log_bind_values(["INT4", "INT8", "BOOL", "UUID"]);
+    /// ```
+    pub fn log_bind_values<I>(mut self, types: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.log_settings.log_bind_values(types);
+        self
+    }
+
+    /// Append a [sqlcommenter](https://google.github.io/sqlcommenter/) comment with the tags
+    /// and trace context of `commenter` to each statement.
+    ///
+    /// The comment shows up wherever Postgres reports the text of the statement, such as in
+    /// its logs or `pg_stat_activity`. See [`SqlCommenter`] for details.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// use sqlx_core::sql_comment::SqlCommenter;
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .sql_commenter(SqlCommenter::new().tag("application", "billing"));
+    /// ```
+    pub fn sql_commenter(mut self, commenter: SqlCommenter) -> Self {
+        self.sql_commenter = Some(commenter);
+        self
+    }
+
+    /// Install `interceptor`, which may rewrite or reject each statement before it is
+    /// executed.
+    ///
+    /// Interceptors are called in the order they were installed, each with the statement
+    /// as rewritten by the previous ones. See [`QueryInterceptor`] for details.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// use sqlx_core::interceptor::{InterceptedQuery, Interception};
+    ///
+    /// let options = PgConnectOptions::new().interceptor(|query: &InterceptedQuery<'_>| {
+    ///     if query.sql.trim_start().to_ascii_uppercase().starts_with("TRUNCATE") {
+    ///         Interception::Reject("TRUNCATE is not allowed".into())
+    ///     } else {
+    ///         Interception::Continue
+    ///     }
+    /// });
+    /// ```
+    pub fn interceptor(mut self, interceptor: impl QueryInterceptor) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
@@ -560,6 +1049,19 @@ impl PgConnectOptions {
         self.application_name.as_deref()
     }
 
+    /// Get the time zone of the session.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new();
+    /// assert_eq!(options.get_time_zone(), "UTC");
+    /// ```
+    pub fn get_time_zone(&self) -> &str {
+        &self.time_zone
+    }
+
     /// Get the options.
     ///
     /// # Example
@@ -8,11 +8,49 @@ use sqlx_core::row::debug_row;
 pub(crate) use sqlx_core::row::Row;
 use std::sync::Arc;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "json")]
+mod deserialize;
+#[cfg(feature = "json")]
+mod export;
+#[cfg(feature = "json")]
+mod json;
+mod map;
+#[cfg(feature = "spill")]
+mod spill;
+
+#[cfg(feature = "arrow")]
+pub use arrow::PgFetchArrowExt;
+#[cfg(feature = "json")]
+pub use deserialize::PgRowDeserializeError;
+#[cfg(feature = "json")]
+pub use export::PgExportExt;
+#[cfg(feature = "json")]
+pub use json::PgFetchJsonExt;
+pub use map::{PgFetchMapsExt, PgRowMap};
+#[cfg(feature = "spill")]
+pub use spill::{PgFetchSpilledExt, PgSpilledRows, PgSpilledRowsIter};
+
 /// Implementation of [`Row`] for PostgreSQL.
 pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    pub(crate) time_zone: Option<Arc<str>>,
+}
+
+impl PgRow {
+    /// Copies the row out of the buffers of the connection it was received on.
+    ///
+    /// Rows borrow their bytes from buffers which the connection shares between the rows it
+    /// receives, and reuses once those rows are dropped; a row kept for long keeps the
+    /// buffer it came from alive, which is usually much larger than the row. Detaching the
+    /// rows which are kept, such as a few out of a large result, lets the connection reuse
+    /// its buffers.
+    pub fn detach(&mut self) {
+        self.data.detach();
+    }
 }
 
 impl Row for PgRow {
@@ -35,10 +73,39 @@ impl Row for PgRow {
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
+            time_zone: self.time_zone.as_deref(),
         })
     }
 }
 
+// the type domains are based on, or `ty` itself
+#[cfg(any(feature = "json", feature = "arrow"))]
+fn base_type(ty: &crate::PgTypeInfo) -> &crate::PgTypeInfo {
+    match ty.kind() {
+        crate::PgTypeKind::Domain(base) => base_type(base),
+        _ => ty,
+    }
+}
+
+#[cfg(any(feature = "json", feature = "arrow"))]
+fn format_uuid(bytes: &[u8]) -> Result<String, crate::error::BoxDynError> {
+    if bytes.len() != 16 {
+        return Err(format!("expected 16 bytes for a UUID, got {}", bytes.len()).into());
+    }
+
+    let mut uuid = String::with_capacity(36);
+
+    for (i, byte) in bytes.iter().enumerate() {
+        if matches!(i, 4 | 6 | 8 | 10) {
+            uuid.push('-');
+        }
+
+        uuid.push_str(&format!("{byte:02x}"));
+    }
+
+    Ok(uuid)
+}
+
 impl ColumnIndex<PgRow> for &'_ str {
     fn index(&self, row: &PgRow) -> Result<usize, Error> {
         row.metadata
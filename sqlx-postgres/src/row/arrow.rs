@@ -0,0 +1,537 @@
+use std::borrow::Cow;
+use std::cmp;
+use std::sync::Arc;
+
+use arrow_array::builder::{
+    ArrayBuilder, BinaryBuilder, BooleanBuilder, Date32Builder, FixedSizeBinaryBuilder,
+    Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder, StringBuilder,
+    Time64MicrosecondBuilder, TimestampMicrosecondBuilder, UInt32Builder,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use super::{base_type, format_uuid, Row};
+use crate::decode::Decode;
+use crate::error::{BoxDynError, Error};
+use crate::executor::{Execute, Executor};
+use crate::type_info::PgType;
+use crate::types::{Oid, PgDecimal};
+use crate::value::ValueRef;
+use crate::{PgColumn, PgRow, PgTypeInfo, PgTypeKind, PgValueFormat, PgValueRef, Postgres};
+
+// Postgres counts dates and timestamps from 2000-01-01, Arrow from 1970-01-01
+const EPOCH_OFFSET_DAYS: i32 = 10_957;
+const EPOCH_OFFSET_MICROS: i64 = 946_684_800_000_000;
+
+/// Fetch the results of a query as Arrow [`RecordBatch`]es, for handing them to DataFusion,
+/// Parquet writers and the like without a Rust type per row.
+///
+/// The values of each row are decoded straight into the arrays of the batch being built.
+/// Unless the schema hint has a field of the same name, each column becomes a nullable field
+/// typed after its Postgres type:
+///
+/// | Postgres                                                       | Arrow                              |
+/// |----------------------------------------------------------------|------------------------------------|
+/// | `BOOL`                                                         | `Boolean`                          |
+/// | `INT2`, `INT4`, `INT8`                                         | `Int16`, `Int32`, `Int64`          |
+/// | `OID`                                                          | `UInt32`                           |
+/// | `FLOAT4`, `FLOAT8`                                             | `Float32`, `Float64`               |
+/// | `TEXT`, `VARCHAR`, `CHAR(N)`, `NAME`, enums, `JSON`, `JSONB`   | `Utf8`                             |
+/// | `NUMERIC`                                                      | `Utf8`, to keep its precision      |
+/// | `BYTEA`                                                        | `Binary`                           |
+/// | `UUID`                                                         | `FixedSizeBinary(16)`              |
+/// | `DATE`                                                         | `Date32`                           |
+/// | `TIME`                                                         | `Time64(Microsecond)`              |
+/// | `TIMESTAMP`                                                    | `Timestamp(Microsecond, None)`     |
+/// | `TIMESTAMPTZ`                                                  | `Timestamp(Microsecond, "+00:00")` |
+///
+/// Queries without arguments run with [`raw_sql()`][sqlx_core::raw_sql::raw_sql] receive their
+/// values as text, in which case the columns of types other than `BOOL`, the numbers and
+/// `BYTEA` become `Utf8`. Columns of other types can be cast to `TEXT` in the query.
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use futures_util::TryStreamExt;
+/// use sqlx::postgres::PgFetchArrowExt;
+///
+/// let mut batches =
+///     sqlx::query("SELECT id, name, created_at FROM users").fetch_arrow(pool, None, 8192);
+///
+/// while let Some(batch) = batches.try_next().await? {
+///     // write `batch` to a Parquet file, register it with DataFusion, ...
+///     # drop(batch);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait PgFetchArrowExt<'q>: Execute<'q, Postgres> + 'q {
+    /// Execute the query and return its rows in batches of up to `batch_size` rows.
+    ///
+    /// The fields of `schema_hint` set the name, type and nullability of the columns of the
+    /// same name; the other columns are typed as above. Besides the types above, integers
+    /// convert to wider integer and floating-point types, and most values convert to `Utf8`.
+    ///
+    /// A result without rows returns no batches.
+    ///
+    /// ### Errors
+    /// If a column has a type which cannot be converted to its field, such as a type not listed
+    /// above or `NULL` in a field which is not nullable.
+    fn fetch_arrow<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        schema_hint: Option<SchemaRef>,
+        batch_size: usize,
+    ) -> BoxStream<'e, Result<RecordBatch, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = Postgres>,
+    {
+        let mut rows = executor.fetch(self);
+        let batch_size = cmp::max(batch_size, 1);
+
+        Box::pin(try_stream! {
+            // the columns are only known once there is a row
+            let Some(first) = rows.try_next().await? else {
+                return Ok(());
+            };
+
+            let mut batch = BatchBuilder::new(&first, schema_hint.as_deref(), batch_size)?;
+            let mut next = Some(first);
+
+            while let Some(row) = next {
+                batch.append(&row)?;
+
+                if batch.len == batch_size {
+                    r#yield!(batch.finish()?);
+                }
+
+                next = rows.try_next().await?;
+            }
+
+            if batch.len > 0 {
+                r#yield!(batch.finish()?);
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<'q, T: Execute<'q, Postgres> + 'q> PgFetchArrowExt<'q> for T {}
+
+struct BatchBuilder {
+    schema: SchemaRef,
+    columns: Vec<ColumnBuilder>,
+    len: usize,
+}
+
+impl BatchBuilder {
+    fn new(row: &PgRow, schema_hint: Option<&Schema>, capacity: usize) -> Result<Self, Error> {
+        let mut fields = Vec::with_capacity(row.len());
+        let mut columns = Vec::with_capacity(row.len());
+
+        for column in row.columns() {
+            let field = match schema_hint.and_then(|hint| hint.field_with_name(&column.name).ok()) {
+                Some(field) => field.clone(),
+                None => infer_field(column, row.format)?,
+            };
+
+            let builder = ColumnBuilder::new(field.data_type(), capacity).ok_or_else(|| {
+                Error::ColumnDecode {
+                    index: column.name.to_string(),
+                    source: format!("Arrow type {} is not supported", field.data_type()).into(),
+                }
+            })?;
+
+            fields.push(field);
+            columns.push(builder);
+        }
+
+        Ok(BatchBuilder {
+            schema: Arc::new(Schema::new(fields)),
+            columns,
+            len: 0,
+        })
+    }
+
+    fn append(&mut self, row: &PgRow) -> Result<(), Error> {
+        for (index, builder) in self.columns.iter_mut().enumerate() {
+            builder
+                .append(row.try_get_raw(index)?)
+                .map_err(|source| Error::ColumnDecode {
+                    index: row.columns()[index].name.to_string(),
+                    source,
+                })?;
+        }
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<RecordBatch, Error> {
+        let arrays = self.columns.iter_mut().map(ColumnBuilder::finish).collect();
+
+        self.len = 0;
+
+        // also checks that fields which are not nullable have no nulls
+        RecordBatch::try_new(self.schema.clone(), arrays).map_err(|e| Error::Decode(e.into()))
+    }
+}
+
+fn infer_field(column: &PgColumn, format: PgValueFormat) -> Result<Field, Error> {
+    let ty = base_type(&column.type_info);
+    let binary = format == PgValueFormat::Binary;
+
+    let data_type = match &ty.0 {
+        PgType::Bool => DataType::Boolean,
+        PgType::Int2 => DataType::Int16,
+        PgType::Int4 => DataType::Int32,
+        PgType::Int8 => DataType::Int64,
+        PgType::Oid => DataType::UInt32,
+        PgType::Float4 => DataType::Float32,
+        PgType::Float8 => DataType::Float64,
+        PgType::Bytea => DataType::Binary,
+        PgType::Uuid if binary => DataType::FixedSizeBinary(16),
+        PgType::Date if binary => DataType::Date32,
+        PgType::Time if binary => DataType::Time64(TimeUnit::Microsecond),
+        PgType::Timestamp if binary => DataType::Timestamp(TimeUnit::Microsecond, None),
+        PgType::Timestamptz if binary => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+        }
+
+        PgType::Text
+        | PgType::Varchar
+        | PgType::Bpchar
+        | PgType::Name
+        | PgType::Unknown
+        | PgType::Json
+        | PgType::Jsonb
+        | PgType::Numeric => DataType::Utf8,
+
+        _ if has_text(ty, format) => DataType::Utf8,
+
+        _ => {
+            return Err(Error::ColumnDecode {
+                index: column.name.to_string(),
+                source: format!(
+                    "values of type {} cannot be converted to Arrow; cast the column to TEXT in \
+                     the query",
+                    ty.0.display_name()
+                )
+                .into(),
+            })
+        }
+    };
+
+    Ok(Field::new(&*column.name, data_type, true))
+}
+
+// every value has a textual representation in the text format, and
+// enums and `citext` use their labels and text in the binary format as well
+fn has_text(ty: &PgTypeInfo, format: PgValueFormat) -> bool {
+    format == PgValueFormat::Text
+        || matches!(ty.kind(), PgTypeKind::Enum(_))
+        || ty.0.display_name().eq_ignore_ascii_case("citext")
+}
+
+/// Builds the array of one column, decoding each value straight into it.
+enum ColumnBuilder {
+    Boolean(BooleanBuilder),
+    Int16(Int16Builder),
+    Int32(Int32Builder),
+    Int64(Int64Builder),
+    UInt32(UInt32Builder),
+    Float32(Float32Builder),
+    Float64(Float64Builder),
+    Utf8(StringBuilder),
+    Binary(BinaryBuilder),
+    Uuid(FixedSizeBinaryBuilder),
+    Date32(Date32Builder),
+    Time64(Time64MicrosecondBuilder),
+    Timestamp(TimestampMicrosecondBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(data_type: &DataType, capacity: usize) -> Option<Self> {
+        Some(match data_type {
+            DataType::Boolean => Self::Boolean(BooleanBuilder::with_capacity(capacity)),
+            DataType::Int16 => Self::Int16(Int16Builder::with_capacity(capacity)),
+            DataType::Int32 => Self::Int32(Int32Builder::with_capacity(capacity)),
+            DataType::Int64 => Self::Int64(Int64Builder::with_capacity(capacity)),
+            DataType::UInt32 => Self::UInt32(UInt32Builder::with_capacity(capacity)),
+            DataType::Float32 => Self::Float32(Float32Builder::with_capacity(capacity)),
+            DataType::Float64 => Self::Float64(Float64Builder::with_capacity(capacity)),
+            DataType::Utf8 => Self::Utf8(StringBuilder::with_capacity(capacity, capacity * 16)),
+            DataType::Binary => Self::Binary(BinaryBuilder::with_capacity(capacity, capacity * 16)),
+            DataType::FixedSizeBinary(16) => {
+                Self::Uuid(FixedSizeBinaryBuilder::with_capacity(capacity, 16))
+            }
+            DataType::Date32 => Self::Date32(Date32Builder::with_capacity(capacity)),
+            DataType::Time64(TimeUnit::Microsecond) => {
+                Self::Time64(Time64MicrosecondBuilder::with_capacity(capacity))
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, time_zone) => Self::Timestamp(
+                TimestampMicrosecondBuilder::with_capacity(capacity)
+                    .with_timezone_opt(time_zone.clone()),
+            ),
+            _ => return None,
+        })
+    }
+
+    fn append(&mut self, value: PgValueRef<'_>) -> Result<(), BoxDynError> {
+        if value.is_null() {
+            self.append_null();
+            return Ok(());
+        }
+
+        match self {
+            Self::Boolean(builder) => builder.append_value(decode_bool(value)?),
+            Self::Int16(builder) => builder.append_value(decode_int(value)?.try_into()?),
+            Self::Int32(builder) => builder.append_value(decode_int(value)?.try_into()?),
+            Self::Int64(builder) => builder.append_value(decode_int(value)?),
+            Self::UInt32(builder) => builder.append_value(decode_int(value)?.try_into()?),
+            Self::Float32(builder) => builder.append_value(decode_float4(value)?),
+            Self::Float64(builder) => builder.append_value(decode_float8(value)?),
+            Self::Utf8(builder) => builder.append_value(decode_text(value)?),
+            Self::Binary(builder) => builder.append_value(decode_bytea(value)?),
+            Self::Uuid(builder) => builder.append_value(decode_uuid(value)?)?,
+            Self::Date32(builder) => builder.append_value(decode_date(value)?),
+            Self::Time64(builder) => builder.append_value(decode_time(value)?),
+            Self::Timestamp(builder) => builder.append_value(decode_timestamp(value)?),
+        }
+
+        Ok(())
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            Self::Boolean(builder) => builder.append_null(),
+            Self::Int16(builder) => builder.append_null(),
+            Self::Int32(builder) => builder.append_null(),
+            Self::Int64(builder) => builder.append_null(),
+            Self::UInt32(builder) => builder.append_null(),
+            Self::Float32(builder) => builder.append_null(),
+            Self::Float64(builder) => builder.append_null(),
+            Self::Utf8(builder) => builder.append_null(),
+            Self::Binary(builder) => builder.append_null(),
+            Self::Uuid(builder) => builder.append_null(),
+            Self::Date32(builder) => builder.append_null(),
+            Self::Time64(builder) => builder.append_null(),
+            Self::Timestamp(builder) => builder.append_null(),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        let builder: &mut dyn ArrayBuilder = match self {
+            Self::Boolean(builder) => builder,
+            Self::Int16(builder) => builder,
+            Self::Int32(builder) => builder,
+            Self::Int64(builder) => builder,
+            Self::UInt32(builder) => builder,
+            Self::Float32(builder) => builder,
+            Self::Float64(builder) => builder,
+            Self::Utf8(builder) => builder,
+            Self::Binary(builder) => builder,
+            Self::Uuid(builder) => builder,
+            Self::Date32(builder) => builder,
+            Self::Time64(builder) => builder,
+            Self::Timestamp(builder) => builder,
+        };
+
+        builder.finish()
+    }
+}
+
+fn decode<'r, T: Decode<'r, Postgres>>(value: PgValueRef<'r>) -> Result<T, BoxDynError> {
+    T::decode(value)
+}
+
+fn decode_bool(value: PgValueRef<'_>) -> Result<bool, BoxDynError> {
+    match base_type(&value.type_info).0 {
+        PgType::Bool => decode::<bool>(value),
+        _ => Err(cannot_convert(&value, "Boolean")),
+    }
+}
+
+fn decode_int(value: PgValueRef<'_>) -> Result<i64, BoxDynError> {
+    match base_type(&value.type_info).0 {
+        PgType::Int2 => Ok(decode::<i16>(value)?.into()),
+        PgType::Int4 => Ok(decode::<i32>(value)?.into()),
+        PgType::Int8 => decode::<i64>(value),
+        PgType::Oid => Ok(decode::<Oid>(value)?.0.into()),
+        _ => Err(cannot_convert(&value, "an integer")),
+    }
+}
+
+fn decode_float4(value: PgValueRef<'_>) -> Result<f32, BoxDynError> {
+    match base_type(&value.type_info).0 {
+        PgType::Float4 => decode::<f32>(value),
+        PgType::Int2 => Ok(decode::<i16>(value)?.into()),
+        _ => Err(cannot_convert(&value, "Float32")),
+    }
+}
+
+fn decode_float8(value: PgValueRef<'_>) -> Result<f64, BoxDynError> {
+    match base_type(&value.type_info).0 {
+        PgType::Float8 => decode::<f64>(value),
+        PgType::Float4 => Ok(decode::<f32>(value)?.into()),
+        PgType::Int2 => Ok(decode::<i16>(value)?.into()),
+        PgType::Int4 => Ok(decode::<i32>(value)?.into()),
+        PgType::Numeric => Ok(decode::<PgDecimal>(value)?.to_string().parse()?),
+        _ => Err(cannot_convert(&value, "Float64")),
+    }
+}
+
+fn decode_text(value: PgValueRef<'_>) -> Result<Cow<'_, str>, BoxDynError> {
+    let ty = base_type(&value.type_info);
+
+    if has_text(ty, value.format()) {
+        return Ok(Cow::Borrowed(value.as_str()?));
+    }
+
+    Ok(match ty.0 {
+        PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+            Cow::Borrowed(value.as_str()?)
+        }
+        PgType::Json => Cow::Borrowed(value.as_str()?),
+        // binary `JSONB` starts with a version number
+        PgType::Jsonb => match value.as_bytes()?.split_first() {
+            Some((1, json)) => Cow::Borrowed(std::str::from_utf8(json)?),
+            _ => return Err("unsupported JSONB format version; expected 1".into()),
+        },
+        PgType::Numeric => Cow::Owned(decode::<PgDecimal>(value)?.to_string()),
+        PgType::Uuid => Cow::Owned(format_uuid(value.as_bytes()?)?),
+        PgType::Bool => Cow::Owned(decode::<bool>(value)?.to_string()),
+        PgType::Int2 | PgType::Int4 | PgType::Int8 | PgType::Oid => {
+            Cow::Owned(decode_int(value)?.to_string())
+        }
+        PgType::Float4 => Cow::Owned(decode::<f32>(value)?.to_string()),
+        PgType::Float8 => Cow::Owned(decode::<f64>(value)?.to_string()),
+        _ => return Err(cannot_convert(&value, "Utf8")),
+    })
+}
+
+fn decode_bytea(value: PgValueRef<'_>) -> Result<Cow<'_, [u8]>, BoxDynError> {
+    match (&base_type(&value.type_info).0, value.format()) {
+        (PgType::Bytea, PgValueFormat::Binary) => Ok(Cow::Borrowed(value.as_bytes()?)),
+        (PgType::Bytea, PgValueFormat::Text) => Ok(Cow::Owned(decode::<Vec<u8>>(value)?)),
+        _ => Err(cannot_convert(&value, "Binary")),
+    }
+}
+
+fn decode_uuid(value: PgValueRef<'_>) -> Result<[u8; 16], BoxDynError> {
+    if !matches!(base_type(&value.type_info).0, PgType::Uuid) {
+        return Err(cannot_convert(&value, "FixedSizeBinary(16)"));
+    }
+
+    let mut uuid = [0; 16];
+
+    match value.format() {
+        PgValueFormat::Binary => uuid = value.as_bytes()?.try_into()?,
+        PgValueFormat::Text => hex::decode_to_slice(value.as_str()?.replace('-', ""), &mut uuid)?,
+    }
+
+    Ok(uuid)
+}
+
+fn decode_date(value: PgValueRef<'_>) -> Result<i32, BoxDynError> {
+    if !is_binary(&value, PgType::Date) {
+        return Err(cannot_convert(&value, "Date32"));
+    }
+
+    match decode::<i32>(value)? {
+        days @ (i32::MIN | i32::MAX) => Err(infinite(days == i32::MAX)),
+        days => days
+            .checked_add(EPOCH_OFFSET_DAYS)
+            .ok_or_else(|| "date out of range for Date32".into()),
+    }
+}
+
+fn decode_time(value: PgValueRef<'_>) -> Result<i64, BoxDynError> {
+    if !is_binary(&value, PgType::Time) {
+        return Err(cannot_convert(&value, "Time64"));
+    }
+
+    decode::<i64>(value)
+}
+
+fn decode_timestamp(value: PgValueRef<'_>) -> Result<i64, BoxDynError> {
+    if !is_binary(&value, PgType::Timestamp) && !is_binary(&value, PgType::Timestamptz) {
+        return Err(cannot_convert(&value, "Timestamp"));
+    }
+
+    // `TIMESTAMPTZ` is in UTC on the wire
+    match decode::<i64>(value)? {
+        micros @ (i64::MIN | i64::MAX) => Err(infinite(micros == i64::MAX)),
+        micros => micros
+            .checked_add(EPOCH_OFFSET_MICROS)
+            .ok_or_else(|| "timestamp out of range for Timestamp".into()),
+    }
+}
+
+// dates and times are only decoded from the binary format
+fn is_binary(value: &PgValueRef<'_>, ty: PgType) -> bool {
+    base_type(&value.type_info).0 == ty && value.format() == PgValueFormat::Binary
+}
+
+fn infinite(positive: bool) -> BoxDynError {
+    let sign = if positive { "" } else { "-" };
+    format!("{sign}infinity cannot be converted to Arrow").into()
+}
+
+fn cannot_convert(value: &PgValueRef<'_>, to: &str) -> BoxDynError {
+    let format = match value.format() {
+        PgValueFormat::Binary => "",
+        PgValueFormat::Text => " in the text format",
+    };
+
+    format!(
+        "values of type {}{format} cannot be converted to {to}",
+        base_type(&value.type_info).0.display_name()
+    )
+    .into()
+}
+
+#[test]
+fn it_infers_fields_from_column_types() {
+    let column = |type_info: PgTypeInfo| PgColumn {
+        ordinal: 0,
+        name: "column".into(),
+        type_info,
+        origin: Default::default(),
+        relation_id: None,
+        relation_attribute_no: None,
+        nullable: None,
+        type_modifier: None,
+    };
+
+    let infer = |type_info, format| {
+        infer_field(&column(type_info), format)
+            .map(|field| field.data_type().clone())
+            .ok()
+    };
+
+    assert_eq!(
+        infer(PgTypeInfo::INT8, PgValueFormat::Binary),
+        Some(DataType::Int64)
+    );
+    assert_eq!(
+        infer(PgTypeInfo::UUID, PgValueFormat::Binary),
+        Some(DataType::FixedSizeBinary(16))
+    );
+    assert_eq!(
+        infer(PgTypeInfo::UUID, PgValueFormat::Text),
+        Some(DataType::Utf8)
+    );
+    assert_eq!(
+        infer(PgTypeInfo::TIMESTAMPTZ, PgValueFormat::Binary),
+        Some(DataType::Timestamp(
+            TimeUnit::Microsecond,
+            Some("+00:00".into())
+        ))
+    );
+    assert_eq!(infer(PgTypeInfo::POINT, PgValueFormat::Binary), None);
+}
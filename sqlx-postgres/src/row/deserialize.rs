@@ -0,0 +1,486 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{
+    self, DeserializeSeed, Error as _, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer};
+use serde_json::Value as JsonValue;
+
+use super::{base_type, format_uuid, Row};
+use crate::decode::Decode;
+use crate::error::{BoxDynError, Error};
+use crate::type_info::PgType;
+use crate::types::{Oid, PgDecimal};
+use crate::value::ValueRef;
+use crate::{PgColumn, PgRow, PgTypeKind, PgValueFormat, PgValueRef, Postgres};
+
+impl PgRow {
+    /// Deserialize the row into any `T: Deserialize`, matching the fields of `T` to the columns of
+    /// the row by name.
+    ///
+    /// Unlike [`FromRow`][sqlx_core::from_row::FromRow], the type of each field is only known to
+    /// `serde`, so this works with types built at runtime such as
+    /// [`serde_json::Value`] and [`HashMap`][std::collections::HashMap]s, and with types which
+    /// only derive `Deserialize`.
+    ///
+    /// Values are converted as follows:
+    ///
+    /// * `BOOL`, the integer and floating-point types and `OID` become numbers and booleans.
+    /// * `TEXT`, `VARCHAR`, `CHAR(N)`, `NAME`, enums and `UUID` become strings;
+    ///   `TEXT` columns can be borrowed from the row as `&str`.
+    /// * `NUMERIC` becomes a string, to keep its precision, unless the field is a number.
+    /// * `BYTEA` becomes bytes.
+    /// * `JSON` and `JSONB` are deserialized from the JSON document they contain.
+    /// * With the `chrono` feature, dates and times become strings in the format `chrono` expects.
+    /// * Arrays of the types above become sequences, and `NULL` becomes `None`.
+    ///
+    /// Columns of other types can be cast to `TEXT` in the query.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Report {
+    ///     region: String,
+    ///     orders: i64,
+    ///     revenue: Option<f64>,
+    /// }
+    ///
+    /// let row = sqlx::query("SELECT region, count(*) AS orders, sum(total) AS revenue FROM orders GROUP BY region")
+    ///     .fetch_one(&mut *conn)
+    ///     .await?;
+    ///
+    /// let report: Report = row.deserialize()?;
+    /// let columns: HashMap<String, serde_json::Value> = row.deserialize()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn deserialize<'r, T: Deserialize<'r>>(&'r self) -> Result<T, Error> {
+        T::deserialize(self).map_err(|e| match e.column {
+            Some(index) => Error::ColumnDecode {
+                index,
+                source: e.source,
+            },
+            None => Error::Decode(e.source),
+        })
+    }
+}
+
+/// The error returned when deserializing a [`PgRow`] fails.
+#[derive(Debug)]
+pub struct PgRowDeserializeError {
+    column: Option<String>,
+    source: BoxDynError,
+}
+
+impl PgRowDeserializeError {
+    /// The name of the column which could not be deserialized, if the error is about a column.
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_deref()
+    }
+
+    fn in_column(mut self, column: &PgColumn) -> Self {
+        self.column.get_or_insert_with(|| column.name.to_string());
+        self
+    }
+}
+
+impl Display for PgRowDeserializeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.column {
+            Some(column) => write!(f, "error deserializing column {column:?}: {}", self.source),
+            None => write!(f, "error deserializing row: {}", self.source),
+        }
+    }
+}
+
+impl StdError for PgRowDeserializeError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+impl de::Error for PgRowDeserializeError {
+    fn custom<T: Display>(msg: T) -> Self {
+        PgRowDeserializeError {
+            column: None,
+            source: msg.to_string().into(),
+        }
+    }
+}
+
+impl From<BoxDynError> for PgRowDeserializeError {
+    fn from(source: BoxDynError) -> Self {
+        PgRowDeserializeError {
+            column: None,
+            source,
+        }
+    }
+}
+
+/// Deserializes the row as a map from column names to values, or as a sequence of values.
+impl<'de> Deserializer<'de> for &'de PgRow {
+    type Error = PgRowDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(Columns {
+            row: self,
+            index: 0,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Columns {
+            row: self,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct map struct enum identifier ignored_any
+    }
+}
+
+struct Columns<'de> {
+    row: &'de PgRow,
+    index: usize,
+}
+
+impl<'de> Columns<'de> {
+    fn value<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<T::Value, PgRowDeserializeError> {
+        let index = self.index;
+        self.index += 1;
+
+        let column = &self.row.metadata.columns[index];
+        let value = self.row.try_get_raw(index).map_err(|e| {
+            PgRowDeserializeError::from(BoxDynError::from(e.to_string())).in_column(column)
+        })?;
+
+        seed.deserialize(ValueDeserializer { value })
+            .map_err(|e| e.in_column(column))
+    }
+}
+
+impl<'de> MapAccess<'de> for Columns<'de> {
+    type Error = PgRowDeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(column) = self.row.metadata.columns.get(self.index) else {
+            return Ok(None);
+        };
+
+        seed.deserialize(BorrowedStrDeserializer::new(&column.name))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.metadata.columns.len() - self.index)
+    }
+}
+
+impl<'de> SeqAccess<'de> for Columns<'de> {
+    type Error = PgRowDeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.index == self.row.metadata.columns.len() {
+            return Ok(None);
+        }
+
+        self.value(seed).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.metadata.columns.len() - self.index)
+    }
+}
+
+// Deserializes a single value of a row, based on its type in Postgres.
+struct ValueDeserializer<'de> {
+    value: PgValueRef<'de>,
+}
+
+impl<'de> ValueDeserializer<'de> {
+    fn decode<T: Decode<'de, Postgres>>(&self) -> Result<T, PgRowDeserializeError> {
+        Ok(T::decode(self.value.clone())?)
+    }
+
+    fn decode_json(&self) -> Result<Option<JsonValue>, PgRowDeserializeError> {
+        match base_type(&self.value.type_info).0 {
+            PgType::Json | PgType::Jsonb => self.decode().map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    // `NUMERIC` values, as decimal strings
+    fn decode_numeric(&self) -> Result<Option<String>, PgRowDeserializeError> {
+        match base_type(&self.value.type_info).0 {
+            PgType::Numeric if !self.value.is_null() => {
+                Ok(Some(self.decode::<PgDecimal>()?.to_string()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // deserialize an array by way of a JSON array, as the elements are decoded by type
+    fn visit_array<T, V>(&self, visitor: V) -> Result<V::Value, PgRowDeserializeError>
+    where
+        T: Decode<'de, Postgres> + serde::Serialize,
+        Vec<Option<T>>: Decode<'de, Postgres>,
+        V: Visitor<'de>,
+    {
+        serde_json::to_value(self.decode::<Vec<Option<T>>>()?)
+            .map_err(PgRowDeserializeError::custom)?
+            .deserialize_any(visitor)
+            .map_err(PgRowDeserializeError::custom)
+    }
+}
+
+/// Convert a value to JSON, the same way it is deserialized into a `serde_json::Value`, except
+/// that `BYTEA` becomes a string in the hex format Postgres uses for text output.
+pub(super) fn value_to_json(value: PgValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+    if !value.is_null() && matches!(base_type(&value.type_info).0, PgType::Bytea) {
+        let bytes = <Vec<u8> as Decode<Postgres>>::decode(value)?;
+        let mut hex = String::with_capacity(2 + bytes.len() * 2);
+        hex.push_str("\\x");
+
+        for byte in bytes {
+            hex.push_str(&format!("{byte:02x}"));
+        }
+
+        return Ok(JsonValue::String(hex));
+    }
+
+    JsonValue::deserialize(ValueDeserializer { value }).map_err(|e| e.source)
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident => $visit:ident($ty:ty),)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self.decode_numeric()? {
+                Some(numeric) => visitor.$visit(numeric.parse::<$ty>().map_err(|e| {
+                    PgRowDeserializeError::custom(format_args!(
+                        "cannot convert NUMERIC {numeric} to {}: {e}",
+                        stringify!($ty)
+                    ))
+                })?),
+                None => self.deserialize_any(visitor),
+            }
+        }
+    )*};
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = PgRowDeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_null() {
+            return visitor.visit_unit();
+        }
+
+        let ty = base_type(&self.value.type_info);
+
+        match &ty.0 {
+            PgType::Bool => visitor.visit_bool(self.decode()?),
+            PgType::Char => visitor.visit_i8(self.decode()?),
+            PgType::Int2 => visitor.visit_i16(self.decode()?),
+            PgType::Int4 => visitor.visit_i32(self.decode()?),
+            PgType::Int8 => visitor.visit_i64(self.decode()?),
+            PgType::Oid => visitor.visit_u32(self.decode::<Oid>()?.0),
+            PgType::Float4 => visitor.visit_f32(self.decode()?),
+            PgType::Float8 => visitor.visit_f64(self.decode()?),
+            PgType::Numeric => visitor.visit_string(self.decode::<PgDecimal>()?.to_string()),
+            PgType::Void => visitor.visit_unit(),
+
+            PgType::Bytea => match self.value.format() {
+                PgValueFormat::Binary => visitor.visit_borrowed_bytes(self.value.as_bytes()?),
+                PgValueFormat::Text => visitor.visit_byte_buf(self.decode()?),
+            },
+
+            PgType::Uuid if self.value.format() == PgValueFormat::Binary => {
+                visitor.visit_string(format_uuid(self.value.as_bytes()?)?)
+            }
+
+            PgType::Json | PgType::Jsonb => self
+                .decode::<JsonValue>()?
+                .deserialize_any(visitor)
+                .map_err(PgRowDeserializeError::custom),
+
+            #[cfg(feature = "chrono")]
+            PgType::Date => visitor.visit_string(self.decode::<chrono::NaiveDate>()?.to_string()),
+
+            #[cfg(feature = "chrono")]
+            PgType::Time => visitor.visit_string(self.decode::<chrono::NaiveTime>()?.to_string()),
+
+            #[cfg(feature = "chrono")]
+            PgType::Timestamp => visitor.visit_string(
+                self.decode::<chrono::NaiveDateTime>()?
+                    .format("%Y-%m-%dT%H:%M:%S%.f")
+                    .to_string(),
+            ),
+
+            #[cfg(feature = "chrono")]
+            PgType::Timestamptz => {
+                visitor.visit_string(self.decode::<chrono::DateTime<chrono::Utc>>()?.to_rfc3339())
+            }
+
+            PgType::BoolArray => self.visit_array::<bool, V>(visitor),
+            PgType::Int2Array => self.visit_array::<i16, V>(visitor),
+            PgType::Int4Array => self.visit_array::<i32, V>(visitor),
+            PgType::Int8Array => self.visit_array::<i64, V>(visitor),
+            PgType::Float4Array => self.visit_array::<f32, V>(visitor),
+            PgType::Float8Array => self.visit_array::<f64, V>(visitor),
+            PgType::JsonArray | PgType::JsonbArray => self.visit_array::<JsonValue, V>(visitor),
+
+            PgType::TextArray | PgType::VarcharArray | PgType::BpcharArray | PgType::NameArray => {
+                self.visit_array::<String, V>(visitor)
+            }
+
+            PgType::NumericArray => serde_json::to_value(
+                self.decode::<Vec<Option<PgDecimal>>>()?
+                    .into_iter()
+                    .map(|numeric| numeric.map(|numeric| numeric.to_string()))
+                    .collect::<Vec<_>>(),
+            )
+            .map_err(PgRowDeserializeError::custom)?
+            .deserialize_any(visitor)
+            .map_err(PgRowDeserializeError::custom),
+
+            PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+                visitor.visit_borrowed_str(self.value.as_str()?)
+            }
+
+            // every value has a textual representation in the text format, and
+            // enums and `citext` use their labels and text in the binary format as well
+            _ if self.value.format() == PgValueFormat::Text
+                || matches!(ty.kind(), PgTypeKind::Enum(_))
+                || ty.0.display_name().eq_ignore_ascii_case("citext") =>
+            {
+                visitor.visit_borrowed_str(self.value.as_str()?)
+            }
+
+            _ => Err(PgRowDeserializeError::custom(format_args!(
+                "values of type {} cannot be deserialized; cast the column to TEXT in the query",
+                ty.0.display_name()
+            ))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_null() {
+            return visitor.visit_none();
+        }
+
+        match self.decode_json()? {
+            // a JSON `null` is `None` as well
+            Some(json) => json
+                .deserialize_option(visitor)
+                .map_err(PgRowDeserializeError::custom),
+            None => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.decode_json()? {
+            Some(json) => json
+                .deserialize_enum(name, variants, visitor)
+                .map_err(PgRowDeserializeError::custom),
+            None => visitor.visit_enum(self.value.as_str()?.into_deserializer()),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    deserialize_number! {
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+    }
+
+    forward_to_deserialize_any! {
+        bool char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+#[test]
+fn uuids_are_formatted_as_hyphenated_hex() {
+    let bytes = [
+        0xa0, 0xee, 0xbc, 0x99, 0x9c, 0x0b, 0x4e, 0xf8, 0xbb, 0x6d, 0x6b, 0xb9, 0xbd, 0x38, 0x0a,
+        0x11,
+    ];
+
+    assert_eq!(
+        format_uuid(&bytes).unwrap(),
+        "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"
+    );
+    assert!(format_uuid(&bytes[..15]).is_err());
+}
@@ -0,0 +1,190 @@
+use std::pin::pin;
+
+use futures_core::future::BoxFuture;
+use futures_core::stream::Stream;
+use futures_util::{FutureExt, TryStreamExt};
+use serde_json::Value as JsonValue;
+
+use super::deserialize::value_to_json;
+use super::Row;
+use crate::error::Error;
+use crate::io::{AsyncWrite, AsyncWriteExt};
+use crate::PgRow;
+
+// the rows are written out whenever this much is buffered
+const BUF_SIZE: usize = 8192;
+
+/// Write a stream of rows, such as the one returned by
+/// [`fetch()`][sqlx_core::executor::Executor::fetch], out as CSV or JSON lines.
+///
+/// Values are converted the same way as by [`PgRow::to_json()`], so columns of types it cannot
+/// convert fail the export; they can be cast to `TEXT` in the query.
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgExportExt;
+///
+/// let mut body = Vec::new();
+///
+/// let exported = sqlx::query("SELECT id, name, created_at FROM users")
+///     .fetch(pool)
+///     .export_csv(&mut body)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Note: Runtime Features
+/// The writer implements the `AsyncWrite` trait of Tokio if the `runtime-tokio` feature is
+/// enabled, and the one of `futures-io` otherwise.
+pub trait PgExportExt: Stream<Item = Result<PgRow, Error>> + Sized {
+    /// Write the rows to `writer` as CSV ([RFC 4180]), with a header of the column names,
+    /// returning the number of rows written.
+    ///
+    /// `NULL` becomes an empty field and the empty string a quoted `""`, as with `COPY ... (FORMAT csv)`.
+    /// Fields are quoted if they contain a comma, a quote or a line break, and arrays and JSON
+    /// are written as JSON text. Records end with `\r\n`.
+    ///
+    /// A result without rows writes nothing, not even the header.
+    ///
+    /// [RFC 4180]: https://datatracker.ietf.org/doc/html/rfc4180
+    fn export_csv<'w, W>(self, writer: W) -> BoxFuture<'w, Result<u64, Error>>
+    where
+        Self: Send + 'w,
+        W: AsyncWrite + Unpin + Send + 'w,
+    {
+        export(self, writer, Format::Csv).boxed()
+    }
+
+    /// Write the rows to `writer` as [JSON lines], one object per row as converted by
+    /// [`PgRow::to_json()`], returning the number of rows written.
+    ///
+    /// [JSON lines]: https://jsonlines.org/
+    fn export_jsonl<'w, W>(self, writer: W) -> BoxFuture<'w, Result<u64, Error>>
+    where
+        Self: Send + 'w,
+        W: AsyncWrite + Unpin + Send + 'w,
+    {
+        export(self, writer, Format::JsonLines).boxed()
+    }
+}
+
+impl<S: Stream<Item = Result<PgRow, Error>>> PgExportExt for S {}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Format {
+    Csv,
+    JsonLines,
+}
+
+async fn export<S, W>(rows: S, mut writer: W, format: Format) -> Result<u64, Error>
+where
+    S: Stream<Item = Result<PgRow, Error>>,
+    W: AsyncWrite + Unpin,
+{
+    let mut rows = pin!(rows);
+    let mut buf = Vec::with_capacity(BUF_SIZE);
+    let mut written = 0;
+
+    while let Some(row) = rows.try_next().await? {
+        match format {
+            Format::Csv => {
+                if written == 0 {
+                    put_csv_header(&mut buf, &row);
+                }
+
+                put_csv_record(&mut buf, &row)?;
+            }
+
+            Format::JsonLines => {
+                serde_json::to_writer(&mut buf, &row.to_json()?)
+                    .map_err(|e| Error::Encode(e.into()))?;
+
+                buf.push(b'\n');
+            }
+        }
+
+        written += 1;
+
+        if buf.len() >= BUF_SIZE {
+            writer.write_all(&buf).await?;
+            buf.clear();
+        }
+    }
+
+    writer.write_all(&buf).await?;
+    writer.flush().await?;
+
+    Ok(written)
+}
+
+fn put_csv_header(buf: &mut Vec<u8>, row: &PgRow) {
+    for (index, column) in row.columns().iter().enumerate() {
+        if index > 0 {
+            buf.push(b',');
+        }
+
+        put_csv_field(buf, &column.name);
+    }
+
+    buf.extend_from_slice(b"\r\n");
+}
+
+fn put_csv_record(buf: &mut Vec<u8>, row: &PgRow) -> Result<(), Error> {
+    for (index, column) in row.columns().iter().enumerate() {
+        if index > 0 {
+            buf.push(b',');
+        }
+
+        let value =
+            value_to_json(row.try_get_raw(index)?).map_err(|source| Error::ColumnDecode {
+                index: column.name.to_string(),
+                source,
+            })?;
+
+        match value {
+            JsonValue::Null => {}
+            JsonValue::String(string) => put_csv_field(buf, &string),
+            value => put_csv_field(buf, &value.to_string()),
+        }
+    }
+
+    buf.extend_from_slice(b"\r\n");
+
+    Ok(())
+}
+
+fn put_csv_field(buf: &mut Vec<u8>, field: &str) {
+    // the empty string is quoted to tell it apart from `NULL`
+    if !field.is_empty() && !field.contains([',', '"', '\r', '\n']) {
+        buf.extend_from_slice(field.as_bytes());
+        return;
+    }
+
+    buf.push(b'"');
+
+    for byte in field.bytes() {
+        if byte == b'"' {
+            buf.push(b'"');
+        }
+
+        buf.push(byte);
+    }
+
+    buf.push(b'"');
+}
+
+#[test]
+fn it_escapes_csv_fields() {
+    let csv = |field: &str| {
+        let mut buf = Vec::new();
+        put_csv_field(&mut buf, field);
+        String::from_utf8(buf).unwrap()
+    };
+
+    assert_eq!(csv("plain"), "plain");
+    assert_eq!(csv(""), r#""""#);
+    assert_eq!(csv("a,b"), r#""a,b""#);
+    assert_eq!(csv(r#"say "hi""#), r#""say ""hi""""#);
+    assert_eq!(csv("two\nlines"), "\"two\nlines\"");
+}
@@ -0,0 +1,70 @@
+use futures_core::future::BoxFuture;
+use futures_util::{FutureExt, TryStreamExt};
+use serde_json::{Map, Value as JsonValue};
+
+use super::deserialize::value_to_json;
+use super::Row;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::{PgRow, Postgres};
+
+impl PgRow {
+    /// Convert the row to a JSON object, mapping each column name to its value.
+    ///
+    /// Values are converted the same way as when [deserializing](Self::deserialize) the row into
+    /// a [`serde_json::Value`]: `NUMERIC` becomes a string to keep its precision, `JSON` and
+    /// `JSONB` are embedded as they are, `NULL` becomes `null` and so on. `BYTEA` becomes a
+    /// string in the hex format Postgres uses for text output (`"\\xdeadbeef"`).
+    ///
+    /// If several columns have the same name, the object holds the value of the last of them.
+    ///
+    /// ### Errors
+    /// If a column has a type which cannot be converted; it can be cast to `TEXT` in the query.
+    pub fn to_json(&self) -> Result<JsonValue, Error> {
+        let mut object = Map::with_capacity(self.len());
+
+        for (index, column) in self.columns().iter().enumerate() {
+            let value =
+                value_to_json(self.try_get_raw(index)?).map_err(|source| Error::ColumnDecode {
+                    index: column.name.to_string(),
+                    source,
+                })?;
+
+            object.insert(column.name.to_string(), value);
+        }
+
+        Ok(JsonValue::Object(object))
+    }
+}
+
+/// Fetch the results of a query as JSON objects, for queries whose columns are not known ahead of
+/// time.
+///
+/// Implemented for anything which can be executed, such as [`query()`][sqlx_core::query::query]
+/// and [`raw_sql()`][sqlx_core::raw_sql::raw_sql].
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool, sql: String) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgFetchJsonExt;
+///
+/// let rows: Vec<serde_json::Value> = sqlx::raw_sql(sqlx::AssertSqlSafe(sql)).fetch_json(pool).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait PgFetchJsonExt<'q>: Execute<'q, Postgres> + 'q {
+    /// Execute the query and return all the resulting rows, each converted with
+    /// [`PgRow::to_json()`].
+    fn fetch_json<'e, 'c: 'e, E>(self, executor: E) -> BoxFuture<'e, Result<Vec<JsonValue>, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = Postgres>,
+    {
+        executor
+            .fetch(self)
+            .and_then(|row| async move { row.to_json() })
+            .try_collect()
+            .boxed()
+    }
+}
+
+impl<'q, T: Execute<'q, Postgres> + 'q> PgFetchJsonExt<'q> for T {}
@@ -0,0 +1,74 @@
+use futures_core::future::BoxFuture;
+use futures_util::{FutureExt, TryStreamExt};
+use sqlx_core::value::ValueRef;
+use sqlx_core::IndexMap;
+
+use super::Row;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::{PgRow, PgValue, Postgres};
+
+/// The values of a row by column name, in the order of the columns, as returned by
+/// [`PgRow::to_map()`].
+pub type PgRowMap = IndexMap<String, PgValue>;
+
+impl PgRow {
+    /// Copy the values of the row into a map from column name to value, in the order of the
+    /// columns.
+    ///
+    /// Each value keeps its type, so it can be inspected with
+    /// [`Value::type_info()`][sqlx_core::value::Value::type_info] and decoded with
+    /// [`Value::try_decode()`][sqlx_core::value::Value::try_decode] once its type is known.
+    ///
+    /// If several columns have the same name, the map holds the value of the last of them.
+    pub fn to_map(&self) -> PgRowMap {
+        let mut map = IndexMap::with_capacity(self.len());
+
+        for (index, column) in self.columns().iter().enumerate() {
+            let value = self
+                .try_get_raw(index)
+                .expect("BUG: column index out of bounds");
+
+            map.insert(column.name.to_string(), ValueRef::to_owned(&value));
+        }
+
+        map
+    }
+}
+
+/// Fetch the results of a query as maps from column name to value, for tools whose queries
+/// are not known at compile time.
+///
+/// Implemented for anything which can be executed, such as [`query()`][sqlx_core::query::query]
+/// and [`raw_sql()`][sqlx_core::raw_sql::raw_sql].
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool, sql: String) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgFetchMapsExt;
+/// use sqlx::{TypeInfo, Value};
+///
+/// for row in sqlx::raw_sql(sqlx::AssertSqlSafe(sql)).fetch_maps(pool).await? {
+///     for (column, value) in &row {
+///         println!("{column}: {}", value.type_info().name());
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait PgFetchMapsExt<'q>: Execute<'q, Postgres> + 'q {
+    /// Execute the query and return all the resulting rows, each converted with
+    /// [`PgRow::to_map()`].
+    fn fetch_maps<'e, 'c: 'e, E>(self, executor: E) -> BoxFuture<'e, Result<Vec<PgRowMap>, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = Postgres>,
+    {
+        executor
+            .fetch(self)
+            .map_ok(|row| row.to_map())
+            .try_collect()
+            .boxed()
+    }
+}
+
+impl<'q, T: Execute<'q, Postgres> + 'q> PgFetchMapsExt<'q> for T {}
@@ -0,0 +1,499 @@
+use std::fmt::{self, Debug, Formatter};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::sync::Arc;
+use std::vec;
+
+use futures_core::future::BoxFuture;
+use futures_util::TryStreamExt;
+use sqlx_core::bytes::Bytes;
+use sqlx_core::rt;
+
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::message::DataRow;
+use crate::statement::PgStatementMetadata;
+use crate::value::PgValueFormat;
+use crate::{PgRow, Postgres};
+
+// the spilled rows are written out whenever this much is buffered
+const BUF_SIZE: usize = 1024 * 1024;
+
+/// Collect the rows of a query like [`fetch_all()`][Executor::fetch_all], but with a bound on
+/// the memory they take, for batch jobs over results which may not fit in memory.
+///
+/// Once the rows add up to the memory limit, the rest of them are written to a temporary file
+/// and read back in order when iterating over the [`PgSpilledRows`]. The file is removed
+/// once they are dropped.
+///
+/// ```rust,no_run
+/// # async fn example(pool: &sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgFetchSpilledExt;
+/// use sqlx::Row;
+///
+/// let rows = sqlx::query("SELECT id, payload FROM events")
+///     .fetch_all_spilled(pool, 256 * 1024 * 1024)
+///     .await?;
+///
+/// for row in rows {
+///     let id: i64 = row?.try_get("id")?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait PgFetchSpilledExt<'q>: Execute<'q, Postgres> + 'q {
+    /// Execute the query and collect its rows, keeping up to `memory_limit` bytes of them in
+    /// memory and writing the rest to a temporary file.
+    ///
+    /// The size of a row is that of its values as sent by the server, which is close to what it
+    /// takes in memory.
+    fn fetch_all_spilled<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+        memory_limit: usize,
+    ) -> BoxFuture<'e, Result<PgSpilledRows, Error>>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = Postgres>,
+    {
+        let mut rows = executor.fetch(self);
+
+        Box::pin(async move {
+            let mut collected = PgSpilledRows {
+                memory: Vec::new(),
+                memory_size: 0,
+                spill: None,
+            };
+
+            while let Some(row) = rows.try_next().await? {
+                let Some(row) = collected.keep(row, memory_limit) else {
+                    continue;
+                };
+
+                let spill = match &mut collected.spill {
+                    Some(spill) => spill,
+                    None => collected.spill.insert(Spill::create().await?),
+                };
+
+                spill.push(&row).await?;
+            }
+
+            if let Some(spill) = &mut collected.spill {
+                spill.finish().await?;
+            }
+
+            Ok(collected)
+        })
+    }
+}
+
+impl<'q, T: Execute<'q, Postgres> + 'q> PgFetchSpilledExt<'q> for T {}
+
+/// The rows collected by [`PgFetchSpilledExt::fetch_all_spilled()`], part in memory and part
+/// in a temporary file.
+///
+/// Iterating over the rows reads those in the file back one by one, failing with
+/// [`Error::Io`] if it cannot be read. The reads block the thread, as with [`std::fs`].
+pub struct PgSpilledRows {
+    memory: Vec<PgRow>,
+    memory_size: usize,
+    spill: Option<Spill>,
+}
+
+/// An iterator over [`PgSpilledRows`].
+pub struct PgSpilledRowsIter {
+    memory: vec::IntoIter<PgRow>,
+    spill: Option<SpillReader>,
+}
+
+// rows written out as: the index of their result set, then the lengths and bytes of their
+// values and of the ranges of the values
+struct Spill {
+    file: Option<File>,
+    buf: Vec<u8>,
+    len: usize,
+    result_sets: Vec<ResultSet>,
+}
+
+struct SpillReader {
+    file: BufReader<File>,
+    remaining: usize,
+    result_sets: Vec<ResultSet>,
+}
+
+// what the rows of a result set share
+struct ResultSet {
+    metadata: Arc<PgStatementMetadata>,
+    format: PgValueFormat,
+    time_zone: Option<Arc<str>>,
+}
+
+impl PgSpilledRows {
+    /// The number of rows.
+    pub fn len(&self) -> usize {
+        self.memory.len() + self.spilled()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of rows written to the temporary file.
+    pub fn spilled(&self) -> usize {
+        self.spill.as_ref().map_or(0, |spill| spill.len)
+    }
+
+    // keep `row` in memory if it fits, or hand it back to be spilled
+    fn keep(&mut self, mut row: PgRow, memory_limit: usize) -> Option<PgRow> {
+        // once a row is spilled, so are all the rows after it, keeping them in order
+        if self.spill.is_some() {
+            return Some(row);
+        }
+
+        // a row received from the connection keeps the (much larger) buffer it was read into
+        // alive until it is copied out
+        row.detach();
+
+        let size = row.data.storage.len() + row.data.values.len();
+
+        if self.memory_size + size > memory_limit {
+            return Some(row);
+        }
+
+        self.memory_size += size;
+        self.memory.push(row);
+
+        None
+    }
+}
+
+impl IntoIterator for PgSpilledRows {
+    type Item = Result<PgRow, Error>;
+    type IntoIter = PgSpilledRowsIter;
+
+    fn into_iter(self) -> PgSpilledRowsIter {
+        PgSpilledRowsIter {
+            memory: self.memory.into_iter(),
+            spill: self.spill.map(|spill| SpillReader {
+                file: BufReader::new(spill.file.expect("BUG: spill file taken")),
+                remaining: spill.len,
+                result_sets: spill.result_sets,
+            }),
+        }
+    }
+}
+
+impl Debug for PgSpilledRows {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgSpilledRows")
+            .field("len", &self.len())
+            .field("spilled", &self.spilled())
+            .finish()
+    }
+}
+
+impl Iterator for PgSpilledRowsIter {
+    type Item = Result<PgRow, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(row) = self.memory.next() {
+            return Some(Ok(row));
+        }
+
+        let spill = self.spill.as_mut()?;
+
+        if spill.remaining == 0 {
+            return None;
+        }
+
+        let row = spill.read();
+
+        // the rest of the file cannot be read once a row is not
+        spill.remaining = if row.is_ok() { spill.remaining - 1 } else { 0 };
+
+        Some(row.map_err(Error::Io))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.memory.len() + self.spill.as_ref().map_or(0, |spill| spill.remaining);
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for PgSpilledRowsIter {}
+
+impl Debug for PgSpilledRowsIter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgSpilledRowsIter")
+            .field("remaining", &self.len())
+            .finish()
+    }
+}
+
+impl Spill {
+    async fn create() -> Result<Self, Error> {
+        let file = rt::spawn_blocking(tempfile::tempfile).await?;
+
+        Ok(Spill {
+            file: Some(file),
+            buf: Vec::with_capacity(BUF_SIZE),
+            len: 0,
+            result_sets: Vec::new(),
+        })
+    }
+
+    async fn push(&mut self, row: &PgRow) -> Result<(), Error> {
+        self.put(row)?;
+
+        if self.buf.len() >= BUF_SIZE {
+            self.write().await?;
+        }
+
+        Ok(())
+    }
+
+    // buffer `row`, to be written out with the next `write()`
+    fn put(&mut self, row: &PgRow) -> io::Result<()> {
+        let same_result_set = self.result_sets.last().is_some_and(|last| {
+            Arc::ptr_eq(&last.metadata, &row.metadata)
+                && last.format == row.format
+                && last.time_zone == row.time_zone
+        });
+
+        if !same_result_set {
+            self.result_sets.push(ResultSet {
+                metadata: Arc::clone(&row.metadata),
+                format: row.format,
+                time_zone: row.time_zone.clone(),
+            });
+        }
+
+        let result_set = self.result_sets.len() - 1;
+
+        put_u32(&mut self.buf, result_set)?;
+        put_bytes(&mut self.buf, &row.data.storage)?;
+        put_bytes(&mut self.buf, &row.data.values)?;
+
+        self.len += 1;
+
+        Ok(())
+    }
+
+    async fn finish(&mut self) -> Result<(), Error> {
+        self.write().await?;
+
+        let mut file = self.file.take().expect("BUG: spill file taken");
+
+        let file = rt::spawn_blocking(move || {
+            file.flush()?;
+            file.seek(SeekFrom::Start(0))?;
+            io::Result::Ok(file)
+        })
+        .await?;
+
+        self.file = Some(file);
+
+        Ok(())
+    }
+
+    async fn write(&mut self) -> Result<(), Error> {
+        let mut file = self.file.take().expect("BUG: spill file taken");
+        let mut buf = std::mem::take(&mut self.buf);
+
+        let (file, buf) = rt::spawn_blocking(move || {
+            file.write_all(&buf)?;
+            buf.clear();
+            io::Result::Ok((file, buf))
+        })
+        .await?;
+
+        self.file = Some(file);
+        self.buf = buf;
+
+        Ok(())
+    }
+}
+
+impl SpillReader {
+    fn read(&mut self) -> io::Result<PgRow> {
+        let result_set = read_u32(&mut self.file)? as usize;
+        let storage = read_bytes(&mut self.file)?;
+        let values = read_bytes(&mut self.file)?;
+
+        let result_set = self
+            .result_sets
+            .get(result_set)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "corrupt spill file"))?;
+
+        Ok(PgRow {
+            data: DataRow { storage, values },
+            format: result_set.format,
+            metadata: Arc::clone(&result_set.metadata),
+            time_zone: result_set.time_zone.clone(),
+        })
+    }
+}
+
+fn put_u32(buf: &mut Vec<u8>, value: usize) -> io::Result<()> {
+    let value = u32::try_from(value)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "row too large to spill"))?;
+
+    buf.extend_from_slice(&value.to_ne_bytes());
+
+    Ok(())
+}
+
+fn put_bytes(buf: &mut Vec<u8>, bytes: &[u8]) -> io::Result<()> {
+    put_u32(buf, bytes.len())?;
+    buf.extend_from_slice(bytes);
+
+    Ok(())
+}
+
+fn read_u32(file: &mut impl Read) -> io::Result<u32> {
+    let mut value = [0; 4];
+    file.read_exact(&mut value)?;
+
+    Ok(u32::from_ne_bytes(value))
+}
+
+fn read_bytes(file: &mut impl Read) -> io::Result<Bytes> {
+    let mut bytes = vec![0; read_u32(file)? as usize];
+    file.read_exact(&mut bytes)?;
+
+    Ok(bytes.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::RowArena;
+
+    // a `DataRow` body with a NULL and `value`
+    fn row(metadata: &Arc<PgStatementMetadata>, value: &[u8]) -> PgRow {
+        let mut body = b"\x00\x02\xff\xff\xff\xff".to_vec();
+        body.extend_from_slice(&u32::try_from(value.len()).unwrap().to_be_bytes());
+        body.extend_from_slice(value);
+
+        PgRow {
+            data: RowArena::default().decode(body.into()).unwrap(),
+            format: PgValueFormat::Text,
+            metadata: Arc::clone(metadata),
+            time_zone: None,
+        }
+    }
+
+    fn rows() -> PgSpilledRows {
+        PgSpilledRows {
+            memory: Vec::new(),
+            memory_size: 0,
+            spill: None,
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let mut buf = Vec::new();
+        put_bytes(&mut buf, b"").unwrap();
+        put_bytes(&mut buf, b"hello").unwrap();
+
+        let mut buf = &buf[..];
+        assert_eq!(read_bytes(&mut buf).unwrap(), &b""[..]);
+        assert_eq!(read_bytes(&mut buf).unwrap(), &b"hello"[..]);
+        assert!(buf.is_empty());
+
+        // a truncated file cannot be read
+        assert!(read_bytes(&mut &b"\x05\x00\x00\x00hi"[..]).is_err());
+    }
+
+    #[test]
+    fn rows_are_kept_until_the_limit() {
+        let metadata = Arc::default();
+        let size = {
+            let row = row(&metadata, b"x");
+            row.data.storage.len() + row.data.values.len()
+        };
+
+        let mut collected = rows();
+
+        assert!(collected.keep(row(&metadata, b"x"), 2 * size).is_none());
+        assert!(collected.keep(row(&metadata, b"x"), 2 * size).is_none());
+        assert!(collected.keep(row(&metadata, b"x"), 2 * size).is_some());
+        assert_eq!(collected.memory.len(), 2);
+        assert_eq!(collected.memory_size, 2 * size);
+
+        // a smaller row still fits, but the rows are kept in order
+        collected.spill = Some(Spill {
+            file: None,
+            buf: Vec::new(),
+            len: 0,
+            result_sets: Vec::new(),
+        });
+        assert!(collected.keep(row(&metadata, b""), 3 * size).is_some());
+    }
+
+    #[test]
+    fn kept_rows_are_detached() {
+        let metadata = Arc::default();
+        let body = Bytes::from(b"\x00\x01\x00\x00\x00\x01x".repeat(1000));
+
+        let row = PgRow {
+            data: RowArena::default().decode(body.slice(..7)).unwrap(),
+            format: PgValueFormat::Text,
+            metadata,
+            time_zone: None,
+        };
+
+        let mut collected = rows();
+        assert!(collected.keep(row, 100).is_none());
+
+        let kept = &collected.memory[0];
+        assert_eq!(kept.data.get(0), Some(&b"x"[..]));
+        assert!(!body.as_ptr_range().contains(&kept.data.storage.as_ptr()));
+    }
+
+    #[test]
+    fn spilled_rows_keep_their_result_set() {
+        let first = Arc::new(PgStatementMetadata::default());
+        let second = Arc::new(PgStatementMetadata::default());
+
+        let mut spill = Spill {
+            file: None,
+            buf: Vec::new(),
+            len: 0,
+            result_sets: Vec::new(),
+        };
+
+        spill.put(&row(&first, b"a")).unwrap();
+        spill.put(&row(&first, b"b")).unwrap();
+        spill.put(&row(&second, b"c")).unwrap();
+        spill.put(&row(&first, b"d")).unwrap();
+        assert_eq!(spill.result_sets.len(), 3);
+
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&spill.buf).unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        spill.file = Some(file);
+
+        let rows = PgSpilledRows {
+            memory: Vec::new(),
+            memory_size: 0,
+            spill: Some(spill),
+        };
+        assert_eq!(rows.len(), 4);
+
+        let read = rows.into_iter().collect::<Result<Vec<_>, _>>().unwrap();
+
+        for (row, (metadata, value)) in read.iter().zip([
+            (&first, b"a"),
+            (&first, b"b"),
+            (&second, b"c"),
+            (&first, b"d"),
+        ]) {
+            assert!(Arc::ptr_eq(&row.metadata, metadata));
+            assert_eq!(row.data.get(0), None);
+            assert_eq!(row.data.get(1), Some(&value[..]));
+        }
+    }
+}
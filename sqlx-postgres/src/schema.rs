@@ -0,0 +1,346 @@
+use crate::error::Error;
+use crate::query_as::query_as;
+use crate::types::Oid;
+use crate::{HashMap, PgConnection, PgTypeInfo};
+
+/// The tables of a database, as returned by [`PgConnection::schema()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgSchema {
+    /// The tables, views and materialized views outside of the system schemas, ordered by
+    /// schema and name.
+    pub tables: Vec<PgTable>,
+}
+
+/// A table, view or materialized view.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgTable {
+    /// The schema of the table.
+    pub schema: String,
+    pub name: String,
+    pub kind: PgTableKind,
+    /// The columns, in order.
+    pub columns: Vec<PgTableColumn>,
+    pub primary_key: Option<PgPrimaryKey>,
+    /// The foreign keys of the table, ordered by name.
+    pub foreign_keys: Vec<PgForeignKey>,
+    /// The indexes of the table, ordered by name, including the index of the primary key.
+    pub indexes: Vec<PgIndex>,
+}
+
+/// The kind of a [`PgTable`], from `pg_class.relkind`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PgTableKind {
+    Table,
+    PartitionedTable,
+    View,
+    MaterializedView,
+    ForeignTable,
+}
+
+/// A column of a [`PgTable`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgTableColumn {
+    pub name: String,
+    pub type_info: PgTypeInfo,
+    /// The type as written in SQL, with its modifiers, such as `character varying(255)`.
+    pub type_name: String,
+    /// Whether the column may be `NULL`, i.e. has no `NOT NULL` constraint.
+    pub nullable: bool,
+    /// The default value of the column, as an SQL expression.
+    pub default: Option<String>,
+}
+
+/// The primary key of a [`PgTable`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgPrimaryKey {
+    /// The name of the constraint.
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+/// A foreign key of a [`PgTable`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgForeignKey {
+    /// The name of the constraint.
+    pub name: String,
+    /// The columns of the table which reference [`referenced_columns`][Self::referenced_columns].
+    pub columns: Vec<String>,
+    pub referenced_schema: String,
+    pub referenced_table: String,
+    pub referenced_columns: Vec<String>,
+    pub on_update: PgForeignKeyAction,
+    pub on_delete: PgForeignKeyAction,
+}
+
+/// What a [`PgForeignKey`] does when the row it references is updated or deleted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PgForeignKeyAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+/// An index of a [`PgTable`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PgIndex {
+    pub name: String,
+    /// The key columns of the index; columns of expressions are only in the
+    /// [`definition`][Self::definition].
+    pub columns: Vec<String>,
+    pub unique: bool,
+    pub primary: bool,
+    /// The `CREATE INDEX` statement of the index.
+    pub definition: String,
+}
+
+// conrelid, conname, contype, columns, referenced schema, table and columns, on update, on delete
+type ConstraintRow = (
+    Oid,
+    String,
+    String,
+    Vec<String>,
+    Option<String>,
+    Option<String>,
+    Vec<String>,
+    String,
+    String,
+);
+
+impl PgConnection {
+    /// Read the tables of the database from the system catalogs, with their columns, keys and
+    /// indexes.
+    ///
+    /// Tables of the system schemas (`pg_catalog`, `information_schema` and the other `pg_*`
+    /// schemas) are left out; tables the user cannot see in the catalogs are too.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let schema = conn.schema().await?;
+    ///
+    /// for table in &schema.tables {
+    ///     println!("{}.{}", table.schema, table.name);
+    ///
+    ///     for column in &table.columns {
+    ///         println!("    {} {}", column.name, column.type_name);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn schema(&mut self) -> Result<PgSchema, Error> {
+        let tables: Vec<(Oid, String, String, String)> = query_as(
+            // language=PostgreSQL
+            r#"
+            SELECT c.oid, n.nspname::text, c.relname::text, c.relkind::text
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            WHERE c.relkind IN ('r', 'p', 'v', 'm', 'f')
+                AND n.nspname !~ '^pg_'
+                AND n.nspname <> 'information_schema'
+            ORDER BY n.nspname, c.relname
+            "#,
+        )
+        .unlimited()
+        .fetch_all(&mut *self)
+        .await?;
+
+        let oids: Vec<Oid> = tables.iter().map(|(oid, ..)| *oid).collect();
+        let by_oid: HashMap<Oid, usize> =
+            oids.iter().enumerate().map(|(i, oid)| (*oid, i)).collect();
+
+        let mut tables: Vec<PgTable> = tables
+            .into_iter()
+            .map(|(_, schema, name, kind)| PgTable {
+                schema,
+                name,
+                kind: PgTableKind::from_relkind(&kind),
+                columns: Vec::new(),
+                primary_key: None,
+                foreign_keys: Vec::new(),
+                indexes: Vec::new(),
+            })
+            .collect();
+
+        let columns: Vec<(Oid, String, Oid, String, bool, Option<String>)> = query_as(
+            // language=PostgreSQL
+            r#"
+            SELECT a.attrelid, a.attname::text, a.atttypid,
+                pg_catalog.format_type(a.atttypid, a.atttypmod),
+                NOT a.attnotnull,
+                pg_catalog.pg_get_expr(d.adbin, d.adrelid)
+            FROM pg_catalog.pg_attribute a
+            LEFT JOIN pg_catalog.pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+            WHERE a.attrelid = ANY($1) AND a.attnum > 0 AND NOT a.attisdropped
+            ORDER BY a.attrelid, a.attnum
+            "#,
+        )
+        .bind(&oids)
+        .unlimited()
+        .fetch_all(&mut *self)
+        .await?;
+
+        for (table, name, type_oid, type_name, nullable, default) in columns {
+            // user-defined types are looked up, and cached, as when describing a query
+            let type_info = self.maybe_fetch_type_info_by_oid(type_oid, true).await?;
+
+            tables[by_oid[&table]].columns.push(PgTableColumn {
+                name,
+                type_info,
+                type_name,
+                nullable,
+                default,
+            });
+        }
+
+        let constraints: Vec<ConstraintRow> = query_as(
+            // language=PostgreSQL
+            r#"
+            SELECT con.conrelid, con.conname::text, con.contype::text,
+                ARRAY(
+                    SELECT a.attname::text
+                    FROM unnest(con.conkey) WITH ORDINALITY k(attnum, i)
+                    JOIN pg_catalog.pg_attribute a
+                        ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+                    ORDER BY k.i
+                ),
+                rn.nspname::text, rc.relname::text,
+                ARRAY(
+                    SELECT a.attname::text
+                    FROM unnest(con.confkey) WITH ORDINALITY k(attnum, i)
+                    JOIN pg_catalog.pg_attribute a
+                        ON a.attrelid = con.confrelid AND a.attnum = k.attnum
+                    ORDER BY k.i
+                ),
+                con.confupdtype::text, con.confdeltype::text
+            FROM pg_catalog.pg_constraint con
+            LEFT JOIN pg_catalog.pg_class rc ON rc.oid = con.confrelid
+            LEFT JOIN pg_catalog.pg_namespace rn ON rn.oid = rc.relnamespace
+            WHERE con.conrelid = ANY($1) AND con.contype IN ('p', 'f')
+            ORDER BY con.conrelid, con.conname
+            "#,
+        )
+        .bind(&oids)
+        .unlimited()
+        .fetch_all(&mut *self)
+        .await?;
+
+        for (
+            table,
+            name,
+            kind,
+            columns,
+            ref_schema,
+            ref_table,
+            ref_columns,
+            on_update,
+            on_delete,
+        ) in constraints
+        {
+            let table = &mut tables[by_oid[&table]];
+
+            match (kind.as_str(), ref_schema, ref_table) {
+                ("p", ..) => table.primary_key = Some(PgPrimaryKey { name, columns }),
+
+                ("f", Some(referenced_schema), Some(referenced_table)) => {
+                    table.foreign_keys.push(PgForeignKey {
+                        name,
+                        columns,
+                        referenced_schema,
+                        referenced_table,
+                        referenced_columns: ref_columns,
+                        on_update: PgForeignKeyAction::from_code(&on_update),
+                        on_delete: PgForeignKeyAction::from_code(&on_delete),
+                    })
+                }
+
+                _ => {}
+            }
+        }
+
+        let indexes: Vec<(Oid, String, Vec<String>, bool, bool, String)> = query_as(
+            // language=PostgreSQL
+            r#"
+            SELECT i.indrelid, c.relname::text,
+                ARRAY(
+                    SELECT a.attname::text
+                    FROM unnest(i.indkey::int2[]) WITH ORDINALITY k(attnum, n)
+                    JOIN pg_catalog.pg_attribute a
+                        ON a.attrelid = i.indrelid AND a.attnum = k.attnum
+                    WHERE k.n <= i.indnkeyatts
+                    ORDER BY k.n
+                ),
+                i.indisunique, i.indisprimary,
+                pg_catalog.pg_get_indexdef(i.indexrelid)
+            FROM pg_catalog.pg_index i
+            JOIN pg_catalog.pg_class c ON c.oid = i.indexrelid
+            WHERE i.indrelid = ANY($1)
+            ORDER BY i.indrelid, c.relname
+            "#,
+        )
+        .bind(&oids)
+        .unlimited()
+        .fetch_all(&mut *self)
+        .await?;
+
+        for (table, name, columns, unique, primary, definition) in indexes {
+            tables[by_oid[&table]].indexes.push(PgIndex {
+                name,
+                columns,
+                unique,
+                primary,
+                definition,
+            });
+        }
+
+        Ok(PgSchema { tables })
+    }
+}
+
+impl PgSchema {
+    /// The table named `name` in `schema`.
+    pub fn table(&self, schema: &str, name: &str) -> Option<&PgTable> {
+        self.tables
+            .iter()
+            .find(|table| table.schema == schema && table.name == name)
+    }
+}
+
+impl PgTable {
+    /// The column named `name`.
+    pub fn column(&self, name: &str) -> Option<&PgTableColumn> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
+
+impl PgTableKind {
+    fn from_relkind(relkind: &str) -> Self {
+        match relkind {
+            "p" => PgTableKind::PartitionedTable,
+            "v" => PgTableKind::View,
+            "m" => PgTableKind::MaterializedView,
+            "f" => PgTableKind::ForeignTable,
+            _ => PgTableKind::Table,
+        }
+    }
+}
+
+impl PgForeignKeyAction {
+    fn from_code(code: &str) -> Self {
+        match code {
+            "r" => PgForeignKeyAction::Restrict,
+            "c" => PgForeignKeyAction::Cascade,
+            "n" => PgForeignKeyAction::SetNull,
+            "d" => PgForeignKeyAction::SetDefault,
+            _ => PgForeignKeyAction::NoAction,
+        }
+    }
+}
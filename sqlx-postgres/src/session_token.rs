@@ -0,0 +1,139 @@
+use crate::error::Error;
+use crate::pool::PoolConnection;
+use crate::types::PgLsn;
+use crate::{PgConnection, PgPool, Postgres};
+
+/// Tracks the writes of a session, such as those of one user across requests, so that its
+/// reads go to a replica only once the replica has replayed them.
+///
+/// After committing a write on the primary, call [`.record_write()`][Self::record_write]
+/// to record the position of the primary in its write-ahead log. Reads then use
+/// [`.acquire_for_read()`][Self::acquire_for_read], which picks a replica that has replayed
+/// the log up to that position, or the primary if none has, so a session always reads its
+/// own writes.
+///
+/// To carry the token across requests, store its [`lsn()`][Self::lsn], e.g. in a cookie,
+/// and restore it with `PgSessionToken::from()`.
+///
+/// ```rust,no_run
+/// # async fn example(primary: sqlx::PgPool, replicas: Vec<sqlx::PgPool>) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgSessionToken;
+///
+/// let mut token = PgSessionToken::new();
+///
+/// let mut conn = primary.acquire().await?;
+/// sqlx::query("UPDATE users SET name = 'Ferris' WHERE id = 1")
+///     .execute(&mut *conn)
+///     .await?;
+/// token.record_write(&mut conn).await?;
+///
+/// // on a replica which has seen the update, or else on the primary
+/// let mut conn = token.acquire_for_read(&primary, &replicas).await?;
+/// let name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = 1")
+///     .fetch_one(&mut *conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct PgSessionToken {
+    lsn: Option<PgLsn>,
+}
+
+impl PgSessionToken {
+    /// A token without writes, whose reads may go to any replica.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The position in the write-ahead log of the latest write recorded, if any.
+    pub fn lsn(&self) -> Option<PgLsn> {
+        self.lsn
+    }
+
+    /// Record a write at `lsn`, unless a later one was already recorded.
+    pub fn observe(&mut self, lsn: PgLsn) {
+        self.lsn = std::cmp::max(self.lsn, Some(lsn));
+    }
+
+    /// Record the current position of `primary` in its write-ahead log, and return it.
+    ///
+    /// Call it once the writes are committed: a position read within a transaction is
+    /// before its commit.
+    pub async fn record_write(&mut self, primary: &mut PgConnection) -> Result<PgLsn, Error> {
+        let lsn = crate::query_scalar::query_scalar("SELECT pg_current_wal_lsn()")
+            .unlimited()
+            .fetch_one(primary)
+            .await?;
+
+        self.observe(lsn);
+
+        Ok(lsn)
+    }
+
+    /// Returns `true` if `replica` has replayed every write recorded in this token.
+    ///
+    /// A server which is not in recovery, i.e. a primary, has every write.
+    pub async fn is_replayed_by(&self, replica: &mut PgConnection) -> Result<bool, Error> {
+        let Some(lsn) = self.lsn else {
+            return Ok(true);
+        };
+
+        crate::query_scalar::query_scalar(
+            "SELECT NOT pg_is_in_recovery() OR coalesce(pg_last_wal_replay_lsn() >= $1, false)",
+        )
+        .bind(lsn)
+        .unlimited()
+        .fetch_one(replica)
+        .await
+    }
+
+    /// Acquire a connection to read from: from the first of `replicas` which has replayed
+    /// every write recorded in this token, or else from `primary`.
+    ///
+    /// A replica which cannot be reached is skipped, so reads fall back to the primary
+    /// rather than fail.
+    pub async fn acquire_for_read(
+        &self,
+        primary: &PgPool,
+        replicas: &[PgPool],
+    ) -> Result<PoolConnection<Postgres>, Error> {
+        for replica in replicas {
+            let mut conn = match replica.acquire().await {
+                Ok(conn) => conn,
+                Err(error) => {
+                    tracing::debug!(%error, "failed to acquire a connection to a replica");
+                    continue;
+                }
+            };
+
+            match self.is_replayed_by(&mut conn).await {
+                Ok(true) => return Ok(conn),
+                Ok(false) => {}
+                Err(error) => {
+                    tracing::debug!(%error, "failed to check the replay position of a replica");
+                }
+            }
+        }
+
+        primary.acquire().await
+    }
+}
+
+impl From<PgLsn> for PgSessionToken {
+    fn from(lsn: PgLsn) -> Self {
+        PgSessionToken { lsn: Some(lsn) }
+    }
+}
+
+#[test]
+fn tokens_keep_the_latest_write() {
+    let mut token = PgSessionToken::new();
+    assert_eq!(token.lsn(), None);
+
+    token.observe(PgLsn(20));
+    token.observe(PgLsn(10));
+    assert_eq!(token.lsn(), Some(PgLsn(20)));
+
+    assert_eq!(PgSessionToken::from(PgLsn(20)), token);
+}
@@ -0,0 +1,172 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::pool::PoolConnection;
+use crate::{PgConnection, PgPool, Postgres};
+
+type SchemaFn = dyn Fn(&str) -> String + Send + Sync;
+
+/// Routes each tenant to its own schema, for applications which keep the tables of each
+/// tenant in a schema of their own.
+///
+/// [`.acquire()`][Self::acquire] takes a connection from the pool and sets its
+/// `search_path` to the schema of the tenant, followed by any [shared
+/// schemas][Self::shared_schema], so that unqualified table names refer to the tables of
+/// that tenant.
+///
+/// The `search_path` is reset when the returned [`PgTenantConnection`] is dropped. The
+/// reset is flushed by the pool before the connection is released back to it, so the next
+/// task to acquire the connection, whether through this router or not, cannot see the
+/// schema of the previous tenant.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgTenantRouter;
+///
+/// let router = PgTenantRouter::new(pool)
+///     .schema_name(|tenant| format!("tenant_{tenant}"))
+///     .shared_schema("public");
+///
+/// let mut conn = router.acquire("acme").await?;
+///
+/// // reads `tenant_acme.invoices`
+/// let count: i64 = sqlx::query_scalar("SELECT count(*) FROM invoices")
+///     .fetch_one(&mut *conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PgTenantRouter {
+    pool: PgPool,
+    schema: Arc<SchemaFn>,
+    shared_schemas: Vec<String>,
+}
+
+impl PgTenantRouter {
+    /// Route tenants of `pool` to the schema named after their key.
+    pub fn new(pool: PgPool) -> Self {
+        PgTenantRouter {
+            pool,
+            schema: Arc::new(str::to_owned),
+            shared_schemas: Vec::new(),
+        }
+    }
+
+    /// Set the function which returns the name of the schema of a tenant, given its key.
+    pub fn schema_name<F>(mut self, schema: F) -> Self
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static,
+    {
+        self.schema = Arc::new(schema);
+        self
+    }
+
+    /// Append `schema` to the `search_path` of every tenant, after the schema of the tenant,
+    /// for tables and functions shared between tenants.
+    pub fn shared_schema(mut self, schema: impl Into<String>) -> Self {
+        self.shared_schemas.push(schema.into());
+        self
+    }
+
+    /// The pool the connections are acquired from.
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    /// Acquire a connection whose `search_path` is set to the schema of `tenant`.
+    ///
+    /// Returns [`Error::InvalidArgument`] if the schema of `tenant` does not exist, rather
+    /// than let unqualified names fall through to a shared schema.
+    pub async fn acquire(&self, tenant: &str) -> Result<PgTenantConnection, Error> {
+        let schema = (self.schema)(tenant);
+
+        let search_path = std::iter::once(&schema)
+            .chain(&self.shared_schemas)
+            .map(|schema| quote_identifier(schema))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let mut conn = self.pool.acquire().await?;
+
+        let set: Option<String> = crate::query_scalar::query_scalar(
+            "SELECT set_config('search_path', $1, false) FROM pg_namespace WHERE nspname = $2",
+        )
+        .bind(&search_path)
+        .bind(&schema)
+        .unlimited()
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        if set.is_none() {
+            return Err(Error::InvalidArgument(format!(
+                "schema {schema:?} of tenant {tenant:?} does not exist"
+            )));
+        }
+
+        Ok(PgTenantConnection {
+            conn,
+            tenant: tenant.to_owned(),
+        })
+    }
+}
+
+impl Debug for PgTenantRouter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgTenantRouter")
+            .field("pool", &self.pool)
+            .field("shared_schemas", &self.shared_schemas)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A connection acquired by [`PgTenantRouter::acquire()`], whose `search_path` is set to
+/// the schema of its tenant until it is dropped.
+#[derive(Debug)]
+pub struct PgTenantConnection {
+    conn: PoolConnection<Postgres>,
+    tenant: String,
+}
+
+impl PgTenantConnection {
+    /// The key of the tenant this connection was acquired for.
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+}
+
+impl Deref for PgTenantConnection {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.conn
+    }
+}
+
+impl DerefMut for PgTenantConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.conn
+    }
+}
+
+impl Drop for PgTenantConnection {
+    fn drop(&mut self) {
+        // flushed by the pool before the connection is released; if it cannot even be
+        // queued, the connection is not fit to be reused
+        if self.conn.queue_simple_query("RESET search_path").is_err() {
+            self.conn.close_on_drop();
+        }
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[test]
+fn identifiers_are_quoted() {
+    assert_eq!(quote_identifier("tenant_1"), "\"tenant_1\"");
+    assert_eq!(quote_identifier("a\", public; --"), "\"a\"\", public; --\"");
+}
@@ -0,0 +1,76 @@
+use std::sync::OnceLock;
+
+use sqlx_core::sync::AsyncSemaphore;
+use testcontainers::core::{IntoContainerPort, WaitFor};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{GenericImage, ImageExt, ReuseDirective};
+
+use crate::error::Error;
+
+/// The image run by `#[sqlx::test(container)]`.
+const DEFAULT_IMAGE: &str = "postgres:17-alpine";
+
+const PASSWORD: &str = "postgres";
+
+// The container is started once for the test binary, whose tests each run on their own runtime.
+static DATABASE_URL: OnceLock<String> = OnceLock::new();
+static STARTING: OnceLock<AsyncSemaphore> = OnceLock::new();
+
+/// The URL of the superuser of a Postgres container, started by the first test to ask for it.
+///
+/// The container is reused rather than removed at the end: statics are never dropped, and
+/// reusing it spares later test runs the start-up.
+pub(super) async fn database_url(image: Option<&str>) -> Result<String, Error> {
+    if let Some(url) = DATABASE_URL.get() {
+        return Ok(url.clone());
+    }
+
+    let starting = STARTING.get_or_init(|| AsyncSemaphore::new(true, 1));
+    let _permit = starting.acquire(1).await;
+
+    if let Some(url) = DATABASE_URL.get() {
+        return Ok(url.clone());
+    }
+
+    let url = start(image.unwrap_or(DEFAULT_IMAGE)).await?;
+
+    Ok(DATABASE_URL.get_or_init(|| url).clone())
+}
+
+async fn start(image: &str) -> Result<String, Error> {
+    // `registry:5000/postgres` has no tag
+    let (name, tag) = match image.rsplit_once(':') {
+        Some((name, tag)) if !tag.contains('/') => (name, tag),
+        _ => (image, "latest"),
+    };
+
+    let container = GenericImage::new(name, tag)
+        .with_exposed_port(5432.tcp())
+        // the entrypoint starts the server once to initialize the database, logging to stdout,
+        // then for good, logging to stderr
+        .with_wait_for(WaitFor::message_on_stdout(
+            "database system is ready to accept connections",
+        ))
+        .with_wait_for(WaitFor::message_on_stderr(
+            "database system is ready to accept connections",
+        ))
+        .with_env_var("POSTGRES_PASSWORD", PASSWORD)
+        .with_reuse(ReuseDirective::Always)
+        .start()
+        .await
+        .map_err(Error::config)?;
+
+    let host = container.get_host().await.map_err(Error::config)?;
+
+    let port = container
+        .get_host_port_ipv4(5432.tcp())
+        .await
+        .map_err(Error::config)?;
+
+    // left running for the other tests of the binary and later test runs
+    std::mem::forget(container);
+
+    Ok(format!(
+        "postgres://postgres:{PASSWORD}@{host}:{port}/postgres"
+    ))
+}
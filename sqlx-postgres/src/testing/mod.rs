@@ -17,6 +17,9 @@ use crate::{PgConnectOptions, PgConnection, Postgres};
 
 pub(crate) use sqlx_core::testing::*;
 
+#[cfg(feature = "testcontainers")]
+mod container;
+
 // Using a blocking `OnceLock` here because the critical sections are short.
 static MASTER_POOL: OnceLock<Pool<Postgres>> = OnceLock::new();
 // Automatically delete any databases created before the start of the test binary.
@@ -44,6 +47,7 @@ impl TestSupport for Postgres {
         let mut conn = PgConnection::connect(&url).await?;
 
         let delete_db_names: Vec<String> = query_scalar("select db_name from _sqlx_test.databases")
+            .unlimited()
             .fetch_all(&mut conn)
             .await?;
 
@@ -90,7 +94,15 @@ impl TestSupport for Postgres {
 }
 
 async fn test_context(args: &TestArgs) -> Result<TestContext<Postgres>, Error> {
-    let url = dotenvy::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let url = match dotenvy::var("DATABASE_URL") {
+        Ok(url) => url,
+        #[cfg(feature = "testcontainers")]
+        Err(_) if args.container => container::database_url(args.container_image).await?,
+        Err(_) if args.container => {
+            panic!("DATABASE_URL must be set, or the `testcontainers` feature enabled")
+        }
+        Err(_) => panic!("DATABASE_URL must be set"),
+    };
 
     let master_opts = PgConnectOptions::from_str(&url).expect("failed to parse DATABASE_URL");
 
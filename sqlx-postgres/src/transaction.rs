@@ -34,6 +34,12 @@ impl TransactionManager for PgTransactionManager {
         rollback.conn.inner.transaction_depth += 1;
         rollback.defuse();
 
+        #[cfg(feature = "otel")]
+        if depth == 0 {
+            let span = sqlx_core::otel::transaction_span(&conn.otel_attributes());
+            conn.inner.transaction_span = Some(span);
+        }
+
         Ok(())
     }
 
@@ -43,6 +49,7 @@ impl TransactionManager for PgTransactionManager {
                 .await?;
 
             conn.inner.transaction_depth -= 1;
+            end_transaction_span(conn, "commit");
         }
 
         Ok(())
@@ -54,6 +61,7 @@ impl TransactionManager for PgTransactionManager {
                 .await?;
 
             conn.inner.transaction_depth -= 1;
+            end_transaction_span(conn, "rollback");
         }
 
         Ok(())
@@ -67,6 +75,7 @@ impl TransactionManager for PgTransactionManager {
             .expect("BUG: Rollback query somehow too large for protocol");
 
             conn.inner.transaction_depth -= 1;
+            end_transaction_span(conn, "rollback");
         }
     }
 
@@ -75,6 +84,19 @@ impl TransactionManager for PgTransactionManager {
     }
 }
 
+// close the span of the transaction once the outermost one has ended
+fn end_transaction_span(conn: &mut PgConnection, outcome: &'static str) {
+    #[cfg(feature = "otel")]
+    if conn.inner.transaction_depth == 0 {
+        if let Some(span) = conn.inner.transaction_span.take() {
+            sqlx_core::otel::record_transaction_outcome(&span, outcome);
+        }
+    }
+
+    #[cfg(not(feature = "otel"))]
+    let _ = (conn, outcome);
+}
+
 struct Rollback<'c> {
     conn: &'c mut PgConnection,
     defuse: bool,
@@ -30,6 +30,10 @@ impl_type_checking!(
 
         sqlx::postgres::types::PgLQuery,
 
+        sqlx::postgres::types::PgMacAddr8,
+
+        sqlx::postgres::types::PgLsn,
+
         sqlx::postgres::types::PgCube,
 
         sqlx::postgres::types::PgPoint,
@@ -64,6 +68,9 @@ impl_type_checking!(
         #[cfg(feature = "bit-vec")]
         sqlx::types::BitVec,
 
+        #[cfg(feature = "bitvec")]
+        sqlx::types::bitvec::BitVec<u8, sqlx::types::bitvec::Msb0>,
+
         sqlx::postgres::types::PgHstore,
         // Arrays
 
@@ -79,6 +86,8 @@ impl_type_checking!(
         Vec<sqlx::postgres::types::Oid> | &[sqlx::postgres::types::Oid],
         Vec<sqlx::postgres::types::PgMoney> | &[sqlx::postgres::types::PgMoney],
         Vec<sqlx::postgres::types::PgInterval> | &[sqlx::postgres::types::PgInterval],
+        Vec<sqlx::postgres::types::PgMacAddr8> | &[sqlx::postgres::types::PgMacAddr8],
+        Vec<sqlx::postgres::types::PgLsn> | &[sqlx::postgres::types::PgLsn],
 
         #[cfg(feature = "uuid")]
         Vec<sqlx::types::Uuid> | &[sqlx::types::Uuid],
@@ -189,6 +198,42 @@ impl_type_checking!(
             Vec<sqlx::postgres::types::PgRange<sqlx::types::time::OffsetDateTime>> |
                 &[sqlx::postgres::types::PgRange<sqlx::types::time::OffsetDateTime>],
         },
+        jiff: {
+            // Scalar types
+            sqlx::types::jiff::Time,
+
+            sqlx::types::jiff::Date,
+
+            sqlx::types::jiff::DateTime,
+
+            sqlx::types::jiff::Timestamp,
+
+            // Array types
+            Vec<sqlx::types::jiff::Time> | &[sqlx::types::jiff::Time],
+
+            Vec<sqlx::types::jiff::Date> | &[sqlx::types::jiff::Date],
+
+            Vec<sqlx::types::jiff::DateTime> | &[sqlx::types::jiff::DateTime],
+
+            Vec<sqlx::types::jiff::Timestamp> | &[sqlx::types::jiff::Timestamp],
+
+            // Range types
+            sqlx::postgres::types::PgRange<sqlx::types::jiff::Date>,
+
+            sqlx::postgres::types::PgRange<sqlx::types::jiff::DateTime>,
+
+            sqlx::postgres::types::PgRange<sqlx::types::jiff::Timestamp>,
+
+            // Arrays of ranges
+            Vec<sqlx::postgres::types::PgRange<sqlx::types::jiff::Date>> |
+                &[sqlx::postgres::types::PgRange<sqlx::types::jiff::Date>],
+
+            Vec<sqlx::postgres::types::PgRange<sqlx::types::jiff::DateTime>> |
+                &[sqlx::postgres::types::PgRange<sqlx::types::jiff::DateTime>],
+
+            Vec<sqlx::postgres::types::PgRange<sqlx::types::jiff::Timestamp>> |
+                &[sqlx::postgres::types::PgRange<sqlx::types::jiff::Timestamp>],
+        },
     },
     numeric-types: {
         bigdecimal: {
@@ -128,6 +128,8 @@ pub enum PgType {
     RecordArray,
     Uuid,
     UuidArray,
+    PgLsn,
+    PgLsnArray,
     Jsonb,
     JsonbArray,
     Int4Range,
@@ -431,6 +433,8 @@ impl PgType {
             2287 => PgType::RecordArray,
             2950 => PgType::Uuid,
             2951 => PgType::UuidArray,
+            3220 => PgType::PgLsn,
+            3221 => PgType::PgLsnArray,
             3802 => PgType::Jsonb,
             3807 => PgType::JsonbArray,
             3904 => PgType::Int4Range,
@@ -539,6 +543,8 @@ impl PgType {
             PgType::RecordArray => Oid(2287),
             PgType::Uuid => Oid(2950),
             PgType::UuidArray => Oid(2951),
+            PgType::PgLsn => Oid(3220),
+            PgType::PgLsnArray => Oid(3221),
             PgType::Jsonb => Oid(3802),
             PgType::JsonbArray => Oid(3807),
             PgType::Int4Range => Oid(3904),
@@ -643,6 +649,8 @@ impl PgType {
             PgType::RecordArray => "RECORD[]",
             PgType::Uuid => "UUID",
             PgType::UuidArray => "UUID[]",
+            PgType::PgLsn => "PG_LSN",
+            PgType::PgLsnArray => "PG_LSN[]",
             PgType::Jsonb => "JSONB",
             PgType::JsonbArray => "JSONB[]",
             PgType::Int4Range => "INT4RANGE",
@@ -744,6 +752,8 @@ impl PgType {
             PgType::RecordArray => "_record",
             PgType::Uuid => "uuid",
             PgType::UuidArray => "_uuid",
+            PgType::PgLsn => "pg_lsn",
+            PgType::PgLsnArray => "_pg_lsn",
             PgType::Jsonb => "jsonb",
             PgType::JsonbArray => "_jsonb",
             PgType::Int4Range => "int4range",
@@ -845,6 +855,8 @@ impl PgType {
             PgType::RecordArray => &PgTypeKind::Array(PgTypeInfo(PgType::Record)),
             PgType::Uuid => &PgTypeKind::Simple,
             PgType::UuidArray => &PgTypeKind::Array(PgTypeInfo(PgType::Uuid)),
+            PgType::PgLsn => &PgTypeKind::Simple,
+            PgType::PgLsnArray => &PgTypeKind::Array(PgTypeInfo(PgType::PgLsn)),
             PgType::Jsonb => &PgTypeKind::Simple,
             PgType::JsonbArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonb)),
             PgType::Int4Range => &PgTypeKind::Range(PgTypeInfo::INT4),
@@ -961,6 +973,8 @@ impl PgType {
             PgType::RecordArray => Some(Cow::Owned(PgTypeInfo(PgType::Record))),
             PgType::Uuid => None,
             PgType::UuidArray => Some(Cow::Owned(PgTypeInfo(PgType::Uuid))),
+            PgType::PgLsn => None,
+            PgType::PgLsnArray => Some(Cow::Owned(PgTypeInfo(PgType::PgLsn))),
             PgType::Jsonb => None,
             PgType::JsonbArray => Some(Cow::Owned(PgTypeInfo(PgType::Jsonb))),
             PgType::Int4Range => None,
@@ -1089,6 +1103,10 @@ impl PgTypeInfo {
     pub(crate) const UUID: Self = Self(PgType::Uuid);
     pub(crate) const UUID_ARRAY: Self = Self(PgType::UuidArray);
 
+    // pg_lsn
+    pub(crate) const PG_LSN: Self = Self(PgType::PgLsn);
+    pub(crate) const PG_LSN_ARRAY: Self = Self(PgType::PgLsnArray);
+
     // record
     pub(crate) const RECORD: Self = Self(PgType::Record);
     pub(crate) const RECORD_ARRAY: Self = Self(PgType::RecordArray);
@@ -1,5 +1,5 @@
 use sqlx_core::bytes::Buf;
-use sqlx_core::types::Text;
+use sqlx_core::types::{Text, TryText};
 use std::borrow::Cow;
 
 use crate::decode::Decode;
@@ -28,13 +28,12 @@ use crate::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 ///    .await?;
 /// ```
 ///
-/// However, this may cause an error if the type being wrapped does not implement `PgHasArrayType`,
-/// e.g. `Vec` itself, because we don't currently support multidimensional arrays:
+/// However, this may cause an error if the type being wrapped does not implement `PgHasArrayType`:
 ///
 /// ```rust,ignore
-/// #[derive(sqlx::Type)] // ERROR: `Vec<i64>` does not implement `PgHasArrayType`
+/// #[derive(sqlx::Type)] // ERROR: `ExternalType` does not implement `PgHasArrayType`
 /// #[sqlx(transparent)]
-/// struct UserIds(Vec<i64>);
+/// struct Wrapper(ExternalType);
 /// ```
 ///
 /// To remedy this, add `#[sqlx(no_pg_array)]`, which disables the generation
@@ -43,15 +42,34 @@ use crate::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 /// ```rust,ignore
 /// #[derive(sqlx::Type)]
 /// #[sqlx(transparent, no_pg_array)]
-/// struct UserIds(Vec<i64>);
+/// struct Wrapper(ExternalType);
 /// ```
 ///
+/// ### Multi-dimensional arrays
+/// `Vec<T>` implements this trait as well, so a multi-dimensional array can be encoded from and
+/// decoded into nested `Vec`s, e.g. `Vec<Vec<i32>>` for an `INT4[][]`.
+/// Postgres requires every sub-array to have the same length; encoding sub-arrays of different
+/// lengths is an error, and so is decoding an array with a different number of dimensions than
+/// the nesting of the `Vec`s.
+///
 /// See [the documentation of `Type`][Type] for more details.
 pub trait PgHasArrayType {
     fn array_type_info() -> PgTypeInfo;
     fn array_compatible(ty: &PgTypeInfo) -> bool {
         *ty == Self::array_type_info()
     }
+
+    /// The type of an array of `Vec<Self>`, which is the same as an array of `Self` except for
+    /// types such as `u8` where `Vec<Self>` is not encoded as an array.
+    #[doc(hidden)]
+    fn nested_array_type_info() -> PgTypeInfo {
+        Self::array_type_info()
+    }
+
+    #[doc(hidden)]
+    fn nested_array_compatible(ty: &PgTypeInfo) -> bool {
+        Self::array_compatible(ty)
+    }
 }
 
 impl<T> PgHasArrayType for &T
@@ -65,6 +83,14 @@ where
     fn array_compatible(ty: &PgTypeInfo) -> bool {
         T::array_compatible(ty)
     }
+
+    fn nested_array_type_info() -> PgTypeInfo {
+        T::nested_array_type_info()
+    }
+
+    fn nested_array_compatible(ty: &PgTypeInfo) -> bool {
+        T::nested_array_compatible(ty)
+    }
 }
 
 impl<T> PgHasArrayType for Option<T>
@@ -78,6 +104,28 @@ where
     fn array_compatible(ty: &PgTypeInfo) -> bool {
         T::array_compatible(ty)
     }
+
+    fn nested_array_type_info() -> PgTypeInfo {
+        T::nested_array_type_info()
+    }
+
+    fn nested_array_compatible(ty: &PgTypeInfo) -> bool {
+        T::nested_array_compatible(ty)
+    }
+}
+
+impl<T> PgHasArrayType for Vec<T>
+where
+    T: PgHasArrayType,
+{
+    // Postgres uses the same type for arrays of any number of dimensions
+    fn array_type_info() -> PgTypeInfo {
+        T::nested_array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::nested_array_compatible(ty)
+    }
 }
 
 impl<T> PgHasArrayType for Text<T> {
@@ -90,6 +138,16 @@ impl<T> PgHasArrayType for Text<T> {
     }
 }
 
+impl<T> PgHasArrayType for TryText<T> {
+    fn array_type_info() -> PgTypeInfo {
+        String::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        String::array_compatible(ty)
+    }
+}
+
 impl<T> Type<Postgres> for [T]
 where
     T: PgHasArrayType,
@@ -179,12 +237,31 @@ where
     }
 }
 
+/// Whether `T` is itself an array, i.e. `Vec<T>` decodes a multi-dimensional array.
+fn is_nested<T: Type<Postgres>>(array: &PgTypeInfo) -> bool {
+    // `compatible()` is checked for the element as well, for types which accept any value
+    T::compatible(array)
+        && array
+            .try_array_element()
+            .is_some_and(|element| !T::compatible(&element))
+}
+
+fn dimension_mismatch(ndim: usize, nested: bool) -> BoxDynError {
+    if nested {
+        format!("encountered an array of {ndim} dimension(s), but expected more; decode it into fewer nested `Vec`s").into()
+    } else {
+        format!("encountered an array of {ndim} dimensions; decode it into nested `Vec`s, e.g. `Vec<Vec<T>>` for two dimensions").into()
+    }
+}
+
 impl<'r, T> Decode<'r, Postgres> for Vec<T>
 where
     T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
 {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         let format = value.format();
+        let time_zone = value.time_zone;
+        let nested = is_nested::<T>(&value.type_info);
 
         match format {
             PgValueFormat::Binary => {
@@ -200,8 +277,11 @@ where
                     return Ok(Vec::new());
                 }
 
-                if ndim != 1 {
-                    return Err(format!("encountered an array of {ndim} dimensions; only one-dimensional arrays are supported").into());
+                let ndim = usize::try_from(ndim)
+                    .map_err(|_| format!("encountered an array of {ndim} dimensions"))?;
+
+                if (ndim > 1) != nested {
+                    return Err(dimension_mismatch(ndim, nested));
                 }
 
                 // appears to have been used in the past to communicate potential NULLS
@@ -220,23 +300,72 @@ where
                         ))
                     })?;
 
-                // length of the array axis
-                let len = buf.get_i32();
+                // the length of each dimension
+                let mut dims = Vec::with_capacity(ndim);
+
+                for dim in 1..=ndim {
+                    let len = buf.get_i32();
 
-                let len = usize::try_from(len)
-                    .map_err(|_| format!("overflow converting array len ({len}) to usize"))?;
+                    let len = usize::try_from(len)
+                        .map_err(|_| format!("overflow converting array len ({len}) to usize"))?;
 
-                // the lower bound, we only support arrays starting from "1"
-                let lower = buf.get_i32();
+                    // the lower bound, we only support arrays starting from "1"
+                    let lower = buf.get_i32();
+
+                    if lower != 1 {
+                        return Err(format!("encountered an array with a lower bound of {lower} in dimension {dim}; only arrays starting at one are supported").into());
+                    }
 
-                if lower != 1 {
-                    return Err(format!("encountered an array with a lower bound of {lower} in the first dimension; only arrays starting at one are supported").into());
+                    dims.push(len);
                 }
 
-                let mut elements = Vec::with_capacity(len);
+                let mut elements = Vec::with_capacity(dims[0]);
+
+                if nested {
+                    // each element of `Self` is a sub-array with the remaining dimensions,
+                    // made into a value of its own by prefixing it with the matching header
+                    let mut header = Vec::new();
+                    header.extend(i32::try_from(ndim - 1)?.to_be_bytes());
+                    header.extend(0_i32.to_be_bytes());
+                    header.extend(element_type_oid.0.to_be_bytes());
+
+                    for &len in &dims[1..] {
+                        header.extend(i32::try_from(len)?.to_be_bytes());
+                        header.extend(1_i32.to_be_bytes());
+                    }
+
+                    let sub_array_len: usize = dims[1..].iter().product();
+
+                    for _ in 0..dims[0] {
+                        let start = buf;
+
+                        for _ in 0..sub_array_len {
+                            PgValueRef::get(
+                                &mut buf,
+                                format,
+                                element_type_info.clone(),
+                                time_zone,
+                            )?;
+                        }
+
+                        let mut sub_array = header.clone();
+                        sub_array.extend_from_slice(&start[..start.len() - buf.len()]);
 
-                for _ in 0..len {
-                    let value_ref = PgValueRef::get(&mut buf, format, element_type_info.clone())?;
+                        elements.push(T::decode(PgValueRef {
+                            value: Some(&sub_array),
+                            row: None,
+                            type_info: value.type_info.clone(),
+                            format,
+                            time_zone,
+                        })?);
+                    }
+
+                    return Ok(elements);
+                }
+
+                for _ in 0..dims[0] {
+                    let value_ref =
+                        PgValueRef::get(&mut buf, format, element_type_info.clone(), time_zone)?;
 
                     elements.push(T::decode(value_ref)?);
                 }
@@ -246,7 +375,11 @@ where
 
             PgValueFormat::Text => {
                 // no type is provided from the database for the element
-                let element_type_info = T::type_info();
+                let element_type_info = if nested {
+                    value.type_info.clone()
+                } else {
+                    T::type_info()
+                };
 
                 let s = value.as_str()?;
 
@@ -270,6 +403,11 @@ where
                 let mut done = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
+                // whether the element was quoted, so `"NULL"` is not mistaken for `NULL`
+                let mut quoted = false;
+                // the nesting depth of sub-arrays within the element, which are kept verbatim
+                // to be parsed by the decoder of `T`
+                let mut depth = 0_usize;
                 let mut value = String::with_capacity(10);
                 let mut chars = s.chars();
                 let mut elements = Vec::with_capacity(4);
@@ -279,19 +417,38 @@ where
                         match chars.next() {
                             Some(ch) => match ch {
                                 _ if in_escape => {
+                                    if depth > 0 {
+                                        value.push('\\');
+                                    }
                                     value.push(ch);
                                     in_escape = false;
                                 }
 
                                 '"' => {
                                     in_quotes = !in_quotes;
+
+                                    if depth > 0 {
+                                        value.push(ch);
+                                    } else {
+                                        quoted = true;
+                                    }
                                 }
 
                                 '\\' => {
                                     in_escape = true;
                                 }
 
-                                _ if ch == delimiter && !in_quotes => {
+                                '{' if !in_quotes => {
+                                    depth += 1;
+                                    value.push(ch);
+                                }
+
+                                '}' if !in_quotes => {
+                                    depth = depth.saturating_sub(1);
+                                    value.push(ch);
+                                }
+
+                                _ if ch == delimiter && !in_quotes && depth == 0 => {
                                     break;
                                 }
 
@@ -307,7 +464,15 @@ where
                         }
                     }
 
-                    let value_opt = if value == "NULL" {
+                    let is_sub_array = !quoted && value.starts_with('{');
+
+                    if is_sub_array != nested {
+                        // count the dimensions of the array from its leading braces
+                        let ndim = 1 + value.chars().take_while(|&ch| ch == '{').count();
+                        return Err(dimension_mismatch(ndim, nested));
+                    }
+
+                    let value_opt = if !quoted && value.eq_ignore_ascii_case("NULL") {
                         None
                     } else {
                         Some(value.as_bytes())
@@ -318,9 +483,11 @@ where
                         row: None,
                         type_info: element_type_info.clone(),
                         format,
+                        time_zone,
                     })?);
 
                     value.clear();
+                    quoted = false;
                 }
 
                 Ok(elements)
@@ -328,3 +495,51 @@ where
         }
     }
 }
+
+#[test]
+fn nested_and_null_elements() {
+    let decode_text = |s: &'static str| {
+        Vec::<Option<String>>::decode(PgValueRef {
+            value: Some(s.as_bytes()),
+            row: None,
+            type_info: PgTypeInfo::TEXT_ARRAY,
+            format: PgValueFormat::Text,
+            time_zone: None,
+        })
+    };
+
+    assert_eq!(
+        decode_text(r#"{"NULL",NULL,"a,b"}"#).unwrap(),
+        [Some("NULL".to_owned()), None, Some("a,b".to_owned())]
+    );
+    assert!(decode_text("{{a},{b}}").is_err());
+
+    let matrix = vec![vec![1_i32, 2, 3], vec![4, 5, 6]];
+    let mut buf = PgArgumentBuffer::default();
+    let _ = Encode::<Postgres>::encode_by_ref(&matrix, &mut buf).unwrap();
+
+    let binary = PgValueRef {
+        value: Some(&buf),
+        row: None,
+        type_info: PgTypeInfo::INT4_ARRAY,
+        format: PgValueFormat::Binary,
+        time_zone: None,
+    };
+
+    assert_eq!(Vec::<Vec<i32>>::decode(binary.clone()).unwrap(), matrix);
+    assert!(Vec::<i32>::decode(binary).is_err());
+
+    let decoded = Vec::<Vec<i32>>::decode(PgValueRef {
+        value: Some(b"{{1,2,3},{4,5,6}}"),
+        row: None,
+        type_info: PgTypeInfo::INT4_ARRAY,
+        format: PgValueFormat::Text,
+        time_zone: None,
+    })
+    .unwrap();
+    assert_eq!(decoded, matrix);
+
+    let jagged = vec![vec![1_i32, 2], vec![3]];
+    let mut buf = PgArgumentBuffer::default();
+    assert!(Encode::<Postgres>::encode_by_ref(&jagged, &mut buf).is_err());
+}
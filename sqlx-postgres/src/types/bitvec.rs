@@ -0,0 +1,119 @@
+use crate::arguments::value_size_int4_checked;
+use crate::{
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    types::Type,
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use bitvec::order::{BitOrder, Msb0};
+use bitvec::store::BitStore;
+use bitvec::vec::BitVec;
+use sqlx_core::bytes::Buf;
+use std::{io, mem};
+
+impl<T: BitStore, O: BitOrder> Type<Postgres> for BitVec<T, O> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::VARBIT
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::BIT || *ty == PgTypeInfo::VARBIT
+    }
+}
+
+impl<T: BitStore, O: BitOrder> PgHasArrayType for BitVec<T, O> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::VARBIT_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::BIT_ARRAY || *ty == PgTypeInfo::VARBIT_ARRAY
+    }
+}
+
+impl<T: BitStore, O: BitOrder> Encode<'_, Postgres> for BitVec<T, O> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let len = value_size_int4_checked(self.len())?;
+
+        // Postgres sends the most significant bit of each byte first,
+        // and pads the last byte with zeroes.
+        let mut bits = BitVec::<u8, Msb0>::with_capacity(self.len());
+        bits.extend(self.iter().by_vals());
+        bits.set_uninitialized(false);
+
+        buf.extend(len.to_be_bytes());
+        buf.extend(bits.as_raw_slice());
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i32>() + self.len().div_ceil(8)
+    }
+}
+
+impl<T: BitStore, O: BitOrder> Decode<'_, Postgres> for BitVec<T, O> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let mut bytes = value.as_bytes()?;
+                let len = bytes.get_i32();
+
+                let len = usize::try_from(len).map_err(|_| format!("invalid VARBIT len: {len}"))?;
+
+                if bytes.remaining() != len.div_ceil(8) {
+                    Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "VARBIT length mismatch.",
+                    ))?;
+                }
+
+                let bits = &bitvec::slice::BitSlice::<u8, Msb0>::from_slice(bytes)[..len];
+
+                Ok(bits.iter().by_vals().collect())
+            }
+            PgValueFormat::Text => {
+                let s = value.as_str()?;
+                let mut bits = BitVec::with_capacity(s.len());
+
+                for c in s.chars() {
+                    match c {
+                        '0' => bits.push(false),
+                        '1' => bits.push(true),
+                        _ => {
+                            Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "VARBIT data contains other characters than 1 or 0.",
+                            ))?;
+                        }
+                    }
+                }
+
+                Ok(bits)
+            }
+        }
+    }
+}
+
+#[test]
+fn bitvec_binary_round_trip() {
+    use bitvec::bitvec;
+    use bitvec::order::Lsb0;
+
+    let bits = bitvec![u32, Lsb0; 1, 0, 1, 1, 0, 0, 0, 0, 1, 1];
+
+    let mut buf = PgArgumentBuffer::default();
+    let _ = bits.encode_by_ref(&mut buf).unwrap();
+    assert_eq!(&buf[..], [0, 0, 0, 10, 0b1011_0000, 0b1100_0000]);
+
+    let decoded = BitVec::<u32, Lsb0>::decode(PgValueRef {
+        value: Some(&buf),
+        row: None,
+        type_info: PgTypeInfo::VARBIT,
+        format: PgValueFormat::Binary,
+        time_zone: None,
+    })
+    .unwrap();
+    assert_eq!(decoded, bits);
+}
@@ -12,21 +12,24 @@ impl PgHasArrayType for u8 {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::BYTEA
     }
-}
 
-impl PgHasArrayType for &'_ [u8] {
-    fn array_type_info() -> PgTypeInfo {
+    // `Vec<u8>` is `BYTEA`, so `Vec<Vec<u8>>` is `BYTEA[]`
+    fn nested_array_type_info() -> PgTypeInfo {
         PgTypeInfo::BYTEA_ARRAY
     }
+
+    fn nested_array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::BYTEA_ARRAY
+    }
 }
 
-impl PgHasArrayType for Box<[u8]> {
+impl PgHasArrayType for &'_ [u8] {
     fn array_type_info() -> PgTypeInfo {
-        <[&[u8]] as Type<Postgres>>::type_info()
+        PgTypeInfo::BYTEA_ARRAY
     }
 }
 
-impl PgHasArrayType for Vec<u8> {
+impl PgHasArrayType for Box<[u8]> {
     fn array_type_info() -> PgTypeInfo {
         <[&[u8]] as Type<Postgres>>::type_info()
     }
@@ -56,6 +59,10 @@ impl<const N: usize> Encode<'_, Postgres> for [u8; N] {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
         <&[u8] as Encode<Postgres>>::encode(self.as_slice(), buf)
     }
+
+    fn size_hint(&self) -> usize {
+        N
+    }
 }
 
 impl<'r> Decode<'r, Postgres> for &'r [u8] {
@@ -92,15 +99,57 @@ impl<const N: usize> Decode<'_, Postgres> for [u8; N] {
         let mut bytes = [0u8; N];
         match value.format() {
             PgValueFormat::Binary => {
-                bytes = value.as_bytes()?.try_into()?;
+                let value = value.as_bytes()?;
+                bytes = value
+                    .try_into()
+                    .map_err(|_| length_mismatch::<N>(value.len()))?;
+            }
+            PgValueFormat::Text => {
+                let hex = text_hex_decode_input(value)?;
+                if hex.len() != N * 2 {
+                    return Err(length_mismatch::<N>(hex.len() / 2));
+                }
+                hex::decode_to_slice(hex, &mut bytes)?;
             }
-            PgValueFormat::Text => hex::decode_to_slice(text_hex_decode_input(value)?, &mut bytes)?,
         };
         Ok(bytes)
     }
 }
 
+fn length_mismatch<const N: usize>(len: usize) -> BoxDynError {
+    format!("expected BYTEA of length {N} to decode `[u8; {N}]`, got length {len}").into()
+}
+
 forward_encode_impl!(Arc<[u8]>, &[u8], Postgres);
 forward_encode_impl!(Rc<[u8]>, &[u8], Postgres);
 forward_encode_impl!(Box<[u8]>, &[u8], Postgres);
 forward_encode_impl!(Cow<'_, [u8]>, &[u8], Postgres);
+
+#[test]
+fn decode_fixed_size_checks_length() {
+    let decode = |value, format| {
+        <[u8; 4]>::decode(PgValueRef {
+            value: Some(value),
+            row: None,
+            type_info: PgTypeInfo::BYTEA,
+            format,
+            time_zone: None,
+        })
+    };
+
+    assert_eq!(
+        decode(&[1, 2, 3, 4], PgValueFormat::Binary).unwrap(),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(
+        decode(b"\\x01020304", PgValueFormat::Text).unwrap(),
+        [1, 2, 3, 4]
+    );
+
+    let err = decode(&[1, 2, 3], PgValueFormat::Binary).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected BYTEA of length 4 to decode `[u8; 4]`, got length 3"
+    );
+    assert!(decode(b"\\x0102030405", PgValueFormat::Text).is_err());
+}
@@ -0,0 +1,219 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::numeric::{PgNumeric, PgNumericSign};
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// An amount of money as a whole number of cents (or other hundredths of a currency unit),
+/// for `NUMERIC(_, 2)` and `MONEY` columns.
+///
+/// Conversions are exact: decoding a value with a non-zero third (or later) decimal place,
+/// or which does not fit into an `i64`, is an error rather than rounding, and no value ever goes
+/// through a float.
+///
+/// Values are encoded as `NUMERIC` with a scale of 2, which Postgres converts implicitly when
+/// assigning to a `MONEY` column.
+///
+/// ### Note: `MONEY`
+/// Decoding `MONEY` assumes the `frac_digits` of the database's `lc_monetary` locale is 2,
+/// which is true for most locales; see [`PgMoney`][crate::types::PgMoney].
+/// As with `PgMoney`, decoding `MONEY` in text format is not supported.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Default)]
+pub struct PgMoneyCents(pub i64);
+
+impl Type<Postgres> for PgMoneyCents {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUMERIC
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::NUMERIC || *ty == PgTypeInfo::MONEY
+    }
+}
+
+impl PgHasArrayType for PgMoneyCents {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::NUMERIC_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::NUMERIC_ARRAY || *ty == PgTypeInfo::MONEY_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgMoneyCents {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        numeric_from_cents(self.0).encode(buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        PgNumeric::size_hint(19)
+    }
+}
+
+impl Decode<'_, Postgres> for PgMoneyCents {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.type_info == PgTypeInfo::MONEY {
+            return match value.format() {
+                PgValueFormat::Binary => Ok(Self(<i64 as Decode<Postgres>>::decode(value)?)),
+                PgValueFormat::Text => Err("Reading a `MONEY` value in text format is not \
+                    supported. Please use a prepared query or cast the value to NUMERIC."
+                    .into()),
+            };
+        }
+
+        let cents = match value.format() {
+            PgValueFormat::Binary => cents_from_numeric(PgNumeric::decode(value.as_bytes()?)?)?,
+            PgValueFormat::Text => parse_cents(value.as_str()?)?,
+        };
+
+        Ok(Self(cents))
+    }
+}
+
+fn numeric_from_cents(cents: i64) -> PgNumeric {
+    if cents == 0 {
+        return PgNumeric::ZERO;
+    }
+
+    let mut units = cents.unsigned_abs() / 100;
+    // the first base-10000 digit after the decimal point
+    let fraction =
+        i16::try_from(cents.unsigned_abs() % 100 * 100).expect("BUG: digit out of range");
+
+    // base-10000 digits of the whole units, least significant first
+    let mut digits = Vec::new();
+
+    while units > 0 {
+        digits.push(i16::try_from(units % 10_000).expect("BUG: digit out of range"));
+        units /= 10_000;
+    }
+
+    let weight = i16::try_from(digits.len()).expect("BUG: too many digits") - 1;
+
+    digits.reverse();
+    digits.push(fraction);
+
+    // trailing zeroes are implied by `weight`
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+
+    PgNumeric::Number {
+        sign: if cents < 0 {
+            PgNumericSign::Negative
+        } else {
+            PgNumericSign::Positive
+        },
+        digits,
+        weight,
+        scale: 2,
+    }
+}
+
+fn cents_from_numeric(numeric: PgNumeric) -> Result<i64, BoxDynError> {
+    let PgNumeric::Number {
+        sign,
+        digits,
+        weight,
+        ..
+    } = numeric
+    else {
+        return Err("NaN is not an amount of money".into());
+    };
+
+    let mut cents = 0_i128;
+
+    for (i, &digit) in digits.iter().enumerate() {
+        let exponent = i32::from(weight) - i32::try_from(i)?;
+
+        match exponent {
+            0.. => {
+                let scale = 10_000_i128
+                    .checked_pow(exponent.unsigned_abs())
+                    .ok_or("NUMERIC value is out of range for `PgMoneyCents`")?;
+
+                cents += i128::from(digit) * scale * 100;
+            }
+            -1 if digit % 100 == 0 => cents += i128::from(digit / 100),
+            _ if digit == 0 => {}
+            _ => return Err("NUMERIC value has more than 2 decimal places".into()),
+        }
+
+        if cents > i128::from(i64::MAX) + 1 {
+            return Err("NUMERIC value is out of range for `PgMoneyCents`".into());
+        }
+    }
+
+    if sign == PgNumericSign::Negative {
+        cents = -cents;
+    }
+
+    Ok(i64::try_from(cents).map_err(|_| "NUMERIC value is out of range for `PgMoneyCents`")?)
+}
+
+fn parse_cents(s: &str) -> Result<i64, BoxDynError> {
+    let (units, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let (fraction, rest) = fraction.split_at(std::cmp::min(fraction.len(), 2));
+
+    if rest.bytes().any(|digit| digit != b'0') {
+        return Err("NUMERIC value has more than 2 decimal places".into());
+    }
+
+    let negative = units.starts_with('-');
+    let units: i64 = units.parse()?;
+    let fraction: i64 = format!("{fraction:0<2}").parse()?;
+
+    units
+        .checked_mul(100)
+        .and_then(|cents| {
+            if negative {
+                cents.checked_sub(fraction)
+            } else {
+                cents.checked_add(fraction)
+            }
+        })
+        .ok_or_else(|| format!("{s} is out of range for `PgMoneyCents`").into())
+}
+
+#[test]
+fn cents_round_trip() {
+    for cents in [
+        0,
+        1,
+        5,
+        10,
+        99,
+        100,
+        12_345,
+        1_000_000,
+        -1,
+        -12_345,
+        i64::MAX,
+        i64::MIN,
+    ] {
+        let mut buf = PgArgumentBuffer::default();
+        numeric_from_cents(cents).encode(&mut buf).unwrap();
+
+        let numeric = PgNumeric::decode(&buf).unwrap();
+        assert_eq!(cents_from_numeric(numeric).unwrap(), cents, "{cents}");
+    }
+
+    // 1.234
+    let numeric = PgNumeric::Number {
+        sign: PgNumericSign::Positive,
+        digits: vec![1, 2340],
+        weight: 0,
+        scale: 3,
+    };
+    assert!(cents_from_numeric(numeric).is_err());
+
+    assert_eq!(parse_cents("123.45").unwrap(), 12_345);
+    assert_eq!(parse_cents("-0.5").unwrap(), -50);
+    assert_eq!(parse_cents("7").unwrap(), 700);
+    assert_eq!(parse_cents("1.2300").unwrap(), 123);
+    assert!(parse_cents("1.234").is_err());
+}
@@ -91,6 +91,26 @@ impl<'r> Decode<'r, Postgres> for DateTime<Local> {
     }
 }
 
+/// Decodes in the `TimeZone` of the session the value was received from,
+/// see [`PgConnectOptions::time_zone()`][crate::PgConnectOptions::time_zone].
+///
+/// Values which were not received from a connection (e.g. [`PgValue`][crate::PgValue]s
+/// created by hand) are decoded in UTC.
+#[cfg(feature = "chrono-tz")]
+impl<'r> Decode<'r, Postgres> for DateTime<chrono_tz::Tz> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let time_zone: chrono_tz::Tz = match value.time_zone {
+            Some(name) => name.parse().map_err(|_| {
+                format!("session time zone {name:?} is not a known IANA time zone name")
+            })?,
+            None => chrono_tz::Tz::UTC,
+        };
+
+        let fixed = <DateTime<FixedOffset> as Decode<Postgres>>::decode(value)?;
+        Ok(fixed.with_timezone(&time_zone))
+    }
+}
+
 impl<'r> Decode<'r, Postgres> for DateTime<Utc> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         let fixed = <DateTime<FixedOffset> as Decode<Postgres>>::decode(value)?;
@@ -131,3 +151,31 @@ fn postgres_epoch_datetime() -> NaiveDateTime {
         .and_hms_opt(0, 0, 0)
         .expect("expected 2000-01-01T00:00:00 to be a valid NaiveDateTime")
 }
+
+#[cfg(feature = "chrono-tz")]
+#[test]
+fn decode_in_session_time_zone() {
+    let micros: i64 = 24 * 60 * 60 * 1_000_000;
+    let bytes = micros.to_be_bytes();
+    let value = |time_zone| PgValueRef {
+        value: Some(&bytes),
+        row: None,
+        type_info: PgTypeInfo::TIMESTAMPTZ,
+        format: PgValueFormat::Binary,
+        time_zone,
+    };
+
+    let berlin =
+        <DateTime<chrono_tz::Tz> as Decode<Postgres>>::decode(value(Some("Europe/Berlin")))
+            .unwrap();
+    assert_eq!(berlin.timezone(), chrono_tz::Europe::Berlin);
+    assert_eq!(berlin.to_rfc3339(), "2000-01-02T01:00:00+01:00");
+
+    let utc = <DateTime<chrono_tz::Tz> as Decode<Postgres>>::decode(value(None)).unwrap();
+    assert_eq!(utc, berlin);
+    assert_eq!(utc.timezone(), chrono_tz::Tz::UTC);
+
+    assert!(
+        <DateTime<chrono_tz::Tz> as Decode<Postgres>>::decode(value(Some("<+05>-05"))).is_err()
+    );
+}
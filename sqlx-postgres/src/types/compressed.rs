@@ -0,0 +1,41 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use sqlx_core::types::Compressed;
+
+impl<T> Type<Postgres> for Compressed<T> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA
+    }
+}
+
+impl<T> PgHasArrayType for Compressed<T> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA_ARRAY
+    }
+}
+
+impl<T> Encode<'_, Postgres> for Compressed<T>
+where
+    T: AsRef<[u8]>,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(&self.compress()?);
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for Compressed<T>
+where
+    T: From<Vec<u8>>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        // decoding as `Vec<u8>` takes care of the text format
+        let data = <Vec<u8> as Decode<Postgres>>::decode(value)?;
+
+        Compressed::decompress(&data)
+    }
+}
@@ -0,0 +1,410 @@
+use std::cmp::Ordering;
+use std::fmt::{self, Display, Formatter, Write};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::numeric::{PgNumeric, PgNumericSign};
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// An exact `NUMERIC` value, without depending on `bigdecimal` or `rust_decimal`.
+///
+/// `PgDecimal` stores the decimal digits and scale of the value exactly as Postgres does,
+/// so it round-trips any `NUMERIC` value (including `NaN`) without loss, and it can be
+/// parsed from and formatted to a string. It does not implement arithmetic; use `BigDecimal`
+/// (`bigdecimal` feature) or `Decimal` (`rust_decimal` feature) for that.
+///
+/// The scale (number of digits after the decimal point) is preserved, so `1.50` is displayed
+/// as `1.50`, but comparisons follow Postgres: `1.50 == 1.5`, and `NaN` is equal to itself
+/// and greater than every other value.
+///
+/// ```rust
+/// use sqlx::postgres::types::PgDecimal;
+///
+/// let price: PgDecimal = "19.90".parse().unwrap();
+/// assert_eq!(price.to_string(), "19.90");
+/// assert_eq!(price.scale(), 2);
+/// assert_eq!(price, "19.9".parse().unwrap());
+/// assert!(price < "100".parse().unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PgDecimal(Repr);
+
+#[derive(Debug, Clone)]
+enum Repr {
+    NaN,
+    Number {
+        negative: bool,
+        /// ASCII decimal digits of the value scaled by `10^scale`, with exactly one integer
+        /// digit if the integer part is zero and no other leading zeroes.
+        digits: Box<[u8]>,
+        scale: u16,
+    },
+}
+
+impl PgDecimal {
+    /// The `NaN` value.
+    pub const NAN: Self = PgDecimal(Repr::NaN);
+
+    /// Returns `true` if this value is `NaN`.
+    pub fn is_nan(&self) -> bool {
+        matches!(self.0, Repr::NaN)
+    }
+
+    /// The number of digits after the decimal point (`0` for `NaN`).
+    pub fn scale(&self) -> u16 {
+        match self.0 {
+            Repr::NaN => 0,
+            Repr::Number { scale, .. } => scale,
+        }
+    }
+
+    /// Returns the integer and fractional digits, with trailing zeroes of the fraction removed.
+    fn parts(digits: &[u8], scale: u16) -> (&[u8], &[u8]) {
+        let (integer, fraction) = digits.split_at(digits.len() - usize::from(scale));
+        let trailing_zeroes = fraction.iter().rev().take_while(|&&d| d == b'0').count();
+
+        (integer, &fraction[..fraction.len() - trailing_zeroes])
+    }
+
+    fn to_numeric(&self) -> Result<PgNumeric, BoxDynError> {
+        let Repr::Number {
+            negative,
+            ref digits,
+            scale,
+        } = self.0
+        else {
+            return Ok(PgNumeric::NotANumber);
+        };
+
+        let integer_len = digits.len() - usize::from(scale);
+
+        // align the decimal point to base-10000 digits
+        let left_pad = (4 - integer_len % 4) % 4;
+        let right_pad = (4 - usize::from(scale) % 4) % 4;
+
+        let padded: Vec<u8> = std::iter::repeat_n(b'0', left_pad)
+            .chain(digits.iter().copied())
+            .chain(std::iter::repeat_n(b'0', right_pad))
+            .collect();
+
+        let groups: Vec<i16> = padded
+            .chunks(4)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |group, &digit| group * 10 + i16::from(digit - b'0'))
+            })
+            .collect();
+
+        let leading_zeroes = groups.iter().take_while(|&&group| group == 0).count();
+        let trailing_zeroes = groups.iter().rev().take_while(|&&group| group == 0).count();
+
+        let scale = i16::try_from(scale).map_err(|_| "NUMERIC scale is out of range")?;
+
+        if leading_zeroes == groups.len() {
+            return Ok(PgNumeric::Number {
+                sign: PgNumericSign::Positive,
+                digits: vec![],
+                weight: 0,
+                scale,
+            });
+        }
+
+        let weight = i16::try_from((integer_len + left_pad) / 4)
+            .ok()
+            .and_then(|groups| groups.checked_sub(1))
+            .and_then(|weight| weight.checked_sub(i16::try_from(leading_zeroes).ok()?))
+            .ok_or("NUMERIC value is out of range")?;
+
+        Ok(PgNumeric::Number {
+            sign: if negative {
+                PgNumericSign::Negative
+            } else {
+                PgNumericSign::Positive
+            },
+            digits: groups[leading_zeroes..groups.len() - trailing_zeroes].to_vec(),
+            weight,
+            scale,
+        })
+    }
+
+    fn from_numeric(numeric: PgNumeric) -> Result<Self, BoxDynError> {
+        let PgNumeric::Number {
+            sign,
+            digits,
+            weight,
+            scale,
+        } = numeric
+        else {
+            return Ok(Self::NAN);
+        };
+
+        let scale = usize::try_from(scale).map_err(|_| "NUMERIC scale is negative")?;
+        let group = |i: i32| {
+            usize::try_from(i)
+                .ok()
+                .and_then(|i| digits.get(i))
+                .copied()
+                .unwrap_or(0)
+        };
+
+        let mut s = String::new();
+
+        if sign == PgNumericSign::Negative {
+            s.push('-');
+        }
+
+        if weight < 0 {
+            s.push('0');
+        }
+
+        for i in 0..=i32::from(weight) {
+            if i == 0 {
+                write!(s, "{}", group(i))?;
+            } else {
+                write!(s, "{:04}", group(i))?;
+            }
+        }
+
+        if scale > 0 {
+            let mut fraction = String::new();
+            let mut i = i32::from(weight) + 1;
+
+            while fraction.len() < scale {
+                write!(fraction, "{:04}", group(i))?;
+                i += 1;
+            }
+
+            s.push('.');
+            s.push_str(&fraction[..scale]);
+        }
+
+        s.parse()
+    }
+}
+
+impl FromStr for PgDecimal {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("nan") {
+            return Ok(Self::NAN);
+        }
+
+        let (negative, unsigned) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+
+        let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+        if integer.is_empty() && fraction.is_empty()
+            || !integer
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(format!("invalid NUMERIC value: {s:?}").into());
+        }
+
+        let scale = u16::try_from(fraction.len()).map_err(|_| "NUMERIC scale is out of range")?;
+
+        let integer = integer.trim_start_matches('0');
+        let integer = if integer.is_empty() { "0" } else { integer };
+
+        let digits: Box<[u8]> = integer.bytes().chain(fraction.bytes()).collect();
+        let is_zero = digits.iter().all(|&d| d == b'0');
+
+        Ok(PgDecimal(Repr::Number {
+            negative: negative && !is_zero,
+            digits,
+            scale,
+        }))
+    }
+}
+
+impl Display for PgDecimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Repr::Number {
+            negative,
+            ref digits,
+            scale,
+        } = self.0
+        else {
+            return f.write_str("NaN");
+        };
+
+        let (integer, fraction) = digits.split_at(digits.len() - usize::from(scale));
+        // digits are always ASCII
+        let integer = std::str::from_utf8(integer).map_err(|_| fmt::Error)?;
+        let fraction = std::str::from_utf8(fraction).map_err(|_| fmt::Error)?;
+
+        if negative {
+            f.write_char('-')?;
+        }
+
+        f.write_str(integer)?;
+
+        if !fraction.is_empty() {
+            f.write_char('.')?;
+            f.write_str(fraction)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Ord for PgDecimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Repr::NaN, Repr::NaN) => Ordering::Equal,
+            (Repr::NaN, _) => Ordering::Greater,
+            (_, Repr::NaN) => Ordering::Less,
+            (
+                Repr::Number {
+                    negative: a_negative,
+                    digits: a_digits,
+                    scale: a_scale,
+                },
+                Repr::Number {
+                    negative: b_negative,
+                    digits: b_digits,
+                    scale: b_scale,
+                },
+            ) => {
+                let (a_integer, a_fraction) = Self::parts(a_digits, *a_scale);
+                let (b_integer, b_fraction) = Self::parts(b_digits, *b_scale);
+
+                // integer parts have no leading zeroes, so the longer one is larger
+                let magnitude = a_integer
+                    .len()
+                    .cmp(&b_integer.len())
+                    .then_with(|| a_integer.cmp(b_integer))
+                    .then_with(|| a_fraction.cmp(b_fraction));
+
+                match (a_negative, b_negative) {
+                    (false, false) => magnitude,
+                    (true, true) => magnitude.reverse(),
+                    (false, true) => Ordering::Greater,
+                    (true, false) => Ordering::Less,
+                }
+            }
+        }
+    }
+}
+
+impl PartialOrd for PgDecimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PgDecimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PgDecimal {}
+
+impl Hash for PgDecimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &self.0 {
+            Repr::NaN => state.write_u8(0),
+            Repr::Number {
+                negative,
+                digits,
+                scale,
+            } => {
+                // must be consistent with `Eq`, which ignores trailing zeroes
+                let (integer, fraction) = Self::parts(digits, *scale);
+
+                state.write_u8(1);
+                negative.hash(state);
+                integer.hash(state);
+                fraction.hash(state);
+            }
+        }
+    }
+}
+
+impl Type<Postgres> for PgDecimal {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUMERIC
+    }
+}
+
+impl PgHasArrayType for PgDecimal {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::NUMERIC_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgDecimal {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        self.to_numeric()?.encode(buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        match &self.0 {
+            Repr::NaN => PgNumeric::size_hint(0),
+            Repr::Number { digits, .. } => PgNumeric::size_hint(digits.len() as u64),
+        }
+    }
+}
+
+impl Decode<'_, Postgres> for PgDecimal {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Self::from_numeric(PgNumeric::decode(value.as_bytes()?)?),
+            PgValueFormat::Text => value.as_str()?.parse(),
+        }
+    }
+}
+
+#[test]
+fn decimal_round_trip() {
+    for s in [
+        "0",
+        "0.00",
+        "1",
+        "-1",
+        "10000",
+        "12345.6789",
+        "0.0001",
+        "-0.000012",
+        "1.50",
+        "99999999999999999999.999999999999",
+        "NaN",
+    ] {
+        let value: PgDecimal = s.parse().unwrap();
+        assert_eq!(value.to_string(), s);
+
+        let mut buf = PgArgumentBuffer::default();
+        value.to_numeric().unwrap().encode(&mut buf).unwrap();
+
+        let decoded = PgDecimal::from_numeric(PgNumeric::decode(&buf).unwrap()).unwrap();
+        assert_eq!(decoded.to_string(), s);
+    }
+
+    let parse = |s: &str| s.parse::<PgDecimal>().unwrap();
+
+    assert_eq!(parse("-0").to_string(), "0");
+    assert_eq!(parse("007.5").to_string(), "7.5");
+    assert_eq!(parse(".5").to_string(), "0.5");
+    assert!("1.2.3".parse::<PgDecimal>().is_err());
+    assert!("-".parse::<PgDecimal>().is_err());
+
+    assert_eq!(parse("1.5"), parse("1.500"));
+    assert!(parse("-2") < parse("-1.5"));
+    assert!(parse("0.09") < parse("0.1"));
+    assert!(parse("9.99") < parse("10"));
+    assert!(parse("1000") < PgDecimal::NAN);
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+
+/// A [`Duration`] stored as a number of microseconds in a `BIGINT` (`INT8`) column.
+///
+/// `Duration` itself maps to `INTERVAL`; use this wrapper for columns which store durations
+/// as plain integers instead.
+///
+/// Encoding a duration with sub-microsecond precision (or too large for `BIGINT`)
+/// is an error rather than rounding, and so is decoding a negative value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgDurationMicros(pub Duration);
+
+impl Type<Postgres> for PgDurationMicros {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::INT8
+    }
+}
+
+impl PgHasArrayType for PgDurationMicros {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::INT8_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgDurationMicros {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        if self.0.subsec_nanos() % 1000 != 0 {
+            return Err("`PgDurationMicros` does not support nanoseconds precision".into());
+        }
+
+        let microseconds = i64::try_from(self.0.as_micros())
+            .map_err(|_| "Overflow has occurred for `PgDurationMicros`")?;
+
+        <i64 as Encode<Postgres>>::encode(microseconds, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+impl Decode<'_, Postgres> for PgDurationMicros {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let microseconds = <i64 as Decode<Postgres>>::decode(value)?;
+        let microseconds = u64::try_from(microseconds)
+            .map_err(|_| format!("negative duration of {microseconds} microseconds"))?;
+
+        Ok(Self(Duration::from_micros(microseconds)))
+    }
+}
+
+impl From<Duration> for PgDurationMicros {
+    fn from(value: Duration) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PgDurationMicros> for Duration {
+    fn from(value: PgDurationMicros) -> Self {
+        value.0
+    }
+}
+
+#[test]
+fn duration_micros_round_trip() {
+    use crate::PgValueFormat;
+
+    let mut buf = PgArgumentBuffer::default();
+    let duration = PgDurationMicros(Duration::from_micros(1_500_000));
+    assert!(matches!(duration.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert_eq!(&**buf, 1_500_000_i64.to_be_bytes());
+
+    let decoded = PgDurationMicros::decode(PgValueRef {
+        value: Some(&buf),
+        row: None,
+        type_info: PgTypeInfo::INT8,
+        format: PgValueFormat::Binary,
+        time_zone: None,
+    })
+    .unwrap();
+    assert_eq!(decoded, duration);
+
+    assert!(PgDurationMicros(Duration::from_nanos(1_500))
+        .encode_by_ref(&mut buf)
+        .is_err());
+}
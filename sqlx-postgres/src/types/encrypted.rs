@@ -0,0 +1,103 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use sqlx_core::types::{Cipher, Encrypted};
+
+// The plaintext is the binary encoding of the inner value, so it can be decoded
+// without knowing which type it was encoded from.
+
+impl<T, C> Type<Postgres> for Encrypted<T, C> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA
+    }
+}
+
+impl<T, C> PgHasArrayType for Encrypted<T, C> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA_ARRAY
+    }
+}
+
+impl<'q, T, C> Encode<'q, Postgres> for Encrypted<T, C>
+where
+    T: Encode<'q, Postgres>,
+    C: Cipher,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let mut plaintext = PgArgumentBuffer::default();
+
+        if let IsNull::Yes = T::encode_by_ref(self, &mut plaintext)? {
+            return Ok(IsNull::Yes);
+        }
+
+        if plaintext.needs_patching() {
+            return Err(
+                "cannot encrypt a value whose encoding depends on the type of the \
+                parameter (e.g. an array of a custom type)"
+                    .into(),
+            );
+        }
+
+        buf.extend_from_slice(&C::encrypt(&plaintext)?);
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        T::size_hint(self)
+    }
+}
+
+impl<'r, T, C> Decode<'r, Postgres> for Encrypted<T, C>
+where
+    T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
+    C: Cipher,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let time_zone = value.time_zone;
+        let ciphertext = <Vec<u8> as Decode<Postgres>>::decode(value)?;
+        let plaintext = C::decrypt(&ciphertext)?;
+
+        let value = T::decode(PgValueRef {
+            value: Some(&plaintext),
+            row: None,
+            type_info: T::type_info(),
+            format: PgValueFormat::Binary,
+            time_zone,
+        })?;
+
+        Ok(Encrypted::new(value))
+    }
+}
+
+#[test]
+fn encrypted_round_trip() {
+    struct Reverse;
+
+    impl Cipher for Reverse {
+        fn encrypt(plaintext: &[u8]) -> Result<Vec<u8>, BoxDynError> {
+            Ok(plaintext.iter().rev().copied().collect())
+        }
+
+        fn decrypt(ciphertext: &[u8]) -> Result<Vec<u8>, BoxDynError> {
+            Self::encrypt(ciphertext)
+        }
+    }
+
+    let mut buf = PgArgumentBuffer::default();
+    let value = Encrypted::<_, Reverse>::new(0x0102_0304_i32);
+    assert!(matches!(value.encode_by_ref(&mut buf), Ok(IsNull::No)));
+    assert_eq!(&buf[..], [4, 3, 2, 1]);
+
+    let decoded = Encrypted::<i32, Reverse>::decode(PgValueRef {
+        value: Some(&buf),
+        row: None,
+        type_info: PgTypeInfo::BYTEA,
+        format: PgValueFormat::Binary,
+        time_zone: None,
+    })
+    .unwrap();
+    assert_eq!(decoded, value);
+}
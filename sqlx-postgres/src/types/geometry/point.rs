@@ -23,7 +23,14 @@ use std::str::FromStr;
 ///
 /// [PG.S.8.8.1]: https://www.postgresql.org/docs/current/datatype-geometric.html#DATATYPE-GEOMETRIC-POINTS
 ///
-#[derive(Debug, Clone, PartialEq)]
+/// ### Geographic coordinates
+///
+/// `point` has no coordinate system of its own. To store a location, the usual convention
+/// (shared with PostGIS) is `x` = longitude and `y` = latitude, which [`PgPoint::from_lon_lat()`],
+/// [`PgPoint::lon()`] and [`PgPoint::lat()`] make explicit. Note that distances computed by
+/// Postgres for `point` (e.g. the `<->` operator) are planar, not great-circle distances.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct PgPoint {
     pub x: f64,
     pub y: f64,
@@ -83,7 +90,34 @@ impl FromStr for PgPoint {
     }
 }
 
+impl From<(f64, f64)> for PgPoint {
+    fn from((x, y): (f64, f64)) -> Self {
+        PgPoint { x, y }
+    }
+}
+
+impl From<PgPoint> for (f64, f64) {
+    fn from(point: PgPoint) -> Self {
+        (point.x, point.y)
+    }
+}
+
 impl PgPoint {
+    /// A geographic location, stored as `x` = longitude and `y` = latitude (in degrees).
+    pub fn from_lon_lat(lon: f64, lat: f64) -> Self {
+        PgPoint { x: lon, y: lat }
+    }
+
+    /// The longitude of a geographic location, i.e. `x`.
+    pub fn lon(&self) -> f64 {
+        self.x
+    }
+
+    /// The latitude of a geographic location, i.e. `y`.
+    pub fn lat(&self) -> f64 {
+        self.y
+    }
+
     fn from_bytes(mut bytes: &[u8]) -> Result<PgPoint, BoxDynError> {
         let x = bytes.get_f64();
         let y = bytes.get_f64();
@@ -133,6 +167,14 @@ mod point_tests {
         assert_eq!(point, PgPoint { x: 2.5, y: 3.4 });
     }
 
+    #[test]
+    fn can_convert_lon_lat() {
+        let point = PgPoint::from_lon_lat(-0.1276, 51.5072);
+        assert_eq!(point, PgPoint::from((-0.1276, 51.5072)));
+        assert_eq!((point.lon(), point.lat()), (-0.1276, 51.5072));
+        assert_eq!(<(f64, f64)>::from(point), (-0.1276, 51.5072));
+    }
+
     #[test]
     fn can_serialise_point_type() {
         let point = PgPoint { x: 2.1, y: 5.2 };
@@ -272,6 +272,7 @@ mod test {
         let empty = PgValueRef {
             value: Some(empty.as_slice()),
             row: None,
+            time_zone: None,
             type_info: PgTypeInfo::with_name("hstore"),
             format: PgValueFormat::Binary,
         };
@@ -279,6 +280,7 @@ mod test {
         let name_surname = PgValueRef {
             value: Some(name_surname_age.as_slice()),
             row: None,
+            time_zone: None,
             type_info: PgTypeInfo::with_name("hstore"),
             format: PgValueFormat::Binary,
         };
@@ -298,6 +300,7 @@ mod test {
         let buf = PgValueRef {
             value: Some(&[255, 255, 255, 251]),
             row: None,
+            time_zone: None,
             type_info: PgTypeInfo::with_name("hstore"),
             format: PgValueFormat::Binary,
         };
@@ -1,13 +1,15 @@
 use byteorder::{BigEndian, ByteOrder};
 use std::num::{NonZeroI16, NonZeroI32, NonZeroI64};
 
+use sqlx_core::types::Bounded;
+
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::types::Type;
 use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
-fn int_decode(value: PgValueRef<'_>) -> Result<i64, BoxDynError> {
+pub(crate) fn int_decode(value: PgValueRef<'_>) -> Result<i64, BoxDynError> {
     Ok(match value.format() {
         PgValueFormat::Text => value.as_str()?.parse()?,
         PgValueFormat::Binary => {
@@ -174,3 +176,49 @@ impl PgHasArrayType for NonZeroI64 {
         PgTypeInfo::INT8_ARRAY
     }
 }
+
+impl<T, const MIN: i64, const MAX: i64> PgHasArrayType for Bounded<T, MIN, MAX>
+where
+    T: PgHasArrayType,
+{
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+#[test]
+fn decode_checks_invariants() {
+    fn decode<T: for<'a> Decode<'a, Postgres>>(value: &[u8]) -> Result<T, BoxDynError> {
+        T::decode(PgValueRef {
+            value: Some(value),
+            row: None,
+            type_info: PgTypeInfo::INT4,
+            format: PgValueFormat::Binary,
+            time_zone: None,
+        })
+    }
+
+    assert_eq!(decode::<NonZeroI32>(&7_i32.to_be_bytes()).unwrap().get(), 7);
+    assert_eq!(
+        decode::<NonZeroI32>(&0_i32.to_be_bytes())
+            .unwrap_err()
+            .to_string(),
+        "unexpected zero value for NonZeroI32"
+    );
+
+    type Percentage = Bounded<i32, 0, 100>;
+    assert_eq!(
+        decode::<Percentage>(&100_i32.to_be_bytes()).unwrap().get(),
+        100
+    );
+    assert_eq!(
+        decode::<Percentage>(&101_i32.to_be_bytes())
+            .unwrap_err()
+            .to_string(),
+        "101 is out of range 0..=100"
+    );
+}
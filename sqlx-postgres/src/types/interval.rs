@@ -92,6 +92,12 @@ impl Encode<'_, Postgres> for std::time::Duration {
     }
 }
 
+impl Decode<'_, Postgres> for std::time::Duration {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        PgInterval::decode(value)?.try_into()
+    }
+}
+
 impl TryFrom<std::time::Duration> for PgInterval {
     type Error = BoxDynError;
 
@@ -112,6 +118,33 @@ impl TryFrom<std::time::Duration> for PgInterval {
     }
 }
 
+impl TryFrom<PgInterval> for std::time::Duration {
+    type Error = BoxDynError;
+
+    /// Convert a `PgInterval` to a `std::time::Duration`.
+    ///
+    /// A day is taken to be 24 hours, as Postgres does when comparing intervals.
+    /// This returns an error if the interval is negative or has a month component,
+    /// as the length of a month is not fixed.
+    fn try_from(value: PgInterval) -> Result<Self, BoxDynError> {
+        if value.months != 0 {
+            return Err(
+                "PostgreSQL `INTERVAL` with months cannot be converted to a `Duration`".into(),
+            );
+        }
+
+        let microseconds = i64::from(value.days)
+            .checked_mul(86_400_000_000)
+            .and_then(|days| days.checked_add(value.microseconds))
+            .ok_or("Overflow has occurred converting PostgreSQL `INTERVAL` to a `Duration`")?;
+
+        let microseconds = u64::try_from(microseconds)
+            .map_err(|_| "negative PostgreSQL `INTERVAL` cannot be converted to a `Duration`")?;
+
+        Ok(Self::from_micros(microseconds))
+    }
+}
+
 #[cfg(feature = "chrono")]
 impl Type<Postgres> for chrono::Duration {
     fn type_info() -> PgTypeInfo {
@@ -328,6 +361,33 @@ fn test_pginterval_std() {
 
     // Case when microsecond overflow occurs
     assert!(PgInterval::try_from(std::time::Duration::from_secs(20_000_000_000_000)).is_err());
+
+    // Converting back
+    let interval = PgInterval {
+        months: 0,
+        days: 1,
+        microseconds: 27_000,
+    };
+    assert_eq!(
+        std::time::Duration::try_from(interval).unwrap(),
+        std::time::Duration::from_micros(86_400_027_000)
+    );
+
+    // Case when the interval has months
+    assert!(std::time::Duration::try_from(PgInterval {
+        months: 1,
+        days: 0,
+        microseconds: 0,
+    })
+    .is_err());
+
+    // Case when the interval is negative
+    assert!(std::time::Duration::try_from(PgInterval {
+        months: 0,
+        days: 1,
+        microseconds: -86_400_000_001,
+    })
+    .is_err());
 }
 
 #[test]
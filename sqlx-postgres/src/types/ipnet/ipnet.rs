@@ -76,7 +76,6 @@ impl Decode<'_, Postgres> for IpNet {
             PgValueFormat::Binary => value.as_bytes()?,
             PgValueFormat::Text => {
                 let s = value.as_str()?;
-                println!("{s}");
                 if s.contains('/') {
                     return Ok(s.parse()?);
                 }
@@ -128,3 +127,73 @@ impl Decode<'_, Postgres> for IpNet {
         Err("invalid data received when expecting an INET".into())
     }
 }
+
+macro_rules! impl_ip_net_version {
+    ($ty:ident, $variant:ident, $version:literal) => {
+        impl Type<Postgres> for $ty {
+            fn type_info() -> PgTypeInfo {
+                IpNet::type_info()
+            }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                IpNet::compatible(ty)
+            }
+        }
+
+        impl PgHasArrayType for $ty {
+            fn array_type_info() -> PgTypeInfo {
+                IpNet::array_type_info()
+            }
+
+            fn array_compatible(ty: &PgTypeInfo) -> bool {
+                IpNet::array_compatible(ty)
+            }
+        }
+
+        impl Encode<'_, Postgres> for $ty {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                IpNet::from(*self).encode_by_ref(buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                IpNet::from(*self).size_hint()
+            }
+        }
+
+        impl Decode<'_, Postgres> for $ty {
+            fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+                match IpNet::decode(value)? {
+                    IpNet::$variant(net) => Ok(net),
+                    net => Err(
+                        format!(concat!("expected an ", $version, " network, got {}"), net).into(),
+                    ),
+                }
+            }
+        }
+    };
+}
+
+impl_ip_net_version!(Ipv4Net, V4, "IPv4");
+impl_ip_net_version!(Ipv6Net, V6, "IPv6");
+
+#[test]
+fn decode_ip_net_versions() {
+    let decode = |s: &'static str| PgValueRef {
+        value: Some(s.as_bytes()),
+        row: None,
+        type_info: PgTypeInfo::INET,
+        format: PgValueFormat::Text,
+        time_zone: None,
+    };
+
+    assert_eq!(
+        Ipv4Net::decode(decode("10.0.0.0/8")).unwrap(),
+        "10.0.0.0/8".parse::<Ipv4Net>().unwrap()
+    );
+    assert_eq!(
+        Ipv6Net::decode(decode("::1")).unwrap(),
+        "::1/128".parse::<Ipv6Net>().unwrap()
+    );
+    assert!(Ipv4Net::decode(decode("::1")).is_err());
+    assert!(Ipv6Net::decode(decode("10.0.0.1")).is_err());
+}
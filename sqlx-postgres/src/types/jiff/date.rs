@@ -0,0 +1,89 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::jiff::PG_EPOCH;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use jiff::civil::Date;
+use jiff::SignedDuration;
+use std::mem;
+
+impl Type<Postgres> for Date {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE
+    }
+}
+
+impl PgHasArrayType for Date {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for Date {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        // DATE is encoded as number of days since epoch (2000-01-01)
+        let days = self.duration_since(PG_EPOCH).as_hours() / 24;
+        let days: i32 = days.try_into().map_err(|_| {
+            format!("value {self:?} would overflow binary encoding for Postgres DATE")
+        })?;
+
+        Encode::<Postgres>::encode(days, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i32>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Date {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // DATE is encoded as the days since epoch
+                let days: i32 = Decode::<Postgres>::decode(value)?;
+                PG_EPOCH.checked_add(SignedDuration::from_hours(i64::from(days) * 24))?
+            }
+
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
+
+#[test]
+fn date_round_trip() {
+    use jiff::civil::date;
+
+    for (date, days) in [
+        (PG_EPOCH, 0_i32),
+        (date(1999, 12, 31), -1),
+        (date(1970, 1, 1), -10_957),
+        (date(2024, 2, 29), 8_825),
+        (date(-44, 3, 15), -746_482),
+    ] {
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(date, &mut buf).unwrap();
+        assert_eq!(&buf[..], days.to_be_bytes(), "{date}");
+
+        let value = PgValueRef {
+            value: Some(&buf),
+            row: None,
+            type_info: PgTypeInfo::DATE,
+            format: PgValueFormat::Binary,
+            time_zone: None,
+        };
+        assert_eq!(<Date as Decode<Postgres>>::decode(value).unwrap(), date);
+    }
+
+    let value = PgValueRef {
+        value: Some(b"1999-12-31"),
+        row: None,
+        type_info: PgTypeInfo::DATE,
+        format: PgValueFormat::Text,
+        time_zone: None,
+    };
+    assert_eq!(
+        <Date as Decode<Postgres>>::decode(value).unwrap(),
+        date(1999, 12, 31)
+    );
+}
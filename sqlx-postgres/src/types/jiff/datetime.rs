@@ -0,0 +1,214 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::jiff::{pg_epoch_timestamp, PG_EPOCH_DATETIME, PG_EPOCH_UNIX_MICROS};
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use jiff::civil::DateTime;
+use jiff::tz::TimeZone;
+use jiff::{SignedDuration, Timestamp, Zoned};
+use std::mem;
+
+impl Type<Postgres> for DateTime {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMP
+    }
+}
+
+impl Type<Postgres> for Timestamp {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMPTZ
+    }
+}
+
+impl Type<Postgres> for Zoned {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMPTZ
+    }
+}
+
+impl PgHasArrayType for DateTime {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMP_ARRAY
+    }
+}
+
+impl PgHasArrayType for Timestamp {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMPTZ_ARRAY
+    }
+}
+
+impl PgHasArrayType for Zoned {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMPTZ_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for DateTime {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        // TIMESTAMP is encoded as the microseconds since the epoch
+        let micros: i64 = self
+            .duration_since(PG_EPOCH_DATETIME)
+            .as_micros()
+            .try_into()
+            .map_err(|_| {
+                format!("value {self:?} would overflow binary encoding for Postgres TIMESTAMP")
+            })?;
+
+        Encode::<Postgres>::encode(micros, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for DateTime {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // TIMESTAMP is encoded as the microseconds since the epoch
+                let us: i64 = Decode::<Postgres>::decode(value)?;
+                PG_EPOCH_DATETIME.checked_add(SignedDuration::from_micros(us))?
+            }
+
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
+
+impl Encode<'_, Postgres> for Timestamp {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        // TIMESTAMPTZ is encoded as the microseconds since the epoch in UTC
+        let micros = self.as_microsecond() - PG_EPOCH_UNIX_MICROS;
+
+        Encode::<Postgres>::encode(micros, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Timestamp {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // TIMESTAMPTZ is encoded as the microseconds since the epoch in UTC
+                let us: i64 = Decode::<Postgres>::decode(value)?;
+                pg_epoch_timestamp().checked_add(SignedDuration::from_micros(us))?
+            }
+
+            PgValueFormat::Text => {
+                // Postgres sends the offset as `+HH`, `+HH:MM` or `+HH:MM:SS`,
+                // all of which `Timestamp` accepts.
+                value.as_str()?.parse()?
+            }
+        })
+    }
+}
+
+impl Encode<'_, Postgres> for Zoned {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        Encode::<Postgres>::encode(self.timestamp(), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Zoned {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        // TIMESTAMPTZ does not store the time zone, so this is always decoded in UTC
+        let timestamp = <Timestamp as Decode<Postgres>>::decode(value)?;
+        Ok(timestamp.to_zoned(TimeZone::UTC))
+    }
+}
+
+#[cfg(test)]
+fn value(buf: &[u8], type_info: PgTypeInfo, format: PgValueFormat) -> PgValueRef<'_> {
+    PgValueRef {
+        value: Some(buf),
+        row: None,
+        type_info,
+        format,
+        time_zone: None,
+    }
+}
+
+#[test]
+fn datetime_round_trip() {
+    use jiff::civil::date;
+
+    for (datetime, micros) in [
+        (PG_EPOCH_DATETIME, 0_i64),
+        (date(1999, 12, 31).at(23, 59, 59, 999_999_000), -1),
+        (date(1970, 1, 1).at(0, 0, 0, 0), -PG_EPOCH_UNIX_MICROS),
+        (
+            date(2024, 2, 29).at(12, 0, 0, 500_000_000),
+            762_523_200_500_000,
+        ),
+        (date(-44, 3, 15).at(12, 0, 0, 0), -64_496_001_600_000_000),
+    ] {
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(datetime, &mut buf).unwrap();
+        assert_eq!(&buf[..], micros.to_be_bytes(), "{datetime}");
+
+        let decoded = <DateTime as Decode<Postgres>>::decode(value(
+            &buf,
+            PgTypeInfo::TIMESTAMP,
+            PgValueFormat::Binary,
+        ))
+        .unwrap();
+        assert_eq!(decoded, datetime);
+    }
+
+    let decoded = <DateTime as Decode<Postgres>>::decode(value(
+        b"1999-12-31 23:59:59.5",
+        PgTypeInfo::TIMESTAMP,
+        PgValueFormat::Text,
+    ))
+    .unwrap();
+    assert_eq!(decoded, date(1999, 12, 31).at(23, 59, 59, 500_000_000));
+}
+
+#[test]
+fn timestamp_round_trip() {
+    for (timestamp, micros) in [
+        ("2000-01-01T00:00:00Z", 0_i64),
+        ("1999-12-31T23:59:59.999999Z", -1),
+        ("1969-07-20T20:17:40Z", -960_867_740_000_000),
+        ("2024-02-29T12:00:00.5Z", 762_523_200_500_000),
+        ("-000044-03-15T12:00:00Z", -64_496_001_600_000_000),
+    ] {
+        let timestamp: Timestamp = timestamp.parse().unwrap();
+
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(timestamp, &mut buf).unwrap();
+        assert_eq!(&buf[..], micros.to_be_bytes(), "{timestamp}");
+
+        let decoded = <Timestamp as Decode<Postgres>>::decode(value(
+            &buf,
+            PgTypeInfo::TIMESTAMPTZ,
+            PgValueFormat::Binary,
+        ))
+        .unwrap();
+        assert_eq!(decoded, timestamp);
+    }
+
+    for text in [
+        "2000-01-01 01:00:00+01",
+        "2000-01-01 01:30:00+01:30",
+        "1999-12-31 19:00:00-05:00:00",
+    ] {
+        let decoded = <Timestamp as Decode<Postgres>>::decode(value(
+            text.as_bytes(),
+            PgTypeInfo::TIMESTAMPTZ,
+            PgValueFormat::Text,
+        ))
+        .unwrap();
+        assert_eq!(decoded, pg_epoch_timestamp(), "{text}");
+    }
+}
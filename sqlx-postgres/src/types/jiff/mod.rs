@@ -0,0 +1,30 @@
+mod date;
+mod datetime;
+
+// Named after the `TIME` SQL type.
+mod time;
+
+use jiff::civil::{date, Date, DateTime};
+use jiff::Timestamp;
+
+const PG_EPOCH: Date = date(2000, 1, 1);
+const PG_EPOCH_DATETIME: DateTime = PG_EPOCH.at(0, 0, 0, 0);
+
+/// Microseconds between the Unix epoch and the Postgres epoch (2000-01-01T00:00:00Z).
+const PG_EPOCH_UNIX_MICROS: i64 = 946_684_800_000_000;
+
+fn pg_epoch_timestamp() -> Timestamp {
+    Timestamp::from_microsecond(PG_EPOCH_UNIX_MICROS)
+        .expect("expected 2000-01-01T00:00:00Z to be a valid Timestamp")
+}
+
+#[test]
+fn check_pg_epoch_timestamp() {
+    assert_eq!(
+        pg_epoch_timestamp(),
+        PG_EPOCH_DATETIME
+            .to_zoned(jiff::tz::TimeZone::UTC)
+            .unwrap()
+            .timestamp()
+    );
+}
@@ -0,0 +1,90 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use jiff::civil::Time;
+use jiff::SignedDuration;
+use std::mem;
+
+impl Type<Postgres> for Time {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIME
+    }
+}
+
+impl PgHasArrayType for Time {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIME_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for Time {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        // TIME is encoded as the microseconds since midnight
+        let micros: i64 = self
+            .duration_since(Time::midnight())
+            .as_micros()
+            .try_into()
+            .map_err(|_| {
+                format!("value {self:?} would overflow binary encoding for Postgres TIME")
+            })?;
+
+        Encode::<Postgres>::encode(micros, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<u64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Time {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // TIME is encoded as the microseconds since midnight
+                let us: i64 = Decode::<Postgres>::decode(value)?;
+                Time::midnight().checked_add(SignedDuration::from_micros(us))?
+            }
+
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
+
+#[test]
+fn time_round_trip() {
+    use jiff::civil::time;
+
+    for (time, micros) in [
+        (Time::midnight(), 0_i64),
+        (time(0, 0, 0, 1_000), 1),
+        (time(12, 34, 56, 789_012_000), 45_296_789_012),
+        (time(23, 59, 59, 999_999_000), 86_399_999_999),
+    ] {
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode(time, &mut buf).unwrap();
+        assert_eq!(&buf[..], micros.to_be_bytes(), "{time}");
+
+        let value = PgValueRef {
+            value: Some(&buf),
+            row: None,
+            type_info: PgTypeInfo::TIME,
+            format: PgValueFormat::Binary,
+            time_zone: None,
+        };
+        assert_eq!(<Time as Decode<Postgres>>::decode(value).unwrap(), time);
+    }
+
+    let value = PgValueRef {
+        value: Some(b"12:34:56.789012"),
+        row: None,
+        type_info: PgTypeInfo::TIME,
+        format: PgValueFormat::Text,
+        time_zone: None,
+    };
+    assert_eq!(
+        <Time as Decode<Postgres>>::decode(value).unwrap(),
+        time(12, 34, 56, 789_012_000)
+    );
+}
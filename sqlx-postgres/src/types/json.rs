@@ -97,3 +97,35 @@ where
         serde_json::from_slice(buf).map(Json).map_err(Into::into)
     }
 }
+
+#[test]
+fn decode_raw_value_borrows_from_buffer() {
+    use crate::PgValueRef;
+
+    let jsonb = b"\x01{\"a\": [1, 2]}";
+    let raw = <&JsonRawValue as Decode<Postgres>>::decode(PgValueRef {
+        value: Some(jsonb),
+        row: None,
+        time_zone: None,
+        type_info: PgTypeInfo::JSONB,
+        format: PgValueFormat::Binary,
+    })
+    .unwrap();
+
+    assert_eq!(raw.get(), r#"{"a": [1, 2]}"#);
+    assert_eq!(raw.get().as_ptr(), jsonb[1..].as_ptr());
+
+    let json = br#"{"b" :  true}"#;
+    let Json(raw) = <Json<&JsonRawValue> as Decode<Postgres>>::decode(PgValueRef {
+        value: Some(json),
+        row: None,
+        time_zone: None,
+        type_info: PgTypeInfo::JSON,
+        format: PgValueFormat::Text,
+    })
+    .unwrap();
+
+    // whitespace is preserved as-is
+    assert_eq!(raw.get(), r#"{"b" :  true}"#);
+    assert_eq!(raw.get().as_ptr(), json.as_ptr());
+}
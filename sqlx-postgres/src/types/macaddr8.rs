@@ -0,0 +1,138 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The PostgreSQL [`MACADDR8`] type, a MAC address in EUI-64 format.
+///
+/// Postgres also accepts 6-byte (EUI-48) addresses as input for `MACADDR8`, and converts them
+/// to EUI-64 by inserting `FF:FE` in the middle; see [`PgMacAddr8::from_eui48()`].
+///
+/// For `MACADDR`, enable the `mac_address` feature and use `mac_address::MacAddress`.
+///
+/// [`MACADDR8`]: https://www.postgresql.org/docs/current/datatype-net-types.html#DATATYPE-MACADDR8
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct PgMacAddr8(
+    /// The bytes of the address, in network order.
+    pub [u8; 8],
+);
+
+impl PgMacAddr8 {
+    /// Convert a 6-byte (EUI-48) MAC address to EUI-64 the same way Postgres does,
+    /// by inserting `FF:FE` in the middle.
+    pub fn from_eui48(bytes: [u8; 6]) -> Self {
+        let [a, b, c, d, e, f] = bytes;
+        Self([a, b, c, 0xff, 0xfe, d, e, f])
+    }
+}
+
+impl From<[u8; 8]> for PgMacAddr8 {
+    fn from(bytes: [u8; 8]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<PgMacAddr8> for [u8; 8] {
+    fn from(addr: PgMacAddr8) -> Self {
+        addr.0
+    }
+}
+
+/// Formats the address like Postgres, e.g. `08:00:2b:01:02:03:04:05`.
+impl Display for PgMacAddr8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Type<Postgres> for PgMacAddr8 {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::MACADDR8
+    }
+}
+
+impl PgHasArrayType for PgMacAddr8 {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::MACADDR8_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgMacAddr8 {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(&self.0);
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+impl Decode<'_, Postgres> for PgMacAddr8 {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let bytes = value.as_bytes()?;
+
+                Ok(Self(bytes.try_into().map_err(|_| {
+                    format!(
+                        "invalid data received when expecting a MACADDR8: expected 8 bytes, got {}",
+                        bytes.len()
+                    )
+                })?))
+            }
+            PgValueFormat::Text => parse_text(value.as_str()?),
+        }
+    }
+}
+
+/// Parse the text output of `MACADDR8`, i.e. eight colon-separated hexadecimal bytes.
+fn parse_text(s: &str) -> Result<PgMacAddr8, BoxDynError> {
+    let mut bytes = [0; 8];
+    let mut parts = s.split(':');
+
+    for byte in &mut bytes {
+        let part = parts
+            .next()
+            .ok_or_else(|| format!("invalid MACADDR8 {s:?}: expected 8 bytes"))?;
+
+        *byte = u8::from_str_radix(part, 16)
+            .map_err(|_| format!("invalid MACADDR8 {s:?}: {part:?} is not a hexadecimal byte"))?;
+    }
+
+    if parts.next().is_some() {
+        return Err(format!("invalid MACADDR8 {s:?}: expected 8 bytes").into());
+    }
+
+    Ok(PgMacAddr8(bytes))
+}
+
+#[test]
+fn macaddr8_text_round_trip() {
+    let addr = parse_text("08:00:2b:01:02:03:04:05").unwrap();
+    assert_eq!(
+        addr,
+        PgMacAddr8([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03, 0x04, 0x05])
+    );
+    assert_eq!(addr.to_string(), "08:00:2b:01:02:03:04:05");
+
+    assert_eq!(
+        PgMacAddr8::from_eui48([0x08, 0x00, 0x2b, 0x01, 0x02, 0x03]).to_string(),
+        "08:00:2b:ff:fe:01:02:03"
+    );
+
+    assert!(parse_text("08:00:2b:01:02:03").is_err());
+    assert!(parse_text("08:00:2b:01:02:03:04:05:06").is_err());
+    assert!(parse_text("08:00:2b:01:02:03:04:zz").is_err());
+}
@@ -9,16 +9,23 @@
 //! | `i16`                                 | SMALLINT, SMALLSERIAL, INT2                          |
 //! | `i32`                                 | INT, SERIAL, INT4                                    |
 //! | `i64`                                 | BIGINT, BIGSERIAL, INT8                              |
+//! | `NonZeroI16`, `NonZeroI32`, `NonZeroI64` | SMALLINT, INT, BIGINT (a zero value is an error)  |
+//! | [`Bounded<T, MIN, MAX>`](crate::types::Bounded) | Same as `T` (a value out of range is an error) |
 //! | `f32`                                 | REAL, FLOAT4                                         |
 //! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME, CITEXT                 |
-//! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | BYTEA                                                |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
+//! | `std::time::Duration`                 | INTERVAL                                             |
+//! | [`PgDurationMicros`]                  | BIGINT, INT8 (as microseconds)                       |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
+//! | [`PgMoneyCents`]                      | NUMERIC(_, 2), MONEY (as cents)                      |
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
+//! | [`PgMacAddr8`]                        | MACADDR8                                             |
+//! | [`PgLsn`]                             | PG_LSN                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
 //! | [`PgCube`]                            | CUBE                                                 |
 //! | [`PgPoint`]                           | POINT                                                |
@@ -29,11 +36,28 @@
 //! | [`PgPolygon`]                         | POLYGON                                              |
 //! | [`PgCircle`]                          | CIRCLE                                               |
 //! | [`PgHstore`]                          | HSTORE                                               |
+//! | [`PgScalar`]                          | Any (decode only)                                    |
+//! | [`Encrypted<T, C>`](crate::types::Encrypted) | BYTEA<sup>2</sup>                             |
+//! | [`PgInt8<T>`](PgInt8)                 | BIGINT, INT8<sup>3</sup>                             |
+//! | [`PgNumericInt<T>`](PgNumericInt)     | NUMERIC<sup>3</sup>                                  |
+//! | [`PgDecimal`]                         | NUMERIC (exact, without arithmetic)                  |
+//!
+//! `&str` and `&[u8]` are decoded without copying, by borrowing from the row.
+//! `&[u8]` can only be decoded from the binary format; it is an error to decode it from a query
+//! which returns text (e.g. [`raw_sql()`](sqlx_core::raw_sql::raw_sql)), because `BYTEA` is sent
+//! as hex there. `Cow<str>` and `Cow<[u8]>` are always decoded as owned values;
+//! see [`Decode`](sqlx_core::decode::Decode#zero-copy-decoding).
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
 //! does not consider to be compatible with `TEXT[]`.
 //!
+//! <sup>2</sup> The binary encoding of `T`, encrypted by the [`Cipher`](crate::types::Cipher) `C`.
+//!
+//! <sup>3</sup> Explicit mappings for `u32`, `u64`, `u128`, `usize` and `i128`, which Postgres
+//! has no equivalent for: `PgInt8` fails at bind time if the value is out of range,
+//! `PgNumericInt` stores every value losslessly.
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -60,11 +84,15 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `chrono::DateTime<Utc>`               | TIMESTAMPTZ                                          |
 //! | `chrono::DateTime<Local>`             | TIMESTAMPTZ                                          |
+//! | `chrono::DateTime<chrono_tz::Tz>`     | TIMESTAMPTZ (requires the `chrono-tz` feature)       |
 //! | `chrono::NaiveDateTime`               | TIMESTAMP                                            |
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
 //!
+//! `DateTime<chrono_tz::Tz>` is decoded in the time zone of the session,
+//! see [`PgConnectOptions::time_zone()`][crate::PgConnectOptions::time_zone].
+//!
 //! ### [`time`](https://crates.io/crates/time)
 //!
 //! Requires the `time` Cargo feature flag.
@@ -77,6 +105,20 @@
 //! | `time::Time`                          | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
 //!
+//! ### [`jiff`](https://crates.io/crates/jiff)
+//!
+//! Requires the `jiff` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `jiff::Timestamp`                     | TIMESTAMPTZ                                          |
+//! | `jiff::Zoned`                         | TIMESTAMPTZ                                          |
+//! | `jiff::civil::DateTime`               | TIMESTAMP                                            |
+//! | `jiff::civil::Date`                   | DATE                                                 |
+//! | `jiff::civil::Time`                   | TIME                                                 |
+//!
+//! `jiff::Zoned` values are always decoded in UTC, as `TIMESTAMPTZ` does not store a time zone.
+//!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
 //! Requires the `uuid` Cargo feature flag.
@@ -107,9 +149,13 @@
 //! | Rust type                             | Postgres type(s)                                     |
 //! |---------------------------------------|------------------------------------------------------|
 //! | `ipnet::IpNet`                        | INET, CIDR                                           |
+//! | `ipnet::Ipv4Net`, `ipnet::Ipv6Net`    | INET, CIDR                                           |
 //! | `std::net::IpAddr`                    | INET, CIDR                                           |
 //!
-//! The same `IpAddr` limitation for smaller network prefixes applies as with `ipnet`.
+//! The same `IpAddr` limitation for smaller network prefixes applies as with `ipnetwork`.
+//!
+//! Decoding `Ipv4Net` or `Ipv6Net` fails if the value is of the other IP version.
+
 //!
 //! ### [`mac_address`](https://crates.io/crates/mac_address)
 //!
@@ -127,6 +173,17 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `bit_vec::BitVec`                     | BIT, VARBIT                                          |
 //!
+//! ### [`bitvec`](https://crates.io/crates/bitvec)
+//!
+//! Requires the `bitvec` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `bitvec::vec::BitVec<T, O>`           | BIT, VARBIT                                          |
+//!
+//! Any store and bit order can be used; `BitVec<u8, Msb0>` matches how Postgres stores bits,
+//! and is the type used by the `query!()` macros (unless the `bit-vec` feature is also enabled).
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -136,12 +193,40 @@
 //! | [`Json<T>`]                           | JSON, JSONB                                          |
 //! | `serde_json::Value`                   | JSON, JSONB                                          |
 //! | `&serde_json::value::RawValue`        | JSON, JSONB                                          |
+//! | `Box<serde_json::value::RawValue>`    | JSON, JSONB                                          |
 //!
 //! `Value` and `RawValue` from `serde_json` can be used for unstructured JSON data with
 //! Postgres.
 //!
+//! Decoding `&RawValue` (or `Json<&RawValue>`) borrows the JSON text straight from the row
+//! without building a `Value`, which is the cheapest way to pass JSON through unchanged.
+//!
 //! [`Json<T>`](crate::types::Json) can be used for structured JSON data with Postgres.
 //!
+//! ### Compression
+//!
+//! Requires the `zstd` and/or `lz4` Cargo feature flags.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | [`Compressed<T>`](crate::types::Compressed) | BYTEA                                          |
+//!
+//! `T` is the uncompressed bytes, e.g. `Vec<u8>` (or `&[u8]` when encoding).
+//!
+//! ### [`secrecy`](https://crates.io/crates/secrecy)
+//!
+//! Requires the `secrecy` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `secrecy::SecretString`               | TEXT, VARCHAR, CHAR(N), NAME, CITEXT                 |
+//! | `secrecy::SecretSlice<u8>`            | BYTEA                                                |
+//!
+//! These can only be bound as query parameters (they are not decoded).
+//! Their value is sent to the database as usual, but the `Debug` output of the query arguments
+//! omits all encoded values once a secret has been bound, so passwords and tokens don't
+//! end up in logs.
+//!
 //! # [Composite types](https://www.postgresql.org/docs/current/rowtypes.html)
 //!
 //! User-defined composite types are supported through a derive for `Type`.
@@ -216,22 +301,29 @@ pub(crate) use sqlx_core::types::{Json, Type};
 mod array;
 mod bool;
 mod bytes;
+mod cents;
 mod citext;
+mod decimal;
+mod encrypted;
 mod float;
 mod hstore;
 mod int;
 mod interval;
 mod lquery;
+mod lsn;
 mod ltree;
+mod macaddr8;
 // Not behind a Cargo feature because we require JSON in the driver implementation.
 mod json;
 mod money;
 mod oid;
 mod range;
 mod record;
+mod scalar;
 mod str;
 mod text;
 mod tuple;
+mod unsigned;
 mod void;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
@@ -242,9 +334,10 @@ mod bigdecimal;
 
 mod cube;
 
+mod duration;
+
 mod geometry;
 
-#[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
 mod numeric;
 
 #[cfg(feature = "rust_decimal")]
@@ -256,6 +349,9 @@ mod chrono;
 #[cfg(feature = "time")]
 mod time;
 
+#[cfg(feature = "jiff")]
+mod jiff;
+
 #[cfg(feature = "uuid")]
 mod uuid;
 
@@ -271,9 +367,21 @@ mod mac_address;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 
+#[cfg(feature = "bitvec")]
+mod bitvec;
+
+#[cfg(feature = "secrecy")]
+mod secrecy;
+
+#[cfg(any(feature = "zstd", feature = "lz4"))]
+mod compressed;
+
 pub use array::PgHasArrayType;
+pub use cents::PgMoneyCents;
 pub use citext::PgCiText;
 pub use cube::PgCube;
+pub use decimal::PgDecimal;
+pub use duration::PgDurationMicros;
 pub use geometry::circle::PgCircle;
 pub use geometry::line::PgLine;
 pub use geometry::line_segment::PgLSeg;
@@ -287,12 +395,16 @@ pub use lquery::PgLQuery;
 pub use lquery::PgLQueryLevel;
 pub use lquery::PgLQueryVariant;
 pub use lquery::PgLQueryVariantFlag;
+pub use lsn::PgLsn;
 pub use ltree::PgLTree;
 pub use ltree::PgLTreeLabel;
 pub use ltree::PgLTreeParseError;
+pub use macaddr8::PgMacAddr8;
 pub use money::PgMoney;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use scalar::PgScalar;
+pub use unsigned::{PgInt8, PgNumericInt};
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
@@ -220,6 +220,39 @@ impl Type<Postgres> for PgRange<time::OffsetDateTime> {
     }
 }
 
+#[cfg(feature = "jiff")]
+impl Type<Postgres> for PgRange<jiff::civil::Date> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        range_compatible::<jiff::civil::Date>(ty)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl Type<Postgres> for PgRange<jiff::civil::DateTime> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        range_compatible::<jiff::civil::DateTime>(ty)
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl Type<Postgres> for PgRange<jiff::Timestamp> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_RANGE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        range_compatible::<jiff::Timestamp>(ty)
+    }
+}
+
 impl PgHasArrayType for PgRange<i32> {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT4_RANGE_ARRAY
@@ -288,6 +321,27 @@ impl PgHasArrayType for PgRange<time::OffsetDateTime> {
     }
 }
 
+#[cfg(feature = "jiff")]
+impl PgHasArrayType for PgRange<jiff::civil::Date> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl PgHasArrayType for PgRange<jiff::civil::DateTime> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TS_RANGE_ARRAY
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl PgHasArrayType for PgRange<jiff::Timestamp> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_RANGE_ARRAY
+    }
+}
+
 impl<'q, T> Encode<'q, Postgres> for PgRange<T>
 where
     T: Encode<'q, Postgres>,
@@ -349,8 +403,12 @@ where
                 }
 
                 if !flags.contains(RangeFlags::LB_INF) {
-                    let value =
-                        T::decode(PgValueRef::get(&mut buf, value.format, element_ty.clone())?)?;
+                    let value = T::decode(PgValueRef::get(
+                        &mut buf,
+                        value.format,
+                        element_ty.clone(),
+                        value.time_zone,
+                    )?)?;
 
                     start = if flags.contains(RangeFlags::LB_INC) {
                         Bound::Included(value)
@@ -360,8 +418,12 @@ where
                 }
 
                 if !flags.contains(RangeFlags::UB_INF) {
-                    let value =
-                        T::decode(PgValueRef::get(&mut buf, value.format, element_ty.clone())?)?;
+                    let value = T::decode(PgValueRef::get(
+                        &mut buf,
+                        value.format,
+                        element_ty.clone(),
+                        value.time_zone,
+                    )?)?;
 
                     end = if flags.contains(RangeFlags::UB_INC) {
                         Bound::Included(value)
@@ -451,6 +513,7 @@ where
                             format: PgValueFormat::Text,
                             value: Some(element.as_bytes()),
                             row: None,
+                            time_zone: value.time_zone,
                         })?);
 
                         if count == 1 {
@@ -63,6 +63,7 @@ pub struct PgRecordDecoder<'r> {
     typ: PgTypeInfo,
     fmt: PgValueFormat,
     ind: usize,
+    time_zone: Option<&'r str>,
 }
 
 impl<'r> PgRecordDecoder<'r> {
@@ -88,6 +89,7 @@ impl<'r> PgRecordDecoder<'r> {
             fmt,
             typ,
             ind: 0,
+            time_zone: value.time_zone,
         })
     }
 
@@ -117,7 +119,12 @@ impl<'r> PgRecordDecoder<'r> {
 
                 self.ind += 1;
 
-                T::decode(PgValueRef::get(&mut self.buf, self.fmt, element_type)?)
+                T::decode(PgValueRef::get(
+                    &mut self.buf,
+                    self.fmt,
+                    element_type,
+                    self.time_zone,
+                )?)
             }
 
             PgValueFormat::Text => {
@@ -178,6 +185,7 @@ impl<'r> PgRecordDecoder<'r> {
                     format: self.fmt,
                     value: buf,
                     row: None,
+                    time_zone: self.time_zone,
                 })
             }
         }
@@ -0,0 +1,119 @@
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::{PgDecimal, Type};
+use crate::value::ValueRef;
+use crate::{PgTypeInfo, PgValue, PgValueRef, Postgres};
+
+/// A value of any type, decoded according to the type of the column at runtime.
+///
+/// For tools which need to handle columns whose types are not known at compile time,
+/// e.g. to display the results of arbitrary queries. `PgScalar` is compatible with every type:
+/// common scalar types are decoded into the matching variant, `NULL` into [`PgScalar::Null`],
+/// and anything else is kept as the raw [`PgValue`], which can be inspected or decoded later.
+///
+/// To decode a column which may have one of a known set of types instead,
+/// use [`Either<L, R>`](crate::types::Type#either).
+///
+/// `PgScalar` can only be decoded, not bound as a query parameter.
+///
+/// ```rust,ignore
+/// use sqlx::postgres::types::PgScalar;
+/// use sqlx::Row;
+///
+/// let row = sqlx::query(&user_query).fetch_one(&mut conn).await?;
+///
+/// for i in 0..row.len() {
+///     match row.try_get::<PgScalar, _>(i)? {
+///         PgScalar::Null => println!("NULL"),
+///         PgScalar::Text(text) => println!("{text:?}"),
+///         PgScalar::BigInt(int) => println!("{int}"),
+///         other => println!("{other:?}"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PgScalar {
+    Null,
+    Bool(bool),
+    SmallInt(i16),
+    Integer(i32),
+    BigInt(i64),
+    Real(f32),
+    Double(f64),
+    Numeric(PgDecimal),
+    Text(String),
+    Bytes(Vec<u8>),
+    /// A value of any other type.
+    Other(PgValue),
+}
+
+impl Type<Postgres> for PgScalar {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::UNKNOWN
+    }
+
+    fn compatible(_ty: &PgTypeInfo) -> bool {
+        true
+    }
+}
+
+impl Decode<'_, Postgres> for PgScalar {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.is_null() {
+            return Ok(PgScalar::Null);
+        }
+
+        let ty = value.type_info.clone();
+
+        Ok(if ty == PgTypeInfo::BOOL {
+            PgScalar::Bool(Decode::<Postgres>::decode(value)?)
+        } else if ty == PgTypeInfo::INT2 {
+            PgScalar::SmallInt(Decode::<Postgres>::decode(value)?)
+        } else if ty == PgTypeInfo::INT4 {
+            PgScalar::Integer(Decode::<Postgres>::decode(value)?)
+        } else if ty == PgTypeInfo::INT8 {
+            PgScalar::BigInt(Decode::<Postgres>::decode(value)?)
+        } else if ty == PgTypeInfo::FLOAT4 {
+            PgScalar::Real(Decode::<Postgres>::decode(value)?)
+        } else if ty == PgTypeInfo::FLOAT8 {
+            PgScalar::Double(Decode::<Postgres>::decode(value)?)
+        } else if ty == PgTypeInfo::NUMERIC {
+            PgScalar::Numeric(Decode::<Postgres>::decode(value)?)
+        } else if <String as Type<Postgres>>::compatible(&ty) {
+            PgScalar::Text(Decode::<Postgres>::decode(value)?)
+        } else if <Vec<u8> as Type<Postgres>>::compatible(&ty) {
+            PgScalar::Bytes(Decode::<Postgres>::decode(value)?)
+        } else {
+            PgScalar::Other(ValueRef::to_owned(&value))
+        })
+    }
+}
+
+#[test]
+fn decode_by_runtime_type() {
+    let decode = |value, type_info| {
+        PgScalar::decode(PgValueRef {
+            value,
+            row: None,
+            type_info,
+            format: crate::PgValueFormat::Binary,
+            time_zone: None,
+        })
+        .unwrap()
+    };
+
+    assert!(matches!(decode(None, PgTypeInfo::INT4), PgScalar::Null));
+    assert!(matches!(
+        decode(Some(&[0, 0, 0, 42]), PgTypeInfo::INT4),
+        PgScalar::Integer(42)
+    ));
+    assert!(matches!(
+        decode(Some(b"sqlx"), PgTypeInfo::TEXT),
+        PgScalar::Text(text) if text == "sqlx"
+    ));
+    assert!(matches!(
+        decode(Some(&[0; 16]), PgTypeInfo::UUID),
+        PgScalar::Other(value) if value.type_info == PgTypeInfo::UUID
+    ));
+}
@@ -0,0 +1,73 @@
+use secrecy::{ExposeSecret, SecretSlice, SecretString};
+
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, Postgres};
+
+impl Type<Postgres> for SecretString {
+    fn type_info() -> PgTypeInfo {
+        <str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for SecretString {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for SecretString {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.redact();
+
+        <&str as Encode<Postgres>>::encode(self.expose_secret(), buf)
+    }
+}
+
+impl Type<Postgres> for SecretSlice<u8> {
+    fn type_info() -> PgTypeInfo {
+        <[u8] as Type<Postgres>>::type_info()
+    }
+}
+
+impl PgHasArrayType for SecretSlice<u8> {
+    fn array_type_info() -> PgTypeInfo {
+        <&[u8] as PgHasArrayType>::array_type_info()
+    }
+}
+
+impl Encode<'_, Postgres> for SecretSlice<u8> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.redact();
+
+        <&[u8] as Encode<Postgres>>::encode(self.expose_secret(), buf)
+    }
+}
+
+#[test]
+fn secrets_are_redacted_from_debug_output() {
+    use crate::PgArguments;
+    use sqlx_core::arguments::Arguments;
+
+    let mut args = PgArguments::default();
+    args.add("public").unwrap();
+    assert!(format!("{args:?}").contains("buffer: ["));
+
+    args.add(SecretString::from("hunter2")).unwrap();
+    args.add(SecretSlice::from(b"hunter2".to_vec())).unwrap();
+
+    let secret = format!("{:?}", b"hunter2");
+    let debug = format!("{args:?}");
+    assert!(debug.contains("<redacted>"));
+    assert!(!debug.contains(secret.trim_matches(['[', ']'])));
+    assert_eq!(args.len(), 3);
+}
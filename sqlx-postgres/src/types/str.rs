@@ -101,3 +101,27 @@ forward_encode_impl!(Rc<str>, &str, Postgres);
 forward_encode_impl!(Cow<'_, str>, &str, Postgres);
 forward_encode_impl!(Box<str>, &str, Postgres);
 forward_encode_impl!(String, &str, Postgres);
+
+#[test]
+fn decode_borrows_from_buffer() {
+    use crate::PgValueFormat;
+
+    let buf = b"hello".as_slice();
+    let value = |format| PgValueRef {
+        value: Some(buf),
+        row: None,
+        type_info: PgTypeInfo::TEXT,
+        format,
+        time_zone: None,
+    };
+
+    let s = <&str as Decode<Postgres>>::decode(value(PgValueFormat::Text)).unwrap();
+    assert_eq!(s.as_ptr(), buf.as_ptr());
+
+    let bytes = <&[u8] as Decode<Postgres>>::decode(value(PgValueFormat::Binary)).unwrap();
+    assert_eq!(bytes.as_ptr(), buf.as_ptr());
+    assert!(<&[u8] as Decode<Postgres>>::decode(value(PgValueFormat::Text)).is_err());
+
+    let cow = <Cow<'_, str> as Decode<Postgres>>::decode(value(PgValueFormat::Text)).unwrap();
+    assert!(matches!(cow, Cow::Owned(_)));
+}
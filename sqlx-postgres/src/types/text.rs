@@ -2,7 +2,7 @@ use crate::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
 use sqlx_core::decode::Decode;
 use sqlx_core::encode::{Encode, IsNull};
 use sqlx_core::error::BoxDynError;
-use sqlx_core::types::{Text, Type};
+use sqlx_core::types::{Text, TryDisplay, TryText, Type};
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -38,3 +38,100 @@ where
         Ok(Self(s.parse()?))
     }
 }
+
+impl<T> Type<Postgres> for TryText<T> {
+    fn type_info() -> PgTypeInfo {
+        <String as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<T> Encode<'_, Postgres> for TryText<T>
+where
+    T: TryDisplay,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        let mut text = String::new();
+        self.0.try_fmt(&mut text)?;
+        buf.extend_from_slice(text.as_bytes());
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for TryText<T>
+where
+    T: FromStr,
+    BoxDynError: From<<T as FromStr>::Err>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s: &str = Decode::<Postgres>::decode(value)?;
+        Ok(Self(s.parse()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PgValueFormat;
+
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    impl TryDisplay for Port {
+        fn try_fmt(&self, buf: &mut String) -> Result<(), BoxDynError> {
+            if self.0 == 0 {
+                return Err("port 0 is reserved".into());
+            }
+
+            buf.push_str(&self.0.to_string());
+            Ok(())
+        }
+    }
+
+    impl FromStr for Port {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(Port)
+        }
+    }
+
+    fn text(value: &[u8]) -> PgValueRef<'_> {
+        PgValueRef {
+            value: Some(value),
+            row: None,
+            type_info: PgTypeInfo::TEXT,
+            format: PgValueFormat::Text,
+            time_zone: None,
+        }
+    }
+
+    #[test]
+    fn try_text_round_trip() {
+        let mut buf = PgArgumentBuffer::default();
+        let is_null = TryText(Port(5432)).encode_by_ref(&mut buf).unwrap();
+
+        assert!(matches!(is_null, IsNull::No));
+        assert_eq!(&buf[..], b"5432");
+
+        let decoded = <TryText<Port> as Decode<Postgres>>::decode(text(&buf)).unwrap();
+        assert_eq!(decoded.into_inner(), Port(5432));
+    }
+
+    #[test]
+    fn try_text_returns_errors() {
+        let mut buf = PgArgumentBuffer::default();
+        let Err(err) = TryText(Port(0)).encode_by_ref(&mut buf) else {
+            panic!("port 0 was encoded");
+        };
+
+        assert_eq!(err.to_string(), "port 0 is reserved");
+        // nothing is written for a value which failed to encode
+        assert!(buf.is_empty());
+
+        assert!(<TryText<Port> as Decode<Postgres>>::decode(text(b"http")).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Explicit mappings for integer types that Postgres has no equivalent for.
+//!
+//! Postgres has no unsigned integers, so `u64` etc. do not implement `Type<Postgres>`;
+//! these wrappers pick a mapping instead of forcing a lossy `as i64` cast.
+
+use std::fmt::Display;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::int::int_decode;
+use crate::types::numeric::{PgNumeric, PgNumericSign};
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// Bind an integer as `INT8` (`BIGINT`), failing if it is out of range.
+///
+/// Implemented for `u32`, `u64`, `u128`, `usize` and `i128`.
+/// Encoding a value greater than `i64::MAX` is an error at bind time, and so is decoding a
+/// value which does not fit into `T` (e.g. a negative value into `PgInt8<u64>`).
+///
+/// Values are never truncated, but they must fit into the signed range of `INT8`;
+/// use [`PgNumericInt`] to store the full range of `u64`.
+///
+/// ```rust,ignore
+/// use sqlx::postgres::types::PgInt8;
+///
+/// sqlx::query("INSERT INTO files (id, size) VALUES ($1, $2)")
+///     .bind(id)
+///     .bind(PgInt8(metadata.len()))
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgInt8<T>(pub T);
+
+/// Bind an integer as `NUMERIC`, which can store the full range of `u64`, `u128` and `i128`.
+///
+/// Implemented for `u32`, `u64`, `u128`, `usize`, `i64` and `i128`.
+/// Decoding a value with a fractional part, `NaN`, or which does not fit into `T`
+/// is an error.
+///
+/// A `NUMERIC(20, 0)` column holds any `u64`, and `NUMERIC(39, 0)` any `u128` or `i128`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgNumericInt<T>(pub T);
+
+macro_rules! impl_pg_int8 {
+    ($($int:ty),*) => {$(
+        impl Type<Postgres> for PgInt8<$int> {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::INT8
+            }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                [PgTypeInfo::INT2, PgTypeInfo::INT4, PgTypeInfo::INT8].contains(ty)
+            }
+        }
+
+        impl PgHasArrayType for PgInt8<$int> {
+            fn array_type_info() -> PgTypeInfo {
+                PgTypeInfo::INT8_ARRAY
+            }
+        }
+
+        impl Encode<'_, Postgres> for PgInt8<$int> {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                let value = i64::try_from(self.0)
+                    .map_err(|_| format!("{} is out of range for INT8", self.0))?;
+
+                <i64 as Encode<Postgres>>::encode(value, buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                8
+            }
+        }
+
+        impl Decode<'_, Postgres> for PgInt8<$int> {
+            fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+                let value = int_decode(value)?;
+
+                <$int>::try_from(value)
+                    .map(PgInt8)
+                    .map_err(|_| out_of_range(value, stringify!($int)))
+            }
+        }
+    )*};
+}
+
+impl_pg_int8!(u32, u64, u128, usize, i128);
+
+macro_rules! impl_pg_numeric_int {
+    ($($int:ty),*) => {$(
+        impl Type<Postgres> for PgNumericInt<$int> {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::NUMERIC
+            }
+        }
+
+        impl PgHasArrayType for PgNumericInt<$int> {
+            fn array_type_info() -> PgTypeInfo {
+                PgTypeInfo::NUMERIC_ARRAY
+            }
+        }
+
+        impl Encode<'_, Postgres> for PgNumericInt<$int> {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+                numeric_from_int(self.0.into()).encode(buf)?;
+
+                Ok(IsNull::No)
+            }
+
+            fn size_hint(&self) -> usize {
+                PgNumeric::size_hint(39)
+            }
+        }
+
+        impl Decode<'_, Postgres> for PgNumericInt<$int> {
+            fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+                let value = match value.format() {
+                    PgValueFormat::Binary => int_from_numeric(PgNumeric::decode(value.as_bytes()?)?)?,
+                    PgValueFormat::Text => parse_numeric_int(value.as_str()?)?,
+                };
+
+                <$int>::try_from(value)
+                    .map(PgNumericInt)
+                    .map_err(|_| out_of_range(value, stringify!($int)))
+            }
+        }
+    )*};
+}
+
+impl_pg_numeric_int!(u32, u64, u128, usize, i64, i128);
+
+/// An integer decoded from `NUMERIC`, before it is converted to the target type.
+#[derive(Copy, Clone)]
+enum NumericInt {
+    Negative(i128),
+    Positive(u128),
+}
+
+impl Display for NumericInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumericInt::Negative(value) => value.fmt(f),
+            NumericInt::Positive(value) => value.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_numeric_int_conversions {
+    (unsigned: $($uint:ty),*; signed: $($sint:ty),*) => {
+        $(impl From<$uint> for NumericInt {
+            fn from(value: $uint) -> Self {
+                NumericInt::Positive(value as u128)
+            }
+        })*
+
+        $(impl From<$sint> for NumericInt {
+            fn from(value: $sint) -> Self {
+                if value < 0 {
+                    NumericInt::Negative(value.into())
+                } else {
+                    NumericInt::Positive(value.unsigned_abs() as u128)
+                }
+            }
+        })*
+
+        impl_numeric_int_conversions!($($uint,)* $($sint),*);
+    };
+    ($($int:ty),*) => {$(
+        impl TryFrom<NumericInt> for $int {
+            type Error = ();
+
+            fn try_from(value: NumericInt) -> Result<Self, ()> {
+                match value {
+                    NumericInt::Negative(value) => value.try_into().map_err(|_| ()),
+                    NumericInt::Positive(value) => value.try_into().map_err(|_| ()),
+                }
+            }
+        }
+    )*};
+}
+
+impl_numeric_int_conversions!(unsigned: u32, u64, u128, usize; signed: i64, i128);
+
+fn out_of_range(value: impl Display, ty: &str) -> BoxDynError {
+    format!("{value} is out of range for {ty}").into()
+}
+
+fn numeric_from_int(value: NumericInt) -> PgNumeric {
+    let (negative, mut magnitude) = match value {
+        NumericInt::Negative(value) => (true, value.unsigned_abs()),
+        NumericInt::Positive(value) => (false, value),
+    };
+
+    if magnitude == 0 {
+        return PgNumeric::ZERO;
+    }
+
+    // base-10000 digits, least significant first
+    let mut digits = Vec::new();
+
+    while magnitude > 0 {
+        digits.push(i16::try_from(magnitude % 10_000).expect("BUG: digit out of range"));
+        magnitude /= 10_000;
+    }
+
+    let weight = i16::try_from(digits.len() - 1).expect("BUG: too many digits");
+
+    // trailing zeroes are implied by `weight`
+    let trailing_zeroes = digits.iter().take_while(|&&digit| digit == 0).count();
+    digits.drain(..trailing_zeroes);
+    digits.reverse();
+
+    PgNumeric::Number {
+        sign: if negative {
+            PgNumericSign::Negative
+        } else {
+            PgNumericSign::Positive
+        },
+        digits,
+        weight,
+        scale: 0,
+    }
+}
+
+fn int_from_numeric(numeric: PgNumeric) -> Result<NumericInt, BoxDynError> {
+    let PgNumeric::Number {
+        sign,
+        digits,
+        weight,
+        ..
+    } = numeric
+    else {
+        return Err("NaN is not an integer".into());
+    };
+
+    let integer_digits = usize::try_from(i32::from(weight) + 1).unwrap_or(0);
+
+    if digits.iter().skip(integer_digits).any(|&digit| digit != 0) {
+        return Err("NUMERIC value has a fractional part".into());
+    }
+
+    let mut magnitude = 0_u128;
+
+    for i in 0..integer_digits {
+        let digit = digits.get(i).copied().unwrap_or(0);
+
+        magnitude = magnitude
+            .checked_mul(10_000)
+            .and_then(|magnitude| magnitude.checked_add(u128::try_from(digit).ok()?))
+            .ok_or("NUMERIC value is out of range for a 128-bit integer")?;
+    }
+
+    signed(sign == PgNumericSign::Negative, magnitude)
+}
+
+fn parse_numeric_int(s: &str) -> Result<NumericInt, BoxDynError> {
+    if s == "NaN" {
+        return Err("NaN is not an integer".into());
+    }
+
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    if fraction.bytes().any(|digit| digit != b'0') {
+        return Err("NUMERIC value has a fractional part".into());
+    }
+
+    let (negative, integer) = match integer.strip_prefix('-') {
+        Some(integer) => (true, integer),
+        None => (false, integer),
+    };
+
+    signed(negative, integer.parse()?)
+}
+
+fn signed(negative: bool, magnitude: u128) -> Result<NumericInt, BoxDynError> {
+    if !negative || magnitude == 0 {
+        return Ok(NumericInt::Positive(magnitude));
+    }
+
+    0_i128
+        .checked_sub_unsigned(magnitude)
+        .map(NumericInt::Negative)
+        .ok_or_else(|| format!("-{magnitude} is out of range for a 128-bit integer").into())
+}
+
+#[test]
+fn numeric_int_round_trip() {
+    for value in [
+        NumericInt::from(0_u64),
+        NumericInt::from(1_u64),
+        NumericInt::from(10_000_u64),
+        NumericInt::from(1_000_000_000_000_u64),
+        NumericInt::from(u64::MAX),
+        NumericInt::from(u128::MAX),
+        NumericInt::from(-12_345_i64),
+        NumericInt::from(i128::MIN),
+    ] {
+        let mut buf = PgArgumentBuffer::default();
+        numeric_from_int(value).encode(&mut buf).unwrap();
+
+        let decoded = int_from_numeric(PgNumeric::decode(&buf).unwrap()).unwrap();
+        assert_eq!(decoded.to_string(), value.to_string());
+    }
+
+    // 1.5
+    let fractional = PgNumeric::Number {
+        sign: PgNumericSign::Positive,
+        digits: vec![1, 5000],
+        weight: 0,
+        scale: 1,
+    };
+    assert!(int_from_numeric(fractional).is_err());
+
+    assert_eq!(parse_numeric_int("-42.000").unwrap().to_string(), "-42");
+    assert!(parse_numeric_int("42.5").is_err());
+    assert!(u64::try_from(parse_numeric_int("-1").unwrap()).is_err());
+}
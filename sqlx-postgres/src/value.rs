@@ -4,6 +4,7 @@ use sqlx_core::bytes::{Buf, Bytes};
 pub(crate) use sqlx_core::value::{Value, ValueRef};
 use std::borrow::Cow;
 use std::str::from_utf8;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 #[repr(u8)]
@@ -19,14 +20,17 @@ pub struct PgValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    // the `TimeZone` of the session the value was received from, if known
+    pub(crate) time_zone: Option<&'r str>,
 }
 
 /// Implementation of [`Value`] for PostgreSQL.
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct PgValue {
     pub(crate) value: Option<Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    pub(crate) time_zone: Option<Arc<str>>,
 }
 
 impl<'r> PgValueRef<'r> {
@@ -34,6 +38,7 @@ impl<'r> PgValueRef<'r> {
         buf: &mut &'r [u8],
         format: PgValueFormat,
         ty: PgTypeInfo,
+        time_zone: Option<&'r str>,
     ) -> Result<Self, String> {
         let element_len = buf.get_i32();
 
@@ -54,6 +59,7 @@ impl<'r> PgValueRef<'r> {
             row: None,
             type_info: ty,
             format,
+            time_zone,
         })
     }
 
@@ -83,6 +89,7 @@ impl Value for PgValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            time_zone: self.time_zone.as_deref(),
         }
     }
 
@@ -111,6 +118,7 @@ impl<'r> ValueRef<'r> for PgValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            time_zone: self.time_zone.map(Arc::from),
         }
     }
 
@@ -6,8 +6,9 @@ use std::sync::Once;
 pub use sqlx_core::any::driver::install_drivers;
 
 pub use sqlx_core::any::{
-    Any, AnyArguments, AnyConnectOptions, AnyExecutor, AnyPoolOptions, AnyQueryResult, AnyRow,
-    AnyStatement, AnyTransactionManager, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueRef,
+    install_types, Any, AnyArguments, AnyConnectOptions, AnyCustom, AnyCustomType, AnyExecutor,
+    AnyPoolOptions, AnyQueryResult, AnyRow, AnyStatement, AnyTransactionManager, AnyTypeInfo,
+    AnyTypeInfoKind, AnyValue, AnyValueRef,
 };
 
 #[allow(deprecated)]
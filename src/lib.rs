@@ -6,11 +6,12 @@ pub use sqlx_core::arguments::{Arguments, IntoArguments};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
 pub use sqlx_core::column::ColumnOrigin;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{ConnectOptions, ConnectRetryPolicy, Connection};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
 pub use sqlx_core::executor::{Execute, Executor};
 pub use sqlx_core::from_row::FromRow;
+pub use sqlx_core::interceptor;
 pub use sqlx_core::pool::{self, Pool};
 #[doc(hidden)]
 pub use sqlx_core::query::query_with_result as __query_with_result;
@@ -20,22 +21,52 @@ pub use sqlx_core::query_builder::{self, QueryBuilder};
 #[doc(hidden)]
 pub use sqlx_core::query_scalar::query_scalar_with_result as __query_scalar_with_result;
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
-pub use sqlx_core::raw_sql::{raw_sql, RawSql};
+pub use sqlx_core::query_store::{self, QueryStore};
+pub use sqlx_core::raw_sql::{raw_sql, RawSql, RawStatementError, RawStatementOutput};
 pub use sqlx_core::row::Row;
+pub use sqlx_core::sql_comment::SqlCommenter;
 pub use sqlx_core::sql_str::{AssertSqlSafe, SqlSafeStr, SqlStr};
 pub use sqlx_core::statement::Statement;
-pub use sqlx_core::transaction::Transaction;
+pub use sqlx_core::statistics::{StatementStatistics, StatementStats};
+pub use sqlx_core::transaction::{IsolationLevel, RetryPolicy, Transaction, TransactionOptions};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
 pub use sqlx_core::Either;
 
+pub use sqlx_core::audit;
+pub use sqlx_core::blocking;
 #[doc(inline)]
 pub use sqlx_core::error::{self, Error, Result};
 
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "metrics")]
+#[cfg_attr(docsrs, doc(cfg(feature = "metrics")))]
+pub use sqlx_core::metrics;
+
+#[cfg(feature = "prometheus")]
+#[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+pub use sqlx_core::prometheus;
+
+#[cfg(feature = "mock")]
+#[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
+pub use sqlx_core::mock;
+
+#[cfg(feature = "otel")]
+#[cfg_attr(docsrs, doc(cfg(feature = "otel")))]
+pub use sqlx_core::otel;
+
+/// The rustls crate used by the `tls-rustls-*` features, e.g. to build a `ClientConfig` for
+/// [`PgConnectOptions::ssl_client_config()`][postgres::PgConnectOptions::ssl_client_config].
+#[cfg(any(
+    feature = "tls-rustls-aws-lc-rs",
+    feature = "tls-rustls-ring-webpki",
+    feature = "tls-rustls-ring-native-roots"
+))]
+pub use sqlx_core::net::tls::rustls;
+
 #[cfg(feature = "postgres")]
 #[cfg_attr(docsrs, doc(cfg(feature = "postgres")))]
 #[doc(inline)]
@@ -56,6 +87,10 @@ pub extern crate sqlx_macros;
 #[doc(hidden)]
 pub use sqlx_macros::{FromRow, Type};
 
+#[doc = include_str!("macros/id.md")]
+#[cfg(feature = "derive")]
+pub use sqlx_macros::Id;
+
 // We can't do our normal facade approach with an attribute, but thankfully we can now
 // have docs out-of-line quite easily.
 #[doc = include_str!("macros/test.md")]
@@ -87,6 +122,28 @@ pub mod spec_error;
 #[doc(hidden)]
 pub use sqlx_core::rt as __rt;
 
+/// Selecting the async runtime SQLx runs on.
+///
+/// See [`Runtime`][rt::Runtime].
+pub mod rt {
+    pub use sqlx_core::io::ReadBuf;
+    pub use sqlx_core::net::Socket;
+    pub use sqlx_core::rt::{set_runtime, Runtime, RuntimeAlreadySet};
+
+    #[cfg(any(
+        feature = "_rt-async-global-executor",
+        feature = "_rt-async-std",
+        feature = "_rt-smol"
+    ))]
+    pub use sqlx_core::rt::AsyncIo;
+
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    pub use sqlx_core::rt::IoUring;
+
+    #[cfg(feature = "_rt-tokio")]
+    pub use sqlx_core::rt::Tokio;
+}
+
 /// Conversions between Rust and SQL types.
 ///
 /// To see how each SQL type maps to a Rust type, see the corresponding `types` module for each
@@ -162,10 +219,14 @@ pub mod _unstable {
 
 #[doc(hidden)]
 #[cfg_attr(
-    all(feature = "chrono", feature = "time"),
-    deprecated = "SQLx has both `chrono` and `time` features enabled, \
+    any(
+        all(feature = "chrono", feature = "time"),
+        all(feature = "chrono", feature = "jiff"),
+        all(feature = "time", feature = "jiff"),
+    ),
+    deprecated = "SQLx has more than one of the `chrono`, `time` and `jiff` features enabled, \
         which presents an ambiguity when the `query!()` macros are mapping date/time types. \
-        The `query!()` macros prefer types from `time` by default, \
+        The `query!()` macros prefer types from `time`, then `chrono`, then `jiff` by default, \
         but this behavior should not be relied upon; \
         to resolve the ambiguity, we recommend specifying the preferred crate in a `sqlx.toml` file: \
         https://docs.rs/sqlx/latest/sqlx/config/macros/PreferredCrates.html#field.date_time"